@@ -6,7 +6,7 @@ use agglayer_types::{
         CertificateAggchainDataCtx, MultisigCtx,
     },
     aggchain_proof::AggchainData,
-    Address, Certificate, Digest, L1WitnessCtx, PessimisticRootInput,
+    Address, Certificate, Digest, L1WitnessCtx, NetworkId, PessimisticRootInput, TokenInfo,
 };
 use eyre::Context as _;
 use prover_executor::sp1_fast;
@@ -87,13 +87,43 @@ where
             }
         };
 
+        let gas_token = self.fetch_gas_token(rollup_address).await?;
+
         Ok(L1WitnessCtx {
             prev_pessimistic_root: PessimisticRootInput::Fetched(prev_pessimistic_root.into()),
             l1_info_root,
             aggchain_data_ctx,
+            gas_token,
         })
     }
 
+    /// Fetch the gas token of the network, `None` if it is L1 ETH.
+    pub async fn fetch_gas_token(
+        &self,
+        rollup_address: Address,
+    ) -> Result<Option<TokenInfo>, CertificationError> {
+        let (origin_network, origin_token_address) = self
+            .l1_rpc
+            .get_gas_token(rollup_address)
+            .await
+            .map_err(CertificationError::GasTokenFetchFailed)?;
+
+        if origin_token_address == Address::ZERO {
+            return Ok(None);
+        }
+
+        debug!(
+            origin_network,
+            %origin_token_address,
+            "Gas token from L1"
+        );
+
+        Ok(Some(TokenInfo {
+            origin_network: NetworkId::new(origin_network),
+            origin_token_address,
+        }))
+    }
+
     pub async fn fetch_aggchain_proof_ctx(
         &self,
         rollup_address: Address,
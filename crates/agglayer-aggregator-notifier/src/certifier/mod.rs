@@ -1,20 +1,24 @@
 use std::{panic::AssertUnwindSafe, sync::Arc};
 
 use agglayer_certificate_orchestrator::{CertificationError, Certifier, CertifierOutput};
-use agglayer_config::Config;
+use agglayer_config::{certificate_orchestrator::prover::ProverConfig, Config};
 use agglayer_contracts::{aggchain::AggchainContract, RollupContract};
-use agglayer_prover_types::v1::{
-    generate_proof_request::Stdin, pessimistic_proof_service_client::PessimisticProofServiceClient,
-    ErrorKind, GenerateProofRequest, GenerateProofResponse,
+use agglayer_prover_types::mock;
+use agglayer_storage::{
+    columns::{
+        certification_failure_per_certificate::CertificationFailure,
+        certified_roots_per_certificate::CertifiedRoots,
+    },
+    stores::{DebugWriter, PendingCertificateReader, PendingCertificateWriter},
 };
-use agglayer_storage::stores::{PendingCertificateReader, PendingCertificateWriter};
 use agglayer_types::{
-    aggchain_proof::AggchainData, bincode, Certificate, Digest, Height, LocalNetworkStateData,
-    NetworkId, Proof,
+    aggchain_proof::AggchainData, bincode, Certificate, CertificateId, Digest, Height,
+    LocalNetworkStateData, NetworkId, Proof,
 };
 use eyre::{eyre, Context as _};
 use pessimistic_proof::{
     core::{commitment::StateCommitment, generate_pessimistic_proof, AggchainHashValues},
+    keccak::keccak256,
     local_state::LocalNetworkState,
     multi_batch_header::MultiBatchHeader,
     unified_bridge::{
@@ -25,30 +29,59 @@ use pessimistic_proof::{
 };
 use prover_executor::{sp1_blocking, sp1_fast};
 use sp1_sdk::{
-    CpuProver, Prover, SP1ProofWithPublicValues, SP1Stdin, SP1VerificationError, SP1VerifyingKey,
+    CpuProver, ExecutionReport, HashableKey as _, Prover, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1PublicValues, SP1Stdin, SP1VerificationError, SP1VerifyingKey,
 };
-use tonic::{codec::CompressionEncoding, transport::Channel};
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::ELF;
+use self::{network_quota::NetworkQuota, verification_pool::VerificationPool};
+pub use self::{remote_prover::RemoteProver, replay::CertificationReplay};
 
 mod l1_context;
+mod native_execution;
+mod network_quota;
+mod proving_cost;
+mod remote_prover;
+mod replay;
+mod stdin_capture;
+mod verification_pool;
 
 #[cfg(test)]
 mod tests;
 
+/// Backend producing the proofs of the executed PP program.
+#[derive(Clone)]
+enum ProvingBackend {
+    /// The prover service generating the proofs.
+    Remote(RemoteProver),
+    /// Mock proofs built out of the executed public values with the given
+    /// proving key, without any prover service.
+    ExecuteOnly(Arc<SP1ProvingKey>),
+}
+
 #[derive(Clone)]
 pub struct CertifierClient<PendingStore, L1Rpc> {
     /// The pending store to fetch and store certificates and proofs.
     pending_store: Arc<PendingStore>,
-    /// The prover service client.
-    prover: PessimisticProofServiceClient<Channel>,
-    /// The local CPU verifier to verify the generated proofs.
+    /// The backend generating the proofs.
+    backend: ProvingBackend,
+    /// The ELF of the pessimistic proof program, embedded or loaded from the
+    /// configured path.
+    program: &'static [u8],
+    /// The local CPU prover executing the PP program.
     verifier: Arc<CpuProver>,
     /// The verifying key of the SP1 proof system.
     verifying_key: SP1VerifyingKey,
+    /// Whether the proofs are checked by the mock verifier.
+    mock_verifier: bool,
+    /// The pool of workers verifying the proofs.
+    verification_pool: Arc<VerificationPool>,
     /// The L1 RPC client.
     l1_rpc: Arc<L1Rpc>,
+    /// The debug store recording the inputs of the failed certifications.
+    debug_store: Option<Arc<dyn DebugWriter>>,
+    /// The quota of the proofs requested from the SP1 network, if any.
+    network_quota: Option<Arc<NetworkQuota>>,
     config: Arc<Config>,
 }
 
@@ -59,107 +92,183 @@ impl<PendingStore, L1Rpc> CertifierClient<PendingStore, L1Rpc> {
         l1_rpc: Arc<L1Rpc>,
         config: Arc<Config>,
     ) -> eyre::Result<Self> {
-        debug!("Initializing the CertifierClient verifier...");
-        let (verifier, verifying_key) = sp1_blocking({
-            let mock_verifier = config.mock_verifier;
-            move || {
-                let verifier = if mock_verifier {
-                    sp1_sdk::ProverClient::builder().mock().build()
-                } else {
-                    sp1_sdk::ProverClient::builder().cpu().build()
-                };
-                let (_, verifying_key) = verifier.setup(ELF);
-                (verifier, verifying_key)
-            }
-        })
-        .await
-        .context("Failed setting up SP1 verifier")?;
-        debug!("CertifierClient verifier successfully initialized!");
-
-        debug!("Connecting to the prover service...");
+        let execute_only = matches!(
+            config.certificate_orchestrator.prover,
+            ProverConfig::SP1Execute { .. }
+        );
+        if execute_only && !config.mock_verifier {
+            return Err(eyre!(
+                "The sp1-execute prover produces mock proofs and requires the mock verifier"
+            ));
+        }
 
-        let prover = PessimisticProofServiceClient::connect(prover)
-            .await?
-            .max_decoding_message_size(config.prover.grpc.max_decoding_message_size)
-            .max_encoding_message_size(config.prover.grpc.max_encoding_message_size)
-            .send_compressed(CompressionEncoding::Zstd)
-            .accept_compressed(CompressionEncoding::Zstd);
-        debug!("Successfully connected to the prover service!");
+        let program = Self::program(&config)?;
+        let (verifier, proving_key, verifying_key) =
+            Self::setup_verifier(program, config.mock_verifier).await?;
+        let verifier = Arc::new(verifier);
+        let verification_pool = Arc::new(VerificationPool::new(
+            verifier.clone(),
+            verifying_key.clone(),
+            config.mock_verifier,
+            config.certificate_orchestrator.verification_workers,
+        ));
+
+        // The execute-only backend never requests any proof, so it does not
+        // need the prover service to be reachable.
+        let backend = if execute_only {
+            ProvingBackend::ExecuteOnly(Arc::new(proving_key))
+        } else {
+            ProvingBackend::Remote(
+                RemoteProver::connect(
+                    prover,
+                    &config.prover.grpc,
+                    config.certificate_orchestrator.prover.proving_timeout(),
+                )
+                .await?,
+            )
+        };
 
         Ok(Self {
             pending_store,
-            prover,
-            verifier: Arc::new(verifier),
+            backend,
+            program,
+            verifier,
             verifying_key,
+            mock_verifier: config.mock_verifier,
+            verification_pool,
             l1_rpc,
+            debug_store: None,
+            network_quota: config
+                .certificate_orchestrator
+                .sp1_network_quota
+                .clone()
+                .map(|quota| Arc::new(NetworkQuota::new(quota))),
             config,
         })
     }
 
-    fn verify_proof(
-        verifier: Arc<CpuProver>,
-        verifying_key: &SP1VerifyingKey,
+    /// The ELF of the pessimistic proof program, loaded from the configured
+    /// path if any, or embedded in the binary, and checked against the
+    /// configured hash if any.
+    fn program(config: &Config) -> eyre::Result<&'static [u8]> {
+        let program = pessimistic_proof::elf::resolve(
+            config
+                .prover
+                .elf
+                .as_ref()
+                .map(|elf| (elf.path.as_path(), elf.hash)),
+        )
+        .context("Failed to load the pessimistic proof program")?;
+
+        let program_hash = pessimistic_proof::elf::check(program, config.prover.expected_elf_hash)
+            .context("Integrity check of the pessimistic proof program failed")?;
+        info!(%program_hash, "Loaded the pessimistic proof program");
+
+        Ok(program)
+    }
+
+    /// Set up the local verifier along with the keys of the program.
+    async fn setup_verifier(
+        program: &'static [u8],
+        mock_verifier: bool,
+    ) -> eyre::Result<(CpuProver, SP1ProvingKey, SP1VerifyingKey)> {
+        debug!("Initializing the CertifierClient verifier...");
+        let setup = sp1_blocking(move || {
+            let verifier = if mock_verifier {
+                sp1_sdk::ProverClient::builder().mock().build()
+            } else {
+                sp1_sdk::ProverClient::builder().cpu().build()
+            };
+            let (proving_key, verifying_key) = verifier.setup(program);
+            (verifier, proving_key, verifying_key)
+        })
+        .await
+        .context("Failed setting up SP1 verifier")?;
+        debug!("CertifierClient verifier successfully initialized!");
+
+        Ok(setup)
+    }
+
+    /// Record the inputs of the failed certifications in the debug store.
+    pub fn with_debug_store(mut self, debug_store: Arc<dyn DebugWriter>) -> Self {
+        self.debug_store = Some(debug_store);
+        self
+    }
+
+    /// The vkey of the embedded pessimistic proof program, as registered in
+    /// the `AggLayerGateway` contract.
+    pub fn pessimistic_vkey(&self) -> [u8; 32] {
+        self.verifying_key.bytes32_raw()
+    }
+
+    /// Verify the proof on the verification pool.
+    async fn verify_proof(
+        &self,
         proof: &SP1ProofWithPublicValues,
-    ) -> eyre::Result<()> {
-        // This fail_point is use to make the verification pass or fail
-        fail::fail_point!(
-            "notifier::certifier::certify::before_verifying_proof",
-            |_| {
-                let verifier = sp1_sdk::ProverClient::builder().mock().build();
-                let (_, verifying_key) = verifier.setup(ELF);
-
-                Ok(verifier.verify(proof, &verifying_key)?)
-            }
-        );
+    ) -> Result<(), CertificationError> {
+        self.verification_pool
+            .verify(proof.clone())
+            .await
+            .map_err(|error| match error.downcast::<SP1VerificationError>() {
+                Ok(error) => CertificationError::ProofVerificationFailed {
+                    source: error.into(),
+                },
+                Err(error) => CertificationError::Other(error),
+            })
+    }
 
-        Ok(sp1_fast(|| verifier.verify(proof, verifying_key))
-            .context("Failed verifying sp1 proof")??)
+    /// Digest of the proof, marking it as verified.
+    fn proof_digest(proof: &Proof) -> Result<Digest, CertificationError> {
+        Ok(keccak256(
+            &sp1_fast(|| bincode::default().serialize(proof))
+                .map_err(CertificationError::Other)?
+                .map_err(|source| CertificationError::Serialize { source })?,
+        ))
     }
 }
 
-#[async_trait::async_trait]
-impl<PendingStore, L1Rpc> Certifier for CertifierClient<PendingStore, L1Rpc>
+impl<PendingStore, L1Rpc> CertifierClient<PendingStore, L1Rpc>
 where
-    PendingStore: PendingCertificateReader + PendingCertificateWriter + 'static,
-    L1Rpc: RollupContract + AggchainContract + Send + Sync + 'static,
+    PendingStore: PendingCertificateReader,
 {
-    #[instrument(skip(self, state, height), fields(certificate_id, %network_id), level = "info")]
-    async fn certify(
+    /// Get the proof cached for the certificate, if it was generated from the
+    /// same initial roots and commits to the expected public values.
+    fn cached_proof(
         &self,
-        state: LocalNetworkStateData,
-        network_id: NetworkId,
-        height: Height,
-    ) -> Result<CertifierOutput, CertificationError> {
-        debug!("Certifying the certificate of network {network_id} at height {height}");
-
-        // Fetch certificate from storage
-        let certificate = self
+        certificate_id: &CertificateId,
+        initial_roots: &pessimistic_proof::local_state::StateCommitment,
+        pv_native: &PessimisticProofOutput,
+    ) -> Result<Option<Proof>, CertificationError> {
+        let Some(proof) = self
             .pending_store
-            .get_certificate(network_id, height)?
-            .ok_or(CertificationError::CertificateNotFound(network_id, height))?;
-
-        let certificate_id = certificate.hash();
-        tracing::Span::current().record("certificate_id", certificate_id.to_string());
-
-        let mut prover_client = self.prover.clone();
-        let pending_store = self.pending_store.clone();
-        let verifier = self.verifier.clone();
-        let verifying_key = self.verifying_key.clone();
-
-        let mut state = state.clone();
-        let (multi_batch_header, initial_state, pv_native) = self
-            .witness_generation(&certificate, &mut state, None)
-            .await?;
+            .get_cached_proof(certificate_id, initial_roots)?
+        else {
+            return Ok(None);
+        };
 
-        let prev_pp_root = pv_native.prev_pessimistic_root;
-        let new_pp_root = pv_native.new_pessimistic_root;
-        info!(
-            %prev_pp_root,
-            %new_pp_root,
-            %certificate_id,
-            "Successfully generated the witness for the PP for certificate",
-        );
+        let Proof::SP1(ref sp1_proof) = proof;
+        match PessimisticProofOutput::from_public_values(sp1_proof.public_values.as_slice()) {
+            Ok(public_values) if public_values == *pv_native => {
+                info!(%certificate_id, "Reusing the cached proof of the certificate");
+                Ok(Some(proof))
+            }
+            _ => {
+                warn!(
+                    %certificate_id,
+                    "Discarding the cached proof of the certificate, its public values differ"
+                );
+                Ok(None)
+            }
+        }
+    }
 
+    /// Build the input of the PP program, which includes the aggchain proof
+    /// to verify if any.
+    fn build_stdin(
+        certificate: &Certificate,
+        initial_state: LocalNetworkState,
+        multi_batch_header: &MultiBatchHeader,
+    ) -> Result<SP1Stdin, CertificationError> {
         let network_state = pessimistic_proof::NetworkState::from(initial_state);
         let mut stdin = sp1_fast(|| {
             let mut stdin = SP1Stdin::new();
@@ -200,16 +309,27 @@ where
             }
         };
 
+        Ok(stdin)
+    }
+
+    /// Execute the PP program in the SP1 executor, and check that it commits
+    /// to the public values of the native execution.
+    async fn execute(
+        &self,
+        stdin: &SP1Stdin,
+        pv_native: PessimisticProofOutput,
+    ) -> Result<(SP1PublicValues, ExecutionReport), CertificationError> {
         // SP1 native execution which includes the aggchain proof stark verification
-        let (pv_sp1_execute, _report) = {
+        let (pv_sp1_execute, public_values, report) = {
             // Do not verify the deferred proof if we are in mock mode
-            let deferred_proof_verification = !self.config.mock_verifier;
+            let deferred_proof_verification = !self.mock_verifier;
             let (pv, report) = sp1_blocking({
                 let verifier = self.verifier.clone();
+                let program = self.program;
                 let stdin = stdin.clone();
                 move || {
                     verifier
-                        .execute(ELF, &stdin)
+                        .execute(program, &stdin)
                         .deferred_proof_verification(deferred_proof_verification)
                         .run()
                 }
@@ -218,11 +338,10 @@ where
             .map_err(CertificationError::Other)?
             .map_err(|e| CertificationError::Sp1ExecuteFailed(eyre!(e)))?;
 
-            let pv_sp1_execute: PessimisticProofOutput = PessimisticProofOutput::bincode_codec()
-                .deserialize(pv.as_slice())
-                .map_err(|source| CertificationError::Deserialize { source })?;
+            let pv_sp1_execute = PessimisticProofOutput::from_public_values(pv.as_slice())
+                .map_err(|source| CertificationError::InvalidPublicValues { source })?;
 
-            (pv_sp1_execute, report)
+            (pv_sp1_execute, pv, report)
         };
 
         if pv_sp1_execute != pv_native {
@@ -237,128 +356,298 @@ where
             "Successfully executed the PP program locally"
         );
 
-        let request = GenerateProofRequest {
-            stdin: Some(Stdin::Sp1Stdin(
-                sp1_fast(|| bincode::default().serialize(&stdin))
-                    .map_err(CertificationError::Other)?
-                    .map_err(|source| CertificationError::Serialize { source })?
-                    .into(),
-            )),
+        Ok((public_values, report))
+    }
+
+    /// Execute the PP program and generate its proof, capturing its stdin
+    /// on failure.
+    async fn generate_proof(
+        &self,
+        certificate: &Certificate,
+        initial_state: LocalNetworkState,
+        multi_batch_header: &MultiBatchHeader,
+        pv_native: PessimisticProofOutput,
+    ) -> Result<Proof, CertificationError> {
+        let stdin = Self::build_stdin(certificate, initial_state, multi_batch_header)?;
+
+        let result = self.prove(&stdin, pv_native).await;
+        if result.is_err() {
+            self.capture_failed_stdin(certificate.hash(), &stdin);
+        }
+
+        result
+    }
+
+    /// Execute the PP program on the given stdin and generate its proof.
+    async fn prove(
+        &self,
+        stdin: &SP1Stdin,
+        pv_native: PessimisticProofOutput,
+    ) -> Result<Proof, CertificationError> {
+        let (public_values, report) = self.execute(stdin, pv_native).await?;
+
+        let proof = match &self.backend {
+            ProvingBackend::ExecuteOnly(proving_key) => {
+                info!(
+                    cycles = report.total_instruction_count(),
+                    "Skipping the proof generation, building a mock proof from the executed \
+                     public values"
+                );
+
+                Proof::SP1(mock::mock_proof(proving_key, public_values))
+            }
+            ProvingBackend::Remote(prover) => {
+                if let Some(network_quota) = &self.network_quota {
+                    network_quota
+                        .acquire(report.total_instruction_count())
+                        .await?;
+                }
+
+                prover.generate_proof(stdin).await?
+            }
+        };
+
+        Ok(proof)
+    }
+
+    /// Persist the stdin of a failed proving, when a capture directory is
+    /// configured.
+    fn capture_failed_stdin(&self, certificate_id: CertificateId, stdin: &SP1Stdin) {
+        let Some(dir) = &self.config.certificate_orchestrator.failed_proof_stdin_dir else {
+            return;
         };
 
-        info!("Sending the Proof generation request to the agglayer-prover service...");
-        let prover_response: tonic::Response<GenerateProofResponse> = prover_client
-            .generate_proof(request)
+        match stdin_capture::capture_stdin(dir, certificate_id, stdin) {
+            Ok(path) => warn!(
+                path = %path.display(),
+                "Captured the stdin of the failed proving"
+            ),
+            Err(error) => error!(?error, "Failed to capture the stdin of the failed proving"),
+        }
+    }
+
+    /// Record the inputs of a failed certification in the debug storage, when
+    /// one is configured.
+    fn record_certification_failure(
+        &self,
+        certificate: &Certificate,
+        initial_roots: &pessimistic_proof::local_state::StateCommitment,
+        error: &CertificationError,
+    ) {
+        let Some(debug_store) = &self.debug_store else {
+            return;
+        };
+
+        let failure = CertificationFailure::new(initial_roots.clone(), format!("{error:?}"));
+        if let Err(error) = debug_store.add_certification_failure(certificate, &failure) {
+            error!(?error, "Failed to record the failed certification");
+        }
+    }
+}
+
+impl<PendingStore, L1Rpc> CertifierClient<PendingStore, L1Rpc>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter + 'static,
+    L1Rpc: RollupContract + AggchainContract + Send + Sync + 'static,
+{
+    /// Get the proof submitted for the certificate from outside of the
+    /// agglayer, once checked against the expected public values and
+    /// verified. A submitted proof failing these checks is discarded, for the
+    /// certificate to be proven locally.
+    async fn submitted_proof(
+        &self,
+        certificate_id: &CertificateId,
+        pv_native: &PessimisticProofOutput,
+    ) -> Result<Option<Proof>, CertificationError> {
+        let Some(proof) = self.pending_store.get_submitted_proof(certificate_id)? else {
+            return Ok(None);
+        };
+
+        match self
+            .check_submitted_proof(certificate_id, &proof, pv_native)
             .await
-            .map_err(|source_error| {
-                debug!("Failed to generate the p-proof: {:?}", source_error);
-                if let Ok(error) = bincode::default()
-                    .deserialize::<agglayer_prover_types::v1::GenerateProofError>(
-                        source_error.details(),
-                    )
-                {
-                    match error.error_type() {
-                        ErrorKind::UnableToExecuteProver => {
-                            CertificationError::InternalError("Unable to execute prover".into())
-                        }
-                        ErrorKind::ProverFailed => {
-                            CertificationError::ProverFailed(source_error.message().to_string())
-                        }
-                        ErrorKind::ProofVerificationFailed => {
-                            let proof_error: Result<
-                                pessimistic_proof::error::ProofVerificationError,
-                                _,
-                            > = bincode::default().deserialize(&error.error);
-
-                            match proof_error {
-                                Ok(error) => {
-                                    CertificationError::ProofVerificationFailed { source: error }
-                                }
-                                Err(_source) => {
-                                    warn!(
-                                        "Failed to deserialize the error details coming from the \
-                                         prover: {source_error:?}"
-                                    );
-
-                                    CertificationError::InternalError(
-                                        source_error.message().to_string(),
-                                    )
-                                }
-                            }
-                        }
-
-                        ErrorKind::ExecutorFailed => {
-                            let proof_error: Result<pessimistic_proof::ProofError, _> =
-                                bincode::default().deserialize(&error.error);
-
-                            match proof_error {
-                                Ok(error) => {
-                                    CertificationError::ProverExecutionFailed { source: error }
-                                }
-                                Err(_source) => {
-                                    warn!(
-                                        "Failed to deserialize the error details coming from the \
-                                         prover: {source_error:?}"
-                                    );
-
-                                    CertificationError::InternalError(
-                                        source_error.message().to_string(),
-                                    )
-                                }
-                            }
-                        }
-                        ErrorKind::Unspecified => {
-                            CertificationError::InternalError(source_error.message().to_string())
-                        }
-                    }
-                } else {
-                    warn!(
-                        "Failed to deserialize the error details coming from the prover: \
-                         {source_error:?}"
-                    );
+        {
+            Ok(()) => {
+                info!(%certificate_id, "Using the proof submitted for the certificate");
+                Ok(Some(proof))
+            }
+            Err(
+                error @ (CertificationError::InvalidPublicValues { .. }
+                | CertificationError::SubmittedProofPublicValuesMismatch { .. }
+                | CertificationError::ProofVerificationFailed { .. }),
+            ) => {
+                warn!(
+                    ?error,
+                    %certificate_id,
+                    "Discarding the proof submitted for the certificate, proving it locally"
+                );
+                self.pending_store.remove_submitted_proof(certificate_id)?;
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
 
-                    CertificationError::InternalError(source_error.message().to_string())
-                }
-            })?;
+    /// Check that the submitted proof commits to the expected public values,
+    /// and verify it unless already verified.
+    async fn check_submitted_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+        pv_native: &PessimisticProofOutput,
+    ) -> Result<(), CertificationError> {
+        let Proof::SP1(sp1_proof) = proof;
+        let public_values =
+            PessimisticProofOutput::from_public_values(sp1_proof.public_values.as_slice())
+                .map_err(|source| CertificationError::InvalidPublicValues { source })?;
+
+        if public_values != *pv_native {
+            return Err(CertificationError::SubmittedProofPublicValuesMismatch {
+                expected: Box::new(pv_native.clone()),
+                submitted: Box::new(public_values),
+            });
+        }
 
-        let proof = prover_response.into_inner().proof;
-        let proof: Proof = sp1_fast(|| bincode::default().deserialize(&proof))
-            .map_err(CertificationError::Other)?
-            .map_err(|source| CertificationError::Deserialize { source })?;
+        self.verify_once(certificate_id, proof).await
+    }
+
+    /// Verify the proof of the certificate, unless this very proof was
+    /// already verified, e.g. before a restart or a retry.
+    async fn verify_once(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+    ) -> Result<(), CertificationError> {
+        let proof_digest = Self::proof_digest(proof)?;
+        if self.pending_store.get_verified_proof(certificate_id)? == Some(proof_digest) {
+            info!("Skipping the verification of the p-proof, it was already verified");
+            agglayer_telemetry::verification::record_skipped();
+            return Ok(());
+        }
+
+        let Proof::SP1(proof_to_verify) = proof;
 
-        debug!("Proof successfully generated!");
+        debug!("Verifying the p-proof...");
 
-        let Proof::SP1(ref proof_to_verify) = proof;
+        self.verify_proof(proof_to_verify)
+            .await
+            .inspect_err(|error| error!("Failed to verify the p-proof: {:?}", error))?;
+        self.pending_store
+            .set_verified_proof(certificate_id, &proof_digest)?;
 
-        debug!("Verifying the generated p-proof...");
+        Ok(())
+    }
 
-        if let Err(error) = Self::verify_proof(verifier, &verifying_key, proof_to_verify) {
-            error!("Failed to verify the p-proof: {:?}", error);
-            match error.downcast::<SP1VerificationError>() {
-                Ok(error) => Err(CertificationError::ProofVerificationFailed {
-                    source: error.into(),
-                }),
-                Err(error) => Err(CertificationError::Other(error)),
+    /// Generate, or reuse, and verify the proof of the certificate from the
+    /// given state, returning the new state along with the network and the
+    /// new pessimistic root.
+    async fn certify_certificate(
+        &self,
+        certificate: &Certificate,
+        mut state: LocalNetworkStateData,
+        initial_roots: &pessimistic_proof::local_state::StateCommitment,
+    ) -> Result<(LocalNetworkStateData, NetworkId, Digest), CertificationError> {
+        let certificate_id = certificate.hash();
+        let pending_store = self.pending_store.clone();
+
+        let (multi_batch_header, initial_state, pv_native) = self
+            .witness_generation(certificate, &mut state, None)
+            .await?;
+
+        let prev_pp_root = pv_native.prev_pessimistic_root;
+        let new_pp_root = pv_native.new_pessimistic_root;
+        info!(
+            %prev_pp_root,
+            %new_pp_root,
+            %certificate_id,
+            "Successfully generated the witness for the PP for certificate",
+        );
+
+        // The proof submitted for the certificate skips the local proving, and a
+        // retried certificate reuses the proof generated from the same state.
+        let (proof, generated) = match self.submitted_proof(&certificate_id, &pv_native).await? {
+            Some(proof) => (proof, false),
+            None => {
+                let cached_proof = self.cached_proof(&certificate_id, initial_roots, &pv_native)?;
+                let generated = cached_proof.is_none();
+                let proof = match cached_proof {
+                    Some(proof) => proof,
+                    None => {
+                        self.generate_proof(
+                            certificate,
+                            initial_state,
+                            &multi_batch_header,
+                            pv_native,
+                        )
+                        .await?
+                    }
+                };
+
+                debug!("Proof successfully generated!");
+
+                self.verify_once(&certificate_id, &proof).await?;
+                (proof, generated)
             }
-        } else {
-            info!("Successfully generated and verified the p-proof!");
-
-            // TODO: Check if the key already exists
-            pending_store.insert_generated_proof(&certificate_id, &proof)?;
-
-            // Prune the SMTs of the state
-            state
-                .prune_stale_nodes()
-                .map_err(|e| CertificationError::InternalError(e.to_string()))?;
-
-            Ok(CertifierOutput {
-                certificate,
-                height,
-                new_state: state,
-                network: multi_batch_header.origin_network,
-                new_pp_root,
-            })
+        };
+
+        info!("Successfully generated and verified the p-proof!");
+
+        // TODO: Check if the key already exists
+        let roots = CertifiedRoots {
+            initial_roots: initial_roots.clone(),
+            new_roots: state.get_roots(),
+        };
+        pending_store.insert_certified_proof(&certificate_id, &proof, &roots, generated)?;
+
+        // Prune the SMTs of the state
+        state
+            .prune_stale_nodes()
+            .map_err(|e| CertificationError::InternalError(e.to_string()))?;
+
+        Ok((state, multi_batch_header.origin_network, new_pp_root))
+    }
+}
+
+#[async_trait::async_trait]
+impl<PendingStore, L1Rpc> Certifier for CertifierClient<PendingStore, L1Rpc>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter + 'static,
+    L1Rpc: RollupContract + AggchainContract + Send + Sync + 'static,
+{
+    #[instrument(skip(self, state, height), fields(certificate_id, %network_id), level = "info")]
+    async fn certify(
+        &self,
+        state: LocalNetworkStateData,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<CertifierOutput, CertificationError> {
+        debug!("Certifying the certificate of network {network_id} at height {height}");
+
+        // Fetch certificate from storage
+        let certificate = self
+            .pending_store
+            .get_certificate(network_id, height)?
+            .ok_or(CertificationError::CertificateNotFound(network_id, height))?;
+
+        let certificate_id = certificate.hash();
+        tracing::Span::current().record("certificate_id", certificate_id.to_string());
+
+        let initial_roots = state.get_roots();
+        let result = self
+            .certify_certificate(&certificate, state, &initial_roots)
+            .await;
+        if let Err(error) = &result {
+            self.record_certification_failure(&certificate, &initial_roots, error);
         }
+        let (new_state, network, new_pp_root) = result?;
+
+        Ok(CertifierOutput {
+            certificate,
+            height,
+            new_state,
+            network,
+            new_pp_root,
+        })
     }
 
     async fn witness_generation(
@@ -482,6 +771,14 @@ where
             });
         }
 
-        Ok((multi_batch_header, initial_state, pv))
+        // The proofs only carry the fields of the committed public values.
+        Ok((multi_batch_header, initial_state, pv.committed()))
+    }
+
+    fn prover(&self) -> Option<String> {
+        match &self.backend {
+            ProvingBackend::Remote(prover) => Some(prover.endpoint().to_string()),
+            ProvingBackend::ExecuteOnly(_) => Some("sp1-execute".to_string()),
+        }
     }
 }
@@ -0,0 +1,54 @@
+use agglayer_certificate_orchestrator::{CertificationError, Certifier, NativeExecutor};
+use agglayer_contracts::{aggchain::AggchainContract, RollupContract};
+use agglayer_storage::stores::{PendingCertificateReader, PendingCertificateWriter};
+use agglayer_types::{
+    BalanceChange, Certificate, LocalNetworkStateData, NativeExecutionReport, U256,
+};
+use tracing::{info, instrument};
+
+use crate::CertifierClient;
+
+#[async_trait::async_trait]
+impl<PendingStore, L1Rpc> NativeExecutor for CertifierClient<PendingStore, L1Rpc>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter + 'static,
+    L1Rpc: RollupContract + AggchainContract + Send + Sync + 'static,
+{
+    #[instrument(skip_all, fields(certificate_id = %certificate.hash(), network_id = %certificate.network_id), level = "info")]
+    async fn execute_natively(
+        &self,
+        mut state: LocalNetworkStateData,
+        certificate: &Certificate,
+    ) -> Result<NativeExecutionReport, CertificationError> {
+        let initial_roots = state.get_roots();
+
+        let (multi_batch_header, _initial_state, pv_native) = self
+            .witness_generation(certificate, &mut state, None)
+            .await?;
+
+        // The balance proofs hold the initial balance of every token moved by
+        // the certificate.
+        let balance_changes = multi_batch_header
+            .balances_proofs
+            .iter()
+            .map(|(&token, &(initial_balance, _))| BalanceChange {
+                token,
+                initial_balance,
+                new_balance: U256::from_be_bytes(
+                    *state.balance_tree.get(token).unwrap_or_default(),
+                ),
+            })
+            .collect();
+
+        let report = NativeExecutionReport {
+            certificate_id: certificate.hash(),
+            initial_roots,
+            new_roots: state.get_roots(),
+            new_pessimistic_root: pv_native.new_pessimistic_root,
+            balance_changes,
+        };
+        info!(?report, "Executed the certificate natively");
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::VecDeque, sync::Mutex, time::Duration};
+
+use agglayer_certificate_orchestrator::CertificationError;
+use agglayer_config::certificate_orchestrator::sp1_network_quota::Sp1NetworkQuota;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// Quota of the proofs requested from the SP1 network, tracking the requests
+/// sent over the sliding period.
+pub(crate) struct NetworkQuota {
+    config: Sp1NetworkQuota,
+    /// Time and cycles of the requests sent over the period, oldest first.
+    requests: Mutex<VecDeque<(Instant, u64)>>,
+    /// Serves the requests waiting for the quota in order of arrival.
+    queue: tokio::sync::Mutex<()>,
+}
+
+impl NetworkQuota {
+    pub(crate) fn new(config: Sp1NetworkQuota) -> Self {
+        Self {
+            config,
+            requests: Mutex::new(VecDeque::new()),
+            queue: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Wait, behind the requests queued before it, for the quota to allow a
+    /// request of the given cycles, and record it.
+    pub(crate) async fn acquire(&self, cycles: u64) -> Result<(), CertificationError> {
+        let deadline = Instant::now() + self.config.max_queue_wait;
+
+        let Ok(_turn) = tokio::time::timeout_at(deadline, self.queue.lock()).await else {
+            return Err(self.exhausted(Instant::now(), cycles));
+        };
+
+        loop {
+            let now = Instant::now();
+            match self.try_acquire(now, cycles) {
+                Ok(()) => return Ok(()),
+                Err(available_at) if available_at <= deadline => {
+                    info!(
+                        cycles,
+                        wait = ?available_at - now,
+                        "SP1 network quota reached, queueing the proving request"
+                    );
+                    tokio::time::sleep_until(available_at).await;
+                }
+                Err(available_at) => {
+                    let retry_after = available_at - now;
+                    warn!(cycles, ?retry_after, "SP1 network quota exhausted");
+
+                    return Err(CertificationError::ProverQuotaExhausted { retry_after });
+                }
+            }
+        }
+    }
+
+    /// Record a request of the given cycles if the quota allows it, or
+    /// return when it will.
+    fn try_acquire(&self, now: Instant, cycles: u64) -> Result<(), Instant> {
+        let mut requests = self
+            .requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        while requests
+            .front()
+            .is_some_and(|(sent_at, _)| *sent_at + self.config.period <= now)
+        {
+            requests.pop_front();
+        }
+
+        match self.available_at(&requests, cycles) {
+            None => {
+                requests.push_back((now, cycles));
+                Ok(())
+            }
+            Some(available_at) => Err(available_at),
+        }
+    }
+
+    /// When enough of the given requests expire for the quota to allow one
+    /// more of the given cycles, `None` if it already does.
+    fn available_at(&self, requests: &VecDeque<(Instant, u64)>, cycles: u64) -> Option<Instant> {
+        let allows = |count: usize, used_cycles: u64| {
+            self.config
+                .max_proofs
+                .map_or(true, |max_proofs| count < max_proofs.get() as usize)
+                && self.config.max_cycles.map_or(true, |max_cycles| {
+                    count == 0 || used_cycles.saturating_add(cycles) <= max_cycles
+                })
+        };
+
+        let mut count = requests.len();
+        let mut used_cycles: u64 = requests.iter().map(|(_, cycles)| cycles).sum();
+        if allows(count, used_cycles) {
+            return None;
+        }
+
+        // The quota always allows a request once the period is empty.
+        requests.iter().find_map(|(sent_at, request_cycles)| {
+            count -= 1;
+            used_cycles -= request_cycles;
+            allows(count, used_cycles).then_some(*sent_at + self.config.period)
+        })
+    }
+
+    fn exhausted(&self, now: Instant, cycles: u64) -> CertificationError {
+        let requests = self
+            .requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let retry_after = self
+            .available_at(&requests, cycles)
+            .map_or(Duration::ZERO, |available_at| {
+                available_at.saturating_duration_since(now)
+            });
+
+        CertificationError::ProverQuotaExhausted { retry_after }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+
+    const PERIOD: Duration = Duration::from_secs(60);
+
+    fn quota(max_proofs: Option<u32>, max_cycles: Option<u64>) -> NetworkQuota {
+        NetworkQuota::new(Sp1NetworkQuota {
+            period: PERIOD,
+            max_proofs: max_proofs.and_then(NonZeroU32::new),
+            max_cycles,
+            max_queue_wait: Duration::ZERO,
+        })
+    }
+
+    #[test]
+    fn proofs_are_limited_over_the_period() {
+        let quota = quota(Some(2), None);
+        let start = Instant::now();
+
+        quota.try_acquire(start, 1).unwrap();
+        quota.try_acquire(start + PERIOD / 2, 1).unwrap();
+
+        assert_eq!(
+            quota.try_acquire(start + PERIOD / 2, 1),
+            Err(start + PERIOD)
+        );
+        quota.try_acquire(start + PERIOD, 1).unwrap();
+    }
+
+    #[test]
+    fn cycles_are_limited_over_the_period() {
+        let quota = quota(None, Some(100));
+        let start = Instant::now();
+
+        quota.try_acquire(start, 60).unwrap();
+        quota.try_acquire(start + PERIOD / 2, 30).unwrap();
+
+        assert_eq!(
+            quota.try_acquire(start + PERIOD / 2, 20),
+            Err(start + PERIOD)
+        );
+        quota.try_acquire(start + PERIOD / 2, 10).unwrap();
+    }
+
+    #[test]
+    fn request_above_the_cycles_quota_waits_for_an_empty_period() {
+        let quota = quota(None, Some(100));
+        let start = Instant::now();
+
+        quota.try_acquire(start, 10).unwrap();
+
+        assert_eq!(quota.try_acquire(start, 500), Err(start + PERIOD));
+        quota.try_acquire(start + PERIOD, 500).unwrap();
+    }
+
+    #[tokio::test]
+    async fn exhausted_quota_is_reported_after_the_queue_wait() {
+        let quota = quota(Some(1), None);
+
+        quota.acquire(1).await.unwrap();
+
+        let error = quota.acquire(1).await.unwrap_err();
+        assert!(matches!(
+            error,
+            CertificationError::ProverQuotaExhausted { retry_after } if retry_after <= PERIOD
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use agglayer_certificate_orchestrator::{CertificationError, Certifier, ProvingCostEstimator};
+use agglayer_config::certificate_orchestrator::sp1_network_pricing::Sp1NetworkPricing;
+use agglayer_contracts::{aggchain::AggchainContract, RollupContract};
+use agglayer_storage::stores::{PendingCertificateReader, PendingCertificateWriter};
+use agglayer_types::{Certificate, LocalNetworkStateData, ProvingCostEstimate};
+use tracing::{info, instrument};
+
+use crate::CertifierClient;
+
+#[async_trait::async_trait]
+impl<PendingStore, L1Rpc> ProvingCostEstimator for CertifierClient<PendingStore, L1Rpc>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter + 'static,
+    L1Rpc: RollupContract + AggchainContract + Send + Sync + 'static,
+{
+    #[instrument(skip_all, fields(certificate_id = %certificate.hash(), network_id = %certificate.network_id), level = "info")]
+    async fn estimate_proving_cost(
+        &self,
+        mut state: LocalNetworkStateData,
+        certificate: &Certificate,
+    ) -> Result<ProvingCostEstimate, CertificationError> {
+        let (multi_batch_header, initial_state, pv_native) = self
+            .witness_generation(certificate, &mut state, None)
+            .await?;
+
+        let stdin = Self::build_stdin(certificate, initial_state, &multi_batch_header)?;
+        let (_public_values, report) = self.execute(&stdin, pv_native).await?;
+
+        let estimate = estimate_from_cycles(
+            report.total_instruction_count(),
+            self.config
+                .certificate_orchestrator
+                .sp1_network_pricing
+                .as_ref(),
+        );
+        info!(?estimate, "Estimated the proving cost of the certificate");
+
+        Ok(estimate)
+    }
+}
+
+/// Estimate the cost and time of proving the given number of cycles on the
+/// SP1 network, when its pricing is known.
+pub(crate) fn estimate_from_cycles(
+    cycles: u64,
+    pricing: Option<&Sp1NetworkPricing>,
+) -> ProvingCostEstimate {
+    let Some(pricing) = pricing else {
+        return ProvingCostEstimate {
+            cycles,
+            cost: None,
+            proving_time_secs: None,
+        };
+    };
+
+    let cost = (cycles as u128)
+        .saturating_mul(pricing.price_per_million_cycles)
+        .div_ceil(1_000_000);
+    let proving_time_secs = cycles
+        .div_ceil(pricing.cycles_per_second.max(1))
+        .saturating_add(pricing.proving_overhead.as_secs());
+
+    ProvingCostEstimate {
+        cycles,
+        cost: Some(cost),
+        proving_time_secs: Some(proving_time_secs),
+    }
+}
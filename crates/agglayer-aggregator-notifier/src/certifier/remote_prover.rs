@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use agglayer_certificate_orchestrator::CertificationError;
+use agglayer_prover_config::GrpcConfig;
+use agglayer_prover_types::v1::{
+    generate_proof_request::Stdin, pessimistic_proof_service_client::PessimisticProofServiceClient,
+    ErrorKind, GenerateProofRequest, GenerateProofResponse,
+};
+use agglayer_types::{bincode, Proof};
+use prover_executor::sp1_fast;
+use sp1_sdk::SP1Stdin;
+use tonic::{codec::CompressionEncoding, transport::Channel};
+use tracing::{debug, info, warn};
+
+/// Proving backend delegating the proof generation to a standalone
+/// `agglayer-prover` service through its gRPC proving API.
+///
+/// The prover service runs as a separate process, started with the `prover`
+/// subcommand, which allows to scale the proving independently of the node.
+#[derive(Clone)]
+pub struct RemoteProver {
+    /// The prover service client.
+    client: PessimisticProofServiceClient<Channel>,
+    /// The endpoint of the prover service.
+    endpoint: String,
+    /// Maximum duration of a proof generation request.
+    proving_timeout: Duration,
+}
+
+impl RemoteProver {
+    /// Connect to the prover service listening at the given endpoint, the
+    /// proof generation requests being cancelled after the proving timeout.
+    pub async fn connect(
+        endpoint: String,
+        grpc: &GrpcConfig,
+        proving_timeout: Duration,
+    ) -> eyre::Result<Self> {
+        debug!("Connecting to the prover service at {endpoint}...");
+
+        let client = PessimisticProofServiceClient::connect(endpoint.clone())
+            .await?
+            .max_decoding_message_size(grpc.max_decoding_message_size)
+            .max_encoding_message_size(grpc.max_encoding_message_size)
+            .send_compressed(CompressionEncoding::Zstd)
+            .accept_compressed(CompressionEncoding::Zstd);
+
+        debug!("Successfully connected to the prover service!");
+
+        Ok(Self {
+            client,
+            endpoint,
+            proving_timeout,
+        })
+    }
+
+    /// The endpoint of the prover service.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Request the generation of the proof to the prover service.
+    ///
+    /// The request is dropped once the proving timeout elapses, which resets
+    /// its stream and cancels the proving on the prover service.
+    pub async fn generate_proof(&self, stdin: &SP1Stdin) -> Result<Proof, CertificationError> {
+        let request = GenerateProofRequest {
+            stdin: Some(Stdin::Sp1Stdin(
+                sp1_fast(|| bincode::default().serialize(stdin))
+                    .map_err(CertificationError::Other)?
+                    .map_err(|source| CertificationError::Serialize { source })?
+                    .into(),
+            )),
+        };
+
+        info!("Sending the Proof generation request to the agglayer-prover service...");
+        let mut client = self.client.clone();
+        let prover_response: tonic::Response<GenerateProofResponse> = tokio::time::timeout(
+            self.proving_timeout,
+            client.generate_proof(request),
+        )
+        .await
+        .map_err(|_elapsed| {
+            warn!(timeout = ?self.proving_timeout, "Cancelled the proof generation request");
+            CertificationError::ProvingTimeout {
+                timeout: self.proving_timeout,
+            }
+        })?
+        .map_err(Self::map_error)?;
+
+        let proof = prover_response.into_inner().proof;
+        sp1_fast(|| bincode::default().deserialize(&proof))
+            .map_err(CertificationError::Other)?
+            .map_err(|source| CertificationError::Deserialize { source })
+    }
+
+    /// Convert the error returned by the prover service, using the details it
+    /// carries when they can be decoded.
+    fn map_error(source_error: tonic::Status) -> CertificationError {
+        debug!("Failed to generate the p-proof: {:?}", source_error);
+        let Ok(error) = bincode::default()
+            .deserialize::<agglayer_prover_types::v1::GenerateProofError>(source_error.details())
+        else {
+            warn!(
+                "Failed to deserialize the error details coming from the prover: {source_error:?}"
+            );
+
+            return CertificationError::InternalError(source_error.message().to_string());
+        };
+
+        match error.error_type() {
+            ErrorKind::UnableToExecuteProver => {
+                CertificationError::InternalError("Unable to execute prover".into())
+            }
+            ErrorKind::ProverFailed => {
+                CertificationError::ProverFailed(source_error.message().to_string())
+            }
+            ErrorKind::ProofVerificationFailed => {
+                let proof_error: Result<pessimistic_proof::error::ProofVerificationError, _> =
+                    bincode::default().deserialize(&error.error);
+
+                match proof_error {
+                    Ok(error) => CertificationError::ProofVerificationFailed { source: error },
+                    Err(_source) => {
+                        warn!(
+                            "Failed to deserialize the error details coming from the prover: \
+                             {source_error:?}"
+                        );
+
+                        CertificationError::InternalError(source_error.message().to_string())
+                    }
+                }
+            }
+            ErrorKind::ExecutorFailed => {
+                let proof_error: Result<pessimistic_proof::ProofError, _> =
+                    bincode::default().deserialize(&error.error);
+
+                match proof_error {
+                    Ok(error) => CertificationError::ProverExecutionFailed { source: error },
+                    Err(_source) => {
+                        warn!(
+                            "Failed to deserialize the error details coming from the prover: \
+                             {source_error:?}"
+                        );
+
+                        CertificationError::InternalError(source_error.message().to_string())
+                    }
+                }
+            }
+            ErrorKind::Unspecified => {
+                CertificationError::InternalError(source_error.message().to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Deterministic replay of the certification of a stored certificate.
+//!
+//! The replay runs the certification pipeline, the native execution followed
+//! by the execution of the PP program and a mock proving, against a snapshot
+//! of the network state. Nothing is written to the stores, and the report is
+//! identical across environments for the same certificate, state and L1
+//! context, which allows to locate where two environments diverge.
+
+use std::sync::Arc;
+
+use agglayer_certificate_orchestrator::{CertificationError, Certifier};
+use agglayer_config::Config;
+use agglayer_contracts::{aggchain::AggchainContract, RollupContract};
+use agglayer_prover_types::mock;
+use agglayer_storage::stores::{PendingCertificateReader, PendingCertificateWriter};
+use agglayer_types::{bincode, Certificate, CertificateId, Digest, LocalNetworkStateData};
+use pessimistic_proof::{keccak::keccak256, local_state::StateCommitment, PessimisticProofOutput};
+use prover_executor::sp1_fast;
+use serde::Serialize;
+use tracing::{info, instrument};
+
+use super::{verification_pool::VerificationPool, ProvingBackend};
+use crate::CertifierClient;
+
+/// Report of the replay of a certification.
+#[derive(Clone, Debug, Serialize)]
+pub struct CertificationReplay {
+    pub certificate_id: CertificateId,
+    /// Roots of the network state the certificate was replayed against.
+    pub initial_roots: StateCommitment,
+    /// Roots of the network state once the certificate is applied.
+    pub new_roots: StateCommitment,
+    /// Keccak hash of the stdin of the PP program, encoded as sent to the
+    /// prover service.
+    pub stdin_hash: Digest,
+    /// Public values committed to by the PP program.
+    pub public_values: PessimisticProofOutput,
+    /// Number of cycles of the execution of the PP program.
+    pub cycles: u64,
+    /// Digest of the mock proof built from the public values.
+    pub proof_digest: Digest,
+}
+
+impl<PendingStore, L1Rpc> CertifierClient<PendingStore, L1Rpc> {
+    /// Build a certifier replaying the certifications, which executes the PP
+    /// program and builds mock proofs instead of requesting them from the
+    /// prover service.
+    pub async fn for_replay(
+        pending_store: Arc<PendingStore>,
+        l1_rpc: Arc<L1Rpc>,
+        config: Arc<Config>,
+    ) -> eyre::Result<Self> {
+        let program = Self::program(&config)?;
+        let (verifier, proving_key, verifying_key) = Self::setup_verifier(program, true).await?;
+        let verifier = Arc::new(verifier);
+        let verification_pool = Arc::new(VerificationPool::new(
+            verifier.clone(),
+            verifying_key.clone(),
+            true,
+            1,
+        ));
+
+        Ok(Self {
+            pending_store,
+            backend: ProvingBackend::ExecuteOnly(Arc::new(proving_key)),
+            program,
+            verifier,
+            verifying_key,
+            mock_verifier: true,
+            verification_pool,
+            l1_rpc,
+            debug_store: None,
+            network_quota: None,
+            config,
+        })
+    }
+}
+
+impl<PendingStore, L1Rpc> CertifierClient<PendingStore, L1Rpc>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter + 'static,
+    L1Rpc: RollupContract + AggchainContract + Send + Sync + 'static,
+{
+    /// Replay the certification of the certificate against the given state.
+    ///
+    /// The L1 context is the one of the given settlement transaction if any,
+    /// so that a settled certificate is replayed against the previous
+    /// pessimistic root it was settled with.
+    #[instrument(skip_all, fields(certificate_id = %certificate.hash()), level = "info")]
+    pub async fn replay(
+        &self,
+        certificate: &Certificate,
+        mut state: LocalNetworkStateData,
+        certificate_tx_hash: Option<Digest>,
+    ) -> Result<CertificationReplay, CertificationError> {
+        let initial_roots = state.get_roots();
+
+        let (multi_batch_header, initial_state, pv_native) = self
+            .witness_generation(certificate, &mut state, certificate_tx_hash)
+            .await?;
+
+        let stdin = Self::build_stdin(certificate, initial_state, &multi_batch_header)?;
+        let stdin_hash = keccak256(
+            &sp1_fast(|| bincode::default().serialize(&stdin))
+                .map_err(CertificationError::Other)?
+                .map_err(|source| CertificationError::Serialize { source })?,
+        );
+
+        let (public_values, report) = self.execute(&stdin, pv_native.clone()).await?;
+        let proof_digest = mock::mock_proof_digest(&self.verifying_key, &public_values);
+
+        let ProvingBackend::ExecuteOnly(proving_key) = &self.backend else {
+            return Err(CertificationError::InternalError(
+                "Not a replay certifier".into(),
+            ));
+        };
+        let proof = mock::mock_proof(proving_key, public_values);
+        self.verify_proof(&proof).await?;
+
+        let replay = CertificationReplay {
+            certificate_id: certificate.hash(),
+            initial_roots,
+            new_roots: state.get_roots(),
+            stdin_hash,
+            public_values: pv_native,
+            cycles: report.total_instruction_count(),
+            proof_digest,
+        };
+        info!(?replay, "Replayed the certification of the certificate");
+
+        Ok(replay)
+    }
+}
@@ -0,0 +1,26 @@
+use std::path::{Path, PathBuf};
+
+use agglayer_types::{bincode, CertificateId};
+use eyre::Context as _;
+use prover_executor::sp1_fast;
+use sp1_sdk::SP1Stdin;
+
+/// Write the stdin of a certificate in the given directory, encoded as sent
+/// to the prover service, to be replayed with the `replay-proof` command.
+pub(crate) fn capture_stdin(
+    dir: &Path,
+    certificate_id: CertificateId,
+    stdin: &SP1Stdin,
+) -> eyre::Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create the directory {}", dir.display()))?;
+
+    let bytes = sp1_fast(|| bincode::default().serialize(stdin))?
+        .context("Failed to serialize the stdin")?;
+
+    let path = dir.join(format!("{certificate_id}.stdin"));
+    std::fs::write(&path, bytes)
+        .with_context(|| format!("Failed to write the stdin to {}", path.display()))?;
+
+    Ok(path)
+}
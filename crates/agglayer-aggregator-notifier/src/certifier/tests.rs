@@ -1,12 +1,24 @@
 use std::{sync::Arc, thread, time::Duration};
 
-use agglayer_certificate_orchestrator::Certifier;
-use agglayer_config::Config;
+use agglayer_certificate_orchestrator::{
+    CertificationError, Certifier, NativeExecutor, ProvingCostEstimator,
+};
+use agglayer_config::{
+    certificate_orchestrator::{prover::ProverConfig, sp1_network_pricing::Sp1NetworkPricing},
+    Config,
+};
 use agglayer_contracts::{L1RpcError, Settler};
 use agglayer_primitives::vkey_hash::VKeyHash;
 use agglayer_prover::fake::FakeProver;
-use agglayer_storage::tests::{mocks::MockPendingStore, TempDBDir};
-use agglayer_types::{Address, Height, LocalNetworkStateData, NetworkId};
+use agglayer_prover_types::mock;
+use agglayer_storage::tests::{
+    mocks::{MockDebugStore, MockPendingStore},
+    TempDBDir,
+};
+use agglayer_types::{
+    bincode, Address, BalanceChange, Digest, Height, LocalNetworkStateData, NetworkId, Proof,
+    ProvingCostEstimate, U256,
+};
 use alloy::{
     contract::Error as ContractError,
     network::Ethereum,
@@ -15,11 +27,17 @@ use alloy::{
 };
 use fail::FailScenario;
 use mockall::predicate::{always, eq};
-use pessimistic_proof_test_suite::forest::Forest;
+use pessimistic_proof::ELF;
+use pessimistic_proof_test_suite::{
+    forest::Forest,
+    sample_data::{ETH, USDC},
+};
 use prover_config::ProverType;
+use sp1_sdk::{Prover as _, ProverClient, SP1PublicValues, SP1Stdin};
 use tokio_util::sync::CancellationToken;
 
-use crate::{CertifierClient, ELF};
+use super::proving_cost::estimate_from_cycles;
+use crate::{CertifierClient, RemoteProver};
 
 #[rstest::rstest]
 #[test_log::test(tokio::test)]
@@ -70,8 +88,34 @@ async fn happy_path() {
         .with(eq(network), eq(height))
         .return_once(|_, _| Ok(Some(certificate)));
 
+    let (proof_tx, proof_rx) = std::sync::mpsc::channel();
+    pending_store
+        .expect_insert_certified_proof()
+        .once()
+        .with(eq(certificate_id), always(), always(), eq(true))
+        .return_once(move |_, proof, _, _| {
+            proof_tx.send(proof.clone()).unwrap();
+            Ok(())
+        });
+
+    pending_store
+        .expect_get_submitted_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(None));
+
+    pending_store
+        .expect_get_cached_proof()
+        .once()
+        .with(eq(certificate_id), always())
+        .return_once(|_, _| Ok(None));
+    pending_store
+        .expect_get_verified_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(None));
     pending_store
-        .expect_insert_generated_proof()
+        .expect_set_verified_proof()
         .once()
         .with(eq(certificate_id), always())
         .return_once(|_, _| Ok(()));
@@ -86,6 +130,11 @@ async fn happy_path() {
         .once()
         .returning(|_| Ok(Address::ZERO));
 
+    l1_rpc
+        .expect_get_gas_token()
+        .once()
+        .returning(|_| Ok((0, Address::ZERO)));
+
     l1_rpc
         .expect_default_l1_info_tree_entry()
         .once()
@@ -118,6 +167,17 @@ async fn happy_path() {
 
     assert_eq!(result.new_state.get_roots(), local_state.get_roots());
 
+    // The fake prover generates the mock proof of the executed public values.
+    let Proof::SP1(proof) = proof_rx.recv().unwrap();
+    let (proving_key, verifying_key) = ProverClient::builder().mock().build().setup(ELF);
+    mock::verify_mock_proof(&proof, &verifying_key).unwrap();
+
+    let expected = mock::mock_proof(&proving_key, proof.public_values.clone());
+    assert_eq!(
+        bincode::default().serialize(&proof).unwrap(),
+        bincode::default().serialize(&expected).unwrap()
+    );
+
     scenario.teardown();
 }
 
@@ -176,10 +236,22 @@ async fn prover_timeout() {
         .return_once(|_, _| Ok(Some(certificate)));
 
     pending_store
-        .expect_insert_generated_proof()
+        .expect_insert_certified_proof()
         .never()
+        .with(eq(certificate_id), always(), always(), always())
+        .return_once(|_, _, _, _| Ok(()));
+
+    pending_store
+        .expect_get_submitted_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(None));
+
+    pending_store
+        .expect_get_cached_proof()
+        .once()
         .with(eq(certificate_id), always())
-        .return_once(|_, _| Ok(()));
+        .return_once(|_, _| Ok(None));
 
     l1_rpc
         .expect_get_trusted_sequencer_address()
@@ -191,6 +263,11 @@ async fn prover_timeout() {
         .once()
         .returning(|_| Ok(Address::ZERO));
 
+    l1_rpc
+        .expect_get_gas_token()
+        .once()
+        .returning(|_| Ok((0, Address::ZERO)));
+
     l1_rpc
         .expect_default_l1_info_tree_entry()
         .once()
@@ -225,69 +302,1006 @@ async fn prover_timeout() {
     scenario.teardown();
 }
 
-mockall::mock! {
-    L1Rpc {}
-    #[async_trait::async_trait]
-    impl agglayer_contracts::RollupContract for L1Rpc {
-        async fn get_trusted_sequencer_address(
-            &self,
-            rollup_id: u32,
-            proof_signers: std::collections::HashMap<u32, agglayer_types::Address>,
-        ) -> Result<agglayer_types::Address, L1RpcError>;
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(60))]
+async fn failed_proving_captures_the_stdin_and_records_the_failure() {
+    let scenario = FailScenario::setup();
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+    let capture_dir = base_path.path.join("failed-proofs");
+    config.certificate_orchestrator.failed_proof_stdin_dir = Some(capture_dir.clone());
 
-        async fn get_rollup_contract_address(&self, rollup_id: u32) -> Result<agglayer_types::Address, L1RpcError>;
+    let mut pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+    let prover_config = agglayer_prover_config::ProverConfig {
+        grpc_endpoint: next_available_addr(),
+        primary_prover: ProverType::CpuProver(prover_config::CpuProverConfig {
+            proving_timeout: Duration::from_secs(1),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
 
-        async fn get_l1_info_root(&self, l1_leaf_count: u32) -> Result<[u8; 32], L1RpcError>;
-        fn default_l1_info_tree_entry(&self) -> (u32, [u8; 32]);
-        async fn get_prev_pessimistic_root(&self, rollup_id: u32, before_tx: Option<TxHash>) -> Result<[u8; 32], L1RpcError>;
-        async fn get_verifier_type(&self, rollup_id: u32) -> Result<agglayer_contracts::rollup::VerifierType, L1RpcError>;
-        fn get_rollup_manager_address(&self) -> agglayer_types::Address;
-        fn get_event_filter_block_range(&self) -> u64;
-    }
+    config.prover_entrypoint = format!(
+        "http://{}:{}",
+        prover_config.grpc_endpoint.ip(),
+        prover_config.grpc_endpoint.port()
+    );
 
-    #[async_trait::async_trait]
-    impl agglayer_contracts::AggchainContract for L1Rpc {
-        async fn get_aggchain_vkey_hash(
-            &self,
-            rollup_address: agglayer_types::Address,
-            aggchain_vkey_selector: u16,
-        ) -> Result<VKeyHash, L1RpcError>;
+    let prover_config = Arc::new(prover_config);
 
-        async fn get_aggchain_hash(
-            &self,
-            rollup_address: agglayer_types::primitives::Address,
-            aggchain_data: Bytes,
-        ) -> Result<[u8; 32], L1RpcError>;
+    let cancellation = CancellationToken::new();
+    let prover_cancellation_token = cancellation.clone();
 
-        async fn get_multisig_context(
-            &self,
-            rollup_address: agglayer_types::Address,
-        ) -> Result<(Vec<agglayer_types::Address>, usize), L1RpcError>;
-    }
+    thread::spawn(move || {
+        agglayer_prover::start_prover(prover_config, prover_cancellation_token, ELF);
+    });
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
 
-    #[async_trait::async_trait]
-    impl agglayer_contracts::L1TransactionFetcher for L1Rpc {
-        type Provider = alloy::providers::RootProvider<Ethereum>;
+    let local_state = LocalNetworkStateData::default();
+    let network = NetworkId::new(1);
+    let height = Height::ZERO;
 
-        async fn fetch_transaction_receipt(&self, tx_hash: FixedBytes<32>) -> Result<Option<TransactionReceipt>, L1RpcError>;
+    let state = Forest::new(vec![]);
 
-        fn get_provider(&self) -> &<Self as agglayer_contracts::L1TransactionFetcher>::Provider;
-    }
+    let withdrawals = vec![];
 
-    #[async_trait::async_trait]
-    impl Settler for L1Rpc {
-        fn decode_contract_revert(error: &ContractError) -> Option<String>;
+    let certificate = state.clone().apply_events(&[], &withdrawals);
 
-        async fn verify_pessimistic_trusted_aggregator(
-            &self,
-            rollup_id: u32,
-            l_1_info_tree_leaf_count: u32,
-            new_local_exit_root: [u8; 32],
-            new_pessimistic_root: [u8; 32],
-            proof: Bytes,
-            custom_chain_data: Bytes,
-            nonce: Option<(u64, u128, Option<u128>)>
-        ) -> Result<alloy::providers::PendingTransactionBuilder<Ethereum>, ContractError>;
+    let signer = state.get_signer();
+    let certificate_id = certificate.hash();
+
+    pending_store
+        .expect_get_certificate()
+        .once()
+        .with(eq(network), eq(height))
+        .return_once(|_, _| Ok(Some(certificate)));
+
+    pending_store
+        .expect_insert_certified_proof()
+        .never()
+        .with(eq(certificate_id), always(), always(), always())
+        .return_once(|_, _, _, _| Ok(()));
+
+    pending_store
+        .expect_get_submitted_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(None));
+
+    pending_store
+        .expect_get_cached_proof()
+        .once()
+        .with(eq(certificate_id), always())
+        .return_once(|_, _| Ok(None));
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .once()
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .once()
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .once()
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .once()
+        .returning(|| (0u32, [1u8; 32]));
+
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .once()
+        .returning(|_, _| Ok([0u8; 32]));
+
+    fail::cfg(
+        "notifier::certifier::certify::before_verifying_proof",
+        "return()",
+    )
+    .unwrap();
+
+    let mut debug_store = MockDebugStore::new();
+    let initial_roots = local_state.get_roots();
+    debug_store
+        .expect_add_certification_failure()
+        .once()
+        .withf(move |certificate, failure| {
+            certificate.hash() == certificate_id && failure.initial_roots == initial_roots
+        })
+        .returning(|_, _| Ok(()));
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(pending_store),
+        Arc::new(l1_rpc),
+        Arc::new(config),
+    )
+    .await
+    .unwrap()
+    .with_debug_store(Arc::new(debug_store));
+
+    let result = certifier
+        .certify(local_state.clone(), network, height)
+        .await;
+
+    assert!(result.is_err());
+
+    let stdin_path = capture_dir.join(format!("{certificate_id}.stdin"));
+    let stdin = agglayer_prover::replay::read_stdin(&stdin_path).unwrap();
+    assert_eq!(stdin.buffer.len(), 2);
+
+    scenario.teardown();
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(60))]
+async fn proving_is_cancelled_after_the_timeout() {
+    let endpoint = next_available_addr();
+    let fake_prover = FakeProver::new(ELF)
+        .await
+        .unwrap()
+        .with_proving_delay(Duration::from_secs(3600));
+    let cancellation = CancellationToken::new();
+    FakeProver::spawn_at(fake_prover, endpoint, cancellation.clone())
+        .await
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let prover = RemoteProver::connect(
+        format!("http://{}:{}", endpoint.ip(), endpoint.port()),
+        &Default::default(),
+        Duration::from_millis(500),
+    )
+    .await
+    .unwrap();
+
+    let result = prover.generate_proof(&SP1Stdin::new()).await;
+
+    assert!(matches!(
+        result,
+        Err(CertificationError::ProvingTimeout { timeout }) if timeout == Duration::from_millis(500)
+    ));
+
+    cancellation.cancel();
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn execute_only_certifier_does_not_connect_to_the_prover() {
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+    config.mock_verifier = true;
+    config.certificate_orchestrator.prover = ProverConfig::SP1Execute {
+        proving_timeout: None,
+    };
+
+    // No prover service is listening at this endpoint.
+    let endpoint = next_available_addr();
+    config.prover_entrypoint = format!("http://{}:{}", endpoint.ip(), endpoint.port());
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(MockPendingStore::new()),
+        Arc::new(MockL1Rpc::new()),
+        Arc::new(config),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(certifier.prover().as_deref(), Some("sp1-execute"));
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn cached_proof_is_reused() {
+    let scenario = FailScenario::setup();
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+
+    let mut pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+    let prover_config = agglayer_prover_config::ProverConfig {
+        grpc_endpoint: next_available_addr(),
+        ..Default::default()
+    };
+
+    config.prover_entrypoint = format!(
+        "http://{}:{}",
+        prover_config.grpc_endpoint.ip(),
+        prover_config.grpc_endpoint.port()
+    );
+
+    let fake_prover = FakeProver::new(ELF).await.unwrap();
+    let cancellation = CancellationToken::new();
+    FakeProver::spawn_at(
+        fake_prover,
+        prover_config.grpc_endpoint,
+        cancellation.clone(),
+    )
+    .await
+    .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let local_state = LocalNetworkStateData::default();
+    let network: NetworkId = 1.into();
+    let height = Height::ZERO;
+
+    let state = Forest::new(vec![]);
+    let certificate = state.clone().apply_events(&[], &[]);
+    let signer = state.get_signer();
+    let certificate_id = certificate.hash();
+
+    pending_store
+        .expect_get_certificate()
+        .times(2)
+        .with(eq(network), eq(height))
+        .returning(move |_, _| Ok(Some(certificate.clone())));
+
+    // Behave like the storage: the proof cached by the first certification is
+    // returned to the second one, which doesn't cache it again.
+    let cache = Arc::new(std::sync::Mutex::new(None));
+    pending_store
+        .expect_insert_certified_proof()
+        .once()
+        .with(eq(certificate_id), always(), always(), eq(true))
+        .return_once({
+            let cache = cache.clone();
+            move |_, proof, roots, _| {
+                *cache.lock().unwrap() = Some((roots.initial_roots.clone(), proof.clone()));
+                Ok(())
+            }
+        });
+    pending_store
+        .expect_insert_certified_proof()
+        .once()
+        .with(eq(certificate_id), always(), always(), eq(false))
+        .return_once(|_, _, _, _| Ok(()));
+    pending_store
+        .expect_get_submitted_proof()
+        .times(2)
+        .with(eq(certificate_id))
+        .returning(|_| Ok(None));
+    pending_store
+        .expect_get_cached_proof()
+        .times(2)
+        .with(eq(certificate_id), always())
+        .returning(move |_, initial_roots| {
+            Ok(cache
+                .lock()
+                .unwrap()
+                .as_ref()
+                .filter(|(roots, _)| roots == initial_roots)
+                .map(|(_, proof)| proof.clone()))
+        });
+    let verified = Arc::new(std::sync::Mutex::new(None));
+    pending_store
+        .expect_set_verified_proof()
+        .once()
+        .with(eq(certificate_id), always())
+        .return_once({
+            let verified = verified.clone();
+            move |_, proof_digest| {
+                *verified.lock().unwrap() = Some(*proof_digest);
+                Ok(())
+            }
+        });
+    pending_store
+        .expect_get_verified_proof()
+        .times(2)
+        .with(eq(certificate_id))
+        .returning(move |_| Ok(*verified.lock().unwrap()));
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .times(2)
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .times(2)
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .times(2)
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .times(2)
+        .returning(|| (0u32, [1u8; 32]));
+
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .times(2)
+        .returning(|_, _| Ok([0u8; 32]));
+
+    fail::cfg(
+        "notifier::certifier::certify::before_verifying_proof",
+        "return()",
+    )
+    .unwrap();
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(pending_store),
+        Arc::new(l1_rpc),
+        Arc::new(config),
+    )
+    .await
+    .unwrap();
+
+    certifier
+        .certify(local_state.clone(), network, height)
+        .await
+        .unwrap();
+
+    // The second certification is served from the cache, without caching the
+    // proof again nor verifying it again.
+    fail::cfg(
+        "notifier::certifier::certify::before_verifying_proof",
+        "panic",
+    )
+    .unwrap();
+    certifier
+        .certify(local_state.clone(), network, height)
+        .await
+        .unwrap();
+
+    scenario.teardown();
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn submitted_proof_is_used() {
+    let scenario = FailScenario::setup();
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+
+    let mut pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+    let prover_config = agglayer_prover_config::ProverConfig {
+        grpc_endpoint: next_available_addr(),
+        ..Default::default()
+    };
+
+    config.prover_entrypoint = format!(
+        "http://{}:{}",
+        prover_config.grpc_endpoint.ip(),
+        prover_config.grpc_endpoint.port()
+    );
+
+    let fake_prover = FakeProver::new(ELF).await.unwrap();
+    let cancellation = CancellationToken::new();
+    FakeProver::spawn_at(
+        fake_prover,
+        prover_config.grpc_endpoint,
+        cancellation.clone(),
+    )
+    .await
+    .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let local_state = LocalNetworkStateData::default();
+    let network: NetworkId = 1.into();
+    let height = Height::ZERO;
+
+    let state = Forest::new(vec![]);
+    let certificate = state.clone().apply_events(&[], &[]);
+    let signer = state.get_signer();
+    let certificate_id = certificate.hash();
+
+    pending_store
+        .expect_get_certificate()
+        .once()
+        .with(eq(network), eq(height))
+        .return_once({
+            let certificate = certificate.clone();
+            move |_, _| Ok(Some(certificate))
+        });
+
+    // The proof is built once the expected public values are known.
+    let submitted_proof: Arc<std::sync::Mutex<Option<Proof>>> = Default::default();
+    pending_store
+        .expect_get_submitted_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once({
+            let submitted_proof = submitted_proof.clone();
+            move |_| Ok(submitted_proof.lock().unwrap().clone())
+        });
+
+    let (proof_tx, proof_rx) = std::sync::mpsc::channel();
+    pending_store
+        .expect_insert_certified_proof()
+        .once()
+        .with(eq(certificate_id), always(), always(), eq(false))
+        .return_once(move |_, proof, _, _| {
+            proof_tx.send(proof.clone()).unwrap();
+            Ok(())
+        });
+
+    pending_store.expect_get_cached_proof().never();
+    pending_store
+        .expect_get_verified_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(None));
+    pending_store
+        .expect_set_verified_proof()
+        .once()
+        .with(eq(certificate_id), always())
+        .return_once(|_, _| Ok(()));
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .times(2)
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .times(2)
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .times(2)
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .times(2)
+        .returning(|| (0u32, [1u8; 32]));
+
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .times(2)
+        .returning(|_, _| Ok([0u8; 32]));
+
+    fail::cfg(
+        "notifier::certifier::certify::before_verifying_proof",
+        "return()",
+    )
+    .unwrap();
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(pending_store),
+        Arc::new(l1_rpc),
+        Arc::new(config),
+    )
+    .await
+    .unwrap();
+
+    let (_, _, pv_native) = certifier
+        .witness_generation(&certificate, &mut local_state.clone(), None)
+        .await
+        .unwrap();
+    let public_values = pv_native.to_public_values().unwrap();
+    let (proving_key, _) = ProverClient::builder().mock().build().setup(ELF);
+    let proof = mock::mock_proof(&proving_key, SP1PublicValues::from(&public_values));
+    *submitted_proof.lock().unwrap() = Some(Proof::SP1(proof.clone()));
+
+    certifier
+        .certify(local_state.clone(), network, height)
+        .await
+        .unwrap();
+
+    // The submitted proof is stored as the proof of the certificate.
+    let Proof::SP1(stored) = proof_rx.recv().unwrap();
+    assert_eq!(
+        bincode::default().serialize(&stored).unwrap(),
+        bincode::default().serialize(&proof).unwrap()
+    );
+
+    scenario.teardown();
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn mismatching_submitted_proof_is_discarded() {
+    let scenario = FailScenario::setup();
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+
+    let mut pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+    let prover_config = agglayer_prover_config::ProverConfig {
+        grpc_endpoint: next_available_addr(),
+        ..Default::default()
+    };
+
+    config.prover_entrypoint = format!(
+        "http://{}:{}",
+        prover_config.grpc_endpoint.ip(),
+        prover_config.grpc_endpoint.port()
+    );
+
+    let fake_prover = FakeProver::new(ELF).await.unwrap();
+    let cancellation = CancellationToken::new();
+    FakeProver::spawn_at(
+        fake_prover,
+        prover_config.grpc_endpoint,
+        cancellation.clone(),
+    )
+    .await
+    .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let local_state = LocalNetworkStateData::default();
+    let network: NetworkId = 1.into();
+    let height = Height::ZERO;
+
+    let state = Forest::new(vec![]);
+    let certificate = state.clone().apply_events(&[], &[]);
+    let signer = state.get_signer();
+    let certificate_id = certificate.hash();
+
+    pending_store
+        .expect_get_certificate()
+        .once()
+        .with(eq(network), eq(height))
+        .return_once({
+            let certificate = certificate.clone();
+            move |_, _| Ok(Some(certificate))
+        });
+
+    // The proof is built once the expected public values are known.
+    let submitted_proof: Arc<std::sync::Mutex<Option<Proof>>> = Default::default();
+    pending_store
+        .expect_get_submitted_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once({
+            let submitted_proof = submitted_proof.clone();
+            move |_| Ok(submitted_proof.lock().unwrap().clone())
+        });
+
+    // The submitted proof is discarded, and the certificate proven locally.
+    pending_store
+        .expect_remove_submitted_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(()));
+    pending_store
+        .expect_get_cached_proof()
+        .once()
+        .with(eq(certificate_id), always())
+        .return_once(|_, _| Ok(None));
+    pending_store
+        .expect_get_verified_proof()
+        .once()
+        .with(eq(certificate_id))
+        .return_once(|_| Ok(None));
+    pending_store
+        .expect_set_verified_proof()
+        .once()
+        .with(eq(certificate_id), always())
+        .return_once(|_, _| Ok(()));
+    pending_store
+        .expect_insert_certified_proof()
+        .once()
+        .with(eq(certificate_id), always(), always(), eq(true))
+        .return_once(|_, _, _, _| Ok(()));
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .times(2)
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .times(2)
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .times(2)
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .times(2)
+        .returning(|| (0u32, [1u8; 32]));
+
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .times(2)
+        .returning(|_, _| Ok([0u8; 32]));
+
+    fail::cfg(
+        "notifier::certifier::certify::before_verifying_proof",
+        "return()",
+    )
+    .unwrap();
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(pending_store),
+        Arc::new(l1_rpc),
+        Arc::new(config),
+    )
+    .await
+    .unwrap();
+
+    // The submitted proof commits to another pessimistic root.
+    let (_, _, mut pv_submitted) = certifier
+        .witness_generation(&certificate, &mut local_state.clone(), None)
+        .await
+        .unwrap();
+    pv_submitted.new_pessimistic_root = Digest([0xff; 32]);
+    let public_values = pv_submitted.to_public_values().unwrap();
+    let (proving_key, _) = ProverClient::builder().mock().build().setup(ELF);
+    let proof = mock::mock_proof(&proving_key, SP1PublicValues::from(&public_values));
+    *submitted_proof.lock().unwrap() = Some(Proof::SP1(proof));
+
+    certifier
+        .certify(local_state.clone(), network, height)
+        .await
+        .unwrap();
+
+    scenario.teardown();
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn proving_cost_is_estimated_from_the_cycles() {
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+    config.certificate_orchestrator.sp1_network_pricing = Some(Sp1NetworkPricing {
+        price_per_million_cycles: 2_000_000_000_000,
+        cycles_per_second: 1_000_000,
+        proving_overhead: Duration::from_secs(30),
+    });
+
+    // Nothing is read from nor written to the pending store.
+    let pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+    let prover_config = agglayer_prover_config::ProverConfig {
+        grpc_endpoint: next_available_addr(),
+        ..Default::default()
+    };
+
+    config.prover_entrypoint = format!(
+        "http://{}:{}",
+        prover_config.grpc_endpoint.ip(),
+        prover_config.grpc_endpoint.port()
+    );
+
+    let fake_prover = FakeProver::new(ELF).await.unwrap();
+    let endpoint = prover_config.grpc_endpoint;
+    let cancellation = CancellationToken::new();
+
+    FakeProver::spawn_at(fake_prover, endpoint, cancellation.clone())
+        .await
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let state = Forest::new(vec![]);
+    let certificate = state.clone().apply_events(&[], &[]);
+    let signer = state.get_signer();
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .once()
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .once()
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .once()
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .once()
+        .returning(|| (0u32, [1u8; 32]));
+
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .once()
+        .returning(|_, _| Ok([0u8; 32]));
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(pending_store),
+        Arc::new(l1_rpc),
+        Arc::new(config),
+    )
+    .await
+    .unwrap();
+
+    let estimate = certifier
+        .estimate_proving_cost(LocalNetworkStateData::default(), &certificate)
+        .await
+        .unwrap();
+
+    assert!(estimate.cycles > 0);
+    assert_eq!(
+        estimate.cost,
+        Some((estimate.cycles as u128 * 2_000_000_000_000).div_ceil(1_000_000))
+    );
+    assert_eq!(
+        estimate.proving_time_secs,
+        Some(estimate.cycles.div_ceil(1_000_000) + 30)
+    );
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn native_execution_reports_the_balance_changes() {
+    let base_path = TempDBDir::new();
+    let mut config = Config::new(&base_path.path);
+
+    // Nothing is read from nor written to the pending store.
+    let pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+    let prover_config = agglayer_prover_config::ProverConfig {
+        grpc_endpoint: next_available_addr(),
+        ..Default::default()
+    };
+
+    config.prover_entrypoint = format!(
+        "http://{}:{}",
+        prover_config.grpc_endpoint.ip(),
+        prover_config.grpc_endpoint.port()
+    );
+
+    let fake_prover = FakeProver::new(ELF).await.unwrap();
+    let endpoint = prover_config.grpc_endpoint;
+    let cancellation = CancellationToken::new();
+
+    FakeProver::spawn_at(fake_prover, endpoint, cancellation.clone())
+        .await
+        .unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let mut state = Forest::new([(ETH, U256::from(100u64)), (USDC, U256::from(50u64))]);
+    let initial_state = state.state_b.clone();
+    let certificate = state.apply_events(&[], &[(ETH, U256::from(30u64))]);
+    let signer = state.get_signer();
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .once()
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .once()
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .once()
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .once()
+        .returning(|| (0u32, [1u8; 32]));
+
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .once()
+        .returning(|_, _| Ok([0u8; 32]));
+
+    let certifier = CertifierClient::try_new(
+        config.prover_entrypoint.clone(),
+        Arc::new(pending_store),
+        Arc::new(l1_rpc),
+        Arc::new(config),
+    )
+    .await
+    .unwrap();
+
+    let report = certifier
+        .execute_natively(initial_state.clone(), &certificate)
+        .await
+        .unwrap();
+
+    assert_eq!(report.certificate_id, certificate.hash());
+    assert_eq!(report.initial_roots, initial_state.get_roots());
+    assert_eq!(report.new_roots.ler_leaf_count, 1);
+    assert_ne!(
+        report.new_roots.balance_root,
+        report.initial_roots.balance_root
+    );
+
+    // Only the bridged out token is reported, along with its balance delta.
+    assert_eq!(
+        report.balance_changes,
+        vec![BalanceChange {
+            token: ETH,
+            initial_balance: U256::from(100u64),
+            new_balance: U256::from(70u64),
+        }]
+    );
+}
+
+#[rstest::rstest]
+#[test_log::test(tokio::test)]
+async fn replay_is_deterministic() {
+    let base_path = TempDBDir::new();
+    let config = Config::new(&base_path.path);
+
+    // Nothing is read from nor written to the pending store, and the proofs
+    // are built without the prover service.
+    let pending_store = MockPendingStore::new();
+    let mut l1_rpc = MockL1Rpc::new();
+
+    let state = Forest::new(vec![]);
+    let certificate = state.clone().apply_events(&[], &[]);
+    let signer = state.get_signer();
+    let settlement_tx_hash = Digest([0xab; 32]);
+
+    l1_rpc
+        .expect_get_trusted_sequencer_address()
+        .times(2)
+        .returning(move |_, _| Ok(signer));
+
+    l1_rpc
+        .expect_get_rollup_contract_address()
+        .times(2)
+        .returning(|_| Ok(Address::ZERO));
+
+    l1_rpc
+        .expect_get_gas_token()
+        .times(2)
+        .returning(|_| Ok((0, Address::ZERO)));
+
+    l1_rpc
+        .expect_default_l1_info_tree_entry()
+        .times(2)
+        .returning(|| (0u32, [1u8; 32]));
+
+    // The previous pessimistic root is the one of the settlement.
+    l1_rpc
+        .expect_get_prev_pessimistic_root()
+        .with(always(), eq(Some(TxHash::from(settlement_tx_hash.0))))
+        .times(2)
+        .returning(|_, _| Ok([0u8; 32]));
+
+    let certifier =
+        CertifierClient::for_replay(Arc::new(pending_store), Arc::new(l1_rpc), Arc::new(config))
+            .await
+            .unwrap();
+
+    let replay = certifier
+        .replay(
+            &certificate,
+            LocalNetworkStateData::default(),
+            Some(settlement_tx_hash),
+        )
+        .await
+        .unwrap();
+    let again = certifier
+        .replay(
+            &certificate,
+            LocalNetworkStateData::default(),
+            Some(settlement_tx_hash),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(replay.certificate_id, certificate.hash());
+    assert_eq!(
+        replay.initial_roots,
+        LocalNetworkStateData::default().get_roots()
+    );
+    assert!(replay.cycles > 0);
+    assert_eq!(
+        serde_json::to_value(&replay).unwrap(),
+        serde_json::to_value(&again).unwrap()
+    );
+}
+
+#[test]
+fn only_the_cycles_are_estimated_without_pricing() {
+    assert_eq!(
+        estimate_from_cycles(1_500_000, None),
+        ProvingCostEstimate {
+            cycles: 1_500_000,
+            cost: None,
+            proving_time_secs: None,
+        }
+    );
+}
+
+mockall::mock! {
+    L1Rpc {}
+    #[async_trait::async_trait]
+    impl agglayer_contracts::RollupContract for L1Rpc {
+        async fn get_trusted_sequencer_address(
+            &self,
+            rollup_id: u32,
+            proof_signers: std::collections::HashMap<u32, agglayer_types::Address>,
+        ) -> Result<agglayer_types::Address, L1RpcError>;
+
+        async fn get_rollup_contract_address(&self, rollup_id: u32) -> Result<agglayer_types::Address, L1RpcError>;
+
+        async fn get_l1_info_root(&self, l1_leaf_count: u32) -> Result<[u8; 32], L1RpcError>;
+        fn default_l1_info_tree_entry(&self) -> (u32, [u8; 32]);
+        async fn get_prev_pessimistic_root(&self, rollup_id: u32, before_tx: Option<TxHash>) -> Result<[u8; 32], L1RpcError>;
+        async fn get_verifier_type(&self, rollup_id: u32) -> Result<agglayer_contracts::rollup::VerifierType, L1RpcError>;
+        async fn verify_pessimistic_proof(&self, settlement: &agglayer_contracts::PessimisticSettlement) -> Result<(), L1RpcError>;
+        fn get_rollup_manager_address(&self) -> agglayer_types::Address;
+        fn get_event_filter_block_range(&self) -> u64;
+    }
+
+    #[async_trait::async_trait]
+    impl agglayer_contracts::AggchainContract for L1Rpc {
+        async fn get_aggchain_vkey_hash(
+            &self,
+            rollup_address: agglayer_types::Address,
+            aggchain_vkey_selector: u16,
+        ) -> Result<VKeyHash, L1RpcError>;
+
+        async fn get_aggchain_hash(
+            &self,
+            rollup_address: agglayer_types::primitives::Address,
+            aggchain_data: Bytes,
+        ) -> Result<[u8; 32], L1RpcError>;
+
+        async fn get_multisig_context(
+            &self,
+            rollup_address: agglayer_types::Address,
+        ) -> Result<(Vec<agglayer_types::Address>, usize), L1RpcError>;
+
+        async fn get_gas_token(
+            &self,
+            rollup_address: agglayer_types::Address,
+        ) -> Result<(u32, agglayer_types::Address), L1RpcError>;
+    }
+
+    #[async_trait::async_trait]
+    impl agglayer_contracts::L1TransactionFetcher for L1Rpc {
+        type Provider = alloy::providers::RootProvider<Ethereum>;
+
+        async fn fetch_transaction_receipt(&self, tx_hash: FixedBytes<32>) -> Result<Option<TransactionReceipt>, L1RpcError>;
+
+        fn get_provider(&self) -> &<Self as agglayer_contracts::L1TransactionFetcher>::Provider;
+    }
+
+    #[async_trait::async_trait]
+    impl Settler for L1Rpc {
+        fn decode_contract_revert(error: &ContractError) -> Option<String>;
+
+        async fn verify_pessimistic_trusted_aggregator(
+            &self,
+            rollup_id: u32,
+            l_1_info_tree_leaf_count: u32,
+            new_local_exit_root: [u8; 32],
+            new_pessimistic_root: [u8; 32],
+            proof: Bytes,
+            custom_chain_data: Bytes,
+            nonce: Option<(u64, u128, Option<u128>)>
+        ) -> Result<alloy::providers::PendingTransactionBuilder<Ethereum>, L1RpcError>;
+
+        async fn supports_multicall(&self) -> bool;
+
+        async fn verify_pessimistic_trusted_aggregator_batch(
+            &self,
+            settlements: Vec<agglayer_contracts::PessimisticSettlement>,
+        ) -> Result<alloy::providers::PendingTransactionBuilder<Ethereum>, L1RpcError>;
     }
 }
 
@@ -0,0 +1,124 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use agglayer_prover_types::mock;
+use agglayer_telemetry::verification as metrics;
+use eyre::Context as _;
+use prover_executor::sp1_blocking;
+use sp1_sdk::{CpuProver, Prover as _, SP1ProofWithPublicValues, SP1VerifyingKey};
+use tokio::sync::Semaphore;
+
+/// Pool of workers verifying the generated proofs, off the futures certifying
+/// the certificates, so that a burst of proofs doesn't stall their
+/// certification.
+pub(crate) struct VerificationPool {
+    /// The local CPU verifier to verify the proofs.
+    verifier: Arc<CpuProver>,
+    /// The verifying key of the SP1 proof system.
+    verifying_key: SP1VerifyingKey,
+    /// Whether the proofs are checked by the mock verifier.
+    mock_verifier: bool,
+    workers: Arc<Semaphore>,
+    /// Number of proofs waiting for a worker.
+    queued: AtomicUsize,
+}
+
+impl VerificationPool {
+    pub(crate) fn new(
+        verifier: Arc<CpuProver>,
+        verifying_key: SP1VerifyingKey,
+        mock_verifier: bool,
+        workers: usize,
+    ) -> Self {
+        Self {
+            verifier,
+            verifying_key,
+            mock_verifier,
+            workers: Arc::new(Semaphore::new(workers.max(1))),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Verify the given proof on a worker, once one is available.
+    pub(crate) async fn verify(&self, proof: SP1ProofWithPublicValues) -> eyre::Result<()> {
+        let queued_at = Instant::now();
+        let permit = {
+            let _queued = Queued::new(&self.queued);
+            self.workers
+                .clone()
+                .acquire_owned()
+                .await
+                .context("Verification worker pool is closed")?
+        };
+        let wait = queued_at.elapsed();
+
+        let started_at = Instant::now();
+        let result = sp1_blocking({
+            let verifier = self.verifier.clone();
+            let verifying_key = self.verifying_key.clone();
+            let mock_verifier = self.mock_verifier;
+            move || {
+                let _permit = permit;
+                verify_proof(&verifier, &verifying_key, &proof, mock_verifier)
+            }
+        })
+        .await
+        .context("Verification worker failed")?;
+
+        metrics::record_verification(wait, started_at.elapsed(), result.is_ok());
+
+        result
+    }
+}
+
+fn verify_proof(
+    verifier: &CpuProver,
+    verifying_key: &SP1VerifyingKey,
+    proof: &SP1ProofWithPublicValues,
+    mock_verifier: bool,
+) -> eyre::Result<()> {
+    // This fail_point is use to make the verification pass or fail
+    fail::fail_point!(
+        "notifier::certifier::certify::before_verifying_proof",
+        |_| {
+            let verifier = sp1_sdk::ProverClient::builder().mock().build();
+
+            verifier.verify(proof, verifying_key)?;
+            Ok(mock::verify_mock_proof(proof, verifying_key)?)
+        }
+    );
+
+    verifier.verify(proof, verifying_key)?;
+
+    // The mock verifier only checks the public values, make sure that the
+    // proof is the one derived from them.
+    if mock_verifier {
+        mock::verify_mock_proof(proof, verifying_key)?;
+    }
+
+    Ok(())
+}
+
+/// Proof waiting for a worker, released on drop so that cancelled
+/// verifications don't leak it.
+struct Queued<'a>(&'a AtomicUsize);
+
+impl<'a> Queued<'a> {
+    fn new(queued: &'a AtomicUsize) -> Self {
+        let previous = queued.fetch_add(1, Ordering::SeqCst);
+        metrics::record_queued(previous + 1);
+        Self(queued)
+    }
+}
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        let previous = self.0.fetch_sub(1, Ordering::SeqCst);
+        metrics::record_queued(previous - 1);
+    }
+}
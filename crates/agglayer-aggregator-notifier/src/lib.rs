@@ -1,8 +1,11 @@
-/// ELF of the pessimistic proof program
-const ELF: &[u8] = pessimistic_proof::ELF;
-
 mod certifier;
 mod settlement_client;
 
-pub use certifier::CertifierClient;
-pub use settlement_client::RpcSettlementClient;
+pub use certifier::{CertificationReplay, CertifierClient, RemoteProver};
+#[cfg(any(test, feature = "testutils"))]
+pub use settlement_client::MockSettlementAdapter;
+pub use settlement_client::{
+    AggregatedProofs, EpochPackingStrategy, L1SettlementAdapter, MulticallBatch,
+    RpcSettlementClient, SettleIndividually, SettlementAdapter, SettlementAdapterError,
+    SettlementEventWatcher,
+};
@@ -0,0 +1,255 @@
+//! Targets of the settlement transactions.
+//!
+//! The settlement client prepares the settlement of the certificates, and
+//! hands them over to a [`SettlementAdapter`] which submits them to the
+//! settlement layer. Deployments targeting another rollup manager version or
+//! another settlement layer only have to provide their own adapter.
+
+use std::sync::Arc;
+
+use agglayer_certificate_orchestrator::NonceInfo;
+use agglayer_contracts::{L1RpcError, PessimisticSettlement, Settler};
+use agglayer_types::SettlementTxHash;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SettlementAdapterError {
+    #[error("Failed to submit the settlement transaction: {error}")]
+    Submission {
+        error: String,
+        /// Revert reason of the settlement, when it could be decoded.
+        reason: Option<String>,
+    },
+
+    #[error("Batched settlements are not supported by the settlement target")]
+    BatchUnsupported,
+
+    #[error("Aggregated settlements are not supported by the settlement target")]
+    AggregationUnsupported,
+}
+
+/// Submission of the settlement transactions to a settlement layer.
+#[async_trait::async_trait]
+pub trait SettlementAdapter: Send + Sync {
+    /// Submit the settlement of one certificate, returning the hash of the
+    /// transaction.
+    ///
+    /// When `nonce_info` is provided, the transaction replaces the pending one
+    /// sent with the same nonce.
+    async fn settle(
+        &self,
+        settlement: PessimisticSettlement,
+        nonce_info: Option<NonceInfo>,
+    ) -> Result<SettlementTxHash, SettlementAdapterError>;
+
+    /// Whether several settlements can be submitted in a single transaction.
+    async fn supports_batch_settlement(&self) -> bool;
+
+    /// Submit several settlements in a single transaction, which settles all
+    /// of them or none.
+    async fn settle_batch(
+        &self,
+        settlements: Vec<PessimisticSettlement>,
+    ) -> Result<SettlementTxHash, SettlementAdapterError>;
+
+    /// Whether several settlements can be submitted with a single aggregated
+    /// proof.
+    async fn supports_aggregated_settlement(&self) -> bool {
+        false
+    }
+
+    /// Aggregate the proofs of several settlements and submit them in a single
+    /// transaction, which settles all of them or none.
+    async fn settle_aggregated(
+        &self,
+        _settlements: Vec<PessimisticSettlement>,
+    ) -> Result<SettlementTxHash, SettlementAdapterError> {
+        Err(SettlementAdapterError::AggregationUnsupported)
+    }
+}
+
+/// Settlement on L1 through the `verifyPessimisticTrustedAggregator` function
+/// of the rollup manager.
+///
+/// The rollup manager has no entry point for aggregated proofs, so the
+/// settlements can be batched but not aggregated.
+pub struct L1SettlementAdapter<L1Rpc> {
+    l1_rpc: Arc<L1Rpc>,
+}
+
+impl<L1Rpc> L1SettlementAdapter<L1Rpc> {
+    pub fn new(l1_rpc: Arc<L1Rpc>) -> Self {
+        Self { l1_rpc }
+    }
+}
+
+impl<L1Rpc: Settler> L1SettlementAdapter<L1Rpc> {
+    fn submission_error(error: L1RpcError) -> SettlementAdapterError {
+        match error {
+            L1RpcError::SettlementSubmissionFailed(error) => SettlementAdapterError::Submission {
+                reason: L1Rpc::decode_contract_revert(&error),
+                error: error.to_string(),
+            },
+            error => SettlementAdapterError::Submission {
+                reason: None,
+                error: error.to_string(),
+            },
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<L1Rpc> SettlementAdapter for L1SettlementAdapter<L1Rpc>
+where
+    L1Rpc: Settler + Send + Sync + 'static,
+{
+    async fn settle(
+        &self,
+        settlement: PessimisticSettlement,
+        nonce_info: Option<NonceInfo>,
+    ) -> Result<SettlementTxHash, SettlementAdapterError> {
+        let pending_tx = self
+            .l1_rpc
+            .verify_pessimistic_trusted_aggregator(
+                settlement.rollup_id,
+                settlement.l_1_info_tree_leaf_count,
+                settlement.new_local_exit_root,
+                settlement.new_pessimistic_root,
+                settlement.proof,
+                settlement.custom_chain_data,
+                nonce_info.map(|n| {
+                    (
+                        n.nonce,
+                        n.previous_max_fee_per_gas,
+                        n.previous_max_priority_fee_per_gas,
+                    )
+                }),
+            )
+            .await
+            .map_err(Self::submission_error)?;
+
+        Ok(SettlementTxHash::from(*pending_tx.tx_hash()))
+    }
+
+    async fn supports_batch_settlement(&self) -> bool {
+        self.l1_rpc.supports_multicall().await
+    }
+
+    async fn settle_batch(
+        &self,
+        settlements: Vec<PessimisticSettlement>,
+    ) -> Result<SettlementTxHash, SettlementAdapterError> {
+        let pending_tx = self
+            .l1_rpc
+            .verify_pessimistic_trusted_aggregator_batch(settlements)
+            .await
+            .map_err(Self::submission_error)?;
+
+        Ok(SettlementTxHash::from(*pending_tx.tx_hash()))
+    }
+}
+
+#[cfg(any(test, feature = "testutils"))]
+pub use mock::MockSettlementAdapter;
+
+#[cfg(any(test, feature = "testutils"))]
+mod mock {
+    use std::sync::Mutex;
+
+    use agglayer_primitives::keccak::keccak256_combine;
+
+    use super::*;
+
+    /// Settlement target recording the submitted settlements instead of
+    /// sending them anywhere.
+    ///
+    /// The hash of each transaction is derived from the settlements it
+    /// includes, so that the same submission always results in the same hash.
+    #[derive(Default)]
+    pub struct MockSettlementAdapter {
+        supports_batch_settlement: bool,
+        supports_aggregated_settlement: bool,
+        transactions: Mutex<Vec<Vec<PessimisticSettlement>>>,
+    }
+
+    impl MockSettlementAdapter {
+        pub fn new(supports_batch_settlement: bool) -> Self {
+            Self {
+                supports_batch_settlement,
+                supports_aggregated_settlement: false,
+                transactions: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Accept the aggregated settlements as well.
+        pub fn with_aggregated_settlement(mut self) -> Self {
+            self.supports_aggregated_settlement = true;
+            self
+        }
+
+        /// The settlements submitted so far, grouped by transaction.
+        pub fn transactions(&self) -> Vec<Vec<PessimisticSettlement>> {
+            self.transactions
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone()
+        }
+
+        fn record(&self, settlements: Vec<PessimisticSettlement>) -> SettlementTxHash {
+            let tx_hash = keccak256_combine(settlements.iter().flat_map(|settlement| {
+                [
+                    settlement.rollup_id.to_be_bytes().to_vec(),
+                    settlement.new_local_exit_root.to_vec(),
+                    settlement.new_pessimistic_root.to_vec(),
+                ]
+            }));
+
+            self.transactions
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .push(settlements);
+
+            SettlementTxHash::new(tx_hash)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl SettlementAdapter for MockSettlementAdapter {
+        async fn settle(
+            &self,
+            settlement: PessimisticSettlement,
+            _nonce_info: Option<NonceInfo>,
+        ) -> Result<SettlementTxHash, SettlementAdapterError> {
+            Ok(self.record(vec![settlement]))
+        }
+
+        async fn supports_batch_settlement(&self) -> bool {
+            self.supports_batch_settlement
+        }
+
+        async fn settle_batch(
+            &self,
+            settlements: Vec<PessimisticSettlement>,
+        ) -> Result<SettlementTxHash, SettlementAdapterError> {
+            if !self.supports_batch_settlement {
+                return Err(SettlementAdapterError::BatchUnsupported);
+            }
+
+            Ok(self.record(settlements))
+        }
+
+        async fn supports_aggregated_settlement(&self) -> bool {
+            self.supports_aggregated_settlement
+        }
+
+        async fn settle_aggregated(
+            &self,
+            settlements: Vec<PessimisticSettlement>,
+        ) -> Result<SettlementTxHash, SettlementAdapterError> {
+            if !self.supports_aggregated_settlement {
+                return Err(SettlementAdapterError::AggregationUnsupported);
+            }
+
+            Ok(self.record(settlements))
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Batching of the settlements of the same epoch in a single transaction.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use agglayer_contracts::PessimisticSettlement;
+use agglayer_types::{EpochNumber, SettlementTxHash};
+use tokio::sync::{oneshot, Mutex, OnceCell};
+use tracing::{debug, info, warn};
+
+use super::adapter::{SettlementAdapter, SettlementAdapterError};
+
+/// Settlement waiting for the batch of its epoch to be submitted.
+struct PendingSettlement {
+    settlement: PessimisticSettlement,
+    /// Receives the hash of the batch transaction. Dropped without sending
+    /// when the settlement has to be submitted on its own.
+    tx_hash: oneshot::Sender<SettlementTxHash>,
+}
+
+/// How the settlements of a batch are submitted together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BatchKind {
+    /// In a single multicall transaction, each with its own proof.
+    Multicall,
+    /// With a single proof aggregating the ones of the settlements.
+    Aggregated,
+}
+
+impl BatchKind {
+    async fn is_supported(self, adapter: &dyn SettlementAdapter) -> bool {
+        match self {
+            BatchKind::Multicall => adapter.supports_batch_settlement().await,
+            BatchKind::Aggregated => adapter.supports_aggregated_settlement().await,
+        }
+    }
+
+    async fn submit(
+        self,
+        adapter: &dyn SettlementAdapter,
+        settlements: Vec<PessimisticSettlement>,
+    ) -> Result<SettlementTxHash, SettlementAdapterError> {
+        match self {
+            BatchKind::Multicall => adapter.settle_batch(settlements).await,
+            BatchKind::Aggregated => adapter.settle_aggregated(settlements).await,
+        }
+    }
+}
+
+/// Collects the settlements of the same epoch during a window to submit them
+/// in a single transaction, sharing its fixed gas overhead.
+///
+/// The first settlement of an epoch opens a batch which is submitted once the
+/// window elapsed. Settlements are left to be submitted individually when the
+/// batch ends up with a single settlement, when the settlement target does not
+/// support batches, or when the submission of the batch fails.
+pub(crate) struct SettlementBatcher {
+    window: Duration,
+    kind: BatchKind,
+    pending: Mutex<BTreeMap<EpochNumber, Vec<PendingSettlement>>>,
+    batch_supported: OnceCell<bool>,
+}
+
+impl SettlementBatcher {
+    pub(crate) fn new(window: Duration, kind: BatchKind) -> Self {
+        Self {
+            window,
+            kind,
+            pending: Mutex::new(BTreeMap::new()),
+            batch_supported: OnceCell::new(),
+        }
+    }
+
+    /// Add the settlement to the batch of the epoch and wait for its
+    /// submission, returning the hash of the batch transaction, or `None` if
+    /// the settlement has to be submitted individually.
+    pub(crate) async fn submit(
+        self: &Arc<Self>,
+        adapter: Arc<dyn SettlementAdapter>,
+        epoch_number: EpochNumber,
+        settlement: PessimisticSettlement,
+    ) -> Option<SettlementTxHash> {
+        let (tx_hash, receiver) = oneshot::channel();
+        let settlement = PendingSettlement {
+            settlement,
+            tx_hash,
+        };
+
+        {
+            let mut pending = self.pending.lock().await;
+            if let Some(batch) = pending.get_mut(&epoch_number) {
+                batch.push(settlement);
+            } else {
+                pending.insert(epoch_number, vec![settlement]);
+
+                // The batch is submitted from its own task, so that it does not
+                // depend on the settlement which opened it.
+                tokio::spawn(Arc::clone(self).submit_batch(adapter, epoch_number));
+            }
+        }
+
+        receiver.await.ok()
+    }
+
+    async fn submit_batch(
+        self: Arc<Self>,
+        adapter: Arc<dyn SettlementAdapter>,
+        epoch_number: EpochNumber,
+    ) {
+        tokio::time::sleep(self.window).await;
+
+        let batch = self
+            .pending
+            .lock()
+            .await
+            .remove(&epoch_number)
+            .unwrap_or_default();
+
+        if batch.len() < 2 {
+            debug!(%epoch_number, "No settlement to batch with, settling individually");
+            return;
+        }
+
+        let batch_supported = *self
+            .batch_supported
+            .get_or_init(|| self.kind.is_supported(adapter.as_ref()))
+            .await;
+        if !batch_supported {
+            warn!(
+                %epoch_number,
+                kind = ?self.kind,
+                "The settlement target does not support batches, settling individually"
+            );
+            return;
+        }
+
+        let (settlements, senders): (Vec<_>, Vec<_>) = batch
+            .into_iter()
+            .map(|pending| (pending.settlement, pending.tx_hash))
+            .unzip();
+        let count = settlements.len();
+
+        match self.kind.submit(adapter.as_ref(), settlements).await {
+            Ok(tx_hash) => {
+                info!(
+                    %epoch_number,
+                    %tx_hash,
+                    count,
+                    kind = ?self.kind,
+                    "Submitted the batched settlement transaction"
+                );
+
+                for sender in senders {
+                    _ = sender.send(tx_hash);
+                }
+            }
+            Err(error) => {
+                warn!(
+                    %epoch_number,
+                    %error,
+                    count,
+                    kind = ?self.kind,
+                    "Failed to submit the batched settlement transaction, settling individually"
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,16 @@
+mod adapter;
+mod batch;
+mod packing;
+mod receipt_polling;
 mod rpc;
+mod watcher;
 
+#[cfg(any(test, feature = "testutils"))]
+pub use adapter::MockSettlementAdapter;
+pub use adapter::{L1SettlementAdapter, SettlementAdapter, SettlementAdapterError};
+pub use packing::{AggregatedProofs, EpochPackingStrategy, MulticallBatch, SettleIndividually};
 pub use rpc::RpcSettlementClient;
+pub use watcher::SettlementEventWatcher;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,105 @@
+//! Strategies packing the settlements of the certificates of an epoch into
+//! settlement transactions.
+
+use std::{sync::Arc, time::Duration};
+
+use agglayer_config::outbound::{EpochPacking, OutboundRpcSettleConfig};
+use agglayer_contracts::PessimisticSettlement;
+use agglayer_types::{EpochNumber, SettlementTxHash};
+
+use super::{
+    adapter::SettlementAdapter,
+    batch::{BatchKind, SettlementBatcher},
+};
+
+/// Strategy deciding how the settlements of the certificates of an epoch are
+/// submitted, so that the settlement costs can be tuned per deployment.
+#[async_trait::async_trait]
+pub trait EpochPackingStrategy: Send + Sync {
+    /// Pack the settlement with the other ones of the epoch, returning the
+    /// hash of the transaction including it, or `None` if the settlement has
+    /// to be submitted on its own.
+    async fn pack(
+        &self,
+        adapter: Arc<dyn SettlementAdapter>,
+        epoch_number: EpochNumber,
+        settlement: PessimisticSettlement,
+    ) -> Option<SettlementTxHash>;
+}
+
+/// Build the strategy selected by the configuration.
+pub(crate) fn from_config(config: &OutboundRpcSettleConfig) -> Arc<dyn EpochPackingStrategy> {
+    match config.epoch_packing_strategy() {
+        (EpochPacking::Individual, _) => Arc::new(SettleIndividually),
+        (EpochPacking::Aggregated, window) => Arc::new(AggregatedProofs::new(window)),
+        (EpochPacking::MulticallBatch, window) => Arc::new(MulticallBatch::new(window)),
+    }
+}
+
+/// Settles each certificate in its own transaction.
+pub struct SettleIndividually;
+
+#[async_trait::async_trait]
+impl EpochPackingStrategy for SettleIndividually {
+    async fn pack(
+        &self,
+        _adapter: Arc<dyn SettlementAdapter>,
+        _epoch_number: EpochNumber,
+        _settlement: PessimisticSettlement,
+    ) -> Option<SettlementTxHash> {
+        None
+    }
+}
+
+/// Settles the certificates of an epoch in a single multicall transaction,
+/// each with its own proof.
+pub struct MulticallBatch(Arc<SettlementBatcher>);
+
+impl MulticallBatch {
+    pub fn new(window: Duration) -> Self {
+        Self(Arc::new(SettlementBatcher::new(
+            window,
+            BatchKind::Multicall,
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl EpochPackingStrategy for MulticallBatch {
+    async fn pack(
+        &self,
+        adapter: Arc<dyn SettlementAdapter>,
+        epoch_number: EpochNumber,
+        settlement: PessimisticSettlement,
+    ) -> Option<SettlementTxHash> {
+        self.0.submit(adapter, epoch_number, settlement).await
+    }
+}
+
+/// Settles the certificates of an epoch in a single transaction, with a proof
+/// aggregating their proofs.
+///
+/// The aggregation is left to the [`SettlementAdapter`], the certificates
+/// being settled individually when it does not support it.
+pub struct AggregatedProofs(Arc<SettlementBatcher>);
+
+impl AggregatedProofs {
+    pub fn new(window: Duration) -> Self {
+        Self(Arc::new(SettlementBatcher::new(
+            window,
+            BatchKind::Aggregated,
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl EpochPackingStrategy for AggregatedProofs {
+    async fn pack(
+        &self,
+        adapter: Arc<dyn SettlementAdapter>,
+        epoch_number: EpochNumber,
+        settlement: PessimisticSettlement,
+    ) -> Option<SettlementTxHash> {
+        self.0.submit(adapter, epoch_number, settlement).await
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use agglayer_config::outbound::{OutboundRpcSettleConfig, ReceiptPollingStrategy};
+use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
+    providers::Provider,
+};
+use tracing::warn;
+
+/// Number of blocks over which the L1 block time is estimated.
+const BLOCK_TIME_SAMPLE: u64 = 10;
+
+/// Spacing of the polls of a transaction receipt, according to the
+/// configured strategy.
+pub(super) struct ReceiptPoller {
+    strategy: ReceiptPollingStrategy,
+    retry_interval: Duration,
+    max_interval: Duration,
+    jitter_percent: u32,
+    /// Estimated L1 block time, for the adaptive strategy.
+    block_time: Option<Duration>,
+    /// Number of polls already spaced.
+    polls: u32,
+}
+
+impl ReceiptPoller {
+    pub(super) fn new(config: &OutboundRpcSettleConfig, block_time: Option<Duration>) -> Self {
+        Self {
+            strategy: config.receipt_polling.strategy,
+            retry_interval: config.retry_interval,
+            max_interval: config.receipt_polling.max_interval,
+            jitter_percent: config.receipt_polling.jitter_percent,
+            block_time,
+            polls: 0,
+        }
+    }
+
+    /// Interval before the next poll, while the transaction is missing the
+    /// given number of confirmations, 1 if it isn't mined yet.
+    pub(super) fn next_interval(&mut self, missing_confirmations: u64) -> Duration {
+        let interval = match self.strategy {
+            ReceiptPollingStrategy::Fixed => return self.retry_interval,
+            ReceiptPollingStrategy::Exponential => self
+                .retry_interval
+                .saturating_mul(2u32.saturating_pow(self.polls)),
+            ReceiptPollingStrategy::Adaptive => self
+                .block_time
+                .unwrap_or(self.retry_interval)
+                .saturating_mul(u32::try_from(missing_confirmations.max(1)).unwrap_or(u32::MAX)),
+        }
+        .min(self.max_interval);
+        self.polls = self.polls.saturating_add(1);
+
+        interval + interval.mul_f64(rand::random::<f64>() * self.jitter_percent as f64 / 100.0)
+    }
+}
+
+/// Estimate the L1 block time from the timestamps of the latest blocks.
+pub(super) async fn estimate_block_time(provider: &impl Provider) -> Option<Duration> {
+    let latest = provider
+        .get_block(BlockId::Number(BlockNumberOrTag::Latest))
+        .await
+        .inspect_err(|error| warn!(?error, "Failed to fetch the latest L1 block"))
+        .ok()??;
+    let sample = BLOCK_TIME_SAMPLE.min(latest.header.number);
+    if sample == 0 {
+        return None;
+    }
+
+    let first = provider
+        .get_block(BlockId::Number((latest.header.number - sample).into()))
+        .await
+        .inspect_err(|error| warn!(?error, "Failed to fetch the L1 block"))
+        .ok()??;
+
+    let elapsed = latest
+        .header
+        .timestamp
+        .checked_sub(first.header.timestamp)?;
+    Some(Duration::from_secs(elapsed).div_f64(sample as f64))
+        .filter(|block_time| !block_time.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_config::outbound::ReceiptPollingConfig;
+
+    use super::*;
+
+    fn poller(strategy: ReceiptPollingStrategy, block_time: Option<Duration>) -> ReceiptPoller {
+        let config = OutboundRpcSettleConfig {
+            retry_interval: Duration::from_secs(2),
+            receipt_polling: ReceiptPollingConfig {
+                strategy,
+                max_interval: Duration::from_secs(30),
+                jitter_percent: 0,
+            },
+            ..Default::default()
+        };
+
+        ReceiptPoller::new(&config, block_time)
+    }
+
+    #[test]
+    fn exponential_polling_is_capped() {
+        let mut poller = poller(ReceiptPollingStrategy::Exponential, None);
+
+        let intervals: Vec<_> = (0..6).map(|_| poller.next_interval(1).as_secs()).collect();
+        assert_eq!(intervals, [2, 4, 8, 16, 30, 30]);
+    }
+
+    #[test]
+    fn adaptive_polling_waits_for_the_missing_confirmations() {
+        let mut poller = poller(
+            ReceiptPollingStrategy::Adaptive,
+            Some(Duration::from_secs(12)),
+        );
+
+        assert_eq!(poller.next_interval(1), Duration::from_secs(12));
+        assert_eq!(poller.next_interval(2), Duration::from_secs(24));
+        assert_eq!(poller.next_interval(5), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn jitter_is_bounded() {
+        let mut poller = poller(ReceiptPollingStrategy::Exponential, None);
+        poller.jitter_percent = 50;
+
+        let interval = poller.next_interval(1);
+        assert!(interval >= Duration::from_secs(2) && interval <= Duration::from_secs(3));
+    }
+}
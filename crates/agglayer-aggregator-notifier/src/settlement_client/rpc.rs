@@ -1,38 +1,78 @@
 use std::{sync::Arc, time::Duration};
 
 use agglayer_certificate_orchestrator::{Error, NonceInfo, SettlementClient, TxReceiptStatus};
-use agglayer_config::outbound::OutboundRpcSettleConfig;
-use agglayer_contracts::{rollup::VerifierType, L1TransactionFetcher, RollupContract, Settler};
-use agglayer_storage::stores::{
-    PendingCertificateReader, PerEpochReader, PerEpochWriter, StateReader, StateWriter,
+use agglayer_config::outbound::{EpochPacking, OutboundRpcSettleConfig, ReceiptPollingStrategy};
+use agglayer_contracts::{
+    rollup::VerifierType, L1RpcError, L1TransactionFetcher, PessimisticSettlement, RollupContract,
+    Settler,
+};
+use agglayer_storage::{
+    columns::settlement_attempts_per_certificate::SettlementAttempt,
+    stores::{PendingCertificateReader, PerEpochReader, PerEpochWriter, StateReader, StateWriter},
 };
 use agglayer_types::{
     CertificateHeader, CertificateId, CertificateIndex, CertificateStatus, Digest, EpochNumber,
-    ExecutionMode, Proof, SettlementTxHash, U256,
+    ExecutionMode, NetworkId, Proof, SettlementTxHash, U256,
 };
 use alloy::{
-    eips::BlockNumberOrTag, providers::Provider, rpc::types::TransactionReceipt,
+    eips::BlockNumberOrTag, primitives::Address, providers::Provider,
+    rpc::types::TransactionReceipt,
     signers::k256::elliptic_curve::ff::derive::bitvec::macros::internal::funty::Fundamental,
 };
 use arc_swap::ArcSwap;
 use pessimistic_proof::{proof::DisplayToHex, PessimisticProofOutput};
 use tracing::{debug, error, info, instrument, warn};
 
+use super::{
+    adapter::{L1SettlementAdapter, SettlementAdapter, SettlementAdapterError},
+    packing::{self, EpochPackingStrategy},
+    receipt_polling::{estimate_block_time, ReceiptPoller},
+    watcher::{ObservedVerifications, SettlementEventWatcher},
+};
+
 const MAX_EPOCH_ASSIGNMENT_RETRIES: usize = 5;
 
+const WEI_PER_GWEI: u64 = 1_000_000_000;
+
 /// Rpc-based settlement client for L1 certificate settlement.
 /// Using alloy client to interact with the L1 rollup manager contract.
-#[derive(Default, Clone)]
+///
+/// The settlement transactions are submitted through a [`SettlementAdapter`],
+/// the L1 rollup manager one by default.
+#[derive(Clone)]
 pub struct RpcSettlementClient<StateStore, PendingStore, PerEpochStore, RollupManagerRpc> {
     state_store: Arc<StateStore>,
     pending_store: Arc<PendingStore>,
     config: Arc<OutboundRpcSettleConfig>,
     l1_rpc: Arc<RollupManagerRpc>,
     current_epoch: Arc<ArcSwap<PerEpochStore>>,
+    /// Address of the account sending the settlement transactions.
+    settlement_address: Address,
+    /// Strategy packing the settlements of the same epoch.
+    packing_strategy: Arc<dyn EpochPackingStrategy>,
+    /// Target of the settlement transactions.
+    adapter: Arc<dyn SettlementAdapter>,
+    /// Verifications observed on L1 by the [`SettlementEventWatcher`].
+    observed_verifications: Arc<ObservedVerifications>,
+    /// Settlement key taking over from the current one at an epoch boundary.
+    key_rotation: Option<KeyRotation>,
+}
+
+/// Settlement key activated at the start of an epoch, the settlements of the
+/// previous epochs being still sent, and replaced, with the previous key.
+#[derive(Clone)]
+struct KeyRotation {
+    activation_epoch: EpochNumber,
+    /// Address of the account of the new key.
+    settlement_address: Address,
+    /// Target of the settlement transactions signed with the new key.
+    adapter: Arc<dyn SettlementAdapter>,
 }
 
 impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
     RpcSettlementClient<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
+where
+    RollupManagerRpc: Settler + Send + Sync + 'static,
 {
     /// Try to create a new rpc-based settlement client
     pub fn new(
@@ -41,23 +81,97 @@ impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
         pending_store: Arc<PendingStore>,
         l1_rpc: Arc<RollupManagerRpc>,
         current_epoch: Arc<ArcSwap<PerEpochStore>>,
+        settlement_address: Address,
     ) -> Self {
+        let packing_strategy = packing::from_config(&config);
+
         Self {
             config,
+            adapter: Arc::new(L1SettlementAdapter::new(l1_rpc.clone())),
             l1_rpc,
             state_store,
             pending_store,
             current_epoch,
+            settlement_address,
+            packing_strategy,
+            observed_verifications: Arc::new(ObservedVerifications::default()),
+            key_rotation: None,
+        }
+    }
+
+    /// Watcher of the verification events on L1, feeding the verifications
+    /// it observes to this client. It has to be run for the certificates
+    /// settled through another transaction to be recognized.
+    pub fn event_watcher(&self) -> SettlementEventWatcher<RollupManagerRpc> {
+        SettlementEventWatcher::new(
+            self.l1_rpc.clone(),
+            self.observed_verifications.clone(),
+            std::iter::once(self.settlement_address)
+                .chain(self.key_rotation.as_ref().map(|key| key.settlement_address))
+                .collect(),
+            self.config.event_poll_interval,
+        )
+    }
+
+    /// Submit the settlement transactions through the given adapter instead
+    /// of the L1 rollup manager.
+    pub fn with_settlement_adapter(mut self, adapter: Arc<dyn SettlementAdapter>) -> Self {
+        self.adapter = adapter;
+        self
+    }
+
+    /// Pack the settlements of the same epoch with the given strategy instead
+    /// of the configured one.
+    pub fn with_epoch_packing_strategy(
+        mut self,
+        packing_strategy: Arc<dyn EpochPackingStrategy>,
+    ) -> Self {
+        self.packing_strategy = packing_strategy;
+        self
+    }
+
+    /// Sign the settlements of the certificates from `activation_epoch` on
+    /// with another key, whose transactions are submitted through the given
+    /// adapter.
+    pub fn with_key_rotation(
+        mut self,
+        activation_epoch: EpochNumber,
+        settlement_address: Address,
+        adapter: Arc<dyn SettlementAdapter>,
+    ) -> Self {
+        self.key_rotation = Some(KeyRotation {
+            activation_epoch,
+            settlement_address,
+            adapter,
+        });
+        self
+    }
+
+    /// Check that the settlement adapters support the configured epoch
+    /// packing, which would otherwise settle every certificate individually.
+    pub async fn check_epoch_packing(&self) -> Result<(), SettlementAdapterError> {
+        if self.config.epoch_packing_strategy().0 != EpochPacking::Aggregated {
+            return Ok(());
         }
+
+        let adapters = std::iter::once(&self.adapter)
+            .chain(self.key_rotation.as_ref().map(|key| &key.adapter));
+        for adapter in adapters {
+            if !adapter.supports_aggregated_settlement().await {
+                return Err(SettlementAdapterError::AggregationUnsupported);
+            }
+        }
+
+        Ok(())
     }
 }
 
 impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
     RpcSettlementClient<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
 where
-    StateStore: StateReader,
+    StateStore: StateReader + StateWriter,
     PendingStore: PendingCertificateReader,
-    RollupManagerRpc: RollupContract + Settler,
+    RollupManagerRpc: RollupContract + L1TransactionFetcher,
     PerEpochStore: PerEpochWriter,
 {
     #[instrument(skip(self), fields(network_id, settlement_params), level = "debug")]
@@ -87,21 +201,23 @@ where
 
         // Step 2: Validate epoch assignment
         let dry_current_epoch = self.current_epoch.load();
-        match dry_current_epoch.add_certificate(certificate_id, ExecutionMode::DryRun) {
-            Err(error) => {
-                drop(dry_current_epoch);
-                error!(
-                    %error,
-                    "{}Failed to add the certificate to the current epoch",
-                    ExecutionMode::DryRun.prefix(),
-                );
-                return Err(Error::Storage(error));
-            }
-            Ok((_epoch_number, _certificate_index)) => {
-                drop(dry_current_epoch);
-                info!("Certificate passes the epoch dry run");
-            }
-        }
+        let epoch_number =
+            match dry_current_epoch.add_certificate(certificate_id, ExecutionMode::DryRun) {
+                Err(error) => {
+                    drop(dry_current_epoch);
+                    error!(
+                        %error,
+                        "{}Failed to add the certificate to the current epoch",
+                        ExecutionMode::DryRun.prefix(),
+                    );
+                    return Err(Error::Storage(error));
+                }
+                Ok((epoch_number, _certificate_index)) => {
+                    drop(dry_current_epoch);
+                    info!("Certificate passes the epoch dry run");
+                    epoch_number
+                }
+            };
 
         // Step 3: Get certificate from pending store
         let certificate =
@@ -123,8 +239,8 @@ where
         // Step 4: Deserialize and prepare the proof
         let (output, proof) =
             if let Some(Proof::SP1(proof)) = self.pending_store.get_proof(certificate_id)? {
-                if let Ok(output) = PessimisticProofOutput::bincode_codec()
-                    .deserialize::<PessimisticProofOutput>(proof.public_values.as_slice())
+                if let Ok(output) =
+                    PessimisticProofOutput::from_public_values(proof.public_values.as_slice())
                 {
                     (output, proof.bytes())
                 } else {
@@ -183,57 +299,303 @@ where
         );
         tracing::Span::current().record("settlement_params", &settlement_params);
 
-        // Step 6: Call the contract settlement function and get the pending transaction
-        let pending_tx = match self
-            .l1_rpc
-            .verify_pessimistic_trusted_aggregator(
-                output.origin_network.to_u32(),
-                l1_info_tree_leaf_count,
-                *output.new_local_exit_root.as_ref(),
-                *output.new_pessimistic_root,
-                proof_with_selector.into(),
-                certificate.custom_chain_data.into(),
-                nonce_info.map(|n| {
-                    (
-                        n.nonce,
-                        n.previous_max_fee_per_gas,
-                        n.previous_max_priority_fee_per_gas,
-                    )
-                }),
-            )
-            .await
+        let settlement = PessimisticSettlement {
+            rollup_id: output.origin_network.to_u32(),
+            l_1_info_tree_leaf_count: l1_info_tree_leaf_count,
+            new_local_exit_root: *output.new_local_exit_root.as_ref(),
+            new_pessimistic_root: *output.new_pessimistic_root,
+            proof: proof_with_selector.into(),
+            custom_chain_data: certificate.custom_chain_data.into(),
+        };
+
+        // Step 6: Check the proof against the verifier configured on L1
+        if self.config.verify_proof_before_settlement {
+            self.verify_proof_on_l1(certificate_id, &settlement).await?;
+        }
+
+        // Step 7: Check that the maximum number of attempts is not reached
+        let attempts = self.state_store.get_settlement_attempts(&certificate_id)?;
+        let attempts = u32::try_from(attempts.len()).unwrap_or(u32::MAX);
+        if attempts >= self.config.max_settlement_attempts {
+            error!(
+                attempts,
+                "Maximum number of settlement attempts reached for the certificate"
+            );
+            return Err(Error::SettlementTimeout {
+                certificate_id,
+                attempts,
+            });
+        }
+
+        // Step 8: Select the settlement key and check that its account can pay for the
+        // transaction
+        let (settlement_address, adapter) =
+            self.settlement_key(certificate_id, epoch_number, nonce_info.is_some());
+        self.check_settlement_funds(certificate_id, settlement_address)
+            .await?;
+
+        // Step 9: Pack the settlement with the other ones of the epoch. Replacements of
+        // pending transactions are always submitted individually.
+        let batched_tx_hash = match nonce_info {
+            None => {
+                self.packing_strategy
+                    .pack(adapter.clone(), epoch_number, settlement.clone())
+                    .await
+            }
+            Some(_) => None,
+        };
+
+        let settlement_tx_hash = if let Some(settlement_tx_hash) = batched_tx_hash {
+            info!(%settlement_tx_hash, "Certificate settlement batched in transaction");
+            settlement_tx_hash
+        } else {
+            self.submit_settlement_tx(adapter.as_ref(), certificate_id, settlement, nonce_info)
+                .await?
+        };
+
+        // Step 10: Record the attempt along with the nonce and fees of the transaction
+        let attempt = self.settlement_attempt(settlement_tx_hash).await;
+        if let Err(error) = self
+            .state_store
+            .record_settlement_attempt(&certificate_id, attempt)
+        {
+            error!(?error, "Failed to record the settlement attempt");
+        }
+        if let Err(error) = self
+            .state_store
+            .record_settlement_sender(&settlement_tx_hash, settlement_address.into())
         {
-            Ok(pending_tx) => {
-                info!("Certificate settlement transaction submitted");
-                pending_tx
+            error!(?error, "Failed to record the sender of the settlement tx");
+        }
+
+        Ok(settlement_tx_hash)
+    }
+
+    /// Select the address and adapter of the key signing the settlement.
+    ///
+    /// New settlements are signed with the key active for their epoch, while
+    /// replacements are signed with the key which sent the last attempt, for
+    /// the nonce to match.
+    pub(super) fn settlement_key(
+        &self,
+        certificate_id: CertificateId,
+        epoch_number: EpochNumber,
+        is_replacement: bool,
+    ) -> (Address, Arc<dyn SettlementAdapter>) {
+        let current = (self.settlement_address, self.adapter.clone());
+        let Some(rotation) = &self.key_rotation else {
+            return current;
+        };
+        let next = (rotation.settlement_address, rotation.adapter.clone());
+
+        if is_replacement {
+            let last_sender = self
+                .state_store
+                .get_settlement_attempts(&certificate_id)
+                .and_then(|attempts| match attempts.last() {
+                    Some(attempt) => self
+                        .state_store
+                        .get_settlement_sender(&attempt.settlement_tx_hash),
+                    None => Ok(None),
+                });
+
+            match last_sender {
+                Ok(Some(sender)) if sender.into_alloy() == rotation.settlement_address => {
+                    return next
+                }
+                Ok(Some(_)) => return current,
+                Ok(None) => {}
+                Err(error) => warn!(?error, "Failed to get the sender of the last attempt"),
+            }
+        }
+
+        if epoch_number >= rotation.activation_epoch {
+            next
+        } else {
+            current
+        }
+    }
+
+    /// Submit the settlement of a single certificate and get the hash of the
+    /// transaction.
+    async fn submit_settlement_tx(
+        &self,
+        adapter: &dyn SettlementAdapter,
+        certificate_id: CertificateId,
+        settlement: PessimisticSettlement,
+        nonce_info: Option<NonceInfo>,
+    ) -> Result<SettlementTxHash, Error> {
+        match adapter.settle(settlement, nonce_info).await {
+            Ok(settlement_tx_hash) => {
+                info!(%settlement_tx_hash, "Certificate settlement transaction submitted");
+                Ok(settlement_tx_hash)
             }
             Err(error) => {
                 // TODO: Differentiate between different error types, check if decoding works
                 // properly for custom errors as well.
-                let error_decoded = RollupManagerRpc::decode_contract_revert(&error)
-                    .unwrap_or_else(|| error.to_string());
                 let error_message = error.to_string();
+                let error_decoded = match &error {
+                    SettlementAdapterError::Submission {
+                        reason: Some(reason),
+                        ..
+                    } => reason.clone(),
+                    _ => error_message.clone(),
+                };
 
                 error!(error_message, error_decoded, "Failed to settle certificate");
 
-                return Err(Error::SettlementError {
+                Err(Error::SettlementError {
                     certificate_id,
                     error: error_message,
-                });
+                })
+            }
+        }
+    }
+
+    /// Check that the balance of the settlement account covers the estimated
+    /// cost of a settlement transaction, recording the balance and warning
+    /// when it runs low.
+    ///
+    /// The settlement is not prevented when the balance or the fees cannot be
+    /// fetched, the submission reporting the L1 errors if any.
+    async fn check_settlement_funds(
+        &self,
+        certificate_id: CertificateId,
+        address: Address,
+    ) -> Result<(), Error> {
+        let provider = self.l1_rpc.get_provider();
+
+        let balance = match provider.get_balance(address).await {
+            Ok(balance) => balance,
+            Err(error) => {
+                warn!(?error, %address, "Failed to fetch the balance of the settlement account");
+                return Ok(());
+            }
+        };
+
+        agglayer_telemetry::settlement::record_account_balance(
+            u64::try_from(balance / U256::from(WEI_PER_GWEI)).unwrap_or(u64::MAX),
+        );
+
+        if let Some(threshold) = self.config.low_balance_threshold {
+            if balance < U256::from(threshold) {
+                warn!(%address, %balance, threshold, "Low balance on the settlement account");
+            }
+        }
+
+        let max_fee_per_gas = match provider.estimate_eip1559_fees().await {
+            Ok(estimate) => estimate
+                .max_fee_per_gas
+                .max(self.config.gas_price.floor)
+                .min(self.config.gas_price.ceiling),
+            Err(error) => {
+                warn!(
+                    ?error,
+                    "Failed to estimate the fees of the settlement transaction"
+                );
+                return Ok(());
             }
         };
 
-        // Get the transaction hash from the pending transaction
-        let tx_hash = *pending_tx.tx_hash();
-        info!("Settlement transaction hash: {}", tx_hash);
+        let required = U256::from(max_fee_per_gas)
+            .saturating_mul(U256::from(self.config.settlement_gas_estimate));
+        if balance < required {
+            error!(
+                %address,
+                %balance,
+                %required,
+                "Settlement account cannot cover the estimated cost of the settlement"
+            );
+            agglayer_telemetry::settlement::record_deferred_settlement();
+
+            return Err(Error::InsufficientFunds {
+                certificate_id,
+                balance,
+                required,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check the proof against the verifier and verifying key the network
+    /// settles with on L1, by simulating its verification on the public values
+    /// the rollup manager builds from the settlement. A proof rejected by the
+    /// verifier fails the settlement. If the verification cannot be simulated,
+    /// the settlement is deferred when `require_proof_verification` is set,
+    /// and goes on otherwise.
+    pub(super) async fn verify_proof_on_l1(
+        &self,
+        certificate_id: CertificateId,
+        settlement: &PessimisticSettlement,
+    ) -> Result<(), Error> {
+        match self.l1_rpc.verify_pessimistic_proof(settlement).await {
+            Ok(()) => {
+                debug!("Proof accepted by the verifier on L1");
+                Ok(())
+            }
+            Err(L1RpcError::ProofRejectedByVerifier(reason)) => {
+                error!(
+                    reason,
+                    "Proof rejected by the verifier on L1, aborting the settlement"
+                );
+                Err(Error::ProofRejectedByVerifier {
+                    certificate_id,
+                    error: reason,
+                })
+            }
+            Err(error) if self.config.require_proof_verification => {
+                warn!(
+                    ?error,
+                    "Failed to verify the proof on L1, deferring the settlement"
+                );
+                Err(Error::ProofVerificationUnavailable {
+                    certificate_id,
+                    error: error.to_string(),
+                })
+            }
+            Err(error) => {
+                warn!(
+                    ?error,
+                    "Failed to verify the proof on L1, settling without the verification"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Build the record of a submitted settlement transaction, fetching its
+    /// nonce and fees from the L1 on a best-effort basis.
+    async fn settlement_attempt(&self, settlement_tx_hash: SettlementTxHash) -> SettlementAttempt {
+        use alloy::consensus::Transaction as _;
+
+        let tx = self
+            .l1_rpc
+            .get_provider()
+            .get_transaction_by_hash(settlement_tx_hash.into())
+            .await
+            .inspect_err(|error| {
+                warn!(?error, %settlement_tx_hash, "Failed to fetch the settlement tx");
+            })
+            .ok()
+            .flatten();
 
-        Ok(SettlementTxHash::from(tx_hash))
+        SettlementAttempt {
+            settlement_tx_hash,
+            nonce: tx.as_ref().map(|tx| tx.inner.nonce()),
+            max_fee_per_gas: tx.as_ref().map(|tx| tx.inner.max_fee_per_gas()),
+            max_priority_fee_per_gas: tx
+                .as_ref()
+                .and_then(|tx| tx.inner.max_priority_fee_per_gas()),
+        }
     }
 }
 
 impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
     RpcSettlementClient<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
 where
+    StateStore: StateReader + StateWriter,
+    PendingStore: PendingCertificateReader,
     RollupManagerRpc: L1TransactionFetcher,
     PerEpochStore: PerEpochWriter + PerEpochReader,
 {
@@ -253,8 +615,9 @@ where
         )?;
 
         // Step 1: Wait for transaction receipt with retries
+        let expected_verification = self.expected_verification(certificate_id);
         let receipt = self
-            .wait_for_transaction_receipt(settlement_tx_hash, certificate_id)
+            .wait_for_transaction_receipt(settlement_tx_hash, certificate_id, expected_verification)
             .await?;
 
         if !receipt.inner.tx_type().is_eip1559() {
@@ -268,9 +631,20 @@ where
         // Step 2: Check transaction status
         if !receipt.status() {
             warn!(%settlement_tx_hash, "Certificate settlement transaction failed to settle");
-            return Err(Error::SettlementError {
+
+            // Record the revert for the certificate to be re-packed on admin request
+            // instead of being resubmitted by the chain.
+            let epoch_number = self.current_epoch.load().get_epoch_number();
+            if let Err(error) = self
+                .state_store
+                .record_reverted_settlement(epoch_number, &certificate_id)
+            {
+                warn!(?error, "Failed to record the reverted settlement");
+            }
+
+            return Err(Error::SettlementReverted {
                 certificate_id,
-                error: "Settlement transaction failed".to_string(),
+                settlement_tx_hash,
             });
         }
 
@@ -314,8 +688,89 @@ where
             }
         };
 
+        // Step 4: Account for the cost of the settlement
+        self.record_settlement_cost(certificate_id, epoch_number, &receipt);
+
         Ok((epoch_number, certificate_index))
     }
+
+    /// Network and new pessimistic root verified on L1 by the settlement of
+    /// the certificate, used to recognize it among the observed verifications.
+    ///
+    /// Returns `None` if the proof of the certificate is not available.
+    fn expected_verification(&self, certificate_id: CertificateId) -> Option<(NetworkId, Digest)> {
+        let network_id = match self.state_store.get_certificate_header(&certificate_id) {
+            Ok(Some(header)) => header.network_id,
+            Ok(None) => return None,
+            Err(error) => {
+                warn!(?error, "Failed to get the certificate header");
+                return None;
+            }
+        };
+
+        let proof = match self.pending_store.get_proof(certificate_id) {
+            Ok(Some(Proof::SP1(proof))) => proof,
+            Ok(None) => return None,
+            Err(error) => {
+                warn!(?error, "Failed to get the certificate proof");
+                return None;
+            }
+        };
+
+        PessimisticProofOutput::from_public_values(proof.public_values.as_slice())
+            .ok()
+            .map(|output| (network_id, output.new_pessimistic_root))
+    }
+
+    /// Add the gas used and the fees paid by the settlement transaction to the
+    /// costs of the network for the epoch. The cost of a batched transaction
+    /// is split evenly between the settlements it includes.
+    ///
+    /// The accounting is best-effort, failures are logged and do not affect
+    /// the settlement.
+    fn record_settlement_cost(
+        &self,
+        certificate_id: CertificateId,
+        epoch_number: EpochNumber,
+        receipt: &TransactionReceipt,
+    ) {
+        use agglayer_contracts::contracts::PolygonRollupManager::VerifyPessimisticStateTransition;
+        use alloy::sol_types::SolEvent as _;
+
+        let settlements = receipt
+            .inner
+            .logs()
+            .iter()
+            .filter(|log| {
+                log.topics().first() == Some(&VerifyPessimisticStateTransition::SIGNATURE_HASH)
+            })
+            .count()
+            .max(1) as u64;
+
+        let gas_used = receipt.gas_used / settlements;
+        let fees = u128::from(receipt.gas_used).saturating_mul(receipt.effective_gas_price)
+            / u128::from(settlements);
+
+        let network_id = match self.state_store.get_certificate_header(&certificate_id) {
+            Ok(Some(header)) => header.network_id,
+            Ok(None) => {
+                warn!("Certificate header not found, the settlement cost is not recorded");
+                return;
+            }
+            Err(error) => {
+                error!(?error, "Failed to get the certificate header");
+                return;
+            }
+        };
+
+        match self
+            .state_store
+            .record_settlement_cost(network_id, epoch_number, gas_used, fees)
+        {
+            Ok(()) => debug!(%network_id, gas_used, fees, "Recorded the settlement cost"),
+            Err(error) => error!(?error, "Failed to record the settlement cost"),
+        }
+    }
 }
 
 impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
@@ -323,143 +778,174 @@ impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
 where
     RollupManagerRpc: L1TransactionFetcher,
 {
-    /// Wait for transaction receipt with configurable retries and intervals
+    /// Wait for transaction receipt with configurable retries and intervals.
+    ///
+    /// When confirmations are required, the inclusion of the transaction is
+    /// re-checked at each poll until the confirmation depth is reached, so
+    /// that a transaction dropped or moved by an L1 reorg is followed instead
+    /// of being reported as settled.
+    ///
+    /// While the transaction is not mined, the expected verification is looked
+    /// up among the ones observed on L1, to detect the certificate being
+    /// settled through another transaction.
     async fn wait_for_transaction_receipt(
         &self,
         settlement_tx_hash: SettlementTxHash,
         certificate_id: CertificateId,
+        expected_verification: Option<(NetworkId, Digest)>,
     ) -> Result<TransactionReceipt, Error> {
         let tx_hash = settlement_tx_hash.into();
         let timeout = self
             .config
             .retry_interval
-            .mul_f64(self.config.max_retries as f64);
+            .saturating_mul(u32::try_from(self.config.max_retries).unwrap_or(u32::MAX));
 
         debug!(
             ?timeout,
             max_retries = self.config.max_retries,
             retry_interval = ?self.config.retry_interval,
+            strategy = ?self.config.receipt_polling.strategy,
+            required_confirmations = self.config.confirmations,
             "Waiting for transaction receipt",
         );
 
-        for attempt in 0..=self.config.max_retries {
+        let block_time = match self.config.receipt_polling.strategy {
+            ReceiptPollingStrategy::Adaptive => {
+                estimate_block_time(self.l1_rpc.get_provider()).await
+            }
+            _ => None,
+        };
+        let mut poller = ReceiptPoller::new(&self.config, block_time);
+        let mut waited = Duration::ZERO;
+
+        // Block in which the transaction was included at the last poll.
+        let mut included_in: Option<u64> = None;
+        // Block at which the transaction was first seen not mined.
+        let mut pending_since: Option<u64> = None;
+
+        for attempt in 0.. {
+            // Confirmations still missing to the transaction, 1 while it isn't mined.
+            let mut missing_confirmations = 1;
+
             match self.l1_rpc.fetch_transaction_receipt(tx_hash).await {
                 Ok(Some(receipt)) => {
-                    info!(attempt, "Successfully fetched transaction receipt");
-
-                    // Wait for the required number of confirmations
-                    if self.config.confirmations > 0 {
-                        let receipt_block = receipt.block_number.ok_or_else(|| {
-                            error!(%settlement_tx_hash, "Transaction receipt has no block number");
-                            Error::SettlementError {
-                                certificate_id,
-                                error: "Transaction receipt has no block number".to_string(),
-                            }
-                        })?;
+                    // No confirmations required, return immediately
+                    if self.config.confirmations == 0 {
+                        info!(attempt, "Successfully fetched transaction receipt");
+                        return Ok(receipt);
+                    }
 
-                        debug!(
-                            receipt_block,
-                            required_confirmations = self.config.confirmations,
-                            "Waiting for block confirmations"
-                        );
+                    let receipt_block = receipt.block_number.ok_or_else(|| {
+                        error!(%settlement_tx_hash, "Transaction receipt has no block number");
+                        Error::SettlementError {
+                            certificate_id,
+                            error: "Transaction receipt has no block number".to_string(),
+                        }
+                    })?;
 
-                        // Wait until we have the required number of confirmations
-                        for confirmation_attempt in attempt..=self.config.max_retries {
-                            match self.l1_rpc.get_provider().get_block_number().await {
-                                Ok(current_block) => {
-                                    let confirmations = current_block
-                                        .saturating_sub(receipt_block)
-                                        .saturating_add(1);
-                                    if confirmations >= self.config.confirmations as u64 {
-                                        info!(
-                                            confirmations,
-                                            required_confirmations = self.config.confirmations,
-                                            current_block,
-                                            "Transaction confirmed with required confirmations"
-                                        );
-                                        return Ok(receipt);
-                                    } else {
-                                        debug!(
-                                            confirmations,
-                                            required_confirmations = self.config.confirmations,
-                                            "Waiting for more confirmations, sleeping"
-                                        );
-                                        tokio::time::sleep(self.config.retry_interval).await;
-                                    }
-                                }
-                                Err(error) => {
-                                    if confirmation_attempt <= self.config.max_retries {
-                                        warn!(
-                                            ?error,
-                                            "Failed to get current block number, retrying"
-                                        );
-                                        tokio::time::sleep(self.config.retry_interval).await;
-                                        continue;
-                                    } else {
-                                        error!(
-                                            ?error,
-                                            "Failed to get current block number after maximum \
-                                             retries"
-                                        );
-                                        return Err(Error::SettlementError {
-                                            certificate_id,
-                                            error: format!(
-                                                "Failed to get current block number while waiting \
-                                                 for confirmations of tx {tx_hash}: {error}"
-                                            ),
-                                        });
-                                    }
-                                }
+                    match included_in.replace(receipt_block) {
+                        None => info!(
+                            attempt,
+                            receipt_block, "Successfully fetched transaction receipt"
+                        ),
+                        Some(previous_block) if previous_block != receipt_block => warn!(
+                            previous_block,
+                            receipt_block, "Settlement transaction re-included in another block"
+                        ),
+                        Some(_) => {}
+                    }
+
+                    match self.l1_rpc.get_provider().get_block_number().await {
+                        Ok(current_block) => {
+                            let confirmations = confirmations(receipt_block, current_block);
+                            if confirmations >= self.config.confirmations as u64 {
+                                info!(
+                                    confirmations,
+                                    required_confirmations = self.config.confirmations,
+                                    current_block,
+                                    "Transaction confirmed with required confirmations"
+                                );
+                                return Ok(receipt);
                             }
-                        }
 
-                        // Timeout waiting for confirmations
-                        error!(
-                            ?timeout,
-                            "Timeout while waiting for transaction confirmations"
-                        );
-                        return Err(Error::PendingTransactionTimeout {
-                            certificate_id,
-                            settlement_tx_hash,
-                            error: format!(
-                                "Timeout while waiting for transaction confirmations for tx \
-                                 {tx_hash} after {timeout:?}"
-                            ),
-                        });
-                    } else {
-                        // No confirmations required, return immediately
-                        return Ok(receipt);
+                            debug!(
+                                confirmations,
+                                required_confirmations = self.config.confirmations,
+                                "Waiting for more confirmations, sleeping"
+                            );
+                            missing_confirmations =
+                                self.config.confirmations as u64 - confirmations;
+                        }
+                        Err(error) => {
+                            warn!(?error, "Failed to get current block number, retrying");
+                        }
                     }
                 }
                 Ok(None) => {
-                    // Transaction not yet included in a block, continue retrying
-                    if attempt <= self.config.max_retries {
-                        debug!(
-                            %settlement_tx_hash,
-                            next_attempt = attempt + 1,
-                            max_retries = self.config.max_retries,
-                            retry_interval = ?self.config.retry_interval,
-                            "Transaction receipt not found yet, retrying after {:?}",
-                            self.config.retry_interval
-                        );
-                        tokio::time::sleep(self.config.retry_interval).await;
-                        continue;
-                    } else {
-                        // Max retries reached
-                        error!(
+                    if let Some(verification) = expected_verification
+                        .and_then(|(network_id, new_pessimistic_root)| {
+                            self.observed_verifications
+                                .find(network_id, new_pessimistic_root)
+                        })
+                        .filter(|verification| {
+                            verification.settlement_tx_hash != settlement_tx_hash
+                        })
+                    {
+                        info!(
                             %settlement_tx_hash,
-                            ?timeout,
-                            "Timeout while waiting the pending settlement transaction"
+                            other_tx_hash = %verification.settlement_tx_hash,
+                            "Certificate settled on L1 through another transaction"
                         );
-                        return Err(Error::PendingTransactionTimeout {
+                        return Err(Error::SettledThroughOtherTx {
                             certificate_id,
-                            settlement_tx_hash,
-                            error: format!(
-                                "Timeout while waiting for the pending settlement transaction \
-                                 {timeout:?}"
-                            ),
+                            settlement_tx_hash: verification.settlement_tx_hash,
                         });
                     }
+
+                    if let Some(previous_block) = included_in.take() {
+                        warn!(
+                            %settlement_tx_hash,
+                            previous_block,
+                            "Settlement transaction is no longer included, waiting for its \
+                             re-inclusion"
+                        );
+                    }
+
+                    // Consider the transaction stuck if it is not mined after the configured
+                    // number of blocks, to have it re-broadcast with bumped fees.
+                    if let Some(resubmit_after_blocks) = self.config.resubmit_after_blocks {
+                        match self.l1_rpc.get_provider().get_block_number().await {
+                            Ok(current_block) => {
+                                let since = *pending_since.get_or_insert(current_block);
+                                if current_block.saturating_sub(since) >= resubmit_after_blocks {
+                                    warn!(
+                                        %settlement_tx_hash,
+                                        resubmit_after_blocks,
+                                        "Settlement transaction is stuck, giving up waiting"
+                                    );
+                                    return Err(Error::PendingTransactionTimeout {
+                                        certificate_id,
+                                        settlement_tx_hash,
+                                        error: format!(
+                                            "Settlement transaction {tx_hash} not mined after \
+                                             {resubmit_after_blocks} blocks"
+                                        ),
+                                    });
+                                }
+                            }
+                            Err(error) => {
+                                warn!(?error, "Failed to get current block number");
+                            }
+                        }
+                    }
+
+                    // Transaction not yet included in a block, continue retrying
+                    debug!(
+                        %settlement_tx_hash,
+                        next_attempt = attempt + 1,
+                        max_retries = self.config.max_retries,
+                        "Transaction receipt not found yet, retrying"
+                    );
                 }
                 Err(error) => {
                     // Other error (e.g., network issue, RPC error)
@@ -473,31 +959,51 @@ where
                     });
                 }
             }
+
+            if waited >= timeout {
+                break;
+            }
+            let interval = poller
+                .next_interval(missing_confirmations)
+                .min(timeout - waited);
+            debug!("Polling the transaction receipt again after {interval:?}");
+            tokio::time::sleep(interval).await;
+            waited += interval;
         }
 
-        // This should not be reached, but added for completeness
-        error!(
-            ?timeout,
-            "Unexpected timeout while watching the pending settlement transaction"
-        );
+        let error = if included_in.is_some() {
+            format!(
+                "Timeout while waiting for transaction confirmations for tx {tx_hash} after \
+                 {timeout:?}"
+            )
+        } else {
+            format!("Timeout while waiting for the pending settlement transaction {timeout:?}")
+        };
+
+        error!(%settlement_tx_hash, ?timeout, error);
         Err(Error::PendingTransactionTimeout {
             certificate_id,
             settlement_tx_hash,
-            error: format!(
-                "Unexpected timeout while watching the pending settlement transaction after \
-                 {timeout:?}"
-            ),
+            error,
         })
     }
 }
 
+/// Number of confirmations of a transaction included in `receipt_block`, the
+/// including block counting as the first confirmation.
+pub(super) fn confirmations(receipt_block: u64, current_block: u64) -> u64 {
+    current_block
+        .saturating_sub(receipt_block)
+        .saturating_add(1)
+}
+
 #[async_trait::async_trait]
 impl<StateStore, PendingStore, PerEpochStore, RollupManagerRpc> SettlementClient
     for RpcSettlementClient<StateStore, PendingStore, PerEpochStore, RollupManagerRpc>
 where
     StateStore: StateReader + StateWriter + 'static,
     PendingStore: PendingCertificateReader + 'static,
-    RollupManagerRpc: RollupContract + Settler + L1TransactionFetcher + Send + Sync + 'static,
+    RollupManagerRpc: RollupContract + L1TransactionFetcher + Send + Sync + 'static,
     PerEpochStore: PerEpochWriter + PerEpochReader + 'static,
 {
     type Provider = <RollupManagerRpc as L1TransactionFetcher>::Provider;
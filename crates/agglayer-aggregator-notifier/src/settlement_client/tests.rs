@@ -1,11 +1,16 @@
-use std::sync::Arc;
-
-use agglayer_config::outbound::OutboundRpcSettleConfig;
-use agglayer_contracts::{L1RpcError, L1TransactionFetcher, Settler};
-use agglayer_storage::tests::mocks::{MockPendingStore, MockPerEpochStore, MockStateStore};
+use std::{sync::Arc, time::Duration};
+
+use agglayer_certificate_orchestrator::Error;
+use agglayer_config::outbound::{EpochPacking, OutboundRpcSettleConfig};
+use agglayer_contracts::{L1RpcError, L1TransactionFetcher, PessimisticSettlement, Settler};
+use agglayer_storage::{
+    columns::settlement_attempts_per_certificate::SettlementAttempt,
+    tests::mocks::{MockPendingStore, MockPerEpochStore, MockStateStore},
+};
 use agglayer_types::{
-    aggchain_data::CertificateAggchainDataCtx, Address, CertificateHeader, CertificateStatus,
-    Height, L1WitnessCtx, Metadata, PessimisticRootInput, Proof,
+    aggchain_data::CertificateAggchainDataCtx, Address, CertificateHeader, CertificateId,
+    CertificateStatus, Digest, EpochNumber, Height, L1WitnessCtx, Metadata, NetworkId,
+    PessimisticRootInput, Proof, SettlementTxHash,
 };
 use alloy::{
     primitives::{Bytes, FixedBytes, TxHash},
@@ -18,7 +23,13 @@ use pessimistic_proof::core::commitment::PessimisticRootCommitmentVersion;
 use pessimistic_proof_test_suite::forest::Forest;
 use rstest::rstest;
 
-use crate::settlement_client::RpcSettlementClient;
+use crate::settlement_client::{
+    batch::{BatchKind, SettlementBatcher},
+    rpc::confirmations,
+    watcher::{ObservedVerification, ObservedVerifications},
+    AggregatedProofs, EpochPackingStrategy, MockSettlementAdapter, RpcSettlementClient,
+    SettleIndividually, SettlementAdapter as _, SettlementAdapterError,
+};
 
 mockall::mock! {
     L1Rpc {}
@@ -38,6 +49,7 @@ mockall::mock! {
         async fn get_prev_pessimistic_root(&self, rollup_id: u32, before_tx: Option<TxHash>) -> Result<[u8; 32], L1RpcError>;
 
         async fn get_verifier_type(&self, rollup_id: u32) -> Result<agglayer_contracts::rollup::VerifierType, L1RpcError>;
+        async fn verify_pessimistic_proof(&self, settlement: &PessimisticSettlement) -> Result<(), L1RpcError>;
 
         fn get_rollup_manager_address(&self) -> Address;
         fn get_event_filter_block_range(&self) -> u64;
@@ -65,8 +77,14 @@ mockall::mock! {
             proof: Bytes,
             custom_chain_data: Bytes,
             nonce: Option<(u64, u128, Option<u128>)>
-        ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, alloy::contract::Error>;
+        ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, L1RpcError>;
+
+        async fn supports_multicall(&self) -> bool;
 
+        async fn verify_pessimistic_trusted_aggregator_batch(
+            &self,
+            settlements: Vec<agglayer_contracts::PessimisticSettlement>,
+        ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, L1RpcError>;
     }
 }
 
@@ -95,6 +113,7 @@ async fn epoch_packer_can_settle_one_certificate() {
                     PessimisticRootCommitmentVersion::V2,
                 ),
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                gas_token: None,
             },
         )
         .unwrap();
@@ -160,6 +179,7 @@ async fn epoch_packer_can_settle_one_certificate() {
         Arc::new(pending_store),
         Arc::new(l1_rpc),
         Arc::new(ArcSwap::new(Arc::new(per_epoch_store))),
+        alloy::primitives::Address::ZERO,
     );
 
     let settlement_tx_hash = epoch_packer
@@ -177,7 +197,6 @@ async fn epoch_packer_can_settle_one_certificate() {
 #[ignore = "reaches external endpoint"]
 async fn test_fetch_last_settled_pp_root() {
     use agglayer_certificate_orchestrator::SettlementClient;
-    use agglayer_types::NetworkId;
     use url::Url;
 
     // Use L1_RPC_ENDPOINT environment variable (should be set to Sepolia endpoint)
@@ -229,6 +248,7 @@ async fn test_fetch_last_settled_pp_root() {
         pending_store,
         Arc::new(l1_rpc),
         per_epoch_store,
+        alloy::primitives::Address::ZERO,
     );
 
     // Test fetch_last_settled_pp_root for different network IDs
@@ -289,3 +309,397 @@ async fn test_fetch_last_settled_pp_root() {
 
     tracing::info!("Completed testing fetch_last_settled_pp_root for all network IDs");
 }
+
+#[rstest]
+#[case::same_block(10, 10, 1)]
+#[case::mainnet_depth(10, 21, 12)]
+#[case::lagging_node(10, 9, 1)]
+fn confirmations_count_the_including_block(
+    #[case] receipt_block: u64,
+    #[case] current_block: u64,
+    #[case] expected: u64,
+) {
+    assert_eq!(confirmations(receipt_block, current_block), expected);
+}
+
+fn settlement(rollup_id: u32) -> PessimisticSettlement {
+    PessimisticSettlement {
+        rollup_id,
+        l_1_info_tree_leaf_count: 0,
+        new_local_exit_root: [0; 32],
+        new_pessimistic_root: [0; 32],
+        proof: Bytes::new(),
+        custom_chain_data: Bytes::new(),
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn batcher_submits_the_settlements_of_an_epoch_together() {
+    let adapter = Arc::new(MockSettlementAdapter::new(true));
+    let batcher = Arc::new(SettlementBatcher::new(
+        Duration::from_millis(50),
+        BatchKind::Multicall,
+    ));
+    let epoch_number = EpochNumber::new(1);
+
+    let (first, second) = tokio::join!(
+        batcher.submit(adapter.clone(), epoch_number, settlement(1)),
+        batcher.submit(adapter.clone(), epoch_number, settlement(2)),
+    );
+
+    assert!(first.is_some());
+    assert_eq!(first, second);
+    assert_eq!(
+        adapter.transactions(),
+        vec![vec![settlement(1), settlement(2)]]
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn batcher_leaves_lone_settlements_to_be_submitted_individually() {
+    let adapter = Arc::new(MockSettlementAdapter::new(true));
+    let batcher = Arc::new(SettlementBatcher::new(
+        Duration::from_millis(50),
+        BatchKind::Multicall,
+    ));
+
+    let (first, second) = tokio::join!(
+        batcher.submit(adapter.clone(), EpochNumber::new(1), settlement(1)),
+        batcher.submit(adapter.clone(), EpochNumber::new(2), settlement(2)),
+    );
+
+    assert_eq!(first, None);
+    assert_eq!(second, None);
+    assert!(adapter.transactions().is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn batcher_falls_back_without_batch_support() {
+    let adapter = Arc::new(MockSettlementAdapter::new(false));
+    let batcher = Arc::new(SettlementBatcher::new(
+        Duration::from_millis(50),
+        BatchKind::Multicall,
+    ));
+    let epoch_number = EpochNumber::new(1);
+
+    let (first, second) = tokio::join!(
+        batcher.submit(adapter.clone(), epoch_number, settlement(1)),
+        batcher.submit(adapter.clone(), epoch_number, settlement(2)),
+    );
+
+    assert_eq!(first, None);
+    assert_eq!(second, None);
+    assert!(adapter.transactions().is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn settling_individually_packs_nothing() {
+    let adapter = Arc::new(MockSettlementAdapter::new(true));
+
+    let packed = SettleIndividually
+        .pack(adapter.clone(), EpochNumber::new(1), settlement(1))
+        .await;
+
+    assert_eq!(packed, None);
+    assert!(adapter.transactions().is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn aggregated_proofs_settle_the_epoch_together() {
+    let adapter = Arc::new(MockSettlementAdapter::new(false).with_aggregated_settlement());
+    let strategy = AggregatedProofs::new(Duration::from_millis(50));
+    let epoch_number = EpochNumber::new(1);
+
+    let (first, second) = tokio::join!(
+        strategy.pack(adapter.clone(), epoch_number, settlement(1)),
+        strategy.pack(adapter.clone(), epoch_number, settlement(2)),
+    );
+
+    assert!(first.is_some());
+    assert_eq!(first, second);
+    assert_eq!(
+        adapter.transactions(),
+        vec![vec![settlement(1), settlement(2)]]
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn aggregated_proofs_fall_back_without_aggregation_support() {
+    // Multicall batches alone don't allow to aggregate the proofs.
+    let adapter = Arc::new(MockSettlementAdapter::new(true));
+    let strategy = AggregatedProofs::new(Duration::from_millis(50));
+    let epoch_number = EpochNumber::new(1);
+
+    let (first, second) = tokio::join!(
+        strategy.pack(adapter.clone(), epoch_number, settlement(1)),
+        strategy.pack(adapter.clone(), epoch_number, settlement(2)),
+    );
+
+    assert_eq!(first, None);
+    assert_eq!(second, None);
+    assert!(adapter.transactions().is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn mock_adapter_derives_the_tx_hash_from_the_settlements() {
+    let adapter = MockSettlementAdapter::new(false);
+
+    let first = adapter.settle(settlement(1), None).await.unwrap();
+    let again = adapter.settle(settlement(1), None).await.unwrap();
+    let other = adapter.settle(settlement(2), None).await.unwrap();
+
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    assert!(matches!(
+        adapter
+            .settle_batch(vec![settlement(1), settlement(2)])
+            .await,
+        Err(SettlementAdapterError::BatchUnsupported)
+    ));
+    assert!(matches!(
+        adapter
+            .settle_aggregated(vec![settlement(1), settlement(2)])
+            .await,
+        Err(SettlementAdapterError::AggregationUnsupported)
+    ));
+}
+
+fn verification(network_id: u32, new_pessimistic_root: u8, tx_hash: u8) -> ObservedVerification {
+    ObservedVerification {
+        network_id: NetworkId::new(network_id),
+        new_pessimistic_root: Digest([new_pessimistic_root; 32]),
+        settlement_tx_hash: SettlementTxHash::new(Digest([tx_hash; 32])),
+    }
+}
+
+#[test]
+fn observed_verifications_are_found_by_network_and_root() {
+    let observed = ObservedVerifications::default();
+    observed.record(verification(1, 0xaa, 1));
+    observed.record(verification(2, 0xaa, 2));
+    observed.record(verification(1, 0xaa, 3));
+
+    assert_eq!(
+        observed.find(NetworkId::new(1), Digest([0xaa; 32])),
+        Some(verification(1, 0xaa, 3))
+    );
+    assert_eq!(
+        observed.find(NetworkId::new(2), Digest([0xaa; 32])),
+        Some(verification(2, 0xaa, 2))
+    );
+    assert_eq!(observed.find(NetworkId::new(1), Digest([0xbb; 32])), None);
+    assert_eq!(observed.find(NetworkId::new(3), Digest([0xaa; 32])), None);
+}
+
+#[test]
+fn observed_verifications_forget_the_oldest_ones() {
+    let observed = ObservedVerifications::default();
+    observed.record(verification(1, 0xaa, 1));
+    for tx_hash in 0..1024 {
+        observed.record(verification(2, 0xbb, tx_hash as u8));
+    }
+
+    assert_eq!(observed.find(NetworkId::new(1), Digest([0xaa; 32])), None);
+    assert!(observed
+        .find(NetworkId::new(2), Digest([0xbb; 32]))
+        .is_some());
+}
+
+fn settlement_client(
+    l1_rpc: MockL1Rpc,
+    require_proof_verification: bool,
+) -> RpcSettlementClient<MockStateStore, MockPendingStore, MockPerEpochStore, MockL1Rpc> {
+    RpcSettlementClient::new(
+        Arc::new(OutboundRpcSettleConfig {
+            verify_proof_before_settlement: true,
+            require_proof_verification,
+            ..Default::default()
+        }),
+        Arc::new(MockStateStore::new()),
+        Arc::new(MockPendingStore::new()),
+        Arc::new(l1_rpc),
+        Arc::new(ArcSwap::new(Arc::new(MockPerEpochStore::new()))),
+        alloy::primitives::Address::ZERO,
+    )
+}
+
+#[test_log::test(tokio::test)]
+async fn proof_rejected_by_the_l1_verifier_fails_the_settlement() {
+    let certificate_id = CertificateId::new([1; 32].into());
+    let mut l1_rpc = MockL1Rpc::new();
+    l1_rpc
+        .expect_verify_pessimistic_proof()
+        .once()
+        .with(eq(settlement(1)))
+        .returning(|_| {
+            Err(L1RpcError::ProofRejectedByVerifier(
+                "WrongVerifierSelector()".into(),
+            ))
+        });
+
+    let result = settlement_client(l1_rpc, false)
+        .verify_proof_on_l1(certificate_id, &settlement(1))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(Error::ProofRejectedByVerifier { certificate_id: id, error })
+            if id == certificate_id && error == "WrongVerifierSelector()"
+    ));
+}
+
+#[test_log::test(tokio::test)]
+async fn unavailable_l1_verification_does_not_block_the_settlement() {
+    let mut l1_rpc = MockL1Rpc::new();
+    l1_rpc
+        .expect_verify_pessimistic_proof()
+        .once()
+        .returning(|_| Err(L1RpcError::RollupDataRetrievalFailed));
+
+    settlement_client(l1_rpc, false)
+        .verify_proof_on_l1(CertificateId::new([1; 32].into()), &settlement(1))
+        .await
+        .unwrap();
+}
+
+#[test_log::test(tokio::test)]
+async fn unavailable_l1_verification_defers_the_settlement_when_required() {
+    let certificate_id = CertificateId::new([1; 32].into());
+    let mut l1_rpc = MockL1Rpc::new();
+    l1_rpc
+        .expect_verify_pessimistic_proof()
+        .once()
+        .returning(|_| Err(L1RpcError::RollupDataRetrievalFailed));
+
+    let result = settlement_client(l1_rpc, true)
+        .verify_proof_on_l1(certificate_id, &settlement(1))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(Error::ProofVerificationUnavailable { certificate_id: id, .. }) if id == certificate_id
+    ));
+}
+
+fn aggregated_settlement_client(
+    adapter: MockSettlementAdapter,
+) -> RpcSettlementClient<MockStateStore, MockPendingStore, MockPerEpochStore, MockL1Rpc> {
+    RpcSettlementClient::new(
+        Arc::new(OutboundRpcSettleConfig {
+            epoch_packing: Some(EpochPacking::Aggregated),
+            ..Default::default()
+        }),
+        Arc::new(MockStateStore::new()),
+        Arc::new(MockPendingStore::new()),
+        Arc::new(MockL1Rpc::new()),
+        Arc::new(ArcSwap::new(Arc::new(MockPerEpochStore::new()))),
+        alloy::primitives::Address::ZERO,
+    )
+    .with_settlement_adapter(Arc::new(adapter))
+}
+
+#[test_log::test(tokio::test)]
+async fn aggregated_packing_is_rejected_without_aggregation_support() {
+    let result = aggregated_settlement_client(MockSettlementAdapter::new(true))
+        .check_epoch_packing()
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(SettlementAdapterError::AggregationUnsupported)
+    ));
+}
+
+#[test_log::test(tokio::test)]
+async fn aggregated_packing_is_accepted_with_aggregation_support() {
+    aggregated_settlement_client(MockSettlementAdapter::new(true).with_aggregated_settlement())
+        .check_epoch_packing()
+        .await
+        .unwrap();
+}
+
+#[test_log::test(tokio::test)]
+async fn aggregated_packing_is_rejected_when_the_rotated_key_lacks_support() {
+    let result =
+        aggregated_settlement_client(MockSettlementAdapter::new(true).with_aggregated_settlement())
+            .with_key_rotation(
+                EpochNumber::new(10),
+                alloy::primitives::Address::repeat_byte(2),
+                Arc::new(MockSettlementAdapter::new(true)),
+            )
+            .check_epoch_packing()
+            .await;
+
+    assert!(matches!(
+        result,
+        Err(SettlementAdapterError::AggregationUnsupported)
+    ));
+}
+
+fn rotating_settlement_client(
+    state_store: MockStateStore,
+) -> RpcSettlementClient<MockStateStore, MockPendingStore, MockPerEpochStore, MockL1Rpc> {
+    RpcSettlementClient::new(
+        Arc::new(OutboundRpcSettleConfig::default()),
+        Arc::new(state_store),
+        Arc::new(MockPendingStore::new()),
+        Arc::new(MockL1Rpc::new()),
+        Arc::new(ArcSwap::new(Arc::new(MockPerEpochStore::new()))),
+        alloy::primitives::Address::repeat_byte(1),
+    )
+    .with_key_rotation(
+        EpochNumber::new(10),
+        alloy::primitives::Address::repeat_byte(2),
+        Arc::new(MockSettlementAdapter::new(false)),
+    )
+}
+
+#[rstest]
+#[case::before_activation(9, 1)]
+#[case::at_activation(10, 2)]
+#[case::after_activation(11, 2)]
+fn new_settlements_are_signed_with_the_key_of_their_epoch(
+    #[case] epoch_number: u64,
+    #[case] expected: u8,
+) {
+    let (address, _) = rotating_settlement_client(MockStateStore::new()).settlement_key(
+        CertificateId::new([1; 32].into()),
+        EpochNumber::new(epoch_number),
+        false,
+    );
+
+    assert_eq!(address, alloy::primitives::Address::repeat_byte(expected));
+}
+
+#[test]
+fn replacements_are_signed_with_the_key_of_the_last_attempt() {
+    let certificate_id = CertificateId::new([1; 32].into());
+    let settlement_tx_hash = SettlementTxHash::for_tests();
+
+    let mut state_store = MockStateStore::new();
+    state_store
+        .expect_get_settlement_attempts()
+        .with(eq(certificate_id))
+        .once()
+        .returning(move |_| {
+            Ok(vec![SettlementAttempt {
+                settlement_tx_hash,
+                nonce: Some(1),
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+            }])
+        });
+    state_store
+        .expect_get_settlement_sender()
+        .with(eq(settlement_tx_hash))
+        .once()
+        .returning(|_| Ok(Some(Address::new([1; 20]))));
+
+    let (address, _) = rotating_settlement_client(state_store).settlement_key(
+        certificate_id,
+        EpochNumber::new(11),
+        true,
+    );
+
+    assert_eq!(address, alloy::primitives::Address::repeat_byte(1));
+}
@@ -0,0 +1,190 @@
+//! Watcher of the pessimistic proof verifications on L1.
+//!
+//! The verification events emitted by the rollup manager are recorded so that
+//! a certificate settled through another transaction than the one being
+//! waited for, e.g. a replacement with bumped fees, is recognized as settled.
+//! Verifications which were not sent by the settlement account are reported.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use agglayer_contracts::{
+    contracts::PolygonRollupManager::VerifyPessimisticStateTransition, L1TransactionFetcher,
+    RollupContract,
+};
+use agglayer_types::{Digest, NetworkId, SettlementTxHash};
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::Address,
+    providers::Provider,
+    rpc::types::{Filter, Log},
+    sol_types::SolEvent as _,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Maximum number of verifications kept in memory.
+const MAX_OBSERVED_VERIFICATIONS: usize = 1024;
+
+/// Verification of a pessimistic proof observed on L1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ObservedVerification {
+    pub(crate) network_id: NetworkId,
+    pub(crate) new_pessimistic_root: Digest,
+    pub(crate) settlement_tx_hash: SettlementTxHash,
+}
+
+/// Latest verifications observed on L1, shared between the watcher and the
+/// settlement client.
+#[derive(Default)]
+pub(crate) struct ObservedVerifications {
+    latest: Mutex<VecDeque<ObservedVerification>>,
+}
+
+impl ObservedVerifications {
+    pub(crate) fn record(&self, verification: ObservedVerification) {
+        let mut latest = self
+            .latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if latest.len() == MAX_OBSERVED_VERIFICATIONS {
+            latest.pop_front();
+        }
+        latest.push_back(verification);
+    }
+
+    /// Find the latest verification of the network resulting in the given
+    /// pessimistic root.
+    pub(crate) fn find(
+        &self,
+        network_id: NetworkId,
+        new_pessimistic_root: Digest,
+    ) -> Option<ObservedVerification> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .rev()
+            .find(|verification| {
+                verification.network_id == network_id
+                    && verification.new_pessimistic_root == new_pessimistic_root
+            })
+            .copied()
+    }
+}
+
+/// Task following the `VerifyPessimisticStateTransition` events of the
+/// rollup manager.
+pub struct SettlementEventWatcher<L1Rpc> {
+    l1_rpc: Arc<L1Rpc>,
+    observed: Arc<ObservedVerifications>,
+    /// Addresses of the accounts sending the settlement transactions.
+    settlement_addresses: Vec<Address>,
+    poll_interval: Duration,
+}
+
+impl<L1Rpc> SettlementEventWatcher<L1Rpc>
+where
+    L1Rpc: RollupContract + L1TransactionFetcher,
+{
+    pub(crate) fn new(
+        l1_rpc: Arc<L1Rpc>,
+        observed: Arc<ObservedVerifications>,
+        settlement_addresses: Vec<Address>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            l1_rpc,
+            observed,
+            settlement_addresses,
+            poll_interval,
+        }
+    }
+
+    /// Follow the verification events from the current L1 block until
+    /// cancelled.
+    pub async fn run(self, cancellation_token: CancellationToken) {
+        let mut next_block = None;
+
+        loop {
+            match self.poll(next_block).await {
+                Ok(block) => next_block = Some(block),
+                Err(error) => warn!(?error, "Failed to poll the L1 verification events"),
+            }
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Settlement event watcher cancelled");
+                    return;
+                }
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+    }
+
+    /// Process the verification events from `from_block` to the latest L1
+    /// block, returning the next block to poll from. Starts from the latest
+    /// block when `from_block` is unset.
+    async fn poll(&self, from_block: Option<u64>) -> eyre::Result<u64> {
+        let provider = self.l1_rpc.get_provider();
+        let latest_block = provider.get_block_number().await?;
+        let from_block = from_block.unwrap_or(latest_block);
+        let block_range = self.l1_rpc.get_event_filter_block_range().max(1);
+
+        let mut start_block = from_block;
+        while start_block <= latest_block {
+            // start_block, end_block are inclusive
+            let end_block = latest_block.min(start_block.saturating_add(block_range - 1));
+            let filter = Filter::new()
+                .address(self.l1_rpc.get_rollup_manager_address().into_alloy())
+                .event_signature(VerifyPessimisticStateTransition::SIGNATURE_HASH)
+                .from_block(BlockNumberOrTag::Number(start_block))
+                .to_block(BlockNumberOrTag::Number(end_block));
+
+            for log in provider.get_logs(&filter).await? {
+                self.process(&log);
+            }
+
+            start_block = end_block + 1;
+        }
+
+        Ok(latest_block + 1)
+    }
+
+    fn process(&self, log: &Log) {
+        let (Ok(event), Some(tx_hash)) = (
+            VerifyPessimisticStateTransition::decode_log(&log.clone().into()),
+            log.transaction_hash,
+        ) else {
+            warn!(
+                ?log,
+                "Unable to decode a VerifyPessimisticStateTransition event"
+            );
+            return;
+        };
+
+        let verification = ObservedVerification {
+            network_id: NetworkId::new(event.rollupID),
+            new_pessimistic_root: Digest::from(event.newPessimisticRoot),
+            settlement_tx_hash: SettlementTxHash::from(tx_hash),
+        };
+
+        if !self.settlement_addresses.contains(&event.trustedAggregator) {
+            error!(
+                network_id = %verification.network_id,
+                settlement_tx_hash = %verification.settlement_tx_hash,
+                trusted_aggregator = %event.trustedAggregator,
+                "Pessimistic verification observed on L1 that was not sent by the agglayer"
+            );
+            agglayer_telemetry::settlement::record_unexpected_verification(event.rollupID);
+        } else {
+            debug!(?verification, "Observed pessimistic verification on L1");
+        }
+
+        self.observed.record(verification);
+    }
+}
@@ -1,12 +1,20 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
+use agglayer_config::certificate_orchestrator::retry_policy::{ErrorClass, RetryPolicyConfig};
 use agglayer_storage::{
-    columns::latest_settled_certificate_per_network::SettledCertificate,
+    columns::{
+        audit_log_per_certificate::AuditEvent,
+        latest_settled_certificate_per_network::SettledCertificate,
+    },
     stores::{PendingCertificateReader, PendingCertificateWriter, StateReader, StateWriter},
 };
 use agglayer_types::{
-    Certificate, CertificateHeader, CertificateStatus, CertificateStatusError, Digest,
-    SettlementTxHash,
+    validate_global_index, Certificate, CertificateHeader, CertificateStatus,
+    CertificateStatusError, Digest, NetworkId, SettlementTxHash,
 };
 use tokio::sync::{mpsc, oneshot};
 use tokio_util::sync::CancellationToken;
@@ -14,10 +22,18 @@ use tracing::{debug, error, info, trace, warn};
 
 use crate::{
     network_task::{CertificateSettlementResult, NetworkTaskMessage},
-    Certifier, Error, NonceInfo,
+    proving_queue::{ProvingPermit, ProvingPriority, ProvingQueue},
+    Certifier, Error, NonceInfo, OrchestratorState,
 };
 
-const MAX_TX_RETRY: usize = 5;
+/// Delay before retrying a settlement deferred because the settlement account
+/// cannot cover its estimated cost, or because its proof cannot be verified on
+/// L1.
+const DEFERRED_SETTLEMENT_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Delay before checking again whether the origin networks settled the bridge
+/// exits imported by the certificate.
+const UNSETTLED_IMPORTS_RETRY_INTERVAL: Duration = Duration::from_secs(10);
 
 /// A task that processes a certificate, including certifying it and settling
 /// it.
@@ -38,6 +54,15 @@ pub struct CertificateTask<StateStore, PendingStore, CertifierClient> {
     new_pp_root: Option<Digest>,
     nonce_info: Option<NonceInfo>,
     previous_tx_hashes: HashSet<SettlementTxHash>,
+    /// The orchestrator state to report the proving outcomes to.
+    orchestrator_state: Option<Arc<OrchestratorState>>,
+    /// The queue to wait in before proving, with the priority of the
+    /// certificate.
+    proving_queue: Option<(Arc<ProvingQueue>, ProvingPriority)>,
+    /// The retry policies of the certificate, per class of failure.
+    retry_policy: Arc<RetryPolicyConfig>,
+    /// The number of retries of the certificate, per class of failure.
+    retries: HashMap<ErrorClass, u32>,
 }
 
 impl<StateStore, PendingStore, CertifierClient>
@@ -75,9 +100,36 @@ where
             new_pp_root: None,
             nonce_info: None,
             previous_tx_hashes: HashSet::new(),
+            orchestrator_state: None,
+            proving_queue: None,
+            retry_policy: Default::default(),
+            retries: HashMap::new(),
         })
     }
 
+    /// Report the proving outcomes to the orchestrator state, if any.
+    pub(crate) fn with_orchestrator_state(mut self, state: Option<Arc<OrchestratorState>>) -> Self {
+        self.orchestrator_state = state;
+        self
+    }
+
+    /// Wait in the proving queue, if any, with the given priority before
+    /// proving the certificate.
+    pub(crate) fn with_proving_queue(
+        mut self,
+        queue: Option<Arc<ProvingQueue>>,
+        priority: ProvingPriority,
+    ) -> Self {
+        self.proving_queue = queue.map(|queue| (queue, priority));
+        self
+    }
+
+    /// Retry the failures of the certificate according to the given policies.
+    pub(crate) fn with_retry_policy(mut self, retry_policy: Arc<RetryPolicyConfig>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     #[tracing::instrument(
         name = "CertificateTask::process",
         skip_all,
@@ -367,25 +419,61 @@ where
         let network_id = self.header.network_id;
         let certificate_id = self.header.certificate_id;
 
-        // Retrieve local network state
-        trace!("Retrieving local network state");
-        let (response, state) = oneshot::channel();
-        self.send_to_network_task(NetworkTaskMessage::GetLocalNetworkStateBeforeHeight {
-            height,
-            response,
-        })
-        .await?;
-        let state = state.await.map_err(recv_err)??;
-
-        // Actually certify
-        debug!("Starting certification");
-        let certifier_output = self
-            .certifier_client
-            .certify(*state, network_id, height)
+        let certifier_output = loop {
+            // Retrieve local network state
+            trace!("Retrieving local network state");
+            let (response, state) = oneshot::channel();
+            self.send_to_network_task(NetworkTaskMessage::GetLocalNetworkStateBeforeHeight {
+                height,
+                response,
+            })
             .await?;
+            let state = state.await.map_err(recv_err)??;
+
+            let proving_permit = self.wait_for_proving_turn().await;
+
+            // Actually certify, dropping the proving on shutdown
+            debug!("Starting certification");
+            let certification = tokio::select! {
+                certification = self.certifier_client.certify(*state, network_id, height) => {
+                    certification
+                }
+                _ = self.cancellation_token.cancelled() => {
+                    return Err(CertificateStatusError::InternalError(
+                        "Cancelled while proving the certificate".into(),
+                    ));
+                }
+            };
+            drop(proving_permit);
+
+            match certification {
+                Ok(certifier_output) => {
+                    self.record_proving_outcome(true);
+                    break certifier_output;
+                }
+                Err(error) => {
+                    if error.is_prover_failure() {
+                        self.record_proving_outcome(false);
+                    }
+                    let retried = match error.error_class() {
+                        Some(class) => self.wait_for_retry(class, &error).await?,
+                        None => false,
+                    };
+                    if !retried {
+                        return Err(error.into());
+                    }
+                }
+            }
+        };
         debug!("Proof certification completed");
 
         // Record the certification success
+        self.state_store.record_audit_event(
+            &certificate_id,
+            AuditEvent::Proven {
+                prover: self.certifier_client.prover(),
+            },
+        )?;
         self.set_status(CertificateStatus::Proven)?;
         self.new_pp_root = Some(certifier_output.new_pp_root);
         self.send_to_network_task(NetworkTaskMessage::CertificateExecuted {
@@ -411,42 +499,58 @@ where
             )));
         }
 
-        if self.previous_tx_hashes.len() > MAX_TX_RETRY {
-            error!(
-                previous_tx_hashes = ?self.previous_tx_hashes,
-                max_retries = MAX_TX_RETRY,
-                "More settlement transactions submitted for the same certificate than allowed retries, something is wrong"
-            );
-            return Err(CertificateStatusError::SettlementError(format!(
-                "Too many different settlement transactions submitted for the same certificate: \
-                 {:?}",
-                self.previous_tx_hashes
-            )));
-        }
-
+        // The number of settlement attempts is capped by the settlement client,
+        // which records each of them.
         let height = self.header.height;
         let certificate_id = self.header.certificate_id;
 
-        debug!(
-            "Submitting certificate for settlement, previous nonce is {:?}",
-            self.nonce_info
-        );
-        let (settlement_submitted_notifier, settlement_submitted) = oneshot::channel();
-        self.send_to_network_task(NetworkTaskMessage::CertificateReadyForSettlement {
-            height,
-            certificate_id,
-            nonce_info: self.nonce_info.clone(),
-            previous_tx_hashes: self.previous_tx_hashes.clone(),
-            new_pp_root: self
-                .new_pp_root
-                .ok_or(CertificateStatusError::InternalError(
-                    "CertificateTask::process_from_proven called without a pp_root".into(),
-                ))?,
-            settlement_submitted_notifier,
-        })
-        .await?;
+        self.wait_for_imported_exits().await?;
+
+        let (settlement_tx_hash, nonce_info) = loop {
+            debug!(
+                "Submitting certificate for settlement, previous nonce is {:?}",
+                self.nonce_info
+            );
+            let (settlement_submitted_notifier, settlement_submitted) = oneshot::channel();
+            self.send_to_network_task(NetworkTaskMessage::CertificateReadyForSettlement {
+                height,
+                certificate_id,
+                nonce_info: self.nonce_info.clone(),
+                previous_tx_hashes: self.previous_tx_hashes.clone(),
+                new_pp_root: self
+                    .new_pp_root
+                    .ok_or(CertificateStatusError::InternalError(
+                        "CertificateTask::process_from_proven called without a pp_root".into(),
+                    ))?,
+                settlement_submitted_notifier,
+            })
+            .await?;
 
-        let (settlement_tx_hash, nonce_info) = settlement_submitted.await.map_err(recv_err)??;
+            match settlement_submitted.await.map_err(recv_err)? {
+                Ok(submitted) => break submitted,
+                Err(
+                    error @ (Error::InsufficientFunds { .. }
+                    | Error::ProofVerificationUnavailable { .. }),
+                ) => {
+                    // The certificate stays proven until the settlement account is funded, or
+                    // its proof can be verified on L1.
+                    warn!(
+                        %error,
+                        "Deferring the settlement for {:?}",
+                        DEFERRED_SETTLEMENT_RETRY_INTERVAL
+                    );
+                    tokio::select! {
+                        _ = tokio::time::sleep(DEFERRED_SETTLEMENT_RETRY_INTERVAL) => {}
+                        _ = self.cancellation_token.cancelled() => {
+                            return Err(CertificateStatusError::InternalError(
+                                "Cancelled while the settlement is deferred".into(),
+                            ));
+                        }
+                    }
+                }
+                Err(error) => return Err(error.into()),
+            }
+        };
 
         if self.previous_tx_hashes.insert(settlement_tx_hash) {
             debug!(
@@ -530,7 +634,29 @@ where
             CertificateSettlementResult::Error(error) => {
                 return Err(error);
             }
+            CertificateSettlementResult::Reverted(error) => {
+                if !self
+                    .wait_for_retry(ErrorClass::SettlementRevert, &error)
+                    .await?
+                {
+                    return Err(error);
+                }
+
+                // The nonce of the reverted transaction is consumed, the new
+                // settlement transaction can't replace it.
+                info!("Resubmitting the settlement transaction after a revert");
+                self.nonce_info = None;
+                self.set_status(CertificateStatus::Proven)?;
+                return Box::pin(self.process_from_proven()).await;
+            }
             CertificateSettlementResult::TimeoutError => {
+                let error = CertificateStatusError::SettlementTimeout(
+                    self.retries(ErrorClass::L1Timeout).saturating_add(1),
+                );
+                if !self.wait_for_retry(ErrorClass::L1Timeout, &error).await? {
+                    return Err(error);
+                }
+
                 // Retry the settlement transaction
                 info!(
                     "Retrying the settlement transaction after a timeout for certificate \
@@ -588,6 +714,120 @@ where
         Ok(())
     }
 
+    /// Wait until the origin networks of the imported bridge exits have settled
+    /// them, so that the certificate never settles before its dependencies.
+    ///
+    /// Exits imported from L1 are settled by definition and are not checked.
+    async fn wait_for_imported_exits(&self) -> Result<(), CertificateStatusError> {
+        loop {
+            let unsettled = self.unsettled_import_origins()?;
+            if unsettled.is_empty() {
+                return Ok(());
+            }
+
+            warn!(
+                ?unsettled,
+                "Deferring the settlement until the imported bridge exits are settled by their \
+                 origin networks"
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(UNSETTLED_IMPORTS_RETRY_INTERVAL) => {}
+                _ = self.cancellation_token.cancelled() => {
+                    return Err(CertificateStatusError::InternalError(
+                        "Cancelled while waiting for the imported bridge exits to be settled"
+                            .into(),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Returns the origin networks which haven't settled yet all the bridge
+    /// exits imported by the certificate.
+    fn unsettled_import_origins(&self) -> Result<Vec<NetworkId>, CertificateStatusError> {
+        let mut required_leaf_counts = BTreeMap::<NetworkId, u32>::new();
+        for imported_bridge_exit in &self.certificate.imported_bridge_exits {
+            let global_index = imported_bridge_exit.global_index;
+            let decoded = validate_global_index(global_index).map_err(|error| {
+                CertificateStatusError::InternalError(format!(
+                    "Invalid global index {global_index:?}: {error}"
+                ))
+            })?;
+            let origin = decoded.network_id();
+            if origin == NetworkId::new(0) || origin == self.certificate.network_id {
+                continue;
+            }
+
+            let required = decoded.leaf_index.saturating_add(1);
+            let entry = required_leaf_counts.entry(origin).or_default();
+            *entry = (*entry).max(required);
+        }
+
+        let mut unsettled = Vec::new();
+        for (origin, required) in required_leaf_counts {
+            if self.state_store.read_local_exit_tree_leaf_count(origin)? < required {
+                unsettled.push(origin);
+            }
+        }
+
+        Ok(unsettled)
+    }
+
+    /// Number of retries of the certificate for the given class of failure.
+    fn retries(&self, class: ErrorClass) -> u32 {
+        self.retries.get(&class).copied().unwrap_or(0)
+    }
+
+    /// Whether the failure is retried according to the policy of its class,
+    /// in which case this waits for the backoff of the policy before
+    /// returning.
+    async fn wait_for_retry(
+        &mut self,
+        class: ErrorClass,
+        error: &impl std::fmt::Display,
+    ) -> Result<bool, CertificateStatusError> {
+        let policy = self.retry_policy.policy(class);
+        let retries = self.retries(class);
+        if !policy.allows_retry(retries) {
+            return Ok(false);
+        }
+
+        let backoff = policy.backoff;
+        let retry = retries.saturating_add(1);
+        self.retries.insert(class, retry);
+        warn!(
+            %error,
+            ?class,
+            retry,
+            "Retrying the certificate after {backoff:?}"
+        );
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => Ok(true),
+            _ = self.cancellation_token.cancelled() => {
+                Err(CertificateStatusError::InternalError(
+                    "Cancelled while waiting to retry the certificate".into(),
+                ))
+            }
+        }
+    }
+
+    /// Wait for the turn of the certificate in the proving queue, if any.
+    async fn wait_for_proving_turn(&self) -> Option<ProvingPermit> {
+        let (queue, priority) = self.proving_queue.as_ref()?;
+        debug!(
+            ?priority,
+            "Waiting for the turn of the certificate to be proven"
+        );
+
+        Some(queue.acquire(*priority).await)
+    }
+
+    fn record_proving_outcome(&self, succeeded: bool) {
+        if let Some(state) = &self.orchestrator_state {
+            state.record_proving_outcome(succeeded);
+        }
+    }
+
     fn set_status(&mut self, status: CertificateStatus) -> Result<(), CertificateStatusError> {
         self.state_store
             .update_certificate_header_status(&self.header.certificate_id, &status)?;
@@ -1,4 +1,7 @@
-use agglayer_types::{Certificate, Digest, Height, LocalNetworkStateData, NetworkId};
+use agglayer_types::{
+    Certificate, Digest, Height, LocalNetworkStateData, NativeExecutionReport, NetworkId,
+    ProvingCostEstimate,
+};
 use pessimistic_proof::{
     multi_batch_header::MultiBatchHeader, LocalNetworkState, PessimisticProofOutput,
 };
@@ -42,4 +45,36 @@ pub trait Certifier: Unpin + Send + Sync + 'static {
         state: &mut LocalNetworkStateData,
         certificate_tx_hash: Option<Digest>,
     ) -> Result<(MultiBatchHeader, LocalNetworkState, PessimisticProofOutput), CertificationError>;
+
+    /// Identifier of the prover generating the proofs, recorded in the audit
+    /// log of the certificates.
+    fn prover(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Estimate the cost of proving a certificate, without proving it.
+#[async_trait::async_trait]
+pub trait ProvingCostEstimator: Send + Sync + 'static {
+    /// Execute the pessimistic proof program on the certificate applied on
+    /// top of the given state, and estimate the cost and time of proving it
+    /// on the SP1 network out of the cycles spent.
+    async fn estimate_proving_cost(
+        &self,
+        state: LocalNetworkStateData,
+        certificate: &Certificate,
+    ) -> Result<ProvingCostEstimate, CertificationError>;
+}
+
+/// Apply a certificate natively, without executing the pessimistic proof
+/// program nor storing anything.
+#[async_trait::async_trait]
+pub trait NativeExecutor: Send + Sync + 'static {
+    /// Run the native `apply_batch_header` of the certificate on top of the
+    /// given state, reporting the resulting roots and the balances it moved.
+    async fn execute_natively(
+        &self,
+        state: LocalNetworkStateData,
+        certificate: &Certificate,
+    ) -> Result<NativeExecutionReport, CertificationError>;
 }
@@ -1,11 +1,12 @@
+use agglayer_config::certificate_orchestrator::retry_policy::ErrorClass;
 use agglayer_contracts::L1RpcError;
 use agglayer_types::{
     aggchain_proof::AggchainProofPublicValues, bincode, CertificateId, CertificateStatusError,
-    Digest, Height, NetworkId, SettlementTxHash,
+    Digest, Height, NetworkId, SettlementTxHash, U256,
 };
 use pessimistic_proof::{
     core::commitment::StateCommitment, error::ProofVerificationError, PessimisticProofOutput,
-    ProofError,
+    ProofError, PublicValuesError,
 };
 
 #[derive(thiserror::Error, Debug)]
@@ -59,6 +60,17 @@ pub enum CertificationError {
         sp1_zkvm_execution: Box<PessimisticProofOutput>,
     },
 
+    /// The public values of the proof submitted for the certificate differ
+    /// from the ones computed by the rust native execution.
+    #[error(
+        "Mismatch on the public values of the submitted proof. expected: {expected:?}, submitted: \
+         {submitted:?}"
+    )]
+    SubmittedProofPublicValuesMismatch {
+        expected: Box<PessimisticProofOutput>,
+        submitted: Box<PessimisticProofOutput>,
+    },
+
     #[error("Type error: {source}")]
     Types { source: agglayer_types::Error },
 
@@ -68,6 +80,10 @@ pub enum CertificationError {
     #[error("Deserialize error")]
     Deserialize { source: bincode::Error },
 
+    /// The public values of a proof don't encode a pessimistic proof output.
+    #[error("Invalid public values")]
+    InvalidPublicValues { source: PublicValuesError },
+
     #[error("Internal error: {0}")]
     InternalError(String),
 
@@ -80,6 +96,16 @@ pub enum CertificationError {
     #[error("Prover execution failed")]
     ProverExecutionFailed { source: ProofError },
 
+    /// The prover did not return the proof within the proving timeout of its
+    /// backend, the request being cancelled.
+    #[error("Proving timed out after {timeout:?}")]
+    ProvingTimeout { timeout: std::time::Duration },
+
+    /// The quota of the SP1 network was not freed up within the maximum
+    /// queue wait, the proof not being requested.
+    #[error("SP1 network quota exhausted, frees up in {retry_after:?}")]
+    ProverQuotaExhausted { retry_after: std::time::Duration },
+
     #[error("Storage error: {0}")]
     Storage(#[from] agglayer_storage::error::Error),
 
@@ -135,6 +161,39 @@ pub enum CertificationError {
     /// the L1.
     #[error("Unable to fetch the multisig context: {0}")]
     MultisigContextFetchFailed(#[source] L1RpcError),
+
+    /// Gas token of the network fail to be fetched from the L1.
+    #[error("Unable to fetch the gas token: {0}")]
+    GasTokenFetchFailed(#[source] L1RpcError),
+}
+
+impl CertificationError {
+    /// Whether the error is a failure of the prover itself, as opposed to the
+    /// certificate being rejected.
+    pub fn is_prover_failure(&self) -> bool {
+        matches!(
+            self,
+            CertificationError::ProverFailed(_)
+                | CertificationError::ProverReturnedUnspecifiedError
+                | CertificationError::ProverExecutionFailed { .. }
+                | CertificationError::ProvingTimeout { .. }
+        )
+    }
+
+    /// Class of the failure for the retry policies, if the error is a failure
+    /// of the prover.
+    pub fn error_class(&self) -> Option<ErrorClass> {
+        match self {
+            CertificationError::ProverFailed(_)
+            | CertificationError::ProverReturnedUnspecifiedError
+            | CertificationError::ProvingTimeout { .. } => Some(ErrorClass::ProverTransient),
+            CertificationError::ProverExecutionFailed { .. }
+            | CertificationError::ProofVerificationFailed { .. } => {
+                Some(ErrorClass::ProverPermanent)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl From<CertificationError> for CertificateStatusError {
@@ -167,6 +226,12 @@ impl From<CertificationError> for CertificateStatusError {
             CertificationError::Types { source } => {
                 CertificateStatusError::TypeConversionError(source)
             }
+            CertificationError::ProvingTimeout { timeout } => {
+                CertificateStatusError::ProvingTimeout(timeout.as_secs())
+            }
+            CertificationError::ProverQuotaExhausted { retry_after } => {
+                CertificateStatusError::ProverQuotaExhausted(retry_after.as_secs())
+            }
             error => {
                 let error = eyre::Error::from(error);
                 CertificateStatusError::InternalError(format!("{error:?}"))
@@ -217,6 +282,59 @@ pub enum Error {
         error: String,
     },
 
+    /// The settlement transaction of the certificate was mined but reverted.
+    #[error(
+        "Settlement transaction {settlement_tx_hash} of the certificate {certificate_id} reverted"
+    )]
+    SettlementReverted {
+        certificate_id: CertificateId,
+        settlement_tx_hash: SettlementTxHash,
+    },
+
+    #[error("Settlement of the certificate {certificate_id} timed out after {attempts} attempts")]
+    SettlementTimeout {
+        certificate_id: CertificateId,
+        attempts: u32,
+    },
+
+    /// The settlement account cannot cover the estimated cost of the
+    /// settlement transaction, which is deferred until it is funded.
+    #[error(
+        "Insufficient funds to settle the certificate {certificate_id}: balance {balance} wei, \
+         estimated cost {required} wei"
+    )]
+    InsufficientFunds {
+        certificate_id: CertificateId,
+        balance: U256,
+        required: U256,
+    },
+
+    /// The proof of the certificate is rejected by the verifier the network
+    /// settles with on L1, checked before submitting the settlement.
+    #[error(
+        "The proof of the certificate {certificate_id} is rejected by the L1 verifier: {error}"
+    )]
+    ProofRejectedByVerifier {
+        certificate_id: CertificateId,
+        error: String,
+    },
+
+    /// The proof of the certificate cannot be verified on L1, the settlement
+    /// being deferred until it can be.
+    #[error("The proof of the certificate {certificate_id} cannot be verified on L1: {error}")]
+    ProofVerificationUnavailable {
+        certificate_id: CertificateId,
+        error: String,
+    },
+
+    /// The settlement of the certificate was observed on L1 in another
+    /// transaction than the one being waited for.
+    #[error("The certificate {certificate_id} was settled on L1 through {settlement_tx_hash}")]
+    SettledThroughOtherTx {
+        certificate_id: CertificateId,
+        settlement_tx_hash: SettlementTxHash,
+    },
+
     #[error("Failed to persist the state after {certificate_id}: {error}")]
     PersistenceError {
         certificate_id: CertificateId,
@@ -261,6 +379,24 @@ impl From<Error> for CertificateStatusError {
                 CertificateStatusError::InternalError("NotFoundCertificateHeader".to_string())
             }
             Error::SettlementError { error, .. } => CertificateStatusError::SettlementError(error),
+            error @ Error::SettlementReverted { .. } => {
+                CertificateStatusError::SettlementError(error.to_string())
+            }
+            Error::SettlementTimeout { attempts, .. } => {
+                CertificateStatusError::SettlementTimeout(attempts)
+            }
+            error @ Error::InsufficientFunds { .. } => {
+                CertificateStatusError::SettlementError(error.to_string())
+            }
+            error @ Error::ProofRejectedByVerifier { .. } => {
+                CertificateStatusError::SettlementError(error.to_string())
+            }
+            error @ Error::ProofVerificationUnavailable { .. } => {
+                CertificateStatusError::SettlementError(error.to_string())
+            }
+            error @ Error::SettledThroughOtherTx { .. } => {
+                CertificateStatusError::InternalError(error.to_string())
+            }
             Error::PersistenceError { error, .. } => {
                 CertificateStatusError::InternalError(error.to_string())
             }
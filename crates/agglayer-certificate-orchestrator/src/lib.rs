@@ -7,6 +7,9 @@ use std::{
 };
 
 use agglayer_clock::{ClockRef, Event};
+use agglayer_config::certificate_orchestrator::{
+    epoch_participation::EpochParticipationConfig, retry_policy::RetryPolicyConfig,
+};
 use agglayer_storage::{
     columns::{
         latest_proven_certificate_per_network::ProvenCertificate,
@@ -17,12 +20,16 @@ use agglayer_storage::{
         PerEpochReader, PerEpochWriter, StateReader, StateWriter,
     },
 };
-use agglayer_types::{CertificateId, EpochNumber, Height, NetworkId};
+use agglayer_types::{CertificateId, EpochEvent, EpochNumber, Height, NetworkId};
 use arc_swap::ArcSwap;
 use futures_util::{stream::FuturesUnordered, FutureExt, Stream, StreamExt, TryFutureExt};
 use network_task::{NetworkTask, NewCertificate};
+use proving_queue::ProvingQueue;
 use tokio::{
-    sync::mpsc::{self, Receiver},
+    sync::{
+        broadcast,
+        mpsc::{self, Receiver},
+    },
     task::JoinHandle,
 };
 use tokio_util::sync::{CancellationToken, WaitForCancellationFutureOwned};
@@ -31,15 +38,25 @@ use tracing::{debug, error, warn};
 mod certificate_task;
 mod certifier;
 mod error;
+mod network_lock;
 mod network_task;
+mod proving_queue;
 mod settlement_client;
+mod state;
 
 #[cfg(test)]
 mod tests;
 
-pub use certifier::{CertificateInput, Certifier, CertifierOutput, CertifierResult};
+pub use certifier::{
+    CertificateInput, Certifier, CertifierOutput, CertifierResult, NativeExecutor,
+    ProvingCostEstimator,
+};
 pub use error::{CertificationError, Error, PreCertificationError};
 pub use settlement_client::{NonceInfo, SettlementClient, TxReceiptStatus};
+pub use state::{
+    CertificateStage, ClockState, EpochCursor, InFlightCertificate, NetworkTaskState,
+    OrchestratorSnapshot, OrchestratorState, ProvingStats,
+};
 
 const MAX_POLL_READS: usize = 1_000;
 
@@ -111,6 +128,21 @@ pub struct CertificateOrchestrator<
 
     /// Network task future resolver.
     network_tasks: NetworkTasks,
+
+    /// Sender of the epoch lifecycle events, if any.
+    epoch_events: Option<broadcast::Sender<EpochEvent>>,
+
+    /// View of the in-memory state, for diagnosis.
+    state: Arc<OrchestratorState>,
+
+    /// Queue limiting the number of certificates proven at the same time.
+    proving_queue: Arc<ProvingQueue>,
+
+    /// Retry policies of the certificates, per class of failure.
+    retry_policy: Arc<RetryPolicyConfig>,
+
+    /// Epochs in which each network settles its certificates.
+    epoch_participation: Arc<EpochParticipationConfig>,
 }
 
 impl<Sc, CertifierClient, PendingStore, EpochsStore, PerEpochStore, StateStore>
@@ -146,6 +178,7 @@ where
                 tokio_stream::wrappers::BroadcastStream::new(clock.subscribe()?),
                 |v| v.ok(),
             )),
+            state: Arc::new(OrchestratorState::new(clock.clone())),
             clock_ref: clock,
             settlement_client: Arc::new(settlement_client),
             certifier_task_builder: Arc::new(certifier_task_builder),
@@ -158,6 +191,10 @@ where
             state_store,
             spawned_network_tasks: Default::default(),
             network_tasks: FuturesUnordered::new(),
+            epoch_events: None,
+            proving_queue: Arc::new(ProvingQueue::new(0)),
+            retry_policy: Default::default(),
+            epoch_participation: Default::default(),
         })
     }
 }
@@ -190,6 +227,18 @@ where
     /// - `cancellation_token`: Sets the cancellation token for graceful
     ///   shutdown.
     /// - `epoch_packing_builder`: Sets the task builder for epoch packing.
+    /// - `epoch_events`: Optionally sets the sender of the epoch lifecycle
+    ///   events.
+    /// - `state`: Optionally sets the view of the in-memory state to update.
+    /// - `max_concurrent_proofs`: Optionally limits the number of certificates
+    ///   proven at the same time, the waiting ones being proven in order of
+    ///   their epoch deadline.
+    /// - `retry_policy`: Optionally sets the retry policies of the
+    ///   certificates, per class of failure. Only the L1 timeouts are retried
+    ///   by default.
+    /// - `epoch_participation`: Optionally restricts the settlement of some
+    ///   networks to every Nth epoch. Every network settles in every epoch by
+    ///   default.
     /// - `start`: Starts the CertificateOrchestrator.
     ///
     /// # Errors
@@ -209,6 +258,11 @@ where
         epochs_store: Arc<EpochsStore>,
         current_epoch: Arc<ArcSwap<PerEpochStore>>,
         state_store: Arc<StateStore>,
+        epoch_events: Option<broadcast::Sender<EpochEvent>>,
+        state: Option<Arc<OrchestratorState>>,
+        max_concurrent_proofs: Option<usize>,
+        retry_policy: Option<RetryPolicyConfig>,
+        epoch_participation: Option<EpochParticipationConfig>,
     ) -> eyre::Result<JoinHandle<()>> {
         let mut orchestrator = Self::try_new(
             clock,
@@ -221,6 +275,26 @@ where
             current_epoch,
             state_store,
         )?;
+        orchestrator.epoch_events = epoch_events;
+        if let Some(state) = state {
+            orchestrator.state = state;
+        }
+        if let Some(max_concurrent_proofs) = max_concurrent_proofs {
+            orchestrator.proving_queue = Arc::new(ProvingQueue::new(max_concurrent_proofs));
+        }
+        if let Some(retry_policy) = retry_policy {
+            orchestrator.retry_policy = Arc::new(retry_policy);
+        }
+        if let Some(epoch_participation) = epoch_participation {
+            orchestrator.epoch_participation = Arc::new(epoch_participation);
+        }
+        {
+            let current_epoch = orchestrator.current_epoch.load();
+            orchestrator.state.set_epoch(
+                current_epoch.get_epoch_number(),
+                current_epoch.is_epoch_packed(),
+            );
+        }
 
         // Try to spawn the certifier tasks for the next height of each network
         for ProvenCertificate(_, network_id, _height) in
@@ -269,7 +343,12 @@ where
             self.clock_ref.clone(),
             network_id,
             receiver,
-        )?;
+        )?
+        .with_orchestrator_state(self.state.clone())
+        .with_proving_queue(self.proving_queue.clone())
+        .with_network_lock(self.state.network_lock(network_id))
+        .with_retry_policy(self.retry_policy.clone())
+        .with_epoch_participation(self.epoch_participation.clone());
 
         let task_future = task
             .run(self.cancellation_token.clone())
@@ -277,11 +356,25 @@ where
             .boxed();
         self.network_tasks.push(task_future);
 
+        self.state.add_network(network_id, &sender);
         self.spawned_network_tasks.insert(network_id, sender);
 
         Ok(())
     }
 
+    /// Record an epoch lifecycle event in the event log, and notify the
+    /// subscribers, if any.
+    fn notify_epoch_event(&self, event: EpochEvent) {
+        if let Err(error) = self.state_store.record_epoch_event(event) {
+            error!(?error, ?event, "Failed to record the epoch event");
+        }
+
+        if let Some(sender) = &self.epoch_events {
+            // An error only means that there is no subscriber.
+            _ = sender.send(event);
+        }
+    }
+
     /// Function that receives the certificates cursor pushed by the RPC module.
     /// This function is responsible for:
     /// - Updating the cursors for the proofs that have been generated so far.
@@ -319,10 +412,14 @@ where
     /// event. The function is responsible for:
     /// - Opening the next epoch.
     /// - Spawning the epoch packing task.
+    /// - Notifying the epoch lifecycle events.
     fn handle_epoch_end(&mut self, epoch: EpochNumber) -> Result<(), Error> {
         debug!("Start the settlement of the epoch {}", epoch);
 
         let closing_epoch = self.current_epoch.load_full();
+        self.notify_epoch_event(EpochEvent::PackingStarted {
+            epoch_number: epoch,
+        });
         if let Err(error) = closing_epoch.start_packing() {
             error!("Failed to pack the epoch {}: {:?}", epoch, error);
 
@@ -345,6 +442,10 @@ where
             }
         }
 
+        self.notify_epoch_event(EpochEvent::Settled {
+            epoch_number: epoch,
+        });
+
         // TODO: Check for overflow
         let next_epoch = epoch.next();
 
@@ -352,7 +453,13 @@ where
             .epochs_store
             .open_with_start_checkpoint(next_epoch, closing_epoch.get_end_checkpoint())
         {
-            Ok(new_epoch) => self.current_epoch.store(Arc::new(new_epoch)),
+            Ok(new_epoch) => {
+                self.current_epoch.store(Arc::new(new_epoch));
+                self.state.set_epoch(next_epoch, false);
+                self.notify_epoch_event(EpochEvent::Opened {
+                    epoch_number: next_epoch,
+                });
+            }
             Err(error) => {
                 let msg = format!(
                     "CRITICAL error: Failed to open the next epoch {next_epoch}: {error:?}",
@@ -395,11 +502,13 @@ where
             Poll::Ready(Some(Ok(network_id))) => {
                 warn!("Network task for {} completed successfully", network_id);
                 _ = self.spawned_network_tasks.remove(&network_id);
+                self.state.remove_network(network_id);
             }
 
             Poll::Ready(Some(Err((network_id, error)))) => {
                 warn!("Network task for rollup {network_id} failed: {error:?}");
                 _ = self.spawned_network_tasks.remove(&network_id);
+                self.state.remove_network(network_id);
             }
             Poll::Ready(None) => {}
             Poll::Pending => {}
@@ -0,0 +1,36 @@
+//! Serialization of the certificates of a network through the certify and
+//! settle pipeline.
+//!
+//! A network task processes the certificates of its network one at a time,
+//! but the certificate task it spawns outlives it when it fails. The
+//! certificate task holds the lock of its network until it completes, so that
+//! the next network task spawned for the network waits for it before picking
+//! up the next certificate, while the other networks run concurrently.
+//!
+//! The lock is also held while cancelling a pending certificate of the
+//! network, so that its network task does not pick it up meanwhile.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use agglayer_types::NetworkId;
+use parking_lot::Mutex;
+
+#[cfg(test)]
+mod tests;
+
+/// Lock of a network, held while one of its certificates is in the pipeline.
+pub type NetworkLock = Arc<tokio::sync::Mutex<()>>;
+
+/// Locks of the networks, shared by the successive network tasks of each
+/// network.
+#[derive(Default)]
+pub(crate) struct NetworkLocks {
+    locks: Mutex<BTreeMap<NetworkId, NetworkLock>>,
+}
+
+impl NetworkLocks {
+    /// Lock of the given network.
+    pub(crate) fn get(&self, network_id: NetworkId) -> NetworkLock {
+        self.locks.lock().entry(network_id).or_default().clone()
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use agglayer_types::Height;
+use futures_util::FutureExt as _;
+use tokio::sync::mpsc;
+
+use super::*;
+
+#[tokio::test]
+async fn same_network_is_serialized() {
+    let locks = NetworkLocks::default();
+    let network_id = NetworkId::new(1);
+
+    let guard = locks.get(network_id).lock_owned().await;
+    assert!(locks.get(network_id).lock_owned().now_or_never().is_none());
+
+    drop(guard);
+    assert!(locks.get(network_id).lock_owned().now_or_never().is_some());
+}
+
+#[tokio::test]
+async fn networks_run_concurrently() {
+    let locks = NetworkLocks::default();
+
+    let _guard = locks.get(NetworkId::new(1)).lock_owned().await;
+    assert!(locks
+        .get(NetworkId::new(2))
+        .lock_owned()
+        .now_or_never()
+        .is_some());
+}
+
+#[tokio::test]
+async fn racing_certificates_at_consecutive_heights_dont_overlap() {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Event {
+        Started(Height),
+        Completed(Height),
+    }
+
+    let locks = Arc::new(NetworkLocks::default());
+    let network_id = NetworkId::new(1);
+    let (events_sender, mut events) = mpsc::unbounded_channel();
+
+    // The pipeline of the first height is slower than the one of the next
+    // height, which is spawned right after it.
+    let tasks = [(Height::ZERO, 50), (Height::new(1), 0)].map(|(height, millis)| {
+        let lock = locks.get(network_id);
+        let events_sender = events_sender.clone();
+        tokio::spawn(async move {
+            let _guard = lock.lock_owned().await;
+            events_sender.send(Event::Started(height)).unwrap();
+            tokio::time::sleep(Duration::from_millis(millis)).await;
+            events_sender.send(Event::Completed(height)).unwrap();
+        })
+    });
+    drop(events_sender);
+
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let mut order = Vec::new();
+    while let Some(event) = events.recv().await {
+        order.push(event);
+    }
+
+    assert_eq!(
+        order,
+        vec![
+            Event::Started(Height::ZERO),
+            Event::Completed(Height::ZERO),
+            Event::Started(Height::new(1)),
+            Event::Completed(Height::new(1)),
+        ]
+    );
+}
@@ -1,6 +1,9 @@
 use std::{collections::HashSet, sync::Arc};
 
 use agglayer_clock::ClockRef;
+use agglayer_config::certificate_orchestrator::{
+    epoch_participation::EpochParticipationConfig, retry_policy::RetryPolicyConfig,
+};
 use agglayer_storage::{
     columns::latest_settled_certificate_per_network::SettledCertificate,
     stores::{PendingCertificateReader, PendingCertificateWriter, StateReader, StateWriter},
@@ -17,7 +20,13 @@ use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use crate::{certificate_task::CertificateTask, Certifier, Error, NonceInfo, SettlementClient};
+use crate::{
+    certificate_task::CertificateTask,
+    network_lock::NetworkLock,
+    proving_queue::{ProvingPriority, ProvingQueue},
+    state::{CertificateStage, InFlightCertificate, NetworkTaskState, OrchestratorState},
+    Certifier, Error, NonceInfo, SettlementClient,
+};
 
 #[cfg(test)]
 mod tests;
@@ -68,7 +77,7 @@ pub enum NetworkTaskMessage {
         previous_tx_hashes: HashSet<SettlementTxHash>,
         new_pp_root: Digest,
         settlement_submitted_notifier:
-            oneshot::Sender<Result<(SettlementTxHash, Option<NonceInfo>), CertificateStatusError>>,
+            oneshot::Sender<Result<(SettlementTxHash, Option<NonceInfo>), Error>>,
     },
 
     /// Notify the network task that a certificate is waiting for settlement to
@@ -119,6 +128,9 @@ pub enum NetworkTaskMessage {
 pub enum CertificateSettlementResult {
     Settled(EpochNumber, CertificateIndex),
     TimeoutError,
+    /// The settlement transaction reverted, with the error to record if the
+    /// settlement isn't retried.
+    Reverted(CertificateStatusError),
     Error(CertificateStatusError),
     SettledThroughOtherTx(SettlementTxHash),
 }
@@ -147,6 +159,16 @@ pub(crate) struct NetworkTask<CertifierClient, SettlementClient, PendingStore, S
     at_capacity_for_epoch: bool,
     /// latest certificate settled
     latest_settled: Option<SettledCertificate>,
+    /// The orchestrator state to report the state of the network task to.
+    orchestrator_state: Option<Arc<OrchestratorState>>,
+    /// The queue limiting the number of certificates proven at the same time.
+    proving_queue: Option<Arc<ProvingQueue>>,
+    /// The lock held while a certificate of the network is in the pipeline.
+    network_lock: NetworkLock,
+    /// The retry policies of the certificates, per class of failure.
+    retry_policy: Arc<RetryPolicyConfig>,
+    /// The epochs in which the network settles its certificates.
+    epoch_participation: Arc<EpochParticipationConfig>,
 }
 
 impl<CertifierClient, Sc, PendingStore, StateStore>
@@ -196,9 +218,92 @@ where
             at_capacity_for_epoch: false,
             latest_settled,
             settlement_client,
+            orchestrator_state: None,
+            proving_queue: None,
+            network_lock: Default::default(),
+            retry_policy: Default::default(),
+            epoch_participation: Default::default(),
         })
     }
 
+    /// Report the state of the network task to the orchestrator state.
+    pub(crate) fn with_orchestrator_state(mut self, state: Arc<OrchestratorState>) -> Self {
+        self.orchestrator_state = Some(state);
+        self
+    }
+
+    /// Wait for a place in the proving queue before proving the certificates.
+    pub(crate) fn with_proving_queue(mut self, queue: Arc<ProvingQueue>) -> Self {
+        self.proving_queue = Some(queue);
+        self
+    }
+
+    /// Share the lock of the network with the previous network tasks of the
+    /// network, whose certificate tasks may still be running.
+    pub(crate) fn with_network_lock(mut self, lock: NetworkLock) -> Self {
+        self.network_lock = lock;
+        self
+    }
+
+    /// Retry the failures of the certificates according to the given policies.
+    pub(crate) fn with_retry_policy(mut self, retry_policy: Arc<RetryPolicyConfig>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Only settle the certificates of the network in the epochs allowed by
+    /// the given participation policy.
+    pub(crate) fn with_epoch_participation(
+        mut self,
+        epoch_participation: Arc<EpochParticipationConfig>,
+    ) -> Self {
+        self.epoch_participation = epoch_participation;
+        self
+    }
+
+    /// Whether the network may settle a certificate in the current epoch.
+    fn settles_in_current_epoch(&self) -> bool {
+        self.epoch_participation
+            .settles_in_epoch(self.network_id, self.clock_ref.current_epoch())
+    }
+
+    /// Priority of the certificates of the network in the proving queue: the
+    /// certificate should be proven by the end of the current epoch, and
+    /// comes after the ones of the networks without any certificate settled
+    /// in this epoch.
+    fn proving_priority(&self) -> ProvingPriority {
+        let current_epoch = self.clock_ref.current_epoch();
+
+        ProvingPriority {
+            deadline: current_epoch,
+            network_settled_in_epoch: matches!(
+                self.latest_settled,
+                Some(SettledCertificate(_, _, epoch, _)) if epoch == current_epoch
+            ),
+        }
+    }
+
+    fn update_orchestrator_state(&self, update: impl FnOnce(&mut NetworkTaskState)) {
+        if let Some(state) = &self.orchestrator_state {
+            state.update_network(self.network_id, update);
+        }
+    }
+
+    fn set_in_flight_stage(
+        &self,
+        certificate_id: CertificateId,
+        height: Height,
+        stage: CertificateStage,
+    ) {
+        self.update_orchestrator_state(|state| {
+            state.in_flight = Some(InFlightCertificate {
+                certificate_id,
+                height,
+                stage,
+            });
+        });
+    }
+
     #[tracing::instrument(
         name = "NetworkTask::run",
         skip_all,
@@ -236,9 +341,21 @@ where
                 Height::ZERO
             };
 
+        if !self.settles_in_current_epoch() {
+            debug!("Not settling in the epoch {current_epoch}");
+            self.at_capacity_for_epoch = true;
+        }
+
         let mut first_run = true;
 
         loop {
+            let at_capacity_for_epoch = self.at_capacity_for_epoch;
+            self.update_orchestrator_state(|state| {
+                state.next_expected_height = Some(next_expected_height);
+                state.at_capacity_for_epoch = at_capacity_for_epoch;
+                state.in_flight = None;
+            });
+
             tokio::select! {
                 // TODO (IN ANOTHER PR): move cancellation token to make_progress, have make_progess return ControlFlow?
                 _ = cancellation_token.cancelled() => {
@@ -269,6 +386,9 @@ where
     ) -> Result<(), Error> {
         if *first_run {
             *first_run = false;
+            if !self.settles_in_current_epoch() {
+                return Ok(());
+            }
         } else {
             tokio::select! {
                 event = stream_epoch.recv() => {
@@ -288,6 +408,11 @@ where
                                     warn!("Network {network_id} is at capacity for the epoch {current_epoch}");
                                     return Ok(());
                                 },
+                                _ if !self.settles_in_current_epoch() => {
+                                    debug!("Network {network_id} does not settle in the epoch {current_epoch}");
+                                    self.at_capacity_for_epoch = true;
+                                    return Ok(());
+                                },
                                 _ => {
                                     self.at_capacity_for_epoch = false;
                                 }
@@ -327,6 +452,11 @@ where
             }
         }
 
+        // Wait for the previous certificate of the network to leave the
+        // pipeline, in case its task outlived the network task which spawned
+        // it.
+        let network_guard = self.network_lock.clone().lock_owned().await;
+
         // Get the certificate the pending certificate for the network at the height
         let certificate = if let Some(certificate) = self
             .pending_store
@@ -348,6 +478,11 @@ where
         };
 
         let certificate_id = certificate.hash();
+        self.set_in_flight_stage(
+            certificate_id,
+            *next_expected_height,
+            CertificateStage::Certifying,
+        );
 
         let (sender, mut receiver) = mpsc::channel(1);
 
@@ -356,17 +491,23 @@ where
             .iter()
             .map(|exit| exit.hash())
             .collect::<Vec<Digest>>();
-        let task = tokio::spawn(
-            CertificateTask::new(
-                certificate,
-                sender,
-                self.state_store.clone(),
-                self.pending_store.clone(),
-                self.certifier_client.clone(),
-                cancellation_token.clone(),
-            )?
-            .process(),
-        );
+        let certificate_task = CertificateTask::new(
+            certificate,
+            sender,
+            self.state_store.clone(),
+            self.pending_store.clone(),
+            self.certifier_client.clone(),
+            cancellation_token.clone(),
+        )?
+        .with_orchestrator_state(self.orchestrator_state.clone())
+        .with_proving_queue(self.proving_queue.clone(), self.proving_priority())
+        .with_retry_policy(self.retry_policy.clone());
+        let task = tokio::spawn(async move {
+            // Keep the network locked until the certificate leaves the
+            // pipeline.
+            let _network_guard = network_guard;
+            certificate_task.process().await
+        });
 
         // The pending local network state that should be applied on receiving
         // settlement response.
@@ -390,6 +531,7 @@ where
                         continue;
                     }
                     Some(NetworkTaskMessage::CertificateProven { height, certificate_id }) => {
+                        self.set_in_flight_stage(certificate_id, height, CertificateStage::Proven);
                         if let Err(error) = self
                             .pending_store
                             .set_latest_proven_certificate_per_network(&self.network_id, &height, &certificate_id)
@@ -403,6 +545,7 @@ where
                     }
                     Some(NetworkTaskMessage::CertificateReadyForSettlement { settlement_submitted_notifier,
                         nonce_info, previous_tx_hashes, height, new_pp_root, .. }) => {
+                        self.set_in_flight_stage(certificate_id, height, CertificateStage::SubmittingSettlement);
                         // For now, the network task directly submits the settlement.
                         // In the future, with aggregation, all this will likely move to a separate epoch packer task.
                         // This is the reason why the certificate task does not directly submit and wait for settlement.
@@ -474,7 +617,7 @@ where
                         }
 
                         settlement_submitted_notifier
-                            .send(result.map_err(Into::into))
+                            .send(result)
                             .map_err(|_| Error::InternalError("Certificate notification channel closed".into()))?;
 
                         #[cfg(feature = "testutils")]
@@ -483,6 +626,7 @@ where
                     }
                     Some(NetworkTaskMessage::CertificateWaitingForSettlement { settlement_tx_hash, settlement_complete_notifier,
                         height, new_pp_root, ..}) => {
+                        self.set_in_flight_stage(certificate_id, height, CertificateStage::WaitingForSettlement);
                         let height = height.as_u64();
                         // See comment on CertificateReadyForSettlement.
                         let result = self
@@ -495,6 +639,12 @@ where
                                 // Certificate has been settled.
                                 CertificateSettlementResult::Settled(epoch, index)
                             }
+                            Err(Error::SettledThroughOtherTx { settlement_tx_hash: other_tx_hash, .. }) => {
+                                // The L1 events show the certificate settled through another transaction.
+                                info!(%certificate_id,
+                                    "Certificate for new height: {height} has been settled on L1 through other transaction {other_tx_hash}");
+                                CertificateSettlementResult::SettledThroughOtherTx(other_tx_hash)
+                            }
                             Err(Error::PendingTransactionTimeout { settlement_tx_hash, .. }) => {
                                 match self.settlement_client.fetch_settlement_receipt_status(settlement_tx_hash).await {
                                     Ok(crate::TxReceiptStatus::TxSuccessful) => {
@@ -532,6 +682,10 @@ where
                                 }
                             }
 
+                            Err(err @ Error::SettlementReverted { .. }) => {
+                                CertificateSettlementResult::Reverted(err.into())
+                            }
+
                             Err(err) => {
                                 CertificateSettlementResult::Error(err.into())
                             }
@@ -586,7 +740,7 @@ where
                     }
                     Some(NetworkTaskMessage::CertificateErrored { .. }) => {
                         // The certificate task already logged everything that should be logged.
-                        self.at_capacity_for_epoch = false;
+                        self.at_capacity_for_epoch = !self.settles_in_current_epoch();
                         break;
                     }
                     Some(NetworkTaskMessage::CheckSettlementTx { certificate_id,settlement_tx_hash, tx_mined_notifier }) => {
@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, sync::Mutex, time::Duration};
+use std::{collections::VecDeque, num::NonZeroU64, sync::Mutex, time::Duration};
 
 use agglayer_storage::{
     stores::{PendingCertificateReader, PendingCertificateWriter, StateWriter},
@@ -23,6 +23,7 @@ use crate::{
     CertificationError, CertifierOutput,
 };
 
+mod recovery;
 mod status;
 
 const SETTLEMENT_TX_HASH_1: SettlementTxHash = SettlementTxHash::new(Digest([1; 32]));
@@ -77,6 +78,8 @@ async fn start_from_zero() {
             }))
         });
 
+    state.expect_record_audit_event().returning(|_, _| Ok(()));
+
     certifier
         .expect_certify()
         .once()
@@ -275,6 +278,8 @@ async fn one_per_epoch() {
                 settlement_tx_hash: None,
             }))
         });
+    state.expect_record_audit_event().returning(|_, _| Ok(()));
+
     certifier
         .expect_certify()
         .once()
@@ -534,6 +539,8 @@ async fn retries() {
     });
     let response_certifier = Arc::new(Mutex::new(responses));
 
+    state.expect_record_audit_event().returning(|_, _| Ok(()));
+
     certifier
         .expect_certify()
         .times(2)
@@ -800,6 +807,8 @@ async fn changing_epoch_triggers_certify() {
             }))
         });
 
+    state.expect_record_audit_event().returning(|_, _| Ok(()));
+
     certifier
         .expect_certify()
         .once()
@@ -1136,6 +1145,111 @@ async fn timeout_certifier() {
     assert_eq!(next_expected_height, Height::ZERO);
 }
 
+#[rstest]
+#[tokio::test]
+#[timeout(Duration::from_secs(1))]
+async fn transient_prover_failure_is_retried() {
+    let mut pending = MockPendingStore::new();
+    let mut state = MockStateStore::new();
+    let mut certifier = MockCertifier::new();
+    let clock_ref = clock();
+    let network_id = 1.into();
+    let (sender, certificate_stream) = mpsc::channel(100);
+
+    let certificate = Certificate::new_for_test(network_id, Height::ZERO);
+    let certificate_id = certificate.hash();
+
+    pending
+        .expect_get_certificate()
+        .once()
+        .with(eq(network_id), eq(Height::ZERO))
+        .returning(|network_id, height| Ok(Some(Certificate::new_for_test(network_id, height))));
+
+    state
+        .expect_get_certificate_header()
+        .once()
+        .with(eq(certificate_id))
+        .returning(|certificate_id| {
+            Ok(Some(agglayer_types::CertificateHeader {
+                network_id: 1.into(),
+                height: Height::ZERO,
+                epoch_number: None,
+                certificate_index: None,
+                certificate_id: *certificate_id,
+                prev_local_exit_root: [1; 32].into(),
+                new_local_exit_root: [0; 32].into(),
+                metadata: Metadata::ZERO,
+                status: CertificateStatus::Pending,
+                settlement_tx_hash: None,
+            }))
+        });
+
+    // Retried once, then put in error.
+    certifier
+        .expect_certify()
+        .times(2)
+        .with(always(), eq(network_id), eq(Height::ZERO))
+        .returning(|_new_state, _network_id, _height| {
+            Err(CertificationError::ProverFailed("Unavailable".to_string()))
+        });
+
+    state
+        .expect_get_latest_settled_certificate_per_network()
+        .once()
+        .with(eq(network_id))
+        .returning(|_| Ok(None));
+
+    state
+        .expect_update_certificate_header_status()
+        .once()
+        .withf(move |id, status| {
+            *id == certificate_id && matches!(status, CertificateStatus::InError { .. })
+        })
+        .returning(|_, _| Ok(()));
+
+    state
+        .expect_read_local_network_state()
+        .returning(|_| Ok(Default::default()));
+
+    let mut retry_policy = RetryPolicyConfig::default();
+    retry_policy.prover_transient.retryable = true;
+    retry_policy.prover_transient.max_retries = Some(1);
+
+    let mut task = NetworkTask::new(
+        Arc::new(pending),
+        Arc::new(state),
+        Arc::new(certifier),
+        Arc::new(MockSettlementClient::new()),
+        clock_ref.clone(),
+        network_id,
+        certificate_stream,
+    )
+    .expect("Failed to create a new network task")
+    .with_retry_policy(Arc::new(retry_policy));
+
+    let mut epochs = task.clock_ref.subscribe().unwrap();
+    let mut next_expected_height = Height::ZERO;
+
+    sender
+        .send(NewCertificate {
+            certificate_id,
+            height: Height::ZERO,
+        })
+        .await
+        .expect("Failed to send the certificate");
+    let mut first_run = true;
+    task.make_progress(
+        &mut epochs,
+        &mut next_expected_height,
+        &mut first_run,
+        &CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(next_expected_height, Height::ZERO);
+}
+
 #[rstest]
 #[test_log::test(tokio::test)]
 #[timeout(Duration::from_secs(2))]
@@ -1205,6 +1319,7 @@ async fn process_next_certificate() {
                     PessimisticRootCommitmentVersion::V2,
                 ),
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                gas_token: None,
             };
 
             let _ = new_state
@@ -1386,3 +1501,74 @@ async fn epoch_jammed(#[values(false, true)] at_capacity: bool) {
     .unwrap();
     assert!(!task.at_capacity_for_epoch);
 }
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(1))]
+async fn settles_only_in_the_epochs_of_the_network() {
+    let mut pending = MockPendingStore::new();
+    let mut state = MockStateStore::new();
+    let clock_ref = clock();
+    let epoch_sender = clock_ref.get_sender();
+    let network_id = 1.into();
+    let (_sender, certificate_stream) = mpsc::channel(1);
+
+    state
+        .expect_read_local_network_state()
+        .returning(|_| Ok(Default::default()));
+
+    state
+        .expect_get_latest_settled_certificate_per_network()
+        .once()
+        .with(eq(network_id))
+        .returning(|_| Ok(None));
+
+    // Only looked up in the epoch 2, the network settling every other epoch.
+    pending
+        .expect_get_certificate()
+        .once()
+        .with(eq(network_id), eq(Height::ZERO))
+        .returning(|_, _| Ok(None));
+
+    let epoch_participation = EpochParticipationConfig {
+        settlement_interval: [(1, NonZeroU64::new(2).unwrap())].into(),
+    };
+
+    clock_ref.update_block_height(1);
+    let mut task = NetworkTask::new(
+        Arc::new(pending),
+        Arc::new(state),
+        Arc::new(MockCertifier::new()),
+        Arc::new(MockSettlementClient::new()),
+        clock_ref.clone(),
+        network_id,
+        certificate_stream,
+    )
+    .expect("Failed to create a new network task")
+    .with_epoch_participation(Arc::new(epoch_participation));
+
+    let mut epochs = task.clock_ref.subscribe().unwrap();
+    let mut next_expected_height = Height::ZERO;
+    let mut first_run = true;
+
+    for epoch in 1..=3 {
+        if epoch > 1 {
+            clock_ref.update_block_height(epoch);
+            epoch_sender
+                .send(agglayer_clock::Event::EpochEnded(EpochNumber::new(
+                    epoch - 1,
+                )))
+                .unwrap();
+        }
+
+        task.make_progress(
+            &mut epochs,
+            &mut next_expected_height,
+            &mut first_run,
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(task.at_capacity_for_epoch, epoch == 3);
+    }
+}
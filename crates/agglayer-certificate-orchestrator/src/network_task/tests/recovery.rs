@@ -0,0 +1,183 @@
+use std::{sync::Arc, time::Duration};
+
+use agglayer_storage::{
+    storage::backup::BackupClient,
+    stores::{
+        pending::PendingStore, state::StateStore, PendingCertificateWriter, StateReader,
+        StateWriter,
+    },
+    tests::{
+        faults::{Fault, Faults, FaultyStore, Operation, Trigger},
+        TempDBDir,
+    },
+};
+use agglayer_test_suite::{get_default_config, sample_data::USDC, Forest};
+use agglayer_types::{aggchain_data::CertificateAggchainDataCtx, L1WitnessCtx};
+use mockall::predicate::{always, eq};
+use pessimistic_proof::core::commitment::PessimisticRootCommitmentVersion;
+use rstest::rstest;
+
+use super::*;
+use crate::{
+    settlement_client::MockSettlementClient,
+    tests::{clock, mocks::MockCertifier},
+};
+
+#[rstest]
+#[test_log::test(tokio::test)]
+#[timeout(Duration::from_secs(2))]
+async fn failed_status_write_is_recovered_on_resubmission() {
+    let tmp = TempDBDir::new();
+    let config = get_default_config(&tmp.path);
+    let faults = Arc::new(Faults::default());
+    let state_store = Arc::new(FaultyStore::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop())
+            .expect("Failed to open the state store"),
+        faults.clone(),
+    ));
+    let pending_store = Arc::new(
+        PendingStore::new_with_path(&config.storage.pending_db_path)
+            .expect("Failed to open the pending store"),
+    );
+
+    let mut certifier = MockCertifier::new();
+    let clock_ref = clock();
+    let network_id = 1.into();
+    let (sender, certificate_stream) = mpsc::channel(100);
+
+    let mut forest = Forest::default();
+
+    let certificate = forest.apply_events(
+        &[(USDC, 10.try_into().unwrap())],
+        &[(USDC, 1.try_into().unwrap())],
+    );
+    let certificate_id = certificate.hash();
+    pending_store
+        .insert_pending_certificate(network_id, Height::ZERO, &certificate)
+        .expect("unable to insert certificate in pending");
+
+    state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Pending)
+        .expect("Failed to insert certificate header");
+
+    certifier
+        .expect_certify()
+        .times(2)
+        .with(always(), eq(network_id), eq(Height::ZERO))
+        .returning(move |mut new_state, network, height| {
+            let signer = agglayer_types::Address::new([0; 20]);
+            let ctx_from_l1 = L1WitnessCtx {
+                l1_info_root: certificate
+                    .l1_info_root()
+                    .expect("Failed to get L1 info root")
+                    .unwrap_or_default(),
+                prev_pessimistic_root: PessimisticRootInput::Computed(
+                    PessimisticRootCommitmentVersion::V2,
+                ),
+                aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                gas_token: None,
+            };
+
+            let _ = new_state
+                .apply_certificate(&certificate, ctx_from_l1)
+                .expect("Failed to apply certificate");
+
+            Ok(CertifierOutput {
+                certificate: certificate.clone(),
+                height,
+                new_state,
+                network,
+                new_pp_root: Digest::ZERO,
+            })
+        });
+
+    // Only the resubmitted certificate reaches the settlement.
+    let mut settlement_client = MockSettlementClient::new();
+    settlement_client
+        .expect_submit_certificate_settlement()
+        .once()
+        .withf(move |i, _| *i == certificate_id)
+        .returning(move |_, _| Ok(SettlementTxHash::for_tests()));
+    settlement_client
+        .expect_fetch_settlement_nonce()
+        .once()
+        .with(eq(SettlementTxHash::for_tests()))
+        .returning(|_| {
+            Ok(Some(NonceInfo {
+                nonce: 1,
+                previous_max_fee_per_gas: 0,
+                previous_max_priority_fee_per_gas: None,
+            }))
+        });
+    settlement_client
+        .expect_wait_for_settlement()
+        .once()
+        .withf(move |t, i| *t == SettlementTxHash::for_tests() && *i == certificate_id)
+        .returning(move |_, _| Ok((EpochNumber::ZERO, CertificateIndex::ZERO)));
+
+    let mut task = NetworkTask::new(
+        pending_store.clone(),
+        state_store.clone(),
+        Arc::new(certifier),
+        Arc::new(settlement_client),
+        clock_ref.clone(),
+        network_id,
+        certificate_stream,
+    )
+    .expect("Failed to create a new network task");
+
+    // Recording the certificate as proven fails once proven.
+    faults.inject(
+        Operation::Named("update_certificate_header_status"),
+        Trigger::Nth(1),
+        Fault::Error,
+    );
+
+    let mut epochs = task.clock_ref.subscribe().unwrap();
+    let mut next_expected_height = Height::ZERO;
+    let mut first_run = true;
+    task.make_progress(
+        &mut epochs,
+        &mut next_expected_height,
+        &mut first_run,
+        &CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+
+    // The failure is recorded on the certificate, which stays at its height.
+    assert_eq!(next_expected_height, Height::ZERO);
+    assert_eq!(faults.calls("update_certificate_header_status"), 2);
+    let header = state_store
+        .get_certificate_header(&certificate_id)
+        .unwrap()
+        .unwrap();
+    assert!(matches!(header.status, CertificateStatus::InError { .. }));
+
+    // The resubmission of the certificate goes through to the settlement.
+    state_store
+        .update_certificate_header_status(&certificate_id, &CertificateStatus::Pending)
+        .expect("Failed to reset the certificate status");
+    sender
+        .send(NewCertificate {
+            certificate_id,
+            height: Height::ZERO,
+        })
+        .await
+        .expect("Failed to send the certificate");
+    task.make_progress(
+        &mut epochs,
+        &mut next_expected_height,
+        &mut first_run,
+        &CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(next_expected_height, Height::new(1));
+    let header = state_store
+        .get_certificate_header(&certificate_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(header.status, CertificateStatus::Settled);
+}
@@ -69,6 +69,7 @@ async fn from_pending_to_settle() {
                     PessimisticRootCommitmentVersion::V2,
                 ),
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                gas_token: None,
             };
 
             let _ = new_state
@@ -203,6 +204,7 @@ async fn from_proven_to_settled() {
                     PessimisticRootCommitmentVersion::V2,
                 ),
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                gas_token: None,
             };
 
             let _ = new_state
@@ -339,6 +341,7 @@ async fn from_candidate_to_settle() {
                             PessimisticRootCommitmentVersion::V2,
                         ),
                         aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                        gas_token: None,
                     },
                 )
                 .unwrap();
@@ -0,0 +1,144 @@
+//! Limit on the number of certificates proven at the same time.
+//!
+//! When the limit is reached, the certificates waiting to be proven are served
+//! by order of priority instead of arrival, so that epochs don't close with
+//! provable but unproven certificates.
+
+use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc};
+
+use agglayer_types::EpochNumber;
+use parking_lot::Mutex;
+use tokio::sync::oneshot;
+
+#[cfg(test)]
+mod tests;
+
+/// Priority of a certificate waiting to be proven, the lowest being served
+/// first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ProvingPriority {
+    /// Epoch by the end of which the certificate should be proven, the closest
+    /// deadline being served first.
+    pub(crate) deadline: EpochNumber,
+    /// Whether the network already has a certificate settled in the epoch of
+    /// the deadline, which puts the certificate behind the ones of the other
+    /// networks.
+    pub(crate) network_settled_in_epoch: bool,
+}
+
+/// Queue granting the permission to prove a certificate.
+pub(crate) struct ProvingQueue {
+    /// Maximum number of certificates proven at the same time, 0 for no limit.
+    max_concurrent_proofs: usize,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Number of certificates being proven.
+    in_progress: usize,
+    /// Certificates waiting to be proven.
+    waiting: BinaryHeap<Reverse<Waiter>>,
+    /// Arrival order of the next waiting certificate, to serve certificates of
+    /// the same priority in order.
+    next_sequence: u64,
+}
+
+struct Waiter {
+    priority: ProvingPriority,
+    sequence: u64,
+    permit_sender: oneshot::Sender<ProvingPermit>,
+}
+
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Waiter {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.priority, self.sequence).cmp(&(other.priority, other.sequence))
+    }
+}
+
+/// Permission to prove a certificate, handed over to the next waiting
+/// certificate once dropped.
+pub(crate) struct ProvingPermit {
+    queue: Option<Arc<ProvingQueue>>,
+}
+
+impl Drop for ProvingPermit {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.release();
+        }
+    }
+}
+
+impl ProvingQueue {
+    pub(crate) fn new(max_concurrent_proofs: usize) -> Self {
+        Self {
+            max_concurrent_proofs,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Wait for the permission to prove a certificate of the given priority.
+    pub(crate) async fn acquire(self: &Arc<Self>, priority: ProvingPriority) -> ProvingPermit {
+        let permit_receiver = {
+            let mut inner = self.inner.lock();
+            let has_capacity =
+                self.max_concurrent_proofs == 0 || inner.in_progress < self.max_concurrent_proofs;
+            if has_capacity && inner.waiting.is_empty() {
+                inner.in_progress += 1;
+
+                return ProvingPermit {
+                    queue: Some(self.clone()),
+                };
+            }
+
+            let (permit_sender, permit_receiver) = oneshot::channel();
+            let sequence = inner.next_sequence;
+            inner.next_sequence += 1;
+            inner.waiting.push(Reverse(Waiter {
+                priority,
+                sequence,
+                permit_sender,
+            }));
+
+            permit_receiver
+        };
+
+        permit_receiver
+            .await
+            .expect("Waiting certificates are only dropped along with the queue")
+    }
+
+    /// Hand the permission over to the waiting certificate of highest
+    /// priority, or free it if there is none.
+    fn release(self: Arc<Self>) {
+        let mut inner = self.inner.lock();
+        while let Some(Reverse(waiter)) = inner.waiting.pop() {
+            let permit = ProvingPermit {
+                queue: Some(self.clone()),
+            };
+            match waiter.permit_sender.send(permit) {
+                Ok(()) => return,
+                // The waiting certificate is gone, its permit must not be
+                // released again.
+                Err(mut permit) => permit.queue = None,
+            }
+        }
+
+        inner.in_progress = inner.in_progress.saturating_sub(1);
+    }
+}
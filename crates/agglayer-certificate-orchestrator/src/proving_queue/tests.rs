@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use futures_util::FutureExt as _;
+use tokio::sync::mpsc;
+
+use super::*;
+
+fn priority(deadline: u64, network_settled_in_epoch: bool) -> ProvingPriority {
+    ProvingPriority {
+        deadline: EpochNumber::new(deadline),
+        network_settled_in_epoch,
+    }
+}
+
+async fn wait_for_waiting(queue: &ProvingQueue, expected: usize) {
+    while queue.inner.lock().waiting.len() != expected {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn unlimited_queue_never_waits() {
+    let queue = Arc::new(ProvingQueue::new(0));
+
+    let mut permits = Vec::new();
+    for _ in 0..10 {
+        let permit = queue.acquire(priority(0, false)).now_or_never();
+        permits.push(permit.expect("no limit on the proving"));
+    }
+
+    assert_eq!(queue.inner.lock().in_progress, 10);
+}
+
+#[tokio::test]
+async fn waiting_certificates_are_served_by_priority() {
+    let queue = Arc::new(ProvingQueue::new(1));
+    let permit = queue.acquire(priority(0, false)).await;
+
+    let (served_sender, mut served) = mpsc::unbounded_channel();
+    let priorities = [priority(2, false), priority(1, true), priority(1, false)];
+    for (index, priority) in priorities.into_iter().enumerate() {
+        let queue = queue.clone();
+        let served_sender = served_sender.clone();
+        tokio::spawn(async move {
+            let _permit = queue.acquire(priority).await;
+            served_sender.send(priority).unwrap();
+        });
+        wait_for_waiting(&queue, index + 1).await;
+    }
+
+    drop(permit);
+
+    let mut order = Vec::new();
+    for _ in 0..3 {
+        order.push(served.recv().await.unwrap());
+    }
+
+    // The closest deadline first, then the networks without any settled
+    // certificate in the epoch.
+    assert_eq!(
+        order,
+        vec![priority(1, false), priority(1, true), priority(2, false)]
+    );
+    assert_eq!(queue.inner.lock().in_progress, 0);
+}
+
+#[tokio::test]
+async fn dropped_waiting_certificate_releases_its_place() {
+    let queue = Arc::new(ProvingQueue::new(1));
+    let permit = queue.acquire(priority(0, false)).await;
+
+    tokio::time::timeout(Duration::from_millis(10), queue.acquire(priority(0, false)))
+        .await
+        .unwrap_err();
+    assert_eq!(queue.inner.lock().waiting.len(), 1);
+
+    drop(permit);
+    assert_eq!(queue.inner.lock().in_progress, 0);
+
+    assert!(queue.acquire(priority(0, false)).now_or_never().is_some());
+}
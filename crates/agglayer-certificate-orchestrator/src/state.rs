@@ -0,0 +1,206 @@
+//! View of the orchestrator in-memory state, kept up to date by the
+//! orchestrator and its network tasks to diagnose stuck certificates.
+
+use std::collections::BTreeMap;
+
+use agglayer_clock::ClockRef;
+use agglayer_types::{CertificateId, EpochNumber, Height, NetworkId};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::{
+    network_lock::{NetworkLock, NetworkLocks},
+    network_task::NewCertificate,
+};
+
+/// Stage of the certificate being processed by a network task.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CertificateStage {
+    /// The certificate is being executed and proven.
+    Certifying,
+    /// The proof of the certificate is generated.
+    Proven,
+    /// The settlement transaction is being submitted.
+    SubmittingSettlement,
+    /// The settlement transaction is submitted and waited for.
+    WaitingForSettlement,
+}
+
+/// Certificate being processed by a network task.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct InFlightCertificate {
+    pub certificate_id: CertificateId,
+    pub height: Height,
+    pub stage: CertificateStage,
+}
+
+/// State of a network task.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkTaskState {
+    /// Number of certificate notifications waiting to be handled.
+    pub queued_certificates: usize,
+    /// Height of the next certificate to process, unknown until the task
+    /// started.
+    pub next_expected_height: Option<Height>,
+    /// Whether a certificate of the network is already settled in the
+    /// current epoch.
+    pub at_capacity_for_epoch: bool,
+    /// Certificate being processed, if any.
+    pub in_flight: Option<InFlightCertificate>,
+}
+
+/// State of the clock.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ClockState {
+    pub block_height: u64,
+    pub current_epoch: EpochNumber,
+    /// Progress of the current epoch, between 0 and 1.
+    pub epoch_progress: f64,
+}
+
+/// Epoch the settled certificates are assigned to by the orchestrator.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochCursor {
+    pub epoch_number: EpochNumber,
+    pub packed: bool,
+}
+
+/// Outcomes of the proof generations since the orchestrator started.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProvingStats {
+    pub succeeded: u64,
+    /// Proof generations which failed because of the prover. Certificates
+    /// rejected during the certification are not counted.
+    pub failed: u64,
+}
+
+/// Snapshot of the orchestrator in-memory state.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OrchestratorSnapshot {
+    pub clock: ClockState,
+    /// Current epoch of the orchestrator, unknown until it started.
+    pub epoch: Option<EpochCursor>,
+    pub networks: BTreeMap<NetworkId, NetworkTaskState>,
+    pub proving: ProvingStats,
+}
+
+/// Network task tracked in the orchestrator state.
+struct NetworkEntry {
+    /// Queue of the certificate notifications sent to the task.
+    queue: mpsc::WeakSender<NewCertificate>,
+    state: NetworkTaskState,
+}
+
+/// Shared view of the orchestrator in-memory state.
+pub struct OrchestratorState {
+    clock: ClockRef,
+    epoch: Mutex<Option<EpochCursor>>,
+    networks: Mutex<BTreeMap<NetworkId, NetworkEntry>>,
+    proving: Mutex<ProvingStats>,
+    /// Locks serializing the certificates of each network through the
+    /// pipeline.
+    network_locks: NetworkLocks,
+}
+
+impl OrchestratorState {
+    pub fn new(clock: ClockRef) -> Self {
+        Self {
+            clock,
+            epoch: Mutex::new(None),
+            networks: Mutex::new(BTreeMap::new()),
+            proving: Mutex::new(ProvingStats::default()),
+            network_locks: NetworkLocks::default(),
+        }
+    }
+
+    /// Take a snapshot of the current state.
+    pub fn snapshot(&self) -> OrchestratorSnapshot {
+        let networks = self
+            .networks
+            .lock()
+            .iter()
+            .map(|(network_id, entry)| {
+                let queued_certificates = entry
+                    .queue
+                    .upgrade()
+                    .map(|queue| queue.max_capacity() - queue.capacity())
+                    .unwrap_or_default();
+
+                let state = NetworkTaskState {
+                    queued_certificates,
+                    ..entry.state.clone()
+                };
+
+                (*network_id, state)
+            })
+            .collect();
+
+        OrchestratorSnapshot {
+            clock: ClockState {
+                block_height: self.clock.current_block_height(),
+                current_epoch: self.clock.current_epoch(),
+                epoch_progress: self.clock.epoch_progress(),
+            },
+            epoch: *self.epoch.lock(),
+            networks,
+            proving: *self.proving.lock(),
+        }
+    }
+
+    /// Whether the certificate is being processed by its network task.
+    pub fn is_in_flight(&self, certificate_id: &CertificateId) -> bool {
+        self.networks.lock().values().any(|entry| {
+            entry
+                .state
+                .in_flight
+                .is_some_and(|in_flight| in_flight.certificate_id == *certificate_id)
+        })
+    }
+
+    /// Lock of the network, held by its network task from the pickup of a
+    /// certificate until the certificate leaves the pipeline.
+    pub fn network_lock(&self, network_id: NetworkId) -> NetworkLock {
+        self.network_locks.get(network_id)
+    }
+
+    pub(crate) fn set_epoch(&self, epoch_number: EpochNumber, packed: bool) {
+        *self.epoch.lock() = Some(EpochCursor {
+            epoch_number,
+            packed,
+        });
+    }
+
+    pub(crate) fn add_network(&self, network_id: NetworkId, queue: &mpsc::Sender<NewCertificate>) {
+        self.networks.lock().insert(
+            network_id,
+            NetworkEntry {
+                queue: queue.downgrade(),
+                state: NetworkTaskState::default(),
+            },
+        );
+    }
+
+    pub(crate) fn remove_network(&self, network_id: NetworkId) {
+        self.networks.lock().remove(&network_id);
+    }
+
+    pub(crate) fn update_network(
+        &self,
+        network_id: NetworkId,
+        update: impl FnOnce(&mut NetworkTaskState),
+    ) {
+        if let Some(entry) = self.networks.lock().get_mut(&network_id) {
+            update(&mut entry.state);
+        }
+    }
+
+    pub(crate) fn record_proving_outcome(&self, succeeded: bool) {
+        let mut proving = self.proving.lock();
+        if succeeded {
+            proving.succeeded += 1;
+        } else {
+            proving.failed += 1;
+        }
+    }
+}
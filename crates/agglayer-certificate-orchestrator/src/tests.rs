@@ -11,8 +11,14 @@ use agglayer_clock::ClockRef;
 use agglayer_config::Config;
 use agglayer_storage::{
     columns::{
+        api_key_usage::ApiKeyUsage,
+        audit_log_per_certificate::{AuditEvent, AuditRecord},
+        callback_per_certificate::CertificateCallback,
+        event_log::{EventId, LoggedEvent},
+        latest_pending_certificate_per_network::PendingCertificate,
         latest_proven_certificate_per_network::ProvenCertificate,
         latest_settled_certificate_per_network::SettledCertificate,
+        settlement_attempts_per_certificate::SettlementAttempt,
     },
     storage::backup::BackupClient,
     stores::{
@@ -26,17 +32,19 @@ use agglayer_storage::{
     },
 };
 use agglayer_types::{
-    Certificate, CertificateHeader, CertificateId, CertificateIndex, CertificateStatus, Digest,
-    EpochNumber, ExecutionMode, Height, LocalNetworkStateData, NetworkId, Proof, SettlementTxHash,
+    Address, Certificate, CertificateHeader, CertificateId, CertificateIndex, CertificateStatus,
+    Digest, EpochEvent, EpochNumber, EpochSettlementCosts, ExecutionMode, Height,
+    LocalNetworkStateData, NetworkId, Proof, SettlementTxHash,
 };
 use arc_swap::ArcSwap;
 use futures_util::poll;
 use mocks::MockCertifier;
 use pessimistic_proof::{
-    multi_batch_header::MultiBatchHeader, LocalNetworkState, PessimisticProofOutput,
+    local_state::StateCommitment, multi_batch_header::MultiBatchHeader, LocalNetworkState,
+    PessimisticProofOutput,
 };
 use rstest::fixture;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio_util::sync::CancellationToken;
 
 use crate::{
@@ -122,6 +130,20 @@ impl StateReader for DummyPendingStore {
         todo!()
     }
 
+    fn get_latest_pending_certificate_index(
+        &self,
+        _network_id: &NetworkId,
+    ) -> Result<Option<PendingCertificate>, agglayer_storage::error::Error> {
+        todo!()
+    }
+
+    fn get_latest_proven_certificate_index(
+        &self,
+        _network_id: &NetworkId,
+    ) -> Result<Option<ProvenCertificate>, agglayer_storage::error::Error> {
+        todo!()
+    }
+
     fn get_certificate_header(
         &self,
         certificate_id: &CertificateId,
@@ -133,6 +155,19 @@ impl StateReader for DummyPendingStore {
             .get(certificate_id)
             .cloned())
     }
+
+    fn multi_get_certificate_header(
+        &self,
+        certificate_ids: &[CertificateId],
+    ) -> Result<Vec<Option<CertificateHeader>>, agglayer_storage::error::Error> {
+        let certificate_headers = self.certificate_headers.read().unwrap();
+
+        Ok(certificate_ids
+            .iter()
+            .map(|certificate_id| certificate_headers.get(certificate_id).cloned())
+            .collect())
+    }
+
     fn get_current_settled_height(
         &self,
     ) -> Result<Vec<(NetworkId, SettledCertificate)>, agglayer_storage::error::Error> {
@@ -149,6 +184,79 @@ impl StateReader for DummyPendingStore {
             .collect()
     }
 
+    fn get_settlement_attempts(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<Vec<SettlementAttempt>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
+
+    fn get_settlement_sender(
+        &self,
+        _settlement_tx_hash: &SettlementTxHash,
+    ) -> Result<Option<Address>, agglayer_storage::error::Error> {
+        Ok(None)
+    }
+
+    fn get_reverted_settlements(
+        &self,
+        _epoch_number: EpochNumber,
+    ) -> Result<Vec<CertificateId>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
+
+    fn get_settlement_costs(
+        &self,
+        _network_id: NetworkId,
+        _from_epoch: EpochNumber,
+        _to_epoch: EpochNumber,
+    ) -> Result<Vec<EpochSettlementCosts>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
+
+    fn get_certificate_callbacks(
+        &self,
+    ) -> Result<Vec<(CertificateId, CertificateCallback)>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
+
+    fn get_certificate_callback(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<Option<CertificateCallback>, agglayer_storage::error::Error> {
+        Ok(None)
+    }
+
+    fn get_audit_log(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<Vec<AuditRecord>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
+
+    fn get_events(
+        &self,
+        _after: Option<EventId>,
+        _limit: usize,
+    ) -> Result<Vec<(EventId, LoggedEvent)>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
+
+    fn get_latest_event_id(&self) -> Result<Option<EventId>, agglayer_storage::error::Error> {
+        Ok(None)
+    }
+
+    fn subscribe_events(&self) -> watch::Receiver<Option<EventId>> {
+        watch::Sender::new(None).subscribe()
+    }
+
+    fn get_api_key_usage(
+        &self,
+        _period: u64,
+    ) -> Result<BTreeMap<String, ApiKeyUsage>, agglayer_storage::error::Error> {
+        Ok(BTreeMap::new())
+    }
+
     fn get_certificate_header_by_cursor(
         &self,
         network_id: NetworkId,
@@ -168,6 +276,27 @@ impl StateReader for DummyPendingStore {
     ) -> Result<Option<LocalNetworkStateData>, agglayer_storage::error::Error> {
         todo!()
     }
+
+    fn get_settled_roots(
+        &self,
+        _network_id: NetworkId,
+        _height: Height,
+    ) -> Result<Option<StateCommitment>, agglayer_storage::error::Error> {
+        todo!()
+    }
+
+    fn read_local_exit_tree_leaves(
+        &self,
+        _network_id: NetworkId,
+    ) -> Result<Vec<Digest>, agglayer_storage::error::Error> {
+        todo!()
+    }
+    fn read_local_exit_tree_leaf_count(
+        &self,
+        _network_id: NetworkId,
+    ) -> Result<u32, agglayer_storage::error::Error> {
+        Ok(0)
+    }
 }
 impl EpochStoreReader for DummyPendingStore {
     fn get_certificate(
@@ -186,6 +315,15 @@ impl EpochStoreReader for DummyPendingStore {
     ) -> Result<Option<Proof>, agglayer_storage::error::Error> {
         Ok(None)
     }
+
+    fn get_certificates(
+        &self,
+        _epoch_number: EpochNumber,
+        _from_index: CertificateIndex,
+        _limit: usize,
+    ) -> Result<Vec<Certificate>, agglayer_storage::error::Error> {
+        Ok(vec![])
+    }
 }
 
 impl EpochStoreWriter for DummyPendingStore {
@@ -235,6 +373,16 @@ impl PendingCertificateWriter for DummyPendingStore {
         Ok(())
     }
 
+    fn insert_certified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &agglayer_types::Proof,
+        _roots: &agglayer_storage::columns::certified_roots_per_certificate::CertifiedRoots,
+        _cache: bool,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        self.insert_generated_proof(certificate_id, proof)
+    }
+
     fn remove_pending_certificate(
         &self,
         network_id: NetworkId,
@@ -255,6 +403,45 @@ impl PendingCertificateWriter for DummyPendingStore {
 
         Ok(())
     }
+
+    fn insert_cached_proof(
+        &self,
+        _certificate_id: &CertificateId,
+        _initial_roots: &pessimistic_proof::local_state::StateCommitment,
+        _proof: &agglayer_types::Proof,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn remove_cached_proof(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn set_verified_proof(
+        &self,
+        _certificate_id: &CertificateId,
+        _proof_digest: &Digest,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn insert_submitted_proof(
+        &self,
+        _certificate_id: &CertificateId,
+        _proof: &agglayer_types::Proof,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn remove_submitted_proof(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
     fn set_latest_proven_certificate_per_network(
         &self,
         network_id: &NetworkId,
@@ -299,6 +486,83 @@ impl StateWriter for DummyPendingStore {
         todo!()
     }
 
+    fn record_settlement_attempt(
+        &self,
+        _certificate_id: &CertificateId,
+        _attempt: SettlementAttempt,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn record_settlement_sender(
+        &self,
+        _settlement_tx_hash: &SettlementTxHash,
+        _sender: Address,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn record_reverted_settlement(
+        &self,
+        _epoch_number: EpochNumber,
+        _certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn remove_reverted_settlements(
+        &self,
+        _epoch_number: EpochNumber,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn record_settlement_cost(
+        &self,
+        _network_id: NetworkId,
+        _epoch_number: EpochNumber,
+        _gas_used: u64,
+        _fees: u128,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn set_certificate_callback(
+        &self,
+        _certificate_id: &CertificateId,
+        _callback: &CertificateCallback,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn remove_certificate_callback(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn record_audit_event(
+        &self,
+        _certificate_id: &CertificateId,
+        _event: AuditEvent,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn record_epoch_event(&self, _event: EpochEvent) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn add_api_key_usage(
+        &self,
+        _api_key: &str,
+        _period: u64,
+        usage: &ApiKeyUsage,
+    ) -> Result<ApiKeyUsage, agglayer_storage::error::Error> {
+        Ok(*usage)
+    }
+
     fn assign_certificate_to_epoch(
         &self,
         _certificate_id: &CertificateId,
@@ -365,6 +629,24 @@ impl StateWriter for DummyPendingStore {
         Ok(())
     }
 
+    fn set_latest_pending_certificate_index(
+        &self,
+        _network_id: &NetworkId,
+        _height: &Height,
+        _certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
+    fn set_latest_proven_certificate_index(
+        &self,
+        _network_id: &NetworkId,
+        _height: &Height,
+        _certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        Ok(())
+    }
+
     fn write_local_network_state(
         &self,
         _network_id: &NetworkId,
@@ -389,6 +671,16 @@ impl PendingCertificateReader for DummyPendingStore {
         todo!()
     }
 
+    fn get_pending_networks(&self) -> Result<Vec<NetworkId>, agglayer_storage::error::Error> {
+        todo!()
+    }
+
+    fn get_pending_certificates(
+        &self,
+    ) -> Result<Vec<(NetworkId, Height, CertificateId)>, agglayer_storage::error::Error> {
+        todo!()
+    }
+
     fn get_current_proven_height(
         &self,
     ) -> Result<Vec<ProvenCertificate>, agglayer_storage::error::Error> {
@@ -432,6 +724,44 @@ impl PendingCertificateReader for DummyPendingStore {
         Ok(self.proofs.read().unwrap().get(&certificate_id).cloned())
     }
 
+    fn get_cached_proof(
+        &self,
+        _certificate_id: &CertificateId,
+        _initial_roots: &pessimistic_proof::local_state::StateCommitment,
+    ) -> Result<Option<agglayer_types::Proof>, agglayer_storage::error::Error> {
+        Ok(None)
+    }
+
+    fn get_submitted_proof(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<Option<agglayer_types::Proof>, agglayer_storage::error::Error> {
+        Ok(None)
+    }
+
+    fn get_verified_proof(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<Option<Digest>, agglayer_storage::error::Error> {
+        Ok(None)
+    }
+
+    fn get_certified_roots(
+        &self,
+        _certificate_id: &CertificateId,
+    ) -> Result<
+        Option<agglayer_storage::columns::certified_roots_per_certificate::CertifiedRoots>,
+        agglayer_storage::error::Error,
+    > {
+        Ok(None)
+    }
+
+    fn get_proven_certificate_ids(
+        &self,
+    ) -> Result<std::collections::BTreeSet<CertificateId>, agglayer_storage::error::Error> {
+        todo!()
+    }
+
     fn multi_get_certificate(
         &self,
         keys: &[(NetworkId, Height)],
@@ -588,6 +918,289 @@ async fn test_collect_certificates() {
     assert!(current_epoch.is_epoch_packed());
 }
 
+// Epoch lifecycle events are notified at the end of an epoch
+#[test_log::test(tokio::test)]
+async fn test_epoch_events() {
+    let path = TempDBDir::new();
+    let config = Config::new(&path.path);
+    let pending_store = Arc::new(
+        PendingStore::new_with_path(&config.storage.pending_db_path)
+            .expect("Unable to create store"),
+    );
+    let state_store = Arc::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop())
+            .expect("Unable to create store"),
+    );
+
+    let epochs_store = Arc::new(
+        EpochsStore::new(
+            Arc::new(config),
+            EpochNumber::ZERO,
+            pending_store.clone(),
+            state_store.clone(),
+            BackupClient::noop(),
+        )
+        .expect("Unable to create store"),
+    );
+
+    let current_epoch = ArcSwap::new(Arc::new(
+        epochs_store
+            .open(EpochNumber::new(1))
+            .expect("Unable to open epoch"),
+    ));
+    let (clock_sender, _receiver) = broadcast::channel(1);
+    let clock = ClockRef::new(
+        clock_sender.clone(),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(NonZeroU64::new(1).unwrap()),
+    );
+    let (_data_sender, data_receiver) = mpsc::channel(10);
+    let cancellation_token = CancellationToken::new();
+
+    let (check_sender, _check_receiver) = mpsc::channel(1);
+    let check = Check::builder()
+        .pending_store(pending_store.clone())
+        .state_store(state_store.clone())
+        .executed(check_sender)
+        .build();
+
+    let mut orchestrator = CertificateOrchestrator::try_new(
+        clock,
+        data_receiver,
+        cancellation_token,
+        check.clone(),
+        check.clone(),
+        pending_store.clone(),
+        epochs_store,
+        Arc::new(current_epoch),
+        state_store.clone(),
+    )
+    .expect("Unable to create orchestrator");
+
+    let (epoch_events, mut epoch_events_receiver) = broadcast::channel(10);
+    orchestrator.epoch_events = Some(epoch_events);
+
+    _ = clock_sender.send(agglayer_clock::Event::EpochEnded(EpochNumber::new(1)));
+
+    let _poll = poll!(&mut orchestrator);
+
+    assert_eq!(
+        epoch_events_receiver.try_recv().unwrap(),
+        EpochEvent::PackingStarted {
+            epoch_number: EpochNumber::new(1)
+        }
+    );
+    assert_eq!(
+        epoch_events_receiver.try_recv().unwrap(),
+        EpochEvent::Settled {
+            epoch_number: EpochNumber::new(1)
+        }
+    );
+    assert_eq!(
+        epoch_events_receiver.try_recv().unwrap(),
+        EpochEvent::Opened {
+            epoch_number: EpochNumber::new(2)
+        }
+    );
+    assert!(epoch_events_receiver.try_recv().is_err());
+}
+
+// The orchestrator state reflects the spawned network tasks and the current
+// epoch
+#[test_log::test(tokio::test)]
+async fn test_orchestrator_state() {
+    let path = TempDBDir::new();
+    let config = Config::new(&path.path);
+    let pending_store = Arc::new(
+        PendingStore::new_with_path(&config.storage.pending_db_path)
+            .expect("Unable to create store"),
+    );
+    let state_store = Arc::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop())
+            .expect("Unable to create store"),
+    );
+
+    let epochs_store = Arc::new(
+        EpochsStore::new(
+            Arc::new(config),
+            EpochNumber::ZERO,
+            pending_store.clone(),
+            state_store.clone(),
+            BackupClient::noop(),
+        )
+        .expect("Unable to create store"),
+    );
+
+    let current_epoch = ArcSwap::new(Arc::new(
+        epochs_store
+            .open(EpochNumber::new(1))
+            .expect("Unable to open epoch"),
+    ));
+    let (clock_sender, _receiver) = broadcast::channel(1);
+    let clock = ClockRef::new(
+        clock_sender.clone(),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(NonZeroU64::new(1).unwrap()),
+    );
+    let (data_sender, data_receiver) = mpsc::channel(10);
+    let cancellation_token = CancellationToken::new();
+
+    let (check_sender, _check_receiver) = mpsc::channel(1);
+    let check = Check::builder()
+        .pending_store(pending_store.clone())
+        .state_store(state_store.clone())
+        .executed(check_sender)
+        .build();
+
+    let mut orchestrator = CertificateOrchestrator::try_new(
+        clock,
+        data_receiver,
+        cancellation_token,
+        check.clone(),
+        check.clone(),
+        pending_store.clone(),
+        epochs_store,
+        Arc::new(current_epoch),
+        state_store.clone(),
+    )
+    .expect("Unable to create orchestrator");
+
+    _ = data_sender
+        .send((1.into(), Height::new(1), CertificateId::new([0; 32].into())))
+        .await;
+    _ = clock_sender.send(agglayer_clock::Event::EpochEnded(EpochNumber::new(1)));
+
+    let _poll = poll!(&mut orchestrator);
+
+    let snapshot = orchestrator.state.snapshot();
+    assert!(snapshot.networks.contains_key(&NetworkId::new(1)));
+    assert_eq!(
+        snapshot.epoch,
+        Some(crate::EpochCursor {
+            epoch_number: EpochNumber::new(2),
+            packed: false,
+        })
+    );
+    assert_eq!(snapshot.proving, crate::ProvingStats::default());
+}
+
+// Certificates of several networks importing each other's bridge exits are all
+// certified by their network task
+#[test_log::test(tokio::test)]
+async fn test_multi_network_scenario() {
+    use agglayer_test_suite::{
+        sample_data::{ETH, USDC},
+        MultiNetworkForest,
+    };
+
+    let path = TempDBDir::new();
+    let config = Config::new(&path.path);
+    let pending_store = Arc::new(
+        PendingStore::new_with_path(&config.storage.pending_db_path)
+            .expect("Unable to create store"),
+    );
+    let state_store = Arc::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop())
+            .expect("Unable to create store"),
+    );
+    let epochs_store = Arc::new(
+        EpochsStore::new(
+            Arc::new(config),
+            EpochNumber::ZERO,
+            pending_store.clone(),
+            state_store.clone(),
+            BackupClient::noop(),
+        )
+        .expect("Unable to create store"),
+    );
+    let current_epoch = ArcSwap::new(Arc::new(
+        epochs_store
+            .open(EpochNumber::ZERO)
+            .expect("Unable to open epoch"),
+    ));
+
+    let (n1, n2, n3) = (NetworkId::new(1), NetworkId::new(2), NetworkId::new(3));
+    let amount = |x: u64| x.try_into().unwrap();
+    let mut scenario = MultiNetworkForest::new()
+        .with_network(n1, [(USDC, amount(100)), (ETH, amount(100))])
+        .with_network(n2, [(USDC, amount(100))])
+        .with_network(n3, []);
+    scenario
+        .bridge(n1, n2, USDC, amount(10))
+        .bridge(n1, n3, ETH, amount(20))
+        .bridge(n2, n3, USDC, amount(30));
+    let certificates = scenario.next_round();
+
+    let (check_sender, mut check_receiver) = mpsc::channel(certificates.len());
+    let check = Check::builder()
+        .pending_store(pending_store.clone())
+        .state_store(state_store.clone())
+        .executed(check_sender)
+        .build();
+
+    let (clock_sender, _receiver) = broadcast::channel(1);
+    let clock = ClockRef::new(
+        clock_sender,
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(NonZeroU64::new(1).unwrap()),
+    );
+    let (data_sender, data_receiver) = mpsc::channel(10);
+    let cancellation_token = CancellationToken::new();
+    let _handle = CertificateOrchestrator::builder()
+        .clock(clock)
+        .data_receiver(data_receiver)
+        .cancellation_token(cancellation_token.clone())
+        .settlement_client(check.clone())
+        .certifier_task_builder(check)
+        .pending_store(pending_store.clone())
+        .epochs_store(epochs_store)
+        .current_epoch(Arc::new(current_epoch))
+        .state_store(state_store.clone())
+        .start()
+        .await
+        .expect("Unable to start orchestrator");
+
+    for certificate in &certificates {
+        pending_store
+            .insert_pending_certificate(certificate.network_id, certificate.height, certificate)
+            .expect("unable to insert certificate in pending");
+        state_store
+            .insert_certificate_header(certificate, CertificateStatus::Pending)
+            .expect("Failed to insert certificate header");
+        data_sender
+            .send((
+                certificate.network_id,
+                certificate.height,
+                certificate.hash(),
+            ))
+            .await
+            .expect("Failed to send the certificate");
+    }
+
+    let mut certified = BTreeMap::new();
+    for _ in 0..certificates.len() {
+        let output = tokio::time::timeout(std::time::Duration::from_secs(5), check_receiver.recv())
+            .await
+            .expect("Timeout waiting for certification")
+            .expect("Check channel closed");
+        certified.insert((output.network, output.height), output.certificate.hash());
+    }
+
+    let expected: BTreeMap<_, _> = certificates
+        .iter()
+        .map(|certificate| {
+            (
+                (certificate.network_id, certificate.height),
+                certificate.hash(),
+            )
+        })
+        .collect();
+
+    assert_eq!(certified, expected);
+
+    cancellation_token.cancel();
+}
+
 // A certificate received after an EpochEnded is stored for next epoch
 #[tokio::test]
 #[ignore]
@@ -15,9 +15,11 @@ use agglayer_types::EpochNumber;
 use tokio::sync::broadcast;
 
 mod block;
+mod manual;
 mod time;
 
 pub use block::BlockClock;
+pub use manual::{ManualClock, ManualClockHandle};
 pub use time::TimeClock;
 use tokio_util::sync::CancellationToken;
 
@@ -91,6 +93,11 @@ impl ClockRef {
         self.block_height.load(Ordering::Acquire)
     }
 
+    /// Returns the number of Blocks per Epoch.
+    pub fn epoch_duration(&self) -> NonZeroU64 {
+        *self.block_per_epoch
+    }
+
     /// Returns progress information about the current epoch
     pub fn epoch_progress(&self) -> f64 {
         let current_block = self.current_block_height();
@@ -113,4 +120,8 @@ pub enum Error {
     UnableToStart,
     #[error(transparent)]
     BlockClock(#[from] block::BlockClockError),
+    #[error("The Block height overflowed the u64 limit")]
+    BlockHeightOverflow,
+    #[error("Ending {0} Epochs at once exceeds the subscribers capacity")]
+    TooManyEpochs(u64),
 }
@@ -0,0 +1,165 @@
+use std::{
+    num::NonZeroU64,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use agglayer_types::EpochNumber;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error};
+
+use crate::{Clock, ClockRef, Error, Event, BROADCAST_CHANNEL_SIZE};
+
+/// Manually driven [`Clock`] implementation.
+///
+/// The Block height only increases when requested through a
+/// [`ManualClockHandle`], allowing tests and devnets to end the Epochs
+/// deterministically.
+pub struct ManualClock {
+    handle: ManualClockHandle,
+}
+
+/// Handle advancing the Block height of a [`ManualClock`].
+#[derive(Clone)]
+pub struct ManualClockHandle {
+    sender: broadcast::Sender<Event>,
+    block_height: Arc<AtomicU64>,
+    epoch_duration: Arc<NonZeroU64>,
+    /// Serializes the advances so that the Epochs end in order.
+    advance_lock: Arc<Mutex<()>>,
+}
+
+#[async_trait::async_trait]
+impl Clock for ManualClock {
+    async fn spawn(self, _cancellation_token: CancellationToken) -> Result<ClockRef, Error> {
+        Ok(ClockRef {
+            sender: self.handle.sender.clone(),
+            block_height: self.handle.block_height.clone(),
+            block_per_epoch: self.handle.epoch_duration.clone(),
+        })
+    }
+}
+
+impl ManualClock {
+    /// Create a new [`ManualClock`] instance starting at Block 0 with an Epoch
+    /// duration in Blocks.
+    pub fn new(epoch_duration: NonZeroU64) -> Self {
+        let (sender, _receiver) = broadcast::channel(BROADCAST_CHANNEL_SIZE);
+
+        Self {
+            handle: ManualClockHandle {
+                sender,
+                block_height: Arc::new(AtomicU64::new(0)),
+                epoch_duration: Arc::new(epoch_duration),
+                advance_lock: Arc::default(),
+            },
+        }
+    }
+
+    /// Returns a handle to advance the Block height of this Clock.
+    pub fn handle(&self) -> ManualClockHandle {
+        self.handle.clone()
+    }
+}
+
+impl ManualClockHandle {
+    /// Advance the Block height by `blocks`, broadcasting an `EpochEnded`
+    /// event for every Epoch boundary crossed.
+    ///
+    /// Returns the ended Epochs. Ending more Epochs at once than the
+    /// subscribers can buffer is rejected.
+    pub fn advance(&self, blocks: u64) -> Result<Vec<EpochNumber>, Error> {
+        let _guard = self.advance_lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let from = self.block_height.load(Ordering::Acquire);
+        let to = from.checked_add(blocks).ok_or(Error::BlockHeightOverflow)?;
+        let from_epoch = ManualClock::calculate_epoch_number(from, *self.epoch_duration);
+        let to_epoch = ManualClock::calculate_epoch_number(to, *self.epoch_duration);
+        if to_epoch - from_epoch > BROADCAST_CHANNEL_SIZE as u64 {
+            return Err(Error::TooManyEpochs(to_epoch - from_epoch));
+        }
+
+        self.block_height.store(to, Ordering::Release);
+        debug!(from, to, "Manual clock advanced");
+
+        let ended: Vec<EpochNumber> = (from_epoch..to_epoch).map(EpochNumber::new).collect();
+        for epoch in &ended {
+            if let Err(error) = self.sender.send(Event::EpochEnded(*epoch)) {
+                error!("Failed to send EpochEnded event to subscribers: {error}");
+            }
+        }
+
+        Ok(ended)
+    }
+
+    /// Advance the Block height to the start of the next Epoch, ending the
+    /// current one.
+    pub fn end_epoch(&self) -> Result<EpochNumber, Error> {
+        let current_block = self.block_height.load(Ordering::Acquire);
+        let remaining = self.epoch_duration.get() - current_block % *self.epoch_duration;
+
+        self.advance(remaining)?
+            .pop()
+            .ok_or(Error::BlockHeightOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use agglayer_types::EpochNumber;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{Clock, Event, ManualClock, BROADCAST_CHANNEL_SIZE};
+
+    #[tokio::test]
+    async fn epochs_end_only_when_advanced() {
+        let clock = ManualClock::new(NonZeroU64::new(3).unwrap());
+        let handle = clock.handle();
+        let clock_ref = clock.spawn(CancellationToken::new()).await.unwrap();
+        let mut recv = clock_ref.subscribe().unwrap();
+
+        assert_eq!(handle.advance(2).unwrap(), vec![]);
+        assert!(recv.try_recv().is_err());
+        assert_eq!(clock_ref.current_block_height(), 2);
+        assert_eq!(clock_ref.current_epoch(), EpochNumber::ZERO);
+
+        assert_eq!(
+            handle.advance(5).unwrap(),
+            vec![EpochNumber::new(0), EpochNumber::new(1)]
+        );
+        assert_eq!(recv.try_recv(), Ok(Event::EpochEnded(EpochNumber::new(0))));
+        assert_eq!(recv.try_recv(), Ok(Event::EpochEnded(EpochNumber::new(1))));
+        assert_eq!(clock_ref.current_epoch(), EpochNumber::new(2));
+
+        assert_eq!(handle.end_epoch().unwrap(), EpochNumber::new(2));
+        assert_eq!(recv.try_recv(), Ok(Event::EpochEnded(EpochNumber::new(2))));
+        assert_eq!(clock_ref.current_block_height(), 9);
+        assert_eq!(clock_ref.current_epoch(), EpochNumber::new(3));
+    }
+
+    #[tokio::test]
+    async fn overflow_is_rejected() {
+        let clock = ManualClock::new(NonZeroU64::MAX);
+        let handle = clock.handle();
+
+        handle.advance(u64::MAX).unwrap();
+        assert!(handle.advance(1).is_err());
+    }
+
+    #[tokio::test]
+    async fn too_many_epochs_are_rejected() {
+        let clock = ManualClock::new(NonZeroU64::new(1).unwrap());
+        let handle = clock.handle();
+
+        assert!(handle.advance(BROADCAST_CHANNEL_SIZE as u64 + 1).is_err());
+        assert_eq!(
+            handle.advance(BROADCAST_CHANNEL_SIZE as u64).unwrap().len(),
+            BROADCAST_CHANNEL_SIZE
+        );
+    }
+}
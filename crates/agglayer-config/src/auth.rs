@@ -31,6 +31,22 @@ impl Default for AuthConfig {
     }
 }
 
+/// Rotation of the key signing the settlement transactions.
+///
+/// The settlements of the epochs from the activation epoch on are signed with
+/// the next key, which has to be granted the trusted aggregator role on L1
+/// beforehand. The settlements of the previous epochs, replacements included,
+/// keep being signed with the current key.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct AuthRotationConfig {
+    /// The first epoch whose settlements are signed with the next key.
+    pub activation_epoch: u64,
+
+    /// The next key.
+    pub next: AuthConfig,
+}
+
 /// Local configuration.
 ///
 /// It includes private keys for a local wallet.
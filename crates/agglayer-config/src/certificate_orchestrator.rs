@@ -1,7 +1,19 @@
+use std::path::PathBuf;
+
+use epoch_participation::EpochParticipationConfig;
+use pending_expiry::PendingExpiryConfig;
 use prover::ProverConfig;
+use retry_policy::RetryPolicyConfig;
 use serde::{Deserialize, Serialize};
+use sp1_network_pricing::Sp1NetworkPricing;
+use sp1_network_quota::Sp1NetworkQuota;
 
+pub mod epoch_participation;
+pub mod pending_expiry;
 pub mod prover;
+pub mod retry_policy;
+pub mod sp1_network_pricing;
+pub mod sp1_network_quota;
 
 /// The CertificateOrchestrator configuration.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -12,6 +24,59 @@ pub struct CertificateOrchestrator {
 
     #[serde(default = "default_prover_config_default")]
     pub prover: ProverConfig,
+
+    /// Maximum number of certificates proven at the same time. Once reached,
+    /// the waiting certificates are proven in order of their epoch deadline.
+    /// Set to 0 to not limit the proving.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub max_concurrent_proofs: usize,
+
+    /// Number of proofs verified concurrently, off the certification of the
+    /// certificates. The proofs waiting for a worker are verified in order of
+    /// arrival.
+    #[serde(
+        default = "default_verification_workers",
+        skip_serializing_if = "same_as_default_verification_workers"
+    )]
+    pub verification_workers: usize,
+
+    /// Pricing of the SP1 network, used to estimate the cost of proving the
+    /// candidate certificates. Only the cycles are estimated when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sp1_network_pricing: Option<Sp1NetworkPricing>,
+
+    /// Quota of the proofs requested from the SP1 network. The proving is
+    /// not throttled when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sp1_network_quota: Option<Sp1NetworkQuota>,
+
+    /// Directory in which the SP1 stdin of the certificates whose proving
+    /// fails is written, as `<certificate_id>.stdin`, to be replayed locally
+    /// with the `replay-proof` command. Relative paths are resolved from the
+    /// directory of the configuration file. Nothing is captured when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_proof_stdin_dir: Option<PathBuf>,
+
+    /// Maximum number of failed certifications whose inputs are recorded in
+    /// the debug storage, the oldest ones being dropped first. Only recorded
+    /// in debug mode.
+    #[serde(
+        default = "default_max_certification_failures",
+        skip_serializing_if = "same_as_default_max_certification_failures"
+    )]
+    pub max_certification_failures: usize,
+
+    /// Expiry of the certificates staying pending without being certified.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub pending_expiry: PendingExpiryConfig,
+
+    /// Retry policies of the certificates, per class of failure.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub retry_policy: RetryPolicyConfig,
+
+    /// Epochs in which each network settles its certificates.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub epoch_participation: EpochParticipationConfig,
 }
 
 impl Default for CertificateOrchestrator {
@@ -19,6 +84,15 @@ impl Default for CertificateOrchestrator {
         Self {
             input_backpressure_buffer_size: default_input_backpressure_buffer_size_default(),
             prover: default_prover_config_default(),
+            max_concurrent_proofs: 0,
+            verification_workers: default_verification_workers(),
+            sp1_network_pricing: None,
+            sp1_network_quota: None,
+            failed_proof_stdin_dir: None,
+            max_certification_failures: default_max_certification_failures(),
+            pending_expiry: PendingExpiryConfig::default(),
+            retry_policy: RetryPolicyConfig::default(),
+            epoch_participation: EpochParticipationConfig::default(),
         }
     }
 }
@@ -27,7 +101,23 @@ fn default_input_backpressure_buffer_size_default() -> usize {
     1_000
 }
 
+const fn default_verification_workers() -> usize {
+    2
+}
+
+const fn same_as_default_verification_workers(v: &usize) -> bool {
+    *v == default_verification_workers()
+}
+
+const fn default_max_certification_failures() -> usize {
+    100
+}
+
+const fn same_as_default_max_certification_failures(v: &usize) -> bool {
+    *v == default_max_certification_failures()
+}
+
 /// The default prover configuration.
 fn default_prover_config_default() -> ProverConfig {
-    ProverConfig::SP1Local {}
+    ProverConfig::default()
 }
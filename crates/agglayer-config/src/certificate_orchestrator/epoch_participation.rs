@@ -0,0 +1,60 @@
+use std::{collections::BTreeMap, num::NonZeroU64};
+
+use agglayer_types::{EpochNumber, NetworkId};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+/// Epochs in which the networks settle their certificates.
+///
+/// Every network settles in every epoch by default. A low-activity network
+/// can be restricted to every Nth epoch, only settling in the epochs whose
+/// number is a multiple of its interval, to reduce its settlement costs on L1.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct EpochParticipationConfig {
+    /// Number of epochs between two settlements of each network. The networks
+    /// not listed settle every epoch.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub settlement_interval: BTreeMap<u32, NonZeroU64>,
+}
+
+impl EpochParticipationConfig {
+    /// Whether the network may settle a certificate in the given epoch.
+    pub fn settles_in_epoch(&self, network_id: NetworkId, epoch: EpochNumber) -> bool {
+        self.settlement_interval
+            .get(&network_id.to_u32())
+            .map_or(true, |interval| epoch.as_u64() % interval.get() == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn networks_settle_every_epoch_by_default() {
+        let config: EpochParticipationConfig = toml::from_str("").unwrap();
+
+        assert_eq!(config, EpochParticipationConfig::default());
+        assert!(config.settles_in_epoch(NetworkId::new(1), EpochNumber::new(7)));
+    }
+
+    #[test]
+    fn networks_settle_every_nth_epoch() {
+        let config: EpochParticipationConfig = toml::from_str(
+            r#"
+            [settlement-interval]
+            2 = 3
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.settles_in_epoch(NetworkId::new(2), EpochNumber::ZERO));
+        assert!(!config.settles_in_epoch(NetworkId::new(2), EpochNumber::new(1)));
+        assert!(!config.settles_in_epoch(NetworkId::new(2), EpochNumber::new(2)));
+        assert!(config.settles_in_epoch(NetworkId::new(2), EpochNumber::new(3)));
+        assert!(config.settles_in_epoch(NetworkId::new(1), EpochNumber::new(1)));
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Expiry of the pending certificates which are never certified, such as the
+/// ones superseded by the settlement of another certificate or the ones of a
+/// stalled network.
+///
+/// A certificate still pending once its TTL elapsed is put in error as
+/// expired, and is pruned from the pending storage after the retention.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PendingExpiryConfig {
+    /// Time after its submission at which a certificate still pending is
+    /// expired. The pending certificates never expire when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<crate::with::HumanDuration>")]
+    pub ttl: Option<Duration>,
+
+    /// Time after its expiry at which a certificate is pruned from the
+    /// pending storage, unless it was replaced meanwhile.
+    #[serde(default = "default_retention", with = "crate::with::HumanDuration")]
+    pub retention: Duration,
+
+    /// Interval at which the pending certificates are checked.
+    #[serde(
+        default = "default_check_interval",
+        with = "crate::with::HumanDuration"
+    )]
+    pub check_interval: Duration,
+}
+
+impl Default for PendingExpiryConfig {
+    fn default() -> Self {
+        Self {
+            ttl: None,
+            retention: default_retention(),
+            check_interval: default_check_interval(),
+        }
+    }
+}
+
+const fn default_retention() -> Duration {
+    Duration::from_secs(24 * 60 * 60)
+}
+
+const fn default_check_interval() -> Duration {
+    Duration::from_secs(60)
+}
@@ -1,18 +1,77 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
 /// The different prover configuration.
+///
+/// Each backend accepts a `proving-timeout`, after which the proof request is
+/// cancelled and the certificate is put in error, defaulting to a duration
+/// suited to the backend.
+#[serde_as]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum ProverConfig {
-    #[serde(rename = "sp1-local")]
-    SP1Local {},
-    #[serde(rename = "sp1-mock")]
-    SP1Mock {},
-    #[serde(rename = "sp1-network")]
-    SP1Network {},
+    #[serde(rename = "sp1-local", rename_all = "kebab-case")]
+    SP1Local {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[serde_as(as = "Option<crate::with::HumanDuration>")]
+        proving_timeout: Option<Duration>,
+    },
+    /// Generate deterministic mock proofs, derived from the public values of
+    /// the program execution.
+    #[serde(rename = "sp1-mock", rename_all = "kebab-case")]
+    SP1Mock {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[serde_as(as = "Option<crate::with::HumanDuration>")]
+        proving_timeout: Option<Duration>,
+    },
+    #[serde(rename = "sp1-network", rename_all = "kebab-case")]
+    SP1Network {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[serde_as(as = "Option<crate::with::HumanDuration>")]
+        proving_timeout: Option<Duration>,
+    },
+    /// Only execute the program in the SP1 executor, without generating any
+    /// proof. The certifier produces mock proofs out of the public values,
+    /// which requires the mock verifier.
+    #[serde(rename = "sp1-execute", rename_all = "kebab-case")]
+    SP1Execute {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[serde_as(as = "Option<crate::with::HumanDuration>")]
+        proving_timeout: Option<Duration>,
+    },
+}
+
+impl ProverConfig {
+    /// Maximum duration of the proving of a certificate, after which it is
+    /// cancelled.
+    pub fn proving_timeout(&self) -> Duration {
+        match self {
+            Self::SP1Local { proving_timeout } => {
+                proving_timeout.unwrap_or(DEFAULT_SP1_LOCAL_PROVING_TIMEOUT)
+            }
+            Self::SP1Mock { proving_timeout } | Self::SP1Execute { proving_timeout } => {
+                proving_timeout.unwrap_or(DEFAULT_SP1_MOCK_PROVING_TIMEOUT)
+            }
+            Self::SP1Network { proving_timeout } => {
+                proving_timeout.unwrap_or(DEFAULT_SP1_NETWORK_PROVING_TIMEOUT)
+            }
+        }
+    }
 }
 
 impl Default for ProverConfig {
     fn default() -> Self {
-        Self::SP1Local {}
+        Self::SP1Local {
+            proving_timeout: None,
+        }
     }
 }
+
+/// Proving on the CPU of the prover service takes a while for large
+/// certificates.
+const DEFAULT_SP1_LOCAL_PROVING_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+const DEFAULT_SP1_MOCK_PROVING_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+const DEFAULT_SP1_NETWORK_PROVING_TIMEOUT: Duration = Duration::from_secs(30 * 60);
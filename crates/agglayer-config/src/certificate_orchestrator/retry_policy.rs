@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Class of the failures a certificate can hit while being processed, each
+/// one being retried according to its own [`RetryPolicy`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorClass {
+    /// Failure of the prover which may succeed on another attempt, such as
+    /// the prover being unreachable or timing out.
+    ProverTransient,
+    /// Failure of the prover to prove the certificate, which is expected to
+    /// fail again.
+    ProverPermanent,
+    /// Settlement transaction mined but reverted on L1.
+    SettlementRevert,
+    /// Settlement transaction not mined within the configured time.
+    L1Timeout,
+}
+
+/// Retry policies of the certificates, per class of failure.
+///
+/// By default, only the L1 timeouts are retried, as before the policies were
+/// configurable.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RetryPolicyConfig {
+    #[serde(default)]
+    pub prover_transient: RetryPolicy,
+
+    #[serde(default)]
+    pub prover_permanent: RetryPolicy,
+
+    #[serde(default)]
+    pub settlement_revert: RetryPolicy,
+
+    #[serde(default = "default_l1_timeout")]
+    pub l1_timeout: RetryPolicy,
+}
+
+impl Default for RetryPolicyConfig {
+    fn default() -> Self {
+        Self {
+            prover_transient: RetryPolicy::default(),
+            prover_permanent: RetryPolicy::default(),
+            settlement_revert: RetryPolicy::default(),
+            l1_timeout: default_l1_timeout(),
+        }
+    }
+}
+
+impl RetryPolicyConfig {
+    /// Returns the policy of the given class of failure.
+    pub fn policy(&self, class: ErrorClass) -> &RetryPolicy {
+        match class {
+            ErrorClass::ProverTransient => &self.prover_transient,
+            ErrorClass::ProverPermanent => &self.prover_permanent,
+            ErrorClass::SettlementRevert => &self.settlement_revert,
+            ErrorClass::L1Timeout => &self.l1_timeout,
+        }
+    }
+}
+
+/// Retry policy of a class of failure.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RetryPolicy {
+    /// Whether the failure is retried at all. Otherwise, the certificate is
+    /// put in error on the first failure.
+    #[serde(default)]
+    pub retryable: bool,
+
+    /// Maximum number of retries of the certificate for this class of
+    /// failure. The retries are unbounded when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+
+    /// Delay before each retry.
+    #[serde(default, with = "crate::with::HumanDuration")]
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Whether a failure is retried after the given number of retries.
+    pub fn allows_retry(&self, retries: u32) -> bool {
+        self.retryable
+            && self
+                .max_retries
+                .map_or(true, |max_retries| retries < max_retries)
+    }
+}
+
+fn default_l1_timeout() -> RetryPolicy {
+    RetryPolicy {
+        retryable: true,
+        max_retries: None,
+        backoff: Duration::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_l1_timeouts_are_retried_by_default() {
+        let config: RetryPolicyConfig = toml::from_str("").unwrap();
+
+        assert_eq!(config, RetryPolicyConfig::default());
+        assert!(!config.policy(ErrorClass::ProverTransient).allows_retry(0));
+        assert!(!config.policy(ErrorClass::ProverPermanent).allows_retry(0));
+        assert!(!config.policy(ErrorClass::SettlementRevert).allows_retry(0));
+        assert!(config.policy(ErrorClass::L1Timeout).allows_retry(u32::MAX));
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let config: RetryPolicyConfig = toml::from_str(
+            r#"
+            [prover-transient]
+            retryable = true
+            max-retries = 2
+            backoff = "30s"
+            "#,
+        )
+        .unwrap();
+
+        let policy = config.policy(ErrorClass::ProverTransient);
+        assert_eq!(policy.backoff, Duration::from_secs(30));
+        assert!(policy.allows_retry(1));
+        assert!(!policy.allows_retry(2));
+    }
+}
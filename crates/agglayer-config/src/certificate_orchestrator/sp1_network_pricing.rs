@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Pricing of the proofs generated by the SP1 network, used to estimate the
+/// cost and time of proving a certificate out of the cycles of its execution.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Sp1NetworkPricing {
+    /// Price of one million cycles proven by the SP1 network, in the token
+    /// paying for the proofs.
+    /// Can be specified with units: "0.5eth", "1000gwei"
+    #[serde_as(as = "crate::with::EthAmount")]
+    pub price_per_million_cycles: u128,
+
+    /// Number of cycles proven per second by the SP1 network.
+    pub cycles_per_second: u64,
+
+    /// Time spent by the SP1 network on a proof regardless of its cycles,
+    /// such as the request fulfillment and the proof aggregation.
+    #[serde(
+        default = "default_proving_overhead",
+        with = "crate::with::HumanDuration"
+    )]
+    pub proving_overhead: Duration,
+}
+
+const fn default_proving_overhead() -> Duration {
+    Duration::from_secs(30)
+}
@@ -0,0 +1,37 @@
+use std::{num::NonZeroU32, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Quota of the proofs requested from the SP1 network over a sliding period.
+///
+/// The proving requests exceeding the quota are queued, in order, until it
+/// frees up. The requests still waiting after the maximum queue wait put
+/// their certificate in error.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Sp1NetworkQuota {
+    /// Sliding period over which the quota applies.
+    #[serde(with = "crate::with::HumanDuration")]
+    pub period: Duration,
+
+    /// Maximum number of proofs requested over the period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_proofs: Option<NonZeroU32>,
+
+    /// Maximum number of cycles requested to be proven over the period. A
+    /// request exceeding it on its own is only sent once the period is free
+    /// of other requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cycles: Option<u64>,
+
+    /// Maximum duration a proving request waits for the quota to free up.
+    #[serde(
+        default = "default_max_queue_wait",
+        with = "crate::with::HumanDuration"
+    )]
+    pub max_queue_wait: Duration,
+}
+
+const fn default_max_queue_wait() -> Duration {
+    Duration::from_secs(10 * 60)
+}
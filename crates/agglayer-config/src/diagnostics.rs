@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Configuration of the self-diagnostics task, which periodically evaluates
+/// the alerting rules and raises an alert when one of them is triggered.
+///
+/// Alerts are exported as metrics, logged, and posted to the configured
+/// webhooks when they are raised and resolved.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct DiagnosticsConfig {
+    /// Whether the diagnostics task is running.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Interval at which the rules are evaluated.
+    #[serde(default = "default_evaluation_interval")]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub evaluation_interval: Duration,
+
+    /// Number of epochs without any settled certificate, while certificates
+    /// are waiting to be processed, after which an alert is raised. Set to 0
+    /// to disable the rule.
+    #[serde(default = "default_max_epochs_without_settlement")]
+    pub max_epochs_without_settlement: u64,
+
+    /// Percentage of failed proof generations above which an alert is raised.
+    /// Set to 100 to disable the rule.
+    #[serde(default = "default_max_proving_failure_percent")]
+    pub max_proving_failure_percent: u8,
+
+    /// Minimum number of proof generations the failure rate is computed on.
+    #[serde(default = "default_min_proving_attempts")]
+    pub min_proving_attempts: u64,
+
+    /// Number of L1 blocks the block clock can lag behind the L1 head before
+    /// an alert is raised. Only evaluated with the block clock. Set to 0 to
+    /// disable the rule.
+    #[serde(default = "default_max_l1_lag_blocks")]
+    pub max_l1_lag_blocks: u64,
+
+    /// URLs the alerts are posted to as JSON.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhook_urls: Vec<Url>,
+
+    /// Timeout of a webhook request.
+    #[serde(default = "default_webhook_timeout")]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub webhook_timeout: Duration,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            evaluation_interval: default_evaluation_interval(),
+            max_epochs_without_settlement: default_max_epochs_without_settlement(),
+            max_proving_failure_percent: default_max_proving_failure_percent(),
+            min_proving_attempts: default_min_proving_attempts(),
+            max_l1_lag_blocks: default_max_l1_lag_blocks(),
+            webhook_urls: Vec::new(),
+            webhook_timeout: default_webhook_timeout(),
+        }
+    }
+}
+
+const fn default_enabled() -> bool {
+    true
+}
+
+const fn default_evaluation_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+const fn default_max_epochs_without_settlement() -> u64 {
+    3
+}
+
+const fn default_max_proving_failure_percent() -> u8 {
+    50
+}
+
+const fn default_min_proving_attempts() -> u64 {
+    5
+}
+
+const fn default_max_l1_lag_blocks() -> u64 {
+    10
+}
+
+const fn default_webhook_timeout() -> Duration {
+    Duration::from_secs(10)
+}
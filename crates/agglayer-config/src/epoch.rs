@@ -8,6 +8,9 @@ use serde::{Deserialize, Serialize};
 pub enum Epoch {
     TimeClock(TimeClockConfig),
     BlockClock(BlockClockConfig),
+    /// Epochs ending only when requested through the admin API, for tests and
+    /// devnets.
+    ManualClock(ManualClockConfig),
 }
 
 impl Default for Epoch {
@@ -54,6 +57,21 @@ impl Default for TimeClockConfig {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ManualClockConfig {
+    #[serde(default = "default_block_epoch_duration")]
+    pub epoch_duration: NonZeroU64,
+}
+
+impl Default for ManualClockConfig {
+    fn default() -> Self {
+        Self {
+            epoch_duration: default_block_epoch_duration(),
+        }
+    }
+}
+
 // We estimate the block time of L1 to 10min.
 // The goal is to have an epoch duration of 1h.
 // So we need 6 blocks per epoch.
@@ -92,4 +110,18 @@ mod tests {
             matches!(epoch, Epoch::TimeClock(TimeClockConfig { epoch_duration }) if epoch_duration == expected_duration)
         );
     }
+
+    #[test]
+    fn deserialize_manual_epoch() {
+        let config = r#"{"manual-clock":{"epoch-duration":2}}"#;
+
+        let epoch: Epoch = serde_json::from_str(config).unwrap();
+
+        assert_eq!(
+            epoch,
+            Epoch::ManualClock(ManualClockConfig {
+                epoch_duration: NonZeroU64::new(2).unwrap()
+            })
+        );
+    }
 }
@@ -30,6 +30,50 @@ pub struct L1 {
 
     #[serde(default = "L1::default_event_filter_block_range")]
     pub event_filter_block_range: NonZeroU64,
+
+    /// Behavior of the node when the L1 sanity checks performed at startup
+    /// fail.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub startup_checks: StartupChecks,
+
+    /// Settlement ABI of the rollup manager, to keep settling across the
+    /// upgrades of the L1 contracts.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub rollup_manager_abi: RollupManagerAbi,
+}
+
+/// Behavior of the node when the L1 sanity checks performed at startup fail.
+///
+/// The checks verify the L1 chain id, that the rollup manager, the global
+/// exit root and the pessimistic proof verifier contracts are deployed, and
+/// that the pessimistic proof vkey registered on L1 matches the embedded
+/// program.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartupChecks {
+    /// Refuse to start the node.
+    #[default]
+    Enforce,
+    /// Log the failures and start the node in degraded mode.
+    Warn,
+    /// Do not run the checks.
+    Disabled,
+}
+
+/// Settlement ABI of the rollup manager.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RollupManagerAbi {
+    /// Negotiated at startup from the `ROLLUP_MANAGER_VERSION` of the rollup
+    /// manager, the pessimistic ABI if the call reverts. The node refuses to
+    /// start if the version can't be fetched.
+    #[default]
+    Auto,
+    /// Settlement without the aggchain data, of the rollup managers predating
+    /// the aggchains.
+    Pessimistic,
+    /// Settlement along with the aggchain data.
+    Aggchain,
 }
 
 impl L1 {
@@ -59,6 +103,8 @@ impl Default for L1 {
                     .unwrap(),
             rpc_timeout: Self::default_rpc_timeout(),
             event_filter_block_range: Self::default_event_filter_block_range(),
+            startup_checks: StartupChecks::default(),
+            rollup_manager_abi: RollupManagerAbi::default(),
         }
     }
 }
@@ -21,6 +21,7 @@ pub(crate) const DEFAULT_IP: std::net::Ipv4Addr = std::net::Ipv4Addr::new(0, 0,
 
 pub(crate) mod auth;
 pub mod certificate_orchestrator;
+pub mod diagnostics;
 pub mod epoch;
 pub(crate) mod l1;
 pub(crate) mod l2;
@@ -29,22 +30,27 @@ mod multiplier;
 pub mod outbound;
 mod port;
 pub mod rate_limiting;
+pub mod read_only;
 pub(crate) mod rpc;
 pub mod shutdown;
 pub mod storage;
 pub(crate) mod telemetry;
 mod with;
 
-pub use auth::{AuthConfig, GcpKmsConfig, LocalConfig, PrivateKey};
+pub use auth::{AuthConfig, AuthRotationConfig, GcpKmsConfig, LocalConfig, PrivateKey};
 pub use epoch::Epoch;
-pub use l1::L1;
+pub use l1::{RollupManagerAbi, StartupChecks, L1};
 pub use l2::L2;
 pub use log::Log;
 pub use multiplier::Multiplier;
 use port::{Port, PortDefaults};
 use prover::default_prover_entrypoint;
 pub use rate_limiting::RateLimitingConfig;
-pub use rpc::RpcConfig;
+pub use rpc::{
+    ApiKeyConfig, CertificateLimits, HeightPolicy, RpcAdminConfig, RpcApiKeysConfig,
+    RpcCompressionConfig, RpcConfig, RpcGraphqlConfig, RpcIntakeConfig, ScreeningConfig,
+    ScreeningServiceConfig,
+};
 
 /// The Agglayer configuration.
 #[serde_with::serde_as]
@@ -91,6 +97,10 @@ pub struct Config {
     #[serde(default)]
     pub auth: AuthConfig,
 
+    /// The rotation of the settlement key, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_rotation: Option<AuthRotationConfig>,
+
     /// Telemetry configuration.
     #[serde(default)]
     pub telemetry: TelemetryConfig,
@@ -111,6 +121,14 @@ pub struct Config {
     #[serde(default)]
     pub storage: storage::StorageConfig,
 
+    /// The self-diagnostics and alerting configuration.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub diagnostics: diagnostics::DiagnosticsConfig,
+
+    /// The read-only mode configuration.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub read_only: read_only::ReadOnlyConfig,
+
     /// AggLayer prover entrypoint.
     #[serde(default = "default_prover_entrypoint")]
     #[serde(skip_serializing_if = "String::is_empty")]
@@ -180,10 +198,13 @@ impl Config {
             l1: Default::default(),
             l2: Default::default(),
             auth: Default::default(),
+            auth_rotation: None,
             telemetry: Default::default(),
             epoch: Default::default(),
             shutdown: Default::default(),
             certificate_orchestrator: Default::default(),
+            diagnostics: Default::default(),
+            read_only: Default::default(),
             prover_entrypoint: default_prover_entrypoint(),
             prover: Default::default(),
             debug_mode: false,
@@ -205,16 +226,27 @@ impl Config {
 
     /// Get the admin RPC socket address from the configuration.
     pub fn admin_rpc_addr(&self) -> std::net::SocketAddr {
-        std::net::SocketAddr::from((self.rpc.host, self.rpc.admin_port.as_u16()))
+        let host = self.rpc.admin.host.unwrap_or(self.rpc.host.into());
+        std::net::SocketAddr::from((host, self.rpc.admin_port.as_u16()))
     }
 
     pub fn path_contextualized(mut self, base_path: &Path) -> Self {
         self.storage = self.storage.path_contextualized(base_path);
+        if let Some(dir) = &mut self.certificate_orchestrator.failed_proof_stdin_dir {
+            *dir = storage::normalize_path(&base_path.join(&*dir));
+        }
+        if let Some(path) = &mut self.rpc.admin.unix_socket {
+            *path = storage::normalize_path(&base_path.join(&*path));
+        }
 
         self
     }
 
     pub(crate) fn validate(self) -> Result<Self, ConfigurationError> {
+        if self.read_only.enabled && self.storage.secondary_db_path.is_none() {
+            return Err(ConfigurationError::MissingSecondaryDbPath);
+        }
+
         Ok(self)
     }
 }
@@ -230,6 +262,9 @@ pub enum ConfigurationError {
 
     #[error("Failed to deserialize the configuration: {0}")]
     DeserializationError(#[from] toml::de::Error),
+
+    #[error("The read-only mode requires the storage secondary-db-path to be set")]
+    MissingSecondaryDbPath,
 }
 
 #[cfg(any(test, feature = "testutils"))]
@@ -1,7 +1,11 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, net::IpAddr, time::Duration};
 
+use agglayer_primitives::Address;
+pub use agglayer_prover_config::HttpClientConfig;
+use agglayer_types::NetworkId;
 use serde::{Deserialize, Serialize};
-use serde_with::serde_as;
+use serde_with::{serde_as, DisplayFromStr};
+use url::{Host, Url};
 
 use crate::Multiplier;
 
@@ -10,6 +14,167 @@ use crate::Multiplier;
 #[serde(rename = "outbound", rename_all = "kebab-case")]
 pub struct OutboundConfig {
     pub rpc: OutboundRpcConfig,
+
+    /// Outbound configuration of the certificate status callbacks.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub callback: OutboundCallbackConfig,
+
+    /// Outbound configuration of the epoch summary commitments.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub epoch_commitment: OutboundEpochCommitmentConfig,
+
+    /// Proxies and TLS settings of the HTTP connections to the L1 node.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub http: HttpClientConfig,
+}
+
+/// Outbound configuration of the commitments to the certificates of each
+/// settled epoch.
+///
+/// The commitments are always computed and logged. They are also published on
+/// L1 when an address to publish to is configured, as the calldata of a
+/// transaction sending no value to it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct OutboundEpochCommitmentConfig {
+    /// Address the commitments are published to, not published if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_to: Option<Address>,
+}
+
+/// Outbound configuration of the callbacks notifying the submitters of the
+/// final status of their certificates.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct OutboundCallbackConfig {
+    /// Secrets used to sign the callback payloads with HMAC-SHA256, per
+    /// network. Each network has its own secret so that no network can forge
+    /// the callbacks of another one. The payloads of the networks not listed
+    /// are sent unsigned.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde_as(as = "BTreeMap<DisplayFromStr, _>")]
+    pub hmac_secrets: BTreeMap<u32, String>,
+
+    /// Interval at which the certificates with a pending callback are checked
+    /// for a final status.
+    #[serde(default = "default_callback_poll_interval")]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub poll_interval: Duration,
+
+    /// Maximum number of delivery attempts of a callback.
+    #[serde(default = "default_callback_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry of a failed delivery, doubled after each
+    /// attempt.
+    #[serde(default = "default_callback_retry_interval")]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub retry_interval: Duration,
+
+    /// Timeout of a callback request.
+    #[serde(default = "default_callback_request_timeout")]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub request_timeout: Duration,
+
+    /// Hosts the callbacks can be posted to. When not empty, the callback URLs
+    /// of any other host are refused.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_hosts: Vec<String>,
+
+    /// Hosts the callbacks cannot be posted to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_hosts: Vec<String>,
+
+    /// Whether the callbacks can be posted to loopback, private, link-local
+    /// and other non-public addresses. Refused by default, so that the
+    /// submitters cannot make the node reach its internal network.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_private_addresses: bool,
+}
+
+impl Default for OutboundCallbackConfig {
+    fn default() -> Self {
+        Self {
+            hmac_secrets: BTreeMap::new(),
+            poll_interval: default_callback_poll_interval(),
+            max_attempts: default_callback_max_attempts(),
+            retry_interval: default_callback_retry_interval(),
+            request_timeout: default_callback_request_timeout(),
+            allowed_hosts: Vec::new(),
+            denied_hosts: Vec::new(),
+            allow_private_addresses: false,
+        }
+    }
+}
+
+impl OutboundCallbackConfig {
+    /// Secret signing the callback payloads of the network, if any.
+    pub fn hmac_secret(&self, network_id: NetworkId) -> Option<&str> {
+        self.hmac_secrets
+            .get(&network_id.to_u32())
+            .map(String::as_str)
+    }
+
+    /// Whether the callbacks can be posted to the host of the URL. The
+    /// addresses a domain resolves to are checked on delivery with
+    /// [`Self::is_allowed_address`].
+    pub fn is_allowed_url(&self, url: &Url) -> bool {
+        let (Some(host), Some(host_str)) = (url.host(), url.host_str()) else {
+            return false;
+        };
+
+        let listed = |hosts: &[String]| hosts.iter().any(|h| h.eq_ignore_ascii_case(host_str));
+        if listed(&self.denied_hosts)
+            || (!self.allowed_hosts.is_empty() && !listed(&self.allowed_hosts))
+        {
+            return false;
+        }
+
+        match host {
+            Host::Ipv4(ip) => self.is_allowed_address(ip.into()),
+            Host::Ipv6(ip) => self.is_allowed_address(ip.into()),
+            Host::Domain(domain) => {
+                let domain = domain.to_ascii_lowercase();
+                self.allow_private_addresses
+                    || !(domain == "localhost" || domain.ends_with(".localhost"))
+            }
+        }
+    }
+
+    /// Whether the callbacks can be posted to the address.
+    pub fn is_allowed_address(&self, ip: IpAddr) -> bool {
+        self.allow_private_addresses || is_public_address(ip)
+    }
+}
+
+/// Whether the address is reachable on the internet, as opposed to the
+/// loopback, private, shared, link-local and reserved ranges.
+fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || a == 0
+                || (a == 100 && b & 0xc0 == 64)
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_address(ip.into()),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
 }
 
 /// Outbound RPC configuration that is used to configure the outbound RPC
@@ -36,8 +201,18 @@ pub struct OutboundRpcSettleConfig {
     #[serde(with = "crate::with::HumanDuration")]
     pub retry_interval: Duration,
 
-    /// Number of confirmations required for the transaction to resolve a
-    /// receipt.
+    /// Spacing of the polls of the transaction receipt, starting from the
+    /// retry interval. The receipt is polled at the fixed retry interval by
+    /// default.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub receipt_polling: ReceiptPollingConfig,
+
+    /// Number of L1 confirmations required before the certificate is
+    /// considered settled, the block including the transaction counting as
+    /// the first one. The inclusion is re-checked until this depth is reached.
+    ///
+    /// A single confirmation is enough on devnets, while 12 or more are
+    /// recommended on mainnet.
     #[serde(default = "default_rpc_confirmations")]
     pub confirmations: usize,
 
@@ -47,6 +222,74 @@ pub struct OutboundRpcSettleConfig {
     #[serde(with = "crate::with::HumanDuration")]
     pub settlement_timeout: Duration,
 
+    /// Number of L1 blocks after which a settlement transaction that is still
+    /// not mined is considered stuck, and re-broadcast with the same nonce
+    /// and bumped fees. When unset, the transaction is only re-broadcast
+    /// after the receipt polling times out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resubmit_after_blocks: Option<u64>,
+
+    /// Maximum number of settlement transactions submitted for one
+    /// certificate before it is marked in error with a settlement timeout.
+    #[serde(
+        default = "default_max_settlement_attempts",
+        skip_serializing_if = "same_as_default_max_settlement_attempts"
+    )]
+    pub max_settlement_attempts: u32,
+
+    /// Balance (in wei) of the settlement account below which a warning is
+    /// emitted before each settlement.
+    /// Can be specified with units: "1eth", "500000gwei"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<crate::with::EthAmount>")]
+    pub low_balance_threshold: Option<u128>,
+
+    /// Gas expected to be used by a settlement transaction, used along with
+    /// the current fees to estimate its cost. New settlements are deferred
+    /// while the balance of the settlement account cannot cover it.
+    #[serde(
+        default = "default_settlement_gas_estimate",
+        skip_serializing_if = "same_as_default_settlement_gas_estimate"
+    )]
+    pub settlement_gas_estimate: u64,
+
+    /// Strategy used to settle the certificates of an epoch. When unset, the
+    /// settlements are batched in multicall transactions if a batch window is
+    /// set, and submitted individually otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch_packing: Option<EpochPacking>,
+
+    /// Window during which the settlements of the same epoch are collected
+    /// to be submitted together, when the epoch packing strategy batches or
+    /// aggregates them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<crate::with::HumanDuration>")]
+    pub batch_window: Option<Duration>,
+
+    /// Interval at which the verification events of the rollup manager are
+    /// polled, to detect the certificates settled through another transaction
+    /// and the verifications not sent by the agglayer.
+    #[serde(
+        default = "default_event_poll_interval",
+        skip_serializing_if = "same_as_default_event_poll_interval"
+    )]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub event_poll_interval: Duration,
+
+    /// Whether the proof of each certificate is checked against the
+    /// pessimistic proof verifier and verifying key configured on L1 before
+    /// submitting its settlement. The verification of the contracts is
+    /// simulated with a call, so that a proof rejected by them, e.g. after a
+    /// verifying key update, fails the certificate without paying any gas.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub verify_proof_before_settlement: bool,
+
+    /// Whether the settlement is deferred until the proof can be verified on
+    /// L1, e.g. while the L1 RPC fails, rather than submitted unverified. Only
+    /// used along with `verify_proof_before_settlement`.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub require_proof_verification: bool,
+
     /// Gas multiplier factor for the transaction.
     /// The gas is calculated as follows:
     /// `gas = estimate_gas * (gas_multiplier / 100)
@@ -66,14 +309,111 @@ impl Default for OutboundRpcSettleConfig {
         OutboundRpcSettleConfig {
             max_retries: default_rpc_retries(),
             retry_interval: default_rpc_retry_interval(),
+            receipt_polling: ReceiptPollingConfig::default(),
             confirmations: default_rpc_confirmations(),
             settlement_timeout: default_settlement_timeout(),
+            resubmit_after_blocks: None,
+            max_settlement_attempts: default_max_settlement_attempts(),
+            low_balance_threshold: None,
+            settlement_gas_estimate: default_settlement_gas_estimate(),
+            epoch_packing: None,
+            batch_window: None,
+            event_poll_interval: default_event_poll_interval(),
+            verify_proof_before_settlement: false,
+            require_proof_verification: false,
             gas_multiplier_factor: default_gas_multiplier_factor(),
             gas_price: GasPriceConfig::default(),
         }
     }
 }
 
+impl OutboundRpcSettleConfig {
+    /// Strategy used to settle the certificates of an epoch, along with the
+    /// window during which their settlements are collected.
+    pub fn epoch_packing_strategy(&self) -> (EpochPacking, Duration) {
+        let strategy = self
+            .epoch_packing
+            .unwrap_or(if self.batch_window.is_some() {
+                EpochPacking::MulticallBatch
+            } else {
+                EpochPacking::Individual
+            });
+
+        (
+            strategy,
+            self.batch_window.unwrap_or(default_batch_window()),
+        )
+    }
+}
+
+/// Strategy used to settle the certificates of an epoch.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EpochPacking {
+    /// Each certificate is settled in its own transaction.
+    #[default]
+    Individual,
+    /// The proofs of the certificates of an epoch are aggregated and settled
+    /// in a single transaction. The node refuses to start when the settlement
+    /// target does not support it, which the L1 rollup manager does not.
+    Aggregated,
+    /// The settlements of the certificates of an epoch are submitted in a
+    /// single multicall transaction, when the rollup manager supports it.
+    MulticallBatch,
+}
+
+/// Polling of the receipt of a settlement transaction.
+///
+/// Whatever the strategy, the receipt is awaited at most for the retry
+/// interval times the maximum number of retries.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReceiptPollingConfig {
+    /// Strategy used to space the polls.
+    #[serde(default)]
+    pub strategy: ReceiptPollingStrategy,
+
+    /// Maximum interval between two polls.
+    #[serde(
+        default = "default_max_receipt_poll_interval",
+        with = "crate::with::HumanDuration"
+    )]
+    pub max_interval: Duration,
+
+    /// Maximum random jitter added to each interval, in percent of it, to
+    /// spread the polls of the settlements submitted at the same time. Not
+    /// applied to the fixed polling.
+    #[serde(default = "default_receipt_poll_jitter_percent")]
+    pub jitter_percent: u32,
+}
+
+impl Default for ReceiptPollingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: ReceiptPollingStrategy::default(),
+            max_interval: default_max_receipt_poll_interval(),
+            jitter_percent: default_receipt_poll_jitter_percent(),
+        }
+    }
+}
+
+/// Strategy used to space the polls of a transaction receipt.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReceiptPollingStrategy {
+    /// The receipt is polled at the retry interval.
+    #[default]
+    Fixed,
+    /// The interval is doubled after each poll, from the retry interval up
+    /// to the maximum interval.
+    Exponential,
+    /// The receipt is polled once per L1 block, the block time being
+    /// estimated from the latest blocks. While the transaction waits for
+    /// confirmations, the receipt is only polled again once they are all
+    /// expected.
+    Adaptive,
+}
+
 /// Gas price configuration for settlement transactions.
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -117,6 +457,25 @@ const fn same_as_default_gas_multiplier_factor(v: &u32) -> bool {
     *v == default_gas_multiplier_factor()
 }
 
+/// Default maximum number of settlement transactions submitted for one
+/// certificate.
+const fn default_max_settlement_attempts() -> u32 {
+    6
+}
+
+const fn same_as_default_max_settlement_attempts(v: &u32) -> bool {
+    *v == default_max_settlement_attempts()
+}
+
+/// Default gas expected to be used by a settlement transaction.
+const fn default_settlement_gas_estimate() -> u64 {
+    500_000
+}
+
+const fn same_as_default_settlement_gas_estimate(v: &u64) -> bool {
+    *v == default_settlement_gas_estimate()
+}
+
 /// Default number of retries for the transaction.
 const fn default_rpc_retries() -> usize {
     30
@@ -127,6 +486,16 @@ const fn default_rpc_retry_interval() -> Duration {
     Duration::from_secs(10)
 }
 
+/// Default maximum interval between two polls of a transaction receipt.
+const fn default_max_receipt_poll_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// Default maximum jitter of the polls of a transaction receipt, in percent.
+const fn default_receipt_poll_jitter_percent() -> u32 {
+    20
+}
+
 /// Default number of confirmations required for the transaction to resolve a
 /// receipt.
 const fn default_rpc_confirmations() -> usize {
@@ -138,6 +507,40 @@ const fn default_settlement_timeout() -> Duration {
     Duration::from_secs(20 * 60)
 }
 
+/// Default interval for the polling of the L1 verification events.
+const fn default_batch_window() -> Duration {
+    Duration::from_secs(2)
+}
+
+const fn default_event_poll_interval() -> Duration {
+    Duration::from_secs(12)
+}
+
+fn same_as_default_event_poll_interval(v: &Duration) -> bool {
+    *v == default_event_poll_interval()
+}
+
+/// Default interval for the checks of the certificates with a pending
+/// callback.
+const fn default_callback_poll_interval() -> Duration {
+    Duration::from_secs(5)
+}
+
+/// Default number of delivery attempts of a callback.
+const fn default_callback_max_attempts() -> u32 {
+    5
+}
+
+/// Default delay before the first retry of a callback delivery.
+const fn default_callback_retry_interval() -> Duration {
+    Duration::from_secs(2)
+}
+
+/// Default timeout of a callback request.
+const fn default_callback_request_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
 /// Default gas price ceiling for the transaction.
 const fn default_gas_price_ceiling() -> u128 {
     // 100 gwei
@@ -168,11 +571,134 @@ mod tests {
             assert_eq!(config.outbound.rpc.settle.max_retries, 10);
         }
 
+        mod callback {
+            use std::time::Duration;
+
+            use agglayer_types::NetworkId;
+            use rstest::rstest;
+            use url::Url;
+
+            use crate::outbound::OutboundCallbackConfig;
+
+            #[test]
+            fn test_default() {
+                let config = toml::from_str::<OutboundCallbackConfig>("").unwrap();
+
+                assert_eq!(config, OutboundCallbackConfig::default());
+                assert!(config.hmac_secrets.is_empty());
+                assert_eq!(config.poll_interval, Duration::from_secs(5));
+                assert_eq!(config.max_attempts, 5);
+                assert!(config.allowed_hosts.is_empty());
+                assert!(config.denied_hosts.is_empty());
+                assert!(!config.allow_private_addresses);
+            }
+
+            #[test]
+            fn test_custom() {
+                let toml = r#"
+                    poll-interval = "1s"
+                    max-attempts = 3
+                    retry-interval = "500ms"
+                    request-timeout = "3s"
+                    allowed-hosts = ["callbacks.example.com"]
+                    denied-hosts = ["internal.example.com"]
+                    allow-private-addresses = true
+
+                    [hmac-secrets]
+                    1 = "secret"
+                    "#;
+
+                let config = toml::from_str::<OutboundCallbackConfig>(toml).unwrap();
+
+                assert_eq!(config.hmac_secret(NetworkId::new(1)), Some("secret"));
+                assert_eq!(config.hmac_secret(NetworkId::new(2)), None);
+                assert_eq!(config.poll_interval, Duration::from_secs(1));
+                assert_eq!(config.max_attempts, 3);
+                assert_eq!(config.retry_interval, Duration::from_millis(500));
+                assert_eq!(config.request_timeout, Duration::from_secs(3));
+                assert_eq!(config.allowed_hosts, ["callbacks.example.com"]);
+                assert_eq!(config.denied_hosts, ["internal.example.com"]);
+                assert!(config.allow_private_addresses);
+            }
+
+            #[rstest]
+            #[case::public_domain("https://callbacks.example.com/status", true)]
+            #[case::public_address("http://8.8.8.8/status", true)]
+            #[case::localhost("http://localhost:8080/status", false)]
+            #[case::loopback("http://127.0.0.1/status", false)]
+            #[case::private("http://10.1.2.3/status", false)]
+            #[case::link_local("http://169.254.169.254/latest/meta-data", false)]
+            #[case::shared("http://100.64.0.1/status", false)]
+            #[case::loopback_v6("http://[::1]/status", false)]
+            #[case::unique_local_v6("http://[fd00::1]/status", false)]
+            #[case::mapped_v6("http://[::ffff:10.0.0.1]/status", false)]
+            fn private_addresses_are_refused_by_default(#[case] url: &str, #[case] allowed: bool) {
+                let config = OutboundCallbackConfig::default();
+
+                assert_eq!(config.is_allowed_url(&Url::parse(url).unwrap()), allowed);
+            }
+
+            #[test]
+            fn private_addresses_can_be_allowed() {
+                let config = OutboundCallbackConfig {
+                    allow_private_addresses: true,
+                    ..Default::default()
+                };
+
+                assert!(config.is_allowed_url(&Url::parse("http://10.1.2.3/status").unwrap()));
+                assert!(config.is_allowed_url(&Url::parse("http://localhost/status").unwrap()));
+            }
+
+            #[test]
+            fn listed_hosts_are_enforced() {
+                let config = OutboundCallbackConfig {
+                    allowed_hosts: vec!["callbacks.example.com".to_string()],
+                    denied_hosts: vec!["CALLBACKS.example.com".to_string()],
+                    ..Default::default()
+                };
+                assert!(!config
+                    .is_allowed_url(&Url::parse("https://callbacks.example.com/status").unwrap()));
+
+                let config = OutboundCallbackConfig {
+                    allowed_hosts: vec!["callbacks.example.com".to_string()],
+                    ..Default::default()
+                };
+                assert!(config
+                    .is_allowed_url(&Url::parse("https://callbacks.example.com/status").unwrap()));
+                assert!(!config.is_allowed_url(&Url::parse("https://other.example.com/").unwrap()));
+            }
+        }
+
+        mod epoch_commitment {
+            use crate::outbound::OutboundEpochCommitmentConfig;
+
+            #[test]
+            fn test_publish_to() {
+                let config = toml::from_str::<OutboundEpochCommitmentConfig>("").unwrap();
+                assert_eq!(config.publish_to, None);
+
+                let toml = r#"
+                    publish-to = "0x8ba1f109551bD432803012645Ac136ddd64DBA72"
+                    "#;
+
+                let config = toml::from_str::<OutboundEpochCommitmentConfig>(toml).unwrap();
+
+                assert_eq!(
+                    config.publish_to.map(|address| address.into_alloy()),
+                    Some(alloy_primitives::address!(
+                        "8ba1f109551bD432803012645Ac136ddd64DBA72"
+                    ))
+                );
+            }
+        }
+
         mod rpc {
             mod settle {
                 use std::time::Duration;
 
-                use crate::outbound::OutboundRpcSettleConfig;
+                use crate::outbound::{
+                    EpochPacking, OutboundRpcSettleConfig, ReceiptPollingStrategy,
+                };
 
                 #[test]
                 fn test_default() {
@@ -184,6 +710,42 @@ mod tests {
                     assert_eq!(config.max_retries, 30);
                     assert_eq!(config.retry_interval, Duration::from_secs(10));
                     assert_eq!(config.confirmations, 1);
+                    assert_eq!(config.resubmit_after_blocks, None);
+                    assert_eq!(config.max_settlement_attempts, 6);
+                    assert_eq!(config.low_balance_threshold, None);
+                    assert_eq!(config.settlement_gas_estimate, 500_000);
+                    assert_eq!(config.batch_window, None);
+                    assert_eq!(config.event_poll_interval, Duration::from_secs(12));
+                    assert_eq!(
+                        config.epoch_packing_strategy(),
+                        (EpochPacking::Individual, Duration::from_secs(2))
+                    );
+                    assert_eq!(
+                        config.receipt_polling.strategy,
+                        ReceiptPollingStrategy::Fixed
+                    );
+                }
+
+                #[test]
+                fn test_receipt_polling() {
+                    let toml = r#"
+                        [receipt-polling]
+                        strategy = "adaptive"
+                        max-interval = "2m"
+                        jitter-percent = 10
+                        "#;
+
+                    let config = toml::from_str::<OutboundRpcSettleConfig>(toml).unwrap();
+
+                    assert_eq!(
+                        config.receipt_polling.strategy,
+                        ReceiptPollingStrategy::Adaptive
+                    );
+                    assert_eq!(
+                        config.receipt_polling.max_interval,
+                        Duration::from_secs(120)
+                    );
+                    assert_eq!(config.receipt_polling.jitter_percent, 10);
                 }
 
                 #[test]
@@ -192,6 +754,12 @@ mod tests {
                         max-retries = 10
                         retry-interval = 1
                         confirmations = 5
+                        resubmit-after-blocks = 20
+                        max-settlement-attempts = 3
+                        low-balance-threshold = "0.5eth"
+                        settlement-gas-estimate = 400000
+                        batch-window = "2s"
+                        event-poll-interval = "30s"
                         "#;
 
                     let config = toml::from_str::<OutboundRpcSettleConfig>(toml).unwrap();
@@ -199,6 +767,43 @@ mod tests {
                     assert_eq!(config.max_retries, 10);
                     assert_eq!(config.retry_interval, Duration::from_secs(1));
                     assert_eq!(config.confirmations, 5);
+                    assert_eq!(config.resubmit_after_blocks, Some(20));
+                    assert_eq!(config.max_settlement_attempts, 3);
+                    assert_eq!(config.low_balance_threshold, Some(500_000_000_000_000_000));
+                    assert_eq!(config.settlement_gas_estimate, 400_000);
+                    assert_eq!(config.batch_window, Some(Duration::from_secs(2)));
+                    assert_eq!(config.event_poll_interval, Duration::from_secs(30));
+                    assert_eq!(
+                        config.epoch_packing_strategy(),
+                        (EpochPacking::MulticallBatch, Duration::from_secs(2))
+                    );
+                }
+
+                #[test]
+                fn test_epoch_packing() {
+                    let toml = r#"
+                        epoch-packing = "aggregated"
+                        "#;
+
+                    let config = toml::from_str::<OutboundRpcSettleConfig>(toml).unwrap();
+
+                    assert_eq!(config.epoch_packing, Some(EpochPacking::Aggregated));
+                    assert_eq!(
+                        config.epoch_packing_strategy(),
+                        (EpochPacking::Aggregated, Duration::from_secs(2))
+                    );
+
+                    let toml = r#"
+                        epoch-packing = "individual"
+                        batch-window = "5s"
+                        "#;
+
+                    let config = toml::from_str::<OutboundRpcSettleConfig>(toml).unwrap();
+
+                    assert_eq!(
+                        config.epoch_packing_strategy(),
+                        (EpochPacking::Individual, Duration::from_secs(5))
+                    );
                 }
             }
         }
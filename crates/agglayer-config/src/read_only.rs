@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration of the read-only mode, in which the agglayer serves the
+/// queries from the storage of another agglayer while rejecting the writes.
+///
+/// The pending and state storages are opened as secondary instances of the
+/// ones of the primary agglayer, which requires the
+/// [`secondary_db_path`](crate::storage::StorageConfig::secondary_db_path)
+/// to be set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ReadOnlyConfig {
+    /// Whether the agglayer runs in read-only mode.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Interval at which the storages catch up with the writes of the primary
+    /// agglayer.
+    #[serde(default = "default_catch_up_interval")]
+    #[serde(with = "crate::with::HumanDuration")]
+    pub catch_up_interval: Duration,
+}
+
+impl Default for ReadOnlyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            catch_up_interval: default_catch_up_interval(),
+        }
+    }
+}
+
+const fn default_catch_up_interval() -> Duration {
+    Duration::from_secs(1)
+}
@@ -1,8 +1,16 @@
-use std::{net::Ipv4Addr, time::Duration};
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+    time::Duration,
+};
 
+use agglayer_primitives::Address;
+use ipnet::IpNet;
 use jsonrpsee::core::TEN_MB_SIZE_BYTES;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use url::Url;
 
 use crate::{Port, PortDefaults};
 
@@ -85,6 +93,356 @@ pub struct RpcConfig {
     #[serde_as(as = "crate::with::HumanDuration")]
     #[serde(default = "default_request_timeout")]
     pub request_timeout: Duration,
+
+    /// Compression of the request and response bodies.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub compression: RpcCompressionConfig,
+
+    /// Validation of the submitted certificates, run on a dedicated worker
+    /// pool.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub intake: RpcIntakeConfig,
+
+    /// The GraphQL endpoint served on the ReadRPC port.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub graphql: RpcGraphqlConfig,
+
+    /// The listener of the AdminRPC server.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub admin: RpcAdminConfig,
+
+    /// The API keys of the clients of the agglayer, and their quotas.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub api_keys: RpcApiKeysConfig,
+
+    /// Whether the certificate headers can be served signed with the key of
+    /// the agglayer, for the services relaying them to check their origin.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub sign_responses: bool,
+
+    /// Directory of the snapshots of the state storage the certificates can
+    /// be natively executed against by `admin_debugNativeExecution`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_snapshots_dir: Option<PathBuf>,
+}
+
+/// Compression of the JSON-RPC bodies, negotiated with the clients through
+/// the `Accept-Encoding` and `Content-Encoding` headers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcCompressionConfig {
+    /// Whether the gzip encoding is supported.
+    #[serde(default = "default_encoding_enabled")]
+    pub gzip: bool,
+
+    /// Whether the deflate encoding is supported.
+    #[serde(default = "default_encoding_enabled")]
+    pub deflate: bool,
+
+    /// Whether the brotli encoding is supported.
+    #[serde(default = "default_encoding_enabled")]
+    pub br: bool,
+
+    /// Whether the zstd encoding is supported.
+    #[serde(default = "default_encoding_enabled")]
+    pub zstd: bool,
+
+    /// The minimum size of the response body in bytes to be compressed.
+    #[serde(default = "default_min_compression_size")]
+    pub min_size: u16,
+}
+
+impl Default for RpcCompressionConfig {
+    fn default() -> Self {
+        Self {
+            gzip: default_encoding_enabled(),
+            deflate: default_encoding_enabled(),
+            br: default_encoding_enabled(),
+            zstd: default_encoding_enabled(),
+            min_size: default_min_compression_size(),
+        }
+    }
+}
+
+/// Validation of the submitted certificates before they are stored.
+///
+/// The signature recovery and the inclusion proof checks of the imported
+/// bridge exits are run on a bounded pool of blocking workers, so that a burst
+/// of large certificates doesn't starve the RPC executor.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcIntakeConfig {
+    /// The number of certificates validated concurrently.
+    #[serde(default = "default_intake_workers")]
+    pub workers: usize,
+
+    /// The number of certificates waiting for a worker, beyond which the
+    /// submissions are rejected.
+    #[serde(default = "default_intake_queue_size")]
+    pub queue_size: usize,
+
+    /// The number of proving cost estimations run concurrently, beyond which
+    /// the estimations are rejected.
+    #[serde(default = "default_estimation_workers")]
+    pub estimation_workers: usize,
+
+    /// The maximum number of bridge exits of a certificate, unlimited if
+    /// `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bridge_exits: Option<usize>,
+
+    /// The maximum number of imported bridge exits of a certificate, unlimited
+    /// if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_imported_bridge_exits: Option<usize>,
+
+    /// The maximum size of a certificate once serialized, in bytes, unlimited
+    /// if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_certificate_size: Option<usize>,
+
+    /// The limits of the certificates of some networks, overriding the ones
+    /// above.
+    #[serde_as(as = "BTreeMap<serde_with::DisplayFromStr, _>")]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub networks: BTreeMap<u32, CertificateLimits>,
+
+    /// The heights at which the certificates of a network are accepted.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub height_policy: HeightPolicy,
+
+    /// The screening of the destination addresses of the bridge exits.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub screening: ScreeningConfig,
+}
+
+/// Heights at which the certificates of a network are accepted, relative to
+/// its known certificates.
+///
+/// A certificate at a height that is already known is only accepted as a
+/// replacement of a certificate in error.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeightPolicy {
+    /// Only the height following the latest settled certificate is accepted.
+    NextSettled,
+    /// The certificates can be submitted ahead of the settlement of the
+    /// previous ones, as long as there is no gap in the heights.
+    #[default]
+    Contiguous,
+}
+
+impl Default for RpcIntakeConfig {
+    fn default() -> Self {
+        Self {
+            workers: default_intake_workers(),
+            queue_size: default_intake_queue_size(),
+            estimation_workers: default_estimation_workers(),
+            max_bridge_exits: None,
+            max_imported_bridge_exits: None,
+            max_certificate_size: None,
+            networks: BTreeMap::new(),
+            height_policy: Default::default(),
+            screening: Default::default(),
+        }
+    }
+}
+
+impl RpcIntakeConfig {
+    /// The limits of the certificates of the given network.
+    pub fn limits(&self, network_id: u32) -> CertificateLimits {
+        let defaults = CertificateLimits {
+            max_bridge_exits: self.max_bridge_exits,
+            max_imported_bridge_exits: self.max_imported_bridge_exits,
+            max_certificate_size: self.max_certificate_size,
+        };
+
+        match self.networks.get(&network_id) {
+            Some(limits) => CertificateLimits {
+                max_bridge_exits: limits.max_bridge_exits.or(defaults.max_bridge_exits),
+                max_imported_bridge_exits: limits
+                    .max_imported_bridge_exits
+                    .or(defaults.max_imported_bridge_exits),
+                max_certificate_size: limits
+                    .max_certificate_size
+                    .or(defaults.max_certificate_size),
+            },
+            None => defaults,
+        }
+    }
+}
+
+/// Limits of the content of the certificates, checked at intake so that an
+/// oversized certificate is rejected before it takes a prover slot.
+///
+/// For a network, the limits left unset fall back to the global ones.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct CertificateLimits {
+    /// The maximum number of bridge exits of a certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bridge_exits: Option<usize>,
+
+    /// The maximum number of imported bridge exits of a certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_imported_bridge_exits: Option<usize>,
+
+    /// The maximum size of a certificate once serialized, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_certificate_size: Option<usize>,
+}
+
+/// Screening of the destination addresses of the bridge exits of the submitted
+/// certificates, for the operators required to reject the certificates
+/// sending funds to sanctioned addresses.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScreeningConfig {
+    /// The blocked destination addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocklist: Vec<Address>,
+
+    /// The external service screening the destination addresses, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<ScreeningServiceConfig>,
+}
+
+/// External service screening the destination addresses of the bridge exits.
+///
+/// The addresses are posted as JSON to the URL, which answers with the ones
+/// that are blocked.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScreeningServiceConfig {
+    /// The URL of the service.
+    pub url: Url,
+
+    /// The timeout of a request to the service.
+    #[serde(default = "default_screening_timeout")]
+    #[serde_as(as = "crate::with::HumanDuration")]
+    pub timeout: Duration,
+
+    /// Whether the certificates are accepted when the service fails to
+    /// answer. Otherwise, they are rejected until it is back.
+    #[serde(default)]
+    pub fail_open: bool,
+}
+
+/// Optional GraphQL endpoint exposing the certificates, epochs and networks,
+/// so that the explorers can query exactly the fields they need in one
+/// request.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcGraphqlConfig {
+    /// Whether the endpoint is served, at `/graphql`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The maximum number of items of a page.
+    #[serde(default = "default_graphql_max_page_size")]
+    pub max_page_size: usize,
+
+    /// The maximum depth of a query.
+    #[serde(default = "default_graphql_max_depth")]
+    pub max_depth: usize,
+}
+
+impl Default for RpcGraphqlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_page_size: default_graphql_max_page_size(),
+            max_depth: default_graphql_max_depth(),
+        }
+    }
+}
+
+/// Listener of the AdminRPC server, kept apart from the public endpoints so
+/// that the operational controls are never reachable through them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcAdminConfig {
+    /// The address the AdminRPC server is bound to on the `admin-port`,
+    /// typically a loopback one. Defaults to the `host` of the public
+    /// endpoints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<IpAddr>,
+
+    /// The unix socket the AdminRPC server is served on, instead of the
+    /// `admin-port`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unix_socket: Option<PathBuf>,
+
+    /// The networks, in CIDR notation, the AdminRPC server accepts the
+    /// connections from. All the connections are accepted if empty.
+    ///
+    /// Not applied to the unix socket, whose access is controlled by its file
+    /// permissions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_ips: Vec<IpNet>,
+}
+
+/// API keys identifying the clients of the agglayer when it is operated as a
+/// shared service, each key being subject to its own quotas.
+///
+/// The key is passed in the `x-api-key` header of the requests. The usage of
+/// each key is recorded per period, and the requests made once a quota is
+/// exhausted are rejected until the next period.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct RpcApiKeysConfig {
+    /// Whether the requests without an API key are rejected. Otherwise, they
+    /// are not subject to any quota.
+    #[serde(default)]
+    pub required: bool,
+
+    /// The period over which the quotas apply, counted from the UNIX epoch.
+    #[serde_as(as = "crate::with::HumanDuration")]
+    #[serde(default = "default_api_key_period")]
+    pub period: Duration,
+
+    /// The API keys, by name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, ApiKeyConfig>,
+}
+
+impl Default for RpcApiKeysConfig {
+    fn default() -> Self {
+        Self {
+            required: false,
+            period: default_api_key_period(),
+            keys: BTreeMap::new(),
+        }
+    }
+}
+
+impl RpcApiKeysConfig {
+    /// Find the name and configuration of the given API key.
+    pub fn find(&self, api_key: &str) -> Option<(&str, &ApiKeyConfig)> {
+        self.keys
+            .iter()
+            .find(|(_, config)| config.key == api_key)
+            .map(|(name, config)| (name.as_str(), config))
+    }
+}
+
+/// An API key and its quotas, unlimited if `None`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApiKeyConfig {
+    /// The secret passed by the client.
+    pub key: String,
+
+    /// The maximum number of requests per period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u64>,
+
+    /// The maximum size of the certificates submitted per period, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_certificate_bytes: Option<u64>,
 }
 
 impl Default for RpcConfig {
@@ -100,6 +458,13 @@ impl Default for RpcConfig {
             batch_request_limit: None,
             ping_interval: None,
             request_timeout: default_request_timeout(),
+            compression: Default::default(),
+            intake: Default::default(),
+            graphql: Default::default(),
+            admin: Default::default(),
+            api_keys: Default::default(),
+            sign_responses: false,
+            state_snapshots_dir: None,
         }
     }
 }
@@ -124,6 +489,51 @@ const fn default_request_timeout() -> Duration {
     Duration::from_secs(180)
 }
 
+/// The default number of certificates validated concurrently.
+const fn default_intake_workers() -> usize {
+    4
+}
+
+/// The default number of certificates waiting for a validation worker.
+const fn default_intake_queue_size() -> usize {
+    64
+}
+
+/// The default number of proving cost estimations run concurrently.
+const fn default_estimation_workers() -> usize {
+    2
+}
+
+/// The default timeout of a request to the screening service.
+const fn default_screening_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
+/// The default maximum number of items of a GraphQL page.
+const fn default_graphql_max_page_size() -> usize {
+    100
+}
+
+/// The default maximum depth of a GraphQL query.
+const fn default_graphql_max_depth() -> usize {
+    8
+}
+
+/// The default period of the API key quotas.
+const fn default_api_key_period() -> Duration {
+    Duration::from_secs(24 * 60 * 60)
+}
+
+/// Encodings are all supported by default.
+const fn default_encoding_enabled() -> bool {
+    true
+}
+
+/// The default minimum size of the response body to be compressed.
+const fn default_min_compression_size() -> u16 {
+    32
+}
+
 fn same_as_default_body_size(size: &u32) -> bool {
     *size == default_body_size()
 }
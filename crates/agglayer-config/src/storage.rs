@@ -1,8 +1,15 @@
-use std::path::{Component, Path, PathBuf};
+use std::{
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
 
 use agglayer_types::EpochNumber;
 use backup::BackupConfig;
+use compaction::CompactionConfig;
+use garbage_collection::GarbageCollectionConfig;
+use resources::StorageResourcesConfig;
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
 pub(crate) const STORAGE_DIR: &str = "storage";
 const METADATA_DB_NAME: &str = "metadata";
@@ -12,6 +19,9 @@ const EPOCHS_DB_PATH: &str = "epochs";
 const DEBUG_DB_PATH: &str = "debug";
 
 pub mod backup;
+pub mod compaction;
+pub mod garbage_collection;
+pub mod resources;
 
 /// Configuration for the storage.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -30,6 +40,23 @@ pub struct StorageConfig {
     pub debug_db_path: PathBuf,
     /// Backup config
     pub backup: BackupConfig,
+    /// Interval at which every entry of the storage is read back to detect
+    /// the corrupted ones. The storage is never scrubbed when unset.
+    pub scrub_interval: Option<Duration>,
+    /// Storage mirroring the writes to the pending and state storages, and
+    /// against which their reads are compared, to validate a migration
+    /// before cutting over. Disabled when unset.
+    pub shadow_db_path: Option<PathBuf>,
+    /// Directory of the secondary instances opened on the pending and state
+    /// storages in read-only mode, distinct for each read-only agglayer.
+    pub secondary_db_path: Option<PathBuf>,
+    /// Resources allotted to each store.
+    pub resources: StorageResourcesConfig,
+    /// Scheduled compaction of the pending and state storages.
+    pub compaction: CompactionConfig,
+    /// Scheduled collection of the orphaned proofs and of the certificates of
+    /// the removed networks.
+    pub garbage_collection: GarbageCollectionConfig,
 }
 
 impl Default for StorageConfig {
@@ -41,6 +68,12 @@ impl Default for StorageConfig {
             epochs_db_path: Path::new("./").join(STORAGE_DIR).join(EPOCHS_DB_PATH),
             debug_db_path: Path::new("./").join(STORAGE_DIR).join(DEBUG_DB_PATH),
             backup: BackupConfig::default(),
+            scrub_interval: None,
+            shadow_db_path: None,
+            secondary_db_path: None,
+            resources: StorageResourcesConfig::default(),
+            compaction: CompactionConfig::default(),
+            garbage_collection: GarbageCollectionConfig::default(),
         }
     }
 }
@@ -52,6 +85,12 @@ impl StorageConfig {
         self.state_db_path = normalize_path(&base_path.join(&self.state_db_path));
         self.epochs_db_path = normalize_path(&base_path.join(&self.epochs_db_path));
         self.debug_db_path = normalize_path(&base_path.join(&self.debug_db_path));
+        self.shadow_db_path = self
+            .shadow_db_path
+            .map(|path| normalize_path(&base_path.join(path)));
+        self.secondary_db_path = self
+            .secondary_db_path
+            .map(|path| normalize_path(&base_path.join(path)));
 
         self
     }
@@ -72,15 +111,42 @@ impl StorageConfig {
             epochs_db_path: db_path.join(EPOCHS_DB_PATH),
             debug_db_path: db_path.join(DEBUG_DB_PATH),
             backup: BackupConfig::default(),
+            scrub_interval: None,
+            shadow_db_path: None,
+            secondary_db_path: None,
+            resources: StorageResourcesConfig::default(),
+            compaction: CompactionConfig::default(),
+            garbage_collection: GarbageCollectionConfig::default(),
         }
     }
 
     pub fn epoch_db_path(&self, epoch_number: EpochNumber) -> PathBuf {
         self.epochs_db_path.join(format!("{epoch_number}"))
     }
+
+    /// Path of the shadow pending storage, if the shadow mode is enabled.
+    pub fn shadow_pending_db_path(&self) -> Option<PathBuf> {
+        Some(self.shadow_db_path.as_ref()?.join(PENDING_DB_NAME))
+    }
+
+    /// Path of the shadow state storage, if the shadow mode is enabled.
+    pub fn shadow_state_db_path(&self) -> Option<PathBuf> {
+        Some(self.shadow_db_path.as_ref()?.join(STATE_DB_NAME))
+    }
+
+    /// Path of the secondary instance of the pending storage, if configured.
+    pub fn secondary_pending_db_path(&self) -> Option<PathBuf> {
+        Some(self.secondary_db_path.as_ref()?.join(PENDING_DB_NAME))
+    }
+
+    /// Path of the secondary instance of the state storage, if configured.
+    pub fn secondary_state_db_path(&self) -> Option<PathBuf> {
+        Some(self.secondary_db_path.as_ref()?.join(STATE_DB_NAME))
+    }
 }
 
 /// Helper struct to deserialize the storage configuration.
+#[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 struct StorageConfigHelper {
@@ -99,6 +165,25 @@ struct StorageConfigHelper {
     /// Backup config.
     #[serde(default, skip_serializing_if = "BackupConfig::is_disabled")]
     pub backup: BackupConfig,
+    /// Interval at which the storage is scrubbed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<crate::with::HumanDuration>")]
+    pub scrub_interval: Option<Duration>,
+    /// Storage mirroring the writes, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_db_path: Option<PathBuf>,
+    /// Directory of the secondary instances, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_db_path: Option<PathBuf>,
+    /// Resources allotted to each store.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub resources: StorageResourcesConfig,
+    /// Scheduled compaction.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub compaction: CompactionConfig,
+    /// Scheduled garbage collection.
+    #[serde(default, skip_serializing_if = "crate::is_default")]
+    pub garbage_collection: GarbageCollectionConfig,
 }
 
 impl From<StorageConfigHelper> for StorageConfig {
@@ -120,6 +205,12 @@ impl From<StorageConfigHelper> for StorageConfig {
                 .debug_db_path
                 .unwrap_or_else(|| value.db_path.join(DEBUG_DB_PATH)),
             backup: value.backup,
+            scrub_interval: value.scrub_interval,
+            shadow_db_path: value.shadow_db_path,
+            secondary_db_path: value.secondary_db_path,
+            resources: value.resources,
+            compaction: value.compaction,
+            garbage_collection: value.garbage_collection,
         }
     }
 }
@@ -140,6 +231,12 @@ impl From<StorageConfig> for StorageConfigHelper {
             epochs_db_path: None,
             debug_db_path: None,
             backup: value.backup,
+            scrub_interval: value.scrub_interval,
+            shadow_db_path: value.shadow_db_path,
+            secondary_db_path: value.secondary_db_path,
+            resources: value.resources,
+            compaction: value.compaction,
+            garbage_collection: value.garbage_collection,
         }
     }
 }
@@ -208,5 +305,62 @@ mod tests {
         assert_eq!(config.state_db_path, PathBuf::from("/tmp/base/state"));
         assert_eq!(config.epochs_db_path, PathBuf::from("/tmp/base/epochs"));
         assert_eq!(config.debug_db_path, PathBuf::from("/tmp/base/debug"));
+        assert_eq!(config.shadow_pending_db_path(), None);
+    }
+
+    #[test]
+    fn shadow_path() {
+        let value = toml::toml! {
+            db-path = "/tmp/base"
+            shadow-db-path = "/tmp/shadow"
+        };
+
+        let cfg = toml::to_string(&value).unwrap();
+        let config: StorageConfig = toml::from_str(&cfg).unwrap();
+
+        assert_eq!(
+            config.shadow_pending_db_path(),
+            Some(PathBuf::from("/tmp/shadow/pending"))
+        );
+        assert_eq!(
+            config.shadow_state_db_path(),
+            Some(PathBuf::from("/tmp/shadow/state"))
+        );
+    }
+
+    #[test]
+    fn resources() {
+        let value = toml::toml! {
+            epochs-db-path = "/mnt/hdd/epochs"
+
+            [resources]
+            max-open-epochs = 4
+
+            [resources.state]
+            block-cache-size = 1073741824
+            max-open-files = -1
+
+            [resources.epochs]
+            max-open-files = 64
+        };
+
+        let cfg = toml::to_string(&value).unwrap();
+        let config: StorageConfig = toml::from_str(&cfg).unwrap();
+
+        assert_eq!(config.epochs_db_path, PathBuf::from("/mnt/hdd/epochs"));
+        assert_eq!(
+            config.resources.state,
+            resources::DbResourcesConfig {
+                block_cache_size: Some(1073741824),
+                max_open_files: Some(-1),
+            }
+        );
+        assert_eq!(config.resources.epochs.max_open_files, Some(64));
+        assert_eq!(config.resources.max_open_epochs, 4);
+        assert_eq!(config.resources.epochs.block_cache_size, None);
+        assert_eq!(
+            config.resources.pending,
+            resources::DbResourcesConfig::default()
+        );
     }
 }
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Configuration of the scheduled compaction of the storage.
+#[serde_as]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CompactionConfig {
+    /// Interval at which the column families are compacted. The storage is
+    /// never compacted on schedule when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<crate::with::HumanDuration>")]
+    pub interval: Option<Duration>,
+    /// Names of the column families to compact, all of them when empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+}
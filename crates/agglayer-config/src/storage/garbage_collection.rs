@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// Configuration of the garbage collection of the storage.
+#[serde_as]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct GarbageCollectionConfig {
+    /// Interval at which the orphaned entries are collected. The storage is
+    /// never garbage collected when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde_as(as = "Option<crate::with::HumanDuration>")]
+    pub interval: Option<Duration>,
+    /// Only report the orphaned entries, without deleting them.
+    #[serde(skip_serializing_if = "crate::is_false")]
+    pub dry_run: bool,
+}
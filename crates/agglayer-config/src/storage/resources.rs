@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// Resources allotted to the RocksDB instance of each store, so that the hot
+/// and cold stores can be sized for the disks they are placed on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct StorageResourcesConfig {
+    /// Resources of the pending storage.
+    #[serde(skip_serializing_if = "crate::is_default")]
+    pub pending: DbResourcesConfig,
+    /// Resources of the state storage.
+    #[serde(skip_serializing_if = "crate::is_default")]
+    pub state: DbResourcesConfig,
+    /// Resources of each per-epoch storage.
+    #[serde(skip_serializing_if = "crate::is_default")]
+    pub epochs: DbResourcesConfig,
+    /// Resources of the debug storage.
+    #[serde(skip_serializing_if = "crate::is_default")]
+    pub debug: DbResourcesConfig,
+    /// Maximum number of settled epochs kept open for the queries, the least
+    /// recently queried one being closed first. The epochs are opened for
+    /// each query when zero.
+    #[serde(skip_serializing_if = "same_as_default_max_open_epochs")]
+    pub max_open_epochs: usize,
+}
+
+impl Default for StorageResourcesConfig {
+    fn default() -> Self {
+        Self {
+            pending: DbResourcesConfig::default(),
+            state: DbResourcesConfig::default(),
+            epochs: DbResourcesConfig::default(),
+            debug: DbResourcesConfig::default(),
+            max_open_epochs: default_max_open_epochs(),
+        }
+    }
+}
+
+const fn default_max_open_epochs() -> usize {
+    16
+}
+
+const fn same_as_default_max_open_epochs(v: &usize) -> bool {
+    *v == default_max_open_epochs()
+}
+
+/// Resources allotted to a RocksDB instance, the RocksDB defaults being used
+/// for the unset ones.
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct DbResourcesConfig {
+    /// Size in bytes of the block cache shared by the column families.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_cache_size: Option<u64>,
+    /// Maximum number of files kept open, `-1` keeping all of them open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_open_files: Option<i32>,
+}
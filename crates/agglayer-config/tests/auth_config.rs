@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use agglayer_config::Config;
+use agglayer_config::{AuthConfig, Config};
 use insta::assert_toml_snapshot;
 
 #[test]
@@ -21,3 +21,20 @@ fn auth_kebab_case() {
         }),
     });
 }
+
+#[test]
+fn auth_rotation() {
+    let input = "./tests/fixtures/valide_config/auth_rotation.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    let rotation = config.auth_rotation.unwrap();
+    assert_eq!(rotation.activation_epoch, 100);
+    let AuthConfig::Local(next) = rotation.next else {
+        panic!("Expected a local next key");
+    };
+    assert_eq!(
+        next.private_keys[0].path,
+        Path::new("/pk/next.keystore").to_path_buf()
+    );
+}
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use agglayer_config::{outbound::OutboundConfig, Multiplier};
+use agglayer_config::{
+    outbound::{HttpClientConfig, OutboundConfig},
+    Multiplier,
+};
 use insta::assert_toml_snapshot;
 
 #[test]
@@ -39,3 +42,21 @@ fn deserialize_custom_outbound_config() {
 
     assert_toml_snapshot!(config);
 }
+
+#[test]
+fn deserialize_http_outbound_config() {
+    let input = "./tests/fixtures/outbound/http.toml";
+    let content = std::fs::read_to_string(input).unwrap();
+    let config: OutboundConfig = toml::from_str(&content).unwrap();
+
+    assert_eq!(
+        config.http,
+        HttpClientConfig {
+            ca_bundle: Some("/etc/agglayer/ca.pem".into()),
+            client_identity: Some("/etc/agglayer/client.pem".into()),
+            http_proxy: None,
+            https_proxy: Some("http://proxy.internal:3128".into()),
+            no_proxy: vec!["localhost".into(), ".internal".into()],
+        }
+    );
+}
@@ -1,4 +1,8 @@
-use std::path::Path;
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    path::Path,
+    time::Duration,
+};
 
 use agglayer_config::Config;
 use agglayer_prover_config::ProverConfig;
@@ -117,3 +121,451 @@ fn extra_certificate_signers() {
         alloy_primitives::address!("abcdefabcdefabcdefabcdefabcdefabcdefabcd").0
     );
 }
+
+#[test]
+fn rpc_compression() {
+    let input = "./tests/fixtures/valide_config/rpc_compression.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.rpc.compression,
+        agglayer_config::RpcCompressionConfig {
+            gzip: true,
+            deflate: false,
+            br: false,
+            zstd: true,
+            min_size: 1024,
+        }
+    );
+}
+
+#[test]
+fn rpc_intake() {
+    let input = "./tests/fixtures/valide_config/rpc_intake.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.rpc.intake,
+        agglayer_config::RpcIntakeConfig {
+            workers: 8,
+            queue_size: 64,
+            estimation_workers: 2,
+            max_bridge_exits: None,
+            max_imported_bridge_exits: Some(1000),
+            max_certificate_size: Some(5_000_000),
+            networks: [(
+                2,
+                agglayer_config::CertificateLimits {
+                    max_bridge_exits: Some(100),
+                    max_imported_bridge_exits: None,
+                    max_certificate_size: Some(100_000),
+                }
+            )]
+            .into(),
+            height_policy: agglayer_config::HeightPolicy::NextSettled,
+            screening: Default::default(),
+        }
+    );
+
+    // The limits unset for a network fall back to the global ones.
+    assert_eq!(
+        config.rpc.intake.limits(2),
+        agglayer_config::CertificateLimits {
+            max_bridge_exits: Some(100),
+            max_imported_bridge_exits: Some(1000),
+            max_certificate_size: Some(100_000),
+        }
+    );
+    assert_eq!(
+        config.rpc.intake.limits(1),
+        agglayer_config::CertificateLimits {
+            max_bridge_exits: None,
+            max_imported_bridge_exits: Some(1000),
+            max_certificate_size: Some(5_000_000),
+        }
+    );
+}
+
+#[test]
+fn rpc_screening() {
+    let input = "./tests/fixtures/valide_config/rpc_screening.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+    let screening = config.rpc.intake.screening;
+
+    assert_eq!(
+        screening
+            .blocklist
+            .iter()
+            .map(|address| address.into_alloy())
+            .collect::<Vec<_>>(),
+        vec![alloy_primitives::address!(
+            "8ba1f109551bD432803012645Ac136ddd64DBA72"
+        )]
+    );
+    assert_eq!(
+        screening.service,
+        Some(agglayer_config::ScreeningServiceConfig {
+            url: "http://screening:8080/screen".parse().unwrap(),
+            timeout: Duration::from_secs(2),
+            fail_open: false,
+        })
+    );
+}
+
+#[test]
+fn rpc_sign_responses() {
+    let input = "./tests/fixtures/valide_config/rpc_sign_responses.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert!(config.rpc.sign_responses);
+    assert!(!agglayer_config::RpcConfig::default().sign_responses);
+}
+
+#[test]
+fn rpc_state_snapshots_dir() {
+    let input = "./tests/fixtures/valide_config/rpc_state_snapshots_dir.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.rpc.state_snapshots_dir,
+        Some("/var/lib/agglayer/snapshots".into())
+    );
+    assert!(agglayer_config::RpcConfig::default()
+        .state_snapshots_dir
+        .is_none());
+}
+
+#[test]
+fn rpc_graphql() {
+    let input = "./tests/fixtures/valide_config/rpc_graphql.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.rpc.graphql,
+        agglayer_config::RpcGraphqlConfig {
+            enabled: true,
+            max_page_size: 50,
+            max_depth: 8,
+        }
+    );
+}
+
+#[test]
+fn rpc_admin() {
+    let input = "./tests/fixtures/valide_config/rpc_admin.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.rpc.admin,
+        agglayer_config::RpcAdminConfig {
+            host: Some("127.0.0.1".parse().unwrap()),
+            unix_socket: Some("/run/agglayer/admin.sock".into()),
+            allowed_ips: vec!["10.0.0.0/8".parse().unwrap(), "::1/128".parse().unwrap()],
+        }
+    );
+    assert_eq!(config.admin_rpc_addr(), "127.0.0.1:9191".parse().unwrap());
+}
+
+#[test]
+fn rpc_api_keys() {
+    let input = "./tests/fixtures/valide_config/rpc_api_keys.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.rpc.api_keys,
+        agglayer_config::RpcApiKeysConfig {
+            required: true,
+            period: Duration::from_secs(3600),
+            keys: [
+                (
+                    "chain-a".to_string(),
+                    agglayer_config::ApiKeyConfig {
+                        key: "secret-a".to_string(),
+                        max_requests: Some(1000),
+                        max_certificate_bytes: Some(10_000_000),
+                    },
+                ),
+                (
+                    "chain-b".to_string(),
+                    agglayer_config::ApiKeyConfig {
+                        key: "secret-b".to_string(),
+                        max_requests: None,
+                        max_certificate_bytes: None,
+                    },
+                ),
+            ]
+            .into(),
+        }
+    );
+    assert_eq!(
+        config.rpc.api_keys.find("secret-b").map(|(name, _)| name),
+        Some("chain-b")
+    );
+}
+
+#[test]
+fn diagnostics() {
+    let input = "./tests/fixtures/valide_config/diagnostics.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.diagnostics,
+        agglayer_config::diagnostics::DiagnosticsConfig {
+            evaluation_interval: Duration::from_secs(30),
+            max_epochs_without_settlement: 5,
+            max_proving_failure_percent: 20,
+            max_l1_lag_blocks: 0,
+            webhook_urls: vec!["https://alerts.example.com/agglayer".parse().unwrap()],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn max_concurrent_proofs() {
+    let input = "./tests/fixtures/valide_config/max_concurrent_proofs.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(config.certificate_orchestrator.max_concurrent_proofs, 4);
+    assert_eq!(config.certificate_orchestrator.verification_workers, 8);
+}
+
+#[test]
+fn sp1_network_pricing() {
+    let input = "./tests/fixtures/valide_config/sp1_network_pricing.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    let pricing = config.certificate_orchestrator.sp1_network_pricing.unwrap();
+    assert_eq!(pricing.price_per_million_cycles, 2_000_000_000_000);
+    assert_eq!(pricing.cycles_per_second, 5_000_000);
+    assert_eq!(pricing.proving_overhead, Duration::from_secs(30));
+}
+
+#[test]
+fn sp1_network_quota() {
+    let input = "./tests/fixtures/valide_config/sp1_network_quota.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    let quota = config.certificate_orchestrator.sp1_network_quota.unwrap();
+    assert_eq!(quota.period, Duration::from_secs(60 * 60));
+    assert_eq!(quota.max_proofs, NonZeroU32::new(20));
+    assert_eq!(quota.max_cycles, Some(50_000_000_000));
+    assert_eq!(quota.max_queue_wait, Duration::from_secs(10 * 60));
+}
+
+#[test]
+fn failed_proof_stdin_dir() {
+    let input = "./tests/fixtures/valide_config/failed_proof_stdin_dir.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.certificate_orchestrator.failed_proof_stdin_dir,
+        Some(
+            Path::new("./tests/fixtures/valide_config")
+                .canonicalize()
+                .unwrap()
+                .join("failed-proofs")
+        )
+    );
+    assert_eq!(
+        config.certificate_orchestrator.max_certification_failures,
+        20
+    );
+}
+
+#[test]
+fn pending_expiry() {
+    let input = "./tests/fixtures/valide_config/pending_expiry.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.certificate_orchestrator.pending_expiry,
+        agglayer_config::certificate_orchestrator::pending_expiry::PendingExpiryConfig {
+            ttl: Some(Duration::from_secs(6 * 60 * 60)),
+            retention: Duration::from_secs(2 * 24 * 60 * 60),
+            check_interval: Duration::from_secs(60),
+        }
+    );
+}
+
+#[test]
+fn retry_policy() {
+    use agglayer_config::certificate_orchestrator::retry_policy::RetryPolicy;
+
+    let input = "./tests/fixtures/valide_config/retry_policy.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+    let retry_policy = config.certificate_orchestrator.retry_policy;
+
+    assert_eq!(
+        retry_policy.prover_transient,
+        RetryPolicy {
+            retryable: true,
+            max_retries: Some(3),
+            backoff: Duration::from_secs(30),
+        }
+    );
+    assert_eq!(retry_policy.prover_permanent, RetryPolicy::default());
+    assert_eq!(retry_policy.settlement_revert, RetryPolicy::default());
+    assert!(!retry_policy.l1_timeout.retryable);
+}
+
+#[test]
+fn epoch_participation() {
+    let input = "./tests/fixtures/valide_config/epoch_participation.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config
+            .certificate_orchestrator
+            .epoch_participation
+            .settlement_interval,
+        [(3, 4), (7, 10)]
+            .into_iter()
+            .map(|(network_id, interval)| (network_id, NonZeroU64::new(interval).unwrap()))
+            .collect()
+    );
+}
+
+#[test]
+fn callback_hmac_secrets() {
+    let input = "./tests/fixtures/valide_config/callback_hmac_secrets.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+    let callback = &config.outbound.callback;
+
+    assert_eq!(callback.hmac_secret(1.into()), Some("secret-of-network-1"));
+    assert_eq!(callback.hmac_secret(2.into()), Some("secret-of-network-2"));
+    assert_eq!(callback.hmac_secret(3.into()), None);
+}
+
+#[test]
+fn storage_scrub() {
+    let input = "./tests/fixtures/valide_config/storage_scrub.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.storage.scrub_interval,
+        Some(Duration::from_secs(24 * 60 * 60))
+    );
+}
+
+#[test]
+fn storage_compaction() {
+    let input = "./tests/fixtures/valide_config/storage_compaction.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.storage.compaction,
+        agglayer_config::storage::compaction::CompactionConfig {
+            interval: Some(Duration::from_secs(7 * 24 * 60 * 60)),
+            columns: vec![
+                "certificate_header_cf".to_string(),
+                "pending_queue_cf".to_string()
+            ],
+        }
+    );
+}
+
+#[test]
+fn storage_garbage_collection() {
+    let input = "./tests/fixtures/valide_config/storage_garbage_collection.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.storage.garbage_collection,
+        agglayer_config::storage::garbage_collection::GarbageCollectionConfig {
+            interval: Some(Duration::from_secs(24 * 60 * 60)),
+            dry_run: true,
+        }
+    );
+}
+
+#[test]
+fn read_only() {
+    let input = "./tests/fixtures/valide_config/read_only.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.read_only,
+        agglayer_config::read_only::ReadOnlyConfig {
+            enabled: true,
+            catch_up_interval: Duration::from_secs(5),
+        }
+    );
+    assert!(config
+        .storage
+        .secondary_state_db_path()
+        .unwrap()
+        .ends_with("secondary/state"));
+}
+
+#[test]
+fn read_only_requires_secondary_path() {
+    let input = "./tests/fixtures/invalid_config/read_only_without_secondary_path.toml";
+
+    let error = Config::try_load(Path::new(input)).unwrap_err();
+
+    assert!(error.to_string().contains("secondary-db-path"));
+}
+
+#[test]
+fn prover_proving_timeout() {
+    let input = "./tests/fixtures/valide_config/prover_proving_timeout.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.certificate_orchestrator.prover,
+        agglayer_config::certificate_orchestrator::prover::ProverConfig::SP1Network {
+            proving_timeout: Some(Duration::from_secs(45 * 60)),
+        }
+    );
+    assert_eq!(
+        config.certificate_orchestrator.prover.proving_timeout(),
+        Duration::from_secs(45 * 60)
+    );
+
+    assert_eq!(
+        agglayer_config::certificate_orchestrator::prover::ProverConfig::default()
+            .proving_timeout(),
+        Duration::from_secs(60 * 60)
+    );
+}
+
+#[test]
+fn rollup_manager_abi() {
+    let input = "./tests/fixtures/valide_config/rollup_manager_abi.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.l1.rollup_manager_abi,
+        agglayer_config::RollupManagerAbi::Pessimistic
+    );
+    assert_eq!(
+        Config::new(Path::new("/tmp/agglayer"))
+            .l1
+            .rollup_manager_abi,
+        agglayer_config::RollupManagerAbi::Auto
+    );
+}
@@ -23,6 +23,10 @@ pub trait AggchainContract {
         &self,
         rollup_address: Address,
     ) -> Result<(Vec<Address>, usize), L1RpcError>;
+
+    /// Returns the origin network and address of the gas token of the
+    /// rollup, the zero address meaning L1 ETH.
+    async fn get_gas_token(&self, rollup_address: Address) -> Result<(u32, Address), L1RpcError>;
 }
 
 #[async_trait::async_trait]
@@ -95,4 +99,23 @@ where
 
         Ok((signers, threshold))
     }
+
+    async fn get_gas_token(&self, rollup_address: Address) -> Result<(u32, Address), L1RpcError> {
+        let client = AggchainBase::new(rollup_address.into(), self.rpc.clone());
+
+        let gas_token_address = client
+            .gasTokenAddress()
+            .call()
+            .await
+            .map(Address::from_alloy)
+            .map_err(L1RpcError::GasTokenFetchFailed)?;
+
+        let gas_token_network = client
+            .gasTokenNetwork()
+            .call()
+            .await
+            .map_err(L1RpcError::GasTokenFetchFailed)?;
+
+        Ok((gas_token_network, gas_token_address))
+    }
 }
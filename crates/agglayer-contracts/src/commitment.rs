@@ -0,0 +1,53 @@
+use alloy::{
+    network::TransactionBuilder as _,
+    primitives::{Address, TxHash, B256},
+    providers::Provider,
+    rpc::types::TransactionRequest,
+};
+use tracing::debug;
+
+use crate::{L1RpcClient, L1RpcError};
+
+/// Publication on L1 of the commitments to the certificates of the settled
+/// epochs.
+#[async_trait::async_trait]
+pub trait EpochCommitmentPublisher {
+    /// Publish the commitment of the epoch as the calldata of a transaction
+    /// sending no value to the given address, the epoch number encoded as 8
+    /// big-endian bytes followed by the 32 bytes of the commitment.
+    async fn publish_epoch_commitment(
+        &self,
+        to: Address,
+        epoch_number: u64,
+        commitment: B256,
+    ) -> Result<TxHash, L1RpcError>;
+}
+
+#[async_trait::async_trait]
+impl<RpcProvider> EpochCommitmentPublisher for L1RpcClient<RpcProvider>
+where
+    RpcProvider: Provider + Clone + 'static,
+{
+    async fn publish_epoch_commitment(
+        &self,
+        to: Address,
+        epoch_number: u64,
+        commitment: B256,
+    ) -> Result<TxHash, L1RpcError> {
+        let input = [epoch_number.to_be_bytes().as_slice(), commitment.as_slice()].concat();
+        let request = TransactionRequest::default().with_to(to).with_input(input);
+
+        let pending = self.rpc.send_transaction(request).await.map_err(|error| {
+            L1RpcError::EpochCommitmentPublicationFailed {
+                epoch_number,
+                source: error.into(),
+            }
+        })?;
+        debug!(
+            tx_hash = %pending.tx_hash(),
+            "Published the commitment of the epoch {epoch_number}"
+        );
+
+        Ok(*pending.tx_hash())
+    }
+}
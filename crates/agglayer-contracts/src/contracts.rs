@@ -40,6 +40,58 @@ sol!(
     "src/contracts/PolygonRollupManager.json"
 );
 
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug, Eq, PartialEq)]
+    interface IMulticall {
+        /// Execute the given calls on the contract itself, in the context of
+        /// the original sender.
+        function multicall(bytes[] calldata data) external returns (bytes[] memory results);
+    }
+);
+
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug, Eq, PartialEq)]
+    interface IPessimisticRollupManager {
+        /// Settlement of the rollup managers predating the aggchains, which
+        /// take no aggchain data.
+        function verifyPessimisticTrustedAggregator(
+            uint32 rollupID,
+            uint32 l1InfoTreeLeafCount,
+            bytes32 newLocalExitRoot,
+            bytes32 newPessimisticRoot,
+            bytes calldata proof
+        ) external;
+
+        /// Public values of the pessimistic proof checked by the settlement of
+        /// the rollup managers predating the aggchains.
+        function getInputPessimisticBytes(
+            uint32 rollupID,
+            bytes32 l1InfoTreeRoot,
+            bytes32 newLocalExitRoot,
+            bytes32 newPessimisticRoot
+        ) external view returns (bytes memory);
+    }
+);
+
+sol!(
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug, Eq, PartialEq)]
+    interface ISP1Verifier {
+        /// Verify the proof of the program of the given verifying key,
+        /// reverting if the proof is invalid.
+        function verifyProof(
+            bytes32 programVKey,
+            bytes calldata publicValues,
+            bytes calldata proofBytes
+        ) external view;
+    }
+);
+
 sol!(
     #[allow(missing_docs)]
     #[allow(clippy::too_many_arguments)]
@@ -15,13 +15,15 @@ use alloy::{
 use tracing::{debug, error, info};
 
 pub mod aggchain;
+pub mod commitment;
 pub mod contracts;
 pub mod rollup;
 pub mod settler;
 
 pub use aggchain::AggchainContract;
+pub use commitment::EpochCommitmentPublisher;
 pub use rollup::RollupContract;
-pub use settler::Settler;
+pub use settler::{PessimisticSettlement, RollupManagerAbi, Settler};
 
 /// Gas price parameters for L1 transactions.
 #[derive(Debug, Clone)]
@@ -105,6 +107,8 @@ pub struct L1RpcClient<RpcProvider> {
     /// This is to avoid hitting provider limits when querying large block
     /// ranges or errors like "query returned more than 10000 results".
     event_filter_block_range: u64,
+    /// Settlement ABI of the rollup manager.
+    rollup_manager_abi: RollupManagerAbi,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -171,6 +175,33 @@ pub enum L1RpcError {
     FailedToQueryEvents(String),
     #[error("L1 info roots cache lock poisoned")]
     CacheLockPoisoned,
+    #[error("Unable to retrieve the gateway address: {0}")]
+    GatewayRetrievalFailed(#[source] alloy::contract::Error),
+    #[error("Verifier type {0} does not verify pessimistic proofs")]
+    UnsupportedVerifierType(u8),
+    #[error("The proof is rejected by the verifier on L1: {0}")]
+    ProofRejectedByVerifier(String),
+    #[error("Unable to build the public values of the proof on L1: {0}")]
+    PublicValuesRetrievalFailed(#[source] alloy::contract::Error),
+    #[error("Unable to simulate the proof verification on L1: {0}")]
+    ProofVerificationCallFailed(#[source] alloy::contract::Error),
+    #[error("Unable to fetch the rollup manager version: {0}")]
+    RollupManagerVersionFetchFailed(#[source] alloy::contract::Error),
+    #[error(
+        "The rollup manager settles with the pessimistic ABI, which doesn't take the aggchain \
+         data of the settlement of rollup {rollup_id}"
+    )]
+    AggchainDataUnsupportedByRollupManager { rollup_id: u32 },
+    #[error("Unable to submit the settlement transaction: {0}")]
+    SettlementSubmissionFailed(#[source] alloy::contract::Error),
+    #[error("Unable to fetch the gas token: {0}")]
+    GasTokenFetchFailed(#[source] alloy::contract::Error),
+    #[error("Unable to publish the commitment of the epoch {epoch_number}")]
+    EpochCommitmentPublicationFailed {
+        epoch_number: u64,
+        #[source]
+        source: eyre::Error,
+    },
 }
 
 impl<RpcProvider> L1RpcClient<RpcProvider>
@@ -196,6 +227,7 @@ where
             gas_price_params,
             l1_info_roots: Arc::new(RwLock::new(HashMap::new())),
             event_filter_block_range,
+            rollup_manager_abi: RollupManagerAbi::default(),
         }
     }
 
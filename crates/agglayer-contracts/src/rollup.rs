@@ -13,8 +13,11 @@ use num_traits::FromPrimitive;
 use tracing::{debug, error, trace};
 
 use crate::{
-    contracts::{PolygonRollupManager::RollupDataReturnV2, PolygonZkEvm},
-    L1RpcClient, L1RpcError,
+    contracts::{
+        AgglayerGateway, IPessimisticRollupManager, ISP1Verifier,
+        PolygonRollupManager::RollupDataReturnV2, PolygonZkEvm,
+    },
+    L1RpcClient, L1RpcError, PessimisticSettlement, RollupManagerAbi,
 };
 
 #[derive(Debug, FromPrimitive)]
@@ -48,6 +51,17 @@ pub trait RollupContract {
     async fn get_l1_info_root(&self, l1_leaf_count: u32) -> Result<[u8; 32], L1RpcError>;
     async fn get_verifier_type(&self, rollup_id: u32) -> Result<VerifierType, L1RpcError>;
 
+    /// Simulate the verification of the proof of a settlement by the verifier
+    /// and verifying key the rollup currently settles with on L1, on the
+    /// public values the rollup manager builds from the settlement arguments.
+    ///
+    /// The proof is expected with the selector of the program when the rollup
+    /// verifies through the gateway, as in the settlement transaction.
+    async fn verify_pessimistic_proof(
+        &self,
+        settlement: &PessimisticSettlement,
+    ) -> Result<(), L1RpcError>;
+
     fn default_l1_info_tree_entry(&self) -> (u32, [u8; 32]);
 
     fn get_rollup_manager_address(&self) -> Address;
@@ -296,6 +310,89 @@ where
             .ok_or(L1RpcError::VerifierTypeRetrievalFailed)?)
     }
 
+    async fn verify_pessimistic_proof(
+        &self,
+        settlement: &PessimisticSettlement,
+    ) -> Result<(), L1RpcError> {
+        let rollup_id = settlement.rollup_id;
+        let rollup_data: RollupDataReturnV2 = self
+            .inner
+            .rollupIDToRollupDataV2(rollup_id)
+            .call()
+            .await
+            .map_err(|_| L1RpcError::RollupDataRetrievalFailed)?;
+
+        // Public values checked by the settlement, built by the rollup manager
+        // rather than taken from the proof.
+        let l1_info_root = self
+            .get_l1_info_root(settlement.l_1_info_tree_leaf_count)
+            .await?;
+        let public_values = match self.rollup_manager_abi {
+            RollupManagerAbi::Aggchain => {
+                self.inner
+                    .getInputPessimisticBytes(
+                        rollup_id,
+                        l1_info_root.into(),
+                        settlement.new_local_exit_root.into(),
+                        settlement.new_pessimistic_root.into(),
+                        settlement.custom_chain_data.clone(),
+                    )
+                    .call()
+                    .await
+            }
+            RollupManagerAbi::Pessimistic => {
+                IPessimisticRollupManager::new(*self.inner.address(), self.rpc.clone())
+                    .getInputPessimisticBytes(
+                        rollup_id,
+                        l1_info_root.into(),
+                        settlement.new_local_exit_root.into(),
+                        settlement.new_pessimistic_root.into(),
+                    )
+                    .call()
+                    .await
+            }
+        }
+        .map_err(L1RpcError::PublicValuesRetrievalFailed)?;
+        let proof = settlement.proof.clone();
+
+        let verification = match VerifierType::from_u8(rollup_data.rollupVerifierType) {
+            Some(VerifierType::Pessimistic) => {
+                ISP1Verifier::new(rollup_data.verifier, self.rpc.clone())
+                    .verifyProof(rollup_data.programVKey, public_values, proof)
+                    .call()
+                    .await
+                    .map(|_| ())
+            }
+            Some(VerifierType::ALGateway) => {
+                let gateway = self
+                    .inner
+                    .aggLayerGateway()
+                    .call()
+                    .await
+                    .map_err(L1RpcError::GatewayRetrievalFailed)?;
+
+                AgglayerGateway::new(gateway, self.rpc.clone())
+                    .verifyPessimisticProof(public_values, proof)
+                    .call()
+                    .await
+                    .map(|_| ())
+            }
+            Some(VerifierType::StateTransition) | None => {
+                return Err(L1RpcError::UnsupportedVerifierType(
+                    rollup_data.rollupVerifierType,
+                ));
+            }
+        };
+
+        verification.map_err(|error| match error.as_revert_data() {
+            Some(revert_data) => L1RpcError::ProofRejectedByVerifier(
+                alloy::sol_types::decode_revert_reason(revert_data.as_ref())
+                    .unwrap_or_else(|| format!("0x{}", hex::encode(revert_data))),
+            ),
+            None => L1RpcError::ProofVerificationCallFailed(error),
+        })
+    }
+
     fn get_rollup_manager_address(&self) -> Address {
         (*self.inner.address()).into()
     }
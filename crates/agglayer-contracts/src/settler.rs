@@ -1,15 +1,95 @@
 use alloy::{
-    contract::Error as ContractError,
+    contract::{CallBuilder, CallDecoder, Error as ContractError},
     eips::eip1559::Eip1559Estimation,
     primitives::Bytes,
     providers::{PendingTransactionBuilder, Provider},
+    sol_types::SolCall as _,
 };
-use tracing::debug;
+use tracing::{debug, info, warn};
 
-use crate::{adjust_gas_estimate, L1RpcClient};
+use crate::{
+    adjust_gas_estimate,
+    contracts::{
+        IMulticall, IPessimisticRollupManager,
+        PolygonRollupManager::verifyPessimisticTrustedAggregatorCall,
+    },
+    L1RpcClient, L1RpcError,
+};
 
 const DEFAULT_GAS_PRICE_REPEAT_TX_INCREASE_FACTOR: u128 = 150; //1.5X
 
+/// Arguments of a `verifyPessimisticTrustedAggregator` call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PessimisticSettlement {
+    pub rollup_id: u32,
+    pub l_1_info_tree_leaf_count: u32,
+    pub new_local_exit_root: [u8; 32],
+    pub new_pessimistic_root: [u8; 32],
+    pub proof: Bytes,
+    pub custom_chain_data: Bytes,
+}
+
+impl PessimisticSettlement {
+    /// Calldata of the settlement for the given ABI of the rollup manager.
+    ///
+    /// Fails if the settlement carries aggchain data the rollup manager
+    /// doesn't take.
+    pub fn calldata(self, abi: RollupManagerAbi) -> Result<Bytes, L1RpcError> {
+        let calldata = match abi {
+            RollupManagerAbi::Aggchain => verifyPessimisticTrustedAggregatorCall {
+                rollupID: self.rollup_id,
+                l1InfoTreeLeafCount: self.l_1_info_tree_leaf_count,
+                newLocalExitRoot: self.new_local_exit_root.into(),
+                newPessimisticRoot: self.new_pessimistic_root.into(),
+                proof: self.proof,
+                aggchainData: self.custom_chain_data,
+            }
+            .abi_encode(),
+            RollupManagerAbi::Pessimistic if !self.custom_chain_data.is_empty() => {
+                return Err(L1RpcError::AggchainDataUnsupportedByRollupManager {
+                    rollup_id: self.rollup_id,
+                });
+            }
+            RollupManagerAbi::Pessimistic => {
+                IPessimisticRollupManager::verifyPessimisticTrustedAggregatorCall {
+                    rollupID: self.rollup_id,
+                    l1InfoTreeLeafCount: self.l_1_info_tree_leaf_count,
+                    newLocalExitRoot: self.new_local_exit_root.into(),
+                    newPessimisticRoot: self.new_pessimistic_root.into(),
+                    proof: self.proof,
+                }
+                .abi_encode()
+            }
+        };
+
+        Ok(calldata.into())
+    }
+}
+
+/// Version of the settlement ABI of the rollup manager, which changes along
+/// with the upgrades of the L1 contracts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RollupManagerAbi {
+    /// `verifyPessimisticTrustedAggregator` without the aggchain data, of the
+    /// rollup managers predating the aggchains.
+    Pessimistic,
+    /// `verifyPessimisticTrustedAggregator` along with the aggchain data.
+    #[default]
+    Aggchain,
+}
+
+impl RollupManagerAbi {
+    /// ABI of the rollup manager of the given `ROLLUP_MANAGER_VERSION`, if
+    /// known.
+    pub fn from_version(version: &str) -> Option<Self> {
+        match version {
+            "pessimistic" => Some(Self::Pessimistic),
+            version if version.starts_with("al-") => Some(Self::Aggchain),
+            _ => None,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait Settler {
     fn decode_contract_revert(error: &ContractError) -> Option<String>;
@@ -25,7 +105,18 @@ pub trait Settler {
         custom_chain_data: Bytes,
         nonce_info: Option<(u64, u128, Option<u128>)>, /* nonce, previous_max_fee_per_gas,
                                                         * optional previous_max_priority_fee_per_gas */
-    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, ContractError>;
+    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, L1RpcError>;
+
+    /// Whether the rollup manager accepts batched calls through `multicall`.
+    async fn supports_multicall(&self) -> bool;
+
+    /// Submit several settlements in a single `multicall` transaction. The
+    /// settlements are applied atomically, the whole transaction reverting if
+    /// any of them fails.
+    async fn verify_pessimistic_trusted_aggregator_batch(
+        &self,
+        settlements: Vec<PessimisticSettlement>,
+    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, L1RpcError>;
 }
 
 #[async_trait::async_trait]
@@ -61,16 +152,19 @@ where
         custom_chain_data: Bytes,
         nonce_info: Option<(u64, u128, Option<u128>)>, /* nonce, previous_max_fee_per_gas,
                                                         * optional previous_max_priority_fee_per_gas */
-    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, ContractError> {
+    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, L1RpcError> {
         // Build the transaction call
-        let mut tx_call = self.inner.verifyPessimisticTrustedAggregator(
+        let calldata = PessimisticSettlement {
             rollup_id,
             l_1_info_tree_leaf_count,
-            new_local_exit_root.into(),
-            new_pessimistic_root.into(),
+            new_local_exit_root,
+            new_pessimistic_root,
             proof,
             custom_chain_data,
-        );
+        }
+        .calldata(self.rollup_manager_abi)?;
+        let mut tx_call =
+            CallBuilder::new_raw(self.rpc.clone(), calldata).to(*self.inner.address());
 
         debug!(
             "Building the L1 settlement tx with calldata: {:?}",
@@ -93,6 +187,99 @@ where
             tx_call = tx_call.gas(30000);
         }
 
+        self.send_settlement_tx(tx_call, nonce_info)
+            .await
+            .map_err(L1RpcError::SettlementSubmissionFailed)
+    }
+
+    async fn supports_multicall(&self) -> bool {
+        // An empty batch is a no-op on contracts implementing `multicall`, and
+        // reverts on the others.
+        IMulticall::new(*self.inner.address(), self.rpc.clone())
+            .multicall(Vec::new())
+            .call()
+            .await
+            .inspect_err(|error| debug!(?error, "Rollup manager does not support multicall"))
+            .is_ok()
+    }
+
+    #[tracing::instrument(skip_all, fields(settlements = settlements.len()))]
+    async fn verify_pessimistic_trusted_aggregator_batch(
+        &self,
+        settlements: Vec<PessimisticSettlement>,
+    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, L1RpcError> {
+        let calls = settlements
+            .into_iter()
+            .map(|settlement| settlement.calldata(self.rollup_manager_abi))
+            .collect::<Result<_, _>>()?;
+
+        let rollup_manager = IMulticall::new(*self.inner.address(), self.rpc.clone());
+        let tx_call = rollup_manager.multicall(calls);
+
+        debug!(
+            "Building the batched L1 settlement tx with calldata: {:?}",
+            tx_call.calldata()
+        );
+
+        self.send_settlement_tx(tx_call, None)
+            .await
+            .map_err(L1RpcError::SettlementSubmissionFailed)
+    }
+}
+
+impl<RpcProvider> L1RpcClient<RpcProvider>
+where
+    RpcProvider: Provider + Clone + 'static,
+{
+    /// Settle with the given ABI of the rollup manager.
+    pub fn with_rollup_manager_abi(mut self, abi: RollupManagerAbi) -> Self {
+        self.rollup_manager_abi = abi;
+        self
+    }
+
+    /// Settlement ABI of the rollup manager, from its on-chain
+    /// `ROLLUP_MANAGER_VERSION`.
+    ///
+    /// The versions this binary doesn't know of are assumed to be newer ones,
+    /// which keep the latest ABI. A rollup manager reverting the call predates
+    /// the versioning, hence the aggchains. Any other failure is returned, the
+    /// ABI being then unknown.
+    pub async fn negotiate_rollup_manager_abi(&self) -> Result<RollupManagerAbi, L1RpcError> {
+        let version = match self.inner.ROLLUP_MANAGER_VERSION().call().await {
+            Ok(version) => version,
+            Err(error) if error.as_revert_data().is_some() => {
+                let abi = RollupManagerAbi::Pessimistic;
+                info!(
+                    ?error,
+                    ?abi,
+                    "Unversioned rollup manager, settling with the ABI predating the aggchains"
+                );
+                return Ok(abi);
+            }
+            Err(error) => return Err(L1RpcError::RollupManagerVersionFetchFailed(error)),
+        };
+
+        let abi = RollupManagerAbi::from_version(&version).unwrap_or_else(|| {
+            warn!(%version, "Unknown rollup manager version, assuming the latest settlement ABI");
+            RollupManagerAbi::default()
+        });
+        info!(%version, ?abi, "Negotiated the settlement ABI of the rollup manager");
+
+        Ok(abi)
+    }
+
+    /// Apply the configured gas limit and fees to the settlement call and send
+    /// it. Fees are bumped over the previous ones when a nonce is provided, in
+    /// order to replace a pending transaction.
+    async fn send_settlement_tx<P, D>(
+        &self,
+        mut tx_call: CallBuilder<P, D>,
+        nonce_info: Option<(u64, u128, Option<u128>)>,
+    ) -> Result<PendingTransactionBuilder<alloy::network::Ethereum>, ContractError>
+    where
+        P: Provider,
+        D: CallDecoder + Send + Sync,
+    {
         // Check if a gas multiplier factor is provided
         if self.gas_multiplier_factor != 100 {
             // Adjust the gas limit based on the configuration.
@@ -102,9 +289,8 @@ where
             let adjusted_gas =
                 (gas_estimate.saturating_mul(self.gas_multiplier_factor as u64)) / 100;
             debug!(
-                "Applying gas multiplier factor: {} for rollup_id: {}. Estimated gas: {}, \
-                 Adjusted gas: {}",
-                self.gas_multiplier_factor, rollup_id, gas_estimate, adjusted_gas
+                "Applying gas multiplier factor: {}. Estimated gas: {}, Adjusted gas: {}",
+                self.gas_multiplier_factor, gas_estimate, adjusted_gas
             );
             tx_call = tx_call.gas(adjusted_gas);
         }
@@ -155,7 +341,6 @@ where
                     debug!(
                         provided_nonce_info = ?nonce_info,
                         adjusted_max_fees = ?adjust,
-                        %rollup_id,
                         "Nonce provided, increasing  previous max_fee_per_gas and \
                          max_priority_fee_per_gas"
                     );
@@ -175,3 +360,120 @@ where
         tx_call.send().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settlement(custom_chain_data: Bytes) -> PessimisticSettlement {
+        PessimisticSettlement {
+            rollup_id: 1,
+            l_1_info_tree_leaf_count: 2,
+            new_local_exit_root: [3; 32],
+            new_pessimistic_root: [4; 32],
+            proof: Bytes::from_static(b"proof"),
+            custom_chain_data,
+        }
+    }
+
+    #[test]
+    fn calldata_follows_the_abi_of_the_rollup_manager() {
+        let aggchain = settlement(Bytes::new())
+            .calldata(RollupManagerAbi::Aggchain)
+            .unwrap();
+        let call = verifyPessimisticTrustedAggregatorCall::abi_decode(&aggchain).unwrap();
+        assert_eq!(call.rollupID, 1);
+        assert!(call.aggchainData.is_empty());
+
+        let pessimistic = settlement(Bytes::new())
+            .calldata(RollupManagerAbi::Pessimistic)
+            .unwrap();
+        assert_eq!(
+            pessimistic[..4],
+            IPessimisticRollupManager::verifyPessimisticTrustedAggregatorCall::SELECTOR
+        );
+        let call = IPessimisticRollupManager::verifyPessimisticTrustedAggregatorCall::abi_decode(
+            &pessimistic,
+        )
+        .unwrap();
+        assert_eq!(call.newPessimisticRoot.0, [4; 32]);
+        assert_eq!(call.proof, Bytes::from_static(b"proof"));
+    }
+
+    #[test]
+    fn aggchain_data_is_rejected_by_the_pessimistic_abi() {
+        let result = settlement(Bytes::from_static(b"aggchain data"))
+            .calldata(RollupManagerAbi::Pessimistic);
+
+        assert!(matches!(
+            result,
+            Err(L1RpcError::AggchainDataUnsupportedByRollupManager { rollup_id: 1 })
+        ));
+    }
+
+    #[test]
+    fn abi_is_negotiated_from_the_version() {
+        assert_eq!(
+            RollupManagerAbi::from_version("pessimistic"),
+            Some(RollupManagerAbi::Pessimistic)
+        );
+        assert_eq!(
+            RollupManagerAbi::from_version("al-v0.3.0"),
+            Some(RollupManagerAbi::Aggchain)
+        );
+        assert_eq!(RollupManagerAbi::from_version("v1.0.0"), None);
+    }
+
+    /// Client of a rollup manager behind the given mocked L1.
+    fn mocked_rollup_manager(
+        asserter: alloy::providers::mock::Asserter,
+    ) -> L1RpcClient<impl Provider + Clone + 'static> {
+        let rpc = alloy::providers::ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .on_mocked_client(asserter);
+
+        L1RpcClient {
+            rpc: std::sync::Arc::new(rpc.clone()),
+            inner: crate::contracts::PolygonRollupManager::new(Default::default(), rpc),
+            global_exit_root_manager_contract: Default::default(),
+            default_l1_info_tree_entry: (0, [1; 32]),
+            gas_multiplier_factor: 100,
+            gas_price_params: crate::GasPriceParams::default(),
+            l1_info_roots: Default::default(),
+            event_filter_block_range: 10000,
+            rollup_manager_abi: RollupManagerAbi::default(),
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn abi_is_negotiated_with_the_rollup_manager() {
+        use alloy::sol_types::SolValue as _;
+
+        let asserter = alloy::providers::mock::Asserter::new();
+        asserter.push_success(&Bytes::from(
+            ("pessimistic".to_string(),).abi_encode_params(),
+        ));
+
+        let abi = mocked_rollup_manager(asserter)
+            .negotiate_rollup_manager_abi()
+            .await
+            .unwrap();
+
+        assert_eq!(abi, RollupManagerAbi::Pessimistic);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn abi_is_unknown_if_the_version_cannot_be_fetched() {
+        let asserter = alloy::providers::mock::Asserter::new();
+        asserter.push_failure_msg("connection refused");
+
+        let result = mocked_rollup_manager(asserter)
+            .negotiate_rollup_manager_abi()
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(L1RpcError::RollupManagerVersionFetchFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+//! GraphQL endpoint over the agglayer storage, for the explorer frontends to
+//! query the certificates, epochs, networks and settlement transactions with
+//! only the fields they need.
+//!
+//! The endpoint is read-only: the certificates are still submitted through
+//! the JSON-RPC and gRPC APIs.
+
+use std::sync::Arc;
+
+use agglayer_config::RpcGraphqlConfig;
+use agglayer_rpc::AgglayerService;
+use agglayer_storage::stores::{
+    DebugReader, EpochStoreReader, NetworkInfoReader, PendingCertificateReader, StateReader,
+};
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+use async_graphql_axum::GraphQL;
+
+use crate::{
+    reader::Reader,
+    schema::{Limits, QueryRoot},
+};
+
+mod reader;
+mod schema;
+#[cfg(test)]
+mod tests;
+
+/// Path of the GraphQL endpoint.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+type AgglayerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+fn build_schema(config: &RpcGraphqlConfig, reader: Arc<dyn Reader>) -> AgglayerSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(reader)
+        .data(Limits {
+            max_page_size: config.max_page_size,
+        })
+        .limit_depth(config.max_depth)
+        .finish()
+}
+
+/// Build the router serving the GraphQL queries.
+pub fn router<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>(
+    config: &RpcGraphqlConfig,
+    rpc_service: Arc<AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>>,
+) -> axum::Router
+where
+    PendingStore: PendingCertificateReader + 'static,
+    StateStore: NetworkInfoReader + StateReader + 'static,
+    DebugStore: DebugReader + 'static,
+    L1Rpc: Send + Sync + 'static,
+    EpochsStore: EpochStoreReader + 'static,
+{
+    let schema = build_schema(config, rpc_service);
+
+    axum::Router::new().route(
+        GRAPHQL_PATH,
+        axum::routing::post_service(GraphQL::new(schema)),
+    )
+}
@@ -0,0 +1,107 @@
+use agglayer_rpc::{AgglayerService, CertificateRetrievalError};
+use agglayer_storage::{
+    columns::settlement_attempts_per_certificate::SettlementAttempt,
+    stores::{
+        DebugReader, EpochStoreReader, NetworkInfoReader, PendingCertificateReader, StateReader,
+    },
+};
+use agglayer_types::{
+    CertificateHeader, CertificateId, CertificateIndex, EpochConfiguration, EpochNumber, Height,
+    NetworkId, NetworkSummary,
+};
+
+/// Read access to the agglayer state needed to resolve the queries.
+///
+/// Hides the generics of the RPC service from the schema.
+pub(crate) trait Reader: Send + Sync {
+    fn certificate_header(
+        &self,
+        certificate_id: CertificateId,
+    ) -> async_graphql::Result<Option<CertificateHeader>>;
+
+    fn certificate_headers(
+        &self,
+        network_id: NetworkId,
+        from_height: Height,
+        limit: usize,
+    ) -> async_graphql::Result<Vec<CertificateHeader>>;
+
+    fn epoch_certificate_headers(
+        &self,
+        epoch_number: EpochNumber,
+        from_index: CertificateIndex,
+        limit: usize,
+    ) -> async_graphql::Result<Vec<CertificateHeader>>;
+
+    fn settlement_attempts(
+        &self,
+        certificate_id: CertificateId,
+    ) -> async_graphql::Result<Vec<SettlementAttempt>>;
+
+    fn networks(&self) -> async_graphql::Result<Vec<NetworkSummary>>;
+
+    fn epoch_configuration(&self) -> Option<EpochConfiguration>;
+}
+
+/// Error returned to the clients, the details being logged by the RPC
+/// service.
+fn internal_error<E>(_: E) -> async_graphql::Error {
+    async_graphql::Error::new("Internal error")
+}
+
+impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore> Reader
+    for AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
+where
+    PendingStore: PendingCertificateReader + 'static,
+    StateStore: NetworkInfoReader + StateReader + 'static,
+    DebugStore: DebugReader + 'static,
+    L1Rpc: Send + Sync + 'static,
+    EpochsStore: EpochStoreReader + 'static,
+{
+    fn certificate_header(
+        &self,
+        certificate_id: CertificateId,
+    ) -> async_graphql::Result<Option<CertificateHeader>> {
+        match self.fetch_certificate_header(certificate_id) {
+            Ok(header) => Ok(Some(header)),
+            Err(CertificateRetrievalError::NotFound { .. }) => Ok(None),
+            Err(error) => Err(internal_error(error)),
+        }
+    }
+
+    fn certificate_headers(
+        &self,
+        network_id: NetworkId,
+        from_height: Height,
+        limit: usize,
+    ) -> async_graphql::Result<Vec<CertificateHeader>> {
+        self.get_certificate_headers(network_id, from_height, limit)
+            .map_err(internal_error)
+    }
+
+    fn epoch_certificate_headers(
+        &self,
+        epoch_number: EpochNumber,
+        from_index: CertificateIndex,
+        limit: usize,
+    ) -> async_graphql::Result<Vec<CertificateHeader>> {
+        self.get_epoch_certificate_headers(epoch_number, from_index, limit)
+            .map_err(internal_error)
+    }
+
+    fn settlement_attempts(
+        &self,
+        certificate_id: CertificateId,
+    ) -> async_graphql::Result<Vec<SettlementAttempt>> {
+        self.get_settlement_attempts(certificate_id)
+            .map_err(internal_error)
+    }
+
+    fn networks(&self) -> async_graphql::Result<Vec<NetworkSummary>> {
+        self.get_networks().map_err(internal_error)
+    }
+
+    fn epoch_configuration(&self) -> Option<EpochConfiguration> {
+        self.get_epoch_configuration()
+    }
+}
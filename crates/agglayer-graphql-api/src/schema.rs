@@ -0,0 +1,324 @@
+use std::sync::Arc;
+
+use agglayer_storage::columns::settlement_attempts_per_certificate::SettlementAttempt;
+use agglayer_types::{
+    CertificateHeader, CertificateId, CertificateIndex, CertificateStatus, EpochNumber, Height,
+    NetworkId, NetworkSummary,
+};
+use async_graphql::{ComplexObject, Context, Enum, Object, SimpleObject};
+
+use crate::reader::Reader;
+
+/// Limits applied to the paginated fields.
+pub(crate) struct Limits {
+    pub(crate) max_page_size: usize,
+}
+
+/// Status of a certificate.
+#[derive(Clone, Copy, Debug, Enum, PartialEq, Eq)]
+pub(crate) enum Status {
+    Pending,
+    Proven,
+    Candidate,
+    InError,
+    Settled,
+}
+
+impl From<&CertificateStatus> for Status {
+    fn from(status: &CertificateStatus) -> Self {
+        match status {
+            CertificateStatus::Pending => Self::Pending,
+            CertificateStatus::Proven => Self::Proven,
+            CertificateStatus::Candidate => Self::Candidate,
+            CertificateStatus::InError { .. } => Self::InError,
+            CertificateStatus::Settled => Self::Settled,
+        }
+    }
+}
+
+/// Certificate known by the agglayer.
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(crate) struct Certificate {
+    id: String,
+    network_id: u32,
+    height: u64,
+    epoch_number: Option<u64>,
+    certificate_index: Option<u64>,
+    prev_local_exit_root: String,
+    new_local_exit_root: String,
+    metadata: String,
+    status: Status,
+    /// The error of the certificate, if in error.
+    error: Option<String>,
+    settlement_tx_hash: Option<String>,
+    #[graphql(skip)]
+    certificate_id: CertificateId,
+}
+
+impl From<CertificateHeader> for Certificate {
+    fn from(header: CertificateHeader) -> Self {
+        Self {
+            id: header.certificate_id.to_string(),
+            network_id: header.network_id.to_u32(),
+            height: header.height.as_u64(),
+            epoch_number: header.epoch_number.map(|epoch| epoch.as_u64()),
+            certificate_index: header.certificate_index.map(|index| index.as_u64()),
+            prev_local_exit_root: header.prev_local_exit_root.to_string(),
+            new_local_exit_root: header.new_local_exit_root.to_string(),
+            metadata: header.metadata.to_string(),
+            status: Status::from(&header.status),
+            error: match &header.status {
+                CertificateStatus::InError { error } => Some(error.to_string()),
+                _ => None,
+            },
+            settlement_tx_hash: header.settlement_tx_hash.map(|hash| hash.to_string()),
+            certificate_id: header.certificate_id,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Certificate {
+    /// The settlement transactions submitted for the certificate, in order.
+    async fn settlement_attempts(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<SettlementTransaction>> {
+        Ok(reader(ctx)?
+            .settlement_attempts(self.certificate_id)?
+            .into_iter()
+            .map(SettlementTransaction::from)
+            .collect())
+    }
+}
+
+/// Settlement transaction submitted to the L1.
+#[derive(SimpleObject)]
+pub(crate) struct SettlementTransaction {
+    tx_hash: String,
+    nonce: Option<u64>,
+    /// Fees of the transaction in wei, as decimal strings.
+    max_fee_per_gas: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
+}
+
+impl From<SettlementAttempt> for SettlementTransaction {
+    fn from(attempt: SettlementAttempt) -> Self {
+        Self {
+            tx_hash: attempt.settlement_tx_hash.to_string(),
+            nonce: attempt.nonce,
+            max_fee_per_gas: attempt.max_fee_per_gas.map(|fee| fee.to_string()),
+            max_priority_fee_per_gas: attempt.max_priority_fee_per_gas.map(|fee| fee.to_string()),
+        }
+    }
+}
+
+/// Page of certificates.
+#[derive(SimpleObject)]
+pub(crate) struct CertificatePage {
+    items: Vec<Certificate>,
+    /// Position to request the next page from, if any.
+    next: Option<u64>,
+}
+
+/// Network known by the agglayer.
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(crate) struct Network {
+    network_id: u32,
+    network_type: String,
+    settled_height: Option<u64>,
+    latest_pending_height: Option<u64>,
+    /// Time of the latest activity, in seconds since the UNIX epoch.
+    latest_activity: Option<u64>,
+}
+
+impl From<NetworkSummary> for Network {
+    fn from(summary: NetworkSummary) -> Self {
+        Self {
+            network_id: summary.network_id.to_u32(),
+            network_type: format!("{:?}", summary.network_type),
+            settled_height: summary.settled_height.map(|height| height.as_u64()),
+            latest_pending_height: summary.latest_pending_height.map(|height| height.as_u64()),
+            latest_activity: summary.latest_activity,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Network {
+    /// The certificates of the network, by height.
+    async fn certificates(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] from_height: u64,
+        first: Option<usize>,
+        status: Option<Status>,
+    ) -> async_graphql::Result<CertificatePage> {
+        network_certificates(ctx, self.network_id, from_height, first, status)
+    }
+}
+
+/// Epoch of the agglayer.
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(crate) struct Epoch {
+    number: u64,
+    /// First and last L1 blocks of the epoch, if the epochs are based on the
+    /// L1 blocks.
+    start_block: Option<u64>,
+    end_block: Option<u64>,
+}
+
+#[ComplexObject]
+impl Epoch {
+    /// The certificates settled in the epoch, by index.
+    async fn certificates(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] from_index: u64,
+        first: Option<usize>,
+        status: Option<Status>,
+    ) -> async_graphql::Result<CertificatePage> {
+        let reader = reader(ctx)?;
+        let epoch_number = EpochNumber::new(self.number);
+
+        load_page(ctx, from_index, first, status, |from, limit| {
+            reader.epoch_certificate_headers(epoch_number, CertificateIndex::new(from), limit)
+        })
+    }
+}
+
+pub(crate) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The certificate with the given identifier.
+    async fn certificate(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> async_graphql::Result<Option<Certificate>> {
+        let certificate_id = serde_json::from_value(serde_json::Value::String(id))
+            .map_err(|_| async_graphql::Error::new("Invalid certificate id"))?;
+
+        Ok(reader(ctx)?
+            .certificate_header(certificate_id)?
+            .map(Certificate::from))
+    }
+
+    /// The certificates of the network, by height.
+    async fn certificates(
+        &self,
+        ctx: &Context<'_>,
+        network_id: u32,
+        #[graphql(default)] from_height: u64,
+        first: Option<usize>,
+        status: Option<Status>,
+    ) -> async_graphql::Result<CertificatePage> {
+        network_certificates(ctx, network_id, from_height, first, status)
+    }
+
+    /// The networks known by the agglayer.
+    async fn networks(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Network>> {
+        Ok(reader(ctx)?
+            .networks()?
+            .into_iter()
+            .map(Network::from)
+            .collect())
+    }
+
+    /// The network with the given identifier, if known.
+    async fn network(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<Option<Network>> {
+        Ok(reader(ctx)?
+            .networks()?
+            .into_iter()
+            .find(|summary| summary.network_id.to_u32() == id)
+            .map(Network::from))
+    }
+
+    /// The epoch with the given number.
+    async fn epoch(&self, ctx: &Context<'_>, number: u64) -> async_graphql::Result<Epoch> {
+        let blocks = reader(ctx)?
+            .epoch_configuration()
+            .and_then(|configuration| {
+                let start = configuration
+                    .epoch_duration
+                    .checked_mul(number)?
+                    .checked_add(configuration.genesis_block)?;
+                let end = start.checked_add(configuration.epoch_duration.checked_sub(1)?)?;
+                Some((start, end))
+            });
+
+        Ok(Epoch {
+            number,
+            start_block: blocks.map(|(start, _)| start),
+            end_block: blocks.map(|(_, end)| end),
+        })
+    }
+}
+
+fn reader<'a>(ctx: &Context<'a>) -> async_graphql::Result<&'a Arc<dyn Reader>> {
+    ctx.data::<Arc<dyn Reader>>()
+}
+
+fn network_certificates(
+    ctx: &Context<'_>,
+    network_id: u32,
+    from_height: u64,
+    first: Option<usize>,
+    status: Option<Status>,
+) -> async_graphql::Result<CertificatePage> {
+    let reader = reader(ctx)?;
+    let network_id = NetworkId::new(network_id);
+
+    load_page(ctx, from_height, first, status, |from, limit| {
+        reader.certificate_headers(network_id, Height::new(from), limit)
+    })
+}
+
+/// Load a page of at most `first` certificates matching the status, starting
+/// at the given position.
+///
+/// The headers are loaded by batches of the page size from their contiguous
+/// positions, either the heights or the indexes in the epoch.
+fn load_page(
+    ctx: &Context<'_>,
+    from: u64,
+    first: Option<usize>,
+    status: Option<Status>,
+    mut load: impl FnMut(u64, usize) -> async_graphql::Result<Vec<CertificateHeader>>,
+) -> async_graphql::Result<CertificatePage> {
+    let max_page_size = ctx.data::<Limits>()?.max_page_size;
+    let page_size = first.unwrap_or(max_page_size);
+    if page_size == 0 || page_size > max_page_size {
+        return Err(async_graphql::Error::new(format!(
+            "The page size must be between 1 and {max_page_size}"
+        )));
+    }
+
+    let mut items = Vec::new();
+    let mut cursor = from;
+    loop {
+        let headers = load(cursor, page_size)?;
+        let exhausted = headers.len() < page_size;
+
+        for header in headers {
+            cursor += 1;
+            if status.map_or(true, |status| status == Status::from(&header.status)) {
+                items.push(Certificate::from(header));
+                if items.len() == page_size {
+                    return Ok(CertificatePage {
+                        items,
+                        next: Some(cursor),
+                    });
+                }
+            }
+        }
+
+        if exhausted {
+            return Ok(CertificatePage { items, next: None });
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use agglayer_config::{Config, RpcGraphqlConfig};
+use agglayer_rpc::AgglayerService;
+use agglayer_storage::{
+    columns::settlement_attempts_per_certificate::SettlementAttempt,
+    storage::backup::BackupClient,
+    stores::{
+        debug::DebugStore, epochs::EpochsStore, pending::PendingStore, state::StateStore,
+        StateWriter as _,
+    },
+    tests::TempDBDir,
+};
+use agglayer_types::{
+    Certificate, CertificateId, CertificateStatus, CertificateStatusError, Digest, EpochNumber,
+    Height, NetworkId, SettlementTxHash,
+};
+use serde_json::json;
+
+use crate::{build_schema, AgglayerSchema};
+
+struct L1Rpc {}
+
+struct TestContext {
+    schema: AgglayerSchema,
+    state_store: Arc<StateStore>,
+    _tmp: TempDBDir,
+}
+
+impl TestContext {
+    fn new(graphql_config: &RpcGraphqlConfig) -> Self {
+        let tmp = TempDBDir::new();
+        let config = Arc::new(Config::new(&tmp.path));
+
+        let pending_store =
+            Arc::new(PendingStore::new_with_path(&config.storage.pending_db_path).unwrap());
+        let state_store = Arc::new(
+            StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop()).unwrap(),
+        );
+        let debug_store =
+            Arc::new(DebugStore::new_with_path(&config.storage.debug_db_path).unwrap());
+        let epochs_store = Arc::new(
+            EpochsStore::new(
+                config.clone(),
+                EpochNumber::ZERO,
+                pending_store.clone(),
+                state_store.clone(),
+                BackupClient::noop(),
+            )
+            .unwrap(),
+        );
+
+        let (sender, _receiver) = tokio::sync::mpsc::channel(10);
+        let service = Arc::new(AgglayerService::new(
+            sender,
+            pending_store,
+            state_store.clone(),
+            debug_store,
+            epochs_store,
+            config,
+            Arc::new(L1Rpc {}),
+        ));
+
+        Self {
+            schema: build_schema(graphql_config, service),
+            state_store,
+            _tmp: tmp,
+        }
+    }
+
+    /// Insert settled certificates for the network 1 at the heights 0 to 2,
+    /// the one at height 1 being in error.
+    fn insert_certificates(&self) -> Vec<CertificateId> {
+        let certificate_ids = (0..3)
+            .map(|height| {
+                let certificate = Certificate::new_for_test(NetworkId::new(1), Height::new(height));
+                self.state_store
+                    .insert_certificate_header(&certificate, CertificateStatus::Settled)
+                    .unwrap();
+                certificate.hash()
+            })
+            .collect::<Vec<_>>();
+
+        self.state_store
+            .update_certificate_header_status(
+                &certificate_ids[1],
+                &CertificateStatus::error(CertificateStatusError::TrustedSequencerNotFound(
+                    NetworkId::new(1),
+                )),
+            )
+            .unwrap();
+
+        certificate_ids
+    }
+
+    async fn query(&self, query: &str) -> Result<serde_json::Value, Vec<String>> {
+        let response = self.schema.execute(query).await;
+        if !response.errors.is_empty() {
+            return Err(response
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect());
+        }
+
+        Ok(response.data.into_json().unwrap())
+    }
+}
+
+#[tokio::test]
+async fn certificates_are_paginated_and_filtered() {
+    let context = TestContext::new(&RpcGraphqlConfig::default());
+    context.insert_certificates();
+
+    let data = context
+        .query("{ certificates(networkId: 1, first: 2) { items { height status } next } }")
+        .await
+        .unwrap();
+    assert_eq!(
+        data["certificates"],
+        json!({
+            "items": [
+                { "height": 0, "status": "SETTLED" },
+                { "height": 1, "status": "IN_ERROR" },
+            ],
+            "next": 2,
+        })
+    );
+
+    let data = context
+        .query("{ certificates(networkId: 1, fromHeight: 2, first: 2) { items { height } next } }")
+        .await
+        .unwrap();
+    assert_eq!(
+        data["certificates"],
+        json!({ "items": [{ "height": 2 }], "next": null })
+    );
+
+    let data = context
+        .query(
+            "{ certificates(networkId: 1, first: 2, status: SETTLED) { items { height } next } }",
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        data["certificates"],
+        json!({ "items": [{ "height": 0 }, { "height": 2 }], "next": 3 })
+    );
+}
+
+#[tokio::test]
+async fn certificate_exposes_its_settlement_transactions() {
+    let context = TestContext::new(&RpcGraphqlConfig::default());
+    let certificate_ids = context.insert_certificates();
+
+    let settlement_tx_hash = SettlementTxHash::new(Digest([2; 32]));
+    context
+        .state_store
+        .record_settlement_attempt(
+            &certificate_ids[0],
+            SettlementAttempt {
+                settlement_tx_hash,
+                nonce: Some(7),
+                max_fee_per_gas: Some(1_000),
+                max_priority_fee_per_gas: None,
+            },
+        )
+        .unwrap();
+
+    let data = context
+        .query(&format!(
+            "{{ certificate(id: \"{}\") {{ id height settlementAttempts {{ txHash nonce \
+             maxFeePerGas maxPriorityFeePerGas }} }} }}",
+            certificate_ids[0]
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        data["certificate"],
+        json!({
+            "id": certificate_ids[0].to_string(),
+            "height": 0,
+            "settlementAttempts": [{
+                "txHash": settlement_tx_hash.to_string(),
+                "nonce": 7,
+                "maxFeePerGas": "1000",
+                "maxPriorityFeePerGas": null,
+            }],
+        })
+    );
+
+    let unknown = CertificateId::new(Digest([9; 32]));
+    let data = context
+        .query(&format!("{{ certificate(id: \"{unknown}\") {{ id }} }}"))
+        .await
+        .unwrap();
+    assert_eq!(data["certificate"], json!(null));
+}
+
+#[tokio::test]
+async fn page_size_and_depth_are_limited() {
+    let context = TestContext::new(&RpcGraphqlConfig {
+        enabled: true,
+        max_page_size: 2,
+        max_depth: 3,
+    });
+    let certificate_ids = context.insert_certificates();
+
+    let errors = context
+        .query("{ certificates(networkId: 1, first: 3) { items { height } } }")
+        .await
+        .unwrap_err();
+    assert_eq!(errors, ["The page size must be between 1 and 2"]);
+
+    // Without a page size, the pages are as large as allowed.
+    let data = context
+        .query("{ certificates(networkId: 1) { next } }")
+        .await
+        .unwrap();
+    assert_eq!(data["certificates"]["next"], json!(2));
+
+    let data = context
+        .query(&format!(
+            "{{ certificate(id: \"{}\") {{ settlementAttempts {{ txHash }} }} }}",
+            certificate_ids[0]
+        ))
+        .await
+        .unwrap();
+    assert_eq!(data["certificate"]["settlementAttempts"], json!([]));
+
+    let errors = context
+        .query("{ networks { certificates { items { height } } } }")
+        .await
+        .unwrap_err();
+    assert_eq!(errors, ["Query is nested too deep."]);
+}
@@ -29,6 +29,62 @@ impl From<CertificateSubmissionErrorWrapper> for tonic::Status {
                 tonic::Status::internal("Orchestrator not responsive")
             }
 
+            error @ (agglayer_rpc::CertificateSubmissionError::InvalidCallbackUrl { .. }
+            | agglayer_rpc::CertificateSubmissionError::ConflictingCallbackUrl {
+                ..
+            }) => tonic::Status::invalid_argument(error.to_string()),
+
+            error @ (agglayer_rpc::CertificateSubmissionError::InvalidGlobalIndex { .. }
+            | agglayer_rpc::CertificateSubmissionError::TooManyBridgeExits { .. }
+            | agglayer_rpc::CertificateSubmissionError::TooManyImportedBridgeExits {
+                ..
+            }
+            | agglayer_rpc::CertificateSubmissionError::CertificateTooLarge { .. }
+            | agglayer_rpc::CertificateSubmissionError::InconsistentImportedBridgeExits(
+                _,
+            )
+            | agglayer_rpc::CertificateSubmissionError::InvalidImportedBridgeExit {
+                ..
+            }) => tonic::Status::invalid_argument(error.to_string()),
+
+            error @ agglayer_rpc::CertificateSubmissionError::IntakeOverloaded => {
+                warn!("returning intake overloaded to RPC");
+                tonic::Status::resource_exhausted(error.to_string())
+            }
+
+            agglayer_rpc::CertificateSubmissionError::Maintenance => {
+                tonic::Status::with_error_details(
+                    tonic::Code::Unavailable,
+                    "The agglayer is in maintenance, retry later",
+                    ErrorDetails::with_error_info(
+                        SubmitCertificateErrorKind::Maintenance.as_str_name(),
+                        error.context,
+                        HashMap::new(),
+                    ),
+                )
+            }
+
+            error @ agglayer_rpc::CertificateSubmissionError::ReadOnly => {
+                tonic::Status::failed_precondition(error.to_string())
+            }
+
+            error @ agglayer_rpc::CertificateSubmissionError::BlockedBridgeExit { .. } => {
+                tonic::Status::permission_denied(error.to_string())
+            }
+
+            error @ agglayer_rpc::CertificateSubmissionError::ScreeningFailed(_) => {
+                warn!("returning screening failure to RPC");
+                tonic::Status::unavailable(error.to_string())
+            }
+
+            agglayer_rpc::CertificateSubmissionError::IntakeWorkerFailed => {
+                tonic::Status::internal("Certificate validation failed")
+            }
+
+            error @ agglayer_rpc::CertificateSubmissionError::UnknownNetwork { .. } => {
+                tonic::Status::not_found(error.to_string())
+            }
+
             agglayer_rpc::CertificateSubmissionError::SignatureError(
                 signature_verification_error,
             ) => tonic::Status::with_error_details(
@@ -41,6 +97,24 @@ impl From<CertificateSubmissionErrorWrapper> for tonic::Status {
                 ),
             ),
 
+            agglayer_rpc::CertificateSubmissionError::UnexpectedHeight {
+                network_id,
+                height,
+                expected_height,
+            } => tonic::Status::with_error_details(
+                tonic::Code::FailedPrecondition,
+                format!("Unexpected certificate height {height}, expected {expected_height}"),
+                ErrorDetails::with_error_info(
+                    SubmitCertificateErrorKind::UnexpectedHeight.as_str_name(),
+                    error.context,
+                    [
+                        ("network_id".into(), network_id.to_string()),
+                        ("height".into(), height.to_string()),
+                        ("expected_height".into(), expected_height.to_string()),
+                    ],
+                ),
+            ),
+
             agglayer_rpc::CertificateSubmissionError::UnableToReplacePendingCertificate {
                 reason,
                 height,
@@ -1,14 +1,18 @@
-use std::{str::FromStr, sync::Arc};
+use std::{net::SocketAddr, str::FromStr, sync::Arc};
 
 use agglayer_contracts::{AggchainContract, L1TransactionFetcher, RollupContract};
 use agglayer_grpc_server::node::v1::certificate_submission_service_server::CertificateSubmissionService;
-use agglayer_grpc_types::node::v1::{
-    SubmitCertificateErrorKind, SubmitCertificateRequest, SubmitCertificateResponse,
+use agglayer_grpc_types::node::{
+    types::v1,
+    v1::{SubmitCertificateErrorKind, SubmitCertificateRequest, SubmitCertificateResponse},
 };
 use agglayer_rpc::AgglayerService;
-use agglayer_storage::stores::{
-    DebugReader, DebugWriter, EpochStoreReader, PendingCertificateReader, PendingCertificateWriter,
-    StateReader, StateWriter,
+use agglayer_storage::{
+    columns::audit_log_per_certificate::{SubmissionApi, Submitter},
+    stores::{
+        DebugReader, DebugWriter, EpochStoreReader, PendingCertificateReader,
+        PendingCertificateWriter, StateReader, StateWriter,
+    },
 };
 use agglayer_types::Signature;
 use error::CertificateSubmissionErrorWrapper;
@@ -48,6 +52,11 @@ where
         let extra_signature: Option<Signature> =
             get_extra_signature(request.metadata()).map_err(|e| *e)?;
 
+        let submitter = Submitter {
+            api: SubmissionApi::Grpc,
+            address: peer_address(&request),
+        };
+
         let certificate: agglayer_types::Certificate = match request.into_inner().certificate {
             Some(certificate) => certificate.try_into().map_err(
                 |error: agglayer_grpc_types::compat::v1::Error| {
@@ -65,20 +74,35 @@ where
             None => return Err(tonic::Status::invalid_argument("Missing certificate")),
         };
 
-        let certificate_id = self
+        // NOTE: Status callbacks are not supported on the grpc api
+        let callback_url = None;
+
+        let submission = self
             .service
-            .send_certificate(certificate, extra_signature)
+            .send_certificate(certificate, extra_signature, callback_url, submitter)
             .await
             .map_err(|error| {
                 CertificateSubmissionErrorWrapper::new(error, SUBMIT_CERTIFICATE_METHOD_PATH)
             })?;
 
         Ok(tonic::Response::new(SubmitCertificateResponse {
-            certificate_id: Some(certificate_id.into()),
+            certificate_id: Some(submission.certificate_id.into()),
+            status: v1::CertificateStatus::from(&submission.status).into(),
+            already_known: submission.already_known,
         }))
     }
 }
 
+/// Address of the peer which sent the request, as set by the server when
+/// serving with the connection info.
+fn peer_address<T>(request: &tonic::Request<T>) -> Option<SocketAddr> {
+    request
+        .extensions()
+        .get::<axum::extract::ConnectInfo<SocketAddr>>()
+        .map(|info| info.0)
+        .or_else(|| request.remote_addr())
+}
+
 pub(crate) fn get_extra_signature(
     metadata: &tonic::metadata::MetadataMap,
 ) -> Result<Option<Signature>, Box<Status>> {
@@ -12,16 +12,24 @@ impl From<CertificateStatusError> for v1::CertificateStatusError {
     }
 }
 
+impl From<&CertificateStatus> for v1::CertificateStatus {
+    fn from(value: &CertificateStatus) -> Self {
+        match value {
+            CertificateStatus::Pending => v1::CertificateStatus::Pending,
+            CertificateStatus::Proven => v1::CertificateStatus::Proven,
+            CertificateStatus::Candidate => v1::CertificateStatus::Candidate,
+            CertificateStatus::InError { .. } => v1::CertificateStatus::InError,
+            CertificateStatus::Settled => v1::CertificateStatus::Settled,
+        }
+    }
+}
+
 impl From<CertificateHeader> for v1::CertificateHeader {
     fn from(value: CertificateHeader) -> Self {
-        let (status, error) = match value.status {
-            CertificateStatus::Pending => (v1::CertificateStatus::Pending, None),
-            CertificateStatus::Proven => (v1::CertificateStatus::Proven, None),
-            CertificateStatus::Candidate => (v1::CertificateStatus::Candidate, None),
-            CertificateStatus::InError { error } => {
-                (v1::CertificateStatus::InError, Some((*error).into()))
-            }
-            CertificateStatus::Settled => (v1::CertificateStatus::Settled, None),
+        let status = v1::CertificateStatus::from(&value.status);
+        let error = match value.status {
+            CertificateStatus::InError { error } => Some((*error).into()),
+            _ => None,
         };
         v1::CertificateHeader {
             network_id: value.network_id.into(),
@@ -16,6 +16,8 @@ impl Display for SubmitCertificateErrorKind {
             SubmitCertificateErrorKind::UnableToReplacePendingCertificate => {
                 write!(f, "Unable to replace pending certificate")
             }
+            SubmitCertificateErrorKind::UnexpectedHeight => write!(f, "Unexpected height"),
+            SubmitCertificateErrorKind::Maintenance => write!(f, "Maintenance"),
         }
     }
 }
@@ -13,6 +13,13 @@ pub struct SubmitCertificateResponse {
     /// The certificate id of the submitted certificate.
     #[prost(message, optional, tag="1")]
     pub certificate_id: ::core::option::Option<super::types::v1::CertificateId>,
+    /// The current status of the certificate.
+    #[prost(enumeration="super::types::v1::CertificateStatus", tag="2")]
+    pub status: i32,
+    /// Whether the same certificate was already submitted, in which case it is
+    /// left untouched.
+    #[prost(bool, tag="3")]
+    pub already_known: bool,
 }
 /// The kind of error that occurred and that are reported by the service.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -28,6 +35,10 @@ pub enum SubmitCertificateErrorKind {
     SignatureVerification = 3,
     /// Unable to replace pending certificate.
     UnableToReplacePendingCertificate = 4,
+    /// The certificate height doesn't follow the known certificates.
+    UnexpectedHeight = 5,
+    /// The agglayer is in maintenance and doesn't accept new certificates.
+    Maintenance = 6,
 }
 impl SubmitCertificateErrorKind {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -41,6 +52,8 @@ impl SubmitCertificateErrorKind {
             Self::InvalidData => "SUBMIT_CERTIFICATE_ERROR_KIND_INVALID_DATA",
             Self::SignatureVerification => "SUBMIT_CERTIFICATE_ERROR_KIND_SIGNATURE_VERIFICATION",
             Self::UnableToReplacePendingCertificate => "SUBMIT_CERTIFICATE_ERROR_KIND_UNABLE_TO_REPLACE_PENDING_CERTIFICATE",
+            Self::UnexpectedHeight => "SUBMIT_CERTIFICATE_ERROR_KIND_UNEXPECTED_HEIGHT",
+            Self::Maintenance => "SUBMIT_CERTIFICATE_ERROR_KIND_MAINTENANCE",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -51,6 +64,8 @@ impl SubmitCertificateErrorKind {
             "SUBMIT_CERTIFICATE_ERROR_KIND_INVALID_DATA" => Some(Self::InvalidData),
             "SUBMIT_CERTIFICATE_ERROR_KIND_SIGNATURE_VERIFICATION" => Some(Self::SignatureVerification),
             "SUBMIT_CERTIFICATE_ERROR_KIND_UNABLE_TO_REPLACE_PENDING_CERTIFICATE" => Some(Self::UnableToReplacePendingCertificate),
+            "SUBMIT_CERTIFICATE_ERROR_KIND_UNEXPECTED_HEIGHT" => Some(Self::UnexpectedHeight),
+            "SUBMIT_CERTIFICATE_ERROR_KIND_MAINTENANCE" => Some(Self::Maintenance),
             _ => None,
         }
     }
@@ -1128,6 +1128,8 @@ impl serde::Serialize for SubmitCertificateErrorKind {
             Self::InvalidData => "SUBMIT_CERTIFICATE_ERROR_KIND_INVALID_DATA",
             Self::SignatureVerification => "SUBMIT_CERTIFICATE_ERROR_KIND_SIGNATURE_VERIFICATION",
             Self::UnableToReplacePendingCertificate => "SUBMIT_CERTIFICATE_ERROR_KIND_UNABLE_TO_REPLACE_PENDING_CERTIFICATE",
+            Self::UnexpectedHeight => "SUBMIT_CERTIFICATE_ERROR_KIND_UNEXPECTED_HEIGHT",
+            Self::Maintenance => "SUBMIT_CERTIFICATE_ERROR_KIND_MAINTENANCE",
         };
         serializer.serialize_str(variant)
     }
@@ -1144,6 +1146,8 @@ impl<'de> serde::Deserialize<'de> for SubmitCertificateErrorKind {
             "SUBMIT_CERTIFICATE_ERROR_KIND_INVALID_DATA",
             "SUBMIT_CERTIFICATE_ERROR_KIND_SIGNATURE_VERIFICATION",
             "SUBMIT_CERTIFICATE_ERROR_KIND_UNABLE_TO_REPLACE_PENDING_CERTIFICATE",
+            "SUBMIT_CERTIFICATE_ERROR_KIND_UNEXPECTED_HEIGHT",
+            "SUBMIT_CERTIFICATE_ERROR_KIND_MAINTENANCE",
         ];
 
         struct GeneratedVisitor;
@@ -1189,6 +1193,8 @@ impl<'de> serde::Deserialize<'de> for SubmitCertificateErrorKind {
                     "SUBMIT_CERTIFICATE_ERROR_KIND_INVALID_DATA" => Ok(SubmitCertificateErrorKind::InvalidData),
                     "SUBMIT_CERTIFICATE_ERROR_KIND_SIGNATURE_VERIFICATION" => Ok(SubmitCertificateErrorKind::SignatureVerification),
                     "SUBMIT_CERTIFICATE_ERROR_KIND_UNABLE_TO_REPLACE_PENDING_CERTIFICATE" => Ok(SubmitCertificateErrorKind::UnableToReplacePendingCertificate),
+                    "SUBMIT_CERTIFICATE_ERROR_KIND_UNEXPECTED_HEIGHT" => Ok(SubmitCertificateErrorKind::UnexpectedHeight),
+                    "SUBMIT_CERTIFICATE_ERROR_KIND_MAINTENANCE" => Ok(SubmitCertificateErrorKind::Maintenance),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -1298,10 +1304,24 @@ impl serde::Serialize for SubmitCertificateResponse {
         if self.certificate_id.is_some() {
             len += 1;
         }
+        if self.status != 0 {
+            len += 1;
+        }
+        if self.already_known {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("agglayer.node.v1.SubmitCertificateResponse", len)?;
         if let Some(v) = self.certificate_id.as_ref() {
             struct_ser.serialize_field("certificateId", v)?;
         }
+        if self.status != 0 {
+            let v = super::types::v1::CertificateStatus::try_from(self.status)
+                .map_err(|_| serde::ser::Error::custom(format!("Invalid variant {}", self.status)))?;
+            struct_ser.serialize_field("status", &v)?;
+        }
+        if self.already_known {
+            struct_ser.serialize_field("alreadyKnown", &self.already_known)?;
+        }
         struct_ser.end()
     }
 }
@@ -1314,11 +1334,16 @@ impl<'de> serde::Deserialize<'de> for SubmitCertificateResponse {
         const FIELDS: &[&str] = &[
             "certificate_id",
             "certificateId",
+            "status",
+            "already_known",
+            "alreadyKnown",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             CertificateId,
+            Status,
+            AlreadyKnown,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1341,6 +1366,8 @@ impl<'de> serde::Deserialize<'de> for SubmitCertificateResponse {
                     {
                         match value {
                             "certificateId" | "certificate_id" => Ok(GeneratedField::CertificateId),
+                            "status" => Ok(GeneratedField::Status),
+                            "alreadyKnown" | "already_known" => Ok(GeneratedField::AlreadyKnown),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1361,6 +1388,8 @@ impl<'de> serde::Deserialize<'de> for SubmitCertificateResponse {
                     V: serde::de::MapAccess<'de>,
             {
                 let mut certificate_id__ = None;
+                let mut status__ = None;
+                let mut already_known__ = None;
                 while let Some(k) = map_.next_key()? {
                     match k {
                         GeneratedField::CertificateId => {
@@ -1369,10 +1398,24 @@ impl<'de> serde::Deserialize<'de> for SubmitCertificateResponse {
                             }
                             certificate_id__ = map_.next_value()?;
                         }
+                        GeneratedField::Status => {
+                            if status__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("status"));
+                            }
+                            status__ = Some(map_.next_value::<super::types::v1::CertificateStatus>()? as i32);
+                        }
+                        GeneratedField::AlreadyKnown => {
+                            if already_known__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("alreadyKnown"));
+                            }
+                            already_known__ = Some(map_.next_value()?);
+                        }
                     }
                 }
                 Ok(SubmitCertificateResponse {
                     certificate_id: certificate_id__,
+                    status: status__.unwrap_or_default(),
+                    already_known: already_known__.unwrap_or_default(),
                 })
             }
         }
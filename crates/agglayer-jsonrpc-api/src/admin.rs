@@ -1,21 +1,40 @@
-use std::sync::Arc;
+use std::{
+    path::{Component, Path},
+    sync::Arc,
+};
 
+use agglayer_certificate_orchestrator::{NativeExecutor, OrchestratorSnapshot, OrchestratorState};
+use agglayer_clock::ManualClockHandle;
 use agglayer_config::Config;
-use agglayer_storage::stores::{
-    DebugReader, DebugWriter, PendingCertificateReader, PendingCertificateWriter, StateReader,
-    StateWriter,
+use agglayer_rpc::{ApiKeyUsageReport, Maintenance, MaintenanceState};
+use agglayer_storage::{
+    columns::certification_failure_per_certificate::CertificationFailure,
+    storage::{
+        backup::BackupClient,
+        compactor::{CompactionTarget, Compactor},
+        scrubber::{LatestScrubReport, ScrubReport},
+        state_db_cf_definitions, DB,
+    },
+    stores::{
+        DebugReader, DebugWriter, PendingCertificateReader, PendingCertificateWriter, StateReader,
+        StateWriter,
+    },
 };
 use agglayer_types::{
     Certificate, CertificateHeader, CertificateId, CertificateStatus, CertificateStatusError,
-    Height, NetworkId, SettlementTxHash,
+    EpochNumber, Height, LocalNetworkStateData, NativeExecutionReport, NetworkId, SettlementTxHash,
 };
 use jsonrpsee::{core::async_trait, proc_macros::rpc, server::ServerBuilder};
 use tokio::sync::mpsc;
-use tower_http::{compression::CompressionLayer, cors::CorsLayer};
+use tower_http::cors::CorsLayer;
 use tracing::{error, info, instrument, warn};
 
 use super::error::RpcResult;
-use crate::{error::Error, rpc_middleware, JsonRpcService};
+use crate::{
+    compression::{compression_layer, decompression_layer},
+    error::Error,
+    rpc_middleware, JsonRpcService,
+};
 
 #[rpc(server, namespace = "admin")]
 pub(crate) trait AdminAgglayer {
@@ -25,6 +44,14 @@ pub(crate) trait AdminAgglayer {
         certificate_id: CertificateId,
     ) -> RpcResult<(Certificate, Option<CertificateHeader>)>;
 
+    /// Inputs of the last failed certification of the certificate, recorded
+    /// in debug mode.
+    #[method(name = "getCertificationFailure")]
+    async fn get_certification_failure(
+        &self,
+        certificate_id: CertificateId,
+    ) -> RpcResult<(Certificate, CertificationFailure)>;
+
     #[method(name = "forcePushPendingCertificate")]
     async fn force_push_pending_certificate(
         &self,
@@ -57,6 +84,60 @@ pub(crate) trait AdminAgglayer {
 
     #[method(name = "removePendingProof")]
     async fn remove_pending_proof(&self, certificate_id: CertificateId) -> RpcResult<()>;
+
+    /// Settle again the certificates whose settlement reverted during the
+    /// given epoch, instead of waiting for the chains to resubmit them. They
+    /// are packed into the epoch current at their new settlement, with the
+    /// next indexes. Returns the re-packed certificates.
+    #[method(name = "repackEpoch")]
+    async fn repack_epoch(&self, epoch_number: EpochNumber) -> RpcResult<Vec<CertificateId>>;
+
+    /// Dump the in-memory state of the orchestrator: network tasks, in-flight
+    /// certificates, current epoch and clock.
+    #[method(name = "getOrchestratorState")]
+    async fn get_orchestrator_state(&self) -> RpcResult<OrchestratorSnapshot>;
+
+    /// Stop accepting new certificates, then flush the storage once the
+    /// in-flight ones are processed, exiting afterwards if requested.
+    #[method(name = "enterMaintenance")]
+    async fn enter_maintenance(&self, exit_when_ready: bool) -> RpcResult<MaintenanceState>;
+
+    /// Accept new certificates again.
+    #[method(name = "leaveMaintenance")]
+    async fn leave_maintenance(&self) -> RpcResult<MaintenanceState>;
+
+    #[method(name = "getMaintenanceState")]
+    async fn get_maintenance_state(&self) -> RpcResult<MaintenanceState>;
+
+    /// Usage and quotas of all the API keys over the current quota period.
+    #[method(name = "getApiKeyUsage")]
+    async fn get_api_key_usage(&self) -> RpcResult<ApiKeyUsageReport>;
+
+    /// Corrupted entries found by the latest scrub of the storage.
+    #[method(name = "getScrubReport")]
+    async fn get_scrub_report(&self) -> RpcResult<ScrubReport>;
+
+    /// Compact the given column families of the storage, or all of them when
+    /// none is given, in the background. The compaction progress is reported
+    /// by the storage metrics.
+    #[method(name = "compactStorage")]
+    async fn compact_storage(&self, columns: Vec<String>) -> RpcResult<Vec<CompactionTarget>>;
+
+    /// Advance the manual epoch clock by the given number of blocks, returning
+    /// the ended epochs.
+    #[method(name = "advanceClock")]
+    async fn advance_clock(&self, blocks: u64) -> RpcResult<Vec<EpochNumber>>;
+
+    /// Apply a candidate certificate natively, without executing the
+    /// pessimistic proof program, on top of the settled state of its network
+    /// or of the named snapshot of the state storage, and report the
+    /// resulting roots and the balances it moved. Nothing is stored.
+    #[method(name = "debugNativeExecution")]
+    async fn debug_native_execution(
+        &self,
+        certificate: Certificate,
+        state_snapshot: Option<String>,
+    ) -> RpcResult<NativeExecutionReport>;
 }
 
 /// The Admin RPC agglayer service implementation.
@@ -66,6 +147,12 @@ pub struct AdminAgglayerImpl<PendingStore, StateStore, DebugStore> {
     state: Arc<StateStore>,
     debug_store: Arc<DebugStore>,
     config: Arc<Config>,
+    orchestrator_state: Arc<OrchestratorState>,
+    maintenance: Arc<Maintenance>,
+    scrub_report: LatestScrubReport,
+    compactor: Compactor,
+    manual_clock: Option<ManualClockHandle>,
+    native_executor: Option<Arc<dyn NativeExecutor>>,
 }
 
 impl<PendingStore, StateStore, DebugStore> AdminAgglayerImpl<PendingStore, StateStore, DebugStore> {
@@ -76,6 +163,7 @@ impl<PendingStore, StateStore, DebugStore> AdminAgglayerImpl<PendingStore, State
         state: Arc<StateStore>,
         debug_store: Arc<DebugStore>,
         config: Arc<Config>,
+        orchestrator_state: Arc<OrchestratorState>,
     ) -> Self {
         Self {
             certificate_sender,
@@ -83,8 +171,78 @@ impl<PendingStore, StateStore, DebugStore> AdminAgglayerImpl<PendingStore, State
             state,
             debug_store,
             config,
+            orchestrator_state,
+            maintenance: Arc::default(),
+            scrub_report: LatestScrubReport::default(),
+            compactor: Compactor::default(),
+            manual_clock: None,
+            native_executor: None,
         }
     }
+
+    /// Trigger the given maintenance mode, shared with the services accepting
+    /// the certificates.
+    pub fn with_maintenance(mut self, maintenance: Arc<Maintenance>) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    /// Expose the report of the latest scrub of the storage.
+    pub fn with_scrub_report(mut self, scrub_report: LatestScrubReport) -> Self {
+        self.scrub_report = scrub_report;
+        self
+    }
+
+    /// Compact the storage with the given compactor, shared with the
+    /// scheduled compactions.
+    pub fn with_compactor(mut self, compactor: Compactor) -> Self {
+        self.compactor = compactor;
+        self
+    }
+
+    /// Drive the epochs with the given manual clock, if any.
+    pub fn with_manual_clock(mut self, manual_clock: Option<ManualClockHandle>) -> Self {
+        self.manual_clock = manual_clock;
+        self
+    }
+
+    /// Execute the candidate certificates natively with the given executor.
+    pub fn with_native_executor(mut self, executor: Arc<dyn NativeExecutor>) -> Self {
+        self.native_executor = Some(executor);
+        self
+    }
+}
+
+/// Settled state of the network in the named snapshot of the state storage,
+/// opened read-only from the snapshots directory.
+pub(crate) fn read_snapshot_state(
+    dir: &Path,
+    name: &str,
+    network_id: NetworkId,
+) -> Result<LocalNetworkStateData, Error> {
+    // The snapshot is named by a single directory, which can't escape the
+    // snapshots directory.
+    let mut components = Path::new(name).components();
+    let (Some(Component::Normal(_)), None) = (components.next(), components.next()) else {
+        return Err(Error::InvalidArgument(format!(
+            "Invalid state snapshot name {name:?}"
+        )));
+    };
+
+    let path = dir.join(name);
+    if !path.is_dir() {
+        return Err(Error::ResourceNotFound(format!("StateSnapshot({name})")));
+    }
+
+    let snapshot = DB::open_cf_readonly(&path, state_db_cf_definitions())
+        .map_err(|error| Error::internal(format!("Failed to open the state snapshot: {error}")))?;
+
+    Ok(
+        agglayer_storage::stores::state::StateStore::new(Arc::new(snapshot), BackupClient::noop())
+            .read_local_network_state(network_id)
+            .map_err(|error| Error::internal(error.to_string()))?
+            .unwrap_or_default(),
+    )
 }
 
 impl<PendingStore, StateStore, DebugStore> AdminAgglayerImpl<PendingStore, StateStore, DebugStore>
@@ -129,14 +287,17 @@ where
             .allow_origin(tower_http::cors::Any)
             .allow_headers([hyper::header::CONTENT_TYPE]);
 
-        // Create a middleware stack with the CORS middleware and a proxy layer for
-        // health checks.
+        // Create a middleware stack with the compression and CORS middlewares.
         let middleware = tower::ServiceBuilder::new()
-            .layer(CompressionLayer::new())
+            .layer(compression_layer(&config.rpc.compression))
+            .layer(decompression_layer(&config.rpc.compression))
             .layer(cors);
 
-        let service_builder =
-            server_builder.set_rpc_middleware(rpc_middleware::from_config(&config));
+        let service_builder = server_builder.set_rpc_middleware(rpc_middleware::from_config(
+            &config,
+            // The admin API is not subject to the quotas of the clients.
+            rpc_middleware::QuotaLayer::disabled(),
+        ));
         let (stop_handle, server_handle) = jsonrpsee::server::stop_channel();
         std::mem::forget(server_handle);
 
@@ -198,6 +359,33 @@ where
         }
     }
 
+    #[instrument(skip(self), level = "debug")]
+    async fn get_certification_failure(
+        &self,
+        certificate_id: CertificateId,
+    ) -> RpcResult<(Certificate, CertificationFailure)> {
+        let failure = self
+            .debug_store
+            .get_certification_failure(&certificate_id)
+            .map_err(|error| {
+                error!(?error, "Failed to get the certification failure");
+                Error::internal("Unable to get the certification failure")
+            })?
+            .ok_or_else(|| {
+                Error::ResourceNotFound(format!("CertificationFailure({certificate_id})"))
+            })?;
+        let certificate = self
+            .debug_store
+            .get_certificate(&certificate_id)
+            .map_err(|error| {
+                error!(?error, "Failed to get certificate");
+                Error::internal("Unable to get certificate")
+            })?
+            .ok_or_else(|| Error::ResourceNotFound(format!("Certificate({certificate_id})")))?;
+
+        Ok((certificate, failure))
+    }
+
     #[instrument(skip(self, certificate), level = "debug")]
     async fn force_push_pending_certificate(
         &self,
@@ -334,7 +522,14 @@ where
                 &certificate.network_id,
                 &certificate.height,
                 &certificate.certificate_id,
-            ) {
+            )
+            .and_then(|_| {
+                self.state.set_latest_pending_certificate_index(
+                    &certificate.network_id,
+                    &certificate.height,
+                    &certificate.certificate_id,
+                )
+            }) {
             Ok(_) => Ok(()),
             Err(error) => {
                 error!("Failed to update latest pending certificate: {}", error);
@@ -371,7 +566,14 @@ where
                 &certificate.network_id,
                 &certificate.height,
                 &certificate.certificate_id,
-            ) {
+            )
+            .and_then(|_| {
+                self.state.set_latest_proven_certificate_index(
+                    &certificate.network_id,
+                    &certificate.height,
+                    &certificate.certificate_id,
+                )
+            }) {
             Ok(_) => Ok(()),
             Err(error) => {
                 error!("Failed to update latest proven certificate: {}", error);
@@ -394,9 +596,106 @@ where
             .map_err(|error| {
                 error!("Failed to remove generated proof: {}", error);
                 Error::internal("Unable to remove generated proof")
+            })?;
+
+        // Make sure the next proof is generated from scratch.
+        self.pending_store
+            .remove_cached_proof(&certificate_id)
+            .map_err(|error| {
+                error!("Failed to remove cached proof: {}", error);
+                Error::internal("Unable to remove cached proof")
+            })?;
+
+        self.pending_store
+            .remove_submitted_proof(&certificate_id)
+            .map_err(|error| {
+                error!("Failed to remove submitted proof: {}", error);
+                Error::internal("Unable to remove submitted proof")
             })
     }
 
+    #[instrument(skip(self), level = "debug")]
+    async fn repack_epoch(&self, epoch_number: EpochNumber) -> RpcResult<Vec<CertificateId>> {
+        warn!(%epoch_number, "(ADMIN) Re-packing the reverted settlements of the epoch");
+
+        let reverted = self
+            .state
+            .get_reverted_settlements(epoch_number)
+            .map_err(|error| {
+                error!(?error, "Failed to get the reverted settlements");
+                Error::internal("Unable to get the reverted settlements")
+            })?;
+
+        let mut repacked = Vec::new();
+        for certificate_id in reverted {
+            let Some(header) =
+                self.state
+                    .get_certificate_header(&certificate_id)
+                    .map_err(|error| {
+                        error!(?error, "Failed to get certificate header");
+                        Error::internal("Unable to get certificate header")
+                    })?
+            else {
+                warn!(%certificate_id, "Skipping the re-packing of an unknown certificate");
+                continue;
+            };
+
+            // Only the certificates still pending and in error are settled again, the
+            // other ones were settled through another transaction, replaced by the
+            // chain or are already being processed.
+            let is_pending = self
+                .pending_store
+                .get_certificate(header.network_id, header.height)
+                .map_err(|error| {
+                    error!(?error, "Failed to get pending certificate");
+                    Error::internal("Unable to get pending certificate")
+                })?
+                .is_some_and(|certificate| certificate.hash() == certificate_id);
+            if !is_pending || !matches!(header.status, CertificateStatus::InError { .. }) {
+                info!(
+                    %certificate_id,
+                    status = %header.status,
+                    "Skipping the re-packing of a certificate no longer in error"
+                );
+                continue;
+            }
+
+            if header.settlement_tx_hash.is_some() {
+                self.state
+                    .remove_settlement_tx_hash(&certificate_id)
+                    .map_err(|error| {
+                        error!(?error, "Failed to remove settlement_tx_hash");
+                        Error::internal("Unable to remove settlement_tx_hash")
+                    })?;
+            }
+            self.state
+                .update_certificate_header_status(&certificate_id, &CertificateStatus::Pending)
+                .map_err(|error| {
+                    error!(?error, "Failed to update certificate status");
+                    Error::internal("Unable to update certificate status")
+                })?;
+            self.certificate_sender
+                .send((header.network_id, header.height, certificate_id))
+                .await
+                .map_err(|error| {
+                    error!(?error, "Failed to send certificate to orchestrator");
+                    Error::internal("Unable to send certificate to orchestrator")
+                })?;
+
+            info!(%certificate_id, "Certificate sent to be settled again");
+            repacked.push(certificate_id);
+        }
+
+        self.state
+            .remove_reverted_settlements(epoch_number)
+            .map_err(|error| {
+                error!(?error, "Failed to remove the reverted settlements");
+                Error::internal("Unable to remove the reverted settlements")
+            })?;
+
+        Ok(repacked)
+    }
+
     #[instrument(skip(self), level = "debug")]
     async fn remove_pending_certificate(
         &self,
@@ -460,4 +759,103 @@ where
 
         Ok(())
     }
+
+    #[instrument(skip(self), level = "debug")]
+    async fn get_orchestrator_state(&self) -> RpcResult<OrchestratorSnapshot> {
+        Ok(self.orchestrator_state.snapshot())
+    }
+
+    #[instrument(skip(self), level = "info")]
+    async fn enter_maintenance(&self, exit_when_ready: bool) -> RpcResult<MaintenanceState> {
+        let state = self.maintenance.enter(exit_when_ready);
+        warn!(
+            ?state,
+            "Entering maintenance, new certificates are rejected"
+        );
+
+        Ok(state)
+    }
+
+    #[instrument(skip(self), level = "info")]
+    async fn leave_maintenance(&self) -> RpcResult<MaintenanceState> {
+        let state = self.maintenance.leave();
+        info!("Leaving maintenance, new certificates are accepted");
+
+        Ok(state)
+    }
+
+    #[instrument(skip(self), level = "debug")]
+    async fn get_maintenance_state(&self) -> RpcResult<MaintenanceState> {
+        Ok(self.maintenance.state())
+    }
+
+    async fn get_api_key_usage(&self) -> RpcResult<ApiKeyUsageReport> {
+        ApiKeyUsageReport::load(&self.config.rpc.api_keys, self.state.as_ref()).map_err(|error| {
+            error!(?error, "Failed to get the usage of the API keys");
+            Error::internal("Unable to get the usage of the API keys")
+        })
+    }
+
+    async fn get_scrub_report(&self) -> RpcResult<ScrubReport> {
+        Ok(self.scrub_report.get())
+    }
+
+    #[instrument(skip(self), level = "info")]
+    async fn compact_storage(&self, columns: Vec<String>) -> RpcResult<Vec<CompactionTarget>> {
+        let targets = self
+            .compactor
+            .spawn(&columns)
+            .map_err(|error| Error::InvalidArgument(error.to_string()))?;
+        info!(count = targets.len(), "Storage compaction started");
+
+        Ok(targets)
+    }
+
+    #[instrument(skip(self), level = "debug")]
+    async fn advance_clock(&self, blocks: u64) -> RpcResult<Vec<EpochNumber>> {
+        let manual_clock = self.manual_clock.as_ref().ok_or_else(|| {
+            Error::internal("AggLayer isn't configured with a ManualClock configuration")
+        })?;
+        let ended_epochs = manual_clock
+            .advance(blocks)
+            .map_err(|error| Error::InvalidArgument(error.to_string()))?;
+        info!(blocks, ?ended_epochs, "Manual clock advanced");
+
+        Ok(ended_epochs)
+    }
+
+    #[instrument(skip(self, certificate), fields(certificate_id = %certificate.hash()), level = "debug")]
+    async fn debug_native_execution(
+        &self,
+        certificate: Certificate,
+        state_snapshot: Option<String>,
+    ) -> RpcResult<NativeExecutionReport> {
+        let executor = self
+            .native_executor
+            .as_ref()
+            .ok_or_else(|| Error::internal("The native execution is not available"))?;
+
+        let network_id = certificate.network_id;
+        let state = match state_snapshot {
+            Some(name) => {
+                let dir = self.config.rpc.state_snapshots_dir.clone().ok_or_else(|| {
+                    Error::InvalidArgument("No state snapshot is available".to_string())
+                })?;
+
+                // RocksDB opens the snapshot with blocking reads from the disk.
+                tokio::task::spawn_blocking(move || read_snapshot_state(&dir, &name, network_id))
+                    .await
+                    .map_err(|error| {
+                        Error::internal(format!("Failed to read the state snapshot: {error}"))
+                    })??
+            }
+            None => self
+                .state
+                .read_local_network_state(network_id)
+                .map_err(|error| Error::internal(error.to_string()))?
+                .unwrap_or_default(),
+        };
+
+        Ok(executor.execute_natively(state, &certificate).await?)
+    }
 }
@@ -0,0 +1,40 @@
+use agglayer_config::RpcCompressionConfig;
+use tower_http::{
+    compression::{
+        predicate::{NotForContentType, SizeAbove},
+        CompressionLayer, Predicate,
+    },
+    decompression::RequestDecompressionLayer,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Create the layer compressing the responses with the encodings accepted by
+/// the client among the ones enabled in the configuration.
+pub(crate) fn compression_layer(config: &RpcCompressionConfig) -> CompressionLayer<impl Predicate> {
+    // Same predicate as the default one, with a configurable minimum size.
+    let predicate = SizeAbove::new(config.min_size)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE);
+
+    CompressionLayer::new()
+        .gzip(config.gzip)
+        .deflate(config.deflate)
+        .br(config.br)
+        .zstd(config.zstd)
+        .compress_when(predicate)
+}
+
+/// Create the layer decompressing the request bodies. Requests with an
+/// encoding which is not enabled in the configuration are rejected.
+///
+/// The request body size limit applies to the decompressed body.
+pub(crate) fn decompression_layer(config: &RpcCompressionConfig) -> RequestDecompressionLayer {
+    RequestDecompressionLayer::new()
+        .gzip(config.gzip)
+        .deflate(config.deflate)
+        .br(config.br)
+        .zstd(config.zstd)
+}
@@ -0,0 +1,87 @@
+use agglayer_config::RpcCompressionConfig;
+use hyper::{header, StatusCode};
+
+use super::{compression_layer, decompression_layer};
+
+/// Serve a router answering with a body of the given size behind the
+/// compression layers, returning its URL.
+async fn serve(config: RpcCompressionConfig, body_size: usize) -> String {
+    let router = axum::Router::new()
+        .route(
+            "/",
+            axum::routing::post(move |body: String| async move {
+                format!("{body}{}", "a".repeat(body_size))
+            }),
+        )
+        .layer(
+            tower::ServiceBuilder::new()
+                .layer(compression_layer(&config))
+                .layer(decompression_layer(&config)),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    url
+}
+
+async fn post(url: &str, headers: &[(header::HeaderName, &str)]) -> reqwest::Response {
+    let mut request = reqwest::Client::new().post(url).body("");
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn response_is_compressed_with_accepted_encoding() {
+    let url = serve(RpcCompressionConfig::default(), 1024).await;
+
+    let response = post(&url, &[(header::ACCEPT_ENCODING, "gzip")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+}
+
+#[tokio::test]
+async fn response_is_not_compressed_with_disabled_encoding() {
+    let config = RpcCompressionConfig {
+        gzip: false,
+        ..Default::default()
+    };
+    let url = serve(config, 1024).await;
+
+    let response = post(&url, &[(header::ACCEPT_ENCODING, "gzip")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn small_response_is_not_compressed() {
+    let config = RpcCompressionConfig {
+        min_size: 2048,
+        ..Default::default()
+    };
+    let url = serve(config, 1024).await;
+
+    let response = post(&url, &[(header::ACCEPT_ENCODING, "gzip")]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn request_with_disabled_encoding_is_rejected() {
+    let config = RpcCompressionConfig {
+        deflate: false,
+        ..Default::default()
+    };
+    let url = serve(config, 0).await;
+
+    let response = post(&url, &[(header::CONTENT_ENCODING, "deflate")]).await;
+
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
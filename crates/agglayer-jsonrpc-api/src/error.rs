@@ -1,7 +1,9 @@
 //! Support for structured errors in RPC.
 
+use agglayer_certificate_orchestrator::CertificationError;
 use agglayer_rate_limiting::RateLimited as RateLimitedError;
 use agglayer_rpc::CertificateSubmissionError;
+use agglayer_types::{Height, NetworkId};
 use alloy::primitives::B256;
 use jsonrpsee::types::error::ErrorObjectOwned;
 use serde::Serialize;
@@ -33,6 +35,24 @@ pub mod code {
 
     /// Resource not found.
     pub const RESOURCE_NOT_FOUND: i32 = -10008;
+
+    /// The certificate height doesn't follow the known certificates.
+    pub const UNEXPECTED_HEIGHT: i32 = -10009;
+
+    /// The agglayer is in maintenance and doesn't accept new certificates.
+    pub const MAINTENANCE: i32 = -10010;
+
+    /// The API key is missing or unknown.
+    pub const INVALID_API_KEY: i32 = -10011;
+
+    /// A quota of the API key is exhausted for the current period.
+    pub const QUOTA_EXCEEDED: i32 = -10012;
+
+    /// The certificate exceeds the size limits of the agglayer.
+    pub const CERTIFICATE_TOO_LARGE: i32 = -10013;
+
+    /// The agglayer is read-only and doesn't accept writes.
+    pub const READ_ONLY: i32 = -10014;
 }
 
 #[derive(PartialEq, Eq, Serialize, Debug, Clone, thiserror::Error)]
@@ -135,6 +155,37 @@ pub enum Error {
     #[error("Cannot send certificate: {detail}")]
     SendCertificate { detail: String },
 
+    #[error("Unexpected certificate height {height}, expected {expected_height}")]
+    #[serde(rename_all = "kebab-case")]
+    UnexpectedHeight {
+        network_id: NetworkId,
+        height: Height,
+        expected_height: Height,
+    },
+
+    #[error("The agglayer is in maintenance, retry later")]
+    Maintenance,
+
+    #[error("The agglayer is read-only, submit to the primary agglayer")]
+    ReadOnly,
+
+    #[error("Too many {limit} in the certificate: {count}, the maximum is {max}")]
+    #[serde(rename_all = "kebab-case")]
+    CertificateTooLarge {
+        limit: &'static str,
+        count: usize,
+        max: usize,
+    },
+
+    #[error("Too many certificates are waiting for validation, retry later")]
+    Overloaded,
+
+    #[error("Invalid API key: {detail}")]
+    InvalidApiKey { detail: String },
+
+    #[error("Quota exceeded: {detail}")]
+    QuotaExceeded { detail: String },
+
     #[error("Rate limited")]
     #[serde(rename_all = "kebab-case")]
     RateLimited {
@@ -166,6 +217,13 @@ impl Error {
             Self::Settlement(_) => code::SETTLEMENT_ERROR,
             Self::Status(_) => code::STATUS_ERROR,
             Self::SendCertificate { .. } => code::SEND_CERTIFICATE,
+            Self::UnexpectedHeight { .. } => code::UNEXPECTED_HEIGHT,
+            Self::Maintenance => code::MAINTENANCE,
+            Self::ReadOnly => code::READ_ONLY,
+            Self::CertificateTooLarge { .. } => code::CERTIFICATE_TOO_LARGE,
+            Self::Overloaded => code::RATE_LIMITED,
+            Self::InvalidApiKey { .. } => code::INVALID_API_KEY,
+            Self::QuotaExceeded { .. } => code::QUOTA_EXCEEDED,
             Self::RateLimited { .. } => code::RATE_LIMITED,
         }
     }
@@ -195,6 +253,7 @@ impl From<SendTxError> for Error {
                 ValidationError::RootVerification { detail }.into()
             }
             E::Settlement(error) => error.into(),
+            E::ReadOnly => Self::ReadOnly,
         }
     }
 }
@@ -232,8 +291,69 @@ impl From<TxStatusError> for Error {
 
 impl From<CertificateSubmissionError> for Error {
     fn from(error: CertificateSubmissionError) -> Self {
-        let detail = error.to_string();
-        Self::SendCertificate { detail }
+        use agglayer_rpc::error::SignatureVerificationError as S;
+        use CertificateSubmissionError as E;
+        match error {
+            E::UnknownNetwork { network_id } => Self::RollupNotRegistered {
+                rollup_id: network_id.to_u32(),
+            },
+            // The context of the signature could not be fetched from L1.
+            error @ E::SignatureError(
+                S::UnableToRetrieveTrustedSequencerAddress(_)
+                | S::UnableToRetrieveRollupContractAddress { .. }
+                | S::UnableToRetrieveMultisigContext { .. }
+                | S::ContractError(_),
+            ) => {
+                let detail = error.to_string();
+                Self::SendCertificate { detail }
+            }
+            E::SignatureError(error) => {
+                let detail = error.to_string();
+                Self::SignatureMismatch { detail }
+            }
+            E::UnexpectedHeight {
+                network_id,
+                height,
+                expected_height,
+            } => Self::UnexpectedHeight {
+                network_id,
+                height,
+                expected_height,
+            },
+            E::TooManyBridgeExits { count, max } => Self::CertificateTooLarge {
+                limit: "bridge exits",
+                count,
+                max,
+            },
+            E::TooManyImportedBridgeExits { count, max } => Self::CertificateTooLarge {
+                limit: "imported bridge exits",
+                count,
+                max,
+            },
+            E::CertificateTooLarge { size, max } => Self::CertificateTooLarge {
+                limit: "bytes",
+                count: size,
+                max,
+            },
+            E::IntakeOverloaded => Self::Overloaded,
+            E::Maintenance => Self::Maintenance,
+            E::ReadOnly => Self::ReadOnly,
+            error @ (E::InvalidCallbackUrl { .. } | E::ConflictingCallbackUrl { .. }) => {
+                Self::InvalidArgument(error.to_string())
+            }
+            error @ (E::Storage(_) | E::OrchestratorNotResponsive | E::IntakeWorkerFailed) => {
+                Self::internal(error.to_string())
+            }
+            error @ (E::InvalidGlobalIndex { .. }
+            | E::InconsistentImportedBridgeExits(_)
+            | E::InvalidImportedBridgeExit { .. }
+            | E::BlockedBridgeExit { .. }
+            | E::ScreeningFailed(_)
+            | E::UnableToReplacePendingCertificate { .. }) => {
+                let detail = error.to_string();
+                Self::SendCertificate { detail }
+            }
+        }
     }
 }
 
@@ -250,6 +370,61 @@ impl From<agglayer_rpc::CertificateRetrievalError> for Error {
     }
 }
 
+impl From<agglayer_rpc::ProofRetrievalError> for Error {
+    fn from(err: agglayer_rpc::ProofRetrievalError) -> Self {
+        match err {
+            agglayer_rpc::ProofRetrievalError::Storage(error) => Self::internal(error.to_string()),
+            agglayer_rpc::ProofRetrievalError::NotFound { certificate_id } => {
+                Self::ResourceNotFound(format!("Proof({certificate_id})"))
+            }
+        }
+    }
+}
+
+impl From<agglayer_rpc::ProofSubmissionError> for Error {
+    fn from(err: agglayer_rpc::ProofSubmissionError) -> Self {
+        match err {
+            agglayer_rpc::ProofSubmissionError::Storage(error) => Self::internal(error.to_string()),
+            agglayer_rpc::ProofSubmissionError::CertificateNotFound { certificate_id } => {
+                Self::ResourceNotFound(format!("Certificate({certificate_id})"))
+            }
+            error @ agglayer_rpc::ProofSubmissionError::CertificateNotPending { .. } => {
+                Self::InvalidArgument(error.to_string())
+            }
+            error @ agglayer_rpc::ProofSubmissionError::UnableToRetrieveSigner { .. } => {
+                Self::internal(error.to_string())
+            }
+            error @ agglayer_rpc::ProofSubmissionError::InvalidSignature { .. } => {
+                Self::SignatureMismatch {
+                    detail: error.to_string(),
+                }
+            }
+            agglayer_rpc::ProofSubmissionError::ProofEncoding(error) => {
+                Self::internal(error.to_string())
+            }
+            agglayer_rpc::ProofSubmissionError::ReadOnly => Self::ReadOnly,
+        }
+    }
+}
+
+impl From<agglayer_rpc::CertificateCancellationError> for Error {
+    fn from(err: agglayer_rpc::CertificateCancellationError) -> Self {
+        use agglayer_rpc::CertificateCancellationError as E;
+        match err {
+            E::Storage(error) => Self::internal(error.to_string()),
+            E::NotFound { certificate_id } => {
+                Self::ResourceNotFound(format!("Certificate({certificate_id})"))
+            }
+            error @ E::NotPending { .. } => Self::InvalidArgument(error.to_string()),
+            error @ E::UnableToRetrieveSigner { .. } => Self::internal(error.to_string()),
+            error @ E::InvalidSignature { .. } => Self::SignatureMismatch {
+                detail: error.to_string(),
+            },
+            E::ReadOnly => Self::ReadOnly,
+        }
+    }
+}
+
 impl From<agglayer_rpc::GetNetworkInfoError> for Error {
     fn from(err: agglayer_rpc::GetNetworkInfoError) -> Self {
         // Since NetworkStateRetrievalError is currently empty, convert to internal
@@ -258,6 +433,110 @@ impl From<agglayer_rpc::GetNetworkInfoError> for Error {
     }
 }
 
+impl From<agglayer_rpc::GetCertificateStatusesError> for Error {
+    fn from(err: agglayer_rpc::GetCertificateStatusesError) -> Self {
+        match err {
+            agglayer_rpc::GetCertificateStatusesError::Storage(error) => {
+                Self::internal(error.to_string())
+            }
+            error @ agglayer_rpc::GetCertificateStatusesError::TooManyCertificates { .. } => {
+                Self::InvalidArgument(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<agglayer_rpc::GetNetworksError> for Error {
+    fn from(err: agglayer_rpc::GetNetworksError) -> Self {
+        match err {
+            agglayer_rpc::GetNetworksError::Storage(error) => Self::internal(error.to_string()),
+        }
+    }
+}
+
+impl From<agglayer_rpc::GetCurrentEpochStatusError> for Error {
+    fn from(err: agglayer_rpc::GetCurrentEpochStatusError) -> Self {
+        match err {
+            agglayer_rpc::GetCurrentEpochStatusError::Storage(error) => {
+                Self::internal(error.to_string())
+            }
+            error @ agglayer_rpc::GetCurrentEpochStatusError::NoClock => {
+                Self::internal(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<agglayer_rpc::GetNetworkRootsError> for Error {
+    fn from(err: agglayer_rpc::GetNetworkRootsError) -> Self {
+        use agglayer_rpc::GetNetworkRootsError as E;
+        match err {
+            E::Storage(error) => Self::internal(error.to_string()),
+            E::NoSettledCertificate { network_id } => {
+                Self::ResourceNotFound(format!("SettledCertificate({network_id})"))
+            }
+            error @ E::HeightNotSettled { .. } => Self::InvalidArgument(error.to_string()),
+            E::RootsNotRecorded { network_id, height } => {
+                Self::ResourceNotFound(format!("NetworkRoots({network_id}, {height})"))
+            }
+        }
+    }
+}
+
+impl From<agglayer_rpc::GetSettledExitProofError> for Error {
+    fn from(err: agglayer_rpc::GetSettledExitProofError) -> Self {
+        use agglayer_rpc::GetSettledExitProofError as E;
+        match err {
+            E::Storage(error) => Self::internal(error.to_string()),
+            E::NoSettledCertificate { network_id } => {
+                Self::ResourceNotFound(format!("SettledCertificate({network_id})"))
+            }
+            error @ E::LeafIndexOutOfBounds { .. } => Self::InvalidArgument(error.to_string()),
+            error @ E::InconsistentExitTree { .. } => Self::internal(error.to_string()),
+        }
+    }
+}
+
+impl From<agglayer_rpc::GetSettlementCostsError> for Error {
+    fn from(err: agglayer_rpc::GetSettlementCostsError) -> Self {
+        match err {
+            agglayer_rpc::GetSettlementCostsError::Storage(error) => {
+                Self::internal(error.to_string())
+            }
+            error @ agglayer_rpc::GetSettlementCostsError::InvalidEpochRange { .. } => {
+                Self::InvalidArgument(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<agglayer_rpc::QuotaError> for Error {
+    fn from(err: agglayer_rpc::QuotaError) -> Self {
+        use agglayer_rpc::QuotaError as E;
+        match err {
+            E::Storage(error) => Self::internal(error.to_string()),
+            error @ (E::MissingApiKey | E::UnknownApiKey) => Self::InvalidApiKey {
+                detail: error.to_string(),
+            },
+            error @ (E::RequestsExhausted { .. } | E::CertificateBytesExhausted { .. }) => {
+                Self::QuotaExceeded {
+                    detail: error.to_string(),
+                }
+            }
+        }
+    }
+}
+
+impl From<CertificationError> for Error {
+    fn from(error: CertificationError) -> Self {
+        match error {
+            CertificationError::Storage(error) => Self::internal(error.to_string()),
+            CertificationError::InternalError(detail) => Self::Internal(detail),
+            error => ValidationError::dry_run(error).into(),
+        }
+    }
+}
+
 // This impl establishes the integration with `jsonrpsee` errors.
 impl From<Error> for ErrorObjectOwned {
     fn from(err: Error) -> Self {
@@ -0,0 +1,201 @@
+//! Server-Sent Events stream of the certificate and epoch events, for the
+//! consumers which can't hold a WebSocket connection.
+//!
+//! The events are read from the event log, each carrying its identifier so
+//! that a consumer reconnecting with the `Last-Event-ID` header resumes right
+//! after the last event it received. Without it, only the events recorded
+//! after the connection are streamed.
+
+use std::{collections::VecDeque, sync::Arc};
+
+use agglayer_contracts::{AggchainContract, L1TransactionFetcher, RollupContract};
+use agglayer_storage::{
+    columns::{
+        audit_log_per_certificate::{AuditEvent, SubmissionApi},
+        event_log::{self, EventId, LoggedEvent},
+    },
+    stores::{
+        DebugReader, DebugWriter, EpochStoreReader, NetworkInfoReader, PendingCertificateReader,
+        PendingCertificateWriter, StateReader, StateWriter,
+    },
+};
+use agglayer_types::{CertificateId, CertificateStatus, EpochEvent, SettlementTxHash};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::error;
+
+/// Path of the event stream.
+pub(crate) const EVENTS_PATH: &str = "/events";
+
+/// Header sent by the consumers to resume the stream.
+const LAST_EVENT_ID: &str = "last-event-id";
+
+/// Number of events read from the event log at once.
+const EVENTS_BATCH_SIZE: usize = 100;
+
+type RpcService<Rpc, PendingStore, StateStore, DebugStore, EpochsStore> =
+    agglayer_rpc::AgglayerService<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>;
+
+/// Payload of a streamed certificate event.
+#[derive(Serialize)]
+struct CertificateEventPayload {
+    timestamp: DateTime<Utc>,
+    certificate_id: CertificateId,
+    event: CertificateEvent,
+}
+
+/// Step of the lifecycle of a certificate, as streamed to the consumers.
+///
+/// Mirrors the audit log events, without the address of the submitter.
+#[derive(Serialize)]
+enum CertificateEvent {
+    Submitted {
+        api: SubmissionApi,
+    },
+    StatusChanged {
+        status: CertificateStatus,
+    },
+    Proven {
+        prover: Option<String>,
+    },
+    SettlementSubmitted {
+        settlement_tx_hash: SettlementTxHash,
+    },
+}
+
+impl From<AuditEvent> for CertificateEvent {
+    fn from(event: AuditEvent) -> Self {
+        match event {
+            AuditEvent::Submitted { submitter } => Self::Submitted { api: submitter.api },
+            AuditEvent::StatusChanged { status } => Self::StatusChanged { status },
+            AuditEvent::Proven { prover } => Self::Proven { prover },
+            AuditEvent::SettlementSubmitted { settlement_tx_hash } => {
+                Self::SettlementSubmitted { settlement_tx_hash }
+            }
+        }
+    }
+}
+
+/// Payload of a streamed epoch event.
+#[derive(Serialize)]
+struct EpochEventPayload {
+    timestamp: DateTime<Utc>,
+    event: EpochEvent,
+}
+
+/// Convert an event of the log into a Server-Sent Event.
+fn to_sse_event(event_id: EventId, logged: LoggedEvent) -> Result<Event, axum::Error> {
+    let LoggedEvent { timestamp, event } = logged;
+    let sse_event = Event::default().id(event_id.0.to_string());
+
+    match event {
+        event_log::Event::Certificate {
+            certificate_id,
+            event,
+        } => sse_event
+            .event("certificate")
+            .json_data(CertificateEventPayload {
+                timestamp,
+                certificate_id,
+                event: event.into(),
+            }),
+        event_log::Event::Epoch(event) => sse_event
+            .event("epoch")
+            .json_data(EpochEventPayload { timestamp, event }),
+    }
+}
+
+/// Position of a consumer in the event log.
+struct Cursor<Rpc, PendingStore, StateStore, DebugStore, EpochsStore> {
+    rpc_service: Arc<RpcService<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>>,
+    /// Last event sent to the consumer.
+    after: Option<EventId>,
+    /// Events read from the log and not sent yet.
+    pending: VecDeque<(EventId, LoggedEvent)>,
+    appended: watch::Receiver<Option<EventId>>,
+}
+
+/// Stream the events of the log, resuming after the `Last-Event-ID` header
+/// if provided.
+pub(crate) async fn stream_events<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>(
+    State(rpc_service): State<
+        Arc<RpcService<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>>,
+    >,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, String)>
+where
+    Rpc: RollupContract + AggchainContract + L1TransactionFetcher + 'static + Send + Sync,
+    PendingStore: PendingCertificateWriter + PendingCertificateReader + 'static,
+    StateStore: NetworkInfoReader + StateReader + StateWriter + 'static,
+    DebugStore: DebugReader + DebugWriter + 'static,
+    EpochsStore: EpochStoreReader + 'static,
+{
+    // Subscribe before reading the log so that no appended event is missed.
+    let appended = rpc_service.subscribe_events();
+
+    let after = match headers.get(LAST_EVENT_ID) {
+        Some(value) => {
+            let event_id = value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse().ok())
+                .ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        "Invalid Last-Event-ID header".to_string(),
+                    )
+                })?;
+            Some(EventId(event_id))
+        }
+        None => rpc_service.get_latest_event_id().map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to read the event log".to_string(),
+            )
+        })?,
+    };
+
+    let cursor = Cursor {
+        rpc_service,
+        after,
+        pending: VecDeque::new(),
+        appended,
+    };
+
+    let stream = futures::stream::unfold(cursor, |mut cursor| async move {
+        loop {
+            if let Some((event_id, logged)) = cursor.pending.pop_front() {
+                cursor.after = Some(event_id);
+                return Some((to_sse_event(event_id, logged), cursor));
+            }
+
+            match cursor
+                .rpc_service
+                .get_events(cursor.after, EVENTS_BATCH_SIZE)
+            {
+                Ok(events) if !events.is_empty() => {
+                    cursor.pending = events.into();
+                    continue;
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    error!(?error, "Ending the event stream");
+                    return None;
+                }
+            }
+
+            if cursor.appended.changed().await.is_err() {
+                return None;
+            }
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
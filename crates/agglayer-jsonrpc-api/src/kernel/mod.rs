@@ -93,6 +93,12 @@ impl<RpcProvider> Kernel<RpcProvider> {
         &self.rate_limiter
     }
 
+    /// Check if the agglayer is read-only, in which case no transaction is
+    /// settled.
+    pub(crate) fn is_read_only(&self) -> bool {
+        self.config.read_only.enabled
+    }
+
     /// Check if the given rollup id is registered in the configuration.
     pub(crate) fn check_rollup_registered(&self, rollup_id: u32) -> bool {
         self.config.full_node_rpcs.contains_key(&rollup_id)
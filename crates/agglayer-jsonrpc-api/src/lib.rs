@@ -1,33 +1,56 @@
 use std::{
     future::Future,
+    net::SocketAddr,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
 };
 
+use agglayer_certificate_orchestrator::{OrchestratorState, ProvingCostEstimator};
 use agglayer_contracts::{AggchainContract, L1TransactionFetcher, RollupContract};
-use agglayer_storage::stores::{
-    DebugReader, DebugWriter, EpochStoreReader, NetworkInfoReader, PendingCertificateReader,
-    PendingCertificateWriter, StateReader, StateWriter,
+use agglayer_rpc::{ApiKeyUsageReport, ProofEncoding};
+use agglayer_signer::ConfiguredSigner;
+use agglayer_storage::{
+    columns::audit_log_per_certificate::{SubmissionApi, Submitter},
+    stores::{
+        DebugReader, DebugWriter, EpochStoreReader, NetworkInfoReader, PendingCertificateReader,
+        PendingCertificateWriter, StateReader, StateWriter,
+    },
 };
 use agglayer_types::{
-    Certificate, CertificateHeader, CertificateId, EpochConfiguration, NetworkId, NetworkInfo,
+    Certificate, CertificateHeader, CertificateHeaderDetails, CertificateId, CertificateStatus,
+    CertificateSubmissionReceipt, EpochConfiguration, EpochEvent, EpochNumber, EpochStatus, Height,
+    NetworkId, NetworkInfo, NetworkRoots, NetworkSummary, Proof, ProvingCostEstimate,
+    SettledExitProof, SettlementCostsReport, Signature, SignedCertificateHeader, VersionInfo,
+};
+use alloy::{
+    primitives::{Bytes, B256},
+    providers::Provider,
+    signers::Signer as _,
 };
-use alloy::{primitives::B256, providers::Provider};
 use error::{Error, RpcResult};
 use futures::FutureExt;
 use hyper::StatusCode;
 use jsonrpsee::{
-    core::async_trait,
+    core::{async_trait, SubscriptionResult},
     proc_macros::rpc,
     server::{HttpBody, PingConfig, ServerBuilder},
+    Extensions, PendingSubscriptionSink, SubscriptionMessage,
+};
+use tokio::sync::broadcast;
+use tower_http::cors::CorsLayer;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::{
+    compression::{compression_layer, decompression_layer},
+    service::AgglayerService,
+    signed_tx::SignedTx,
 };
-use tower_http::{compression::CompressionLayer, cors::CorsLayer};
-use tracing::info;
-
-use crate::{service::AgglayerService, signed_tx::SignedTx};
 
+mod compression;
 mod error;
+mod events;
 pub mod kernel;
 mod rpc_middleware;
 pub mod service;
@@ -50,18 +73,95 @@ trait Agglayer {
     #[method(name = "getTxStatus")]
     async fn get_tx_status(&self, hash: B256) -> RpcResult<TxStatus>;
 
-    #[method(name = "sendCertificate")]
-    async fn send_certificate(&self, certificate: Certificate) -> RpcResult<CertificateId>;
+    /// Submit a certificate. When a callback URL is given, the final status
+    /// of the certificate is posted to it once reached.
+    ///
+    /// Along with the id of the certificate, its position in the queue of
+    /// its network and the epochs in which it is expected to be settled are
+    /// returned.
+    #[method(name = "sendCertificate", with_extensions)]
+    async fn send_certificate(
+        &self,
+        certificate: Certificate,
+        callback_url: Option<Url>,
+    ) -> RpcResult<CertificateSubmissionReceipt>;
+
+    /// Submit the proof of a pending certificate generated outside of the
+    /// agglayer, which is then used instead of proving the certificate. The
+    /// signature is the one of the trusted sequencer of the network over the
+    /// proof submission commitment of the certificate id and the proof.
+    #[method(name = "submitProof")]
+    async fn submit_proof(
+        &self,
+        certificate_id: CertificateId,
+        proof: Proof,
+        signature: Signature,
+    ) -> RpcResult<()>;
+
+    /// Withdraw a certificate which is still pending and not yet taken by the
+    /// orchestrator. The signature is the one of the trusted sequencer of the
+    /// network over the cancellation commitment of the certificate id, the
+    /// certificate being then in error as cancelled.
+    #[method(name = "cancelCertificate")]
+    async fn cancel_certificate(
+        &self,
+        certificate_id: CertificateId,
+        signature: Signature,
+    ) -> RpcResult<()>;
+
+    /// Estimate the cost and time of proving a candidate certificate on the
+    /// SP1 network, by executing the pessimistic proof program on top of the
+    /// settled state of the network. The certificate is not submitted, but
+    /// must be signed as for its submission. The estimations run concurrently
+    /// are bounded by `rpc.intake.estimation-workers`, beyond which they are
+    /// rejected.
+    #[method(name = "estimateCertificate")]
+    async fn estimate_certificate(
+        &self,
+        certificate: Certificate,
+    ) -> RpcResult<ProvingCostEstimate>;
 
+    /// Header of the certificate, along with the L1 block including its
+    /// settlement transaction and its number of confirmations once settled.
     #[method(name = "getCertificateHeader")]
     async fn get_certificate_header(
         &self,
         certificate_id: CertificateId,
-    ) -> RpcResult<CertificateHeader>;
+    ) -> RpcResult<CertificateHeaderDetails>;
+
+    /// Header of the certificate signed with the key of the agglayer, over
+    /// its `CertificateHeader::signature_commitment`. Only available when the
+    /// response signing is enabled.
+    #[method(name = "getSignedCertificateHeader")]
+    async fn get_signed_certificate_header(
+        &self,
+        certificate_id: CertificateId,
+    ) -> RpcResult<SignedCertificateHeader>;
+
+    /// Proof of the certificate as the bincode serialization of the `Proof`,
+    /// `null` if it has not been generated yet. The `zstd` encoding returns
+    /// it compressed instead, as a single zstd frame.
+    #[method(name = "getCertificateProof")]
+    async fn get_certificate_proof(
+        &self,
+        certificate_id: CertificateId,
+        encoding: Option<ProofEncoding>,
+    ) -> RpcResult<Option<Bytes>>;
 
     #[method(name = "getEpochConfiguration")]
     async fn get_epoch_configuration(&self) -> RpcResult<EpochConfiguration>;
 
+    /// Status of the current epoch, with its bounds, its expected close time
+    /// and the number of networks which can still settle a certificate in it,
+    /// for the submitters to time their certificates.
+    #[method(name = "getCurrentEpochStatus")]
+    async fn get_current_epoch_status(&self) -> RpcResult<EpochStatus>;
+
+    /// Version of the running agglayer and of its embedded pessimistic proof
+    /// program.
+    #[method(name = "getVersion")]
+    async fn get_version(&self) -> RpcResult<VersionInfo>;
+
     #[method(name = "getLatestKnownCertificateHeader")]
     async fn get_latest_known_certificate_header(
         &self,
@@ -82,6 +182,56 @@ trait Agglayer {
 
     #[method(name = "getNetworkInfo")]
     async fn get_network_info(&self, network_id: NetworkId) -> RpcResult<NetworkInfo>;
+
+    /// Networks known by the agglayer, with their latest heights and the time
+    /// of their latest activity.
+    #[method(name = "getNetworks")]
+    async fn get_networks(&self) -> RpcResult<Vec<NetworkSummary>>;
+
+    /// Roots of the local network state of the network at the given settled
+    /// height, or at the latest settled height if omitted.
+    #[method(name = "getNetworkRoots")]
+    async fn get_network_roots(
+        &self,
+        network_id: NetworkId,
+        height: Option<Height>,
+    ) -> RpcResult<NetworkRoots>;
+
+    /// Merkle proof of a leaf of the local exit tree of the network against
+    /// its latest settled local exit root.
+    #[method(name = "getSettledExitProof")]
+    async fn get_settled_exit_proof(
+        &self,
+        network_id: NetworkId,
+        leaf_index: u32,
+    ) -> RpcResult<SettledExitProof>;
+
+    #[method(name = "getSettlementCosts")]
+    async fn get_settlement_costs(
+        &self,
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    ) -> RpcResult<SettlementCostsReport>;
+
+    /// Statuses of the certificates, in the order of the given ids, `null`
+    /// for the unknown ones.
+    #[method(name = "getCertificateStatuses")]
+    async fn get_certificate_statuses(
+        &self,
+        certificate_ids: Vec<CertificateId>,
+    ) -> RpcResult<Vec<Option<CertificateStatus>>>;
+
+    /// Usage and quotas of the API key of the request over the current quota
+    /// period.
+    #[method(name = "getApiKeyUsage", with_extensions)]
+    async fn get_api_key_usage(&self) -> RpcResult<ApiKeyUsageReport>;
+
+    /// Subscribe to the epoch lifecycle events, emitted when an epoch is
+    /// opened, when its packing starts and when all its certificates are
+    /// settled.
+    #[subscription(name = "subscribeEpochs", unsubscribe = "unsubscribeEpochs", item = EpochEvent)]
+    async fn subscribe_epochs(&self) -> SubscriptionResult;
 }
 
 /// The RPC agglayer service implementation.
@@ -89,6 +239,10 @@ pub struct AgglayerImpl<V0Rpc, Rpc, PendingStore, StateStore, DebugStore, Epochs
     service: Arc<AgglayerService<V0Rpc>>,
     pub(crate) rpc_service:
         Arc<agglayer_rpc::AgglayerService<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>>,
+    epoch_events: broadcast::Sender<EpochEvent>,
+    proving_cost_estimator: Option<Arc<dyn ProvingCostEstimator>>,
+    response_signer: Option<Arc<ConfiguredSigner>>,
+    orchestrator_state: Option<Arc<OrchestratorState>>,
 }
 
 impl<V0Rpc, Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
@@ -100,12 +254,38 @@ impl<V0Rpc, Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
         rpc_service: Arc<
             agglayer_rpc::AgglayerService<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>,
         >,
+        epoch_events: broadcast::Sender<EpochEvent>,
     ) -> Self {
         Self {
             service,
             rpc_service,
+            epoch_events,
+            proving_cost_estimator: None,
+            response_signer: None,
+            orchestrator_state: None,
         }
     }
+
+    /// Estimate the proving cost of the candidate certificates with the given
+    /// estimator.
+    pub fn with_proving_cost_estimator(mut self, estimator: Arc<dyn ProvingCostEstimator>) -> Self {
+        self.proving_cost_estimator = Some(estimator);
+        self
+    }
+
+    /// Sign the certificate headers served by
+    /// `interop_getSignedCertificateHeader` with the given signer.
+    pub fn with_response_signer(mut self, signer: Arc<ConfiguredSigner>) -> Self {
+        self.response_signer = Some(signer);
+        self
+    }
+
+    /// Refuse to cancel the certificates being processed by the orchestrator
+    /// of the given state.
+    pub fn with_orchestrator_state(mut self, orchestrator_state: Arc<OrchestratorState>) -> Self {
+        self.orchestrator_state = Some(orchestrator_state);
+        self
+    }
 }
 
 impl<V0Rpc, Rpc, PendingStore, StateStore, DebugStore, EpochsStore> Drop
@@ -159,16 +339,31 @@ where
                 hyper::Method::OPTIONS,
             ])
             .allow_origin(tower_http::cors::Any)
-            .allow_headers([hyper::header::CONTENT_TYPE]);
+            .allow_headers([hyper::header::CONTENT_TYPE, rpc_middleware::API_KEY_HEADER]);
 
-        // Create a middleware stack with the CORS middleware and a proxy layer for
-        // health checks.
+        // Create a middleware stack with the compression and CORS middlewares,
+        // passing the API key along to the RPC middleware.
         let middleware = tower::ServiceBuilder::new()
-            .layer(CompressionLayer::new())
+            .map_request(rpc_middleware::extract_api_key::<axum::body::Body>)
+            .layer(compression_layer(&config.rpc.compression))
+            .layer(decompression_layer(&config.rpc.compression))
+            .layer(cors.clone());
+
+        // The event stream is not compressed, so that each event is flushed to
+        // the consumers as soon as it is recorded.
+        let events = axum::Router::new()
+            .route(
+                events::EVENTS_PATH,
+                axum::routing::get(
+                    events::stream_events::<Rpc, PendingStore, StateStore, DebugStore, EpochsStore>,
+                ),
+            )
+            .with_state(self.rpc_service.clone())
             .layer(cors);
 
+        let quota = rpc_middleware::QuotaLayer::new(self.rpc_service.clone());
         let service_builder =
-            server_builder.set_rpc_middleware(rpc_middleware::from_config(config));
+            server_builder.set_rpc_middleware(rpc_middleware::from_config(config, quota));
 
         let (stop_handle, server_handle) = jsonrpsee::server::stop_channel();
         // Server handle isn't used as we're relying on axum to manage the server
@@ -187,7 +382,8 @@ where
             .route("/", axum::routing::get_service(service.clone()))
             .route("/json-rpc", axum::routing::post_service(service.clone()))
             .route("/json-rpc", axum::routing::get_service(service.clone()))
-            .layer(middleware))
+            .layer(middleware)
+            .merge(events))
     }
 }
 
@@ -210,21 +406,151 @@ where
         Ok(self.service.get_tx_status(hash).await?.to_string())
     }
 
-    async fn send_certificate(&self, certificate: Certificate) -> RpcResult<CertificateId> {
+    async fn send_certificate(
+        &self,
+        extensions: &Extensions,
+        certificate: Certificate,
+        callback_url: Option<Url>,
+    ) -> RpcResult<CertificateSubmissionReceipt> {
         // NOTE: Extra certificate signature is not supported on the json rpc api
         let extra_signature = None;
 
+        // The peer address is only known when served with the connection info.
+        let submitter = Submitter {
+            api: SubmissionApi::JsonRpc,
+            address: extensions
+                .get::<axum::extract::ConnectInfo<SocketAddr>>()
+                .map(|info| info.0),
+        };
+
+        // The status of an already known certificate can be queried with
+        // `interop_getCertificateHeader`.
+        let submission = self
+            .rpc_service
+            .send_certificate(certificate, extra_signature, callback_url, submitter)
+            .await?;
+
+        Ok(CertificateSubmissionReceipt {
+            certificate_id: submission.certificate_id,
+            position: submission.position,
+        })
+    }
+
+    async fn submit_proof(
+        &self,
+        certificate_id: CertificateId,
+        proof: Proof,
+        signature: Signature,
+    ) -> RpcResult<()> {
         Ok(self
             .rpc_service
-            .send_certificate(certificate, extra_signature)
+            .submit_proof(certificate_id, proof, signature)
             .await?)
     }
 
+    async fn cancel_certificate(
+        &self,
+        certificate_id: CertificateId,
+        signature: Signature,
+    ) -> RpcResult<()> {
+        // The network task picks up the certificates of its network under the
+        // lock of the network, which is held until the certificate is cancelled.
+        let _network_guard = match &self.orchestrator_state {
+            Some(state) => {
+                let network_id = self
+                    .rpc_service
+                    .fetch_certificate_header(certificate_id)?
+                    .network_id;
+                let guard = state
+                    .network_lock(network_id)
+                    .try_lock_owned()
+                    .map_err(|_| {
+                        if state.is_in_flight(&certificate_id) {
+                            Error::InvalidArgument(format!(
+                                "Certificate {certificate_id} is being processed, it can no \
+                                 longer be cancelled"
+                            ))
+                        } else {
+                            Error::InvalidArgument(format!(
+                                "Network {network_id} has a certificate being processed, retry \
+                                 once it is settled"
+                            ))
+                        }
+                    })?;
+
+                Some(guard)
+            }
+            None => None,
+        };
+
+        Ok(self
+            .rpc_service
+            .cancel_certificate(certificate_id, signature)
+            .await?)
+    }
+
+    async fn estimate_certificate(
+        &self,
+        certificate: Certificate,
+    ) -> RpcResult<ProvingCostEstimate> {
+        let estimator = self
+            .proving_cost_estimator
+            .as_ref()
+            .ok_or_else(|| Error::internal("The proving cost estimation is not available"))?;
+
+        let (certificate, _permit) = self
+            .rpc_service
+            .check_candidate_certificate(certificate)
+            .await?;
+
+        let state = self
+            .rpc_service
+            .get_local_network_state(certificate.network_id)
+            .map_err(|error| Error::internal(error.to_string()))?;
+
+        Ok(estimator.estimate_proving_cost(state, &certificate).await?)
+    }
+
     async fn get_certificate_header(
         &self,
         certificate_id: CertificateId,
-    ) -> RpcResult<CertificateHeader> {
-        Ok(self.rpc_service.fetch_certificate_header(certificate_id)?)
+    ) -> RpcResult<CertificateHeaderDetails> {
+        Ok(self
+            .rpc_service
+            .fetch_certificate_header_details(certificate_id)
+            .await?)
+    }
+
+    async fn get_signed_certificate_header(
+        &self,
+        certificate_id: CertificateId,
+    ) -> RpcResult<SignedCertificateHeader> {
+        let signer = self
+            .response_signer
+            .as_ref()
+            .ok_or_else(|| Error::internal("The response signing is not enabled"))?;
+
+        let header = self.rpc_service.fetch_certificate_header(certificate_id)?;
+        let signature = signer
+            .sign_hash(&B256::new(header.signature_commitment().0))
+            .await
+            .map_err(|error| Error::internal(error.to_string()))?;
+
+        Ok(SignedCertificateHeader {
+            header,
+            signature: signature.into(),
+        })
+    }
+
+    async fn get_certificate_proof(
+        &self,
+        certificate_id: CertificateId,
+        encoding: Option<ProofEncoding>,
+    ) -> RpcResult<Option<Bytes>> {
+        Ok(self
+            .rpc_service
+            .get_encoded_proof(certificate_id, encoding.unwrap_or_default())?
+            .map(Bytes::from))
     }
 
     async fn get_epoch_configuration(&self) -> RpcResult<EpochConfiguration> {
@@ -236,6 +562,14 @@ where
         })?)
     }
 
+    async fn get_current_epoch_status(&self) -> RpcResult<EpochStatus> {
+        Ok(self.rpc_service.get_current_epoch_status()?)
+    }
+
+    async fn get_version(&self) -> RpcResult<VersionInfo> {
+        Ok(self.rpc_service.get_version().clone())
+    }
+
     async fn get_latest_known_certificate_header(
         &self,
         network_id: NetworkId,
@@ -271,6 +605,83 @@ where
 
         Ok(state)
     }
+
+    async fn get_networks(&self) -> RpcResult<Vec<NetworkSummary>> {
+        Ok(self.rpc_service.get_networks()?)
+    }
+
+    async fn get_network_roots(
+        &self,
+        network_id: NetworkId,
+        height: Option<Height>,
+    ) -> RpcResult<NetworkRoots> {
+        Ok(self.rpc_service.get_network_roots(network_id, height)?)
+    }
+
+    async fn get_settled_exit_proof(
+        &self,
+        network_id: NetworkId,
+        leaf_index: u32,
+    ) -> RpcResult<SettledExitProof> {
+        Ok(self
+            .rpc_service
+            .get_settled_exit_proof(network_id, leaf_index)?)
+    }
+
+    async fn get_settlement_costs(
+        &self,
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    ) -> RpcResult<SettlementCostsReport> {
+        Ok(self
+            .rpc_service
+            .get_settlement_costs(network_id, from_epoch, to_epoch)?)
+    }
+
+    async fn get_certificate_statuses(
+        &self,
+        certificate_ids: Vec<CertificateId>,
+    ) -> RpcResult<Vec<Option<CertificateStatus>>> {
+        Ok(self
+            .rpc_service
+            .get_certificate_statuses(&certificate_ids)?)
+    }
+
+    async fn get_api_key_usage(&self, extensions: &Extensions) -> RpcResult<ApiKeyUsageReport> {
+        let api_key = extensions.get::<rpc_middleware::ApiKey>();
+
+        Ok(self
+            .rpc_service
+            .get_api_key_usage(api_key.map(|key| key.0.as_str()))?)
+    }
+
+    async fn subscribe_epochs(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let mut epoch_events = self.epoch_events.subscribe();
+        let sink = pending.accept().await?;
+
+        loop {
+            let event = tokio::select! {
+                _ = sink.closed() => break,
+                event = epoch_events.recv() => event,
+            };
+
+            match event {
+                Ok(event) => {
+                    let message = SubscriptionMessage::from_json(&event)?;
+                    if sink.send(message).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!(skipped, "Epoch events subscriber lagging behind");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+
+        Ok(())
+    }
 }
 
 type TxStatus = String;
@@ -0,0 +1,185 @@
+//! RPC middleware for recording the request metrics.
+
+use std::{fmt, future::Future, time::Instant};
+
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use serde::{
+    de::{IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use super::RequestInfo;
+
+/// An RPC layer that records the request count, error count and latency of
+/// each method.
+#[derive(Clone, Debug)]
+pub struct MetricsLayer {}
+
+impl MetricsLayer {
+    pub fn new() -> Self {
+        MetricsLayer {}
+    }
+}
+
+impl<S> tower::Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService(inner)
+    }
+}
+
+pub struct MetricsService<S>(S);
+
+impl<'a, S: RpcServiceT<'a>> RpcServiceT<'a> for MetricsService<S> {
+    type Future = MetricsFuture<'a, S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let network_id = network_label(request.params().as_str());
+
+        MetricsFuture {
+            started_at: Instant::now(),
+            network_id,
+            request_info: RequestInfo::from_request(&request),
+            inner: self.0.call(request),
+        }
+    }
+}
+
+#[pin_project::pin_project]
+pub struct MetricsFuture<'a, F> {
+    /// Time at which the request started to be handled.
+    started_at: Instant,
+
+    /// Network the request relates to, if any.
+    network_id: Option<u32>,
+
+    /// Request and method information.
+    request_info: RequestInfo<'a>,
+
+    /// The future to record the metrics of.
+    #[pin]
+    inner: F,
+}
+
+impl<F: Future<Output = MethodResponse>> Future for MetricsFuture<'_, F> {
+    type Output = MethodResponse;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let this = self.project();
+
+        let response = std::task::ready!(this.inner.poll(cx));
+        agglayer_telemetry::rpc::record_request(
+            &this.request_info.method,
+            *this.network_id,
+            response.as_error_code(),
+            this.started_at.elapsed(),
+        );
+
+        std::task::Poll::Ready(response)
+    }
+}
+
+/// Extract the network the request relates to from its parameters.
+///
+/// The network is taken from the first positional parameter, either when it
+/// is a network id or when it is an object with a `network_id` field, such as
+/// a certificate.
+pub(crate) fn network_label(params: Option<&str>) -> Option<u32> {
+    serde_json::from_str::<FirstParamNetwork>(params?)
+        .ok()
+        .and_then(|first| first.0)
+}
+
+/// Network found in the first element of the positional parameters.
+struct FirstParamNetwork(Option<u32>);
+
+impl<'de> Deserialize<'de> for FirstParamNetwork {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ParamsVisitor;
+
+        impl<'de> Visitor<'de> for ParamsVisitor {
+            type Value = FirstParamNetwork;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("positional parameters")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let network_id = seq
+                    .next_element::<NetworkParam>()?
+                    .and_then(|param| param.0);
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+                Ok(FirstParamNetwork(network_id))
+            }
+        }
+
+        deserializer.deserialize_seq(ParamsVisitor)
+    }
+}
+
+/// Network id, or object carrying a `network_id` field. Any other parameter
+/// is skipped without being deserialized.
+struct NetworkParam(Option<u32>);
+
+impl<'de> Deserialize<'de> for NetworkParam {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ParamVisitor;
+
+        impl<'de> Visitor<'de> for ParamVisitor {
+            type Value = NetworkParam;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("any parameter")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(NetworkParam(u32::try_from(value).ok()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut network_id = None;
+                while let Some(key) = map.next_key::<std::borrow::Cow<'de, str>>()? {
+                    if key == "network_id" {
+                        network_id = map.next_value::<NetworkParam>()?.0;
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+
+                Ok(NetworkParam(network_id))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                Ok(NetworkParam(None))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, _: i64) -> Result<Self::Value, E> {
+                Ok(NetworkParam(None))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(NetworkParam(None))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(NetworkParam(None))
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(NetworkParam(None))
+            }
+
+            fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+                Ok(NetworkParam(None))
+            }
+        }
+
+        deserializer.deserialize_any(ParamVisitor)
+    }
+}
@@ -5,12 +5,17 @@ use tower::layer::util::{Identity, Stack};
 
 mod cancel_logger;
 mod logging_timeout;
+mod metrics;
+mod quota;
 
 #[cfg(test)]
 mod tests;
 
 pub use cancel_logger::CancelLoggerLayer;
 pub use logging_timeout::LoggingTimeoutLayer;
+pub use metrics::MetricsLayer;
+pub(crate) use quota::extract_api_key;
+pub use quota::{ApiKey, ApiKeyQuotas, QuotaLayer, API_KEY_HEADER};
 
 /// Information about the method being executed.
 struct RequestInfo<'a> {
@@ -28,16 +33,29 @@ impl<'a> RequestInfo<'a> {
 }
 
 /// The stack of RPC middleware layers.
-pub type RpcStack = Stack<LoggingTimeoutLayer, Stack<CancelLoggerLayer, Identity>>;
+pub type RpcStack = Stack<
+    LoggingTimeoutLayer,
+    Stack<QuotaLayer, Stack<MetricsLayer, Stack<CancelLoggerLayer, Identity>>>,
+>;
 
 /// Build the middleware stack with given params.
-pub fn build(request_timeout: std::time::Duration) -> RpcServiceBuilder<RpcStack> {
+pub fn build(
+    request_timeout: std::time::Duration,
+    quota: QuotaLayer,
+) -> RpcServiceBuilder<RpcStack> {
     jsonrpsee::server::middleware::rpc::RpcServiceBuilder::new()
         .layer(CancelLoggerLayer::new())
+        // Record the metrics outside of the timeout to account for the timed out requests.
+        .layer(MetricsLayer::new())
+        // Record the metrics of the requests rejected by the quotas as well.
+        .layer(quota)
         .layer(LoggingTimeoutLayer::new(request_timeout))
 }
 
-/// Build the RPC middleware stack from config.
-pub fn from_config(config: &agglayer_config::Config) -> RpcServiceBuilder<RpcStack> {
-    build(config.rpc.request_timeout)
+/// Build the RPC middleware stack from config, enforcing the given quotas.
+pub fn from_config(
+    config: &agglayer_config::Config,
+    quota: QuotaLayer,
+) -> RpcServiceBuilder<RpcStack> {
+    build(config.rpc.request_timeout, quota)
 }
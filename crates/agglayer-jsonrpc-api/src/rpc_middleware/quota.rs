@@ -0,0 +1,129 @@
+//! RPC middleware enforcing the quotas of the API keys.
+
+use std::sync::Arc;
+
+use agglayer_rpc::{AgglayerService, QuotaError};
+use agglayer_storage::stores::{StateReader, StateWriter};
+use futures::future::{ready, Either, Ready};
+use http::HeaderName;
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObjectOwned, Request},
+    MethodResponse,
+};
+
+use crate::error::Error;
+
+/// Header in which the clients pass their API key.
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Method whose parameters are accounted for in the certificate quota.
+const SEND_CERTIFICATE_METHOD: &str = "interop_sendCertificate";
+
+/// API key of the request, copied from its HTTP header to its extensions.
+#[derive(Clone, Debug)]
+pub struct ApiKey(pub String);
+
+/// Copy the API key header of the HTTP request to its extensions, which are
+/// passed along to the RPC requests.
+pub(crate) fn extract_api_key<B>(mut request: http::Request<B>) -> http::Request<B> {
+    let api_key = request
+        .headers()
+        .get(&API_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| ApiKey(value.to_string()));
+
+    if let Some(api_key) = api_key {
+        request.extensions_mut().insert(api_key);
+    }
+
+    request
+}
+
+/// Accounting of the requests against the quotas of their API key.
+///
+/// Hides the generics of the RPC service from the middleware stack.
+pub trait ApiKeyQuotas: Send + Sync {
+    fn consume(&self, api_key: Option<&str>, certificate_bytes: u64) -> Result<(), QuotaError>;
+}
+
+impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore> ApiKeyQuotas
+    for AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
+where
+    PendingStore: Send + Sync,
+    StateStore: StateReader + StateWriter,
+    DebugStore: Send + Sync,
+    L1Rpc: Send + Sync,
+    EpochsStore: Send + Sync,
+{
+    fn consume(&self, api_key: Option<&str>, certificate_bytes: u64) -> Result<(), QuotaError> {
+        self.consume_api_key_quota(api_key, certificate_bytes)
+    }
+}
+
+/// An RPC layer that rejects the requests once a quota of their API key is
+/// exhausted.
+#[derive(Clone)]
+pub struct QuotaLayer {
+    quotas: Option<Arc<dyn ApiKeyQuotas>>,
+}
+
+impl QuotaLayer {
+    pub fn new(quotas: Arc<dyn ApiKeyQuotas>) -> Self {
+        Self {
+            quotas: Some(quotas),
+        }
+    }
+
+    /// A layer letting all the requests through.
+    pub fn disabled() -> Self {
+        Self { quotas: None }
+    }
+}
+
+impl<S> tower::Layer<S> for QuotaLayer {
+    type Service = QuotaService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        QuotaService {
+            inner,
+            quotas: self.quotas.clone(),
+        }
+    }
+}
+
+pub struct QuotaService<S> {
+    /// The underlying service.
+    inner: S,
+
+    /// The quotas to enforce, if any.
+    quotas: Option<Arc<dyn ApiKeyQuotas>>,
+}
+
+impl<'a, S: RpcServiceT<'a>> RpcServiceT<'a> for QuotaService<S> {
+    type Future = Either<S::Future, Ready<MethodResponse>>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let Some(quotas) = &self.quotas else {
+            return Either::Left(self.inner.call(request));
+        };
+
+        let api_key = request.extensions().get::<ApiKey>();
+        let certificate_bytes = if request.method_name() == SEND_CERTIFICATE_METHOD {
+            request
+                .params()
+                .as_str()
+                .map_or(0, |params| params.len() as u64)
+        } else {
+            0
+        };
+
+        match quotas.consume(api_key.map(|key| key.0.as_str()), certificate_bytes) {
+            Ok(()) => Either::Left(self.inner.call(request)),
+            Err(error) => {
+                let error = ErrorObjectOwned::from(Error::from(error));
+                Either::Right(ready(MethodResponse::error(request.id, error)))
+            }
+        }
+    }
+}
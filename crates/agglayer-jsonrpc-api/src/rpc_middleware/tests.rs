@@ -11,7 +11,7 @@ use jsonrpsee::{
 };
 use tracing_subscriber::layer::SubscriberExt;
 
-use super::LoggingTimeoutLayer;
+use super::{LoggingTimeoutLayer, QuotaLayer};
 
 #[rpc(server)]
 trait Test {
@@ -99,7 +99,7 @@ const CANCELLED_STR: &str = "`do_stuff` was cancelled";
 #[tokio::test]
 async fn completed_before_deadline() {
     let (log, res) = capture_log(async {
-        let middleware = super::build(Duration::from_secs(10));
+        let middleware = super::build(Duration::from_secs(10), QuotaLayer::disabled());
         let (_server, client) = TestRpc::start(Duration::from_secs(1), middleware).await;
 
         let res = client.do_stuff().await;
@@ -117,7 +117,7 @@ async fn completed_before_deadline() {
 #[tokio::test]
 async fn timed_out() {
     let (log, res) = capture_log(async {
-        let middleware = super::build(Duration::from_secs(2));
+        let middleware = super::build(Duration::from_secs(2), QuotaLayer::disabled());
         let (_server, client) = TestRpc::start(Duration::from_secs(10), middleware).await;
 
         let res = client.do_stuff().await;
@@ -146,7 +146,7 @@ async fn timed_out() {
 #[tokio::test]
 async fn request_dropped() {
     let (log, res) = capture_log(async {
-        let middleware = super::build(Duration::from_secs(20));
+        let middleware = super::build(Duration::from_secs(20), QuotaLayer::disabled());
         let (_server, client) = TestRpc::start(Duration::from_secs(10), middleware).await;
 
         let res = tokio::time::timeout(Duration::from_secs(2), client.do_stuff()).await;
@@ -163,3 +163,21 @@ async fn request_dropped() {
     assert!(!log_contains(&log, TIMED_OUT_STR));
     assert!(log_contains(&log, CANCELLED_STR));
 }
+
+#[rstest::rstest]
+#[case::network_id(Some(r#"[7]"#), Some(7))]
+#[case::network_id_with_more_params(Some(r#"[7, 1, 2]"#), Some(7))]
+#[case::certificate(
+    Some(r#"[{"network_id": 3, "height": 1, "bridge_exits": []}, null]"#),
+    Some(3)
+)]
+#[case::certificate_id(Some(r#"["0x0101"]"#), None)]
+#[case::list_of_ids(Some(r#"[["0x0101"], 2]"#), None)]
+#[case::object_without_network_id(Some(r#"[{"tx": {"rollup_id": 1}}]"#), None)]
+#[case::out_of_range(Some(r#"[4294967296]"#), None)]
+#[case::named_params(Some(r#"{"network_id": 1}"#), None)]
+#[case::empty(Some(r#"[]"#), None)]
+#[case::no_params(None, None)]
+fn network_label(#[case] params: Option<&str>, #[case] expected: Option<u32>) {
+    assert_eq!(super::metrics::network_label(params), expected);
+}
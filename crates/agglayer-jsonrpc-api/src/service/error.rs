@@ -53,6 +53,9 @@ pub enum SendTxError {
 
     #[error("Settlement failed: {0}")]
     Settlement(SettlementError),
+
+    #[error("The agglayer is read-only and doesn't settle transactions")]
+    ReadOnly,
 }
 
 impl SendTxError {
@@ -50,6 +50,10 @@ where
 
         agglayer_telemetry::SEND_TX.add(1, metrics_attrs);
 
+        if self.kernel.is_read_only() {
+            return Err(SendTxError::ReadOnly);
+        }
+
         let rollup_id = tx.tx.rollup_id;
         if !self.kernel.check_rollup_registered(rollup_id) {
             error!("Rollup {rollup_id} is not registered");
@@ -1,6 +1,32 @@
+mod advance_clock;
+mod api_keys;
+mod cancel_certificate;
+mod compact_storage;
+mod debug_native_execution;
 mod errors;
+mod estimate_certificate;
+mod events;
+mod fuzz;
 mod get_certificate_header;
+mod get_certificate_proof;
+mod get_certificate_statuses;
+mod get_certification_failure;
+mod get_current_epoch_status;
 mod get_epoch_configuration;
 mod get_latest_known_certificate_header;
+mod get_latest_settled_certificate_header;
+mod get_network_roots;
+mod get_networks;
+mod get_orchestrator_state;
+mod get_scrub_report;
+mod get_settled_exit_proof;
+mod get_settlement_costs;
+mod get_signed_certificate_header;
 mod get_tx_status;
+mod get_version;
+mod maintenance;
+mod read_only;
+mod repack_epoch;
 mod send_certificate;
+mod submit_proof;
+mod subscribe_epochs;
@@ -0,0 +1,49 @@
+use std::num::NonZeroU64;
+
+use agglayer_config::{epoch::ManualClockConfig, Epoch};
+use agglayer_types::EpochNumber;
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[test_log::test(tokio::test)]
+async fn manual_clock_ends_the_crossed_epochs() {
+    let mut config = TestContext::get_default_config();
+    config.epoch = Epoch::ManualClock(ManualClockConfig {
+        epoch_duration: NonZeroU64::new(2).unwrap(),
+    });
+
+    let context = TestContext::new_with_config(config).await;
+
+    let ended: Vec<EpochNumber> = context
+        .admin_client
+        .request("admin_advanceClock", rpc_params![1])
+        .await
+        .unwrap();
+    assert_eq!(ended, vec![]);
+
+    let ended: Vec<EpochNumber> = context
+        .admin_client
+        .request("admin_advanceClock", rpc_params![4])
+        .await
+        .unwrap();
+    assert_eq!(ended, vec![EpochNumber::new(0), EpochNumber::new(1)]);
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn other_clocks_are_not_advanced(#[future] context: TestContext) {
+    let res: Result<Vec<EpochNumber>, ClientError> = context
+        .admin_client
+        .request("admin_advanceClock", rpc_params![1])
+        .await;
+
+    let expected_message =
+        "Internal error: AggLayer isn't configured with a ManualClock configuration";
+    assert!(matches!(res, Err(ClientError::Call(obj)) if obj.message() == expected_message));
+}
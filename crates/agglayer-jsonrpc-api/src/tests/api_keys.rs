@@ -0,0 +1,106 @@
+use agglayer_config::ApiKeyConfig;
+use agglayer_rpc::ApiKeyUsageReport;
+use agglayer_types::{Certificate, CertificateSubmissionReceipt, Height, VersionInfo};
+use http::{HeaderMap, HeaderValue};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+
+use crate::{error::code, rpc_middleware::API_KEY_HEADER, testutils::TestContext};
+
+fn client_with_api_key(context: &TestContext, api_key: &str) -> HttpClient {
+    let mut headers = HeaderMap::new();
+    headers.insert(API_KEY_HEADER, HeaderValue::from_str(api_key).unwrap());
+
+    HttpClientBuilder::default()
+        .set_headers(headers)
+        .build(format!("http://{}/", context.api_addr))
+        .unwrap()
+}
+
+fn error_code<T: std::fmt::Debug>(res: Result<T, ClientError>) -> i32 {
+    match res {
+        Err(ClientError::Call(error)) => error.code(),
+        res => panic!("Unexpected result: {res:?}"),
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn requests_are_limited_by_the_api_key_quotas() {
+    let mut config = TestContext::get_default_config();
+    config.rpc.api_keys.required = true;
+    config.rpc.api_keys.keys = [
+        (
+            "chain-a".to_string(),
+            ApiKeyConfig {
+                key: "secret-a".to_string(),
+                max_requests: Some(2),
+                max_certificate_bytes: None,
+            },
+        ),
+        (
+            "chain-b".to_string(),
+            ApiKeyConfig {
+                key: "secret-b".to_string(),
+                max_requests: None,
+                max_certificate_bytes: Some(100),
+            },
+        ),
+    ]
+    .into();
+    let context = TestContext::new_with_config(config).await;
+
+    let res: Result<VersionInfo, _> = context
+        .api_client
+        .request("interop_getVersion", rpc_params![])
+        .await;
+    assert_eq!(error_code(res), code::INVALID_API_KEY);
+
+    let unknown = client_with_api_key(&context, "secret-c");
+    let res: Result<VersionInfo, _> = unknown.request("interop_getVersion", rpc_params![]).await;
+    assert_eq!(error_code(res), code::INVALID_API_KEY);
+
+    let chain_a = client_with_api_key(&context, "secret-a");
+    for _ in 0..2 {
+        let _: VersionInfo = chain_a
+            .request("interop_getVersion", rpc_params![])
+            .await
+            .unwrap();
+    }
+    let res: Result<VersionInfo, _> = chain_a.request("interop_getVersion", rpc_params![]).await;
+    assert_eq!(error_code(res), code::QUOTA_EXCEEDED);
+
+    let chain_b = client_with_api_key(&context, "secret-b");
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+    let res: Result<CertificateSubmissionReceipt, _> = chain_b
+        .request("interop_sendCertificate", rpc_params![certificate])
+        .await;
+    assert_eq!(error_code(res), code::QUOTA_EXCEEDED);
+
+    // Each client only sees the usage of its own key.
+    let report: ApiKeyUsageReport = chain_b
+        .request("interop_getApiKeyUsage", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(report.keys.len(), 1);
+    assert_eq!(report.keys[0].name, "chain-b");
+    assert_eq!(report.keys[0].requests, 2);
+    assert!(report.keys[0].certificate_bytes > 100);
+
+    let report: ApiKeyUsageReport = context
+        .admin_client
+        .request("admin_getApiKeyUsage", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(report.period_end - report.period_start, 24 * 60 * 60);
+    assert_eq!(
+        report
+            .keys
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.requests, entry.max_requests))
+            .collect::<Vec<_>>(),
+        [("chain-a", 3, Some(2)), ("chain-b", 2, None)]
+    );
+}
@@ -0,0 +1,242 @@
+use agglayer_storage::{
+    columns::audit_log_per_certificate::AuditEvent,
+    stores::{
+        PendingCertificateReader as _, PendingCertificateWriter as _, StateReader as _,
+        StateWriter as _,
+    },
+};
+use agglayer_types::{
+    Certificate, CertificateId, CertificateStatus, CertificateStatusError, Height, NetworkId,
+    Signature,
+};
+use alloy::signers::SignerSync as _;
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+fn sign_cancellation(network_id: NetworkId, certificate_id: CertificateId) -> Signature {
+    let signature = Certificate::wallet_for_test(network_id)
+        .sign_hash_sync(&certificate_id.cancellation_commitment().0.into())
+        .unwrap();
+
+    Signature::new(signature.r(), signature.s(), signature.v())
+}
+
+fn insert_pending(context: &TestContext, certificate: &Certificate) {
+    context
+        .pending_store
+        .insert_pending_certificate(certificate.network_id, certificate.height, certificate)
+        .unwrap();
+    context
+        .state_store
+        .insert_certificate_header(certificate, CertificateStatus::Pending)
+        .unwrap();
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn cancels_a_pending_certificate(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    insert_pending(&context, &certificate);
+
+    let _: () = context
+        .api_client
+        .request(
+            "interop_cancelCertificate",
+            rpc_params![
+                certificate_id,
+                sign_cancellation(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert!(context
+        .pending_store
+        .get_certificate(NetworkId::new(1), Height::ZERO)
+        .unwrap()
+        .is_none());
+
+    let cancelled = CertificateStatus::error(CertificateStatusError::Cancelled);
+    let header = context
+        .state_store
+        .get_certificate_header(&certificate_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(header.status, cancelled);
+
+    let audit_log = context.state_store.get_audit_log(&certificate_id).unwrap();
+    assert!(matches!(
+        &audit_log.last().unwrap().event,
+        AuditEvent::StatusChanged { status } if *status == cancelled
+    ));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn rejects_the_signature_of_another_network(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    insert_pending(&context, &certificate);
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_cancelCertificate",
+            rpc_params![
+                certificate_id,
+                sign_cancellation(NetworkId::new(2), certificate_id)
+            ],
+        )
+        .await;
+
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == crate::error::code::SIGNATURE_MISMATCH
+    ));
+    assert!(context
+        .pending_store
+        .get_certificate(NetworkId::new(1), Height::ZERO)
+        .unwrap()
+        .is_some());
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn rejects_a_proven_certificate(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Proven)
+        .unwrap();
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_cancelCertificate",
+            rpc_params![
+                certificate_id,
+                sign_cancellation(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await;
+
+    let expected_message = format!(
+        "Invalid argument: Certificate {certificate_id} is Proven, it can no longer be cancelled"
+    );
+    assert!(
+        matches!(result.unwrap_err(), ClientError::Call(obj) if obj.message() == expected_message)
+    );
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn rejects_an_unknown_certificate(#[future] context: TestContext) {
+    let certificate_id = Certificate::new_for_test(NetworkId::new(1), Height::ZERO).hash();
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_cancelCertificate",
+            rpc_params![
+                certificate_id,
+                sign_cancellation(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await;
+
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == crate::error::code::RESOURCE_NOT_FOUND
+    ));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn rejects_a_certificate_picked_up(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    insert_pending(&context, &certificate);
+
+    // The network task holds the lock of the network from the pickup.
+    let _network_guard = context
+        .orchestrator_state
+        .network_lock(NetworkId::new(1))
+        .try_lock_owned()
+        .unwrap();
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_cancelCertificate",
+            rpc_params![
+                certificate_id,
+                sign_cancellation(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await;
+
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == jsonrpsee::types::error::INVALID_PARAMS_CODE
+    ));
+    assert!(context
+        .pending_store
+        .get_certificate(NetworkId::new(1), Height::ZERO)
+        .unwrap()
+        .is_some());
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn cancellation_races_with_the_pickup(#[future] context: TestContext) {
+    let network_id = NetworkId::new(1);
+
+    for height in 0..20 {
+        let certificate = Certificate::new_for_test(network_id, Height::new(height));
+        let certificate_id = certificate.hash();
+        insert_pending(&context, &certificate);
+
+        let cancel = context.api_client.request::<(), _>(
+            "interop_cancelCertificate",
+            rpc_params![
+                certificate_id,
+                sign_cancellation(network_id, certificate_id)
+            ],
+        );
+
+        // Pick up the certificate the way the network task does, keeping the
+        // lock while the certificate is in the pipeline.
+        let network_lock = context.orchestrator_state.network_lock(network_id);
+        let pickup = async {
+            for _ in 0..height % 4 {
+                tokio::task::yield_now().await;
+            }
+            let guard = network_lock.lock_owned().await;
+            context
+                .pending_store
+                .get_certificate(network_id, Height::new(height))
+                .unwrap()
+                .map(|_| guard)
+        };
+
+        let (cancelled, picked_up) = tokio::join!(cancel, pickup);
+        assert_ne!(
+            cancelled.is_ok(),
+            picked_up.is_some(),
+            "certificate at height {height} both cancelled and picked up, or neither"
+        );
+    }
+}
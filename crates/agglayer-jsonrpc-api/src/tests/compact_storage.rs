@@ -0,0 +1,33 @@
+use agglayer_storage::storage::compactor::CompactionTarget;
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn unknown_columns_are_rejected(#[future] context: TestContext) {
+    let targets: Vec<CompactionTarget> = context
+        .admin_client
+        .request("admin_compactStorage", rpc_params![Vec::<String>::new()])
+        .await
+        .unwrap();
+    assert_eq!(targets, vec![]);
+
+    let res: Result<Vec<CompactionTarget>, _> = context
+        .admin_client
+        .request("admin_compactStorage", rpc_params![vec!["unknown_cf"]])
+        .await;
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+    assert_eq!(
+        error.message(),
+        "Invalid argument: Unknown column family unknown_cf"
+    );
+}
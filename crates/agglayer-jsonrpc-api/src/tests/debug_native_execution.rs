@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use agglayer_storage::{
+    storage::{backup::BackupClient, state_db_cf_definitions, DB},
+    stores::{state::StateStore, StateWriter as _},
+    tests::TempDBDir,
+};
+use agglayer_types::{Certificate, Digest, Height, LocalNetworkStateData, NetworkId};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::{
+    admin::read_snapshot_state,
+    error::Error,
+    testutils::{context, TestContext},
+};
+
+const NETWORK_ID: NetworkId = NetworkId::new(1);
+
+#[test]
+fn reads_the_state_of_a_snapshot() {
+    let snapshots = TempDBDir::new();
+    let mut local_state = LocalNetworkStateData::default();
+    let leaf = Digest([1; 32]);
+    local_state.exit_tree.add_leaf(leaf).unwrap();
+    {
+        let db = DB::open_cf(
+            &snapshots.path.join("before-upgrade"),
+            state_db_cf_definitions(),
+        )
+        .unwrap();
+        StateStore::new(Arc::new(db), BackupClient::noop())
+            .write_local_network_state(&NETWORK_ID, &local_state, &[leaf])
+            .unwrap();
+    }
+
+    let state = read_snapshot_state(&snapshots.path, "before-upgrade", NETWORK_ID).unwrap();
+    assert_eq!(state.get_roots(), local_state.get_roots());
+
+    // The networks unknown to the snapshot start from the empty state.
+    let state = read_snapshot_state(&snapshots.path, "before-upgrade", NetworkId::new(2)).unwrap();
+    assert_eq!(
+        state.get_roots(),
+        LocalNetworkStateData::default().get_roots()
+    );
+}
+
+#[test]
+fn snapshots_are_looked_up_in_the_snapshots_directory_only() {
+    let snapshots = TempDBDir::new();
+
+    for name in ["../state", "/var/lib/agglayer/state", ""] {
+        let error = read_snapshot_state(&snapshots.path, name, NETWORK_ID).unwrap_err();
+        assert!(matches!(error, Error::InvalidArgument(_)), "{name}");
+    }
+
+    let error = read_snapshot_state(&snapshots.path, "unknown", NETWORK_ID).unwrap_err();
+    assert!(matches!(error, Error::ResourceNotFound(_)));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn native_execution_is_an_admin_method(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NETWORK_ID, Height::ZERO);
+
+    let result: Result<serde_json::Value, ClientError> = context
+        .api_client
+        .request(
+            "interop_debugNativeExecution",
+            rpc_params![certificate.clone(), Option::<String>::None],
+        )
+        .await;
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == jsonrpsee::types::error::METHOD_NOT_FOUND_CODE
+    ));
+
+    // The test context has no native executor.
+    let result: Result<serde_json::Value, ClientError> = context
+        .admin_client
+        .request(
+            "admin_debugNativeExecution",
+            rpc_params![certificate, Option::<String>::None],
+        )
+        .await;
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == jsonrpsee::types::error::INTERNAL_ERROR_CODE
+    ));
+}
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use agglayer_rate_limiting::{self, component, Component};
 use agglayer_rpc::error::SignatureVerificationError;
-use agglayer_types::{Address, CertificateId, Digest};
+use agglayer_types::{Address, CertificateId, Digest, Height, NetworkId};
 use alloy::{
     contract::Error as ContractError,
     primitives::{SignatureError as AlloySignatureError, B256},
@@ -147,6 +147,47 @@ type WallClockLimitedInfo = <component::SendTx as Component>::LimitedInfo;
     "cert_notfound",
     agglayer_rpc::CertificateRetrievalError::NotFound { certificate_id: CertificateId::new(Digest([0x51; 32])) }
 )]
+#[case(
+    "cert_height",
+    agglayer_rpc::CertificateSubmissionError::UnexpectedHeight {
+        network_id: NetworkId::new(1),
+        height: Height::new(5),
+        expected_height: Height::new(3),
+    }
+)]
+#[case(
+    "cert_maintenance",
+    agglayer_rpc::CertificateSubmissionError::Maintenance
+)]
+#[case("cert_read_only", agglayer_rpc::CertificateSubmissionError::ReadOnly)]
+#[case(
+    "cert_unknown_network",
+    agglayer_rpc::CertificateSubmissionError::UnknownNetwork {
+        network_id: NetworkId::new(7),
+    }
+)]
+#[case(
+    "cert_signature",
+    agglayer_rpc::CertificateSubmissionError::SignatureError(
+        SignatureVerificationError::SignatureMissing
+    )
+)]
+#[case(
+    "cert_too_large",
+    agglayer_rpc::CertificateSubmissionError::TooManyBridgeExits { count: 3, max: 2 }
+)]
+#[case(
+    "cert_overloaded",
+    agglayer_rpc::CertificateSubmissionError::IntakeOverloaded
+)]
+#[case("quota_api_key", agglayer_rpc::QuotaError::UnknownApiKey)]
+#[case(
+    "quota_requests",
+    agglayer_rpc::QuotaError::RequestsExhausted {
+        name: "chain-a".to_string(),
+        limit: 1000,
+    }
+)]
 fn rpc_error_rendering(#[case] name: &str, #[case] err: impl Into<Error>) {
     let err: Error = err.into();
     let debug_str = format!("{err:?}");
@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use agglayer_certificate_orchestrator::{CertificationError, ProvingCostEstimator};
+use agglayer_types::{Certificate, Height, LocalNetworkStateData, NetworkId, ProvingCostEstimate};
+use jsonrpsee::core::async_trait;
+use tokio::sync::Notify;
+
+use crate::{
+    error::{Error, ValidationError},
+    testutils::TestContext,
+    AgglayerServer,
+};
+
+const ESTIMATE: ProvingCostEstimate = ProvingCostEstimate {
+    cycles: 4_000_000,
+    cost: Some(8_000_000_000_000),
+    proving_time_secs: Some(34),
+};
+
+/// Estimator returning a fixed estimate, or failing when asked to.
+struct FixedEstimator {
+    fail: bool,
+}
+
+#[async_trait]
+impl ProvingCostEstimator for FixedEstimator {
+    async fn estimate_proving_cost(
+        &self,
+        state: LocalNetworkStateData,
+        _certificate: &Certificate,
+    ) -> Result<ProvingCostEstimate, CertificationError> {
+        // Nothing was settled yet for the network.
+        assert_eq!(
+            state.get_roots(),
+            LocalNetworkStateData::default().get_roots()
+        );
+
+        if self.fail {
+            return Err(CertificationError::Sp1ExecuteFailed(eyre::eyre!(
+                "execution failed"
+            )));
+        }
+
+        Ok(ESTIMATE)
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn returns_the_estimate_of_the_certificate() {
+    let raw_rpc = TestContext::new_raw_rpc().await;
+    let rpc = raw_rpc
+        .rpc
+        .with_proving_cost_estimator(Arc::new(FixedEstimator { fail: false }));
+
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let estimate = rpc.estimate_certificate(certificate).await.unwrap();
+
+    assert_eq!(estimate, ESTIMATE);
+}
+
+#[test_log::test(tokio::test)]
+async fn failed_execution_is_a_validation_error() {
+    let raw_rpc = TestContext::new_raw_rpc().await;
+    let rpc = raw_rpc
+        .rpc
+        .with_proving_cost_estimator(Arc::new(FixedEstimator { fail: true }));
+
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let error = rpc.estimate_certificate(certificate).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Validation(ValidationError::DryRun { .. })
+    ));
+}
+
+#[test_log::test(tokio::test)]
+async fn estimation_is_unavailable_without_estimator() {
+    let raw_rpc = TestContext::new_raw_rpc().await;
+
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let error = raw_rpc
+        .rpc
+        .estimate_certificate(certificate)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, Error::Internal(_)));
+}
+
+/// Estimator waiting to be released before returning the fixed estimate.
+struct BlockingEstimator {
+    release: Arc<Notify>,
+}
+
+#[async_trait]
+impl ProvingCostEstimator for BlockingEstimator {
+    async fn estimate_proving_cost(
+        &self,
+        _state: LocalNetworkStateData,
+        _certificate: &Certificate,
+    ) -> Result<ProvingCostEstimate, CertificationError> {
+        self.release.notified().await;
+
+        Ok(ESTIMATE)
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn unsigned_certificate_is_not_estimated() {
+    let raw_rpc = TestContext::new_raw_rpc().await;
+    let rpc = raw_rpc
+        .rpc
+        .with_proving_cost_estimator(Arc::new(FixedEstimator { fail: false }));
+
+    // Signed for another network than the one it is estimated for.
+    let mut certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    certificate.network_id = NetworkId::new(2);
+    let error = rpc.estimate_certificate(certificate).await.unwrap_err();
+
+    assert!(matches!(error, Error::SignatureMismatch { .. }));
+}
+
+#[test_log::test(tokio::test)]
+async fn estimations_beyond_the_workers_are_rejected() {
+    let mut config = TestContext::get_default_config();
+    config.rpc.intake.estimation_workers = 1;
+    let raw_rpc = TestContext::new_raw_rpc_with_config(config).await;
+    let release = Arc::new(Notify::new());
+    let rpc = raw_rpc
+        .rpc
+        .with_proving_cost_estimator(Arc::new(BlockingEstimator {
+            release: release.clone(),
+        }));
+
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let (running, rejected) = tokio::join!(rpc.estimate_certificate(certificate.clone()), async {
+        let rejected = rpc.estimate_certificate(certificate.clone()).await;
+        release.notify_one();
+        rejected
+    });
+
+    assert_eq!(running.unwrap(), ESTIMATE);
+    assert!(matches!(rejected.unwrap_err(), Error::Overloaded));
+}
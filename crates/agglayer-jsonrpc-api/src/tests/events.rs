@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use agglayer_storage::{
+    columns::audit_log_per_certificate::{AuditEvent, SubmissionApi, Submitter},
+    stores::StateWriter as _,
+};
+use agglayer_types::{CertificateId, Digest, EpochEvent, EpochNumber};
+use hyper::StatusCode;
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+/// Fields of a received Server-Sent Event.
+#[derive(Debug)]
+struct ReceivedEvent {
+    id: String,
+    event: String,
+    data: serde_json::Value,
+}
+
+impl ReceivedEvent {
+    fn parse(raw: &str) -> Self {
+        let field = |name: &str| {
+            raw.lines()
+                .find_map(|line| {
+                    let (field, value) = line.split_once(':')?;
+                    (field == name).then(|| value.trim_start().to_string())
+                })
+                .unwrap()
+        };
+
+        Self {
+            id: field("id"),
+            event: field("event"),
+            data: serde_json::from_str(&field("data")).unwrap(),
+        }
+    }
+}
+
+/// Read the stream until it contains the given number of events, skipping
+/// the keep-alive comments.
+async fn read_events(response: &mut reqwest::Response, count: usize) -> Vec<ReceivedEvent> {
+    let mut buffer = String::new();
+    loop {
+        let events = buffer
+            .split_terminator("\n\n")
+            .filter(|event| !event.starts_with(':'))
+            .collect::<Vec<_>>();
+        if buffer.ends_with("\n\n") && events.len() >= count {
+            return events.into_iter().map(ReceivedEvent::parse).collect();
+        }
+
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+}
+
+fn opened(epoch_number: u64) -> EpochEvent {
+    EpochEvent::Opened {
+        epoch_number: EpochNumber::new(epoch_number),
+    }
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn events_are_streamed_from_the_last_event_id(#[future] context: TestContext) {
+    let certificate_id = CertificateId::new(Digest([1; 32]));
+    context.state_store.record_epoch_event(opened(1)).unwrap();
+    context
+        .state_store
+        .record_audit_event(
+            &certificate_id,
+            AuditEvent::Submitted {
+                submitter: Submitter {
+                    api: SubmissionApi::JsonRpc,
+                    address: Some("127.0.0.1:4444".parse().unwrap()),
+                },
+            },
+        )
+        .unwrap();
+
+    let mut response = reqwest::Client::new()
+        .get(format!("http://{}/events", context.api_addr))
+        .header("Last-Event-ID", "0")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let events = read_events(&mut response, 1).await;
+    assert_eq!(events[0].id, "1");
+    assert_eq!(events[0].event, "certificate");
+    assert_eq!(
+        events[0].data["certificate_id"],
+        serde_json::to_value(certificate_id).unwrap()
+    );
+    // The address of the submitter is not streamed.
+    assert_eq!(
+        events[0].data["event"],
+        serde_json::json!({ "Submitted": { "api": "JsonRpc" } })
+    );
+
+    // Events recorded while connected are streamed as they are appended.
+    context.state_store.record_epoch_event(opened(2)).unwrap();
+
+    let events = read_events(&mut response, 1).await;
+    assert_eq!(events[0].id, "2");
+    assert_eq!(events[0].event, "epoch");
+    assert_eq!(
+        events[0].data["event"],
+        serde_json::to_value(opened(2)).unwrap()
+    );
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn only_new_events_are_streamed_without_last_event_id(#[future] context: TestContext) {
+    context.state_store.record_epoch_event(opened(1)).unwrap();
+
+    let mut response = reqwest::Client::new()
+        .get(format!("http://{}/events", context.api_addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    context.state_store.record_epoch_event(opened(2)).unwrap();
+
+    let events = read_events(&mut response, 1).await;
+    assert_eq!(events[0].id, "1");
+    assert_eq!(
+        events[0].data["event"],
+        serde_json::to_value(opened(2)).unwrap()
+    );
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn invalid_last_event_id_is_rejected(#[future] context: TestContext) {
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/events", context.api_addr))
+        .header("Last-Event-ID", "latest")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,114 @@
+//! Fuzzers making sure that malformed submissions are rejected instead of
+//! panicking the node.
+//!
+//! Each fuzzer runs as a regular test, and can be run as a fuzz target with
+//! `cargo bolero test -p agglayer-jsonrpc-api <fuzzer name>`, or along with
+//! the other fuzzers with `scripts/fuzz.sh`, which seeds their corpus with the
+//! sample data.
+
+use std::path::Path;
+
+use agglayer_types::{Certificate, CertificateId, NetworkId, Proof};
+use jsonrpsee::types::Params;
+use pessimistic_proof_test_suite::sample_data;
+
+/// Parse the parameters of a method taking a single argument, the same way
+/// the generated server code does.
+fn parse_single_param<T: serde::de::DeserializeOwned>(raw: &str) {
+    let mut params = Params::new(Some(raw)).sequence();
+    let _ = params.next::<T>();
+}
+
+macro_rules! make_param_fuzzers {
+    ($($test:ident => $type:ty),* $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                bolero::check!().for_each(|bytes: &[u8]| {
+                    let _ = serde_json::from_slice::<$type>(bytes);
+
+                    if let Ok(raw) = std::str::from_utf8(bytes) {
+                        parse_single_param::<$type>(raw);
+                    }
+                })
+            }
+        )*
+    };
+}
+
+make_param_fuzzers!(
+    fuzz_parse_certificate => Certificate,
+    fuzz_parse_certificate_id => CertificateId,
+    fuzz_parse_network_id => NetworkId,
+    fuzz_parse_proof => Proof,
+);
+
+/// Certificates of the sample data.
+fn sample_certificates() -> Vec<Certificate> {
+    [
+        "cert_h0.json",
+        "cert_h1.json",
+        "cert_h2.json",
+        "n15-cert_h0.json",
+    ]
+    .into_iter()
+    .map(sample_data::load_certificate)
+    .collect()
+}
+
+/// JSON-RPC parameters built from the sample certificates, used as seeds for
+/// the corruption test.
+fn seeds() -> Vec<String> {
+    sample_certificates()
+        .iter()
+        .map(|certificate| serde_json::to_string(&[certificate]).expect("valid certificate"))
+        .collect()
+}
+
+/// Write the sample certificates as the corpus of the certificate parsing
+/// fuzzer, under `$FUZZ_CORPUS_DIR/fuzz_parse_certificate`, both as a value
+/// and as the parameters of a request. Run by `scripts/fuzz.sh` before
+/// fuzzing.
+#[test]
+#[ignore = "writes the fuzzing corpus, run by scripts/fuzz.sh"]
+fn write_fuzz_corpus() {
+    let dir = std::env::var("FUZZ_CORPUS_DIR").expect("FUZZ_CORPUS_DIR is set");
+    let dir = Path::new(&dir).join("fuzz_parse_certificate");
+    std::fs::create_dir_all(&dir).expect("corpus directory created");
+
+    for (index, certificate) in sample_certificates().iter().enumerate() {
+        let value = serde_json::to_string(certificate).expect("valid certificate");
+        std::fs::write(dir.join(format!("sample-{index}")), value).expect("seed written");
+    }
+    for (index, params) in seeds().into_iter().enumerate() {
+        std::fs::write(dir.join(format!("sample-params-{index}")), params).expect("seed written");
+    }
+}
+
+/// Maximum number of truncations and corruptions derived from each seed.
+const MAX_CORRUPTIONS: usize = 512;
+
+#[test]
+fn corrupted_certificate_params_do_not_panic() {
+    for seed in seeds() {
+        let bytes = seed.as_bytes();
+        let step = bytes.len() / MAX_CORRUPTIONS + 1;
+
+        for len in (0..bytes.len()).step_by(step) {
+            if let Ok(raw) = std::str::from_utf8(&bytes[..len]) {
+                parse_single_param::<Certificate>(raw);
+            }
+        }
+
+        for index in (0..bytes.len()).step_by(step) {
+            for replacement in [b'0', b'"', b'{', b']', b'-'] {
+                let mut corrupted = bytes.to_vec();
+                corrupted[index] = replacement;
+
+                if let Ok(raw) = std::str::from_utf8(&corrupted) {
+                    parse_single_param::<Certificate>(raw);
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use agglayer_storage::stores::StateWriter as _;
 use agglayer_types::{
-    Certificate, CertificateHeader, CertificateId, CertificateStatus, CertificateStatusError,
-    Digest, Height,
+    Certificate, CertificateHeader, CertificateHeaderDetails, CertificateId, CertificateStatus,
+    CertificateStatusError, CertificateSubmissionReceipt, Digest, Height, SettlementTxHash,
 };
+use alloy::providers::{mock::Asserter, ProviderBuilder};
 use insta::assert_snapshot;
 use jsonrpsee::{
     core::{client::ClientT, ClientError},
@@ -40,9 +41,13 @@ async fn fetch_known_certificate_header(#[future] mut context: TestContext) {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -66,9 +71,13 @@ async fn get_certificate_header_after_sending_the_certificate(#[future] mut cont
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -93,6 +102,87 @@ async fn get_certificate_header_after_sending_the_certificate(#[future] mut cont
     assert!(matches!(error, ClientError::Call(obj) if obj.message() == expected_message));
 }
 
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn settled_certificate_header_details() {
+    let asserter = Asserter::new();
+    let provider = ProviderBuilder::new().on_mocked_client(asserter.clone());
+    let context = TestContext::new_with_provider(TestContext::get_default_config(), provider).await;
+
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+    let id = certificate.hash();
+    let settlement_tx_hash = SettlementTxHash::from(Digest::from([1; 32]));
+
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Settled)
+        .unwrap();
+    context
+        .state_store
+        .update_settlement_tx_hash(&id, settlement_tx_hash, false)
+        .unwrap();
+
+    // Receipt of the settlement transaction, mined in the block 100.
+    asserter.push_success(&json!({
+        "transactionHash": Digest::from(settlement_tx_hash),
+        "transactionIndex": "0x0",
+        "blockHash": Digest([2; 32]),
+        "blockNumber": "0x64",
+        "from": "0x0000000000000000000000000000000000000001",
+        "to": "0x0000000000000000000000000000000000000002",
+        "cumulativeGasUsed": "0x5208",
+        "gasUsed": "0x5208",
+        "effectiveGasPrice": "0x1",
+        "contractAddress": null,
+        "logs": [],
+        "logsBloom": format!("0x{}", "0".repeat(512)),
+        "status": "0x1",
+        "type": "0x2",
+    }));
+    // Current L1 block.
+    asserter.push_success(&"0x69");
+
+    let payload: CertificateHeaderDetails = context
+        .api_client
+        .request("interop_getCertificateHeader", rpc_params![id])
+        .await
+        .unwrap();
+
+    assert_eq!(payload.header.certificate_id, id);
+    assert_eq!(payload.header.settlement_tx_hash, Some(settlement_tx_hash));
+    assert_eq!(payload.l1_block_number, Some(100));
+    assert_eq!(payload.confirmations, Some(6));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn certificate_header_without_l1(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+    let id = certificate.hash();
+
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Settled)
+        .unwrap();
+    context
+        .state_store
+        .update_settlement_tx_hash(&id, SettlementTxHash::from(Digest::from([1; 32])), false)
+        .unwrap();
+
+    // The mocked L1 has no response, the header is served without the L1
+    // details.
+    let payload: CertificateHeaderDetails = context
+        .api_client
+        .request("interop_getCertificateHeader", rpc_params![id])
+        .await
+        .unwrap();
+
+    assert_eq!(payload.header.certificate_id, id);
+    assert_eq!(payload.l1_block_number, None);
+    assert_eq!(payload.confirmations, None);
+}
+
 #[rstest]
 #[awt]
 #[test_log::test(tokio::test)]
@@ -199,12 +289,16 @@ async fn debug_fetch_known_certificate() {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
         .inspect_err(|e| {
             eprintln!("Error interop_sendCertificate: {e:?}");
         })
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -235,9 +329,13 @@ async fn debug_get_certificate_after_sending_the_certificate() {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -278,9 +376,13 @@ async fn debug_get_certificate_after_overwrite() {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -311,9 +413,13 @@ async fn debug_get_certificate_after_overwrite() {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id2, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -358,9 +464,13 @@ async fn debug_get_certificate_after_overwrite_with_debug_false() {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
@@ -391,9 +501,13 @@ async fn debug_get_certificate_after_overwrite_with_debug_false() {
 
     let res: CertificateId = context
         .api_client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     assert_eq!(id2, res);
     assert!(context.certificate_receiver.try_recv().is_ok());
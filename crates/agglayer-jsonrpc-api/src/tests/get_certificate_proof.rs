@@ -0,0 +1,83 @@
+use agglayer_storage::{
+    columns::Codec as _,
+    stores::{PendingCertificateWriter as _, StateWriter as _},
+};
+use agglayer_types::{Certificate, CertificateId, CertificateStatus, Height, NetworkId, Proof};
+use alloy::primitives::Bytes;
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn returns_the_proof(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Proven)
+        .unwrap();
+    context
+        .pending_store
+        .insert_generated_proof(&certificate_id, &Proof::dummy())
+        .unwrap();
+
+    let proof: Option<Bytes> = context
+        .api_client
+        .request("interop_getCertificateProof", rpc_params![certificate_id])
+        .await
+        .unwrap();
+
+    let proof = proof.expect("The proof has been generated");
+    assert!(!proof.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]));
+    let proof: Proof = agglayer_types::bincode::default()
+        .deserialize(&proof)
+        .unwrap();
+    assert!(matches!(proof, Proof::SP1(_)));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn returns_the_compressed_proof_on_request(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Proven)
+        .unwrap();
+    context
+        .pending_store
+        .insert_generated_proof(&certificate_id, &Proof::dummy())
+        .unwrap();
+
+    let proof: Option<Bytes> = context
+        .api_client
+        .request(
+            "interop_getCertificateProof",
+            rpc_params![certificate_id, "zstd"],
+        )
+        .await
+        .unwrap();
+
+    let proof = proof.expect("The proof has been generated");
+    assert!(proof.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]));
+    assert!(matches!(Proof::decode(&proof).unwrap(), Proof::SP1(_)));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn returns_null_without_proof(#[future] context: TestContext) {
+    let certificate_id = CertificateId::new([0xff; 32].into());
+
+    let proof: Option<Bytes> = context
+        .api_client
+        .request("interop_getCertificateProof", rpc_params![certificate_id])
+        .await
+        .unwrap();
+
+    assert!(proof.is_none());
+}
@@ -0,0 +1,72 @@
+use agglayer_rpc::MAX_CERTIFICATE_STATUSES_PER_REQUEST;
+use agglayer_storage::stores::StateWriter as _;
+use agglayer_types::{Certificate, CertificateId, CertificateStatus, Height, NetworkId};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn returns_the_statuses_in_request_order(#[future] context: TestContext) {
+    let settled = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let proven = Certificate::new_for_test(NetworkId::new(2), Height::ZERO);
+
+    for (certificate, status) in [
+        (&settled, CertificateStatus::Settled),
+        (&proven, CertificateStatus::Proven),
+    ] {
+        context
+            .state_store
+            .insert_certificate_header(certificate, status)
+            .unwrap();
+    }
+
+    let unknown = CertificateId::new([0xff; 32].into());
+    let statuses: Vec<Option<CertificateStatus>> = context
+        .api_client
+        .request(
+            "interop_getCertificateStatuses",
+            rpc_params![vec![proven.hash(), unknown, settled.hash()]],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        statuses,
+        vec![
+            Some(CertificateStatus::Proven),
+            None,
+            Some(CertificateStatus::Settled)
+        ]
+    );
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reject_too_many_certificates(#[future] context: TestContext) {
+    let certificate_ids =
+        vec![CertificateId::new([0; 32].into()); MAX_CERTIFICATE_STATUSES_PER_REQUEST + 1];
+
+    let payload: Result<Vec<Option<CertificateStatus>>, ClientError> = context
+        .api_client
+        .request(
+            "interop_getCertificateStatuses",
+            rpc_params![certificate_ids],
+        )
+        .await;
+
+    let error = payload.unwrap_err();
+
+    let expected_message = format!(
+        "Invalid argument: Too many certificates requested: {}, the maximum is {}",
+        MAX_CERTIFICATE_STATUSES_PER_REQUEST + 1,
+        MAX_CERTIFICATE_STATUSES_PER_REQUEST
+    );
+    assert!(matches!(error, ClientError::Call(obj) if obj.message() == expected_message));
+}
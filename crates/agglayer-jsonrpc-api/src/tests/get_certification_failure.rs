@@ -0,0 +1,49 @@
+use agglayer_storage::{
+    columns::certification_failure_per_certificate::CertificationFailure, stores::DebugWriter as _,
+};
+use agglayer_types::{Certificate, Height};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::TestContext;
+
+#[rstest]
+#[test_log::test(tokio::test)]
+async fn recorded_failure_is_returned() {
+    let mut config = TestContext::get_default_config();
+    config.debug_mode = true;
+
+    let context = TestContext::new_with_config(config).await;
+
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+    let certificate_id = certificate.hash();
+
+    let res: Result<(Certificate, CertificationFailure), _> = context
+        .admin_client
+        .request("admin_getCertificationFailure", rpc_params![certificate_id])
+        .await;
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(
+        error.message(),
+        format!("Resource not found: CertificationFailure({certificate_id})")
+    );
+
+    let failure = CertificationFailure::new(Default::default(), "Native execution failed".into());
+    context
+        .debug_store
+        .add_certification_failure(&certificate, &failure)
+        .unwrap();
+
+    let (returned_certificate, returned_failure): (Certificate, CertificationFailure) = context
+        .admin_client
+        .request("admin_getCertificationFailure", rpc_params![certificate_id])
+        .await
+        .unwrap();
+    assert_eq!(returned_certificate, certificate);
+    assert_eq!(returned_failure, failure);
+}
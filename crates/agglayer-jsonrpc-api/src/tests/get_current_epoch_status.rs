@@ -0,0 +1,84 @@
+use std::{
+    num::NonZeroU64,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use agglayer_config::{epoch::BlockClockConfig, Epoch};
+use agglayer_storage::stores::{PendingCertificateWriter as _, StateWriter as _};
+use agglayer_types::{Certificate, CertificateIndex, EpochNumber, EpochStatus, Height, NetworkId};
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+fn settle(context: &TestContext, network_id: NetworkId, epoch_number: EpochNumber) {
+    let certificate = Certificate::new_for_test(network_id, Height::ZERO);
+    context
+        .state_store
+        .set_latest_settled_certificate_for_network(
+            &certificate.network_id,
+            &certificate.height,
+            &certificate.hash(),
+            &epoch_number,
+            &CertificateIndex::ZERO,
+        )
+        .unwrap();
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn current_epoch_status(#[future] context: TestContext) {
+    context.clock_ref.update_block_height(3);
+
+    settle(&context, NetworkId::new(1), EpochNumber::new(3));
+    settle(&context, NetworkId::new(2), EpochNumber::new(1));
+
+    let pending = Certificate::new_for_test(NetworkId::new(3), Height::ZERO);
+    context
+        .pending_store
+        .insert_pending_certificate(pending.network_id, pending.height, &pending)
+        .unwrap();
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let status: EpochStatus = context
+        .api_client
+        .request("interop_getCurrentEpochStatus", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(status.epoch_number, EpochNumber::new(3));
+    assert_eq!(status.start_block, 3);
+    assert_eq!(status.end_block, 4);
+    assert_eq!(status.remaining_blocks, 1);
+    assert!(status.expected_close_time.unwrap() >= now);
+    assert_eq!(status.settled_networks, 1);
+    assert_eq!(status.remaining_capacity, 2);
+}
+
+#[test_log::test(tokio::test)]
+async fn current_epoch_status_from_genesis_block() {
+    let mut config = TestContext::get_default_config();
+    config.epoch = Epoch::BlockClock(BlockClockConfig {
+        epoch_duration: NonZeroU64::new(1).unwrap(),
+        genesis_block: 100,
+    });
+
+    let context = TestContext::new_with_config(config).await;
+    context.clock_ref.update_block_height(5);
+
+    let status: EpochStatus = context
+        .api_client
+        .request("interop_getCurrentEpochStatus", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(status.epoch_number, EpochNumber::new(5));
+    assert_eq!(status.start_block, 105);
+    assert_eq!(status.end_block, 106);
+    assert_eq!(status.settled_networks, 0);
+    assert_eq!(status.remaining_capacity, 0);
+}
@@ -0,0 +1,76 @@
+use agglayer_config::Config;
+use agglayer_storage::{stores::StateWriter, tests::TempDBDir};
+use agglayer_types::{
+    Certificate, CertificateHeader, CertificateIndex, CertificateStatus, EpochNumber, Height,
+    NetworkId,
+};
+use jsonrpsee::{core::client::ClientT, rpc_params};
+
+use crate::testutils::TestContext;
+
+#[test_log::test(tokio::test)]
+async fn returns_the_latest_settled_certificate_header() {
+    let tmp = TempDBDir::new();
+    let config = Config::new(&tmp.path);
+    let context = TestContext::new_with_config(config).await;
+
+    let network_id = 1.into();
+
+    let previous_certificate = Certificate::new_for_test(network_id, Height::ZERO);
+    let settled_certificate = Certificate::new_for_test(network_id, Height::new(1));
+    let proven_certificate = Certificate::new_for_test(network_id, Height::new(2));
+
+    for (certificate, status) in [
+        (&previous_certificate, CertificateStatus::Settled),
+        (&settled_certificate, CertificateStatus::Settled),
+        (&proven_certificate, CertificateStatus::Proven),
+    ] {
+        context
+            .state_store
+            .insert_certificate_header(certificate, status)
+            .expect("unable to insert certificate header");
+    }
+
+    context
+        .state_store
+        .set_latest_settled_certificate_for_network(
+            &network_id,
+            &settled_certificate.height,
+            &settled_certificate.hash(),
+            &EpochNumber::ZERO,
+            &CertificateIndex::ZERO,
+        )
+        .expect("unable to set latest settled certificate");
+
+    let payload: Option<CertificateHeader> = context
+        .api_client
+        .request(
+            "interop_getLatestSettledCertificateHeader",
+            rpc_params![network_id],
+        )
+        .await
+        .unwrap();
+
+    let header = payload.expect("the settled certificate header");
+    assert_eq!(header.certificate_id, settled_certificate.hash());
+    assert_eq!(header.height, Height::new(1));
+    assert_eq!(header.status, CertificateStatus::Settled);
+}
+
+#[test_log::test(tokio::test)]
+async fn returns_none_without_settled_certificate() {
+    let tmp = TempDBDir::new();
+    let config = Config::new(&tmp.path);
+    let context = TestContext::new_with_config(config).await;
+
+    let payload: Option<CertificateHeader> = context
+        .api_client
+        .request(
+            "interop_getLatestSettledCertificateHeader",
+            rpc_params![NetworkId::new(1)],
+        )
+        .await
+        .unwrap();
+
+    assert!(payload.is_none());
+}
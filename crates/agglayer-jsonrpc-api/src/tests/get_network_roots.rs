@@ -0,0 +1,114 @@
+use agglayer_storage::stores::StateWriter as _;
+use agglayer_types::{
+    CertificateId, CertificateIndex, Digest, EpochNumber, Height, LocalNetworkStateData, NetworkId,
+    NetworkRoots,
+};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+const NETWORK_ID: NetworkId = NetworkId::new(1);
+
+/// Settle one certificate per state, each adding a bridge exit, returning the
+/// settled states.
+fn settle_states(context: &TestContext, count: u8) -> Vec<LocalNetworkStateData> {
+    let mut local_state = LocalNetworkStateData::default();
+
+    (0..count)
+        .map(|height| {
+            let leaf = Digest([height; 32]);
+            local_state.exit_tree.add_leaf(leaf).unwrap();
+            context
+                .state_store
+                .write_local_network_state(&NETWORK_ID, &local_state, &[leaf])
+                .unwrap();
+            context
+                .state_store
+                .set_latest_settled_certificate_for_network(
+                    &NETWORK_ID,
+                    &Height::new(height.into()),
+                    &CertificateId::new([height; 32].into()),
+                    &EpochNumber::ZERO,
+                    &CertificateIndex::ZERO,
+                )
+                .unwrap();
+
+            local_state.clone()
+        })
+        .collect()
+}
+
+fn assert_roots(roots: &NetworkRoots, height: u64, state: &LocalNetworkStateData) {
+    let expected = state.get_roots();
+
+    assert_eq!(roots.network_id, NETWORK_ID);
+    assert_eq!(roots.height, Height::new(height));
+    assert_eq!(roots.exit_root, expected.exit_root);
+    assert_eq!(roots.ler_leaf_count, expected.ler_leaf_count);
+    assert_eq!(roots.balance_root, expected.balance_root);
+    assert_eq!(roots.nullifier_root, expected.nullifier_root);
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn returns_the_roots_at_the_settled_heights(#[future] context: TestContext) {
+    let states = settle_states(&context, 2);
+
+    let latest: NetworkRoots = context
+        .api_client
+        .request("interop_getNetworkRoots", rpc_params![NETWORK_ID])
+        .await
+        .unwrap();
+    assert_roots(&latest, 1, &states[1]);
+
+    let first: NetworkRoots = context
+        .api_client
+        .request(
+            "interop_getNetworkRoots",
+            rpc_params![NETWORK_ID, Height::ZERO],
+        )
+        .await
+        .unwrap();
+    assert_roots(&first, 0, &states[0]);
+    assert_ne!(first.exit_root, latest.exit_root);
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn rejects_unsettled_heights(#[future] context: TestContext) {
+    settle_states(&context, 1);
+
+    let res: Result<NetworkRoots, _> = context
+        .api_client
+        .request(
+            "interop_getNetworkRoots",
+            rpc_params![NETWORK_ID, Height::new(1)],
+        )
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn unsettled_network_has_no_roots(#[future] context: TestContext) {
+    let res: Result<NetworkRoots, _> = context
+        .api_client
+        .request("interop_getNetworkRoots", rpc_params![NETWORK_ID])
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::RESOURCE_NOT_FOUND);
+}
@@ -0,0 +1,76 @@
+use agglayer_storage::stores::{PendingCertificateWriter as _, StateWriter as _};
+use agglayer_types::{
+    Certificate, CertificateIndex, CertificateStatus, EpochNumber, Height, NetworkId,
+    NetworkSummary, NetworkType,
+};
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn lists_the_settled_and_pending_networks(#[future] context: TestContext) {
+    let networks: Vec<NetworkSummary> = context
+        .api_client
+        .request("interop_getNetworks", rpc_params![])
+        .await
+        .unwrap();
+    assert!(networks.is_empty());
+
+    let settled = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    context
+        .state_store
+        .insert_certificate_header(&settled, CertificateStatus::Settled)
+        .unwrap();
+    context
+        .state_store
+        .set_latest_settled_certificate_for_network(
+            &settled.network_id,
+            &settled.height,
+            &settled.hash(),
+            &EpochNumber::ZERO,
+            &CertificateIndex::ZERO,
+        )
+        .unwrap();
+
+    let pending = Certificate::new_for_test(NetworkId::new(2), Height::new(1));
+    context
+        .state_store
+        .insert_certificate_header(&pending, CertificateStatus::Pending)
+        .unwrap();
+    context
+        .pending_store
+        .insert_pending_certificate(pending.network_id, pending.height, &pending)
+        .unwrap();
+    context
+        .pending_store
+        .set_latest_pending_certificate_per_network(
+            &pending.network_id,
+            &pending.height,
+            &pending.hash(),
+        )
+        .unwrap();
+
+    let networks: Vec<NetworkSummary> = context
+        .api_client
+        .request("interop_getNetworks", rpc_params![])
+        .await
+        .unwrap();
+
+    let [settled_network, pending_network] = networks.as_slice() else {
+        panic!("Unexpected networks: {networks:?}");
+    };
+
+    assert_eq!(settled_network.network_id, NetworkId::new(1));
+    assert_eq!(settled_network.network_type, NetworkType::Unspecified);
+    assert_eq!(settled_network.settled_height, Some(Height::ZERO));
+    assert_eq!(settled_network.latest_pending_height, None);
+    assert!(settled_network.latest_activity.is_some());
+
+    assert_eq!(pending_network.network_id, NetworkId::new(2));
+    assert_eq!(pending_network.settled_height, None);
+    assert_eq!(pending_network.latest_pending_height, Some(Height::new(1)));
+    assert!(pending_network.latest_activity.is_some());
+}
@@ -0,0 +1,22 @@
+use agglayer_certificate_orchestrator::OrchestratorSnapshot;
+use agglayer_types::EpochNumber;
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn orchestrator_state_is_dumped(#[future] context: TestContext) {
+    let snapshot: OrchestratorSnapshot = context
+        .admin_client
+        .request("admin_getOrchestratorState", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot, context.orchestrator_state.snapshot());
+    assert_eq!(snapshot.clock.current_epoch, EpochNumber::ZERO);
+    assert_eq!(snapshot.epoch, None);
+    assert!(snapshot.networks.is_empty());
+}
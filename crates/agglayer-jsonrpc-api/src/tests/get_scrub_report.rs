@@ -0,0 +1,18 @@
+use agglayer_storage::storage::scrubber::ScrubReport;
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn scrub_report_is_empty_until_scrubbed(#[future] context: TestContext) {
+    let report: ScrubReport = context
+        .admin_client
+        .request("admin_getScrubReport", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(report, ScrubReport::default());
+}
@@ -0,0 +1,106 @@
+use agglayer_storage::stores::StateWriter as _;
+use agglayer_types::{
+    Certificate, CertificateIndex, CertificateStatus, Digest, EpochNumber, Height,
+    LocalNetworkStateData, NetworkId, SettledExitProof,
+};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+const NETWORK_ID: NetworkId = NetworkId::new(1);
+
+/// Settle a certificate of the network whose local exit tree holds the given
+/// leaves, returning the settled local exit root.
+fn settle_exit_tree(context: &TestContext, leaves: &[Digest]) -> Digest {
+    let mut local_state = LocalNetworkStateData::default();
+    for leaf in leaves {
+        local_state.exit_tree.add_leaf(*leaf).unwrap();
+    }
+    let root = local_state.exit_tree.get_root();
+
+    let mut certificate = Certificate::new_for_test(NETWORK_ID, Height::ZERO);
+    certificate.new_local_exit_root = root.into();
+
+    context
+        .state_store
+        .write_local_network_state(&NETWORK_ID, &local_state, leaves)
+        .unwrap();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Settled)
+        .unwrap();
+    context
+        .state_store
+        .set_latest_settled_certificate_for_network(
+            &NETWORK_ID,
+            &Height::ZERO,
+            &certificate.hash(),
+            &EpochNumber::ZERO,
+            &CertificateIndex::ZERO,
+        )
+        .unwrap();
+
+    root
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn proves_the_settled_leaves(#[future] context: TestContext) {
+    let leaves = (0..5u8).map(|i| Digest([i; 32])).collect::<Vec<_>>();
+    let root = settle_exit_tree(&context, &leaves);
+
+    for (leaf_index, leaf) in leaves.iter().enumerate() {
+        let exit_proof: SettledExitProof = context
+            .api_client
+            .request(
+                "interop_getSettledExitProof",
+                rpc_params![NETWORK_ID, leaf_index],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(exit_proof.network_id, NETWORK_ID);
+        assert_eq!(exit_proof.settled_height, Height::ZERO);
+        assert_eq!(exit_proof.local_exit_root, root.into());
+        assert_eq!(exit_proof.leaf_count, 5);
+        assert_eq!(exit_proof.leaf, *leaf);
+        assert!(exit_proof.proof.verify(*leaf, leaf_index as u32, root));
+    }
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn rejects_leaves_out_of_the_settled_tree(#[future] context: TestContext) {
+    settle_exit_tree(&context, &[Digest([1; 32])]);
+
+    let res: Result<SettledExitProof, _> = context
+        .api_client
+        .request("interop_getSettledExitProof", rpc_params![NETWORK_ID, 1])
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn unsettled_network_has_no_proof(#[future] context: TestContext) {
+    let res: Result<SettledExitProof, _> = context
+        .api_client
+        .request("interop_getSettledExitProof", rpc_params![NETWORK_ID, 0])
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::RESOURCE_NOT_FOUND);
+}
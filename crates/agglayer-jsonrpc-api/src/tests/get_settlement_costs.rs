@@ -0,0 +1,75 @@
+use agglayer_storage::stores::StateWriter as _;
+use agglayer_types::{EpochNumber, NetworkId, SettlementCosts, SettlementCostsReport};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn report_settlement_costs_over_epoch_range(#[future] context: TestContext) {
+    let network_id = NetworkId::new(1);
+    let epoch = EpochNumber::new;
+
+    for (epoch_number, gas_used, fees) in [(1, 100, 1_000), (1, 200, 2_000), (4, 300, 3_000)] {
+        context
+            .state_store
+            .record_settlement_cost(network_id, epoch(epoch_number), gas_used, fees)
+            .unwrap();
+    }
+
+    let report: SettlementCostsReport = context
+        .api_client
+        .request(
+            "interop_getSettlementCosts",
+            rpc_params![network_id, epoch(0), epoch(3)],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.epochs.len(), 1);
+    assert_eq!(report.epochs[0].epoch_number, epoch(1));
+    assert_eq!(
+        report.total,
+        SettlementCosts {
+            settled_certificates: 2,
+            gas_used: 300,
+            fees: 3_000,
+        }
+    );
+
+    let report: SettlementCostsReport = context
+        .api_client
+        .request(
+            "interop_getSettlementCosts",
+            rpc_params![network_id, epoch(0), epoch(10)],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(report.epochs.len(), 2);
+    assert_eq!(report.total.settled_certificates, 3);
+    assert_eq!(report.total.fees, 6_000);
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reject_inverted_epoch_range(#[future] context: TestContext) {
+    let payload: Result<SettlementCostsReport, ClientError> = context
+        .api_client
+        .request(
+            "interop_getSettlementCosts",
+            rpc_params![NetworkId::new(1), EpochNumber::new(3), EpochNumber::new(1)],
+        )
+        .await;
+
+    let error = payload.unwrap_err();
+
+    let expected_message = "Invalid argument: Invalid epoch range: 3 is after 1";
+    assert!(matches!(error, ClientError::Call(obj) if obj.message() == expected_message));
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use agglayer_signer::ConfiguredSigner;
+use agglayer_storage::stores::StateWriter as _;
+use agglayer_types::{Certificate, CertificateStatus, Height, NetworkId};
+
+use crate::{error::Error, testutils::TestContext, AgglayerServer};
+
+#[test_log::test(tokio::test)]
+async fn header_is_signed_by_the_agglayer() {
+    let raw_rpc = TestContext::new_raw_rpc().await;
+    let wallet = Certificate::wallet_for_test(NetworkId::ETH_L1);
+    let address = wallet.address();
+    let rpc = raw_rpc
+        .rpc
+        .with_response_signer(Arc::new(ConfiguredSigner::from_local(wallet)));
+
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    raw_rpc
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Settled)
+        .unwrap();
+
+    let signed = rpc
+        .get_signed_certificate_header(certificate.hash())
+        .await
+        .unwrap();
+
+    assert_eq!(signed.header.certificate_id, certificate.hash());
+    assert_eq!(signed.header.status, CertificateStatus::Settled);
+    assert_eq!(signed.recover_signer().unwrap(), address.into());
+}
+
+#[test_log::test(tokio::test)]
+async fn signing_is_unavailable_without_signer() {
+    let raw_rpc = TestContext::new_raw_rpc().await;
+
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    raw_rpc
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Pending)
+        .unwrap();
+
+    let error = raw_rpc
+        .rpc
+        .get_signed_certificate_header(certificate.hash())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, Error::Internal(_)));
+}
@@ -0,0 +1,36 @@
+use agglayer_types::{BuildInfo, Digest, VersionInfo};
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reports_the_running_version(#[future] context: TestContext) {
+    let version: serde_json::Value = context
+        .api_client
+        .request("interop_getVersion", rpc_params![])
+        .await
+        .unwrap();
+
+    // The build information is flattened next to the pessimistic proof one.
+    let mut keys = version.as_object().unwrap().keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(
+        keys,
+        [
+            "features",
+            "git_commit",
+            "pp_program_version",
+            "pp_vkey_hash",
+            "version"
+        ]
+    );
+
+    let version: VersionInfo = serde_json::from_value(version).unwrap();
+    assert_eq!(
+        version,
+        VersionInfo::new(BuildInfo::default(), Digest::default())
+    );
+}
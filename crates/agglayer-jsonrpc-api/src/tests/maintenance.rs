@@ -0,0 +1,92 @@
+use agglayer_rpc::{MaintenanceState, MaintenanceStatus};
+use agglayer_types::{Certificate, CertificateId, CertificateSubmissionReceipt, Height, NetworkId};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+
+use crate::testutils::TestContext;
+
+#[test_log::test(tokio::test)]
+async fn certificates_are_rejected_during_maintenance() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let mut context = TestContext::new_with_config(config).await;
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+
+    let state: MaintenanceState = context
+        .admin_client
+        .request("admin_enterMaintenance", rpc_params![false])
+        .await
+        .unwrap();
+    assert_eq!(
+        state,
+        MaintenanceState {
+            status: MaintenanceStatus::Draining,
+            exit_when_ready: false,
+        }
+    );
+    assert_eq!(context.maintenance.state(), state);
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .await;
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::MAINTENANCE);
+    assert!(context.certificate_receiver.try_recv().is_err());
+
+    let state: MaintenanceState = context
+        .admin_client
+        .request("admin_leaveMaintenance", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(state.status, MaintenanceStatus::Inactive);
+
+    let cert_id: CertificateId = context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
+        .await
+        .unwrap()
+        .certificate_id;
+    assert_eq!(context.certificate_receiver.try_recv().unwrap().2, cert_id);
+}
+
+#[test_log::test(tokio::test)]
+async fn maintenance_state_is_reported() {
+    let context = TestContext::new_with_config(TestContext::get_default_config()).await;
+
+    let state: MaintenanceState = context
+        .admin_client
+        .request("admin_getMaintenanceState", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(state, MaintenanceState::default());
+
+    context.maintenance.enter(true);
+    assert_eq!(
+        context.maintenance.set_ready(),
+        Some(MaintenanceState {
+            status: MaintenanceStatus::Ready,
+            exit_when_ready: true,
+        })
+    );
+
+    let state: MaintenanceState = context
+        .admin_client
+        .request("admin_getMaintenanceState", rpc_params![])
+        .await
+        .unwrap();
+    assert_eq!(state.status, MaintenanceStatus::Ready);
+    assert!(state.exit_when_ready);
+}
@@ -0,0 +1,79 @@
+use agglayer_storage::stores::StateWriter as _;
+use agglayer_types::{
+    Certificate, CertificateHeader, CertificateStatus, CertificateSubmissionReceipt, Height,
+    NetworkId, Proof, Signature,
+};
+use alloy::signers::SignerSync as _;
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+
+use crate::testutils::TestContext;
+
+async fn read_only_context() -> TestContext {
+    let mut config = TestContext::get_default_config();
+    config.read_only.enabled = true;
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+
+    TestContext::new_with_config(config).await
+}
+
+#[test_log::test(tokio::test)]
+async fn certificates_are_rejected() {
+    let mut context = read_only_context().await;
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request("interop_sendCertificate", rpc_params![certificate])
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::READ_ONLY);
+    assert!(context.certificate_receiver.try_recv().is_err());
+}
+
+#[test_log::test(tokio::test)]
+async fn proofs_are_rejected_and_queries_served() {
+    let context = read_only_context().await;
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Pending)
+        .unwrap();
+    let commitment = certificate_id
+        .proof_submission_commitment(&Proof::dummy())
+        .unwrap();
+    let signature = Certificate::wallet_for_test(NetworkId::new(1))
+        .sign_hash_sync(&commitment.0.into())
+        .unwrap();
+    let signature = Signature::new(signature.r(), signature.s(), signature.v());
+
+    let res: Result<(), _> = context
+        .api_client
+        .request(
+            "interop_submitProof",
+            rpc_params![certificate_id, Proof::dummy(), signature],
+        )
+        .await;
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::READ_ONLY);
+
+    let header: CertificateHeader = context
+        .api_client
+        .request("interop_getCertificateHeader", rpc_params![certificate_id])
+        .await
+        .unwrap();
+    assert_eq!(header.certificate_id, certificate_id);
+}
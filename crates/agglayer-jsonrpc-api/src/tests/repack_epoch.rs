@@ -0,0 +1,87 @@
+use agglayer_storage::stores::{PendingCertificateWriter as _, StateReader as _, StateWriter as _};
+use agglayer_types::{
+    Certificate, CertificateId, CertificateStatus, CertificateStatusError, Digest, EpochNumber,
+    Height, NetworkId, SettlementTxHash,
+};
+use jsonrpsee::{core::client::ClientT, rpc_params};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reverted_settlements_are_settled_again(#[future] mut context: TestContext) {
+    let epoch_number = EpochNumber::new(1);
+
+    // A certificate in error after the revert of its settlement.
+    let reverted = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let reverted_id = reverted.hash();
+    context
+        .state_store
+        .insert_certificate_header(&reverted, CertificateStatus::Pending)
+        .unwrap();
+    context
+        .state_store
+        .update_settlement_tx_hash(
+            &reverted_id,
+            SettlementTxHash::from(Digest::from([1; 32])),
+            false,
+        )
+        .unwrap();
+    context
+        .state_store
+        .update_certificate_header_status(
+            &reverted_id,
+            &CertificateStatus::error(CertificateStatusError::SettlementError(
+                "Settlement transaction failed".to_string(),
+            )),
+        )
+        .unwrap();
+    context
+        .pending_store
+        .insert_pending_certificate(NetworkId::new(1), Height::ZERO, &reverted)
+        .unwrap();
+
+    // A certificate since settled through another transaction.
+    let settled = Certificate::new_for_test(NetworkId::new(2), Height::ZERO);
+    let settled_id = settled.hash();
+    context
+        .state_store
+        .insert_certificate_header(&settled, CertificateStatus::Settled)
+        .unwrap();
+
+    for certificate_id in [reverted_id, settled_id] {
+        context
+            .state_store
+            .record_reverted_settlement(epoch_number, &certificate_id)
+            .unwrap();
+    }
+
+    let repacked: Vec<CertificateId> = context
+        .admin_client
+        .request("admin_repackEpoch", rpc_params![epoch_number])
+        .await
+        .unwrap();
+
+    assert_eq!(repacked, vec![reverted_id]);
+    assert_eq!(
+        context.certificate_receiver.try_recv(),
+        Ok((NetworkId::new(1), Height::ZERO, reverted_id))
+    );
+    assert!(context.certificate_receiver.try_recv().is_err());
+
+    let header = context
+        .state_store
+        .get_certificate_header(&reverted_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(header.status, CertificateStatus::Pending);
+    assert_eq!(header.settlement_tx_hash, None);
+
+    assert!(context
+        .state_store
+        .get_reverted_settlements(epoch_number)
+        .unwrap()
+        .is_empty());
+}
@@ -1,13 +1,20 @@
-use agglayer_config::Config;
+use agglayer_config::{CertificateLimits, Config};
 use agglayer_storage::{
+    columns::{
+        audit_log_per_certificate::{AuditEvent, SubmissionApi},
+        callback_per_certificate::CertificateCallback,
+    },
     stores::{PendingCertificateWriter as _, StateReader as _, StateWriter as _},
     tests::TempDBDir,
 };
 use agglayer_types::{
-    Certificate, CertificateHeader, CertificateId, CertificateStatus, Digest, Height, Metadata,
-    NetworkId, SettlementTxHash,
+    Certificate, CertificateHeader, CertificateId, CertificateStatus, CertificateSubmissionReceipt,
+    Digest, EpochNumber, EpochWindow, Height, Metadata, NetworkId, SettlementTxHash,
+};
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
 };
-use jsonrpsee::{core::client::ClientT, rpc_params};
 
 use crate::testutils::TestContext;
 
@@ -24,18 +31,215 @@ async fn send_certificate_method_can_be_called_and_succeed() {
     let client = context.api_client.clone();
 
     let cert_id: CertificateId = client
-        .request(
+        .request::<CertificateSubmissionReceipt, _>(
             "interop_sendCertificate",
             rpc_params![Certificate::new_for_test(1.into(), Height::ZERO)],
         )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
     let received_cert = context.certificate_receiver.try_recv();
 
     assert!(received_cert.is_ok());
     assert_eq!(received_cert.unwrap().2, cert_id);
 }
 
+#[test_log::test(tokio::test)]
+async fn send_certificate_registers_the_callback() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let context = TestContext::new_with_config(config).await;
+
+    let cert_id: CertificateId = context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![
+                Certificate::new_for_test(1.into(), Height::ZERO),
+                "https://example.com/callback"
+            ],
+        )
+        .await
+        .unwrap()
+        .certificate_id;
+
+    let callbacks = context.state_store.get_certificate_callbacks().unwrap();
+    assert_eq!(
+        callbacks,
+        vec![(
+            cert_id,
+            CertificateCallback {
+                url: "https://example.com/callback".to_string()
+            }
+        )]
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_records_the_submitter() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let context = TestContext::new_with_config(config).await;
+
+    let cert_id: CertificateId = context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![Certificate::new_for_test(1.into(), Height::ZERO)],
+        )
+        .await
+        .unwrap()
+        .certificate_id;
+
+    let events = context
+        .state_store
+        .get_audit_log(&cert_id)
+        .unwrap()
+        .into_iter()
+        .map(|record| record.event)
+        .collect::<Vec<_>>();
+
+    let [AuditEvent::Submitted { submitter }, AuditEvent::StatusChanged { status }] = &events[..]
+    else {
+        panic!("Unexpected audit log: {events:?}");
+    };
+    assert_eq!(submitter.api, SubmissionApi::JsonRpc);
+    assert!(submitter
+        .address
+        .is_some_and(|address| address.ip().is_loopback()));
+    assert_eq!(*status, CertificateStatus::Pending);
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_rejects_non_http_callback() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let mut context = TestContext::new_with_config(config).await;
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request(
+            "interop_sendCertificate",
+            rpc_params![
+                Certificate::new_for_test(1.into(), Height::ZERO),
+                "ftp://example.com/callback"
+            ],
+        )
+        .await;
+
+    assert!(res.is_err());
+    assert!(context.certificate_receiver.try_recv().is_err());
+    assert!(context
+        .state_store
+        .get_certificate_callbacks()
+        .unwrap()
+        .is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_rejects_private_callback() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let mut context = TestContext::new_with_config(config).await;
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request(
+            "interop_sendCertificate",
+            rpc_params![
+                Certificate::new_for_test(1.into(), Height::ZERO),
+                "http://169.254.169.254/latest/meta-data"
+            ],
+        )
+        .await;
+
+    assert!(matches!(
+        res.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == jsonrpsee::types::error::INVALID_PARAMS_CODE
+    ));
+    assert!(context.certificate_receiver.try_recv().is_err());
+    assert!(context
+        .state_store
+        .get_certificate_callbacks()
+        .unwrap()
+        .is_empty());
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_rejects_a_conflicting_callback_on_resubmission() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let context = TestContext::new_with_config(config).await;
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+
+    let cert_id: CertificateId = context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone(), "https://example.com/callback"],
+        )
+        .await
+        .unwrap()
+        .certificate_id;
+
+    // Retrying with the same callback is accepted.
+    context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone(), "https://example.com/callback"],
+        )
+        .await
+        .unwrap();
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request(
+            "interop_sendCertificate",
+            rpc_params![certificate, "https://attacker.example/callback"],
+        )
+        .await;
+
+    assert!(matches!(
+        res.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == jsonrpsee::types::error::INVALID_PARAMS_CODE
+    ));
+    assert_eq!(
+        context
+            .state_store
+            .get_certificate_callback(&cert_id)
+            .unwrap(),
+        Some(CertificateCallback {
+            url: "https://example.com/callback".to_string()
+        })
+    );
+}
+
 #[test_log::test(tokio::test)]
 async fn send_certificate_method_can_be_called_and_fail() {
     let path = TempDBDir::new();
@@ -66,7 +270,7 @@ async fn send_certificate_method_requires_known_signer() {
     );
 
     let context = TestContext::new_with_config(config).await;
-    let send_request: Result<CertificateId, _> = context
+    let send_request: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request(
             "interop_sendCertificate",
@@ -77,6 +281,177 @@ async fn send_certificate_method_requires_known_signer() {
     assert!(send_request.is_err());
 }
 
+#[test_log::test(tokio::test)]
+async fn send_certificate_is_idempotent() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let mut context = TestContext::new_with_config(config).await;
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+
+    let cert_id: CertificateId = context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
+        .await
+        .unwrap()
+        .certificate_id;
+    assert_eq!(context.certificate_receiver.try_recv().unwrap().2, cert_id);
+
+    let resubmitted_cert_id: CertificateId = context
+        .api_client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
+        .await
+        .unwrap()
+        .certificate_id;
+    assert_eq!(resubmitted_cert_id, cert_id);
+
+    // The known certificate is left untouched.
+    assert!(context.certificate_receiver.try_recv().is_err());
+    let events = context.state_store.get_audit_log(&cert_id).unwrap();
+    assert_eq!(events.len(), 2);
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_returns_the_queue_position() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let context = TestContext::new_with_config(config).await;
+    let certificate = Certificate::new_for_test(1.into(), Height::ZERO);
+
+    let receipt: CertificateSubmissionReceipt = context
+        .api_client
+        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .await
+        .unwrap();
+
+    assert_eq!(receipt.certificate_id, certificate.hash());
+    assert_eq!(receipt.position.queue_position, 0);
+    assert_eq!(receipt.position.current_epoch, Some(EpochNumber::ZERO));
+    assert_eq!(
+        receipt.position.estimated_certification_window,
+        Some(EpochWindow {
+            first: EpochNumber::ZERO,
+            last: EpochNumber::new(1),
+        })
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_rejects_height_gap() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    let mut context = TestContext::new_with_config(config).await;
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request(
+            "interop_sendCertificate",
+            rpc_params![Certificate::new_for_test(1.into(), Height::new(2))],
+        )
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::UNEXPECTED_HEIGHT);
+    assert_eq!(
+        error.message(),
+        "Unexpected certificate height 2, expected 0"
+    );
+    assert!(context.certificate_receiver.try_recv().is_err());
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_enforces_the_network_limits() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    config.rpc.intake.networks.insert(
+        1,
+        CertificateLimits {
+            max_certificate_size: Some(100),
+            ..Default::default()
+        },
+    );
+    let mut context = TestContext::new_with_config(config).await;
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request(
+            "interop_sendCertificate",
+            rpc_params![Certificate::new_for_test(1.into(), Height::ZERO)],
+        )
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::CERTIFICATE_TOO_LARGE);
+    assert!(error.message().ends_with("the maximum is 100"));
+    assert!(context.certificate_receiver.try_recv().is_err());
+}
+
+#[test_log::test(tokio::test)]
+async fn send_certificate_ahead_of_settlement_follows_the_height_policy() {
+    let mut config = TestContext::get_default_config();
+    config.proof_signers.insert(
+        1,
+        Certificate::wallet_for_test(NetworkId::new(1))
+            .address()
+            .into(),
+    );
+    config.rpc.intake.height_policy = agglayer_config::HeightPolicy::NextSettled;
+    let context = TestContext::new_with_config(config).await;
+    let network_id = 1.into();
+
+    let pending_certificate = Certificate::new_for_test(network_id, Height::ZERO);
+    context
+        .state_store
+        .insert_certificate_header(&pending_certificate, CertificateStatus::Pending)
+        .expect("unable to insert pending certificate header");
+    context
+        .pending_store
+        .insert_pending_certificate(network_id, Height::ZERO, &pending_certificate)
+        .expect("unable to insert pending certificate");
+
+    let res: Result<CertificateSubmissionReceipt, _> = context
+        .api_client
+        .request(
+            "interop_sendCertificate",
+            rpc_params![Certificate::new_for_test(network_id, Height::new(1))],
+        )
+        .await;
+
+    let Err(ClientError::Call(error)) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(error.code(), crate::error::code::UNEXPECTED_HEIGHT);
+}
+
 #[test_log::test(tokio::test)]
 async fn pending_certificate_in_error_can_be_replaced() {
     let path = TempDBDir::new();
@@ -106,7 +481,7 @@ async fn pending_certificate_in_error_can_be_replaced() {
         .insert_pending_certificate(network_id, Height::ZERO, &pending_certificate)
         .expect("unable to insert pending certificate");
 
-    let res: Result<CertificateId, _> = context
+    let res: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request(
             "interop_sendCertificate",
@@ -126,7 +501,7 @@ async fn pending_certificate_in_error_can_be_replaced() {
         )
         .expect("unable to insert pending certificate header");
 
-    let res: Result<CertificateId, _> = context
+    let res: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request("interop_sendCertificate", rpc_params![second_pending])
         .await;
@@ -166,7 +541,8 @@ async fn pending_certificate_in_error_force_push() {
         .insert_pending_certificate(network_id, Height::ZERO, &pending_certificate)
         .expect("unable to insert pending certificate");
 
-    let res: Result<CertificateId, _> = context
+    // Resubmitting the pending certificate returns it as is.
+    let res: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request(
             "interop_sendCertificate",
@@ -174,7 +550,7 @@ async fn pending_certificate_in_error_force_push() {
         )
         .await;
 
-    assert!(res.is_err());
+    assert_eq!(res.unwrap(), certificate_id);
 
     context
         .state_store
@@ -186,7 +562,7 @@ async fn pending_certificate_in_error_force_push() {
         )
         .expect("Unable to update certificate header status");
 
-    let res: Result<CertificateId, _> = context
+    let res: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request(
             "interop_sendCertificate",
@@ -248,7 +624,8 @@ async fn pending_certificate_in_error_force_set_status() {
         .insert_pending_certificate(network_id, Height::ZERO, &pending_certificate)
         .expect("unable to insert pending certificate");
 
-    let res: Result<CertificateId, _> = context
+    // Resubmitting the pending certificate returns it as is.
+    let res: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request(
             "interop_sendCertificate",
@@ -256,7 +633,7 @@ async fn pending_certificate_in_error_force_set_status() {
         )
         .await;
 
-    assert!(res.is_err());
+    assert_eq!(res.unwrap(), certificate_id);
 
     context
         .state_store
@@ -268,7 +645,7 @@ async fn pending_certificate_in_error_force_set_status() {
         )
         .expect("Unable to update certificate header status");
 
-    let res: Result<CertificateId, _> = context
+    let res: Result<CertificateSubmissionReceipt, _> = context
         .api_client
         .request(
             "interop_sendCertificate",
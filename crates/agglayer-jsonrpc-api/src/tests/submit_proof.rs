@@ -0,0 +1,149 @@
+use agglayer_storage::stores::{PendingCertificateReader as _, StateWriter as _};
+use agglayer_types::{
+    Certificate, CertificateId, CertificateStatus, Height, NetworkId, Proof, Signature,
+};
+use alloy::signers::SignerSync as _;
+use jsonrpsee::{
+    core::{client::ClientT, ClientError},
+    rpc_params,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+fn sign_proof_submission(network_id: NetworkId, certificate_id: CertificateId) -> Signature {
+    let commitment = certificate_id
+        .proof_submission_commitment(&Proof::dummy())
+        .unwrap();
+    let signature = Certificate::wallet_for_test(network_id)
+        .sign_hash_sync(&commitment.0.into())
+        .unwrap();
+
+    Signature::new(signature.r(), signature.s(), signature.v())
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn stores_the_proof_of_a_pending_certificate(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Pending)
+        .unwrap();
+
+    let _: () = context
+        .api_client
+        .request(
+            "interop_submitProof",
+            rpc_params![
+                certificate_id,
+                Proof::dummy(),
+                sign_proof_submission(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await
+        .unwrap();
+
+    assert!(context
+        .pending_store
+        .get_submitted_proof(&certificate_id)
+        .unwrap()
+        .is_some());
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reject_proof_of_a_settled_certificate(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Settled)
+        .unwrap();
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_submitProof",
+            rpc_params![
+                certificate_id,
+                Proof::dummy(),
+                sign_proof_submission(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await;
+
+    let expected_message = format!(
+        "Invalid argument: Certificate {certificate_id} is Settled, a proof can no longer be \
+         submitted"
+    );
+    assert!(
+        matches!(result.unwrap_err(), ClientError::Call(obj) if obj.message() == expected_message)
+    );
+    assert!(context
+        .pending_store
+        .get_submitted_proof(&certificate_id)
+        .unwrap()
+        .is_none());
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reject_proof_of_an_unknown_certificate(#[future] context: TestContext) {
+    let certificate_id = CertificateId::new([0xff; 32].into());
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_submitProof",
+            rpc_params![
+                certificate_id,
+                Proof::dummy(),
+                sign_proof_submission(NetworkId::new(1), certificate_id)
+            ],
+        )
+        .await;
+
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == crate::error::code::RESOURCE_NOT_FOUND
+    ));
+}
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn reject_proof_signed_by_another_network(#[future] context: TestContext) {
+    let certificate = Certificate::new_for_test(NetworkId::new(1), Height::ZERO);
+    let certificate_id = certificate.hash();
+    context
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Pending)
+        .unwrap();
+
+    let result: Result<(), ClientError> = context
+        .api_client
+        .request(
+            "interop_submitProof",
+            rpc_params![
+                certificate_id,
+                Proof::dummy(),
+                sign_proof_submission(NetworkId::new(2), certificate_id)
+            ],
+        )
+        .await;
+
+    assert!(matches!(
+        result.unwrap_err(),
+        ClientError::Call(obj) if obj.code() == crate::error::code::SIGNATURE_MISMATCH
+    ));
+    assert!(context
+        .pending_store
+        .get_submitted_proof(&certificate_id)
+        .unwrap()
+        .is_none());
+}
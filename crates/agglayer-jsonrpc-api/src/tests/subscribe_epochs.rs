@@ -0,0 +1,48 @@
+use agglayer_types::{EpochEvent, EpochNumber};
+use jsonrpsee::{
+    core::client::{Subscription, SubscriptionClientT},
+    rpc_params,
+    ws_client::WsClientBuilder,
+};
+use rstest::*;
+
+use crate::testutils::{context, TestContext};
+
+#[rstest]
+#[awt]
+#[test_log::test(tokio::test)]
+async fn epoch_events_are_forwarded_to_subscribers(#[future] context: TestContext) {
+    let client = WsClientBuilder::default()
+        .build(format!("ws://{}/", context.api_addr))
+        .await
+        .unwrap();
+
+    let mut subscription: Subscription<EpochEvent> = client
+        .subscribe(
+            "interop_subscribeEpochs",
+            rpc_params![],
+            "interop_unsubscribeEpochs",
+        )
+        .await
+        .unwrap();
+
+    let events = [
+        EpochEvent::PackingStarted {
+            epoch_number: EpochNumber::new(1),
+        },
+        EpochEvent::Settled {
+            epoch_number: EpochNumber::new(1),
+        },
+        EpochEvent::Opened {
+            epoch_number: EpochNumber::new(2),
+        },
+    ];
+    for event in events {
+        context.epoch_events.send(event).unwrap();
+    }
+
+    for expected in events {
+        let event = subscription.next().await.unwrap().unwrap();
+        assert_eq!(event, expected);
+    }
+}
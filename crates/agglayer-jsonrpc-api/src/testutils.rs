@@ -1,7 +1,15 @@
-use std::{future::IntoFuture as _, net::SocketAddr, sync::Arc};
+use std::{
+    future::IntoFuture as _,
+    net::SocketAddr,
+    num::NonZeroU64,
+    sync::{atomic::AtomicU64, Arc},
+};
 
-use agglayer_config::Config;
+use agglayer_certificate_orchestrator::OrchestratorState;
+use agglayer_clock::{ClockRef, ManualClock};
+use agglayer_config::{Config, Epoch};
 use agglayer_contracts::L1RpcClient;
+use agglayer_rpc::Maintenance;
 use agglayer_storage::{
     storage::{
         backup::BackupClient, debug_db_cf_definitions, pending_db_cf_definitions,
@@ -10,7 +18,7 @@ use agglayer_storage::{
     stores::{debug::DebugStore, epochs::EpochsStore, pending::PendingStore, state::StateStore},
     tests::TempDBDir,
 };
-use agglayer_types::{Certificate, CertificateId, EpochNumber, Height, NetworkId};
+use agglayer_types::{Certificate, CertificateId, EpochEvent, EpochNumber, Height, NetworkId};
 use alloy::{
     providers::{
         fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
@@ -21,6 +29,7 @@ use alloy::{
 };
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use rstest::*;
+use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 
 // Import the AgglayerServer trait to get access to into_rpc()
@@ -65,10 +74,16 @@ pub struct TestContext {
     pub cancellation_token: CancellationToken,
     pub state_store: Arc<StateStore>,
     pub pending_store: Arc<PendingStore>,
+    pub debug_store: Arc<DebugStore>,
+    pub api_addr: SocketAddr,
     pub api_client: HttpClient,
     pub admin_client: HttpClient,
     pub config: Arc<Config>,
     pub certificate_receiver: tokio::sync::mpsc::Receiver<(NetworkId, Height, CertificateId)>,
+    pub epoch_events: broadcast::Sender<EpochEvent>,
+    pub orchestrator_state: Arc<OrchestratorState>,
+    pub maintenance: Arc<Maintenance>,
+    pub clock_ref: ClockRef,
 }
 
 impl TestContext {
@@ -145,28 +160,48 @@ impl TestContext {
         );
 
         // Create agglayer_rpc::AgglayerService with the provider
-        let rpc_service = Arc::new(agglayer_rpc::AgglayerService::new(
-            certificate_sender.clone(),
-            pending_store.clone(),
-            state_store.clone(),
-            debug_store.clone(),
-            epochs_store,
-            config.clone(),
-            Arc::new(l1_rpc_client),
-        ));
+        let maintenance = Arc::new(Maintenance::default());
+        let clock_ref = ClockRef::new(
+            broadcast::channel(1).0,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(NonZeroU64::new(1).unwrap()),
+        );
+        let rpc_service = Arc::new(
+            agglayer_rpc::AgglayerService::new(
+                certificate_sender.clone(),
+                pending_store.clone(),
+                state_store.clone(),
+                debug_store.clone(),
+                epochs_store,
+                config.clone(),
+                Arc::new(l1_rpc_client),
+            )
+            .with_maintenance(maintenance.clone())
+            .with_clock(clock_ref.clone()),
+        );
 
         // Create AgglayerImpl
-        let agglayer_impl = crate::AgglayerImpl::new(v0_service, rpc_service);
+        let (epoch_events, _) = broadcast::channel(16);
+        let orchestrator_state = Arc::new(OrchestratorState::new(clock_ref.clone()));
+        let agglayer_impl = crate::AgglayerImpl::new(v0_service, rpc_service, epoch_events.clone())
+            .with_orchestrator_state(orchestrator_state.clone());
 
         // Create the routers
         let router = agglayer_impl.start().await.unwrap();
+        let manual_clock = match &config.epoch {
+            Epoch::ManualClock(cfg) => Some(ManualClock::new(cfg.epoch_duration).handle()),
+            _ => None,
+        };
         let admin_router = AdminAgglayerImpl::new(
             certificate_sender,
             pending_store.clone(),
             state_store.clone(),
             debug_store.clone(),
             config.clone(),
+            orchestrator_state.clone(),
         )
+        .with_maintenance(maintenance.clone())
+        .with_manual_clock(manual_clock)
         .start()
         .await
         .unwrap();
@@ -185,8 +220,11 @@ impl TestContext {
 
         let listener_admin = tokio::net::TcpListener::bind(admin_addr).await.unwrap();
 
-        let api_server = axum::serve(listener_api, router)
-            .with_graceful_shutdown(cancellation_token.child_token().cancelled_owned());
+        let api_server = axum::serve(
+            listener_api,
+            router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+        )
+        .with_graceful_shutdown(cancellation_token.child_token().cancelled_owned());
         let admin_server = axum::serve(listener_admin, admin_router)
             .with_graceful_shutdown(cancellation_token.child_token().cancelled_owned());
 
@@ -197,10 +235,16 @@ impl TestContext {
             cancellation_token,
             state_store,
             pending_store,
+            debug_store,
+            api_addr,
             api_client,
             admin_client,
             config,
             certificate_receiver,
+            epoch_events,
+            orchestrator_state,
+            maintenance,
+            clock_ref,
         }
     }
 
@@ -306,7 +350,8 @@ impl TestContext {
         ));
 
         // Create AgglayerImpl
-        let agglayer_impl = crate::AgglayerImpl::new(v0_service, rpc_service);
+        let (epoch_events, _) = broadcast::channel(16);
+        let agglayer_impl = crate::AgglayerImpl::new(v0_service, rpc_service, epoch_events);
 
         RawRpcContext {
             rpc: agglayer_impl,
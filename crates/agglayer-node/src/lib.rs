@@ -1,6 +1,7 @@
 use std::{future::IntoFuture, path::PathBuf, sync::Arc};
 
 use agglayer_config::Config;
+use agglayer_types::BuildInfo;
 use eyre::bail;
 use node::Node;
 use tokio_util::sync::CancellationToken;
@@ -9,6 +10,7 @@ mod logging;
 
 mod epoch_synchronizer;
 mod node;
+pub mod replay;
 
 use agglayer_telemetry::ServerBuilder as MetricsBuilder;
 
@@ -23,6 +25,7 @@ use agglayer_telemetry::ServerBuilder as MetricsBuilder;
 pub fn main(
     cfg: PathBuf,
     version: &str,
+    build_info: BuildInfo,
     cancellation_token: Option<CancellationToken>,
 ) -> eyre::Result<()> {
     let cfg = cfg.canonicalize().map_err(|_| {
@@ -107,6 +110,7 @@ pub fn main(
     let node = node_runtime.block_on(
         Node::builder()
             .config(config.clone())
+            .build_info(build_info)
             .cancellation_token(global_cancellation_token.clone())
             .start(),
     )?;
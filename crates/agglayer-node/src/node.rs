@@ -1,17 +1,24 @@
-use std::{num::NonZeroU64, sync::Arc};
+use std::{
+    future::IntoFuture as _, net::SocketAddr, num::NonZeroU64, os::unix::fs::FileTypeExt as _,
+    sync::Arc, time::Duration,
+};
 
-use agglayer_aggregator_notifier::{CertifierClient, RpcSettlementClient};
-use agglayer_certificate_orchestrator::CertificateOrchestrator;
-use agglayer_clock::{BlockClock, Clock, TimeClock};
+use agglayer_aggregator_notifier::{CertifierClient, L1SettlementAdapter, RpcSettlementClient};
+use agglayer_certificate_orchestrator::{CertificateOrchestrator, OrchestratorState};
+use agglayer_clock::{BlockClock, Clock, ManualClock, TimeClock};
 use agglayer_config::{storage::backup::BackupConfig, Config, Epoch};
-use agglayer_contracts::{contracts::PolygonRollupManager, L1RpcClient};
+use agglayer_contracts::{contracts::PolygonRollupManager, L1RpcClient, RollupManagerAbi};
 use agglayer_jsonrpc_api::{
     admin::AdminAgglayerImpl, kernel::Kernel, service::AgglayerService, AgglayerImpl,
 };
+use agglayer_rpc::Maintenance;
 use agglayer_signer::ConfiguredSigner;
 use agglayer_storage::{
     storage::{
         backup::{BackupClient, BackupEngine},
+        compactor::Compactor,
+        metrics_reporter::MetricsReporter,
+        scrubber::{LatestScrubReport, Scrubber},
         DB,
     },
     stores::{
@@ -19,19 +26,54 @@ use agglayer_storage::{
         PerEpochReader as _,
     },
 };
+use agglayer_types::{BuildInfo, Digest, EpochNumber, VersionInfo};
 use alloy::{
     network::EthereumWallet,
     providers::{ProviderBuilder, WsConnect},
     signers::Signer,
 };
 use eyre::Context as _;
-use tokio::{sync::mpsc, task::JoinHandle};
+use futures::{future::BoxFuture, FutureExt as _};
+use tokio::{
+    sync::{broadcast, mpsc},
+    task::JoinHandle,
+};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
+use self::{
+    callbacks::CallbackNotifier,
+    diagnostics::{Diagnostics, L1Head},
+    epoch_commitment::EpochCommitter,
+    garbage_collector::GarbageCollector,
+    maintenance::MaintenanceTask,
+    pending_expiry::PendingExpiry,
+};
 use crate::epoch_synchronizer::EpochSynchronizer;
 
 pub(crate) mod api;
+mod callbacks;
+mod diagnostics;
+mod epoch_commitment;
+mod garbage_collector;
+pub(crate) mod http_client;
+mod maintenance;
+mod pending_expiry;
+mod read_only;
+mod startup_checks;
+
+/// Number of epoch events buffered for the slowest subscriber.
+const EPOCH_EVENTS_CHANNEL_SIZE: usize = 16;
+
+/// Interval at which the RocksDB properties are recorded.
+const STORAGE_METRICS_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Number of attempts at negotiating the settlement ABI of the rollup manager
+/// before refusing to start.
+const ABI_NEGOTIATION_ATTEMPTS: u32 = 5;
+
+/// Delay between the attempts at negotiating the settlement ABI.
+const ABI_NEGOTIATION_RETRY_DELAY: Duration = Duration::from_secs(2);
 
 pub(crate) struct Node {
     pub(crate) rpc_handle: JoinHandle<()>,
@@ -46,6 +88,8 @@ impl Node {
     ///
     /// - `builder`: Creates a new builder instance.
     /// - `config`: Sets the configuration.
+    /// - `build_info`: Sets the build of the running binary, reported by the
+    ///   RPC.
     /// - `start`: Starts the Agglayer node.
     ///
     /// # Examples
@@ -53,6 +97,7 @@ impl Node {
     /// # use std::sync::Arc;
     /// # use agglayer_config::Config;
     /// # use agglayer_node::Node;
+    /// # use agglayer_types::BuildInfo;
     /// # use tokio_util::sync::CancellationToken;
     /// #
     /// async fn start_node() -> eyre::Result<()> {
@@ -60,6 +105,7 @@ impl Node {
     ///
     ///    Node::builder()
     ///      .config(config)
+    ///      .build_info(BuildInfo::default())
     ///      .cancellation_token(CancellationToken::new())
     ///      .start()
     ///      .await?;
@@ -73,11 +119,13 @@ impl Node {
     /// This function will return an error if:
     /// - The L1 node URL is invalid.
     /// - The configured signer is invalid.
+    /// - The L1 startup checks failed while being enforced.
     /// - The RPC server failed to start.
     /// - The [`TimeClock`] failed to start.
     #[builder(entry = "builder", exit = "start", visibility = "pub(crate)")]
     pub(crate) async fn start(
         config: Arc<Config>,
+        build_info: BuildInfo,
         cancellation_token: CancellationToken,
     ) -> eyre::Result<Self> {
         if config.mock_verifier {
@@ -87,15 +135,38 @@ impl Node {
             );
         }
 
+        if config.read_only.enabled {
+            info!("Starting in read-only mode.");
+            return read_only::start(config, build_info, cancellation_token).await;
+        }
+
         // Initializing storage
-        let pending_db = Arc::new(DB::open_cf(
+        let mut pending_db = DB::open_cf_with_resources(
             &config.storage.pending_db_path,
-            agglayer_storage::storage::pending_db_cf_definitions(),
-        )?);
-        let state_db = Arc::new(DB::open_cf(
+            &agglayer_storage::storage::PENDING_DB_CFS,
+            &config.storage.resources.pending,
+        )?;
+        let mut state_db = DB::open_cf_with_resources(
             &config.storage.state_db_path,
-            agglayer_storage::storage::state_db_cf_definitions(),
-        )?);
+            &agglayer_storage::storage::STATE_DB_CFS,
+            &config.storage.resources.state,
+        )?;
+        if let (Some(shadow_pending_db_path), Some(shadow_state_db_path)) = (
+            config.storage.shadow_pending_db_path(),
+            config.storage.shadow_state_db_path(),
+        ) {
+            pending_db = pending_db.with_shadow(DB::open_cf(
+                &shadow_pending_db_path,
+                agglayer_storage::storage::pending_db_cf_definitions(),
+            )?);
+            state_db = state_db.with_shadow(DB::open_cf(
+                &shadow_state_db_path,
+                agglayer_storage::storage::state_db_cf_definitions(),
+            )?);
+            info!("Storage writes mirrored into the shadow storage.");
+        }
+        let pending_db = Arc::new(pending_db);
+        let state_db = Arc::new(state_db);
 
         // Initialize backup engine
         let backup_client = if let BackupConfig::Enabled {
@@ -118,10 +189,55 @@ impl Node {
         } else {
             BackupClient::noop()
         };
+
+        let storage_metrics_reporter = MetricsReporter::new(
+            vec![("state", state_db.clone()), ("pending", pending_db.clone())],
+            STORAGE_METRICS_INTERVAL,
+        );
+        tokio::spawn(storage_metrics_reporter.run(cancellation_token.clone()));
+        let scrub_report = match config.storage.scrub_interval {
+            Some(interval) => {
+                let scrubber = Scrubber::new(
+                    vec![("state", state_db.clone()), ("pending", pending_db.clone())],
+                    interval,
+                );
+                let scrub_report = scrubber.latest_report();
+                tokio::spawn(scrubber.run(cancellation_token.clone()));
+                info!("Storage scrubber started.");
+
+                scrub_report
+            }
+            None => LatestScrubReport::default(),
+        };
+        let compactor = Compactor::new(vec![
+            ("state", state_db.clone()),
+            ("pending", pending_db.clone()),
+        ]);
+        if let Some(interval) = config.storage.compaction.interval {
+            let columns = config.storage.compaction.columns.clone();
+            compactor
+                .targets(&columns)
+                .context("Invalid storage compaction columns")?;
+            tokio::spawn(
+                compactor
+                    .clone()
+                    .run(columns, interval, cancellation_token.clone()),
+            );
+            info!("Storage compactor started.");
+        }
         let state_store = Arc::new(StateStore::new(state_db.clone(), backup_client.clone()));
         let pending_store = Arc::new(PendingStore::new(pending_db.clone()));
         let debug_store = if config.debug_mode {
-            Arc::new(DebugStore::new_with_path(&config.storage.debug_db_path)?)
+            Arc::new(
+                DebugStore::new(Arc::new(DB::open_cf_with_resources(
+                    &config.storage.debug_db_path,
+                    &agglayer_storage::storage::DEBUG_DB_CFS,
+                    &config.storage.resources.debug,
+                )?))
+                .with_max_certification_failures(
+                    config.certificate_orchestrator.max_certification_failures,
+                ),
+            )
         } else {
             Arc::new(DebugStore::Disabled)
         };
@@ -129,6 +245,7 @@ impl Node {
         info!("Storage initialized.");
 
         // Spawn the TimeClock.
+        let mut manual_clock = None;
         let clock_ref = match &config.epoch {
             Epoch::BlockClock(cfg) => {
                 info!(
@@ -157,6 +274,14 @@ impl Node {
                     ))?;
                 let clock = TimeClock::new_now(duration);
 
+                clock.spawn(cancellation_token.clone()).await?
+            }
+            Epoch::ManualClock(cfg) => {
+                warn!("Starting ManualClock, the epochs only end on admin request");
+
+                let clock = ManualClock::new(cfg.epoch_duration);
+                manual_clock = Some(clock.handle());
+
                 clock.spawn(cancellation_token.clone()).await?
             }
         };
@@ -191,52 +316,168 @@ impl Node {
         let wallet = EthereumWallet::from(signer);
         let provider = ProviderBuilder::new()
             .wallet(wallet)
-            .on_http(config.l1.node_url.clone());
+            .on_client(http_client::l1_rpc_client(
+                &config.outbound.http,
+                config.l1.node_url.clone(),
+            )?);
         let rpc = Arc::new(provider);
 
         tracing::debug!("RPC provider created");
-        let rollup_manager = Arc::new(
-            L1RpcClient::try_new(
-                rpc.clone(),
-                PolygonRollupManager::new(config.l1.rollup_manager_contract.into(), (*rpc).clone()),
-                config.l1.polygon_zkevm_global_exit_root_v2_contract.into(),
-                config.outbound.rpc.settle.gas_multiplier_factor,
-                {
-                    let gas_config = &config.outbound.rpc.settle.gas_price;
-                    agglayer_contracts::GasPriceParams::new(
-                        gas_config.multiplier.as_u64_per_1000(),
-                        gas_config.floor..=gas_config.ceiling,
-                    )?
-                },
-                config.l1.event_filter_block_range.get(),
-            )
-            .await?,
-        );
+        let rollup_manager = L1RpcClient::try_new(
+            rpc.clone(),
+            PolygonRollupManager::new(config.l1.rollup_manager_contract.into(), (*rpc).clone()),
+            config.l1.polygon_zkevm_global_exit_root_v2_contract.into(),
+            config.outbound.rpc.settle.gas_multiplier_factor,
+            {
+                let gas_config = &config.outbound.rpc.settle.gas_price;
+                agglayer_contracts::GasPriceParams::new(
+                    gas_config.multiplier.as_u64_per_1000(),
+                    gas_config.floor..=gas_config.ceiling,
+                )?
+            },
+            config.l1.event_filter_block_range.get(),
+        )
+        .await?;
+
+        // Settlement ABI of the rollup manager, either configured or negotiated with
+        // the deployed contract.
+        let rollup_manager_abi = match config.l1.rollup_manager_abi {
+            agglayer_config::RollupManagerAbi::Auto => {
+                let mut attempt = 1;
+                loop {
+                    match rollup_manager.negotiate_rollup_manager_abi().await {
+                        Ok(abi) => break abi,
+                        Err(error) if attempt < ABI_NEGOTIATION_ATTEMPTS => {
+                            warn!(
+                                ?error,
+                                attempt,
+                                "Unable to negotiate the settlement ABI of the rollup manager, \
+                                 retrying"
+                            );
+                            attempt += 1;
+                            tokio::time::sleep(ABI_NEGOTIATION_RETRY_DELAY).await;
+                        }
+                        Err(error) => {
+                            return Err(error).context(
+                                "Unable to negotiate the settlement ABI of the rollup manager, \
+                                 set `l1.rollup-manager-abi` to settle with a given one",
+                            );
+                        }
+                    }
+                }
+            }
+            agglayer_config::RollupManagerAbi::Pessimistic => RollupManagerAbi::Pessimistic,
+            agglayer_config::RollupManagerAbi::Aggchain => RollupManagerAbi::Aggchain,
+        };
+        info!(?rollup_manager_abi, "Settling with the rollup manager ABI");
+
+        let rollup_manager = Arc::new(rollup_manager.with_rollup_manager_abi(rollup_manager_abi));
         tracing::debug!("RollupManager created");
 
+        // Rollup manager client signing with the next settlement key, if a rotation is
+        // configured.
+        let rotated_rollup_manager = match &config.auth_rotation {
+            Some(rotation) => {
+                let signer =
+                    ConfiguredSigner::from_auth(config.l1.chain_id, &rotation.next).await?;
+                let next_address = signer.address();
+                info!(
+                    "Settlement key rotation to {next_address:?} at epoch {}",
+                    rotation.activation_epoch
+                );
+
+                let provider = ProviderBuilder::new()
+                    .wallet(EthereumWallet::from(signer))
+                    .on_client(http_client::l1_rpc_client(
+                        &config.outbound.http,
+                        config.l1.node_url.clone(),
+                    )?);
+                let rpc = Arc::new(provider);
+                let rollup_manager = L1RpcClient::try_new(
+                    rpc.clone(),
+                    PolygonRollupManager::new(
+                        config.l1.rollup_manager_contract.into(),
+                        (*rpc).clone(),
+                    ),
+                    config.l1.polygon_zkevm_global_exit_root_v2_contract.into(),
+                    config.outbound.rpc.settle.gas_multiplier_factor,
+                    {
+                        let gas_config = &config.outbound.rpc.settle.gas_price;
+                        agglayer_contracts::GasPriceParams::new(
+                            gas_config.multiplier.as_u64_per_1000(),
+                            gas_config.floor..=gas_config.ceiling,
+                        )?
+                    },
+                    config.l1.event_filter_block_range.get(),
+                )
+                .await?
+                .with_rollup_manager_abi(rollup_manager_abi);
+
+                Some((
+                    EpochNumber::new(rotation.activation_epoch),
+                    next_address,
+                    Arc::new(rollup_manager),
+                ))
+            }
+            None => None,
+        };
+
         let certifier_client = CertifierClient::try_new(
             config.prover_entrypoint.clone(),
             pending_store.clone(),
             Arc::clone(&rollup_manager),
             Arc::clone(&config),
         )
-        .await?;
+        .await?
+        .with_debug_store(debug_store.clone());
         info!("Certifier client created.");
 
+        startup_checks::run(&*rpc, &config.l1, certifier_client.pessimistic_vkey()).await?;
+
         // Construct the core.
         let core = Kernel::new(rpc.clone(), config.clone()).unwrap();
 
         let current_epoch_store = Arc::new(arc_swap::ArcSwap::new(Arc::new(current_epoch_store)));
-        let epoch_packing_aggregator_task = RpcSettlementClient::new(
+        let mut epoch_packing_aggregator_task = RpcSettlementClient::new(
             Arc::new(config.outbound.rpc.settle.clone()),
             state_store.clone(),
             pending_store.clone(),
             Arc::clone(&rollup_manager),
             current_epoch_store.clone(),
+            address,
         );
+        if let Some((activation_epoch, next_address, rotated_rollup_manager)) =
+            rotated_rollup_manager
+        {
+            epoch_packing_aggregator_task = epoch_packing_aggregator_task.with_key_rotation(
+                activation_epoch,
+                next_address,
+                Arc::new(L1SettlementAdapter::new(rotated_rollup_manager)),
+            );
+        }
+        epoch_packing_aggregator_task
+            .check_epoch_packing()
+            .await
+            .context("Unsupported epoch packing strategy")?;
 
         info!("Epoch packing aggregator task created.");
 
+        tokio::spawn(
+            epoch_packing_aggregator_task
+                .event_watcher()
+                .run(cancellation_token.clone()),
+        );
+
+        let callback_notifier =
+            CallbackNotifier::try_new(config.outbound.callback.clone(), state_store.clone())
+                .context("Failed creating certificate callback notifier")?;
+        tokio::spawn(callback_notifier.run(cancellation_token.clone()));
+
+        info!("Certificate callback notifier started.");
+
+        let (epoch_events, _) = broadcast::channel(EPOCH_EVENTS_CHANNEL_SIZE);
+        let orchestrator_state = Arc::new(OrchestratorState::new(clock_ref.clone()));
+
         let (data_sender, data_receiver) = mpsc::channel(
             config
                 .certificate_orchestrator
@@ -244,7 +485,7 @@ impl Node {
         );
 
         let certificate_orchestrator_handle = CertificateOrchestrator::builder()
-            .clock(clock_ref)
+            .clock(clock_ref.clone())
             .data_receiver(data_receiver)
             .cancellation_token(cancellation_token.clone())
             .settlement_client(epoch_packing_aggregator_task)
@@ -252,42 +493,145 @@ impl Node {
             .epochs_store(epochs_store.clone())
             .current_epoch(current_epoch_store)
             .state_store(state_store.clone())
-            .certifier_task_builder(certifier_client)
+            .certifier_task_builder(certifier_client.clone())
+            .epoch_events(epoch_events.clone())
+            .state(orchestrator_state.clone())
+            .max_concurrent_proofs(config.certificate_orchestrator.max_concurrent_proofs)
+            .retry_policy(config.certificate_orchestrator.retry_policy.clone())
+            .epoch_participation(config.certificate_orchestrator.epoch_participation.clone())
             .start()
             .await
             .context("Failed starting certificate orchestrator")?;
 
         info!("Certificate orchestrator started.");
 
-        // Set up the core service object.
-        let service = Arc::new(AgglayerService::new(core));
-        let rpc_service = Arc::new(agglayer_rpc::AgglayerService::new(
-            data_sender.clone(),
-            pending_store.clone(),
+        let epoch_committer = EpochCommitter::new(
+            &config.outbound.epoch_commitment,
             state_store.clone(),
-            debug_store.clone(),
             epochs_store.clone(),
-            config.clone(),
-            Arc::clone(&rollup_manager),
+        );
+        tokio::spawn(epoch_committer.run(
+            rollup_manager.clone(),
+            epoch_events.subscribe(),
+            cancellation_token.clone(),
         ));
 
+        info!("Epoch committer started.");
+
+        if config.diagnostics.enabled {
+            // The L1 lag is only meaningful when the epochs follow the L1 blocks.
+            let l1_head = match &config.epoch {
+                Epoch::BlockClock(cfg) => Some(L1Head {
+                    provider: rpc.clone(),
+                    genesis_block: cfg.genesis_block,
+                }),
+                Epoch::TimeClock(_) | Epoch::ManualClock(_) => None,
+            };
+            let diagnostics = Diagnostics::try_new(
+                config.diagnostics.clone(),
+                state_store.clone(),
+                orchestrator_state.clone(),
+                l1_head,
+            )
+            .context("Failed creating diagnostics task")?;
+            tokio::spawn(diagnostics.run(cancellation_token.clone()));
+
+            info!("Diagnostics task started.");
+        }
+
+        if let Some(pending_expiry) = PendingExpiry::new(
+            &config.certificate_orchestrator.pending_expiry,
+            pending_store.clone(),
+            state_store.clone(),
+            orchestrator_state.clone(),
+        ) {
+            tokio::spawn(pending_expiry.run(cancellation_token.clone()));
+
+            info!("Pending certificate expiry started.");
+        }
+
+        if let Some(garbage_collector) = GarbageCollector::new(
+            &config.storage.garbage_collection,
+            pending_store.clone(),
+            state_store.clone(),
+        ) {
+            tokio::spawn(garbage_collector.run(rollup_manager.clone(), cancellation_token.clone()));
+
+            info!("Storage garbage collector started.");
+        }
+
+        let maintenance = Arc::new(Maintenance::default());
+        let maintenance_task = MaintenanceTask::new(
+            maintenance.clone(),
+            orchestrator_state.clone(),
+            vec![("state", state_db), ("pending", pending_db)],
+        );
+        tokio::spawn(maintenance_task.run(cancellation_token.clone()));
+
+        // Set up the core service object.
+        let service = Arc::new(AgglayerService::new(core));
+        let screening = agglayer_rpc::screening::from_config(&config.rpc.intake.screening)?;
+        let rpc_service = Arc::new(
+            agglayer_rpc::AgglayerService::new(
+                data_sender.clone(),
+                pending_store.clone(),
+                state_store.clone(),
+                debug_store.clone(),
+                epochs_store.clone(),
+                config.clone(),
+                Arc::clone(&rollup_manager),
+            )
+            .with_maintenance(maintenance.clone())
+            .with_version_info(VersionInfo::new(
+                build_info,
+                Digest(certifier_client.pessimistic_vkey()),
+            ))
+            .with_clock(clock_ref)
+            .with_screening(screening),
+        );
+
+        let certifier_client = Arc::new(certifier_client);
         let admin_router = AdminAgglayerImpl::new(
             data_sender,
             pending_store.clone(),
             state_store.clone(),
             debug_store.clone(),
             config.clone(),
+            orchestrator_state.clone(),
         )
+        .with_maintenance(maintenance)
+        .with_scrub_report(scrub_report)
+        .with_compactor(compactor)
+        .with_manual_clock(manual_clock)
+        .with_native_executor(certifier_client.clone())
         .start()
         .await
         .context("Failed starting admin router")?;
 
         // Bind the core to the RPC server.
-        let json_rpc_router = AgglayerImpl::new(service, rpc_service.clone())
+        let mut json_rpc = AgglayerImpl::new(service, rpc_service.clone(), epoch_events)
+            .with_proving_cost_estimator(certifier_client)
+            .with_orchestrator_state(orchestrator_state);
+        if config.rpc.sign_responses {
+            // The first signer is owned by the L1 provider wallet.
+            let response_signer = ConfiguredSigner::new(config.clone()).await?;
+            info!(
+                "Signing the certificate headers with {}",
+                response_signer.address()
+            );
+            json_rpc = json_rpc.with_response_signer(Arc::new(response_signer));
+        }
+        let json_rpc_router = json_rpc
             .start()
             .await
             .context("Failed starting JSON-RPC router")?;
 
+        let graphql_router = config
+            .rpc
+            .graphql
+            .enabled
+            .then(|| agglayer_graphql_api::router(&config.rpc.graphql, rpc_service.clone()));
+
         let public_grpc_router =
             agglayer_grpc_api::Server::with_config(config.clone(), rpc_service)
                 .build()
@@ -295,25 +639,37 @@ impl Node {
 
         let health_router = api::rest::health_router();
 
-        let readrpc_router = axum::Router::new()
+        let mut readrpc_router = axum::Router::new()
             .merge(health_router)
             .merge(json_rpc_router);
+        if let Some(graphql_router) = graphql_router {
+            info!(
+                path = agglayer_graphql_api::GRAPHQL_PATH,
+                "GraphQL endpoint enabled"
+            );
+            readrpc_router = readrpc_router.merge(graphql_router);
+        }
 
         let readrpc_listener = tokio::net::TcpListener::bind(config.readrpc_addr()).await?;
         let public_grpc_listener = tokio::net::TcpListener::bind(config.public_grpc_addr()).await?;
-        let admin_listener = tokio::net::TcpListener::bind(config.admin_rpc_addr()).await?;
         info!(on = %config.readrpc_addr(), "ReadRPC listening");
         info!(on = %config.public_grpc_addr(), "Public gRPC listening");
-        info!(on = %config.admin_rpc_addr(), "AdminRPC listening");
 
-        let readrpc_server = axum::serve(readrpc_listener, readrpc_router)
-            .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
+        // Serve with the connection info to record the address of the
+        // certificate submitters in the audit log.
+        let readrpc_server = axum::serve(
+            readrpc_listener,
+            readrpc_router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
 
-        let public_grpc_server = axum::serve(public_grpc_listener, public_grpc_router)
-            .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
+        let public_grpc_server = axum::serve(
+            public_grpc_listener,
+            public_grpc_router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
 
-        let admin_server = axum::serve(admin_listener, admin_router)
-            .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
+        let admin_server = serve_admin(&config, admin_router, cancellation_token.clone()).await?;
 
         let rpc_handle = tokio::spawn(async move {
             tokio::select! {
@@ -344,3 +700,45 @@ impl Node {
         debug!("Node shutdown completed.");
     }
 }
+
+/// Serve the AdminRPC router on its unix socket if configured, or on its
+/// port restricted to the allowed networks otherwise.
+async fn serve_admin(
+    config: &Config,
+    admin_router: axum::Router,
+    cancellation_token: CancellationToken,
+) -> eyre::Result<BoxFuture<'static, std::io::Result<()>>> {
+    let shutdown = cancellation_token.cancelled_owned();
+
+    if let Some(path) = &config.rpc.admin.unix_socket {
+        // Remove the socket left over by a previous run, if any.
+        if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+            std::fs::remove_file(path).context("Failed to remove the AdminRPC unix socket")?;
+        }
+
+        let listener = tokio::net::UnixListener::bind(path)
+            .context("Failed to bind the AdminRPC unix socket")?;
+        info!(on = %path.display(), "AdminRPC listening");
+
+        return Ok(axum::serve(listener, admin_router)
+            .with_graceful_shutdown(shutdown)
+            .into_future()
+            .boxed());
+    }
+
+    let listener = tokio::net::TcpListener::bind(config.admin_rpc_addr()).await?;
+    info!(
+        on = %config.admin_rpc_addr(),
+        allowed_ips = ?config.rpc.admin.allowed_ips,
+        "AdminRPC listening"
+    );
+
+    let admin_router = api::admin::allow_list(admin_router, &config.rpc.admin.allowed_ips);
+    Ok(axum::serve(
+        listener,
+        admin_router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown)
+    .into_future()
+    .boxed())
+}
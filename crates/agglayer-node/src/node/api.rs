@@ -1,3 +1,4 @@
+pub(crate) mod admin;
 pub(crate) mod rest;
 
 #[cfg(test)]
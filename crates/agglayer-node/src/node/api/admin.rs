@@ -0,0 +1,41 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse as _, Response},
+};
+use hyper::StatusCode;
+use ipnet::IpNet;
+use tracing::warn;
+
+/// Restrict the router to the connections from the given networks, all the
+/// connections being accepted if none is given.
+///
+/// The router must be served with the connection info.
+pub(crate) fn allow_list(router: axum::Router, allowed_ips: &[IpNet]) -> axum::Router {
+    if allowed_ips.is_empty() {
+        return router;
+    }
+
+    router.layer(axum::middleware::from_fn_with_state(
+        Arc::<[IpNet]>::from(allowed_ips),
+        check_peer,
+    ))
+}
+
+async fn check_peer(
+    State(allowed_ips): State<Arc<[IpNet]>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // IPv4 peers of a dual-stack listener are seen as IPv4-mapped IPv6 ones.
+    let ip = peer.ip().to_canonical();
+    if allowed_ips.iter().any(|network| network.contains(&ip)) {
+        return next.run(request).await;
+    }
+
+    warn!(%peer, "Rejected an admin request from outside the allow-list");
+    StatusCode::FORBIDDEN.into_response()
+}
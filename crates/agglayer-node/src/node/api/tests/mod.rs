@@ -41,3 +41,44 @@ async fn healthcheck_method_can_be_called() {
     assert_eq!(out.as_str(), "{\"health\":true}");
     token.cancel();
 }
+
+#[test_log::test(tokio::test)]
+async fn admin_requests_are_restricted_to_the_allow_list() {
+    async fn status(allowed_ips: &[&str]) -> http::StatusCode {
+        let allowed_ips = allowed_ips
+            .iter()
+            .map(|network| network.parse().unwrap())
+            .collect::<Vec<_>>();
+        let router = api::admin::allow_list(api::rest::health_router(), &allowed_ips);
+        let addr = TestContext::next_available_address();
+        let listener = TcpListener::bind(addr).await.unwrap();
+
+        let token = CancellationToken::new();
+        tokio::spawn(
+            axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+            )
+            .with_graceful_shutdown(token.clone().cancelled_owned())
+            .into_future(),
+        );
+
+        let http_client = Client::builder(TokioExecutor::new()).build_http();
+        let req = Request::builder()
+            .method("GET")
+            .uri(format!("http://{addr}/health"))
+            .body(Empty::<hyper::body::Bytes>::new())
+            .expect("request builder");
+        let status = http_client.request(req).await.unwrap().status();
+
+        token.cancel();
+        status
+    }
+
+    assert_eq!(status(&[]).await, http::StatusCode::OK);
+    assert_eq!(status(&["127.0.0.0/8"]).await, http::StatusCode::OK);
+    assert_eq!(
+        status(&["10.0.0.0/8", "::1/128"]).await,
+        http::StatusCode::FORBIDDEN
+    );
+}
@@ -0,0 +1,538 @@
+//! Notification of the final status of the certificates to the callback URLs
+//! registered on submission.
+//!
+//! The registered callbacks are persisted in the state storage and checked
+//! periodically. Once a certificate reaches a final status, its header is
+//! posted as JSON to the callback URL, signed with HMAC-SHA256 when a secret
+//! is configured for its network.
+//!
+//! The callback of a settled certificate is dropped once delivered or given up
+//! on. A certificate in error can still be resubmitted and settle, so each of
+//! its errors is notified once and the callback is kept until it settles or
+//! another certificate replaces it at its height. The errors already notified
+//! are tracked in memory, so they are notified again after a restart.
+//!
+//! The callbacks are only posted to the hosts allowed by the configuration,
+//! which refuses the non-public addresses by default, the addresses the hosts
+//! resolve to included, and redirects are not followed.
+
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use agglayer_config::outbound::OutboundCallbackConfig;
+use agglayer_storage::{
+    columns::callback_per_certificate::CertificateCallback,
+    stores::{StateReader, StateWriter},
+};
+use agglayer_types::{CertificateHeader, CertificateId, CertificateStatus};
+use hmac::{Hmac, Mac as _};
+use sha2::Sha256;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Header carrying the HMAC-SHA256 signature of the payload.
+pub(crate) const SIGNATURE_HEADER: &str = "X-Agglayer-Signature";
+
+/// Header carrying the id of the certificate the payload relates to.
+pub(crate) const CERTIFICATE_ID_HEADER: &str = "X-Agglayer-Certificate-Id";
+
+/// Task delivering the certificate status callbacks.
+pub(crate) struct CallbackNotifier<StateStore> {
+    config: Arc<OutboundCallbackConfig>,
+    state_store: Arc<StateStore>,
+    client: reqwest::Client,
+    /// Certificates for which a delivery is ongoing.
+    in_flight: Arc<Mutex<HashSet<CertificateId>>>,
+    /// Errors notified for the certificates whose callback is kept.
+    notified: Arc<Mutex<HashMap<CertificateId, CertificateStatus>>>,
+}
+
+impl<StateStore> CallbackNotifier<StateStore>
+where
+    StateStore: StateReader + StateWriter + 'static,
+{
+    pub(crate) fn try_new(
+        config: OutboundCallbackConfig,
+        state_store: Arc<StateStore>,
+    ) -> eyre::Result<Self> {
+        let config = Arc::new(config);
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(CallbackResolver {
+                config: config.clone(),
+            }))
+            .build()?;
+
+        Ok(Self {
+            config,
+            state_store,
+            client,
+            in_flight: Default::default(),
+            notified: Default::default(),
+        })
+    }
+
+    /// Check the pending callbacks until cancelled.
+    pub(crate) async fn run(self, cancellation_token: CancellationToken) {
+        loop {
+            if let Err(error) = self.poll() {
+                warn!(?error, "Failed to check the pending certificate callbacks");
+            }
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Certificate callback notifier cancelled");
+                    return;
+                }
+                _ = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+    }
+
+    /// Spawn the delivery of the callbacks of the certificates which reached a
+    /// final status not notified yet, and drop the callbacks of the replaced
+    /// certificates.
+    fn poll(&self) -> Result<(), agglayer_storage::error::Error> {
+        for (certificate_id, callback) in self.state_store.get_certificate_callbacks()? {
+            let Some(header) = self.state_store.get_certificate_header(&certificate_id)? else {
+                continue;
+            };
+
+            let keep = match &header.status {
+                CertificateStatus::Settled => false,
+                CertificateStatus::InError { .. } => {
+                    if self.is_replaced(&header)? {
+                        debug!(%certificate_id, "Dropping the callback of a replaced certificate");
+                        self.state_store
+                            .remove_certificate_callback(&certificate_id)?;
+                        lock(&self.notified).remove(&certificate_id);
+                        continue;
+                    }
+
+                    if lock(&self.notified).get(&certificate_id) == Some(&header.status) {
+                        continue;
+                    }
+
+                    true
+                }
+                _ => {
+                    // The certificate was resubmitted, its next error is
+                    // notified as well.
+                    lock(&self.notified).remove(&certificate_id);
+                    continue;
+                }
+            };
+
+            if !lock(&self.in_flight).insert(certificate_id) {
+                continue;
+            }
+
+            let delivery = Delivery {
+                config: self.config.clone(),
+                state_store: self.state_store.clone(),
+                client: self.client.clone(),
+                in_flight: self.in_flight.clone(),
+                notified: self.notified.clone(),
+            };
+            tokio::spawn(delivery.run(callback, header, keep));
+        }
+
+        Ok(())
+    }
+
+    /// Whether another certificate took the height of the certificate.
+    fn is_replaced(
+        &self,
+        header: &CertificateHeader,
+    ) -> Result<bool, agglayer_storage::error::Error> {
+        Ok(self
+            .state_store
+            .get_certificate_header_by_cursor(header.network_id, header.height)?
+            .is_some_and(|current| current.certificate_id != header.certificate_id))
+    }
+}
+
+/// Delivery of a single callback.
+struct Delivery<StateStore> {
+    config: Arc<OutboundCallbackConfig>,
+    state_store: Arc<StateStore>,
+    client: reqwest::Client,
+    in_flight: Arc<Mutex<HashSet<CertificateId>>>,
+    notified: Arc<Mutex<HashMap<CertificateId, CertificateStatus>>>,
+}
+
+impl<StateStore> Delivery<StateStore>
+where
+    StateStore: StateWriter,
+{
+    /// Deliver the callback, then drop it unless it has to be kept for the
+    /// next status of the certificate.
+    async fn run(self, callback: CertificateCallback, header: CertificateHeader, keep: bool) {
+        let certificate_id = header.certificate_id;
+
+        match self.deliver(&callback, &header).await {
+            Ok(()) => debug!(%certificate_id, url = callback.url, "Certificate callback delivered"),
+            Err(error) => error!(
+                ?error,
+                %certificate_id,
+                url = callback.url,
+                "Giving up on the delivery of the certificate callback"
+            ),
+        }
+
+        if keep {
+            lock(&self.notified).insert(certificate_id, header.status);
+        } else {
+            if let Err(error) = self
+                .state_store
+                .remove_certificate_callback(&certificate_id)
+            {
+                error!(?error, %certificate_id, "Failed to remove the certificate callback");
+            }
+            lock(&self.notified).remove(&certificate_id);
+        }
+
+        lock(&self.in_flight).remove(&certificate_id);
+    }
+
+    /// Post the header to the callback URL, retrying with an exponential
+    /// backoff until the configured number of attempts is reached.
+    async fn deliver(
+        &self,
+        callback: &CertificateCallback,
+        header: &CertificateHeader,
+    ) -> eyre::Result<()> {
+        // The resolver only checks the hosts given by name.
+        let url = reqwest::Url::parse(&callback.url)?;
+        if !self.config.is_allowed_url(&url) {
+            eyre::bail!("The callback host is not allowed");
+        }
+
+        let body = serde_json::to_vec(header)?;
+        let signature = self
+            .config
+            .hmac_secret(header.network_id)
+            .map(|secret| sign(secret, &body));
+
+        let mut retry_interval = self.config.retry_interval;
+        let mut attempt = 1;
+        loop {
+            let mut request = self
+                .client
+                .post(url.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(CERTIFICATE_ID_HEADER, header.certificate_id.to_string())
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+
+            let error = match request.send().await.and_then(|r| r.error_for_status()) {
+                Ok(_) => return Ok(()),
+                Err(error) => error,
+            };
+
+            if attempt >= self.config.max_attempts {
+                return Err(error.into());
+            }
+
+            warn!(
+                ?error,
+                certificate_id = %header.certificate_id,
+                attempt,
+                "Failed to deliver the certificate callback, retrying in {retry_interval:?}"
+            );
+            tokio::time::sleep(retry_interval).await;
+            retry_interval = retry_interval.saturating_mul(2);
+            attempt += 1;
+        }
+    }
+}
+
+/// Resolver of the callback hosts, leaving out the addresses the callbacks
+/// cannot be posted to.
+struct CallbackResolver {
+    config: Arc<OutboundCallbackConfig>,
+}
+
+impl CallbackResolver {
+    async fn resolve_allowed(
+        config: Arc<OutboundCallbackConfig>,
+        name: reqwest::dns::Name,
+    ) -> Result<reqwest::dns::Addrs, Box<dyn std::error::Error + Send + Sync>> {
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+            .await?
+            .filter(|addr| config.is_allowed_address(addr.ip()))
+            .collect();
+        if addrs.is_empty() {
+            return Err(format!("{} resolves to no allowed address", name.as_str()).into());
+        }
+
+        Ok(Box::new(addrs.into_iter()))
+    }
+}
+
+impl reqwest::dns::Resolve for CallbackResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(Self::resolve_allowed(self.config.clone(), name))
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Signature of the payload, formatted as `sha256=<hex digest>`.
+pub(crate) fn sign(secret: &str, payload: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use agglayer_storage::tests::mocks::MockStateStore;
+    use agglayer_types::{CertificateStatusError, Height, Metadata, NetworkId};
+    use axum::{http::HeaderMap, routing::post, Router};
+    use mockall::predicate::eq;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn header(status: CertificateStatus) -> CertificateHeader {
+        CertificateHeader {
+            network_id: NetworkId::new(1),
+            height: Height::ZERO,
+            epoch_number: None,
+            certificate_index: None,
+            certificate_id: CertificateId::new([1; 32].into()),
+            prev_local_exit_root: [0; 32].into(),
+            new_local_exit_root: [1; 32].into(),
+            metadata: Metadata::ZERO,
+            status,
+            settlement_tx_hash: None,
+        }
+    }
+
+    fn in_error() -> CertificateStatus {
+        CertificateStatus::error(CertificateStatusError::InternalError("failure".to_string()))
+    }
+
+    /// Server recording the callbacks posted to the returned URL.
+    async fn callback_server() -> (String, mpsc::UnboundedReceiver<(HeaderMap, String)>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let router = Router::new().route(
+            "/callback",
+            post(move |headers: HeaderMap, body: String| {
+                let sender = sender.clone();
+                async move {
+                    sender.send((headers, body)).unwrap();
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/callback", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        (url, receiver)
+    }
+
+    /// State store holding the callback and the header of a certificate.
+    fn state_store(url: String, header: CertificateHeader) -> MockStateStore {
+        let certificate_id = header.certificate_id;
+
+        let mut state_store = MockStateStore::new();
+        state_store
+            .expect_get_certificate_callbacks()
+            .returning(move || {
+                Ok(vec![(
+                    certificate_id,
+                    CertificateCallback { url: url.clone() },
+                )])
+            });
+        state_store
+            .expect_get_certificate_header()
+            .with(eq(certificate_id))
+            .returning(move |_| Ok(Some(header.clone())));
+
+        state_store
+    }
+
+    /// Config of a notifier posting to the local test server.
+    fn local_config() -> OutboundCallbackConfig {
+        OutboundCallbackConfig {
+            poll_interval: Duration::from_millis(10),
+            allow_private_addresses: true,
+            ..Default::default()
+        }
+    }
+
+    async fn wait_for_deliveries<StateStore>(notifier: &CallbackNotifier<StateStore>) {
+        while !notifier.in_flight.lock().unwrap().is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    #[test]
+    fn signature_is_the_hex_encoded_hmac() {
+        // Test vector from RFC 4231, test case 2.
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn settled_certificate_is_posted_and_callback_removed() {
+        let (url, mut receiver) = callback_server().await;
+        let header = header(CertificateStatus::Settled);
+        let certificate_id = header.certificate_id;
+
+        let mut state_store = state_store(url, header.clone());
+        state_store
+            .expect_remove_certificate_callback()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+
+        let config = OutboundCallbackConfig {
+            hmac_secrets: [(1, "secret".to_string()), (2, "other".to_string())].into(),
+            ..local_config()
+        };
+        let notifier = CallbackNotifier::try_new(config, Arc::new(state_store)).unwrap();
+        notifier.poll().unwrap();
+
+        let (headers, body) = receiver.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<CertificateHeader>(&body).unwrap(),
+            header
+        );
+        assert_eq!(
+            headers[CERTIFICATE_ID_HEADER].to_str().unwrap(),
+            certificate_id.to_string()
+        );
+        assert_eq!(
+            headers[SIGNATURE_HEADER].to_str().unwrap(),
+            sign("secret", body.as_bytes())
+        );
+
+        wait_for_deliveries(&notifier).await;
+    }
+
+    #[tokio::test]
+    async fn in_error_certificate_is_notified_once_and_callback_kept() {
+        let (url, mut receiver) = callback_server().await;
+        let header = header(in_error());
+        let certificate_id = header.certificate_id;
+
+        let mut state_store = state_store(url, header.clone());
+        state_store
+            .expect_get_certificate_header_by_cursor()
+            .with(eq(header.network_id), eq(header.height))
+            .returning({
+                let header = header.clone();
+                move |_, _| Ok(Some(header.clone()))
+            });
+        state_store.expect_remove_certificate_callback().never();
+
+        let notifier = CallbackNotifier::try_new(local_config(), Arc::new(state_store)).unwrap();
+        notifier.poll().unwrap();
+
+        let (_, body) = receiver.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<CertificateHeader>(&body).unwrap(),
+            header
+        );
+        wait_for_deliveries(&notifier).await;
+        assert_eq!(
+            notifier.notified.lock().unwrap().get(&certificate_id),
+            Some(&header.status)
+        );
+
+        // The same error is not notified again.
+        notifier.poll().unwrap();
+        assert!(notifier.in_flight.lock().unwrap().is_empty());
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn callback_of_a_replaced_certificate_is_dropped() {
+        let header = header(in_error());
+        let certificate_id = header.certificate_id;
+
+        let mut state_store =
+            state_store("http://127.0.0.1:1/callback".to_string(), header.clone());
+        state_store
+            .expect_get_certificate_header_by_cursor()
+            .returning({
+                let header = header.clone();
+                move |_, _| {
+                    Ok(Some(CertificateHeader {
+                        certificate_id: CertificateId::new([2; 32].into()),
+                        status: CertificateStatus::Pending,
+                        ..header.clone()
+                    }))
+                }
+            });
+        state_store
+            .expect_remove_certificate_callback()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+
+        let notifier = CallbackNotifier::try_new(local_config(), Arc::new(state_store)).unwrap();
+        notifier.poll().unwrap();
+
+        assert!(notifier.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn private_callback_address_is_refused_by_default() {
+        let (url, mut receiver) = callback_server().await;
+        let header = header(CertificateStatus::Settled);
+        let certificate_id = header.certificate_id;
+
+        let mut state_store = state_store(url, header);
+        state_store
+            .expect_remove_certificate_callback()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+
+        let config = OutboundCallbackConfig {
+            max_attempts: 1,
+            ..Default::default()
+        };
+        let notifier = CallbackNotifier::try_new(config, Arc::new(state_store)).unwrap();
+        notifier.poll().unwrap();
+        wait_for_deliveries(&notifier).await;
+
+        assert!(receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn pending_certificate_is_not_notified() {
+        let mut state_store = state_store(
+            "http://127.0.0.1:1/callback".to_string(),
+            header(CertificateStatus::Pending),
+        );
+        state_store.expect_remove_certificate_callback().never();
+
+        let notifier =
+            CallbackNotifier::try_new(OutboundCallbackConfig::default(), Arc::new(state_store))
+                .unwrap();
+        notifier.poll().unwrap();
+
+        assert!(notifier.in_flight.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,510 @@
+//! Self-diagnostics of the node, raising alerts on operational issues.
+//!
+//! The alerting rules are evaluated periodically on the orchestrator state,
+//! the state storage and the L1 head. An alert is raised when a rule starts
+//! being triggered and resolved once it is not anymore. The state of every
+//! rule is exported as a metric, and the raised and resolved alerts are
+//! logged and posted as JSON to the configured webhooks.
+
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use agglayer_certificate_orchestrator::{OrchestratorState, ProvingStats};
+use agglayer_config::diagnostics::DiagnosticsConfig;
+use agglayer_storage::stores::StateReader;
+use agglayer_types::EpochNumber;
+use alloy::providers::Provider;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Alerting rule evaluated by the diagnostics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Rule {
+    /// No certificate was settled for too many epochs.
+    NoSettlement,
+    /// Too many proof generations failed.
+    ProvingFailureRate,
+    /// The block clock lags too far behind the L1 head.
+    L1Lag,
+}
+
+impl Rule {
+    const ALL: [Rule; 3] = [Rule::NoSettlement, Rule::ProvingFailureRate, Rule::L1Lag];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Rule::NoSettlement => "no_settlement",
+            Rule::ProvingFailureRate => "proving_failure_rate",
+            Rule::L1Lag => "l1_lag",
+        }
+    }
+}
+
+/// Whether an alert is raised or resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AlertStatus {
+    Firing,
+    Resolved,
+}
+
+/// Payload posted to the webhooks.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct Alert {
+    pub(crate) rule: Rule,
+    pub(crate) status: AlertStatus,
+    pub(crate) message: String,
+    /// Seconds since the UNIX epoch.
+    pub(crate) timestamp: u64,
+}
+
+/// L1 head the block clock is compared to.
+pub(crate) struct L1Head<P> {
+    pub(crate) provider: P,
+    /// The L1 block the block clock starts counting from.
+    pub(crate) genesis_block: u64,
+}
+
+/// Values the rules are evaluated on.
+#[derive(Clone, Copy, Debug, Default)]
+struct Observations {
+    current_epoch: EpochNumber,
+    latest_settled_epoch: Option<EpochNumber>,
+    /// Whether certificates are waiting to be processed.
+    has_pending_work: bool,
+    proving: ProvingStats,
+    /// Number of blocks the block clock lags behind the L1 head, if known.
+    l1_lag: Option<u64>,
+}
+
+/// Evaluation of the rules, keeping track of the values they are evaluated
+/// against between two evaluations.
+struct Evaluator {
+    config: DiagnosticsConfig,
+    /// Last epoch at which a certificate was settled or no certificate was
+    /// waiting to be processed.
+    last_progress_epoch: Option<EpochNumber>,
+    /// Proving outcomes at the start of the current window.
+    proving_window_start: ProvingStats,
+    /// Outcome of the proving failure rate rule on the last complete window.
+    proving_verdict: Option<String>,
+}
+
+impl Evaluator {
+    fn new(config: DiagnosticsConfig) -> Self {
+        Self {
+            config,
+            last_progress_epoch: None,
+            proving_window_start: ProvingStats::default(),
+            proving_verdict: None,
+        }
+    }
+
+    /// Evaluate every rule, returning the alert message of the triggered ones.
+    fn evaluate(&mut self, observations: &Observations) -> Vec<(Rule, Option<String>)> {
+        Rule::ALL
+            .into_iter()
+            .map(|rule| {
+                let verdict = match rule {
+                    Rule::NoSettlement => self.no_settlement(observations),
+                    Rule::ProvingFailureRate => self.proving_failure_rate(observations),
+                    Rule::L1Lag => self.l1_lag(observations),
+                };
+
+                (rule, verdict)
+            })
+            .collect()
+    }
+
+    fn no_settlement(&mut self, observations: &Observations) -> Option<String> {
+        let current_epoch = observations.current_epoch;
+        let last_progress_epoch = if observations.has_pending_work {
+            self.last_progress_epoch
+                .max(observations.latest_settled_epoch)
+                .unwrap_or(current_epoch)
+        } else {
+            current_epoch
+        };
+        self.last_progress_epoch = Some(last_progress_epoch);
+
+        let max_epochs = self.config.max_epochs_without_settlement;
+        let epochs = current_epoch
+            .as_u64()
+            .saturating_sub(last_progress_epoch.as_u64());
+        (max_epochs > 0 && epochs >= max_epochs).then(|| {
+            format!("No certificate settled for {epochs} epochs while certificates are pending")
+        })
+    }
+
+    fn proving_failure_rate(&mut self, observations: &Observations) -> Option<String> {
+        let max_percent = u64::from(self.config.max_proving_failure_percent);
+        if max_percent >= 100 {
+            return None;
+        }
+
+        let succeeded = observations
+            .proving
+            .succeeded
+            .saturating_sub(self.proving_window_start.succeeded);
+        let failed = observations
+            .proving
+            .failed
+            .saturating_sub(self.proving_window_start.failed);
+        let attempts = succeeded + failed;
+        if attempts < self.config.min_proving_attempts.max(1) {
+            return self.proving_verdict.clone();
+        }
+
+        self.proving_window_start = observations.proving;
+        self.proving_verdict = (failed * 100 > max_percent * attempts)
+            .then(|| format!("{failed} out of the last {attempts} proof generations failed"));
+
+        self.proving_verdict.clone()
+    }
+
+    fn l1_lag(&self, observations: &Observations) -> Option<String> {
+        let max_lag = self.config.max_l1_lag_blocks;
+        let lag = observations.l1_lag?;
+
+        (max_lag > 0 && lag > max_lag)
+            .then(|| format!("The block clock lags {lag} blocks behind the L1 head"))
+    }
+}
+
+/// Task evaluating the alerting rules.
+pub(crate) struct Diagnostics<StateStore, P> {
+    evaluator: Evaluator,
+    state_store: Arc<StateStore>,
+    orchestrator_state: Arc<OrchestratorState>,
+    l1_head: Option<L1Head<P>>,
+    client: reqwest::Client,
+    /// Rules whose alert is currently firing.
+    firing: HashSet<Rule>,
+}
+
+impl<StateStore, P> Diagnostics<StateStore, P>
+where
+    StateStore: StateReader,
+    P: Provider,
+{
+    pub(crate) fn try_new(
+        config: DiagnosticsConfig,
+        state_store: Arc<StateStore>,
+        orchestrator_state: Arc<OrchestratorState>,
+        l1_head: Option<L1Head<P>>,
+    ) -> eyre::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.webhook_timeout)
+            .build()?;
+
+        Ok(Self {
+            evaluator: Evaluator::new(config),
+            state_store,
+            orchestrator_state,
+            l1_head,
+            client,
+            firing: HashSet::new(),
+        })
+    }
+
+    /// Evaluate the rules periodically until cancelled.
+    pub(crate) async fn run(mut self, cancellation_token: CancellationToken) {
+        let mut interval = tokio::time::interval(self.evaluator.config.evaluation_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Diagnostics task cancelled");
+                    return;
+                }
+                _ = interval.tick() => {
+                    match self.observe().await {
+                        Ok(observations) => self.evaluate(&observations),
+                        Err(error) => warn!(?error, "Failed to gather the diagnostics observations"),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn observe(&self) -> eyre::Result<Observations> {
+        let snapshot = self.orchestrator_state.snapshot();
+        let has_pending_work = snapshot
+            .networks
+            .values()
+            .any(|network| network.in_flight.is_some() || network.queued_certificates > 0);
+
+        let l1_lag = match &self.l1_head {
+            Some(l1_head) => {
+                let head = l1_head.provider.get_block_number().await?;
+                Some(
+                    head.saturating_sub(l1_head.genesis_block)
+                        .saturating_sub(snapshot.clock.block_height),
+                )
+            }
+            None => None,
+        };
+
+        Ok(Observations {
+            current_epoch: snapshot.clock.current_epoch,
+            latest_settled_epoch: self.state_store.get_latest_settled_epoch()?,
+            has_pending_work,
+            proving: snapshot.proving,
+            l1_lag,
+        })
+    }
+
+    /// Evaluate the rules and raise or resolve the alerts accordingly.
+    fn evaluate(&mut self, observations: &Observations) {
+        debug!(?observations, "Evaluating the diagnostics rules");
+
+        for (rule, verdict) in self.evaluator.evaluate(observations) {
+            agglayer_telemetry::diagnostics::record_alert_state(rule.name(), verdict.is_some());
+
+            match verdict {
+                Some(message) if self.firing.insert(rule) => {
+                    warn!(rule = rule.name(), "Alert raised: {message}");
+                    agglayer_telemetry::diagnostics::record_alert_raised(rule.name());
+                    self.notify(rule, AlertStatus::Firing, message);
+                }
+                None if self.firing.remove(&rule) => {
+                    info!(rule = rule.name(), "Alert resolved");
+                    self.notify(rule, AlertStatus::Resolved, "Resolved".to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Post the alert to the webhooks, in the background.
+    fn notify(&self, rule: Rule, status: AlertStatus, message: String) {
+        let alert = Alert {
+            rule,
+            status,
+            message,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+
+        for url in &self.evaluator.config.webhook_urls {
+            let request = self.client.post(url.clone()).json(&alert);
+            let url = url.clone();
+            tokio::spawn(async move {
+                if let Err(error) = request.send().await.and_then(|r| r.error_for_status()) {
+                    warn!(?error, %url, "Failed to post the alert to the webhook");
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, sync::atomic::AtomicU64, time::Duration};
+
+    use agglayer_clock::ClockRef;
+    use agglayer_storage::tests::mocks::MockStateStore;
+    use alloy::providers::RootProvider;
+    use axum::{routing::post, Json, Router};
+    use tokio::sync::{broadcast, mpsc};
+
+    use super::*;
+
+    fn observations(current_epoch: u64) -> Observations {
+        Observations {
+            current_epoch: EpochNumber::new(current_epoch),
+            ..Default::default()
+        }
+    }
+
+    fn proving(succeeded: u64, failed: u64) -> ProvingStats {
+        ProvingStats { succeeded, failed }
+    }
+
+    fn verdict(evaluator: &mut Evaluator, rule: Rule, observations: &Observations) -> bool {
+        evaluator
+            .evaluate(observations)
+            .into_iter()
+            .find(|(r, _)| *r == rule)
+            .unwrap()
+            .1
+            .is_some()
+    }
+
+    #[test]
+    fn no_settlement_only_fires_while_certificates_are_pending() {
+        let mut evaluator = Evaluator::new(DiagnosticsConfig {
+            max_epochs_without_settlement: 3,
+            ..Default::default()
+        });
+
+        // Idle epochs don't count.
+        assert!(!verdict(
+            &mut evaluator,
+            Rule::NoSettlement,
+            &observations(10)
+        ));
+
+        let pending = |current_epoch, latest_settled_epoch: Option<u64>| Observations {
+            has_pending_work: true,
+            latest_settled_epoch: latest_settled_epoch.map(EpochNumber::new),
+            ..observations(current_epoch)
+        };
+        assert!(!verdict(
+            &mut evaluator,
+            Rule::NoSettlement,
+            &pending(12, Some(2))
+        ));
+        assert!(verdict(
+            &mut evaluator,
+            Rule::NoSettlement,
+            &pending(13, Some(2))
+        ));
+
+        // A settlement resolves the alert.
+        assert!(!verdict(
+            &mut evaluator,
+            Rule::NoSettlement,
+            &pending(14, Some(14))
+        ));
+        assert!(verdict(
+            &mut evaluator,
+            Rule::NoSettlement,
+            &pending(17, Some(14))
+        ));
+    }
+
+    #[test]
+    fn proving_failure_rate_is_evaluated_on_complete_windows() {
+        let mut evaluator = Evaluator::new(DiagnosticsConfig {
+            max_proving_failure_percent: 50,
+            min_proving_attempts: 4,
+            ..Default::default()
+        });
+        let with_proving = |succeeded, failed| Observations {
+            proving: proving(succeeded, failed),
+            ..observations(0)
+        };
+
+        // Not enough attempts yet.
+        assert!(!verdict(
+            &mut evaluator,
+            Rule::ProvingFailureRate,
+            &with_proving(0, 3)
+        ));
+        assert!(verdict(
+            &mut evaluator,
+            Rule::ProvingFailureRate,
+            &with_proving(1, 3)
+        ));
+        // The verdict is kept until the next window is complete.
+        assert!(verdict(
+            &mut evaluator,
+            Rule::ProvingFailureRate,
+            &with_proving(3, 3)
+        ));
+        assert!(!verdict(
+            &mut evaluator,
+            Rule::ProvingFailureRate,
+            &with_proving(4, 5)
+        ));
+    }
+
+    #[test]
+    fn l1_lag_is_only_evaluated_when_known() {
+        let mut evaluator = Evaluator::new(DiagnosticsConfig {
+            max_l1_lag_blocks: 10,
+            ..Default::default()
+        });
+        let with_lag = |l1_lag| Observations {
+            l1_lag,
+            ..observations(0)
+        };
+
+        assert!(!verdict(&mut evaluator, Rule::L1Lag, &with_lag(None)));
+        assert!(!verdict(&mut evaluator, Rule::L1Lag, &with_lag(Some(10))));
+        assert!(verdict(&mut evaluator, Rule::L1Lag, &with_lag(Some(11))));
+    }
+
+    #[test]
+    fn disabled_rules_never_fire() {
+        let mut evaluator = Evaluator::new(DiagnosticsConfig {
+            max_epochs_without_settlement: 0,
+            max_proving_failure_percent: 100,
+            max_l1_lag_blocks: 0,
+            ..Default::default()
+        });
+        let observations = Observations {
+            current_epoch: EpochNumber::new(100),
+            latest_settled_epoch: Some(EpochNumber::ZERO),
+            has_pending_work: true,
+            proving: proving(0, 100),
+            l1_lag: Some(1000),
+        };
+
+        assert!(evaluator
+            .evaluate(&observations)
+            .into_iter()
+            .all(|(_, verdict)| verdict.is_none()));
+    }
+
+    #[tokio::test]
+    async fn raised_and_resolved_alerts_are_posted() {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let router = Router::new().route(
+            "/alerts",
+            post(move |Json(alert): Json<serde_json::Value>| {
+                let sender = sender.clone();
+                async move {
+                    sender.send(alert).unwrap();
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/alerts", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let clock = ClockRef::new(
+            broadcast::channel(1).0,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(NonZeroU64::new(1).unwrap()),
+        );
+        let config = DiagnosticsConfig {
+            webhook_urls: vec![url.parse().unwrap()],
+            webhook_timeout: Duration::from_secs(1),
+            ..Default::default()
+        };
+        let mut diagnostics = Diagnostics::<_, RootProvider>::try_new(
+            config,
+            Arc::new(MockStateStore::new()),
+            Arc::new(OrchestratorState::new(clock)),
+            None,
+        )
+        .unwrap();
+
+        let lagging = Observations {
+            l1_lag: Some(100),
+            ..observations(0)
+        };
+        diagnostics.evaluate(&lagging);
+        let alert = receiver.recv().await.unwrap();
+        assert_eq!(alert["rule"], "l1_lag");
+        assert_eq!(alert["status"], "firing");
+
+        // Still firing, not posted again.
+        diagnostics.evaluate(&lagging);
+        diagnostics.evaluate(&observations(0));
+        let alert = receiver.recv().await.unwrap();
+        assert_eq!(alert["rule"], "l1_lag");
+        assert_eq!(alert["status"], "resolved");
+        assert!(receiver.try_recv().is_err());
+    }
+}
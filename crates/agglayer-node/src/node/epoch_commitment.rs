@@ -0,0 +1,222 @@
+//! Commitment to the certificates of each settled epoch.
+//!
+//! Once an epoch is settled, a commitment to its certificates, along with
+//! their roots and settlement transactions, is computed and logged. When an
+//! address is configured, it is also published on L1, giving the external
+//! verifiers a compact checkpoint of the agglayer activity.
+
+use std::sync::Arc;
+
+use agglayer_config::outbound::OutboundEpochCommitmentConfig;
+use agglayer_contracts::EpochCommitmentPublisher;
+use agglayer_storage::stores::{EpochStoreReader, StateReader};
+use agglayer_types::{Address, CertificateIndex, EpochEvent, EpochNumber, EpochSummary};
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+/// Number of certificates of an epoch read at once.
+const PAGE_SIZE: usize = 100;
+
+/// Task committing to the certificates of the settled epochs.
+pub(crate) struct EpochCommitter<StateStore, EpochsStore> {
+    publish_to: Option<Address>,
+    state_store: Arc<StateStore>,
+    epochs_store: Arc<EpochsStore>,
+}
+
+impl<StateStore, EpochsStore> EpochCommitter<StateStore, EpochsStore>
+where
+    StateStore: StateReader,
+    EpochsStore: EpochStoreReader,
+{
+    pub(crate) fn new(
+        config: &OutboundEpochCommitmentConfig,
+        state_store: Arc<StateStore>,
+        epochs_store: Arc<EpochsStore>,
+    ) -> Self {
+        Self {
+            publish_to: config.publish_to,
+            state_store,
+            epochs_store,
+        }
+    }
+
+    /// Commit to the epochs as they are settled, until cancelled.
+    pub(crate) async fn run<L1>(
+        self,
+        l1: Arc<L1>,
+        mut epoch_events: broadcast::Receiver<EpochEvent>,
+        cancellation_token: CancellationToken,
+    ) where
+        L1: EpochCommitmentPublisher,
+    {
+        loop {
+            let event = tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Epoch committer cancelled");
+                    return;
+                }
+                event = epoch_events.recv() => event,
+            };
+
+            match event {
+                Ok(EpochEvent::Settled { epoch_number }) => {
+                    self.commit(l1.as_ref(), epoch_number).await
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Epoch committer lagging, {skipped} epoch events skipped");
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    }
+
+    async fn commit<L1>(&self, l1: &L1, epoch_number: EpochNumber)
+    where
+        L1: EpochCommitmentPublisher,
+    {
+        let summary = match self.summary(epoch_number) {
+            Ok(summary) => summary,
+            Err(error) => {
+                error!(?error, "Failed to summarize the epoch {epoch_number}");
+                return;
+            }
+        };
+        let commitment = summary.commitment();
+        info!(
+            %commitment,
+            certificates = summary.certificates.len(),
+            "Commitment of the epoch {epoch_number}"
+        );
+
+        let Some(publish_to) = self.publish_to else {
+            return;
+        };
+
+        match l1
+            .publish_epoch_commitment(
+                publish_to.into_alloy(),
+                epoch_number.as_u64(),
+                commitment.0.into(),
+            )
+            .await
+        {
+            Ok(tx_hash) => info!(
+                %tx_hash,
+                "Published the commitment of the epoch {epoch_number}"
+            ),
+            Err(error) => error!(
+                ?error,
+                "Failed to publish the commitment of the epoch {epoch_number}"
+            ),
+        }
+    }
+
+    /// Summarize the certificates of the epoch, in order.
+    pub(crate) fn summary(
+        &self,
+        epoch_number: EpochNumber,
+    ) -> Result<EpochSummary, agglayer_storage::error::Error> {
+        let mut certificates = Vec::new();
+        loop {
+            let page = self.epochs_store.get_certificates(
+                epoch_number,
+                CertificateIndex::new(certificates.len() as u64),
+                PAGE_SIZE,
+            )?;
+            let ids: Vec<_> = page.iter().map(|certificate| certificate.hash()).collect();
+
+            for (certificate_id, header) in ids
+                .iter()
+                .zip(self.state_store.multi_get_certificate_header(&ids)?)
+            {
+                let Some(header) = header else {
+                    warn!(
+                        "Missing header of the certificate {certificate_id} of the epoch \
+                         {epoch_number}"
+                    );
+                    return Err(agglayer_storage::error::Error::NoCertificateHeader);
+                };
+                certificates.push((&header).into());
+            }
+
+            if page.len() < PAGE_SIZE {
+                return Ok(EpochSummary {
+                    epoch_number,
+                    certificates,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_storage::tests::mocks::{MockEpochsStore, MockStateStore};
+    use agglayer_types::{
+        Certificate, CertificateHeader, CertificateStatus, Height, NetworkId, SettlementTxHash,
+    };
+    use mockall::predicate::eq;
+
+    use super::*;
+
+    fn header(certificate: &Certificate) -> CertificateHeader {
+        CertificateHeader {
+            network_id: certificate.network_id,
+            height: certificate.height,
+            epoch_number: Some(EpochNumber::ONE),
+            certificate_index: None,
+            certificate_id: certificate.hash(),
+            prev_local_exit_root: certificate.prev_local_exit_root,
+            new_local_exit_root: certificate.new_local_exit_root,
+            metadata: certificate.metadata,
+            status: CertificateStatus::Settled,
+            settlement_tx_hash: Some(SettlementTxHash::for_tests()),
+        }
+    }
+
+    #[test]
+    fn summary_lists_the_certificates_of_the_epoch() {
+        let certificates = vec![
+            Certificate::new_for_test(NetworkId::new(1), Height::ZERO),
+            Certificate::new_for_test(NetworkId::new(2), Height::ZERO),
+        ];
+        let headers: Vec<_> = certificates.iter().map(header).collect();
+
+        let mut epochs_store = MockEpochsStore::new();
+        let page = certificates.clone();
+        epochs_store
+            .expect_get_certificates()
+            .with(
+                eq(EpochNumber::ONE),
+                eq(CertificateIndex::new(0)),
+                eq(PAGE_SIZE),
+            )
+            .once()
+            .return_once(move |_, _, _| Ok(page));
+
+        let mut state_store = MockStateStore::new();
+        let stored = headers.clone();
+        state_store
+            .expect_multi_get_certificate_header()
+            .once()
+            .return_once(move |_| Ok(stored.into_iter().map(Some).collect()));
+
+        let committer = EpochCommitter::new(
+            &OutboundEpochCommitmentConfig::default(),
+            Arc::new(state_store),
+            Arc::new(epochs_store),
+        );
+        let summary = committer.summary(EpochNumber::ONE).unwrap();
+
+        assert_eq!(
+            summary,
+            EpochSummary {
+                epoch_number: EpochNumber::ONE,
+                certificates: headers.iter().map(Into::into).collect(),
+            }
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Garbage collection of the orphaned entries of the pending storage.
+//!
+//! A crash between the writes of a certificate and of its proofs, or the
+//! pruning of a certificate interrupted halfway, can leave proofs behind
+//! without any certificate referring to them. Likewise, the certificates of a
+//! network removed from the rollup manager will never settle. Both are
+//! collected periodically to recover their space, or only reported in dry-run
+//! mode. The certificate headers are kept as the history of the networks.
+
+use std::{collections::BTreeSet, sync::Arc, time::Duration};
+
+use agglayer_config::storage::garbage_collection::GarbageCollectionConfig;
+use agglayer_contracts::{L1RpcError, RollupContract};
+use agglayer_storage::stores::{PendingCertificateReader, PendingCertificateWriter, StateReader};
+use agglayer_types::{CertificateId, Height, NetworkId};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// Entries found orphaned by a garbage collection.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct GarbageReport {
+    /// Proofs of certificates which are neither pending nor known.
+    pub(crate) orphaned_proofs: Vec<CertificateId>,
+    /// Pending certificates of the networks removed from L1.
+    pub(crate) removed_network_certificates: Vec<(NetworkId, Height, CertificateId)>,
+}
+
+/// Task collecting the orphaned proofs and the pending certificates of the
+/// removed networks.
+pub(crate) struct GarbageCollector<PendingStore, StateStore> {
+    interval: Duration,
+    dry_run: bool,
+    pending_store: Arc<PendingStore>,
+    state_store: Arc<StateStore>,
+}
+
+impl<PendingStore, StateStore> GarbageCollector<PendingStore, StateStore>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter,
+    StateStore: StateReader,
+{
+    /// Build the task, unless no interval is configured.
+    pub(crate) fn new(
+        config: &GarbageCollectionConfig,
+        pending_store: Arc<PendingStore>,
+        state_store: Arc<StateStore>,
+    ) -> Option<Self> {
+        Some(Self {
+            interval: config.interval?,
+            dry_run: config.dry_run,
+            pending_store,
+            state_store,
+        })
+    }
+
+    /// Collect the garbage periodically until cancelled, the first collection
+    /// happening after the interval.
+    pub(crate) async fn run<L1>(self, l1: Arc<L1>, cancellation_token: CancellationToken)
+    where
+        L1: RollupContract,
+    {
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Storage garbage collector cancelled");
+                    return;
+                }
+                _ = tokio::time::sleep(self.interval) => {}
+            }
+
+            let removed_networks = match self.removed_networks(l1.as_ref()).await {
+                Ok(removed_networks) => removed_networks,
+                Err(error) => {
+                    warn!(?error, "Failed to list the networks to collect");
+                    continue;
+                }
+            };
+
+            match self.collect(&removed_networks, self.dry_run) {
+                Ok(report) => info!(
+                    dry_run = self.dry_run,
+                    orphaned_proofs = ?report.orphaned_proofs,
+                    removed_network_certificates = ?report.removed_network_certificates,
+                    "Storage garbage collected"
+                ),
+                Err(error) => warn!(?error, "Failed to collect the storage garbage"),
+            }
+        }
+    }
+
+    /// The networks having pending certificates while no longer having a
+    /// rollup contract on L1. The networks whose rollup contract can't be
+    /// fetched are kept.
+    async fn removed_networks<L1: RollupContract>(
+        &self,
+        l1: &L1,
+    ) -> Result<BTreeSet<NetworkId>, agglayer_storage::error::Error> {
+        let networks: BTreeSet<NetworkId> = self
+            .pending_store
+            .get_pending_networks()?
+            .into_iter()
+            .chain(
+                self.pending_store
+                    .get_pending_certificates()?
+                    .into_iter()
+                    .map(|(network_id, _, _)| network_id),
+            )
+            .collect();
+
+        let mut removed_networks = BTreeSet::new();
+        for network_id in networks {
+            match l1.get_rollup_contract_address(network_id.to_u32()).await {
+                Ok(_) => {}
+                Err(L1RpcError::InvalidRollupContract(_)) => {
+                    removed_networks.insert(network_id);
+                }
+                Err(error) => {
+                    warn!(?error, %network_id, "Failed to fetch the rollup contract");
+                }
+            }
+        }
+
+        Ok(removed_networks)
+    }
+
+    /// Find the orphaned proofs and the pending certificates of the removed
+    /// networks, and delete them unless in dry-run mode.
+    fn collect(
+        &self,
+        removed_networks: &BTreeSet<NetworkId>,
+        dry_run: bool,
+    ) -> Result<GarbageReport, agglayer_storage::error::Error> {
+        let pending_certificates = self.pending_store.get_pending_certificates()?;
+        let pending_ids: BTreeSet<CertificateId> = pending_certificates
+            .iter()
+            .map(|(_, _, certificate_id)| *certificate_id)
+            .collect();
+
+        let mut report = GarbageReport::default();
+        for certificate_id in self.pending_store.get_proven_certificate_ids()? {
+            if pending_ids.contains(&certificate_id)
+                || self
+                    .state_store
+                    .get_certificate_header(&certificate_id)?
+                    .is_some()
+            {
+                continue;
+            }
+            report.orphaned_proofs.push(certificate_id);
+        }
+        report.removed_network_certificates = pending_certificates
+            .into_iter()
+            .filter(|(network_id, _, _)| removed_networks.contains(network_id))
+            .collect();
+
+        if dry_run {
+            return Ok(report);
+        }
+
+        for certificate_id in &report.orphaned_proofs {
+            info!(%certificate_id, "Deleting orphaned proofs");
+            self.remove_proofs(certificate_id)?;
+        }
+        for (network_id, height, certificate_id) in &report.removed_network_certificates {
+            info!(%network_id, %height, %certificate_id, "Deleting certificate of removed network");
+            self.pending_store
+                .remove_pending_certificate(*network_id, *height)?;
+            self.remove_proofs(certificate_id)?;
+        }
+
+        Ok(report)
+    }
+
+    fn remove_proofs(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<(), agglayer_storage::error::Error> {
+        self.pending_store.remove_generated_proof(certificate_id)?;
+        self.pending_store.remove_cached_proof(certificate_id)?;
+        self.pending_store.remove_submitted_proof(certificate_id)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_storage::tests::mocks::{MockPendingStore, MockStateStore};
+    use agglayer_types::{CertificateHeader, CertificateStatus, Metadata};
+    use mockall::predicate::eq;
+
+    use super::*;
+
+    fn orphaned() -> CertificateId {
+        CertificateId::new([1; 32].into())
+    }
+
+    fn pending() -> CertificateId {
+        CertificateId::new([2; 32].into())
+    }
+
+    fn settled() -> CertificateId {
+        CertificateId::new([3; 32].into())
+    }
+
+    fn header(certificate_id: CertificateId) -> CertificateHeader {
+        CertificateHeader {
+            network_id: NetworkId::new(1),
+            height: Height::ZERO,
+            epoch_number: None,
+            certificate_index: None,
+            certificate_id,
+            prev_local_exit_root: [0; 32].into(),
+            new_local_exit_root: [1; 32].into(),
+            metadata: Metadata::ZERO,
+            status: CertificateStatus::Settled,
+            settlement_tx_hash: None,
+        }
+    }
+
+    fn collector(
+        mut pending_store: MockPendingStore,
+    ) -> GarbageCollector<MockPendingStore, MockStateStore> {
+        pending_store
+            .expect_get_pending_certificates()
+            .returning(|| Ok(vec![(NetworkId::new(2), Height::ZERO, pending())]));
+        pending_store
+            .expect_get_proven_certificate_ids()
+            .returning(|| Ok(BTreeSet::from([orphaned(), pending(), settled()])));
+
+        let mut state_store = MockStateStore::new();
+        state_store
+            .expect_get_certificate_header()
+            .with(eq(orphaned()))
+            .returning(|_| Ok(None));
+        state_store
+            .expect_get_certificate_header()
+            .with(eq(settled()))
+            .returning(|id| Ok(Some(header(*id))));
+
+        let config = GarbageCollectionConfig {
+            interval: Some(Duration::from_secs(60)),
+            dry_run: false,
+        };
+        GarbageCollector::new(&config, Arc::new(pending_store), Arc::new(state_store)).unwrap()
+    }
+
+    fn expect_proofs_removed(pending_store: &mut MockPendingStore, certificate_id: CertificateId) {
+        pending_store
+            .expect_remove_generated_proof()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+        pending_store
+            .expect_remove_cached_proof()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+        pending_store
+            .expect_remove_submitted_proof()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+    }
+
+    #[test]
+    fn no_task_without_interval() {
+        assert!(GarbageCollector::new(
+            &GarbageCollectionConfig::default(),
+            Arc::new(MockPendingStore::new()),
+            Arc::new(MockStateStore::new()),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn orphaned_proofs_are_deleted() {
+        let mut pending_store = MockPendingStore::new();
+        expect_proofs_removed(&mut pending_store, orphaned());
+        pending_store.expect_remove_pending_certificate().never();
+
+        let report = collector(pending_store)
+            .collect(&BTreeSet::new(), false)
+            .unwrap();
+
+        assert_eq!(
+            report,
+            GarbageReport {
+                orphaned_proofs: vec![orphaned()],
+                removed_network_certificates: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn certificates_of_removed_networks_are_deleted() {
+        let mut pending_store = MockPendingStore::new();
+        expect_proofs_removed(&mut pending_store, orphaned());
+        expect_proofs_removed(&mut pending_store, pending());
+        pending_store
+            .expect_remove_pending_certificate()
+            .once()
+            .with(eq(NetworkId::new(2)), eq(Height::ZERO))
+            .returning(|_, _| Ok(()));
+
+        let report = collector(pending_store)
+            .collect(&BTreeSet::from([NetworkId::new(2)]), false)
+            .unwrap();
+
+        assert_eq!(
+            report.removed_network_certificates,
+            vec![(NetworkId::new(2), Height::ZERO, pending())]
+        );
+    }
+
+    #[test]
+    fn dry_run_deletes_nothing() {
+        let mut pending_store = MockPendingStore::new();
+        pending_store.expect_remove_generated_proof().never();
+        pending_store.expect_remove_cached_proof().never();
+        pending_store.expect_remove_submitted_proof().never();
+        pending_store.expect_remove_pending_certificate().never();
+
+        let report = collector(pending_store)
+            .collect(&BTreeSet::from([NetworkId::new(2)]), true)
+            .unwrap();
+
+        assert_eq!(
+            report,
+            GarbageReport {
+                orphaned_proofs: vec![orphaned()],
+                removed_network_certificates: vec![(NetworkId::new(2), Height::ZERO, pending())],
+            }
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! HTTP clients of the outbound connections.
+//!
+//! The connections to the L1 node go through the configured proxies, and
+//! trust the configured CA certificates on top of the system ones.
+
+use std::{fs, path::Path};
+
+use agglayer_config::outbound::HttpClientConfig;
+use alloy::{
+    rpc::client::RpcClient,
+    transports::{http::Http, utils::guess_local_url},
+};
+use eyre::Context as _;
+use reqwest::{Certificate, Identity, NoProxy, Proxy, Url};
+
+/// Build an HTTP client from the outbound configuration.
+pub(crate) fn build(config: &HttpClientConfig) -> eyre::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().use_rustls_tls();
+
+    if let Some(path) = &config.ca_bundle {
+        let certificates =
+            Certificate::from_pem_bundle(&read(path)?).context("Invalid CA bundle")?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some(path) = &config.client_identity {
+        let identity = Identity::from_pem(&read(path)?).context("Invalid client identity")?;
+        builder = builder.identity(identity);
+    }
+
+    let no_proxy = NoProxy::from_string(&config.no_proxy.join(","));
+    if let Some(proxy) = &config.http_proxy {
+        let proxy = Proxy::http(proxy).context("Invalid HTTP proxy")?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+    }
+    if let Some(proxy) = &config.https_proxy {
+        let proxy = Proxy::https(proxy).context("Invalid HTTPS proxy")?;
+        builder = builder.proxy(proxy.no_proxy(no_proxy));
+    }
+
+    builder.build().context("Failed to build the HTTP client")
+}
+
+/// RPC client of the L1 node, connecting through the configured HTTP client.
+pub(crate) fn l1_rpc_client(config: &HttpClientConfig, node_url: Url) -> eyre::Result<RpcClient> {
+    let is_local = guess_local_url(&node_url);
+
+    Ok(RpcClient::new(
+        Http::with_client(build(config)?, node_url),
+        is_local,
+    ))
+}
+
+fn read(path: &Path) -> eyre::Result<Vec<u8>> {
+    fs::read(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_builds_a_client() {
+        build(&HttpClientConfig::default()).unwrap();
+    }
+
+    #[test]
+    fn proxies_are_validated() {
+        let config = HttpClientConfig {
+            https_proxy: Some("not a proxy".into()),
+            ..Default::default()
+        };
+
+        assert!(build(&config).is_err());
+    }
+
+    #[test]
+    fn missing_ca_bundle_is_reported() {
+        let config = HttpClientConfig {
+            ca_bundle: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        };
+
+        let error = build(&config).unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/ca.pem"));
+    }
+}
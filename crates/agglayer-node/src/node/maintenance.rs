@@ -0,0 +1,210 @@
+//! Draining of the in-flight work once the maintenance mode is entered.
+//!
+//! New certificates are rejected by the RPC services as soon as the
+//! maintenance mode is entered from the admin API. This task then waits for
+//! the network tasks to process their in-flight certificates, flushes the
+//! storage and marks the node as ready to be stopped, cancelling the node if
+//! an exit was requested.
+
+use std::{sync::Arc, time::Duration};
+
+use agglayer_certificate_orchestrator::{OrchestratorSnapshot, OrchestratorState};
+use agglayer_rpc::{Maintenance, MaintenanceState, MaintenanceStatus};
+use agglayer_storage::storage::DB;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Interval at which the in-flight work is checked while draining.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub(crate) struct MaintenanceTask {
+    maintenance: Arc<Maintenance>,
+    orchestrator_state: Arc<OrchestratorState>,
+    /// Databases flushed once drained, along with their name.
+    databases: Vec<(&'static str, Arc<DB>)>,
+}
+
+impl MaintenanceTask {
+    pub(crate) fn new(
+        maintenance: Arc<Maintenance>,
+        orchestrator_state: Arc<OrchestratorState>,
+        databases: Vec<(&'static str, Arc<DB>)>,
+    ) -> Self {
+        Self {
+            maintenance,
+            orchestrator_state,
+            databases,
+        }
+    }
+
+    pub(crate) async fn run(self, cancellation_token: CancellationToken) {
+        let mut changes = self.maintenance.subscribe();
+
+        loop {
+            let status = changes.borrow_and_update().status;
+            if status == MaintenanceStatus::Draining {
+                self.drain(&mut changes, &cancellation_token).await;
+            }
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => return,
+                changed = changes.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wait for the in-flight work to be done, then flush the storage.
+    /// Returns early if the maintenance is left meanwhile.
+    async fn drain(
+        &self,
+        changes: &mut watch::Receiver<MaintenanceState>,
+        cancellation_token: &CancellationToken,
+    ) {
+        info!("Draining the in-flight certificates for maintenance");
+
+        loop {
+            let snapshot = self.orchestrator_state.snapshot();
+            let in_flight = in_flight_work(&snapshot);
+            if in_flight == 0 {
+                break;
+            }
+            debug!(in_flight, "Waiting for the in-flight certificates");
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => return,
+                _ = tokio::time::sleep(DRAIN_POLL_INTERVAL) => {}
+                _ = changes.changed() => {
+                    if changes.borrow().status != MaintenanceStatus::Draining {
+                        info!("Maintenance left while draining");
+                        return;
+                    }
+                }
+            }
+        }
+
+        for (name, db) in &self.databases {
+            if let Err(error) = db.flush() {
+                error!(?error, db = *name, "Failed to flush the database");
+            }
+        }
+
+        let Some(state) = self.maintenance.set_ready() else {
+            info!("Maintenance left while draining");
+            return;
+        };
+
+        if state.exit_when_ready {
+            warn!("In-flight certificates processed and storage flushed, exiting for maintenance");
+            cancellation_token.cancel();
+        } else {
+            info!("In-flight certificates processed and storage flushed, ready for maintenance");
+        }
+    }
+}
+
+/// Number of certificates being processed or queued by the network tasks.
+fn in_flight_work(snapshot: &OrchestratorSnapshot) -> usize {
+    snapshot
+        .networks
+        .values()
+        .map(|network| network.queued_certificates + usize::from(network.in_flight.is_some()))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, sync::atomic::AtomicU64};
+
+    use agglayer_certificate_orchestrator::{
+        CertificateStage, InFlightCertificate, NetworkTaskState,
+    };
+    use agglayer_clock::ClockRef;
+    use agglayer_storage::{storage::state_db_cf_definitions, tests::TempDBDir};
+    use agglayer_types::{CertificateId, Digest, Height, NetworkId};
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    fn orchestrator_state() -> Arc<OrchestratorState> {
+        Arc::new(OrchestratorState::new(ClockRef::new(
+            broadcast::channel(1).0,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(NonZeroU64::new(1).unwrap()),
+        )))
+    }
+
+    #[test]
+    fn in_flight_work_counts_the_queued_and_processed_certificates() {
+        let mut snapshot = orchestrator_state().snapshot();
+        assert_eq!(in_flight_work(&snapshot), 0);
+
+        snapshot.networks.insert(
+            NetworkId::new(1),
+            NetworkTaskState {
+                queued_certificates: 2,
+                in_flight: Some(InFlightCertificate {
+                    certificate_id: CertificateId::new(Digest([1; 32])),
+                    height: Height::ZERO,
+                    stage: CertificateStage::Certifying,
+                }),
+                ..Default::default()
+            },
+        );
+        snapshot
+            .networks
+            .insert(NetworkId::new(2), NetworkTaskState::default());
+
+        assert_eq!(in_flight_work(&snapshot), 3);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn node_is_cancelled_once_drained_when_exiting() {
+        let tmp = TempDBDir::new();
+        let db = Arc::new(DB::open_cf(tmp.path.as_path(), state_db_cf_definitions()).unwrap());
+        let maintenance = Arc::new(Maintenance::default());
+        let cancellation_token = CancellationToken::new();
+
+        let task = MaintenanceTask::new(
+            maintenance.clone(),
+            orchestrator_state(),
+            vec![("state", db)],
+        );
+        let handle = tokio::spawn(task.run(cancellation_token.clone()));
+
+        maintenance.enter(true);
+        tokio::time::timeout(Duration::from_secs(5), cancellation_token.cancelled())
+            .await
+            .unwrap();
+
+        assert_eq!(maintenance.state().status, MaintenanceStatus::Ready);
+        handle.await.unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn node_keeps_running_once_drained_without_exiting() {
+        let maintenance = Arc::new(Maintenance::default());
+        let cancellation_token = CancellationToken::new();
+
+        let task = MaintenanceTask::new(maintenance.clone(), orchestrator_state(), vec![]);
+        let handle = tokio::spawn(task.run(cancellation_token.clone()));
+
+        let mut changes = maintenance.subscribe();
+        maintenance.enter(false);
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            changes.wait_for(|state| state.status == MaintenanceStatus::Ready),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(!cancellation_token.is_cancelled());
+
+        cancellation_token.cancel();
+        handle.await.unwrap();
+    }
+}
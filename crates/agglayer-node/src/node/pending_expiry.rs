@@ -0,0 +1,506 @@
+//! Expiry of the certificates staying pending without being certified.
+//!
+//! Certificates which are never certified, such as the ones superseded by the
+//! settlement of another certificate or the ones of a stalled network, would
+//! otherwise stay in the pending storage forever. Once the TTL elapsed since
+//! their submission, they are put in error as expired, which lets their
+//! network replace them, and are pruned from the pending storage once the
+//! retention elapsed since their expiry.
+//!
+//! Only the certificate at the next height of its network is expired, under
+//! the lock of the network as when cancelling it, so that its network task
+//! does not pick it up meanwhile.
+
+use std::{sync::Arc, time::Duration};
+
+use agglayer_certificate_orchestrator::OrchestratorState;
+use agglayer_config::certificate_orchestrator::pending_expiry::PendingExpiryConfig;
+use agglayer_storage::{
+    columns::{
+        audit_log_per_certificate::AuditEvent,
+        latest_settled_certificate_per_network::SettledCertificate,
+    },
+    stores::{PendingCertificateReader, PendingCertificateWriter, StateReader, StateWriter},
+};
+use agglayer_types::{CertificateStatus, CertificateStatusError, Height};
+use chrono::{DateTime, Utc};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// Task expiring and pruning the abandoned pending certificates.
+pub(crate) struct PendingExpiry<PendingStore, StateStore> {
+    ttl: Duration,
+    retention: Duration,
+    check_interval: Duration,
+    pending_store: Arc<PendingStore>,
+    state_store: Arc<StateStore>,
+    orchestrator_state: Arc<OrchestratorState>,
+}
+
+impl<PendingStore, StateStore> PendingExpiry<PendingStore, StateStore>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter,
+    StateStore: StateReader + StateWriter,
+{
+    /// Build the task, unless no TTL is configured.
+    pub(crate) fn new(
+        config: &PendingExpiryConfig,
+        pending_store: Arc<PendingStore>,
+        state_store: Arc<StateStore>,
+        orchestrator_state: Arc<OrchestratorState>,
+    ) -> Option<Self> {
+        Some(Self {
+            ttl: config.ttl?,
+            retention: config.retention,
+            check_interval: config.check_interval,
+            pending_store,
+            state_store,
+            orchestrator_state,
+        })
+    }
+
+    /// Check the pending certificates until cancelled.
+    pub(crate) async fn run(self, cancellation_token: CancellationToken) {
+        loop {
+            if let Err(error) = self.check(Utc::now()) {
+                warn!(
+                    ?error,
+                    "Failed to check the expiry of the pending certificates"
+                );
+            }
+
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Pending certificate expiry cancelled");
+                    return;
+                }
+                _ = tokio::time::sleep(self.check_interval) => {}
+            }
+        }
+    }
+
+    /// Expire the certificates pending for longer than the TTL, and prune the
+    /// ones expired for longer than the retention. The networks with a
+    /// certificate being processed by the orchestrator are left untouched
+    /// until the next check.
+    fn check(&self, now: DateTime<Utc>) -> Result<(), agglayer_storage::error::Error> {
+        for (network_id, height, certificate_id) in self.pending_store.get_pending_certificates()? {
+            // The network task picks up the certificates of its network under
+            // the lock of the network, which is held until the certificate is
+            // expired or pruned.
+            let Ok(_network_guard) = self
+                .orchestrator_state
+                .network_lock(network_id)
+                .try_lock_owned()
+            else {
+                continue;
+            };
+
+            let Some(header) = self.state_store.get_certificate_header(&certificate_id)? else {
+                continue;
+            };
+            let audit_log = self.state_store.get_audit_log(&certificate_id)?;
+
+            match header.status {
+                CertificateStatus::Pending => {
+                    // The certificates above the next height cannot be picked
+                    // up before it settles, they are left to their network.
+                    let next_height = self
+                        .state_store
+                        .get_latest_settled_certificate_per_network(&network_id)?
+                        .map_or(Height::ZERO, |(_, SettledCertificate(_, height, ..))| {
+                            height.next()
+                        });
+                    if height != next_height {
+                        continue;
+                    }
+
+                    // Certificates submitted before the audit log was
+                    // recorded have no known submission time.
+                    let Some(submitted_at) = audit_log.first().map(|record| record.timestamp)
+                    else {
+                        continue;
+                    };
+                    if elapsed(submitted_at, now) < self.ttl {
+                        continue;
+                    }
+
+                    info!(%network_id, %height, %certificate_id, "Expiring pending certificate");
+                    self.state_store.update_certificate_header_status(
+                        &certificate_id,
+                        &CertificateStatus::error(CertificateStatusError::Expired(
+                            self.ttl.as_secs(),
+                        )),
+                    )?;
+                }
+                CertificateStatus::InError { error }
+                    if matches!(*error, CertificateStatusError::Expired(_)) =>
+                {
+                    let Some(expired_at) = audit_log
+                        .iter()
+                        .rev()
+                        .find(|record| matches!(record.event, AuditEvent::StatusChanged { .. }))
+                        .map(|record| record.timestamp)
+                    else {
+                        continue;
+                    };
+                    if elapsed(expired_at, now) < self.retention {
+                        continue;
+                    }
+
+                    info!(%network_id, %height, %certificate_id, "Pruning expired certificate");
+                    self.pending_store
+                        .remove_pending_certificate(network_id, height)?;
+                    self.pending_store.remove_generated_proof(&certificate_id)?;
+                    self.pending_store.remove_cached_proof(&certificate_id)?;
+                    self.pending_store.remove_submitted_proof(&certificate_id)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Time elapsed from the given timestamp, zero if it is in the future.
+fn elapsed(since: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    now.signed_duration_since(since)
+        .to_std()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, sync::atomic::AtomicU64};
+
+    use agglayer_clock::ClockRef;
+    use agglayer_storage::{
+        columns::audit_log_per_certificate::{AuditRecord, SubmissionApi, Submitter},
+        storage::backup::BackupClient,
+        stores::{pending::PendingStore, state::StateStore},
+        tests::{
+            mocks::{MockPendingStore, MockStateStore},
+            TempDBDir,
+        },
+    };
+    use agglayer_types::{Certificate, CertificateHeader, CertificateId, Metadata, NetworkId};
+    use chrono::TimeDelta;
+    use mockall::predicate::eq;
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    fn header(status: CertificateStatus) -> CertificateHeader {
+        CertificateHeader {
+            network_id: NetworkId::new(1),
+            height: Height::ZERO,
+            epoch_number: None,
+            certificate_index: None,
+            certificate_id: CertificateId::new([1; 32].into()),
+            prev_local_exit_root: [0; 32].into(),
+            new_local_exit_root: [1; 32].into(),
+            metadata: Metadata::ZERO,
+            status,
+            settlement_tx_hash: None,
+        }
+    }
+
+    fn submitted(timestamp: DateTime<Utc>) -> AuditRecord {
+        AuditRecord {
+            timestamp,
+            event: AuditEvent::Submitted {
+                submitter: Submitter {
+                    api: SubmissionApi::JsonRpc,
+                    address: None,
+                },
+            },
+        }
+    }
+
+    fn expired() -> CertificateStatus {
+        CertificateStatus::error(CertificateStatusError::Expired(HOUR.as_secs()))
+    }
+
+    fn config() -> PendingExpiryConfig {
+        PendingExpiryConfig {
+            ttl: Some(HOUR),
+            retention: 24 * HOUR,
+            ..Default::default()
+        }
+    }
+
+    fn orchestrator_state() -> Arc<OrchestratorState> {
+        Arc::new(OrchestratorState::new(ClockRef::new(
+            broadcast::channel(1).0,
+            Arc::new(AtomicU64::new(0)),
+            Arc::new(NonZeroU64::new(1).unwrap()),
+        )))
+    }
+
+    fn expiry(
+        header: CertificateHeader,
+        audit_log: Vec<AuditRecord>,
+        mut pending_store: MockPendingStore,
+        mut state_store: MockStateStore,
+    ) -> PendingExpiry<MockPendingStore, MockStateStore> {
+        let (network_id, height, certificate_id) =
+            (header.network_id, header.height, header.certificate_id);
+        pending_store
+            .expect_get_pending_certificates()
+            .returning(move || Ok(vec![(network_id, height, certificate_id)]));
+        state_store
+            .expect_get_certificate_header()
+            .with(eq(certificate_id))
+            .returning(move |_| Ok(Some(header.clone())));
+        state_store
+            .expect_get_audit_log()
+            .with(eq(certificate_id))
+            .returning(move |_| Ok(audit_log.clone()));
+        state_store
+            .expect_get_latest_settled_certificate_per_network()
+            .returning(|_| Ok(None));
+
+        PendingExpiry::new(
+            &config(),
+            Arc::new(pending_store),
+            Arc::new(state_store),
+            orchestrator_state(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn no_task_without_ttl() {
+        assert!(PendingExpiry::new(
+            &PendingExpiryConfig::default(),
+            Arc::new(MockPendingStore::new()),
+            Arc::new(MockStateStore::new()),
+            orchestrator_state(),
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn pending_certificate_is_expired_after_the_ttl() {
+        let now = Utc::now();
+        let header = header(CertificateStatus::Pending);
+        let certificate_id = header.certificate_id;
+
+        let mut state_store = MockStateStore::new();
+        state_store
+            .expect_update_certificate_header_status()
+            .once()
+            .with(eq(certificate_id), eq(expired()))
+            .returning(|_, _| Ok(()));
+
+        let expiry = expiry(
+            header,
+            vec![submitted(now - TimeDelta::hours(2))],
+            MockPendingStore::new(),
+            state_store,
+        );
+        expiry.check(now).unwrap();
+    }
+
+    #[test]
+    fn recent_pending_certificate_is_kept() {
+        let now = Utc::now();
+
+        let mut state_store = MockStateStore::new();
+        state_store
+            .expect_update_certificate_header_status()
+            .never();
+
+        let expiry = expiry(
+            header(CertificateStatus::Pending),
+            vec![submitted(now - TimeDelta::minutes(30))],
+            MockPendingStore::new(),
+            state_store,
+        );
+        expiry.check(now).unwrap();
+    }
+
+    #[test]
+    fn pending_certificate_above_the_next_height_is_kept() {
+        let now = Utc::now();
+
+        let mut state_store = MockStateStore::new();
+        state_store
+            .expect_update_certificate_header_status()
+            .never();
+
+        let expiry = expiry(
+            CertificateHeader {
+                height: Height::new(1),
+                ..header(CertificateStatus::Pending)
+            },
+            vec![submitted(now - TimeDelta::hours(2))],
+            MockPendingStore::new(),
+            state_store,
+        );
+        expiry.check(now).unwrap();
+    }
+
+    #[test]
+    fn pending_certificate_picked_up_is_kept() {
+        let now = Utc::now();
+        let header = header(CertificateStatus::Pending);
+        let network_id = header.network_id;
+
+        let mut state_store = MockStateStore::new();
+        state_store
+            .expect_update_certificate_header_status()
+            .never();
+
+        let expiry = expiry(
+            header,
+            vec![submitted(now - TimeDelta::hours(2))],
+            MockPendingStore::new(),
+            state_store,
+        );
+
+        // The network task holds the lock of the network from the pickup.
+        let _network_guard = expiry
+            .orchestrator_state
+            .network_lock(network_id)
+            .try_lock_owned()
+            .unwrap();
+        expiry.check(now).unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn expiry_races_with_the_pickup() {
+        let now = Utc::now();
+        let pending_dir = TempDBDir::new();
+        let state_dir = TempDBDir::new();
+        let pending_store = Arc::new(PendingStore::new_with_path(&pending_dir.path).unwrap());
+        let state_store =
+            Arc::new(StateStore::new_with_path(&state_dir.path, BackupClient::noop()).unwrap());
+        let expiry = Arc::new(
+            PendingExpiry::new(
+                &config(),
+                pending_store.clone(),
+                state_store.clone(),
+                orchestrator_state(),
+            )
+            .unwrap(),
+        );
+
+        for network in 1..=20 {
+            let network_id = NetworkId::new(network);
+            let certificate = Certificate::new_for_test(network_id, Height::ZERO);
+            let certificate_id = certificate.hash();
+            pending_store
+                .insert_pending_certificate(network_id, Height::ZERO, &certificate)
+                .unwrap();
+            state_store
+                .insert_certificate_header(&certificate, CertificateStatus::Pending)
+                .unwrap();
+            state_store
+                .record_audit_event(&certificate_id, submitted(now).event)
+                .unwrap();
+
+            let check = tokio::task::spawn_blocking({
+                let expiry = expiry.clone();
+                move || expiry.check(now + TimeDelta::hours(2))
+            });
+
+            // Pick up the certificate the way the network task does, keeping
+            // the lock while the certificate is in the pipeline.
+            let network_lock = expiry.orchestrator_state.network_lock(network_id);
+            let pickup = async {
+                for _ in 0..network % 4 {
+                    tokio::task::yield_now().await;
+                }
+                let guard = network_lock.lock_owned().await;
+                let header = state_store
+                    .get_certificate_header(&certificate_id)
+                    .unwrap()
+                    .unwrap();
+                (header.status == CertificateStatus::Pending).then_some(guard)
+            };
+
+            let (checked, picked_up) = tokio::join!(check, pickup);
+            checked.unwrap().unwrap();
+
+            let header = state_store
+                .get_certificate_header(&certificate_id)
+                .unwrap()
+                .unwrap();
+            assert_ne!(
+                header.status == expired(),
+                picked_up.is_some(),
+                "certificate of network {network} both expired and picked up, or neither"
+            );
+        }
+    }
+
+    #[test]
+    fn expired_certificate_is_pruned_after_the_retention() {
+        let now = Utc::now();
+        let header = header(expired());
+        let (network_id, height, certificate_id) =
+            (header.network_id, header.height, header.certificate_id);
+
+        let mut pending_store = MockPendingStore::new();
+        pending_store
+            .expect_remove_pending_certificate()
+            .once()
+            .with(eq(network_id), eq(height))
+            .returning(|_, _| Ok(()));
+        pending_store
+            .expect_remove_generated_proof()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+        pending_store
+            .expect_remove_cached_proof()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+        pending_store
+            .expect_remove_submitted_proof()
+            .once()
+            .with(eq(certificate_id))
+            .returning(|_| Ok(()));
+
+        let expiry = expiry(
+            header,
+            vec![
+                submitted(now - TimeDelta::hours(48)),
+                AuditRecord {
+                    timestamp: now - TimeDelta::hours(25),
+                    event: AuditEvent::StatusChanged { status: expired() },
+                },
+            ],
+            pending_store,
+            MockStateStore::new(),
+        );
+        expiry.check(now).unwrap();
+    }
+
+    #[test]
+    fn recently_expired_certificate_is_kept() {
+        let now = Utc::now();
+
+        let mut pending_store = MockPendingStore::new();
+        pending_store.expect_remove_pending_certificate().never();
+
+        let expiry = expiry(
+            header(expired()),
+            vec![
+                submitted(now - TimeDelta::hours(2)),
+                AuditRecord {
+                    timestamp: now - TimeDelta::hours(1),
+                    event: AuditEvent::StatusChanged { status: expired() },
+                },
+            ],
+            pending_store,
+            MockStateStore::new(),
+        );
+        expiry.check(now).unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+//! Read-only agglayer, serving the queries from secondary instances of the
+//! storage of a primary agglayer.
+//!
+//! The replicas don't run the orchestrator, the settlement nor the AdminRPC,
+//! and the RPC rejects the certificates and proofs submitted to them.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use agglayer_config::Config;
+use agglayer_contracts::{contracts::PolygonRollupManager, L1RpcClient};
+use agglayer_jsonrpc_api::{kernel::Kernel, service::AgglayerService, AgglayerImpl};
+use agglayer_storage::{
+    storage::{backup::BackupClient, catch_up::CatchUp, metrics_reporter::MetricsReporter, DB},
+    stores::{debug::DebugStore, epochs::EpochsStore, pending::PendingStore, state::StateStore},
+};
+use agglayer_types::{BuildInfo, Digest, EpochNumber, VersionInfo};
+use alloy::providers::ProviderBuilder;
+use eyre::Context as _;
+use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info};
+
+use super::{api, http_client, Node, EPOCH_EVENTS_CHANNEL_SIZE, STORAGE_METRICS_INTERVAL};
+
+/// Start the agglayer in read-only mode.
+pub(super) async fn start(
+    config: Arc<Config>,
+    build_info: BuildInfo,
+    cancellation_token: CancellationToken,
+) -> eyre::Result<Node> {
+    let (Some(secondary_pending_db_path), Some(secondary_state_db_path)) = (
+        config.storage.secondary_pending_db_path(),
+        config.storage.secondary_state_db_path(),
+    ) else {
+        eyre::bail!("The read-only mode requires the storage secondary-db-path to be set");
+    };
+
+    // Initializing storage
+    let pending_db = Arc::new(DB::open_cf_secondary(
+        &config.storage.pending_db_path,
+        &secondary_pending_db_path,
+        agglayer_storage::storage::pending_db_cf_definitions(),
+    )?);
+    let state_db = Arc::new(DB::open_cf_secondary(
+        &config.storage.state_db_path,
+        &secondary_state_db_path,
+        agglayer_storage::storage::state_db_cf_definitions(),
+    )?);
+
+    let catch_up = CatchUp::new(
+        vec![("state", state_db.clone()), ("pending", pending_db.clone())],
+        config.read_only.catch_up_interval,
+    );
+    tokio::spawn(catch_up.run(cancellation_token.clone()));
+    info!("Storage catch up started.");
+
+    let storage_metrics_reporter = MetricsReporter::new(
+        vec![("state", state_db.clone()), ("pending", pending_db.clone())],
+        STORAGE_METRICS_INTERVAL,
+    );
+    tokio::spawn(storage_metrics_reporter.run(cancellation_token.clone()));
+
+    let state_store = Arc::new(StateStore::new(state_db, BackupClient::noop()));
+    let pending_store = Arc::new(PendingStore::new(pending_db));
+    let debug_store = Arc::new(DebugStore::Disabled);
+    // The per-epoch storages are opened read-only by the readers, the current
+    // epoch is only needed to write to them.
+    let epochs_store = Arc::new(EpochsStore::new(
+        config.clone(),
+        EpochNumber::ZERO,
+        pending_store.clone(),
+        state_store.clone(),
+        BackupClient::noop(),
+    )?);
+
+    info!("Storage initialized in read-only mode.");
+
+    // No transaction is sent to L1, hence no signer.
+    let rpc = Arc::new(ProviderBuilder::new().on_client(http_client::l1_rpc_client(
+        &config.outbound.http,
+        config.l1.node_url.clone(),
+    )?));
+    let rollup_manager = Arc::new(
+        L1RpcClient::try_new(
+            rpc.clone(),
+            PolygonRollupManager::new(config.l1.rollup_manager_contract.into(), (*rpc).clone()),
+            config.l1.polygon_zkevm_global_exit_root_v2_contract.into(),
+            config.outbound.rpc.settle.gas_multiplier_factor,
+            {
+                let gas_config = &config.outbound.rpc.settle.gas_price;
+                agglayer_contracts::GasPriceParams::new(
+                    gas_config.multiplier.as_u64_per_1000(),
+                    gas_config.floor..=gas_config.ceiling,
+                )?
+            },
+            config.l1.event_filter_block_range.get(),
+        )
+        .await?,
+    );
+    debug!("RollupManager created");
+
+    let core = Kernel::new(rpc, config.clone())?;
+
+    // The submissions are rejected before reaching the orchestrator, which
+    // doesn't run on a replica.
+    let (data_sender, _) = mpsc::channel(1);
+    let (epoch_events, _) = broadcast::channel(EPOCH_EVENTS_CHANNEL_SIZE);
+
+    let service = Arc::new(AgglayerService::new(core));
+    let rpc_service = Arc::new(
+        agglayer_rpc::AgglayerService::new(
+            data_sender,
+            pending_store,
+            state_store,
+            debug_store,
+            epochs_store,
+            config.clone(),
+            rollup_manager,
+        )
+        // The vkey is provided by the certifier, which doesn't run on a
+        // replica.
+        .with_version_info(VersionInfo::new(build_info, Digest::ZERO)),
+    );
+
+    let json_rpc_router = AgglayerImpl::new(service, rpc_service.clone(), epoch_events)
+        .start()
+        .await
+        .context("Failed starting JSON-RPC router")?;
+
+    let graphql_router = config
+        .rpc
+        .graphql
+        .enabled
+        .then(|| agglayer_graphql_api::router(&config.rpc.graphql, rpc_service.clone()));
+
+    let public_grpc_router = agglayer_grpc_api::Server::with_config(config.clone(), rpc_service)
+        .build()
+        .inspect_err(|err| error!(?err, "Failed to build public gRPC router"))?;
+
+    let mut readrpc_router = axum::Router::new()
+        .merge(api::rest::health_router())
+        .merge(json_rpc_router);
+    if let Some(graphql_router) = graphql_router {
+        info!(
+            path = agglayer_graphql_api::GRAPHQL_PATH,
+            "GraphQL endpoint enabled"
+        );
+        readrpc_router = readrpc_router.merge(graphql_router);
+    }
+
+    let readrpc_listener = tokio::net::TcpListener::bind(config.readrpc_addr()).await?;
+    let public_grpc_listener = tokio::net::TcpListener::bind(config.public_grpc_addr()).await?;
+    info!(on = %config.readrpc_addr(), "ReadRPC listening");
+    info!(on = %config.public_grpc_addr(), "Public gRPC listening");
+
+    let readrpc_server = axum::serve(
+        readrpc_listener,
+        readrpc_router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
+
+    let public_grpc_server = axum::serve(
+        public_grpc_listener,
+        public_grpc_router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(cancellation_token.clone().cancelled_owned());
+
+    // Without orchestrator, the node runs until cancelled.
+    let certificate_orchestrator_handle =
+        tokio::spawn(cancellation_token.clone().cancelled_owned());
+
+    let rpc_handle = tokio::spawn(async move {
+        tokio::select! {
+            _ = readrpc_server => {},
+            _ = public_grpc_server => {},
+            _ = cancellation_token.cancelled() => {
+                debug!("Node RPC shutdown requested.");
+            }
+        }
+    });
+
+    Ok(Node {
+        rpc_handle,
+        certificate_orchestrator_handle,
+    })
+}
@@ -0,0 +1,194 @@
+//! Sanity checks of the L1 environment, performed before starting the node.
+
+use agglayer_config::{StartupChecks, L1};
+use agglayer_contracts::contracts::{AgglayerGateway, PolygonRollupManager};
+use alloy::{
+    primitives::{Address, FixedBytes, B256},
+    providers::Provider,
+};
+use pessimistic_proof::core::PESSIMISTIC_PROOF_PROGRAM_SELECTOR;
+use tracing::{error, info, warn};
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum StartupCheckError {
+    #[error("L1 chain id mismatch: configured {configured}, got {actual}")]
+    ChainIdMismatch { configured: u64, actual: u64 },
+
+    #[error("No contract deployed at the {contract} address {address}")]
+    MissingContract {
+        contract: &'static str,
+        address: Address,
+    },
+
+    #[error("Pessimistic proof vkey mismatch: {on_chain} on L1, {embedded} embedded")]
+    VKeyMismatch { on_chain: B256, embedded: B256 },
+
+    #[error("Unable to {action}: {source}")]
+    L1Query {
+        action: &'static str,
+        #[source]
+        source: eyre::Error,
+    },
+}
+
+impl StartupCheckError {
+    fn l1_query(action: &'static str, source: impl Into<eyre::Error>) -> Self {
+        Self::L1Query {
+            action,
+            source: source.into(),
+        }
+    }
+}
+
+/// Run the L1 sanity checks and act on the failures depending on the
+/// configured [`StartupChecks`].
+///
+/// Returns an error if any check failed and the checks are enforced.
+pub(crate) async fn run<P: Provider>(
+    provider: &P,
+    config: &L1,
+    pessimistic_vkey: [u8; 32],
+) -> eyre::Result<()> {
+    if config.startup_checks == StartupChecks::Disabled {
+        warn!("L1 startup checks are disabled");
+        return Ok(());
+    }
+
+    let failures = check_l1(provider, config, pessimistic_vkey.into()).await;
+
+    if failures.is_empty() {
+        info!("L1 startup checks passed");
+        return Ok(());
+    }
+
+    for failure in &failures {
+        error!("L1 startup check failed: {failure}");
+    }
+
+    match config.startup_checks {
+        StartupChecks::Enforce => eyre::bail!(
+            "{} L1 startup check(s) failed, refusing to start",
+            failures.len()
+        ),
+        StartupChecks::Warn | StartupChecks::Disabled => {
+            warn!(
+                "Starting in degraded mode despite {} failed L1 startup check(s), settlements are \
+                 likely to fail",
+                failures.len()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Check the L1 environment against the configuration, returning every
+/// failure.
+async fn check_l1<P: Provider>(
+    provider: &P,
+    config: &L1,
+    embedded_vkey: B256,
+) -> Vec<StartupCheckError> {
+    let mut failures = Vec::new();
+
+    match provider.get_chain_id().await {
+        Ok(actual) => failures.extend(check_chain_id(config.chain_id, actual)),
+        Err(error) => failures.push(StartupCheckError::l1_query("fetch the chain id", error)),
+    }
+
+    let rollup_manager: Address = config.rollup_manager_contract.into();
+    let global_exit_root: Address = config.polygon_zkevm_global_exit_root_v2_contract.into();
+    for (contract, address) in [
+        ("rollup manager", rollup_manager),
+        ("global exit root", global_exit_root),
+    ] {
+        failures.extend(check_contract(provider, contract, address).await);
+    }
+
+    // The pessimistic proof verifier is routed by the gateway registered in
+    // the rollup manager.
+    let gateway = match PolygonRollupManager::new(rollup_manager, provider)
+        .aggLayerGateway()
+        .call()
+        .await
+    {
+        Ok(gateway) => gateway,
+        Err(error) => {
+            failures.push(StartupCheckError::l1_query(
+                "fetch the gateway address",
+                error,
+            ));
+            return failures;
+        }
+    };
+    failures.extend(check_contract(provider, "gateway", gateway).await);
+
+    match AgglayerGateway::new(gateway, provider)
+        .pessimisticVKeyRoutes(FixedBytes(PESSIMISTIC_PROOF_PROGRAM_SELECTOR))
+        .call()
+        .await
+    {
+        Ok(route) => {
+            failures.extend(check_contract(provider, "verifier", route.verifier).await);
+            failures.extend(check_vkey(route.pessimisticVKey, embedded_vkey));
+        }
+        Err(error) => failures.push(StartupCheckError::l1_query(
+            "fetch the pessimistic proof route",
+            error,
+        )),
+    }
+
+    failures
+}
+
+async fn check_contract<P: Provider>(
+    provider: &P,
+    contract: &'static str,
+    address: Address,
+) -> Option<StartupCheckError> {
+    match provider.get_code_at(address).await {
+        Ok(code) if code.is_empty() => {
+            Some(StartupCheckError::MissingContract { contract, address })
+        }
+        Ok(_) => None,
+        Err(error) => Some(StartupCheckError::l1_query(
+            "fetch the contract code",
+            error,
+        )),
+    }
+}
+
+fn check_chain_id(configured: u64, actual: u64) -> Option<StartupCheckError> {
+    (configured != actual).then_some(StartupCheckError::ChainIdMismatch { configured, actual })
+}
+
+fn check_vkey(on_chain: B256, embedded: B256) -> Option<StartupCheckError> {
+    (on_chain != embedded).then_some(StartupCheckError::VKeyMismatch { on_chain, embedded })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_id_must_match() {
+        assert!(check_chain_id(1, 1).is_none());
+        assert!(matches!(
+            check_chain_id(1, 11155111),
+            Some(StartupCheckError::ChainIdMismatch {
+                configured: 1,
+                actual: 11155111
+            })
+        ));
+    }
+
+    #[test]
+    fn vkey_must_match() {
+        let embedded = B256::repeat_byte(0xaa);
+
+        assert!(check_vkey(embedded, embedded).is_none());
+        assert!(matches!(
+            check_vkey(B256::ZERO, embedded),
+            Some(StartupCheckError::VKeyMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+//! Replay of the certification of a stored certificate, to debug the
+//! discrepancies between environments.
+//!
+//! The storage is opened read-only, which allows to replay alongside a running
+//! node, and against a snapshot of the state storage taken in another
+//! environment.
+
+use std::{path::Path, sync::Arc};
+
+use agglayer_aggregator_notifier::{CertificationReplay, CertifierClient};
+use agglayer_config::Config;
+use agglayer_contracts::{contracts::PolygonRollupManager, L1RpcClient};
+use agglayer_storage::{
+    storage::{backup::BackupClient, pending_db_cf_definitions, state_db_cf_definitions, DB},
+    stores::{
+        epochs::EpochsStore, pending::PendingStore, state::StateStore, EpochStoreReader as _,
+        PendingCertificateReader as _, StateReader as _,
+    },
+};
+use agglayer_types::{CertificateId, Digest, EpochNumber};
+use alloy::providers::ProviderBuilder;
+use eyre::{eyre, Context as _};
+use tracing::{info, warn};
+
+use crate::node::http_client;
+
+/// Replay the certification of the certificate against the state of its
+/// network in the given state storage snapshot, or in the state storage of
+/// the node otherwise.
+///
+/// The state storage of the node holds the state once the settled
+/// certificates are applied, replaying a settled certificate requires a
+/// snapshot taken before its settlement.
+pub async fn replay_certification(
+    config: Arc<Config>,
+    certificate_id: CertificateId,
+    state_snapshot: Option<&Path>,
+) -> eyre::Result<CertificationReplay> {
+    let pending_db =
+        DB::open_cf_readonly(&config.storage.pending_db_path, pending_db_cf_definitions())
+            .context("Failed to open the pending database")?;
+    let state_db = DB::open_cf_readonly(&config.storage.state_db_path, state_db_cf_definitions())
+        .context("Failed to open the state database")?;
+    let pending_store = Arc::new(PendingStore::new(Arc::new(pending_db)));
+    let state_store = Arc::new(StateStore::new(Arc::new(state_db), BackupClient::noop()));
+
+    let header = state_store
+        .get_certificate_header(&certificate_id)?
+        .ok_or_else(|| eyre!("Unknown certificate {certificate_id}"))?;
+
+    // The certificate is pending until its epoch is packed.
+    let certificate = match pending_store.get_certificate(header.network_id, header.height)? {
+        Some(certificate) if certificate.hash() == certificate_id => certificate,
+        _ => {
+            let (Some(epoch_number), Some(certificate_index)) =
+                (header.epoch_number, header.certificate_index)
+            else {
+                return Err(eyre!(
+                    "The certificate {certificate_id} is no longer stored"
+                ));
+            };
+
+            // The per-epoch storages are opened read-only by the readers.
+            let epochs_store = EpochsStore::new(
+                config.clone(),
+                EpochNumber::ZERO,
+                pending_store.clone(),
+                state_store.clone(),
+                BackupClient::noop(),
+            )?;
+            epochs_store
+                .get_certificate(epoch_number, certificate_index)?
+                .ok_or_else(|| {
+                    eyre!(
+                        "The certificate {certificate_id} is missing from the epoch {epoch_number}"
+                    )
+                })?
+        }
+    };
+
+    let state = match state_snapshot {
+        Some(path) => {
+            let snapshot = DB::open_cf_readonly(path, state_db_cf_definitions())
+                .with_context(|| format!("Failed to open the state snapshot {}", path.display()))?;
+            StateStore::new(Arc::new(snapshot), BackupClient::noop())
+                .read_local_network_state(header.network_id)?
+        }
+        None => state_store.read_local_network_state(header.network_id)?,
+    }
+    .unwrap_or_default();
+
+    // The roots the certificate was certified against, to spot a snapshot
+    // which is not the one the certificate was certified from.
+    if let Some(roots) = pending_store.get_certified_roots(&certificate_id)? {
+        if roots.initial_roots != state.get_roots() {
+            warn!(
+                certified_from = ?roots.initial_roots,
+                replayed_from = ?state.get_roots(),
+                "The state differs from the one the certificate was certified against"
+            );
+        }
+    }
+
+    // No transaction is sent to L1, hence no signer.
+    let rpc = Arc::new(ProviderBuilder::new().on_client(http_client::l1_rpc_client(
+        &config.outbound.http,
+        config.l1.node_url.clone(),
+    )?));
+    let l1_rpc = Arc::new(
+        L1RpcClient::try_new(
+            rpc.clone(),
+            PolygonRollupManager::new(config.l1.rollup_manager_contract.into(), (*rpc).clone()),
+            config.l1.polygon_zkevm_global_exit_root_v2_contract.into(),
+            config.outbound.rpc.settle.gas_multiplier_factor,
+            {
+                let gas_config = &config.outbound.rpc.settle.gas_price;
+                agglayer_contracts::GasPriceParams::new(
+                    gas_config.multiplier.as_u64_per_1000(),
+                    gas_config.floor..=gas_config.ceiling,
+                )?
+            },
+            config.l1.event_filter_block_range.get(),
+        )
+        .await?,
+    );
+
+    let certifier = CertifierClient::for_replay(pending_store, l1_rpc, config).await?;
+
+    info!(%certificate_id, "Replaying the certification of the certificate");
+
+    // A settled certificate is replayed against the L1 context of its
+    // settlement.
+    let replay = certifier
+        .replay(
+            &certificate,
+            state,
+            header.settlement_tx_hash.map(Digest::from),
+        )
+        .await?;
+
+    Ok(replay)
+}
@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use agglayer_primitives::Digest;
+use serde::{Deserialize, Serialize};
+
+/// ELF of the pessimistic proof program loaded at runtime, for the binaries
+/// built without the embedded one.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProgramElfConfig {
+    /// Path of the ELF.
+    pub path: PathBuf,
+
+    /// Keccak hash of the ELF, checked when loading it.
+    pub hash: Digest,
+}
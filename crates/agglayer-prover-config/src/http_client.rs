@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Configuration of the outbound HTTP connections, for the deployments
+/// reaching the external services through a proxy or a TLS-intercepting
+/// gateway.
+///
+/// The connections use the system proxies and CA certificates when unset.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HttpClientConfig {
+    /// PEM bundle of the CA certificates trusted in addition to the system
+    /// ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// PEM file of the client certificate, followed by its private key, for
+    /// the endpoints requiring mutual TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_identity: Option<PathBuf>,
+
+    /// Proxy of the plain HTTP connections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_proxy: Option<String>,
+
+    /// Proxy of the HTTPS connections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+
+    /// Hosts reached without going through the proxies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_proxy: Vec<String>,
+}
@@ -4,13 +4,19 @@ use std::{
     time::Duration,
 };
 
+use agglayer_primitives::Digest;
 use prover_config::{default_max_concurrency_limit, NetworkProverConfig, ProverType};
 use prover_logger::log::Log;
 use prover_utils::with;
 use serde::{Deserialize, Serialize};
 
-pub use crate::{shutdown::ShutdownConfig, telemetry::TelemetryConfig};
+pub use crate::{
+    elf::ProgramElfConfig, http_client::HttpClientConfig, shutdown::ShutdownConfig,
+    telemetry::TelemetryConfig,
+};
 
+pub(crate) mod elf;
+pub(crate) mod http_client;
 pub mod shutdown;
 pub(crate) mod telemetry;
 
@@ -60,6 +66,20 @@ pub struct ProverConfig {
     /// The fallback prover to be used for generation of the pessimistic proof
     #[serde(default)]
     pub fallback_prover: Option<ProverType>,
+
+    /// Proxies and TLS settings of the connections to the prover network.
+    #[serde(default, skip_serializing_if = "crate::default")]
+    pub outbound_http: HttpClientConfig,
+
+    /// ELF of the pessimistic proof program to load at runtime, instead of
+    /// the one embedded in the binary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elf: Option<ProgramElfConfig>,
+
+    /// Expected keccak hash of the pessimistic proof program, embedded or
+    /// loaded, checked at startup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_elf_hash: Option<Digest>,
 }
 
 impl Default for ProverConfig {
@@ -75,6 +95,9 @@ impl Default for ProverConfig {
             primary_prover: ProverType::NetworkProver(NetworkProverConfig::default()),
             fallback_prover: None,
             grpc: Default::default(),
+            outbound_http: Default::default(),
+            elf: None,
+            expected_elf_hash: None,
         }
     }
 }
@@ -123,6 +146,16 @@ impl Default for GrpcConfig {
 pub struct ClientProverConfig {
     #[serde(default)]
     pub grpc: GrpcConfig,
+
+    /// ELF of the pessimistic proof program to load at runtime, instead of
+    /// the one embedded in the binary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elf: Option<ProgramElfConfig>,
+
+    /// Expected keccak hash of the pessimistic proof program, embedded or
+    /// loaded, checked at startup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_elf_hash: Option<Digest>,
 }
 
 const fn default_max_decoding_message_size() -> usize {
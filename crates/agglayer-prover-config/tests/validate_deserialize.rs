@@ -43,3 +43,50 @@ fn prover_grpc_max_decoding_message_size() {
 
     assert_eq!(config.grpc.max_decoding_message_size, 100 * 1024 * 1024);
 }
+
+#[test]
+fn outbound_http() {
+    let input = "./tests/fixtures/validate_config/outbound_http.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert_eq!(
+        config.outbound_http.ca_bundle,
+        Some("/etc/agglayer/ca.pem".into())
+    );
+    assert_eq!(
+        config.outbound_http.https_proxy.as_deref(),
+        Some("http://proxy.internal:3128")
+    );
+    assert!(config.outbound_http.no_proxy.is_empty());
+}
+
+#[test]
+fn program_elf() {
+    let input = "./tests/fixtures/validate_config/program_elf.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    let elf = config.elf.unwrap();
+    assert_eq!(
+        elf.path,
+        Path::new("/opt/agglayer/pessimistic-proof-program.elf")
+    );
+    assert_eq!(
+        elf.hash.to_string(),
+        "0x27ae5ba08d7291c96c8cbddcc148bf48a6d68c7974b94356f53754ef6171d757"
+    );
+}
+
+#[test]
+fn expected_elf_hash() {
+    let input = "./tests/fixtures/validate_config/expected_elf_hash.toml";
+
+    let config = Config::try_load(Path::new(input)).unwrap();
+
+    assert!(config.elf.is_none());
+    assert_eq!(
+        config.expected_elf_hash.unwrap().to_string(),
+        "0x27ae5ba08d7291c96c8cbddcc148bf48a6d68c7974b94356f53754ef6171d757"
+    );
+}
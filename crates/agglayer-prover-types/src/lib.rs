@@ -12,6 +12,7 @@ pub enum Proof {
     SP1(SP1ProofWithPublicValues),
 }
 pub mod error;
+pub mod mock;
 pub use agglayer_interop::types::bincode;
 pub use error::{Error, ErrorWrapper};
 use serde::{Deserialize, Serialize};
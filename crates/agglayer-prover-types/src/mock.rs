@@ -0,0 +1,76 @@
+//! Deterministic mock proofs.
+//!
+//! Mock proofs are content-addressed: the encoded proof is a digest of the
+//! verifying key and of the public values. Proving the same program over the
+//! same inputs always results in the same proof, which lets tests assert the
+//! identity of the proofs going through the node.
+
+use agglayer_primitives::{keccak::keccak256_combine, Digest};
+use sp1_sdk::{
+    HashableKey as _, SP1Proof, SP1ProofMode, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1PublicValues, SP1VerifyingKey, SP1_CIRCUIT_VERSION,
+};
+
+const MOCK_PROOF_DOMAIN: &[u8] = b"AGGLAYER_MOCK_PROOF";
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MockProofError {
+    #[error("Expected a plonk mock proof")]
+    UnexpectedProofMode,
+
+    #[error("Mock proof mismatch: expected {expected}, got {got}")]
+    Mismatch { expected: String, got: String },
+}
+
+/// Digest identifying the mock proof of the given public values.
+pub fn mock_proof_digest(
+    verifying_key: &SP1VerifyingKey,
+    public_values: &SP1PublicValues,
+) -> Digest {
+    keccak256_combine([
+        MOCK_PROOF_DOMAIN,
+        verifying_key.bytes32_raw().as_slice(),
+        public_values.as_slice(),
+    ])
+}
+
+/// Build the mock proof of the given public values.
+pub fn mock_proof(
+    proving_key: &SP1ProvingKey,
+    public_values: SP1PublicValues,
+) -> SP1ProofWithPublicValues {
+    let encoded_proof = hex::encode(mock_proof_digest(&proving_key.vk, &public_values).0);
+
+    let mut proof = SP1ProofWithPublicValues::create_mock_proof(
+        proving_key,
+        public_values,
+        SP1ProofMode::Plonk,
+        SP1_CIRCUIT_VERSION,
+    );
+
+    if let SP1Proof::Plonk(ref mut plonk) = proof.proof {
+        plonk.encoded_proof = encoded_proof;
+    }
+
+    proof
+}
+
+/// Check that the proof is the mock proof of its public values.
+pub fn verify_mock_proof(
+    proof: &SP1ProofWithPublicValues,
+    verifying_key: &SP1VerifyingKey,
+) -> Result<(), MockProofError> {
+    let SP1Proof::Plonk(ref plonk) = proof.proof else {
+        return Err(MockProofError::UnexpectedProofMode);
+    };
+
+    let expected = hex::encode(mock_proof_digest(verifying_key, &proof.public_values).0);
+    if plonk.encoded_proof != expected {
+        return Err(MockProofError::Mismatch {
+            expected,
+            got: plonk.encoded_proof.clone(),
+        });
+    }
+
+    Ok(())
+}
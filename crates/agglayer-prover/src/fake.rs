@@ -1,8 +1,8 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use agglayer_prover_config::DEFAULT_GRPC_MESSAGE_SIZE;
 use agglayer_prover_types::{
-    bincode,
+    bincode, mock,
     v1::{
         generate_proof_request::Stdin,
         pessimistic_proof_service_server::{
@@ -16,9 +16,14 @@ use sp1_sdk::{CpuProver, Prover as _, ProverClient, SP1Stdin};
 use tonic::{codec::CompressionEncoding, transport::Server};
 use tracing::{debug, error, info, warn};
 
+/// Prover service generating deterministic mock proofs, see
+/// [`agglayer_prover_types::mock`].
 pub struct FakeProver {
     prover: Arc<CpuProver>,
+    elf: &'static [u8],
     proving_key: sp1_sdk::SP1ProvingKey,
+    /// Time spent before answering each proof generation request.
+    proving_delay: Duration,
 }
 
 impl FakeProver {
@@ -27,12 +32,21 @@ impl FakeProver {
             let prover = ProverClient::builder().mock().build();
             let (proving_key, _verifying_key) = prover.setup(elf);
             Self {
+                elf,
                 proving_key,
                 prover: Arc::new(prover),
+                proving_delay: Duration::ZERO,
             }
         })
         .await
     }
+
+    /// Delay the answer to each proof generation request, to simulate a slow
+    /// proving.
+    pub fn with_proving_delay(mut self, proving_delay: Duration) -> Self {
+        self.proving_delay = proving_delay;
+        self
+    }
 }
 
 impl FakeProver {
@@ -90,6 +104,8 @@ impl PessimisticProofService for FakeProver {
     ) -> Result<tonic::Response<agglayer_prover_types::v1::GenerateProofResponse>, tonic::Status>
     {
         debug!("Received proof generation request");
+        tokio::time::sleep(self.proving_delay).await;
+
         let request_inner = request.into_inner();
         let stdin: SP1Stdin = match request_inner.stdin {
             Some(Stdin::Sp1Stdin(stdin)) => sp1_fast(|| bincode::default().deserialize(&stdin))
@@ -100,9 +116,10 @@ impl PessimisticProofService for FakeProver {
             }
         };
 
-        let result = sp1_fast(|| self.prover.prove(&self.proving_key, &stdin).plonk().run())
+        let result = sp1_fast(|| self.prover.execute(self.elf, &stdin).run())
             .map_err(|error| Error::ProverFailed(error.to_string()))
-            .and_then(|res| res.map_err(|error| Error::ProverFailed(error.to_string())));
+            .and_then(|res| res.map_err(|error| Error::ProverFailed(error.to_string())))
+            .map(|(public_values, _report)| mock::mock_proof(&self.proving_key, public_values));
         match result {
             Ok(proof) => {
                 let proof = sp1_fast(|| {
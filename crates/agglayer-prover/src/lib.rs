@@ -1,11 +1,13 @@
 use std::{path::PathBuf, sync::Arc};
 
+use agglayer_prover_config::HttpClientConfig;
 use eyre::Context as _;
 use prover_engine::ProverEngine;
 
 #[cfg(feature = "testutils")]
 pub mod fake;
 pub mod prover;
+pub mod replay;
 mod rpc;
 
 /// This is the main prover entrypoint.
@@ -18,6 +20,7 @@ mod rpc;
 /// completed.
 pub fn main(cfg: PathBuf, version: &str, program: &'static [u8]) -> eyre::Result<()> {
     let config = Arc::new(agglayer_prover_config::ProverConfig::try_load(&cfg)?);
+    export_outbound_http(&config.outbound_http)?;
 
     // Initialize the logger
     prover_logger::tracing(&config.log);
@@ -53,6 +56,41 @@ pub fn main(cfg: PathBuf, version: &str, program: &'static [u8]) -> eyre::Result
     .start()
 }
 
+/// Pass the outbound HTTP configuration to the client of the prover network,
+/// which is built by the executor, through the environment variables it
+/// honors. Must be called before any other thread is started.
+///
+/// The CA bundle replaces the system certificates for this client, so it has
+/// to include them if they are still needed.
+fn export_outbound_http(config: &HttpClientConfig) -> eyre::Result<()> {
+    if config.client_identity.is_some() {
+        eyre::bail!("Client certificates are not supported for the prover network connections");
+    }
+
+    let variables = [
+        ("HTTP_PROXY", config.http_proxy.clone()),
+        ("HTTPS_PROXY", config.https_proxy.clone()),
+        (
+            "NO_PROXY",
+            (!config.no_proxy.is_empty()).then(|| config.no_proxy.join(",")),
+        ),
+        (
+            "SSL_CERT_FILE",
+            config
+                .ca_bundle
+                .as_ref()
+                .map(|path| path.display().to_string()),
+        ),
+    ];
+    for (name, value) in variables {
+        if let Some(value) = value {
+            std::env::set_var(name, value);
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn compute_program_vkey(program: &'static [u8]) -> eyre::Result<String> {
     let vkey = prover_executor::Executor::compute_program_vkey(program)
         .await
@@ -0,0 +1,72 @@
+//! Replay of the proving of a certificate from the SP1 stdin captured by the
+//! node when its proving failed.
+
+use std::path::Path;
+
+use eyre::{eyre, Context as _};
+use prover_executor::{sp1_blocking, sp1_fast};
+use sp1_sdk::{Prover as _, ProverClient, SP1Stdin};
+use tracing::info;
+
+/// Outcome of the replay of a captured stdin.
+#[derive(Debug)]
+pub struct ReplayReport {
+    /// Number of cycles executed by the program.
+    pub cycles: u64,
+    /// Public values committed by the program.
+    pub public_values: Vec<u8>,
+    /// Whether a proof was generated and verified.
+    pub proven: bool,
+}
+
+/// Read a stdin captured by the node, encoded as sent to the prover service.
+pub fn read_stdin(path: &Path) -> eyre::Result<SP1Stdin> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read the stdin at {}", path.display()))?;
+
+    sp1_fast(|| agglayer_prover_types::bincode::default().deserialize(&bytes))?
+        .context("Failed to deserialize the stdin")
+}
+
+/// Execute the program on the captured stdin and, if requested, generate and
+/// verify its proof with the local CPU prover.
+pub async fn replay(
+    program: &'static [u8],
+    stdin_path: &Path,
+    prove: bool,
+) -> eyre::Result<ReplayReport> {
+    let stdin = read_stdin(stdin_path)?;
+
+    sp1_blocking(move || {
+        let client = ProverClient::builder().cpu().build();
+
+        let (public_values, report) = client
+            .execute(program, &stdin)
+            .run()
+            .map_err(|error| eyre!(error))
+            .context("Failed to execute the program")?;
+        let cycles = report.total_instruction_count();
+        info!(cycles, "Successfully executed the program");
+
+        if prove {
+            let (proving_key, verifying_key) = client.setup(program);
+            let proof = client
+                .prove(&proving_key, &stdin)
+                .plonk()
+                .run()
+                .map_err(|error| eyre!(error))
+                .context("Failed to generate the proof")?;
+            client
+                .verify(&proof, &verifying_key)
+                .context("Failed to verify the proof")?;
+            info!("Successfully generated and verified the proof");
+        }
+
+        Ok(ReplayReport {
+            cycles,
+            public_values: public_values.to_vec(),
+            proven: prove,
+        })
+    })
+    .await?
+}
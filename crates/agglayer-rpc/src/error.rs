@@ -2,10 +2,14 @@
 use agglayer_contracts::L1RpcError;
 pub use agglayer_storage::error::Error as StorageError;
 pub use agglayer_types::primitives::Digest;
-use agglayer_types::{Address, CertificateId, Height, NetworkId, SignerError};
+use agglayer_types::{
+    Address, CertificateId, CertificateStatus, EpochNumber, GlobalIndex, GlobalIndexError, Height,
+    NetworkId, SignerError,
+};
 use alloy::contract::Error as ContractError;
+use pessimistic_proof::unified_bridge;
 
-pub use crate::rate_limiting::RateLimited as RateLimitedError;
+pub use crate::{rate_limiting::RateLimited as RateLimitedError, screening::ScreeningError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CertificateRetrievalError {
@@ -27,6 +31,69 @@ pub enum CertificateSubmissionError {
     #[error("Failed to validate certificate signature: {0}")]
     SignatureError(#[source] SignatureVerificationError),
 
+    #[error("Network {network_id} is not registered on L1")]
+    UnknownNetwork { network_id: NetworkId },
+
+    #[error("Invalid callback URL {url}: {reason}")]
+    InvalidCallbackUrl { url: String, reason: &'static str },
+
+    #[error("Certificate {certificate_id} is already registered with another callback URL")]
+    ConflictingCallbackUrl { certificate_id: CertificateId },
+
+    #[error("Invalid global index {global_index:?} in the imported bridge exits: {source}")]
+    InvalidGlobalIndex {
+        global_index: GlobalIndex,
+        #[source]
+        source: GlobalIndexError,
+    },
+
+    #[error("Too many bridge exits: {count}, the maximum is {max}")]
+    TooManyBridgeExits { count: usize, max: usize },
+
+    #[error("Too many imported bridge exits: {count}, the maximum is {max}")]
+    TooManyImportedBridgeExits { count: usize, max: usize },
+
+    #[error("Certificate too large: {size} bytes, the maximum is {max}")]
+    CertificateTooLarge { size: usize, max: usize },
+
+    #[error("Bridge exit to the blocked address {address}")]
+    BlockedBridgeExit { address: Address },
+
+    #[error("Failed to screen the bridge exits: {0}")]
+    ScreeningFailed(#[source] ScreeningError),
+
+    #[error("Inconsistent imported bridge exits: {0}")]
+    InconsistentImportedBridgeExits(#[source] agglayer_types::Error),
+
+    #[error("Invalid inclusion proof of the imported bridge exit {global_index:?}: {source}")]
+    InvalidImportedBridgeExit {
+        global_index: GlobalIndex,
+        #[source]
+        source: unified_bridge::Error,
+    },
+
+    #[error(
+        "Unexpected certificate height {height} for network {network_id}, expected \
+         {expected_height}"
+    )]
+    UnexpectedHeight {
+        network_id: NetworkId,
+        height: Height,
+        expected_height: Height,
+    },
+
+    #[error("Too many certificates are waiting for validation, retry later")]
+    IntakeOverloaded,
+
+    #[error("Failed to validate the certificate")]
+    IntakeWorkerFailed,
+
+    #[error("The agglayer is in maintenance and doesn't accept new certificates, retry later")]
+    Maintenance,
+
+    #[error("The agglayer is read-only and doesn't accept new certificates")]
+    ReadOnly,
+
     #[error("Unable to replace pending certificate at height {height} for network {network_id}")]
     UnableToReplacePendingCertificate {
         reason: String,
@@ -133,6 +200,65 @@ pub enum ProofRetrievalError {
     NotFound { certificate_id: CertificateId },
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ProofSubmissionError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("Certificate {certificate_id} not found")]
+    CertificateNotFound { certificate_id: CertificateId },
+
+    #[error("Certificate {certificate_id} is {status}, a proof can no longer be submitted")]
+    CertificateNotPending {
+        certificate_id: CertificateId,
+        status: CertificateStatus,
+    },
+
+    #[error("Unable to retrieve the trusted sequencer address of network {network_id}")]
+    UnableToRetrieveSigner {
+        network_id: NetworkId,
+        #[source]
+        source: L1RpcError,
+    },
+
+    #[error("Invalid proof submission signature, expected signer: {expected_signer}")]
+    InvalidSignature { expected_signer: Address },
+
+    #[error("Failed to encode the submitted proof")]
+    ProofEncoding(#[source] agglayer_types::bincode::Error),
+
+    #[error("The agglayer is read-only and doesn't accept proofs")]
+    ReadOnly,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CertificateCancellationError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("Certificate {certificate_id} not found")]
+    NotFound { certificate_id: CertificateId },
+
+    #[error("Certificate {certificate_id} is {status}, it can no longer be cancelled")]
+    NotPending {
+        certificate_id: CertificateId,
+        status: CertificateStatus,
+    },
+
+    #[error("Unable to retrieve the trusted sequencer address of network {network_id}")]
+    UnableToRetrieveSigner {
+        network_id: NetworkId,
+        #[source]
+        source: L1RpcError,
+    },
+
+    #[error("Invalid cancellation signature, expected signer: {expected_signer}")]
+    InvalidSignature { expected_signer: Address },
+
+    #[error("The agglayer is read-only and doesn't cancel certificates")]
+    ReadOnly,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum GetLatestCertificateError {
     #[error(transparent)]
@@ -181,3 +307,109 @@ pub enum GetNetworkInfoError {
         source: eyre::Error,
     },
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetCertificateStatusesError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("Too many certificates requested: {requested}, the maximum is {max}")]
+    TooManyCertificates { requested: usize, max: usize },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetNetworksError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetCurrentEpochStatusError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("The agglayer isn't clocked, thus no epoch status is available")]
+    NoClock,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetNetworkRootsError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("No settled certificate for network {network_id}")]
+    NoSettledCertificate { network_id: NetworkId },
+
+    #[error(
+        "Height {height} of network {network_id} is not settled, the latest settled height is \
+         {settled_height}"
+    )]
+    HeightNotSettled {
+        network_id: NetworkId,
+        height: Height,
+        settled_height: Height,
+    },
+
+    #[error("The roots of network {network_id} at height {height} were not recorded")]
+    RootsNotRecorded {
+        network_id: NetworkId,
+        height: Height,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetSettledExitProofError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("No settled certificate for network {network_id}")]
+    NoSettledCertificate { network_id: NetworkId },
+
+    #[error(
+        "Leaf {leaf_index} is not in the settled local exit tree of network {network_id}, which \
+         has {leaf_count} leaves"
+    )]
+    LeafIndexOutOfBounds {
+        network_id: NetworkId,
+        leaf_index: u32,
+        leaf_count: u32,
+    },
+
+    /// The stored local exit tree is not the settled one, which happens
+    /// briefly while a new certificate of the network is being settled.
+    #[error(
+        "The local exit tree of network {network_id} doesn't match its latest settled local exit \
+         root"
+    )]
+    InconsistentExitTree { network_id: NetworkId },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetSettlementCostsError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("Invalid epoch range: {from_epoch} is after {to_epoch}")]
+    InvalidEpochRange {
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuotaError {
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error("Missing API key")]
+    MissingApiKey,
+
+    #[error("Unknown API key")]
+    UnknownApiKey,
+
+    #[error("Request quota of API key {name} exhausted: {limit} requests per period")]
+    RequestsExhausted { name: String, limit: u64 },
+
+    #[error("Certificate quota of API key {name} exhausted: {limit} bytes per period")]
+    CertificateBytesExhausted { name: String, limit: u64 },
+}
@@ -0,0 +1,313 @@
+//! Validation of the submitted certificates on a bounded pool of blocking
+//! workers, off the RPC executor.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use agglayer_config::{CertificateLimits, RpcIntakeConfig};
+use agglayer_telemetry::intake as metrics;
+use agglayer_types::{
+    aggchain_data::MultisigCtx, aggchain_proof::AggchainData, bincode, validate_global_index,
+    Address, Certificate, NetworkId, Signature,
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{error, warn};
+
+use crate::{error::SignatureVerificationError, CertificateSubmissionError};
+
+/// Context of the verification of the certificate signature, fetched from L1.
+pub(crate) enum SignatureVerificationCtx {
+    /// The trusted sequencer expected to sign the certificate.
+    TrustedSequencer(Address),
+    /// The signers and threshold of the multisig of the network.
+    Multisig(MultisigCtx),
+}
+
+/// Checks of a certificate run on an intake worker.
+pub(crate) struct IntakeValidation {
+    pub(crate) limits: CertificateLimits,
+    pub(crate) extra_signer: Option<Address>,
+    pub(crate) extra_signature: Option<Signature>,
+    pub(crate) signature_ctx: SignatureVerificationCtx,
+}
+
+impl IntakeValidation {
+    /// Check the size of the certificate, the inclusion proofs of its
+    /// imported bridge exits and its signatures.
+    pub(crate) fn validate(
+        self,
+        certificate: &Certificate,
+    ) -> Result<(), CertificateSubmissionError> {
+        if let Some(max) = self.limits.max_bridge_exits {
+            let count = certificate.bridge_exits.len();
+            if count > max {
+                return Err(CertificateSubmissionError::TooManyBridgeExits { count, max });
+            }
+        }
+
+        if let Some(max) = self.limits.max_imported_bridge_exits {
+            let count = certificate.imported_bridge_exits.len();
+            if count > max {
+                return Err(CertificateSubmissionError::TooManyImportedBridgeExits { count, max });
+            }
+        }
+
+        if let Some(max) = self.limits.max_certificate_size {
+            let size = certificate_size(certificate)?;
+            if size > max {
+                return Err(CertificateSubmissionError::CertificateTooLarge { size, max });
+            }
+        }
+
+        validate_imported_bridge_exits(certificate)?;
+
+        verify_extra_signature(certificate, self.extra_signer, self.extra_signature).map_err(
+            |error| {
+                error!(
+                    ?error,
+                    "Failed to verify the extra signature for the certificate"
+                );
+                CertificateSubmissionError::SignatureError(error)
+            },
+        )?;
+
+        verify_signature(certificate, self.signature_ctx).map_err(|error| {
+            error!(
+                ?error,
+                "Failed to verify the signature within the certificate"
+            );
+            CertificateSubmissionError::SignatureError(error)
+        })
+    }
+}
+
+/// Size of the certificate once serialized, as it is stored and sent to the
+/// prover.
+fn certificate_size(certificate: &Certificate) -> Result<usize, CertificateSubmissionError> {
+    bincode::default()
+        .serialize(certificate)
+        .map(|bytes| bytes.len())
+        .map_err(|error| {
+            error!(?error, "Failed to serialize the certificate");
+            CertificateSubmissionError::IntakeWorkerFailed
+        })
+}
+
+/// Check the global indexes and the inclusion proofs of the imported bridge
+/// exits against the L1 info root they refer to.
+fn validate_imported_bridge_exits(
+    certificate: &Certificate,
+) -> Result<(), CertificateSubmissionError> {
+    for imported_bridge_exit in &certificate.imported_bridge_exits {
+        let global_index = imported_bridge_exit.global_index;
+        validate_global_index(global_index).map_err(|source| {
+            CertificateSubmissionError::InvalidGlobalIndex {
+                global_index,
+                source,
+            }
+        })?;
+
+        if !imported_bridge_exit.valid_claim() {
+            return Err(CertificateSubmissionError::InconsistentImportedBridgeExits(
+                agglayer_types::Error::InconsistentGlobalExitRoot,
+            ));
+        }
+    }
+
+    let Some(l1_info_root) = certificate
+        .l1_info_root()
+        .map_err(CertificateSubmissionError::InconsistentImportedBridgeExits)?
+    else {
+        return Ok(());
+    };
+
+    for imported_bridge_exit in &certificate.imported_bridge_exits {
+        imported_bridge_exit
+            .verify_path(l1_info_root)
+            .map_err(
+                |source| CertificateSubmissionError::InvalidImportedBridgeExit {
+                    global_index: imported_bridge_exit.global_index,
+                    source,
+                },
+            )?;
+    }
+
+    Ok(())
+}
+
+/// Verify the extra [`Certificate`] signature.
+pub(crate) fn verify_extra_signature(
+    certificate: &Certificate,
+    extra_signer: Option<Address>,
+    extra_signature: Option<Signature>,
+) -> Result<(), SignatureVerificationError> {
+    match (extra_signer, extra_signature) {
+        // Extra signature expected and provided
+        (Some(expected_extra_signer), Some(extra_signature)) => certificate
+            .verify_extra_signature(expected_extra_signer, extra_signature)
+            .map_err(SignatureVerificationError::from_signer_error)?,
+        // Extra signature is expected but missing
+        (Some(expected_signer), None) => {
+            return Err(SignatureVerificationError::MissingExtraSignature {
+                network_id: certificate.network_id,
+                expected_signer,
+            });
+        }
+        // Extra signature provided but not required
+        (None, Some(_)) => {
+            warn!("Unexpected extra signature provided");
+        }
+        // No extra signature provided nor required
+        (None, None) => {}
+    };
+
+    Ok(())
+}
+
+/// Verify the signature of the [`Certificate`] against the context fetched
+/// from L1.
+pub(crate) fn verify_signature(
+    certificate: &Certificate,
+    ctx: SignatureVerificationCtx,
+) -> Result<(), SignatureVerificationError> {
+    match (&certificate.aggchain_data, ctx) {
+        (AggchainData::ECDSA { signature }, SignatureVerificationCtx::TrustedSequencer(signer)) => {
+            certificate.verify_legacy_ecdsa(signer, signature)
+        }
+        (
+            AggchainData::Generic { signature, .. },
+            SignatureVerificationCtx::TrustedSequencer(signer),
+        ) => certificate.verify_aggchain_proof_signature(signer, signature),
+        (AggchainData::MultisigOnly { multisig }, SignatureVerificationCtx::Multisig(ctx))
+        | (
+            AggchainData::MultisigAndAggchainProof { multisig, .. },
+            SignatureVerificationCtx::Multisig(ctx),
+        ) => certificate.verify_multisig(multisig.into(), ctx),
+        _ => unreachable!("the signature context is fetched for the aggchain data"),
+    }
+    .map_err(SignatureVerificationError::from_signer_error)
+}
+
+/// Bounded pool of blocking workers validating the submitted certificates.
+pub(crate) struct IntakePool {
+    workers: Arc<Semaphore>,
+    queued: AtomicUsize,
+    queue_size: usize,
+}
+
+impl IntakePool {
+    pub(crate) fn new(config: &RpcIntakeConfig) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(config.workers.max(1))),
+            queued: AtomicUsize::new(0),
+            queue_size: config.queue_size,
+        }
+    }
+
+    /// Run the given validation on a worker, once one is available.
+    ///
+    /// Fails without waiting if the queue of certificates waiting for a
+    /// worker is full.
+    pub(crate) async fn run<T, F>(
+        &self,
+        network_id: NetworkId,
+        validation: F,
+    ) -> Result<T, CertificateSubmissionError>
+    where
+        F: FnOnce() -> Result<T, CertificateSubmissionError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let queued_at = Instant::now();
+
+        let permit = match self.workers.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let Some(_slot) = QueueSlot::take(&self.queued, self.queue_size) else {
+                    warn!("Rejecting the certificate, too many are waiting for validation");
+                    metrics::record_rejected(network_id.to_u32(), "overloaded");
+                    return Err(CertificateSubmissionError::IntakeOverloaded);
+                };
+
+                self.workers
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .map_err(|error| {
+                        error!(?error, "Intake worker pool is closed");
+                        CertificateSubmissionError::IntakeWorkerFailed
+                    })?
+            }
+        };
+        let wait = queued_at.elapsed();
+
+        let started_at = Instant::now();
+        let result = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            validation()
+        })
+        .await
+        .map_err(|error| {
+            error!(?error, "Intake worker failed to validate the certificate");
+            CertificateSubmissionError::IntakeWorkerFailed
+        })?;
+
+        metrics::record_validation(network_id.to_u32(), wait, started_at.elapsed());
+        if result.is_err() {
+            metrics::record_rejected(network_id.to_u32(), "invalid");
+        }
+
+        result
+    }
+}
+
+/// Bounded pool of the proving cost estimations of candidate certificates,
+/// rejecting the estimations beyond its size rather than queueing them.
+pub(crate) struct EstimationPool {
+    workers: Arc<Semaphore>,
+}
+
+impl EstimationPool {
+    pub(crate) fn new(config: &RpcIntakeConfig) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(config.estimation_workers.max(1))),
+        }
+    }
+
+    /// Take a slot of the pool, held until the returned permit is dropped.
+    pub(crate) fn try_acquire(&self) -> Result<OwnedSemaphorePermit, CertificateSubmissionError> {
+        self.workers.clone().try_acquire_owned().map_err(|_| {
+            warn!("Rejecting the estimation, too many are running");
+            CertificateSubmissionError::IntakeOverloaded
+        })
+    }
+}
+
+/// Slot in the queue of certificates waiting for a worker, released on drop so
+/// that cancelled requests don't leak it.
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl<'a> QueueSlot<'a> {
+    fn take(queued: &'a AtomicUsize, queue_size: usize) -> Option<Self> {
+        queued
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |queued| {
+                (queued < queue_size).then_some(queued + 1)
+            })
+            .ok()
+            .map(|previous| {
+                metrics::record_queued(previous + 1);
+                Self(queued)
+            })
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        let previous = self.0.fetch_sub(1, Ordering::SeqCst);
+        metrics::record_queued(previous - 1);
+    }
+}
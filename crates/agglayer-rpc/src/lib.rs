@@ -1,32 +1,74 @@
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use agglayer_config::{epoch::BlockClockConfig, Config, Epoch};
-use agglayer_contracts::{AggchainContract, L1TransactionFetcher, RollupContract};
-use agglayer_primitives::Hashable;
+use agglayer_clock::ClockRef;
+use agglayer_config::{epoch::BlockClockConfig, Config, Epoch, HeightPolicy};
+use agglayer_contracts::{AggchainContract, L1RpcError, L1TransactionFetcher, RollupContract};
+use agglayer_primitives::{Hashable, B256};
 use agglayer_rate_limiting as rate_limiting;
 use agglayer_storage::{
-    columns::latest_settled_certificate_per_network::SettledCertificate,
+    columns::{
+        audit_log_per_certificate::{AuditEvent, Submitter},
+        callback_per_certificate::CertificateCallback,
+        event_log::{EventId, LoggedEvent},
+        latest_pending_certificate_per_network::PendingCertificate,
+        latest_proven_certificate_per_network::ProvenCertificate,
+        latest_settled_certificate_per_network::SettledCertificate,
+        settlement_attempts_per_certificate::SettlementAttempt,
+    },
+    storage::DBError,
     stores::{
         DebugReader, DebugWriter, EpochStoreReader, NetworkInfoReader, PendingCertificateReader,
         PendingCertificateWriter, StateReader, StateWriter,
     },
 };
+use agglayer_telemetry::intake as intake_metrics;
+use agglayer_tries::roots::LocalExitRoot;
 use agglayer_types::{
-    aggchain_data::MultisigCtx, aggchain_proof::AggchainData, Address, Certificate,
-    CertificateHeader, CertificateId, CertificateStatus, EpochConfiguration, Height, NetworkId,
-    NetworkInfo, NetworkStatus, NetworkType, SettledClaim, Signature, U256,
+    aggchain_data::MultisigCtx, aggchain_proof::AggchainData, Address, BuildInfo, Certificate,
+    CertificateHeader, CertificateHeaderDetails, CertificateId, CertificateIndex,
+    CertificateStatus, CertificateStatusError, Digest, EpochConfiguration, EpochNumber,
+    EpochStatus, EpochWindow, Height, LocalNetworkStateData, NetworkId, NetworkInfo, NetworkRoots,
+    NetworkStatus, NetworkSummary, NetworkType, Proof, QueuePosition, SettledClaim,
+    SettledExitProof, SettlementCostsReport, Signature, VersionInfo, U256,
 };
+use alloy::providers::Provider as _;
 use error::SignatureVerificationError;
-use tokio::sync::mpsc;
+use intake::{EstimationPool, IntakePool, IntakeValidation, SignatureVerificationCtx};
+pub use maintenance::{Maintenance, MaintenanceState, MaintenanceStatus};
+use pessimistic_proof::local_exit_tree::{data::LocalExitTreeData, LOCAL_EXIT_TREE_DEPTH};
+pub use quota::{ApiKeyUsageEntry, ApiKeyUsageReport};
+use screening::BridgeExitScreening;
+use tokio::sync::{mpsc, watch, OwnedSemaphorePermit};
 use tracing::{debug, error, info, instrument, warn};
+use url::Url;
 
-pub use self::error::{CertificateRetrievalError, CertificateSubmissionError, GetNetworkInfoError};
-use crate::error::{GetLatestCertificateError, GetLatestSettledClaimError, ProofRetrievalError};
+pub use self::error::{
+    CertificateCancellationError, CertificateRetrievalError, CertificateSubmissionError,
+    GetCertificateStatusesError, GetCurrentEpochStatusError, GetNetworkInfoError,
+    GetNetworkRootsError, GetNetworksError, GetSettledExitProofError, GetSettlementCostsError,
+    ProofRetrievalError, ProofSubmissionError, QuotaError,
+};
+use crate::error::{GetLatestCertificateError, GetLatestSettledClaimError, StorageError};
 
 pub mod error;
+mod intake;
+mod maintenance;
+mod quota;
+pub mod screening;
 #[cfg(test)]
 mod tests;
 
+/// Maximum number of certificates whose status can be requested at once.
+pub const MAX_CERTIFICATE_STATUSES_PER_REQUEST: usize = 100;
+
+/// Block time of L1, used to estimate the close time of the epochs of a block
+/// clock.
+const L1_BLOCK_TIME: Duration = Duration::from_secs(12);
+
 /// The RPC agglayer service implementation.
 pub struct AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore> {
     certificate_sender: mpsc::Sender<(NetworkId, Height, CertificateId)>,
@@ -36,6 +78,12 @@ pub struct AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsSt
     epochs_store: Arc<EpochsStore>,
     config: Arc<Config>,
     l1_rpc_provider: Arc<L1Rpc>,
+    intake: IntakePool,
+    estimations: EstimationPool,
+    maintenance: Arc<Maintenance>,
+    version_info: VersionInfo,
+    clock: Option<ClockRef>,
+    screening: Vec<Arc<dyn BridgeExitScreening>>,
 }
 
 impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
@@ -51,6 +99,9 @@ impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
         config: Arc<Config>,
         l1_rpc_provider: Arc<L1Rpc>,
     ) -> Self {
+        let intake = IntakePool::new(&config.rpc.intake);
+        let estimations = EstimationPool::new(&config.rpc.intake);
+
         Self {
             certificate_sender,
             pending_store,
@@ -59,14 +110,54 @@ impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
             epochs_store,
             config,
             l1_rpc_provider,
+            intake,
+            estimations,
+            maintenance: Arc::default(),
+            version_info: VersionInfo::new(BuildInfo::default(), Digest::default()),
+            clock: None,
+            screening: Vec::new(),
         }
     }
 
+    /// Share the given maintenance mode, which rejects the new certificates
+    /// while active.
+    pub fn with_maintenance(mut self, maintenance: Arc<Maintenance>) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    /// Report the given version of the running agglayer.
+    pub fn with_version_info(mut self, version_info: VersionInfo) -> Self {
+        self.version_info = version_info;
+        self
+    }
+
+    /// Estimate the epochs of the submitted certificates with the given clock.
+    pub fn with_clock(mut self, clock: ClockRef) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Screen the bridge exits of the submitted certificates with the given
+    /// hooks, on top of the ones already set.
+    pub fn with_screening(
+        mut self,
+        screening: impl IntoIterator<Item = Arc<dyn BridgeExitScreening>>,
+    ) -> Self {
+        self.screening.extend(screening);
+        self
+    }
+
     /// Get access to the configuration.
     pub fn config(&self) -> &Config {
         &self.config
     }
 
+    /// Get the version of the running agglayer.
+    pub fn get_version(&self) -> &VersionInfo {
+        &self.version_info
+    }
+
     pub fn get_epoch_configuration(&self) -> Option<EpochConfiguration> {
         info!("Received request to get epoch configuration");
 
@@ -93,6 +184,52 @@ impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore> Drop
     }
 }
 
+impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
+    AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
+where
+    PendingStore: PendingCertificateReader,
+    StateStore: StateReader,
+{
+    /// Get the id and height of the latest pending certificate of the
+    /// network, from the index maintained along with the certificate headers.
+    /// Falls back to the pending storage for the networks without any
+    /// certificate indexed yet.
+    fn get_latest_pending_certificate(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<Option<(CertificateId, Height)>, agglayer_storage::error::Error> {
+        match self
+            .state
+            .get_latest_pending_certificate_index(&network_id)?
+        {
+            Some(PendingCertificate(id, height)) => Ok(Some((id, height))),
+            None => self
+                .pending_store
+                .get_latest_pending_certificate_for_network(&network_id),
+        }
+    }
+
+    /// Get the id and height of the latest proven certificate of the network,
+    /// from the index maintained along with the certificate headers. Falls
+    /// back to the pending storage for the networks without any certificate
+    /// indexed yet.
+    fn get_latest_proven_certificate(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<Option<(CertificateId, Height)>, agglayer_storage::error::Error> {
+        match self
+            .state
+            .get_latest_proven_certificate_index(&network_id)?
+        {
+            Some(ProvenCertificate(id, _, height)) => Ok(Some((id, height))),
+            None => Ok(self
+                .pending_store
+                .get_latest_proven_certificate_per_network(&network_id)?
+                .map(|(_, height, id)| (id, height))),
+        }
+    }
+}
+
 impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
     AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
 where
@@ -117,14 +254,11 @@ where
             .map(|(_, SettledCertificate(id, height, _, _))| (id, height));
 
         let proven_certificate_id_and_height = self
-            .pending_store
-            .get_latest_proven_certificate_per_network(&network_id)
-            .inspect_err(|e| error!("Failed to get latest proven certificate: {e}"))?
-            .map(|(_, height, id)| (id, height));
+            .get_latest_proven_certificate(network_id)
+            .inspect_err(|e| error!("Failed to get latest proven certificate: {e}"))?;
 
         let pending_certificate_id_and_height = self
-            .pending_store
-            .get_latest_pending_certificate_for_network(&network_id)
+            .get_latest_pending_certificate(network_id)
             .inspect_err(|e| error!("Failed to get latest pending certificate: {e}"))?;
 
         let certificate_id = [
@@ -153,10 +287,8 @@ where
         debug!("Received request to get the latest available certificate for rollup {network_id}");
 
         let proven_certificate_id_and_height = self
-            .pending_store
-            .get_latest_proven_certificate_per_network(&network_id)
-            .inspect_err(|e| error!("Failed to get latest proven certificate: {e}"))?
-            .map(|(_, height, id)| (id, height));
+            .get_latest_proven_certificate(network_id)
+            .inspect_err(|e| error!("Failed to get latest proven certificate: {e}"))?;
 
         let settled_certificate_id_and_height = self
             .state
@@ -262,8 +394,7 @@ where
         network_id: NetworkId,
     ) -> Result<Option<CertificateHeader>, CertificateRetrievalError> {
         let id = match self
-            .pending_store
-            .get_latest_pending_certificate_for_network(&network_id)
+            .get_latest_pending_certificate(network_id)
             .inspect_err(|e| error!("Failed to get latest pending certificate id: {e}"))?
         {
             Some((id, _height)) => id,
@@ -343,6 +474,53 @@ where
         }
     }
 
+    /// Proof of the certificate in the given encoding, served from its
+    /// encoding in the store without decoding it.
+    pub fn get_encoded_proof(
+        &self,
+        certificate_id: CertificateId,
+        encoding: ProofEncoding,
+    ) -> Result<Option<Vec<u8>>, ProofRetrievalError> {
+        let stored = match self
+            .pending_store
+            .get_encoded_proof(certificate_id)
+            .inspect_err(|error| {
+                error!(
+                    ?error,
+                    "Failed to get proof for certificate {certificate_id} from pending store",
+                );
+            })? {
+            Some(stored) => Some(stored),
+            None => match self.fetch_certificate_header(certificate_id) {
+                Ok(CertificateHeader {
+                    epoch_number: Some(epoch_number),
+                    certificate_index: Some(certificate_index),
+                    ..
+                }) => self
+                    .epochs_store
+                    .get_encoded_proof(epoch_number, certificate_index)
+                    .map_err(|error| {
+                        error!(
+                            ?error,
+                            "Failed to get proof for certificate {certificate_id} from epoch store",
+                        );
+                        ProofRetrievalError::NotFound { certificate_id }
+                    })?,
+                _ => None,
+            },
+        };
+
+        // The proofs are stored as their compressed bincode encoding, or as the
+        // plain one for those stored before the compression.
+        stored
+            .map(|stored| match encoding {
+                ProofEncoding::Bincode => agglayer_storage::columns::decompressed(stored),
+                ProofEncoding::Zstd => agglayer_storage::columns::compressed(stored),
+            })
+            .transpose()
+            .map_err(|error| ProofRetrievalError::Storage(DBError::from(error).into()))
+    }
+
     pub fn get_latest_settled_claim(
         &self,
         network_id: NetworkId,
@@ -479,8 +657,7 @@ where
                     // Extract settled_pp_root from the settled certificate's proof public values
                     network_info.settled_pp_root = match self.get_proof(cert.certificate_id) {
                         Ok(Some(agglayer_types::Proof::SP1(sp1_proof))) => {
-                            match pessimistic_proof::PessimisticProofOutput::bincode_codec()
-                                .deserialize::<pessimistic_proof::PessimisticProofOutput>(
+                            match pessimistic_proof::PessimisticProofOutput::from_public_values(
                                 sp1_proof.public_values.as_slice(),
                             ) {
                                 Ok(output) => Some(output.new_pessimistic_root),
@@ -647,6 +824,404 @@ where
 
         Ok(network_info)
     }
+
+    /// Report the L1 costs of the settlement of the certificates of the
+    /// network over the inclusive range of epochs.
+    pub fn get_settlement_costs(
+        &self,
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    ) -> Result<SettlementCostsReport, GetSettlementCostsError> {
+        debug!(
+            "Received request to get the settlement costs for rollup {network_id} from epoch \
+             {from_epoch} to {to_epoch}"
+        );
+
+        if from_epoch > to_epoch {
+            return Err(GetSettlementCostsError::InvalidEpochRange {
+                from_epoch,
+                to_epoch,
+            });
+        }
+
+        let epochs = self
+            .state
+            .get_settlement_costs(network_id, from_epoch, to_epoch)
+            .inspect_err(|error| error!(?error, "Failed to get the settlement costs"))?;
+
+        Ok(SettlementCostsReport::new(
+            network_id, from_epoch, to_epoch, epochs,
+        ))
+    }
+
+    /// Get the statuses of the certificates in a single storage round trip,
+    /// in the order of the given ids. Unknown certificates have no status.
+    pub fn get_certificate_statuses(
+        &self,
+        certificate_ids: &[CertificateId],
+    ) -> Result<Vec<Option<CertificateStatus>>, GetCertificateStatusesError> {
+        debug!(
+            "Received request to get the statuses of {} certificates",
+            certificate_ids.len()
+        );
+
+        if certificate_ids.len() > MAX_CERTIFICATE_STATUSES_PER_REQUEST {
+            return Err(GetCertificateStatusesError::TooManyCertificates {
+                requested: certificate_ids.len(),
+                max: MAX_CERTIFICATE_STATUSES_PER_REQUEST,
+            });
+        }
+
+        let headers = self
+            .state
+            .multi_get_certificate_header(certificate_ids)
+            .inspect_err(|error| error!(?error, "Failed to get the certificate headers"))?;
+
+        Ok(headers
+            .into_iter()
+            .map(|header| header.map(|header| header.status))
+            .collect())
+    }
+
+    /// List the networks known by the agglayer, with their latest heights
+    /// and the time of their latest activity.
+    pub fn get_networks(&self) -> Result<Vec<NetworkSummary>, GetNetworksError> {
+        debug!("Received request to list the known networks");
+
+        let mut networks = self
+            .state
+            .get_known_networks()
+            .inspect_err(|error| error!(?error, "Failed to list the known networks"))?;
+        networks.extend(
+            self.pending_store
+                .get_pending_networks()
+                .inspect_err(|error| error!(?error, "Failed to list the pending networks"))?,
+        );
+        networks.sort_unstable();
+        networks.dedup();
+
+        networks
+            .into_iter()
+            .map(|network_id| {
+                self.get_network_summary(network_id)
+                    .inspect_err(|error| error!(?error, "Failed to summarize network {network_id}"))
+            })
+            .collect()
+    }
+
+    /// Status of the current epoch, with its bounds, its expected close time
+    /// and the number of networks which can still settle a certificate in
+    /// it.
+    pub fn get_current_epoch_status(&self) -> Result<EpochStatus, GetCurrentEpochStatusError> {
+        debug!("Received request to get the current epoch status");
+
+        let Some(clock) = &self.clock else {
+            return Err(GetCurrentEpochStatusError::NoClock);
+        };
+
+        let epoch_number = clock.current_epoch();
+        let epoch_duration = clock.epoch_duration().get();
+        let current_block = clock.current_block_height();
+
+        // The block clock counts the blocks from its genesis block, the time
+        // clock counts the seconds since its start.
+        let (genesis_block, block_time) = match &self.config.epoch {
+            Epoch::BlockClock(BlockClockConfig { genesis_block, .. }) => {
+                (*genesis_block, Some(L1_BLOCK_TIME))
+            }
+            Epoch::TimeClock(_) => (0, Some(Duration::from_secs(1))),
+            Epoch::ManualClock(_) => (0, None),
+        };
+
+        let start_block = epoch_number.as_u64().saturating_mul(epoch_duration);
+        let end_block = start_block.saturating_add(epoch_duration);
+        let remaining_blocks = end_block.saturating_sub(current_block);
+        let expected_close_time = block_time.and_then(|block_time| {
+            let remaining = block_time.saturating_mul(remaining_blocks.try_into().ok()?);
+            let close_time = SystemTime::now().checked_add(remaining)?;
+            Some(close_time.duration_since(UNIX_EPOCH).ok()?.as_secs())
+        });
+
+        let settled_networks = self
+            .state
+            .get_current_settled_height()
+            .inspect_err(|error| error!(?error, "Failed to get the settled heights"))?
+            .into_iter()
+            .filter(|(_, SettledCertificate(_, _, epoch, _))| *epoch == epoch_number)
+            .count() as u64;
+
+        let mut networks = self
+            .state
+            .get_known_networks()
+            .inspect_err(|error| error!(?error, "Failed to list the known networks"))?;
+        networks.extend(
+            self.pending_store
+                .get_pending_networks()
+                .inspect_err(|error| error!(?error, "Failed to list the pending networks"))?,
+        );
+        networks.sort_unstable();
+        networks.dedup();
+
+        Ok(EpochStatus {
+            epoch_number,
+            start_block: start_block.saturating_add(genesis_block),
+            end_block: end_block.saturating_add(genesis_block),
+            remaining_blocks,
+            expected_close_time,
+            settled_networks,
+            remaining_capacity: (networks.len() as u64).saturating_sub(settled_networks),
+        })
+    }
+
+    fn get_network_summary(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<NetworkSummary, GetNetworksError> {
+        let network_info = self.state.get_network_info(network_id)?;
+
+        let settled = self
+            .state
+            .get_latest_settled_certificate_per_network(&network_id)?
+            .map(|(_, SettledCertificate(certificate_id, height, ..))| (certificate_id, height));
+        let pending = self.get_latest_pending_certificate(network_id)?;
+
+        // The latest certificate is the pending one, if any, as it can only
+        // be above the settled one.
+        let latest_activity = match pending.or(settled) {
+            Some((certificate_id, _)) => self
+                .state
+                .get_audit_log(&certificate_id)?
+                .last()
+                .and_then(|record| u64::try_from(record.timestamp.timestamp()).ok()),
+            None => None,
+        };
+
+        Ok(NetworkSummary {
+            network_id,
+            network_type: network_info.network_type,
+            settled_height: settled
+                .map(|(_, height)| height)
+                .or(network_info.settled_height),
+            latest_pending_height: pending
+                .map(|(_, height)| height)
+                .or(network_info.latest_pending_height),
+            latest_activity,
+        })
+    }
+
+    /// Get the roots of the local network state of the network at the given
+    /// settled height, or at the latest settled height.
+    pub fn get_network_roots(
+        &self,
+        network_id: NetworkId,
+        height: Option<Height>,
+    ) -> Result<NetworkRoots, GetNetworkRootsError> {
+        debug!("Received request to get the roots of network {network_id} at height {height:?}");
+
+        let Some((_, SettledCertificate(_, settled_height, ..))) = self
+            .state
+            .get_latest_settled_certificate_per_network(&network_id)?
+        else {
+            return Err(GetNetworkRootsError::NoSettledCertificate { network_id });
+        };
+
+        let height = height.unwrap_or(settled_height);
+        if height > settled_height {
+            return Err(GetNetworkRootsError::HeightNotSettled {
+                network_id,
+                height,
+                settled_height,
+            });
+        }
+
+        let roots = match self.state.get_settled_roots(network_id, height)? {
+            Some(roots) => roots,
+            // The roots of the certificates settled before they were recorded
+            // are only known for the latest one, from the stored state.
+            None if height == settled_height => self
+                .state
+                .read_local_network_state(network_id)?
+                .map(|state| state.get_roots())
+                .ok_or(GetNetworkRootsError::RootsNotRecorded { network_id, height })?,
+            None => return Err(GetNetworkRootsError::RootsNotRecorded { network_id, height }),
+        };
+
+        Ok(NetworkRoots {
+            network_id,
+            height,
+            exit_root: roots.exit_root,
+            ler_leaf_count: roots.ler_leaf_count,
+            balance_root: roots.balance_root,
+            nullifier_root: roots.nullifier_root,
+        })
+    }
+
+    /// Generate the Merkle proof of a leaf of the local exit tree of the
+    /// network against its latest settled local exit root.
+    pub fn get_settled_exit_proof(
+        &self,
+        network_id: NetworkId,
+        leaf_index: u32,
+    ) -> Result<SettledExitProof, GetSettledExitProofError> {
+        debug!(
+            "Received request to get the proof of the leaf {leaf_index} of the settled local exit \
+             tree of network {network_id}"
+        );
+
+        let Some((_, SettledCertificate(certificate_id, settled_height, ..))) = self
+            .state
+            .get_latest_settled_certificate_per_network(&network_id)?
+        else {
+            return Err(GetSettledExitProofError::NoSettledCertificate { network_id });
+        };
+        let settled_header = self
+            .state
+            .get_certificate_header(&certificate_id)?
+            .ok_or(StorageError::NoCertificateHeader)?;
+
+        let leaves = self
+            .state
+            .read_local_exit_tree_leaves(network_id)
+            .inspect_err(|error| error!(?error, "Failed to read the local exit tree leaves"))?;
+        let exit_tree = LocalExitTreeData::<LOCAL_EXIT_TREE_DEPTH>::from_leaves(leaves.into_iter())
+            .map_err(|error| {
+                error!(?error, "Failed to rebuild the local exit tree");
+                GetSettledExitProofError::InconsistentExitTree { network_id }
+            })?;
+
+        let local_exit_root = LocalExitRoot::new(exit_tree.get_root());
+        if local_exit_root != settled_header.new_local_exit_root {
+            warn!(
+                %certificate_id,
+                "Stored local exit tree doesn't match the settled local exit root"
+            );
+            return Err(GetSettledExitProofError::InconsistentExitTree { network_id });
+        }
+
+        let leaf_count = exit_tree.layers[0].len() as u32;
+        let Some(leaf) = exit_tree.layers[0].get(leaf_index as usize).copied() else {
+            return Err(GetSettledExitProofError::LeafIndexOutOfBounds {
+                network_id,
+                leaf_index,
+                leaf_count,
+            });
+        };
+        let proof = exit_tree.get_proof(leaf_index).map_err(|error| {
+            error!(?error, "Failed to generate the local exit tree proof");
+            GetSettledExitProofError::InconsistentExitTree { network_id }
+        })?;
+
+        Ok(SettledExitProof {
+            network_id,
+            settled_height,
+            local_exit_root,
+            leaf_count,
+            leaf_index,
+            leaf,
+            proof,
+        })
+    }
+
+    /// Get the local state of the network as of its latest settled
+    /// certificate, the one the next certificate applies on top of.
+    pub fn get_local_network_state(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<LocalNetworkStateData, agglayer_storage::error::Error> {
+        debug!("Received request to get the local state of network {network_id}");
+
+        Ok(self
+            .state
+            .read_local_network_state(network_id)
+            .inspect_err(|error| error!(?error, "Failed to read the local network state"))?
+            .unwrap_or_default())
+    }
+
+    /// Get at most `limit` events of the event log, starting right after the
+    /// given event or from the first one.
+    pub fn get_events(
+        &self,
+        after: Option<EventId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, LoggedEvent)>, agglayer_storage::error::Error> {
+        self.state
+            .get_events(after, limit)
+            .inspect_err(|error| error!(?error, "Failed to read the event log"))
+    }
+
+    /// Get the identifier of the last event of the event log.
+    pub fn get_latest_event_id(&self) -> Result<Option<EventId>, agglayer_storage::error::Error> {
+        self.state
+            .get_latest_event_id()
+            .inspect_err(|error| error!(?error, "Failed to read the event log"))
+    }
+
+    /// Watch the last event appended to the event log.
+    pub fn subscribe_events(&self) -> watch::Receiver<Option<EventId>> {
+        self.state.subscribe_events()
+    }
+
+    /// Get the headers of the certificates of the network, in order, starting
+    /// at the given height and stopping at the first unknown one.
+    pub fn get_certificate_headers(
+        &self,
+        network_id: NetworkId,
+        from_height: Height,
+        limit: usize,
+    ) -> Result<Vec<CertificateHeader>, agglayer_storage::error::Error> {
+        let mut headers = Vec::new();
+        let mut height = from_height;
+        while headers.len() < limit {
+            let Some(header) = self
+                .state
+                .get_certificate_header_by_cursor(network_id, height)
+                .inspect_err(|error| error!(?error, "Failed to get the certificate headers"))?
+            else {
+                break;
+            };
+            headers.push(header);
+            height = height.next();
+        }
+
+        Ok(headers)
+    }
+
+    /// Get the headers of the certificates of the epoch, in order, starting at
+    /// the given index.
+    pub fn get_epoch_certificate_headers(
+        &self,
+        epoch_number: EpochNumber,
+        from_index: CertificateIndex,
+        limit: usize,
+    ) -> Result<Vec<CertificateHeader>, agglayer_storage::error::Error> {
+        let certificate_ids = self
+            .epochs_store
+            .get_certificates(epoch_number, from_index, limit)
+            .inspect_err(|error| error!(?error, "Failed to get the certificates of the epoch"))?
+            .iter()
+            .map(Certificate::hash)
+            .collect::<Vec<_>>();
+
+        Ok(self
+            .state
+            .multi_get_certificate_header(&certificate_ids)
+            .inspect_err(|error| error!(?error, "Failed to get the certificate headers"))?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Get the settlement transactions submitted for the certificate, in
+    /// order.
+    pub fn get_settlement_attempts(
+        &self,
+        certificate_id: CertificateId,
+    ) -> Result<Vec<SettlementAttempt>, agglayer_storage::error::Error> {
+        self.state
+            .get_settlement_attempts(&certificate_id)
+            .inspect_err(|error| error!(?error, "Failed to get the settlement attempts"))
+    }
 }
 
 impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
@@ -674,6 +1249,75 @@ where
         Ok(certificate_id)
     }
 
+    /// Status of the given certificate if it is already known and not in
+    /// error, in which case its submission is a no-op.
+    fn get_already_known_certificate_status(
+        &self,
+        certificate_id: CertificateId,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<Option<CertificateStatus>, agglayer_storage::error::Error> {
+        if self.get_known_certificate_id_at_height(network_id, height)? != Some(certificate_id) {
+            return Ok(None);
+        }
+
+        Ok(self
+            .state
+            .get_certificate_header(&certificate_id)?
+            .map(|header| header.status)
+            .filter(|status| !matches!(status, CertificateStatus::InError { .. })))
+    }
+
+    /// Check that the height of the certificate follows the ones of the known
+    /// certificates of the network, as per the configured policy.
+    fn validate_certificate_height(
+        &self,
+        certificate: &Certificate,
+    ) -> Result<(), CertificateSubmissionError> {
+        let network_id = certificate.network_id;
+
+        let next_settled_height = self
+            .state
+            .get_latest_settled_certificate_per_network(&network_id)?
+            .map(|(_, SettledCertificate(_, height, _, _))| height.next())
+            .unwrap_or(Height::ZERO);
+
+        let next_height = match self.config.rpc.intake.height_policy {
+            HeightPolicy::NextSettled => next_settled_height,
+            HeightPolicy::Contiguous => {
+                let latest_proven_height = self
+                    .get_latest_proven_certificate(network_id)?
+                    .map(|(_, height)| height);
+                let latest_pending_height = self
+                    .get_latest_pending_certificate(network_id)?
+                    .map(|(_, height)| height);
+
+                [latest_proven_height, latest_pending_height]
+                    .into_iter()
+                    .flatten()
+                    .map(|height| height.next())
+                    .fold(next_settled_height, std::cmp::max)
+            }
+        };
+
+        // Any height from the one following the latest settled certificate
+        // up to the next one is either a replacement or a new certificate.
+        if certificate.height < next_settled_height || certificate.height > next_height {
+            warn!(
+                height = certificate.height.as_u64(),
+                expected_height = next_height.as_u64(),
+                "Rejecting certificate for network {network_id} at an unexpected height"
+            );
+            return Err(CertificateSubmissionError::UnexpectedHeight {
+                network_id,
+                height: certificate.height,
+                expected_height: next_height,
+            });
+        }
+
+        Ok(())
+    }
+
     #[instrument(skip(self, certificate), level = "info")]
     async fn validate_pre_existing_certificate(
         &self,
@@ -788,13 +1432,13 @@ where
         Ok(())
     }
 
-    /// Verify that the signer of the given [`Certificate`] is the trusted
-    /// sequencer for the rollup id it specified.
-    #[instrument(skip(self), level = "debug")]
-    pub(crate) async fn verify_cert_signature(
+    /// Fetch from L1 the context of the verification of the signature of the
+    /// given [`Certificate`].
+    #[instrument(skip_all, level = "debug")]
+    pub(crate) async fn fetch_signature_verification_ctx(
         &self,
         cert: &Certificate,
-    ) -> Result<(), SignatureVerificationError> {
+    ) -> Result<SignatureVerificationCtx, CertificateSubmissionError> {
         // Verify any signature related data, fetch L1 context when needed.
         let fetch_sequencer_address = || async {
             self.l1_rpc_provider
@@ -803,10 +1447,17 @@ where
                     self.config.proof_signers.clone(),
                 )
                 .await
-                .map_err(|_| {
-                    SignatureVerificationError::UnableToRetrieveTrustedSequencerAddress(
-                        cert.network_id,
-                    )
+                .map_err(|error| match error {
+                    L1RpcError::InvalidRollupContract(_) => {
+                        CertificateSubmissionError::UnknownNetwork {
+                            network_id: cert.network_id,
+                        }
+                    }
+                    _ => CertificateSubmissionError::SignatureError(
+                        SignatureVerificationError::UnableToRetrieveTrustedSequencerAddress(
+                            cert.network_id,
+                        ),
+                    ),
                 })
         };
 
@@ -816,85 +1467,230 @@ where
                 .l1_rpc_provider
                 .get_rollup_contract_address(cert.network_id.into())
                 .await
-                .map_err(|source| {
-                    SignatureVerificationError::UnableToRetrieveRollupContractAddress {
-                        source,
-                        network_id: cert.network_id,
+                .map_err(|source| match source {
+                    L1RpcError::InvalidRollupContract(_) => {
+                        CertificateSubmissionError::UnknownNetwork {
+                            network_id: cert.network_id,
+                        }
                     }
+                    source => CertificateSubmissionError::SignatureError(
+                        SignatureVerificationError::UnableToRetrieveRollupContractAddress {
+                            source,
+                            network_id: cert.network_id,
+                        },
+                    ),
                 })?;
 
             let (signers, threshold) = self
                 .l1_rpc_provider
                 .get_multisig_context(rollup_address)
                 .await
-                .map_err(
-                    |source| SignatureVerificationError::UnableToRetrieveMultisigContext {
-                        source,
-                        network_id: cert.network_id,
-                    },
-                )?;
+                .map_err(|source| {
+                    CertificateSubmissionError::SignatureError(
+                        SignatureVerificationError::UnableToRetrieveMultisigContext {
+                            source,
+                            network_id: cert.network_id,
+                        },
+                    )
+                })?;
 
-            Ok::<MultisigCtx, SignatureVerificationError>(MultisigCtx {
+            Ok::<MultisigCtx, CertificateSubmissionError>(MultisigCtx {
                 signers,
                 threshold,
                 prehash: cert.signature_commitment_values().multisig_commitment(),
             })
         };
 
-        match &cert.aggchain_data {
-            AggchainData::ECDSA { signature } => {
-                cert.verify_legacy_ecdsa(fetch_sequencer_address().await?, signature)
+        Ok(match &cert.aggchain_data {
+            AggchainData::ECDSA { .. } | AggchainData::Generic { .. } => {
+                SignatureVerificationCtx::TrustedSequencer(fetch_sequencer_address().await?)
             }
-            AggchainData::Generic { signature, .. } => {
-                cert.verify_aggchain_proof_signature(fetch_sequencer_address().await?, signature)
+            AggchainData::MultisigOnly { .. } | AggchainData::MultisigAndAggchainProof { .. } => {
+                SignatureVerificationCtx::Multisig(fetch_multisig_context().await?)
             }
-            AggchainData::MultisigOnly { multisig } => {
-                cert.verify_multisig(multisig.into(), fetch_multisig_context().await?)
+        })
+    }
+
+    /// Get the certificate header along with the L1 block including its
+    /// settlement transaction and its number of confirmations, raising an
+    /// error if not found.
+    ///
+    /// The L1 details are left out when the L1 cannot be reached, the header
+    /// being served from the storage regardless.
+    pub async fn fetch_certificate_header_details(
+        &self,
+        certificate_id: CertificateId,
+    ) -> Result<CertificateHeaderDetails, CertificateRetrievalError> {
+        let header = self.fetch_certificate_header(certificate_id)?;
+        let Some(settlement_tx_hash) = header.settlement_tx_hash else {
+            return Ok(header.into());
+        };
+
+        let l1_block_number = match self
+            .l1_rpc_provider
+            .fetch_transaction_receipt(settlement_tx_hash.into())
+            .await
+        {
+            Ok(receipt) => receipt.and_then(|receipt| receipt.block_number),
+            Err(error) => {
+                warn!(
+                    %certificate_id,
+                    %settlement_tx_hash,
+                    "Failed to fetch the settlement transaction receipt: {error}"
+                );
+                None
             }
-            AggchainData::MultisigAndAggchainProof { multisig, .. } => {
-                cert.verify_multisig(multisig.into(), fetch_multisig_context().await?)
+        };
+
+        let confirmations = match l1_block_number {
+            Some(l1_block_number) => {
+                match self.l1_rpc_provider.get_provider().get_block_number().await {
+                    Ok(current_block) => Some(
+                        current_block
+                            .saturating_sub(l1_block_number)
+                            .saturating_add(1),
+                    ),
+                    Err(error) => {
+                        warn!(%certificate_id, "Failed to fetch the L1 block number: {error}");
+                        None
+                    }
+                }
             }
-        }
-        .map_err(SignatureVerificationError::from_signer_error)
+            None => None,
+        };
+
+        Ok(CertificateHeaderDetails {
+            header,
+            l1_block_number,
+            confirmations,
+        })
     }
 
-    /// Verify the extra [`Certificate`] signature.
-    #[instrument(skip_all, level = "debug")]
-    pub(crate) fn verify_extra_cert_signature(
+    /// Submit the proof of a pending certificate generated outside of the
+    /// agglayer, on behalf of its network. The signature is the one of the
+    /// trusted sequencer of the network over the proof submission commitment
+    /// of the certificate id and the proof.
+    ///
+    /// The next certification of the certificate uses this proof instead of
+    /// requesting one from the prover, once checked against the expected
+    /// public values and verified against the vkey of the pessimistic proof
+    /// program. A proof failing these checks is discarded and the certificate
+    /// proven locally.
+    #[instrument(skip(self, proof, signature), level = "info")]
+    pub async fn submit_proof(
         &self,
-        certificate: &Certificate,
-        extra_signer: Option<&Address>,
-        extra_signature: Option<Signature>,
-    ) -> Result<(), SignatureVerificationError> {
-        match (extra_signer, extra_signature) {
-            // Extra signature expected and provided
-            (Some(&expected_extra_signer), Some(extra_signature)) => certificate
-                .verify_extra_signature(expected_extra_signer, extra_signature)
-                .map_err(SignatureVerificationError::from_signer_error)?,
-            // Extra signature is expected but missing
-            (Some(&expected_signer), None) => {
-                return Err(SignatureVerificationError::MissingExtraSignature {
-                    network_id: certificate.network_id,
-                    expected_signer,
+        certificate_id: CertificateId,
+        proof: Proof,
+        signature: Signature,
+    ) -> Result<(), ProofSubmissionError> {
+        info!(%certificate_id, "Received proof for certificate {certificate_id}");
+
+        if self.config.read_only.enabled {
+            return Err(ProofSubmissionError::ReadOnly);
+        }
+
+        let header = self
+            .state
+            .get_certificate_header(&certificate_id)
+            .inspect_err(|err| error!("Failed to get certificate header: {err}"))?
+            .ok_or(ProofSubmissionError::CertificateNotFound { certificate_id })?;
+
+        match header.status {
+            CertificateStatus::Pending | CertificateStatus::InError { .. } => {}
+            status @ (CertificateStatus::Proven
+            | CertificateStatus::Candidate
+            | CertificateStatus::Settled) => {
+                return Err(ProofSubmissionError::CertificateNotPending {
+                    certificate_id,
+                    status,
                 });
             }
-            // Extra signature provided but not required
-            (None, Some(_)) => {
-                warn!("Unexpected extra signature provided");
-            }
-            // No extra signature provided nor required
-            (None, None) => {}
-        };
+        }
+
+        let network_id = header.network_id;
+        let expected_signer = self
+            .l1_rpc_provider
+            .get_trusted_sequencer_address(network_id.to_u32(), self.config.proof_signers.clone())
+            .await
+            .map_err(|source| ProofSubmissionError::UnableToRetrieveSigner {
+                network_id,
+                source,
+            })?;
+        let commitment = certificate_id
+            .proof_submission_commitment(&proof)
+            .map_err(ProofSubmissionError::ProofEncoding)?;
+        if signature
+            .recover_address_from_prehash(&B256::new(commitment.0))
+            .ok()
+            != Some(expected_signer)
+        {
+            return Err(ProofSubmissionError::InvalidSignature { expected_signer });
+        }
+
+        self.pending_store
+            .insert_submitted_proof(&certificate_id, &proof)
+            .inspect_err(|e| error!("Failed to insert the submitted proof: {e}"))?;
 
         Ok(())
     }
 
-    #[instrument(skip(self, certificate), fields(hash, rollup_id = certificate.network_id.to_u32()), level = "info")]
+    /// Check the limits and the signature of a candidate certificate before
+    /// estimating its proving cost, on a slot of the bounded estimation pool.
+    ///
+    /// The returned permit holds the slot and is to be kept until the
+    /// estimation completes. The extra signature is not required, the
+    /// certificate being only estimated.
+    #[instrument(skip_all, fields(rollup_id = certificate.network_id.to_u32()), level = "debug")]
+    pub async fn check_candidate_certificate(
+        &self,
+        certificate: Certificate,
+    ) -> Result<(Certificate, OwnedSemaphorePermit), CertificateSubmissionError> {
+        let permit = self.estimations.try_acquire()?;
+
+        let signature_ctx = self
+            .fetch_signature_verification_ctx(&certificate)
+            .await
+            .inspect_err(|error| {
+                error!(
+                    ?error,
+                    "Failed to fetch the context to verify the certificate signature"
+                );
+            })?;
+
+        let validation = IntakeValidation {
+            limits: self
+                .config
+                .rpc
+                .intake
+                .limits(certificate.network_id.to_u32()),
+            extra_signer: None,
+            extra_signature: None,
+            signature_ctx,
+        };
+        let certificate = self
+            .intake
+            .run(certificate.network_id, move || {
+                validation.validate(&certificate)?;
+                Ok(certificate)
+            })
+            .await?;
+
+        Ok((certificate, permit))
+    }
+
+    /// Submit the certificate to the orchestrator.
+    ///
+    /// When a callback URL is given, the final status of the certificate is
+    /// posted to it once reached. The submitter is recorded in the audit log
+    /// of the certificate.
+    #[instrument(skip(self, certificate, callback_url), fields(hash, rollup_id = certificate.network_id.to_u32()), level = "info")]
     pub async fn send_certificate(
         &self,
         certificate: Certificate,
         extra_signature: Option<Signature>,
-    ) -> Result<CertificateId, CertificateSubmissionError> {
+        callback_url: Option<Url>,
+        submitter: Submitter,
+    ) -> Result<CertificateSubmission, CertificateSubmissionError> {
         let hash = certificate.hash();
         let hash_string = hash.to_string();
         tracing::Span::current().record("hash", &hash_string);
@@ -903,35 +1699,106 @@ where
             %hash,
             "Received certificate {hash} for rollup {} at height {}", certificate.network_id.to_u32(), certificate.height
         );
-        self.validate_pre_existing_certificate(&certificate).await?;
 
-        // Verify the extra certificate signature
-        self.verify_extra_cert_signature(
-            &certificate,
-            self.config()
-                .extra_certificate_signer
-                .get(&certificate.network_id.to_u32()),
-            extra_signature,
-        )
-        .map_err(|error| {
-            error!(
-                ?error,
-                "Failed to verify the extra signature for the certificate"
-            );
-            CertificateSubmissionError::SignatureError(error)
-        })?;
+        // The callback is not part of the signed certificate, so that a
+        // resubmission by anyone else cannot redirect its notification.
+        if let Some(url) = &callback_url {
+            if self
+                .state
+                .get_certificate_callback(&hash)?
+                .is_some_and(|callback| callback.url != url.as_str())
+            {
+                warn!(%hash, "Rejecting certificate {hash}, registered with another callback URL");
+                return Err(CertificateSubmissionError::ConflictingCallbackUrl {
+                    certificate_id: hash,
+                });
+            }
+        }
+
+        // Resubmitting a certificate which is already known and not in error
+        // is a no-op, so that the submitters can safely retry.
+        if let Some(status) = self.get_already_known_certificate_status(
+            hash,
+            certificate.network_id,
+            certificate.height,
+        )? {
+            info!(%hash, %status, "Certificate {hash} is already known");
+            return Ok(CertificateSubmission {
+                certificate_id: hash,
+                status,
+                already_known: true,
+                position: self.queue_position(certificate.network_id, certificate.height)?,
+            });
+        }
 
-        // Verify the certificate signature
-        self.verify_cert_signature(&certificate)
+        if self.config.read_only.enabled {
+            warn!(%hash, "Rejecting certificate {hash}, the agglayer is read-only");
+            return Err(CertificateSubmissionError::ReadOnly);
+        }
+
+        if self.maintenance.is_active() {
+            warn!(%hash, "Rejecting certificate {hash}, the agglayer is in maintenance");
+            return Err(CertificateSubmissionError::Maintenance);
+        }
+
+        if let Some(url) = &callback_url {
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(CertificateSubmissionError::InvalidCallbackUrl {
+                    url: url.to_string(),
+                    reason: "only http and https are supported",
+                });
+            }
+            if !self.config.outbound.callback.is_allowed_url(url) {
+                return Err(CertificateSubmissionError::InvalidCallbackUrl {
+                    url: url.to_string(),
+                    reason: "the host is not allowed",
+                });
+            }
+        }
+
+        self.validate_certificate_height(&certificate)?;
+        self.validate_pre_existing_certificate(&certificate).await?;
+
+        // Fetch the L1 context of the signature here, the checks of the
+        // certificate are then run on an intake worker.
+        let signature_ctx = self
+            .fetch_signature_verification_ctx(&certificate)
             .await
-            .map_err(|error| {
+            .inspect_err(|error| {
                 error!(
                     ?error,
-                    "Failed to verify the signature within the certificate"
+                    "Failed to fetch the context to verify the certificate signature"
                 );
-                CertificateSubmissionError::SignatureError(error)
             })?;
 
+        let validation = IntakeValidation {
+            limits: self
+                .config
+                .rpc
+                .intake
+                .limits(certificate.network_id.to_u32()),
+            extra_signer: self
+                .config
+                .extra_certificate_signer
+                .get(&certificate.network_id.to_u32())
+                .copied(),
+            extra_signature,
+            signature_ctx,
+        };
+        let certificate = self
+            .intake
+            .run(certificate.network_id, move || {
+                validation.validate(&certificate)?;
+                Ok(certificate)
+            })
+            .await?;
+
+        self.screen_bridge_exits(&certificate).await?;
+
+        self.state
+            .record_audit_event(&hash, AuditEvent::Submitted { submitter })
+            .inspect_err(|e| error!("Failed to record the certificate submission: {e}"))?;
+
         // TODO: Batch the different queries.
         // Insert the certificate into the pending store.
         self.pending_store
@@ -947,6 +1814,14 @@ where
             .add_certificate(&certificate)
             .inspect_err(|e| error!("Failed to insert certificate into debug store: {e}"))?;
 
+        if let Some(url) = callback_url {
+            self.state
+                .set_certificate_callback(&hash, &CertificateCallback { url: url.into() })
+                .inspect_err(|e| error!("Failed to register the certificate callback: {e}"))?;
+        }
+
+        let position = self.queue_position(certificate.network_id, certificate.height)?;
+
         self.certificate_sender
             .send((
                 certificate.network_id,
@@ -959,8 +1834,190 @@ where
                 CertificateSubmissionError::OrchestratorNotResponsive
             })?;
 
-        Ok(hash)
+        Ok(CertificateSubmission {
+            certificate_id: hash,
+            status: CertificateStatus::Pending,
+            already_known: false,
+            position,
+        })
+    }
+
+    /// Withdraw a certificate which is still pending, on behalf of its
+    /// network. The signature is the one of the trusted sequencer of the
+    /// network over the cancellation commitment of the certificate id.
+    ///
+    /// The certificate is removed from the pending storage and put in error
+    /// as cancelled, which lets the network submit its height again.
+    pub async fn cancel_certificate(
+        &self,
+        certificate_id: CertificateId,
+        signature: Signature,
+    ) -> Result<(), CertificateCancellationError> {
+        info!(%certificate_id, "Received request to cancel certificate {certificate_id}");
+
+        if self.config.read_only.enabled {
+            return Err(CertificateCancellationError::ReadOnly);
+        }
+
+        let header = self
+            .state
+            .get_certificate_header(&certificate_id)
+            .inspect_err(|error| error!(?error, "Failed to get the certificate header"))?
+            .ok_or(CertificateCancellationError::NotFound { certificate_id })?;
+        if header.status != CertificateStatus::Pending {
+            return Err(CertificateCancellationError::NotPending {
+                certificate_id,
+                status: header.status,
+            });
+        }
+
+        let network_id = header.network_id;
+        let expected_signer = self
+            .l1_rpc_provider
+            .get_trusted_sequencer_address(network_id.to_u32(), self.config.proof_signers.clone())
+            .await
+            .map_err(
+                |source| CertificateCancellationError::UnableToRetrieveSigner {
+                    network_id,
+                    source,
+                },
+            )?;
+        let commitment = certificate_id.cancellation_commitment();
+        if signature
+            .recover_address_from_prehash(&B256::new(commitment.0))
+            .ok()
+            != Some(expected_signer)
+        {
+            return Err(CertificateCancellationError::InvalidSignature { expected_signer });
+        }
+
+        self.pending_store
+            .remove_pending_certificate(network_id, header.height)
+            .inspect_err(|error| error!(?error, "Failed to remove the pending certificate"))?;
+        self.state
+            .update_certificate_header_status(
+                &certificate_id,
+                &CertificateStatus::error(CertificateStatusError::Cancelled),
+            )
+            .inspect_err(|error| error!(?error, "Failed to update the certificate status"))?;
+
+        info!(%certificate_id, %network_id, height = %header.height, "Certificate cancelled");
+
+        Ok(())
     }
+
+    /// Reject the certificate if one of its bridge exits sends to an address
+    /// blocked by the screening hooks.
+    async fn screen_bridge_exits(
+        &self,
+        certificate: &Certificate,
+    ) -> Result<(), CertificateSubmissionError> {
+        if self.screening.is_empty() {
+            return Ok(());
+        }
+
+        let mut seen = HashSet::new();
+        let addresses: Vec<Address> = certificate
+            .bridge_exits
+            .iter()
+            .map(|exit| exit.dest_address)
+            .filter(|address| seen.insert(*address))
+            .collect();
+        if addresses.is_empty() {
+            return Ok(());
+        }
+
+        for screening in &self.screening {
+            let blocked = screening
+                .blocked_addresses(certificate.network_id, &addresses)
+                .await
+                .map_err(|error| {
+                    error!(?error, "Failed to screen the bridge exits");
+                    CertificateSubmissionError::ScreeningFailed(error)
+                })?;
+
+            if let Some(address) = blocked.first() {
+                warn!(%address, "Rejecting the certificate, bridge exit to a blocked address");
+                intake_metrics::record_rejected(certificate.network_id.to_u32(), "blocked");
+                return Err(CertificateSubmissionError::BlockedBridgeExit { address: *address });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Position of the certificate at the given height in the queue of its
+    /// network, the certificates of a network being settled in order and at
+    /// most one per epoch.
+    fn queue_position(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<QueuePosition, StorageError> {
+        let latest_settled = self
+            .state
+            .get_latest_settled_certificate_per_network(&network_id)?
+            .map(|(_, settled)| settled);
+        let next_height = latest_settled
+            .as_ref()
+            .map_or(0, |SettledCertificate(_, height, _, _)| height.as_u64() + 1);
+        let queue_position = height.as_u64().saturating_sub(next_height);
+
+        let Some(clock) = &self.clock else {
+            return Ok(QueuePosition {
+                queue_position,
+                current_epoch: None,
+                estimated_certification_window: None,
+            });
+        };
+
+        // The network can't settle another certificate in the current epoch
+        // once one is settled in it.
+        let current_epoch = clock.current_epoch();
+        let settled_in_current_epoch = matches!(
+            latest_settled,
+            Some(SettledCertificate(_, _, epoch, _)) if epoch == current_epoch
+        );
+        let first = current_epoch
+            .as_u64()
+            .saturating_add(queue_position)
+            .saturating_add(settled_in_current_epoch as u64);
+
+        Ok(QueuePosition {
+            queue_position,
+            current_epoch: Some(current_epoch),
+            estimated_certification_window: Some(EpochWindow {
+                first: EpochNumber::new(first),
+                last: EpochNumber::new(first.saturating_add(1)),
+            }),
+        })
+    }
+}
+
+/// Outcome of a certificate submission.
+/// Encoding of the proofs served to the clients.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProofEncoding {
+    /// Bincode serialization of the [`Proof`], with the options of
+    /// [`agglayer_types::bincode::default`].
+    #[default]
+    Bincode,
+    /// The bincode serialization compressed as a single zstd frame.
+    Zstd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateSubmission {
+    /// Id of the submitted certificate.
+    pub certificate_id: CertificateId,
+    /// Current status of the certificate.
+    pub status: CertificateStatus,
+    /// Whether the same certificate was already submitted, in which case it
+    /// is left untouched.
+    pub already_known: bool,
+    /// Position of the certificate in the queue of its network.
+    pub position: QueuePosition,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -0,0 +1,95 @@
+//! Maintenance mode, in which new certificates are rejected while the
+//! in-flight ones are processed, so that the agglayer can be upgraded cleanly.
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+/// Progress of the maintenance mode.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MaintenanceStatus {
+    /// New certificates are accepted.
+    #[default]
+    Inactive,
+    /// New certificates are rejected while the in-flight ones are processed.
+    Draining,
+    /// The in-flight certificates are processed and the storage is flushed,
+    /// the agglayer can be stopped.
+    Ready,
+}
+
+/// State of the maintenance mode.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MaintenanceState {
+    pub status: MaintenanceStatus,
+    /// Whether the agglayer exits once ready.
+    pub exit_when_ready: bool,
+}
+
+/// Maintenance mode shared by the services accepting the certificates, the
+/// admin API triggering it and the node draining the in-flight work.
+pub struct Maintenance {
+    state: watch::Sender<MaintenanceState>,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self {
+            state: watch::Sender::new(MaintenanceState::default()),
+        }
+    }
+}
+
+impl Maintenance {
+    /// Current state of the maintenance mode.
+    pub fn state(&self) -> MaintenanceState {
+        *self.state.borrow()
+    }
+
+    /// Whether new certificates are rejected.
+    pub fn is_active(&self) -> bool {
+        self.state().status != MaintenanceStatus::Inactive
+    }
+
+    /// Stop accepting new certificates and start draining the in-flight
+    /// ones, exiting once done if requested.
+    pub fn enter(&self, exit_when_ready: bool) -> MaintenanceState {
+        self.state.send_modify(|state| {
+            if state.status == MaintenanceStatus::Inactive {
+                state.status = MaintenanceStatus::Draining;
+            }
+            state.exit_when_ready = exit_when_ready;
+        });
+
+        self.state()
+    }
+
+    /// Accept new certificates again.
+    pub fn leave(&self) -> MaintenanceState {
+        self.state.send_replace(MaintenanceState::default());
+
+        self.state()
+    }
+
+    /// Mark the in-flight work as done, returning the resulting state, or
+    /// `None` if the maintenance was left while draining.
+    pub fn set_ready(&self) -> Option<MaintenanceState> {
+        let mut ready = None;
+        self.state.send_if_modified(|state| {
+            if state.status != MaintenanceStatus::Draining {
+                return false;
+            }
+
+            state.status = MaintenanceStatus::Ready;
+            ready = Some(*state);
+            true
+        });
+
+        ready
+    }
+
+    /// Watch the changes of the maintenance state.
+    pub fn subscribe(&self) -> watch::Receiver<MaintenanceState> {
+        self.state.subscribe()
+    }
+}
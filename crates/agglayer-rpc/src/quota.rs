@@ -0,0 +1,161 @@
+//! Quotas of the API keys identifying the clients of the agglayer, when it is
+//! operated as a shared service.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use agglayer_config::RpcApiKeysConfig;
+use agglayer_storage::{
+    columns::api_key_usage::ApiKeyUsage,
+    stores::{StateReader, StateWriter},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+
+use crate::{
+    error::{QuotaError, StorageError},
+    AgglayerService,
+};
+
+/// Usage of the API keys over the current quota period.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKeyUsageReport {
+    /// Start and end of the period, in seconds since the UNIX epoch.
+    pub period_start: u64,
+    pub period_end: u64,
+    pub keys: Vec<ApiKeyUsageEntry>,
+}
+
+/// Usage and quotas of an API key, the quotas being unlimited if `None`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKeyUsageEntry {
+    pub name: String,
+    pub requests: u64,
+    pub certificate_bytes: u64,
+    pub max_requests: Option<u64>,
+    pub max_certificate_bytes: Option<u64>,
+}
+
+/// Index of the quota period including the given time, counted from the UNIX
+/// epoch.
+fn period_at(period: Duration, time: SystemTime) -> u64 {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    elapsed.as_secs() / period.as_secs().max(1)
+}
+
+impl<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
+    AgglayerService<L1Rpc, PendingStore, StateStore, DebugStore, EpochsStore>
+where
+    StateStore: StateReader + StateWriter,
+{
+    /// Account for a request made with the API key, along with the size of
+    /// the certificate it submits if any.
+    ///
+    /// The requests rejected for exceeding a quota are accounted for as well.
+    pub fn consume_api_key_quota(
+        &self,
+        api_key: Option<&str>,
+        certificate_bytes: u64,
+    ) -> Result<(), QuotaError> {
+        let config = &self.config.rpc.api_keys;
+        if !config.required && config.keys.is_empty() {
+            return Ok(());
+        }
+
+        let Some(api_key) = api_key else {
+            return if config.required {
+                Err(QuotaError::MissingApiKey)
+            } else {
+                Ok(())
+            };
+        };
+
+        let (name, quotas) = config.find(api_key).ok_or(QuotaError::UnknownApiKey)?;
+
+        // The usage can't be recorded in a read-only storage.
+        if self.config.read_only.enabled {
+            return Ok(());
+        }
+
+        let period = period_at(config.period, SystemTime::now());
+        let usage = self
+            .state
+            .add_api_key_usage(
+                name,
+                period,
+                &ApiKeyUsage {
+                    requests: 1,
+                    certificate_bytes,
+                },
+            )
+            .inspect_err(|error| error!(?error, "Failed to record the usage of API key {name}"))?;
+
+        if let Some(limit) = quotas.max_requests.filter(|limit| usage.requests > *limit) {
+            warn!("Request quota of API key {name} exhausted");
+            return Err(QuotaError::RequestsExhausted {
+                name: name.to_string(),
+                limit,
+            });
+        }
+
+        if let Some(limit) = quotas
+            .max_certificate_bytes
+            .filter(|limit| certificate_bytes > 0 && usage.certificate_bytes > *limit)
+        {
+            warn!("Certificate quota of API key {name} exhausted");
+            return Err(QuotaError::CertificateBytesExhausted {
+                name: name.to_string(),
+                limit,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Report the usage of the given API key over the current quota period.
+    pub fn get_api_key_usage(
+        &self,
+        api_key: Option<&str>,
+    ) -> Result<ApiKeyUsageReport, QuotaError> {
+        let config = &self.config.rpc.api_keys;
+        let api_key = api_key.ok_or(QuotaError::MissingApiKey)?;
+        let (name, _) = config.find(api_key).ok_or(QuotaError::UnknownApiKey)?;
+
+        let mut report = ApiKeyUsageReport::load(config, self.state.as_ref())
+            .inspect_err(|error| error!(?error, "Failed to get the usage of API key {name}"))?;
+        report.keys.retain(|entry| entry.name == name);
+
+        Ok(report)
+    }
+}
+
+impl ApiKeyUsageReport {
+    /// Load the usage of the configured API keys over the current quota
+    /// period.
+    pub fn load(config: &RpcApiKeysConfig, state: &impl StateReader) -> Result<Self, StorageError> {
+        let period_secs = config.period.as_secs().max(1);
+        let period = period_at(config.period, SystemTime::now());
+        let mut usage = state.get_api_key_usage(period)?;
+
+        let keys = config
+            .keys
+            .iter()
+            .map(|(name, quotas)| {
+                let usage = usage.remove(name).unwrap_or_default();
+                ApiKeyUsageEntry {
+                    name: name.clone(),
+                    requests: usage.requests,
+                    certificate_bytes: usage.certificate_bytes,
+                    max_requests: quotas.max_requests,
+                    max_certificate_bytes: quotas.max_certificate_bytes,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            period_start: period * period_secs,
+            period_end: (period + 1) * period_secs,
+            keys,
+        })
+    }
+}
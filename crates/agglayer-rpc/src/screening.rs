@@ -0,0 +1,156 @@
+//! Screening of the destination addresses of the bridge exits of the
+//! submitted certificates, rejecting the ones sending to blocked addresses.
+//!
+//! The screening is pluggable through [`BridgeExitScreening`]. A static
+//! blocklist and an external screening service are provided, built from the
+//! configuration with [`from_config`].
+
+use std::{collections::HashSet, sync::Arc};
+
+use agglayer_config::{ScreeningConfig, ScreeningServiceConfig};
+use agglayer_types::{Address, NetworkId};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use url::Url;
+
+/// Errors of the screening of the bridge exits.
+#[derive(Debug, thiserror::Error)]
+pub enum ScreeningError {
+    #[error("Screening service request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Invalid screening service response: {0}")]
+    InvalidResponse(#[from] serde_json::Error),
+}
+
+/// Hook screening the destination addresses of the bridge exits of the
+/// submitted certificates.
+#[async_trait::async_trait]
+pub trait BridgeExitScreening: Send + Sync {
+    /// Returns the blocked addresses among the destination addresses of the
+    /// bridge exits of a certificate of the given network.
+    async fn blocked_addresses(
+        &self,
+        network_id: NetworkId,
+        addresses: &[Address],
+    ) -> Result<Vec<Address>, ScreeningError>;
+}
+
+/// Static list of blocked addresses.
+pub struct Blocklist(HashSet<Address>);
+
+impl Blocklist {
+    pub fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self(addresses.into_iter().collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl BridgeExitScreening for Blocklist {
+    async fn blocked_addresses(
+        &self,
+        _network_id: NetworkId,
+        addresses: &[Address],
+    ) -> Result<Vec<Address>, ScreeningError> {
+        Ok(addresses
+            .iter()
+            .filter(|address| self.0.contains(address))
+            .copied()
+            .collect())
+    }
+}
+
+/// External service screening the addresses.
+///
+/// The addresses are posted as JSON, along with the network of the
+/// certificate, and the service answers with the blocked ones.
+pub struct ScreeningService {
+    url: Url,
+    client: reqwest::Client,
+    fail_open: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct ScreeningRequest<'a> {
+    network_id: NetworkId,
+    addresses: &'a [Address],
+}
+
+#[derive(Deserialize)]
+struct ScreeningResponse {
+    blocked: Vec<Address>,
+}
+
+impl ScreeningService {
+    pub fn try_new(config: &ScreeningServiceConfig) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder().timeout(config.timeout).build()?;
+
+        Ok(Self {
+            url: config.url.clone(),
+            client,
+            fail_open: config.fail_open,
+        })
+    }
+
+    async fn request(
+        &self,
+        network_id: NetworkId,
+        addresses: &[Address],
+    ) -> Result<Vec<Address>, ScreeningError> {
+        let body = serde_json::to_vec(&ScreeningRequest {
+            network_id,
+            addresses,
+        })?;
+
+        let response = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        Ok(serde_json::from_slice::<ScreeningResponse>(&response)?.blocked)
+    }
+}
+
+#[async_trait::async_trait]
+impl BridgeExitScreening for ScreeningService {
+    async fn blocked_addresses(
+        &self,
+        network_id: NetworkId,
+        addresses: &[Address],
+    ) -> Result<Vec<Address>, ScreeningError> {
+        match self.request(network_id, addresses).await {
+            Err(error) if self.fail_open => {
+                warn!(
+                    ?error,
+                    "Screening service failed, accepting the certificate"
+                );
+                Ok(Vec::new())
+            }
+            result => result,
+        }
+    }
+}
+
+/// Build the screening hooks enabled by the configuration.
+pub fn from_config(
+    config: &ScreeningConfig,
+) -> Result<Vec<Arc<dyn BridgeExitScreening>>, reqwest::Error> {
+    let mut screening: Vec<Arc<dyn BridgeExitScreening>> = Vec::new();
+
+    if !config.blocklist.is_empty() {
+        screening.push(Arc::new(Blocklist::new(config.blocklist.iter().copied())));
+    }
+
+    if let Some(service) = &config.service {
+        screening.push(Arc::new(ScreeningService::try_new(service)?));
+    }
+
+    Ok(screening)
+}
@@ -0,0 +1,58 @@
+use agglayer_config::RpcIntakeConfig;
+use agglayer_types::NetworkId;
+use tokio::sync::oneshot;
+
+use crate::{intake::IntakePool, CertificateSubmissionError};
+
+const NETWORK_1: NetworkId = NetworkId::new(1);
+
+fn pool(workers: usize, queue_size: usize) -> IntakePool {
+    IntakePool::new(&RpcIntakeConfig {
+        workers,
+        queue_size,
+        ..Default::default()
+    })
+}
+
+#[tokio::test]
+async fn validation_result_is_returned() {
+    let pool = pool(1, 0);
+
+    assert!(matches!(pool.run(NETWORK_1, || Ok(42)).await, Ok(42)));
+    assert!(matches!(
+        pool.run(NETWORK_1, || Err::<(), _>(
+            CertificateSubmissionError::IntakeWorkerFailed
+        ))
+        .await,
+        Err(CertificateSubmissionError::IntakeWorkerFailed)
+    ));
+}
+
+#[tokio::test]
+async fn submissions_beyond_the_queue_are_rejected() {
+    let pool = pool(1, 0);
+    let (release, released) = oneshot::channel::<()>();
+
+    // Occupy the only worker until released.
+    let busy = pool.run(NETWORK_1, move || {
+        released.blocking_recv().unwrap();
+        Ok(())
+    });
+    let rejected = async {
+        // Let the busy validation take the worker first.
+        tokio::task::yield_now().await;
+        let result = pool.run(NETWORK_1, || Ok(())).await;
+        release.send(()).unwrap();
+        result
+    };
+
+    let (busy, rejected) = tokio::join!(busy, rejected);
+    assert!(busy.is_ok());
+    assert!(matches!(
+        rejected,
+        Err(CertificateSubmissionError::IntakeOverloaded)
+    ));
+
+    // The worker is available again once released.
+    assert!(pool.run(NETWORK_1, || Ok(())).await.is_ok());
+}
@@ -0,0 +1,49 @@
+use crate::{Maintenance, MaintenanceState, MaintenanceStatus};
+
+#[test]
+fn maintenance_goes_through_draining_then_ready() {
+    let maintenance = Maintenance::default();
+    assert!(!maintenance.is_active());
+    assert_eq!(maintenance.set_ready(), None);
+
+    let state = maintenance.enter(false);
+    assert_eq!(state.status, MaintenanceStatus::Draining);
+    assert!(maintenance.is_active());
+
+    let ready = MaintenanceState {
+        status: MaintenanceStatus::Ready,
+        exit_when_ready: false,
+    };
+    assert_eq!(maintenance.set_ready(), Some(ready));
+    // Entering again only updates the exit request.
+    assert_eq!(
+        maintenance.enter(true),
+        MaintenanceState {
+            exit_when_ready: true,
+            ..ready
+        }
+    );
+
+    assert_eq!(maintenance.leave(), MaintenanceState::default());
+    assert!(!maintenance.is_active());
+}
+
+#[test]
+fn leaving_while_draining_cancels_the_maintenance() {
+    let maintenance = Maintenance::default();
+    let mut changes = maintenance.subscribe();
+
+    maintenance.enter(true);
+    assert!(changes.has_changed().unwrap());
+    assert_eq!(
+        changes.borrow_and_update().status,
+        MaintenanceStatus::Draining
+    );
+
+    maintenance.leave();
+    assert_eq!(maintenance.set_ready(), None);
+    assert_eq!(
+        changes.borrow_and_update().status,
+        MaintenanceStatus::Inactive
+    );
+}
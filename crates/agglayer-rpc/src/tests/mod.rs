@@ -1 +1,4 @@
+pub mod intake;
+pub mod maintenance;
 pub mod network_info;
+pub mod screening;
@@ -71,6 +71,16 @@ fn transient_network_info() {
         .once()
         .return_once(move |_, _| Ok(Some(pending_certificate.clone())));
 
+    state_store
+        .expect_get_latest_pending_certificate_index()
+        .with(eq(NETWORK_1))
+        .returning(|_| Ok(None));
+
+    state_store
+        .expect_get_latest_proven_certificate_index()
+        .with(eq(NETWORK_1))
+        .returning(|_| Ok(None));
+
     pending_store
         .expect_get_latest_pending_certificate_for_network()
         .with(eq(NETWORK_1))
@@ -158,6 +168,7 @@ fn pending_certificate_defined() {
                     PessimisticRootCommitmentVersion::V2,
                 ),
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                gas_token: None,
             },
         )
         .unwrap();
@@ -181,6 +192,16 @@ fn pending_certificate_defined() {
         .expect_read_local_network_state()
         .returning(move |_| Ok(Some(network_state.state_b.clone())));
 
+    state_store
+        .expect_get_latest_pending_certificate_index()
+        .with(eq(NETWORK_1))
+        .returning(|_| Ok(None));
+
+    state_store
+        .expect_get_latest_proven_certificate_index()
+        .with(eq(NETWORK_1))
+        .returning(|_| Ok(None));
+
     pending_store
         .expect_get_latest_pending_certificate_for_network()
         .with(eq(NETWORK_1))
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use agglayer_config::ScreeningServiceConfig;
+use agglayer_types::{Address, NetworkId};
+
+use crate::screening::{Blocklist, BridgeExitScreening as _, ScreeningService};
+
+const NETWORK_1: NetworkId = NetworkId::new(1);
+
+#[tokio::test]
+async fn blocklist_returns_the_blocked_addresses() {
+    let blocked: Address = alloy::primitives::Address::repeat_byte(1).into();
+    let allowed: Address = alloy::primitives::Address::repeat_byte(2).into();
+    let blocklist = Blocklist::new([blocked]);
+
+    assert_eq!(
+        blocklist
+            .blocked_addresses(NETWORK_1, &[allowed, blocked])
+            .await
+            .unwrap(),
+        vec![blocked]
+    );
+    assert!(blocklist
+        .blocked_addresses(NETWORK_1, &[allowed])
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+fn unreachable_service(fail_open: bool) -> ScreeningService {
+    // Nothing listens on the discard port.
+    ScreeningService::try_new(&ScreeningServiceConfig {
+        url: "http://127.0.0.1:9/screen".parse().unwrap(),
+        timeout: Duration::from_secs(1),
+        fail_open,
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn unreachable_service_rejects_the_certificates() {
+    let service = unreachable_service(false);
+
+    assert!(service
+        .blocked_addresses(NETWORK_1, &[Address::ZERO])
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn unreachable_service_accepts_the_certificates_if_failing_open() {
+    let service = unreachable_service(true);
+
+    assert!(service
+        .blocked_addresses(NETWORK_1, &[Address::ZERO])
+        .await
+        .unwrap()
+        .is_empty());
+}
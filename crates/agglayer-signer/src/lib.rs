@@ -48,14 +48,18 @@ impl ConfiguredSigner {
 
     /// Get either a local wallet or GCP KMS signer based on the configuration.
     pub async fn new(config: Arc<Config>) -> Result<Self, Error> {
-        match &config.auth {
+        Self::from_auth(config.l1.chain_id, &config.auth).await
+    }
+
+    /// Get either a local wallet or GCP KMS signer based on the given
+    /// authentication configuration, such as the next key of a rotation.
+    pub async fn from_auth(chain_id: u64, auth: &AuthConfig) -> Result<Self, Error> {
+        match auth {
             AuthConfig::GcpKms(ref kms) => {
-                let kms = KMS::new(config.l1.chain_id, kms.clone());
+                let kms = KMS::new(chain_id, kms.clone());
                 Ok(Self::Kms(kms.gcp_kms_signer().await?))
             }
-            AuthConfig::Local(ref local) => {
-                Ok(Self::Local(Self::local_wallet(config.l1.chain_id, local)?))
-            }
+            AuthConfig::Local(ref local) => Ok(Self::Local(Self::local_wallet(chain_id, local)?)),
         }
     }
 
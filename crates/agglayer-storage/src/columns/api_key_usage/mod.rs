@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, API_KEY_USAGE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the usage of the API keys per quota period.
+///
+/// ## Column definition
+///
+/// | key               | value         |
+/// | --                | --            |
+/// | (`u64`, `String`) | `ApiKeyUsage` |
+pub struct ApiKeyUsageColumn;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Key {
+    /// Index of the quota period, counted from the UNIX epoch.
+    pub(crate) period: u64,
+    /// Name of the API key.
+    pub(crate) api_key: String,
+}
+
+/// Usage of an API key over a quota period.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKeyUsage {
+    /// Number of requests made with the key.
+    pub requests: u64,
+    /// Size of the certificates submitted with the key, in bytes.
+    pub certificate_bytes: u64,
+}
+
+impl ApiKeyUsage {
+    pub fn add(&mut self, other: &ApiKeyUsage) {
+        self.requests = self.requests.saturating_add(other.requests);
+        self.certificate_bytes = self
+            .certificate_bytes
+            .saturating_add(other.certificate_bytes);
+    }
+}
+
+pub type Value = ApiKeyUsage;
+
+crate::columns::impl_codec_using_bincode_for!(Key);
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for ApiKeyUsageColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = API_KEY_USAGE_CF;
+}
@@ -0,0 +1,40 @@
+use super::{ApiKeyUsage, Key, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_key() {
+    let key = Key {
+        period: 258,
+        api_key: "chain-a".to_string(),
+    };
+
+    let encoded = key.encode().expect("Unable to encode key");
+
+    let expected_key = Key::decode(&encoded[..]).expect("Unable to decode key");
+
+    assert_eq!(expected_key, key);
+
+    // Big endian, so that the keys of a period are contiguous
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 1, 2]);
+    // api_key
+    assert_eq!(encoded[16..], *b"chain-a");
+}
+
+#[test]
+fn can_parse_value() {
+    let value = ApiKeyUsage {
+        requests: 2,
+        certificate_bytes: 300,
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+
+    // requests
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 0, 2]);
+    // certificate_bytes
+    assert_eq!(encoded[8..16], [0, 0, 0, 0, 0, 0, 1, 44]);
+}
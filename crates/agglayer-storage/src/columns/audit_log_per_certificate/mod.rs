@@ -0,0 +1,74 @@
+use std::net::SocketAddr;
+
+use agglayer_types::{CertificateId, CertificateStatus, SettlementTxHash};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, AUDIT_LOG_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the audit log of the certificates, recording each step
+/// of their lifecycle in order. Records are only ever appended.
+///
+/// ## Column definition
+///
+/// | key             | value              |
+/// | --              | --                 |
+/// | `CertificateId` | `Vec<AuditRecord>` |
+pub struct AuditLogPerCertificateColumn;
+
+/// One entry of the audit log of a certificate.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Time at which the event was recorded.
+    pub timestamp: DateTime<Utc>,
+    pub event: AuditEvent,
+}
+
+/// Step of the lifecycle of a certificate.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AuditEvent {
+    /// The certificate was submitted.
+    Submitted { submitter: Submitter },
+    /// The status of the certificate changed.
+    StatusChanged { status: CertificateStatus },
+    /// The proof of the certificate was generated.
+    Proven {
+        /// The prover which generated the proof, if known.
+        prover: Option<String>,
+    },
+    /// A settlement transaction was submitted for the certificate.
+    SettlementSubmitted {
+        settlement_tx_hash: SettlementTxHash,
+    },
+}
+
+/// Origin of a certificate submission.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Submitter {
+    /// The API the certificate was submitted through.
+    pub api: SubmissionApi,
+    /// The address of the peer which submitted the certificate, if known.
+    pub address: Option<SocketAddr>,
+}
+
+/// API through which a certificate can be submitted.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SubmissionApi {
+    JsonRpc,
+    Grpc,
+}
+
+pub type Key = CertificateId;
+pub type Value = Vec<AuditRecord>;
+
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for AuditLogPerCertificateColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = AUDIT_LOG_PER_CERTIFICATE_CF;
+}
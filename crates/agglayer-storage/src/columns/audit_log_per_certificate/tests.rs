@@ -0,0 +1,48 @@
+use agglayer_types::{CertificateStatus, Digest, SettlementTxHash};
+use chrono::DateTime;
+
+use super::{AuditEvent, AuditRecord, SubmissionApi, Submitter, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_value() {
+    let timestamp = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    let value = vec![
+        AuditRecord {
+            timestamp,
+            event: AuditEvent::Submitted {
+                submitter: Submitter {
+                    api: SubmissionApi::JsonRpc,
+                    address: Some("127.0.0.1:4444".parse().unwrap()),
+                },
+            },
+        },
+        AuditRecord {
+            timestamp,
+            event: AuditEvent::StatusChanged {
+                status: CertificateStatus::Pending,
+            },
+        },
+        AuditRecord {
+            timestamp,
+            event: AuditEvent::Proven {
+                prover: Some("http://prover:8080".to_string()),
+            },
+        },
+        AuditRecord {
+            timestamp,
+            event: AuditEvent::SettlementSubmitted {
+                settlement_tx_hash: SettlementTxHash::new(Digest([1; 32])),
+            },
+        },
+    ];
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+
+    // length
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 0, 4]);
+}
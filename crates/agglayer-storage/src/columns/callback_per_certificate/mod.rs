@@ -0,0 +1,36 @@
+use agglayer_types::CertificateId;
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, CALLBACK_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the callbacks to notify of the final status of the
+/// certificates. Entries are removed once the callback is delivered.
+///
+/// ## Column definition
+///
+/// | key             | value                 |
+/// | --              | --                    |
+/// | `CertificateId` | `CertificateCallback` |
+pub struct CallbackPerCertificateColumn;
+
+/// Callback registered along with a certificate.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CertificateCallback {
+    /// URL to which the final status of the certificate is posted.
+    pub url: String,
+}
+
+pub type Key = CertificateId;
+pub type Value = CertificateCallback;
+
+crate::columns::impl_codec_using_bincode_for!(CertificateCallback);
+
+impl ColumnSchema for CallbackPerCertificateColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = CALLBACK_PER_CERTIFICATE_CF;
+}
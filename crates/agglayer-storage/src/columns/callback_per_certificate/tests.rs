@@ -0,0 +1,20 @@
+use super::CertificateCallback;
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_value() {
+    let value = CertificateCallback {
+        url: "https://example.com/cb".to_string(),
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = CertificateCallback::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+
+    // length
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 0, 22]);
+    // url
+    assert_eq!(&encoded[8..], b"https://example.com/cb");
+}
@@ -0,0 +1,55 @@
+use agglayer_types::CertificateId;
+use chrono::{DateTime, Utc};
+use pessimistic_proof::local_state::StateCommitment;
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, CERTIFICATION_FAILURE_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family of the debug storage recording the inputs of the failed
+/// certifications, the certificate itself being in the debug certificates.
+/// Only the most recent failures are kept.
+///
+/// ## Column definition
+///
+/// | key             | value                  |
+/// | --              | --                     |
+/// | `CertificateId` | `CertificationFailure` |
+pub struct CertificationFailurePerCertificateColumn;
+
+/// The last failed certification of a certificate.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CertificationFailure {
+    /// Time at which the failure was recorded.
+    pub recorded_at: DateTime<Utc>,
+    /// Roots of the network state the certificate was certified against.
+    pub initial_roots: StateCommitment,
+    /// The certification error, which includes the public values of the
+    /// native and zkVM executions when they differ.
+    pub error: String,
+}
+
+impl CertificationFailure {
+    /// A failure recorded now.
+    pub fn new(initial_roots: StateCommitment, error: String) -> Self {
+        Self {
+            recorded_at: Utc::now(),
+            initial_roots,
+            error,
+        }
+    }
+}
+
+pub type Key = CertificateId;
+pub type Value = CertificationFailure;
+
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for CertificationFailurePerCertificateColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = CERTIFICATION_FAILURE_PER_CERTIFICATE_CF;
+}
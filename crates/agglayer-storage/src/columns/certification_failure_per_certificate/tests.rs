@@ -0,0 +1,25 @@
+use agglayer_types::Digest;
+use chrono::DateTime;
+use pessimistic_proof::local_state::StateCommitment;
+
+use super::{CertificationFailure, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_value() {
+    let value = CertificationFailure {
+        recorded_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        initial_roots: StateCommitment {
+            exit_root: Digest([1; 32]),
+            ler_leaf_count: 2,
+            balance_root: Digest([3; 32]),
+            nullifier_root: Digest([4; 32]),
+        },
+        error: "Mismatch on the pessimistic proof public values".to_string(),
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+    let decoded = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(decoded, value);
+}
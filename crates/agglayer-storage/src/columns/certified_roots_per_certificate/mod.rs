@@ -0,0 +1,37 @@
+use agglayer_types::CertificateId;
+use pessimistic_proof::local_state::StateCommitment;
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, CERTIFIED_ROOTS_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family containing the roots of the network state before and after
+/// each certificate having a generated proof, written along with the proof.
+///
+/// ## Column definition
+///
+/// | key             | value            |
+/// | --              | --               |
+/// | `CertificateId` | `CertifiedRoots` |
+pub struct CertifiedRootsPerCertificateColumn;
+
+/// Roots of the network state a proof was generated from and leads to.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CertifiedRoots {
+    pub initial_roots: StateCommitment,
+    pub new_roots: StateCommitment,
+}
+
+pub type Key = CertificateId;
+pub type Value = CertifiedRoots;
+
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for CertifiedRootsPerCertificateColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = CERTIFIED_ROOTS_PER_CERTIFICATE_CF;
+}
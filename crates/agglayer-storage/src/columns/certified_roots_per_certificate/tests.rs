@@ -0,0 +1,23 @@
+use agglayer_types::Digest;
+use pessimistic_proof::local_state::StateCommitment;
+
+use super::{CertifiedRoots, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_value() {
+    let value = CertifiedRoots {
+        initial_roots: StateCommitment::default(),
+        new_roots: StateCommitment {
+            exit_root: Digest([1; 32]),
+            ler_leaf_count: 2,
+            balance_root: Digest([3; 32]),
+            nullifier_root: Digest([4; 32]),
+        },
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+    let decoded = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(decoded, value);
+}
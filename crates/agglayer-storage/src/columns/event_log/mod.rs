@@ -0,0 +1,65 @@
+use agglayer_types::{CertificateId, EpochEvent};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{audit_log_per_certificate::AuditEvent, ColumnSchema, EVENT_LOG_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the log of the certificate and epoch events, in the
+/// order in which they were recorded. Events are only ever appended, with
+/// increasing identifiers, so that consumers can resume after the last one
+/// they received.
+///
+/// ## Column definition
+///
+/// | key       | value         |
+/// | --        | --            |
+/// | `EventId` | `LoggedEvent` |
+pub struct EventLogColumn;
+
+/// Identifier of an event of the log, increasing in the recording order.
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct EventId(pub u64);
+
+impl EventId {
+    #[must_use = "The identifier of the next event is returned but not used"]
+    pub const fn next(&self) -> EventId {
+        EventId(self.0.checked_add(1).expect("Event id overflow"))
+    }
+}
+
+/// One entry of the event log.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LoggedEvent {
+    /// Time at which the event was recorded.
+    pub timestamp: DateTime<Utc>,
+    pub event: Event,
+}
+
+/// Event recorded in the log.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Event {
+    /// A step of the lifecycle of a certificate.
+    Certificate {
+        certificate_id: CertificateId,
+        event: AuditEvent,
+    },
+    /// A step of the lifecycle of an epoch.
+    Epoch(EpochEvent),
+}
+
+pub type Key = EventId;
+pub type Value = LoggedEvent;
+
+crate::columns::impl_codec_using_bincode_for!(Key, Value);
+
+impl ColumnSchema for EventLogColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = EVENT_LOG_CF;
+}
@@ -0,0 +1,44 @@
+use agglayer_types::{CertificateId, CertificateStatus, Digest, EpochEvent, EpochNumber};
+use chrono::DateTime;
+
+use super::{Event, EventId, Key, LoggedEvent, Value};
+use crate::columns::{audit_log_per_certificate::AuditEvent, Codec as _};
+
+#[test]
+fn can_parse_key() {
+    let key = EventId(258);
+
+    let encoded = key.encode().expect("Unable to encode key");
+
+    let expected_key = Key::decode(&encoded[..]).expect("Unable to decode key");
+
+    assert_eq!(expected_key, key);
+
+    // Big endian, so that the events are iterated in order
+    assert_eq!(encoded, [0, 0, 0, 0, 0, 0, 1, 2]);
+}
+
+#[test]
+fn can_parse_value() {
+    let timestamp = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+    for event in [
+        Event::Certificate {
+            certificate_id: CertificateId::new(Digest([1; 32])),
+            event: AuditEvent::StatusChanged {
+                status: CertificateStatus::Proven,
+            },
+        },
+        Event::Epoch(EpochEvent::Opened {
+            epoch_number: EpochNumber::new(3),
+        }),
+    ] {
+        let value = LoggedEvent { timestamp, event };
+
+        let encoded = value.encode().expect("Unable to encode value");
+
+        let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+        assert_eq!(expected_value, value);
+    }
+}
@@ -7,6 +7,9 @@ use super::{ColumnSchema, LATEST_PROVEN_CERTIFICATE_PER_NETWORK_CF};
 /// The key is the network_id and the value is the certificateID and
 /// the height.
 ///
+/// Present in both the pending and the state storage. In the state storage,
+/// it is updated in the same write as the status of the certificate headers.
+///
 /// ## Column definition
 ///
 /// | key         | value                       |
@@ -29,6 +29,9 @@ pub enum CodecError {
 
     #[error(r#"Unable to write encoded bytes: {0}"#)]
     UnableToWriteEncodedBytes(#[from] std::io::Error),
+
+    #[error(r#"Unable to decompress the encoded bytes: {0}"#)]
+    Decompression(#[source] std::io::Error),
 }
 
 pub fn bincode_codec() -> bincode::Codec<impl bincode::Options> {
@@ -41,6 +44,8 @@ pub const NULLIFIER_TREE_PER_NETWORK_CF: &str = "nullifier_tree_per_network_cf";
 pub const BALANCE_TREE_PER_NETWORK_CF: &str = "balance_tree_per_network_cf";
 pub const LOCAL_EXIT_TREE_PER_NETWORK_CF: &str = "local_exit_tree_per_network_cf";
 pub const NETWORK_INFO_CF: &str = "network_info_cf";
+pub const SETTLEMENT_COSTS_PER_NETWORK_CF: &str = "settlement_costs_per_network_cf";
+pub const SETTLED_ROOTS_PER_NETWORK_CF: &str = "settled_roots_per_network_cf";
 
 // Metadata CFs
 pub const CERTIFICATE_HEADER_CF: &str = "certificate_header_cf";
@@ -51,6 +56,13 @@ pub const LATEST_SETTLED_CERTIFICATE_PER_NETWORK_CF: &str =
 pub const LATEST_PENDING_CERTIFICATE_PER_NETWORK_CF: &str =
     "latest_pending_certificate_per_network_cf";
 pub const METADATA_CF: &str = "metadata_cf";
+pub const SETTLEMENT_ATTEMPTS_PER_CERTIFICATE_CF: &str = "settlement_attempts_per_certificate_cf";
+pub const SETTLEMENT_SENDER_PER_TX_CF: &str = "settlement_sender_per_tx_cf";
+pub const REVERTED_SETTLEMENTS_PER_EPOCH_CF: &str = "reverted_settlements_per_epoch_cf";
+pub const CALLBACK_PER_CERTIFICATE_CF: &str = "callback_per_certificate_cf";
+pub const AUDIT_LOG_PER_CERTIFICATE_CF: &str = "audit_log_per_certificate_cf";
+pub const EVENT_LOG_CF: &str = "event_log_cf";
+pub const API_KEY_USAGE_CF: &str = "api_key_usage_cf";
 
 // epochs related CFs
 pub const PER_EPOCH_CERTIFICATES_CF: &str = "per_epoch_certificates_cf";
@@ -62,9 +74,14 @@ pub const PER_EPOCH_START_CHECKPOINT_CF: &str = "per_epoch_start_checkpoint_cf";
 // Pending related CFs
 pub const PENDING_QUEUE_CF: &str = "pending_queue_cf";
 pub const PROOF_PER_CERTIFICATE_CF: &str = "proof_per_certificate_cf";
+pub const PROOF_CACHE_PER_CERTIFICATE_CF: &str = "proof_cache_per_certificate_cf";
+pub const SUBMITTED_PROOF_PER_CERTIFICATE_CF: &str = "submitted_proof_per_certificate_cf";
+pub const CERTIFIED_ROOTS_PER_CERTIFICATE_CF: &str = "certified_roots_per_certificate_cf";
+pub const VERIFIED_PROOF_PER_CERTIFICATE_CF: &str = "verified_proof_per_certificate_cf";
 
 // debug CFs
 pub const DEBUG_CERTIFICATES_CF: &str = "debug_certificates";
+pub const CERTIFICATION_FAILURE_PER_CERTIFICATE_CF: &str = "certification_failure_per_certificate_cf";
 
 pub trait Codec: Sized {
     #[inline]
@@ -100,6 +117,73 @@ macro_rules! impl_codec_using_bincode_for {
 
 pub(crate) use impl_codec_using_bincode_for;
 
+/// Magic number starting every zstd frame.
+pub(crate) const ZSTD_MAGIC_NUMBER: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Implement the codec of large values, such as the proofs, as their
+/// zstd-compressed bincode encoding.
+///
+/// Values stored before the compression are their plain bincode encoding,
+/// which is still decoded as long as it can't start with the zstd magic
+/// number.
+macro_rules! impl_codec_using_compressed_bincode_for {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl $crate::columns::Codec for $type {
+                fn encode_into<W: $crate::columns::io::Write>(
+                    &self,
+                    writer: W,
+                ) -> Result<(), $crate::columns::CodecError> {
+                    let bytes = $crate::columns::bincode_codec().serialize(self)?;
+                    zstd::stream::copy_encode(
+                        bytes.as_slice(),
+                        writer,
+                        zstd::DEFAULT_COMPRESSION_LEVEL,
+                    )?;
+
+                    Ok(())
+                }
+
+                fn decode(buf: &[u8]) -> Result<Self, $crate::columns::CodecError> {
+                    if !buf.starts_with(&$crate::columns::ZSTD_MAGIC_NUMBER) {
+                        return Ok($crate::columns::bincode_codec().deserialize(buf)?);
+                    }
+
+                    let bytes = zstd::stream::decode_all(buf)
+                        .map_err($crate::columns::CodecError::Decompression)?;
+
+                    Ok($crate::columns::bincode_codec().deserialize(&bytes)?)
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_codec_using_compressed_bincode_for;
+
+/// Plain bincode encoding of a value encoded with the compressed codec,
+/// decompressing it unless it predates the compression.
+pub fn decompressed(encoded: Vec<u8>) -> Result<Vec<u8>, CodecError> {
+    if !encoded.starts_with(&ZSTD_MAGIC_NUMBER) {
+        return Ok(encoded);
+    }
+
+    zstd::stream::decode_all(encoded.as_slice()).map_err(CodecError::Decompression)
+}
+
+/// Compressed encoding of a value encoded with the compressed codec,
+/// compressing it only if it predates the compression.
+pub fn compressed(encoded: Vec<u8>) -> Result<Vec<u8>, CodecError> {
+    if encoded.starts_with(&ZSTD_MAGIC_NUMBER) {
+        return Ok(encoded);
+    }
+
+    Ok(zstd::stream::encode_all(
+        encoded.as_slice(),
+        zstd::DEFAULT_COMPRESSION_LEVEL,
+    )?)
+}
+
 pub trait ColumnSchema {
     type Key: Codec;
     type Value: Codec;
@@ -107,25 +191,99 @@ pub trait ColumnSchema {
     const COLUMN_FAMILY_NAME: &'static str;
 }
 
+/// Decode the raw entry of the given column with the codecs of its key and
+/// value, returning `None` when the column is unknown.
+pub(crate) fn check_entry(
+    column: &str,
+    key: &[u8],
+    value: &[u8],
+) -> Option<Result<(), CodecError>> {
+    fn decode<C: ColumnSchema>(key: &[u8], value: &[u8]) -> Result<(), CodecError> {
+        C::Key::decode(key)?;
+        C::Value::decode(value)?;
+
+        Ok(())
+    }
+
+    macro_rules! check_columns {
+        ($($column:ty),* $(,)?) => {
+            $(
+                if column == <$column as ColumnSchema>::COLUMN_FAMILY_NAME {
+                    return Some(decode::<$column>(key, value));
+                }
+            )*
+        };
+    }
+
+    check_columns!(
+        certificate_header::CertificateHeaderColumn,
+        certificate_per_network::CertificatePerNetworkColumn,
+        latest_settled_certificate_per_network::LatestSettledCertificatePerNetworkColumn,
+        latest_pending_certificate_per_network::LatestPendingCertificatePerNetworkColumn,
+        latest_proven_certificate_per_network::LatestProvenCertificatePerNetworkColumn,
+        metadata::MetadataColumn,
+        local_exit_tree_per_network::LocalExitTreePerNetworkColumn,
+        balance_tree_per_network::BalanceTreePerNetworkColumn,
+        nullifier_tree_per_network::NullifierTreePerNetworkColumn,
+        network_info::NetworkInfoColumn,
+        settlement_attempts_per_certificate::SettlementAttemptsPerCertificateColumn,
+        settlement_sender_per_tx::SettlementSenderPerTxColumn,
+        reverted_settlements_per_epoch::RevertedSettlementsPerEpochColumn,
+        settlement_costs_per_network::SettlementCostsPerNetworkColumn,
+        settled_roots_per_network::SettledRootsPerNetworkColumn,
+        callback_per_certificate::CallbackPerCertificateColumn,
+        audit_log_per_certificate::AuditLogPerCertificateColumn,
+        event_log::EventLogColumn,
+        api_key_usage::ApiKeyUsageColumn,
+        pending_queue::PendingQueueColumn,
+        proof_per_certificate::ProofPerCertificateColumn,
+        proof_cache_per_certificate::ProofCachePerCertificateColumn,
+        submitted_proof_per_certificate::SubmittedProofPerCertificateColumn,
+        verified_proof_per_certificate::VerifiedProofPerCertificateColumn,
+        debug_certificates::DebugCertificatesColumn,
+        epochs::certificates::CertificatePerIndexColumn,
+        epochs::metadata::PerEpochMetadataColumn,
+        epochs::proofs::ProofPerIndexColumn,
+        epochs::start_checkpoint::StartCheckpointColumn,
+        epochs::end_checkpoint::EndCheckpointColumn,
+    );
+
+    None
+}
+
 // State
 pub(crate) mod balance_tree_per_network;
 pub(crate) mod certificate_per_network;
 pub(crate) mod local_exit_tree_per_network;
 pub(crate) mod network_info;
 pub(crate) mod nullifier_tree_per_network;
+pub(crate) mod settled_roots_per_network;
+pub(crate) mod settlement_costs_per_network;
 
 // Pending
+pub mod certified_roots_per_certificate;
 pub(crate) mod pending_queue;
+pub mod proof_cache_per_certificate;
 pub(crate) mod proof_per_certificate;
+pub(crate) mod submitted_proof_per_certificate;
+pub(crate) mod verified_proof_per_certificate;
 
 // Metadata
+pub mod api_key_usage;
+pub mod audit_log_per_certificate;
+pub mod callback_per_certificate;
 pub(crate) mod certificate_header;
+pub mod event_log;
 pub mod latest_pending_certificate_per_network;
 pub mod latest_proven_certificate_per_network;
 pub mod latest_settled_certificate_per_network;
 pub(crate) mod metadata;
+pub(crate) mod reverted_settlements_per_epoch;
+pub mod settlement_attempts_per_certificate;
+pub(crate) mod settlement_sender_per_tx;
 
 // Debug
+pub mod certification_failure_per_certificate;
 pub(crate) mod debug_certificates;
 
 // PerEpoch
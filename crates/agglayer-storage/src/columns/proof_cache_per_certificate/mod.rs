@@ -0,0 +1,40 @@
+use agglayer_types::{CertificateId, Proof};
+use pessimistic_proof::local_state::StateCommitment;
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, PROOF_CACHE_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family caching the last proof generated for one certificate, along
+/// with the roots of the network state it was generated from.
+///
+/// Unlike the generated proofs, the cached proofs are kept when a certificate
+/// fails, so that a retry from the same state can reuse the proof.
+///
+/// ## Column definition
+///
+/// | key             | value         |
+/// | --              | --            |
+/// | `CertificateId` | `CachedProof` |
+pub struct ProofCachePerCertificateColumn;
+
+/// A proof along with the initial roots it was generated from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedProof {
+    pub initial_roots: StateCommitment,
+    pub proof: Proof,
+}
+
+pub type Key = CertificateId;
+pub type Value = CachedProof;
+
+crate::columns::impl_codec_using_compressed_bincode_for!(Value);
+
+impl ColumnSchema for ProofCachePerCertificateColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = PROOF_CACHE_PER_CERTIFICATE_CF;
+}
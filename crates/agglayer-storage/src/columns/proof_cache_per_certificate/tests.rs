@@ -0,0 +1,35 @@
+use agglayer_types::{Digest, Proof};
+use pessimistic_proof::local_state::StateCommitment;
+
+use super::{CachedProof, Value};
+use crate::columns::{Codec as _, ZSTD_MAGIC_NUMBER};
+
+#[test]
+fn can_parse_value() {
+    let initial_roots = StateCommitment {
+        exit_root: Digest([1; 32]),
+        ler_leaf_count: 2,
+        balance_root: Digest([3; 32]),
+        nullifier_root: Digest([4; 32]),
+    };
+    let value = CachedProof {
+        initial_roots: initial_roots.clone(),
+        proof: Proof::dummy(),
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value.initial_roots, initial_roots);
+    assert!(matches!(expected_value.proof, Proof::SP1(_)));
+
+    // The cached proof is stored compressed.
+    assert!(encoded.starts_with(&ZSTD_MAGIC_NUMBER));
+    let decompressed = zstd::stream::decode_all(&encoded[..]).unwrap();
+
+    // exit_root
+    assert_eq!(decompressed[..32], [1; 32]);
+    // ler_leaf_count
+    assert_eq!(decompressed[32..36], [0, 0, 0, 2]);
+}
@@ -3,8 +3,9 @@ use serde::Deserialize;
 use sp1_sdk::SP1ProofWithPublicValues;
 
 use crate::columns::{
+    bincode_codec, compressed, decompressed,
     proof_per_certificate::{CertificateId, Proof},
-    Codec as _,
+    Codec as _, ZSTD_MAGIC_NUMBER,
 };
 
 #[test]
@@ -37,6 +38,45 @@ fn can_parse_value() {
     assert!(matches!(expected_value, Proof::SP1(_)));
 }
 
+#[test]
+fn value_is_stored_compressed() {
+    let Proof::SP1(mut proof) = Proof::dummy();
+    proof.public_values = sp1_sdk::SP1PublicValues::from(&[0x2a; 4096]);
+    let value = Proof::SP1(proof);
+    let uncompressed = bincode_codec().serialize(&value).unwrap();
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    assert!(encoded.starts_with(&ZSTD_MAGIC_NUMBER));
+    assert!(encoded.len() < uncompressed.len());
+
+    let decoded = Proof::decode(&encoded[..]).expect("Unable to decode value");
+    assert_eq!(bincode_codec().serialize(&decoded).unwrap(), uncompressed);
+}
+
+#[test]
+fn can_parse_uncompressed_value() {
+    let uncompressed = bincode_codec().serialize(&Proof::dummy()).unwrap();
+
+    let value = Proof::decode(&uncompressed[..]).expect("Unable to decode value");
+
+    assert!(matches!(value, Proof::SP1(_)));
+}
+
+#[test]
+fn encoded_value_converts_between_plain_and_compressed() {
+    let uncompressed = bincode_codec().serialize(&Proof::dummy()).unwrap();
+    let encoded = Proof::dummy().encode().expect("Unable to encode value");
+
+    assert_eq!(decompressed(encoded.clone()).unwrap(), uncompressed);
+    assert_eq!(decompressed(uncompressed.clone()).unwrap(), uncompressed);
+
+    assert_eq!(compressed(encoded.clone()).unwrap(), encoded);
+    let recompressed = compressed(uncompressed.clone()).unwrap();
+    assert!(recompressed.starts_with(&ZSTD_MAGIC_NUMBER));
+    assert_eq!(decompressed(recompressed).unwrap(), uncompressed);
+}
+
 #[derive(Deserialize)]
 struct SP1ProofWithPublicValuesV3 {
     pub proof: sp1_sdk::SP1Proof,
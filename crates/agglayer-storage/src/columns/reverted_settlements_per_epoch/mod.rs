@@ -0,0 +1,28 @@
+use agglayer_types::{CertificateId, EpochNumber};
+
+use super::{ColumnSchema, REVERTED_SETTLEMENTS_PER_EPOCH_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the certificates whose settlement transaction reverted
+/// during an epoch, in order, until they are re-packed.
+///
+/// ## Column definition
+///
+/// | key           | value                |
+/// | --            | --                   |
+/// | `EpochNumber` | `Vec<CertificateId>` |
+pub struct RevertedSettlementsPerEpochColumn;
+
+pub type Key = EpochNumber;
+pub type Value = Vec<CertificateId>;
+
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for RevertedSettlementsPerEpochColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = REVERTED_SETTLEMENTS_PER_EPOCH_CF;
+}
@@ -0,0 +1,35 @@
+use agglayer_types::{CertificateId, EpochNumber};
+
+use super::{Key, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_key() {
+    let key = EpochNumber::new(3);
+
+    let encoded = key.encode().expect("Unable to encode key");
+
+    let expected_key = Key::decode(&encoded[..]).expect("Unable to decode key");
+
+    assert_eq!(expected_key, key);
+    assert_eq!(encoded[..], [0, 0, 0, 0, 0, 0, 0, 3]);
+}
+
+#[test]
+fn can_parse_value() {
+    let value = vec![
+        CertificateId::new([1; 32].into()),
+        CertificateId::new([2; 32].into()),
+    ];
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+
+    // length
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 0, 2]);
+    // first certificate id
+    assert_eq!(encoded[8..40], [1; 32]);
+}
@@ -0,0 +1,36 @@
+use agglayer_types::Height;
+use pessimistic_proof::local_state::StateCommitment;
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, SETTLED_ROOTS_PER_NETWORK_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the roots of the local network state of each network
+/// once its certificate at a given height is settled.
+///
+/// ## Column definition
+///
+/// | key                     | value             |
+/// | --                      | --                |
+/// | (`NetworkId`, `Height`) | `StateCommitment` |
+pub struct SettledRootsPerNetworkColumn;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Key {
+    pub(crate) network_id: u32,
+    pub(crate) height: Height,
+}
+
+pub type Value = StateCommitment;
+
+crate::columns::impl_codec_using_bincode_for!(Key);
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for SettledRootsPerNetworkColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = SETTLED_ROOTS_PER_NETWORK_CF;
+}
@@ -0,0 +1,40 @@
+use agglayer_types::{Digest, Height};
+use pessimistic_proof::local_state::StateCommitment;
+
+use super::{Key, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_key() {
+    let key = Key {
+        network_id: 1,
+        height: Height::new(200),
+    };
+
+    let encoded = key.encode().expect("Unable to encode key");
+
+    let expected_key = Key::decode(&encoded[..]).expect("Unable to decode key");
+
+    assert_eq!(expected_key, key);
+
+    // network_id
+    assert_eq!(encoded[..4], [0, 0, 0, 1]);
+    // height
+    assert_eq!(encoded[4..12], [0, 0, 0, 0, 0, 0, 0, 200]);
+}
+
+#[test]
+fn can_parse_value() {
+    let value = StateCommitment {
+        exit_root: Digest([1; 32]),
+        ler_leaf_count: 3,
+        balance_root: Digest([2; 32]),
+        nullifier_root: Digest([3; 32]),
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+}
@@ -0,0 +1,40 @@
+use agglayer_types::{CertificateId, SettlementTxHash};
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, SETTLEMENT_ATTEMPTS_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the settlement transactions submitted for one
+/// certificate, in submission order.
+///
+/// ## Column definition
+///
+/// | key             | value                    |
+/// | --              | --                       |
+/// | `CertificateId` | `Vec<SettlementAttempt>` |
+pub struct SettlementAttemptsPerCertificateColumn;
+
+/// One submission of a settlement transaction.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SettlementAttempt {
+    pub settlement_tx_hash: SettlementTxHash,
+    /// The nonce and fees of the transaction, if they could be fetched from
+    /// the L1.
+    pub nonce: Option<u64>,
+    pub max_fee_per_gas: Option<u128>,
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+pub type Key = CertificateId;
+pub type Value = Vec<SettlementAttempt>;
+
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for SettlementAttemptsPerCertificateColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = SETTLEMENT_ATTEMPTS_PER_CERTIFICATE_CF;
+}
@@ -0,0 +1,35 @@
+use agglayer_types::{Digest, SettlementTxHash};
+
+use super::{SettlementAttempt, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_value() {
+    let value = vec![
+        SettlementAttempt {
+            settlement_tx_hash: SettlementTxHash::new(Digest([1; 32])),
+            nonce: Some(7),
+            max_fee_per_gas: Some(100),
+            max_priority_fee_per_gas: None,
+        },
+        SettlementAttempt {
+            settlement_tx_hash: SettlementTxHash::new(Digest([2; 32])),
+            nonce: None,
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+        },
+    ];
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+
+    // length
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 0, 2]);
+    // settlement_tx_hash
+    assert_eq!(encoded[8..40], [1; 32]);
+    // nonce
+    assert_eq!(encoded[40..49], [1, 0, 0, 0, 0, 0, 0, 0, 7]);
+}
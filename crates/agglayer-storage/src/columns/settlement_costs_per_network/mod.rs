@@ -0,0 +1,34 @@
+use agglayer_types::{EpochNumber, SettlementCosts};
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnSchema, SETTLEMENT_COSTS_PER_NETWORK_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the settlement costs per network per epoch.
+///
+/// ## Column definition
+///
+/// | key                          | value             |
+/// | --                           | --                |
+/// | (`NetworkId`, `EpochNumber`) | `SettlementCosts` |
+pub struct SettlementCostsPerNetworkColumn;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Key {
+    pub(crate) network_id: u32,
+    pub(crate) epoch_number: EpochNumber,
+}
+
+pub type Value = SettlementCosts;
+
+crate::columns::impl_codec_using_bincode_for!(Key);
+crate::columns::impl_codec_using_bincode_for!(Value);
+
+impl ColumnSchema for SettlementCostsPerNetworkColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = SETTLEMENT_COSTS_PER_NETWORK_CF;
+}
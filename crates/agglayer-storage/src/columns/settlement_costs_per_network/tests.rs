@@ -0,0 +1,46 @@
+use agglayer_types::{EpochNumber, SettlementCosts};
+
+use super::{Key, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_key() {
+    let key = Key {
+        network_id: 1,
+        epoch_number: EpochNumber::new(200),
+    };
+
+    let encoded = key.encode().expect("Unable to encode key");
+
+    let expected_key = Key::decode(&encoded[..]).expect("Unable to decode key");
+
+    assert_eq!(expected_key, key);
+
+    // network_id
+    assert_eq!(encoded[..4], [0, 0, 0, 1]);
+    // epoch_number
+    assert_eq!(encoded[4..12], [0, 0, 0, 0, 0, 0, 0, 200]);
+}
+
+#[test]
+fn can_parse_value() {
+    let value = SettlementCosts {
+        settled_certificates: 2,
+        gas_used: 600_000,
+        fees: 3,
+    };
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    let expected_value = Value::decode(&encoded[..]).expect("Unable to decode value");
+
+    assert_eq!(expected_value, value);
+
+    // settled_certificates
+    assert_eq!(encoded[..8], [0, 0, 0, 0, 0, 0, 0, 2]);
+    // fees
+    assert_eq!(
+        encoded[16..32],
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 3]
+    );
+}
@@ -0,0 +1,28 @@
+use agglayer_types::{Address, SettlementTxHash};
+
+use super::{ColumnSchema, SETTLEMENT_SENDER_PER_TX_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the account which sent each settlement transaction,
+/// recording which key signed it across the rotations of the settlement key.
+///
+/// ## Column definition
+///
+/// | key                | value     |
+/// | --                 | --        |
+/// | `SettlementTxHash` | `Address` |
+pub struct SettlementSenderPerTxColumn;
+
+pub type Key = SettlementTxHash;
+pub type Value = Address;
+
+crate::columns::impl_codec_using_bincode_for!(Key, Value);
+
+impl ColumnSchema for SettlementSenderPerTxColumn {
+    type Key = Key;
+    type Value = Value;
+
+    const COLUMN_FAMILY_NAME: &'static str = SETTLEMENT_SENDER_PER_TX_CF;
+}
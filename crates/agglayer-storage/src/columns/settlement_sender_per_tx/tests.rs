@@ -0,0 +1,29 @@
+use agglayer_types::{Address, Digest, SettlementTxHash};
+
+use super::{Key, Value};
+use crate::columns::Codec as _;
+
+#[test]
+fn can_parse_key() {
+    let key = SettlementTxHash::new(Digest([1; 32]));
+
+    let encoded = key.encode().expect("Unable to encode key");
+
+    assert_eq!(encoded, [1; 32]);
+    assert_eq!(
+        Key::decode(&encoded[..]).expect("Unable to decode key"),
+        key
+    );
+}
+
+#[test]
+fn can_parse_value() {
+    let value = Address::new([0x11; 20]);
+
+    let encoded = value.encode().expect("Unable to encode value");
+
+    assert_eq!(
+        Value::decode(&encoded[..]).expect("Unable to decode value"),
+        value
+    );
+}
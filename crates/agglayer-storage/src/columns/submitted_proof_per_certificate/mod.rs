@@ -0,0 +1,23 @@
+use agglayer_types::{CertificateId, Proof};
+
+use super::{ColumnSchema, SUBMITTED_PROOF_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family for the proofs generated outside of the agglayer, submitted
+/// along with a pending certificate to skip its local proving.
+///
+/// ## Column definition
+///
+/// | key             | value   |
+/// | --              | --      |
+/// | `CertificateId` | `Proof` |
+pub struct SubmittedProofPerCertificateColumn;
+
+impl ColumnSchema for SubmittedProofPerCertificateColumn {
+    type Key = CertificateId;
+    type Value = Proof;
+
+    const COLUMN_FAMILY_NAME: &'static str = SUBMITTED_PROOF_PER_CERTIFICATE_CF;
+}
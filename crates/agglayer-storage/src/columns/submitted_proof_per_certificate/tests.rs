@@ -0,0 +1,29 @@
+use agglayer_types::{CertificateId, Proof};
+
+use super::SubmittedProofPerCertificateColumn;
+use crate::{
+    storage::{pending_db_cf_definitions, DB},
+    tests::TempDBDir,
+};
+
+#[test]
+fn can_store_and_remove_a_submitted_proof() {
+    let tmp = TempDBDir::new();
+    let db = DB::open_cf(tmp.path.as_path(), pending_db_cf_definitions()).unwrap();
+    let certificate_id = CertificateId::new([1; 32].into());
+
+    db.put::<SubmittedProofPerCertificateColumn>(&certificate_id, &Proof::dummy())
+        .unwrap();
+    assert!(matches!(
+        db.get::<SubmittedProofPerCertificateColumn>(&certificate_id)
+            .unwrap(),
+        Some(Proof::SP1(_))
+    ));
+
+    db.delete::<SubmittedProofPerCertificateColumn>(&certificate_id)
+        .unwrap();
+    assert!(db
+        .get::<SubmittedProofPerCertificateColumn>(&certificate_id)
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,26 @@
+use agglayer_types::{CertificateId, Digest};
+
+use super::{ColumnSchema, VERIFIED_PROOF_PER_CERTIFICATE_CF};
+
+#[cfg(test)]
+mod tests;
+
+/// Column family marking the proofs which were verified, so that a proof
+/// reused after a restart or a retry isn't verified again.
+///
+/// The marker is the digest of the verified proof, so that it doesn't apply
+/// to another proof of the same certificate.
+///
+/// ## Column definition
+///
+/// | key             | value    |
+/// | --              | --       |
+/// | `CertificateId` | `Digest` |
+pub struct VerifiedProofPerCertificateColumn;
+
+impl ColumnSchema for VerifiedProofPerCertificateColumn {
+    type Key = CertificateId;
+    type Value = Digest;
+
+    const COLUMN_FAMILY_NAME: &'static str = VERIFIED_PROOF_PER_CERTIFICATE_CF;
+}
@@ -0,0 +1,32 @@
+use agglayer_types::{CertificateId, Digest, Proof};
+use pessimistic_proof::local_state::StateCommitment;
+
+use crate::{
+    stores::{pending::PendingStore, PendingCertificateReader as _, PendingCertificateWriter as _},
+    tests::TempDBDir,
+};
+
+#[test]
+fn verified_proof_is_dropped_along_with_the_cached_proof() {
+    let tmp = TempDBDir::new();
+    let store = PendingStore::new_with_path(tmp.path.as_path()).unwrap();
+    let certificate_id = CertificateId::new([1; 32].into());
+
+    store
+        .insert_cached_proof(
+            &certificate_id,
+            &StateCommitment::default(),
+            &Proof::dummy(),
+        )
+        .unwrap();
+    store
+        .set_verified_proof(&certificate_id, &Digest([2; 32]))
+        .unwrap();
+    assert_eq!(
+        store.get_verified_proof(&certificate_id).unwrap(),
+        Some(Digest([2; 32]))
+    );
+
+    store.remove_cached_proof(&certificate_id).unwrap();
+    assert!(store.get_verified_proof(&certificate_id).unwrap().is_none());
+}
@@ -0,0 +1,114 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{RwLock, RwLockReadGuard},
+};
+
+use super::{Backend, Direction, RawIterator, WriteBatch, WriteOp};
+use crate::storage::DBError;
+
+type Column = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Backend holding the entries in memory, which are lost once dropped.
+pub struct MemoryBackend {
+    column_families: Vec<String>,
+    columns: RwLock<HashMap<String, Column>>,
+}
+
+impl MemoryBackend {
+    /// Create an empty backend with the given column families.
+    pub fn new<S: Into<String>>(column_families: impl IntoIterator<Item = S>) -> Self {
+        let column_families: Vec<String> = column_families.into_iter().map(Into::into).collect();
+        let columns = column_families
+            .iter()
+            .map(|name| (name.clone(), Column::new()))
+            .collect();
+
+        Self {
+            column_families,
+            columns: RwLock::new(columns),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Column>> {
+        self.columns
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn column_families(&self) -> &[String] {
+        &self.column_families
+    }
+
+    fn get(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, DBError> {
+        let columns = self.read();
+        let column = columns.get(column).ok_or(DBError::ColumnFamilyNotFound)?;
+
+        Ok(column.get(key).cloned())
+    }
+
+    fn multi_get(&self, column: &str, keys: &[Vec<u8>]) -> Result<Vec<Option<Vec<u8>>>, DBError> {
+        let columns = self.read();
+        let column = columns.get(column).ok_or(DBError::ColumnFamilyNotFound)?;
+
+        Ok(keys.iter().map(|key| column.get(key).cloned()).collect())
+    }
+
+    fn write(&self, batch: WriteBatch) -> Result<(), DBError> {
+        let mut columns = self
+            .columns
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let ops = batch.into_ops();
+        // Check every column beforehand for the batch to be applied atomically.
+        for op in &ops {
+            let (WriteOp::Put { column, .. } | WriteOp::Delete { column, .. }) = op;
+            if !columns.contains_key(*column) {
+                return Err(DBError::ColumnFamilyNotFound);
+            }
+        }
+
+        for op in ops {
+            match op {
+                WriteOp::Put { column, key, value } => {
+                    if let Some(column) = columns.get_mut(column) {
+                        column.insert(key, value);
+                    }
+                }
+                WriteOp::Delete { column, key } => {
+                    if let Some(column) = columns.get_mut(column) {
+                        column.remove(&key);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn iter(
+        &self,
+        column: &str,
+        lower_bound: Option<Vec<u8>>,
+        direction: Direction,
+    ) -> Result<RawIterator<'_>, DBError> {
+        let columns = self.read();
+        let column = columns.get(column).ok_or(DBError::ColumnFamilyNotFound)?;
+
+        let mut entries: Vec<_> = column
+            .range(lower_bound.unwrap_or_default()..)
+            .map(|(key, value)| Ok((key.clone(), value.clone())))
+            .collect();
+        if direction == Direction::Reverse {
+            entries.reverse();
+        }
+
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn flush(&self) -> Result<(), DBError> {
+        Ok(())
+    }
+}
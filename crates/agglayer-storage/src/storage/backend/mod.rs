@@ -0,0 +1,111 @@
+//! Backends holding the raw entries of the column families.
+//!
+//! The [`DB`](super::DB) encodes and decodes the entries of the columns, and
+//! delegates their storage to a [`Backend`]: RocksDB for the node, or the
+//! in-memory one for the tests and the lightweight deployments.
+
+use super::DBError;
+
+mod memory;
+mod rocksdb;
+
+pub use self::{memory::MemoryBackend, rocksdb::RocksDbBackend};
+
+/// Direction in which the entries of a column are iterated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// In ascending order of the encoded keys.
+    Forward,
+    /// In descending order of the encoded keys.
+    Reverse,
+}
+
+/// Iterator over the raw entries of a column.
+pub type RawIterator<'a> = Box<dyn Iterator<Item = Result<(Vec<u8>, Vec<u8>), DBError>> + 'a>;
+
+/// A write to a column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WriteOp {
+    Put {
+        column: &'static str,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Delete {
+        column: &'static str,
+        key: Vec<u8>,
+    },
+}
+
+/// Writes to the columns of a database, applied atomically.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WriteBatch {
+    ops: Vec<WriteOp>,
+}
+
+impl WriteBatch {
+    /// Put the value for the given key of the column.
+    pub fn put(&mut self, column: &'static str, key: Vec<u8>, value: Vec<u8>) {
+        self.ops.push(WriteOp::Put { column, key, value });
+    }
+
+    /// Delete the given key of the column.
+    pub fn delete(&mut self, column: &'static str, key: Vec<u8>) {
+        self.ops.push(WriteOp::Delete { column, key });
+    }
+
+    /// The writes, in the order they were added.
+    pub fn into_ops(self) -> Vec<WriteOp> {
+        self.ops
+    }
+}
+
+/// Storage of the raw entries of the column families of a database.
+pub trait Backend: Send + Sync {
+    /// Names of the column families.
+    fn column_families(&self) -> &[String];
+
+    /// Get the value of the given key of the column.
+    fn get(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, DBError>;
+
+    /// Get the values of the given keys of the column, from a consistent view
+    /// of the database.
+    fn multi_get(&self, column: &str, keys: &[Vec<u8>]) -> Result<Vec<Option<Vec<u8>>>, DBError>;
+
+    /// Apply the writes atomically.
+    fn write(&self, batch: WriteBatch) -> Result<(), DBError>;
+
+    /// Iterate over the entries of the column whose key is at or above the
+    /// lower bound, if any, in the given direction.
+    fn iter(
+        &self,
+        column: &str,
+        lower_bound: Option<Vec<u8>>,
+        direction: Direction,
+    ) -> Result<RawIterator<'_>, DBError>;
+
+    /// Persist the writes applied so far.
+    fn flush(&self) -> Result<(), DBError>;
+
+    /// Compact the whole key range of the column, dropping the deleted and
+    /// overwritten entries.
+    fn compact(&self, _column: &str) -> Result<(), DBError> {
+        Ok(())
+    }
+
+    /// Catch up with the writes applied by another process, for the backends
+    /// following a primary instance.
+    fn catch_up(&self) -> Result<(), DBError> {
+        Ok(())
+    }
+
+    /// Record the metrics specific to the backend, labelled with the given
+    /// database name.
+    fn record_metrics(&self, _db: &'static str) {}
+
+    /// The underlying RocksDB instance, needed by the features specific to
+    /// RocksDB such as the backups.
+    fn as_rocksdb(&self) -> Option<&::rocksdb::DB> {
+        None
+    }
+}
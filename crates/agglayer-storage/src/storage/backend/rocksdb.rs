@@ -0,0 +1,321 @@
+use std::path::Path;
+
+use agglayer_config::storage::resources::DbResourcesConfig;
+use agglayer_telemetry::storage as metrics;
+use rocksdb::{
+    ColumnFamily, ColumnFamilyDescriptor, DBRawIterator, Options, ReadOptions, WriteOptions,
+};
+use tracing::warn;
+
+use super::{Backend, Direction, RawIterator, WriteBatch, WriteOp};
+use crate::storage::DBError;
+
+/// RocksDB properties reported for the whole database.
+const DB_PROPERTIES: [&str; 5] = [
+    "rocksdb.compaction-pending",
+    "rocksdb.num-running-compactions",
+    "rocksdb.is-write-stopped",
+    "rocksdb.actual-delayed-write-rate",
+    "rocksdb.background-errors",
+];
+
+/// RocksDB properties reported for each column family.
+const COLUMN_PROPERTIES: [&str; 4] = [
+    "rocksdb.estimate-num-keys",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.estimate-pending-compaction-bytes",
+    "rocksdb.cur-size-all-mem-tables",
+];
+
+/// Number of levels for which the SST files are counted.
+const SST_LEVELS: usize = 7;
+
+/// Backend holding the entries in a RocksDB instance.
+pub struct RocksDbBackend {
+    rocksdb: rocksdb::DB,
+    default_write_options: Option<WriteOptions>,
+    /// Names of the column families the database was opened with.
+    column_families: Vec<String>,
+    /// Whether the instance is a secondary of a primary one.
+    secondary: bool,
+}
+
+impl RocksDbBackend {
+    /// Open a new RocksDB instance at the given path with some column families.
+    pub fn open(path: &Path, cfs: Vec<ColumnFamilyDescriptor>) -> Result<Self, DBError> {
+        Self::open_with_resources(path, cfs, &DbResourcesConfig::default())
+    }
+
+    /// Open a new RocksDB instance at the given path with some column
+    /// families, limited to the given resources.
+    pub fn open_with_resources(
+        path: &Path,
+        cfs: Vec<ColumnFamilyDescriptor>,
+        resources: &DbResourcesConfig,
+    ) -> Result<Self, DBError> {
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        apply_resources(&mut options, resources);
+
+        let mut writeopts = WriteOptions::default();
+        writeopts.set_sync(true);
+
+        let column_families = cfs.iter().map(|cf| cf.name().to_string()).collect();
+
+        Ok(Self {
+            rocksdb: rocksdb::DB::open_cf_descriptors(&options, path, cfs)?,
+            default_write_options: Some(writeopts),
+            column_families,
+            secondary: false,
+        })
+    }
+
+    /// Open a RocksDB instance in read-only mode at the given path with some
+    /// column families. This prevents concurrency issues when multiple
+    /// processes need to read from the database.
+    pub fn open_readonly(path: &Path, cfs: Vec<ColumnFamilyDescriptor>) -> Result<Self, DBError> {
+        Self::open_readonly_with_resources(path, cfs, &DbResourcesConfig::default())
+    }
+
+    /// Open a RocksDB instance in read-only mode at the given path with some
+    /// column families, limited to the given resources.
+    pub fn open_readonly_with_resources(
+        path: &Path,
+        cfs: Vec<ColumnFamilyDescriptor>,
+        resources: &DbResourcesConfig,
+    ) -> Result<Self, DBError> {
+        let mut options = Options::default();
+        options.create_if_missing(false); // Don't create if missing in readonly mode
+        options.create_missing_column_families(false); // Don't create missing column families
+        apply_resources(&mut options, resources);
+
+        let column_families = cfs.iter().map(|cf| cf.name().to_string()).collect();
+
+        Ok(Self {
+            rocksdb: rocksdb::DB::open_cf_descriptors_read_only(&options, path, cfs, false)?,
+            default_write_options: None,
+            column_families,
+            secondary: false,
+        })
+    }
+
+    /// Open a RocksDB instance as a secondary of the primary one at the given
+    /// path, keeping its own info logs in the secondary path. Unlike a
+    /// read-only instance, a secondary one can catch up with the writes
+    /// applied by the primary after it was opened.
+    pub fn open_secondary(
+        primary_path: &Path,
+        secondary_path: &Path,
+        cfs: Vec<ColumnFamilyDescriptor>,
+    ) -> Result<Self, DBError> {
+        let mut options = Options::default();
+        options.create_if_missing(false);
+        options.create_missing_column_families(false);
+        // Secondary instances need to keep all the files open to catch up.
+        options.set_max_open_files(-1);
+
+        let column_families = cfs.iter().map(|cf| cf.name().to_string()).collect();
+
+        Ok(Self {
+            rocksdb: rocksdb::DB::open_cf_descriptors_as_secondary(
+                &options,
+                primary_path,
+                secondary_path,
+                cfs,
+            )?,
+            default_write_options: None,
+            column_families,
+            secondary: true,
+        })
+    }
+
+    fn write_options(&self) -> Result<&WriteOptions, DBError> {
+        self.default_write_options
+            .as_ref()
+            .ok_or(DBError::ReadOnlyMode)
+    }
+
+    fn cf(&self, column: &str) -> Result<&ColumnFamily, DBError> {
+        self.rocksdb
+            .cf_handle(column)
+            .ok_or(DBError::ColumnFamilyNotFound)
+    }
+}
+
+impl Backend for RocksDbBackend {
+    fn column_families(&self) -> &[String] {
+        &self.column_families
+    }
+
+    fn get(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, DBError> {
+        Ok(self.rocksdb.get_cf(self.cf(column)?, key)?)
+    }
+
+    fn multi_get(&self, column: &str, keys: &[Vec<u8>]) -> Result<Vec<Option<Vec<u8>>>, DBError> {
+        let cf = self.cf(column)?;
+        let snapshot = self.rocksdb.snapshot();
+
+        snapshot
+            .multi_get_cf(keys.iter().map(|key| (cf, key)))
+            .into_iter()
+            .map(|value| value.map_err(DBError::from))
+            .collect()
+    }
+
+    fn write(&self, batch: WriteBatch) -> Result<(), DBError> {
+        let write_options = self.write_options()?;
+
+        let mut rocksdb_batch = rocksdb::WriteBatch::default();
+        for op in batch.into_ops() {
+            match op {
+                WriteOp::Put { column, key, value } => {
+                    rocksdb_batch.put_cf(self.cf(column)?, key, value)
+                }
+                WriteOp::Delete { column, key } => rocksdb_batch.delete_cf(self.cf(column)?, key),
+            }
+        }
+
+        Ok(self.rocksdb.write_opt(rocksdb_batch, write_options)?)
+    }
+
+    fn iter(
+        &self,
+        column: &str,
+        lower_bound: Option<Vec<u8>>,
+        direction: Direction,
+    ) -> Result<RawIterator<'_>, DBError> {
+        let cf = self.cf(column)?;
+
+        let mut opts = ReadOptions::default();
+        if let Some(lower_bound) = lower_bound {
+            opts.set_iterate_lower_bound(lower_bound);
+        }
+
+        let mut iter = self.rocksdb.raw_iterator_cf_opt(&cf, opts);
+        match direction {
+            Direction::Forward => iter.seek_to_first(),
+            Direction::Reverse => iter.seek_to_last(),
+        }
+
+        Ok(Box::new(RocksDbIterator {
+            iter,
+            direction,
+            done: false,
+        }))
+    }
+
+    fn flush(&self) -> Result<(), DBError> {
+        self.write_options()?;
+
+        for name in &self.column_families {
+            self.rocksdb.flush_cf(self.cf(name)?)?;
+        }
+        self.rocksdb.flush_wal(true)?;
+
+        Ok(())
+    }
+
+    fn compact(&self, column: &str) -> Result<(), DBError> {
+        self.write_options()?;
+        self.rocksdb
+            .compact_range_cf(self.cf(column)?, None::<&[u8]>, None::<&[u8]>);
+
+        Ok(())
+    }
+
+    fn catch_up(&self) -> Result<(), DBError> {
+        if self.secondary {
+            self.rocksdb.try_catch_up_with_primary()?;
+        }
+
+        Ok(())
+    }
+
+    fn record_metrics(&self, db: &'static str) {
+        for property in DB_PROPERTIES {
+            match self.rocksdb.property_int_value(property) {
+                Ok(Some(value)) => metrics::record_rocksdb_property(db, None, property, value),
+                Ok(None) => {}
+                Err(error) => warn!(?error, db, property, "Failed to read RocksDB property"),
+            }
+        }
+
+        for name in &self.column_families {
+            let Some(cf) = self.rocksdb.cf_handle(name) else {
+                continue;
+            };
+
+            for property in COLUMN_PROPERTIES {
+                match self.rocksdb.property_int_value_cf(&cf, property) {
+                    Ok(Some(value)) => {
+                        metrics::record_rocksdb_property(db, Some(name.as_str()), property, value)
+                    }
+                    Ok(None) => {}
+                    Err(error) => {
+                        warn!(?error, db, column = %name, property, "Failed to read RocksDB property")
+                    }
+                }
+            }
+
+            let sst_files = (0..SST_LEVELS)
+                .filter_map(|level| {
+                    let property = format!("rocksdb.num-files-at-level{level}");
+                    self.rocksdb
+                        .property_int_value_cf(&cf, property.as_str())
+                        .ok()
+                        .flatten()
+                })
+                .sum();
+            metrics::record_rocksdb_property(
+                db,
+                Some(name.as_str()),
+                "rocksdb.num-sst-files",
+                sst_files,
+            );
+        }
+    }
+
+    fn as_rocksdb(&self) -> Option<&rocksdb::DB> {
+        Some(&self.rocksdb)
+    }
+}
+
+/// Apply the database-wide resource limits to the options, the block cache
+/// being set on the column families.
+fn apply_resources(options: &mut Options, resources: &DbResourcesConfig) {
+    if let Some(max_open_files) = resources.max_open_files {
+        options.set_max_open_files(max_open_files);
+    }
+}
+
+/// Iterator over the raw entries of a column family, which yields the error
+/// stopping the iteration, such as a checksum mismatch, as its last item.
+struct RocksDbIterator<'a> {
+    iter: DBRawIterator<'a>,
+    direction: Direction,
+    done: bool,
+}
+
+impl Iterator for RocksDbIterator<'_> {
+    type Item = Result<(Vec<u8>, Vec<u8>), DBError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let Some((key, value)) = self.iter.item() else {
+            self.done = true;
+            return self.iter.status().err().map(|error| Err(error.into()));
+        };
+        let entry = (key.to_vec(), value.to_vec());
+
+        match self.direction {
+            Direction::Forward => self.iter.next(),
+            Direction::Reverse => self.iter.prev(),
+        }
+
+        Some(Ok(entry))
+    }
+}
@@ -143,16 +143,13 @@ impl BackupEngine {
                     error!("Failed to open backup engine for epoch db: {:?}", error);
                 }
                 Ok(mut engine) => {
-                    if let Err(error) = engine.create_new_backup_flush(&db.rocksdb, true) {
+                    if let Err(error) = backup_db(&mut engine, db) {
                         error!("Failed to create backup for epoch db: {:?}", error);
                     }
                 }
             }
         } else {
-            if let Err(error) = self
-                .state_engine
-                .create_new_backup_flush(&self.state_db.rocksdb, true)
-            {
+            if let Err(error) = backup_db(&mut self.state_engine, &self.state_db) {
                 error!("Failed to create backup for state db: {:?}", error);
             }
 
@@ -163,10 +160,7 @@ impl BackupEngine {
                 error!("Failed to purge old backup for state db: {:?}", error);
             }
 
-            if let Err(error) = self
-                .pending_engine
-                .create_new_backup_flush(&self.pending_db.rocksdb, true)
-            {
+            if let Err(error) = backup_db(&mut self.pending_engine, &self.pending_db) {
                 error!("Failed to create backup for pending db: {:?}", error);
             }
 
@@ -280,6 +274,17 @@ impl BackupEngine {
     }
 }
 
+/// Back up the database with the given engine, which requires it to be held
+/// by RocksDB.
+fn backup_db(engine: &mut RocksBackupEngine, db: &DB) -> Result<(), BackupError> {
+    let rocksdb = db
+        .backend()
+        .as_rocksdb()
+        .ok_or(BackupError::UnsupportedBackend)?;
+
+    Ok(engine.create_new_backup_flush(rocksdb, true)?)
+}
+
 impl Drop for BackupEngine {
     fn drop(&mut self) {
         info!("Waiting for all requested backups to complete");
@@ -0,0 +1,99 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::DB;
+
+/// Task periodically catching the secondary databases up with the writes
+/// applied by their primary instance.
+pub struct CatchUp {
+    dbs: Vec<(&'static str, Arc<DB>)>,
+    interval: Duration,
+}
+
+impl CatchUp {
+    /// Create a task for the given databases, each labelled with its name.
+    pub fn new(dbs: Vec<(&'static str, Arc<DB>)>, interval: Duration) -> Self {
+        Self { dbs, interval }
+    }
+
+    /// Catch every database up, reporting the failures.
+    pub fn catch_up(&self) {
+        for (name, db) in &self.dbs {
+            if let Err(error) = db.catch_up() {
+                warn!(
+                    ?error,
+                    db = name,
+                    "Failed to catch up with the primary storage"
+                );
+            }
+        }
+    }
+
+    /// Catch the databases up periodically until cancelled.
+    pub async fn run(self, cancellation_token: CancellationToken) {
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Storage catch up cancelled");
+                    break;
+                }
+                _ = interval.tick() => self.catch_up(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_types::{CertificateId, CertificateIndex, EpochNumber, Height};
+
+    use super::*;
+    use crate::{
+        columns::latest_settled_certificate_per_network::{
+            LatestSettledCertificatePerNetworkColumn, SettledCertificate,
+        },
+        storage::state_db_cf_definitions,
+        tests::TempDBDir,
+    };
+
+    #[test]
+    fn secondary_catches_up_with_primary() {
+        let primary_dir = TempDBDir::new();
+        let secondary_dir = TempDBDir::new();
+        let primary = DB::open_cf(primary_dir.path.as_path(), state_db_cf_definitions()).unwrap();
+        let secondary = Arc::new(
+            DB::open_cf_secondary(
+                primary_dir.path.as_path(),
+                secondary_dir.path.as_path(),
+                state_db_cf_definitions(),
+            )
+            .unwrap(),
+        );
+
+        let certificate = SettledCertificate(
+            CertificateId::new([0; 32].into()),
+            Height::ZERO,
+            EpochNumber::ZERO,
+            CertificateIndex::ZERO,
+        );
+        primary
+            .put::<LatestSettledCertificatePerNetworkColumn>(&1.into(), &certificate)
+            .unwrap();
+        assert!(secondary
+            .put::<LatestSettledCertificatePerNetworkColumn>(&2.into(), &certificate)
+            .is_err());
+
+        CatchUp::new(vec![("state", secondary.clone())], Duration::from_secs(1)).catch_up();
+        assert_eq!(
+            secondary
+                .get::<LatestSettledCertificatePerNetworkColumn>(&1.into())
+                .unwrap(),
+            Some(certificate)
+        );
+    }
+}
@@ -1,4 +1,5 @@
-use rocksdb::ColumnFamilyDescriptor;
+use agglayer_config::storage::resources::DbResourcesConfig;
+use rocksdb::{BlockBasedOptions, Cache, ColumnFamilyDescriptor};
 
 pub mod debug;
 pub mod epochs;
@@ -6,12 +7,30 @@ pub mod pending;
 pub mod state;
 
 fn default_db_cf_definitions(cfs: &[&'static str]) -> Vec<ColumnFamilyDescriptor> {
+    db_cf_definitions(cfs, &DbResourcesConfig::default())
+}
+
+/// Definitions for the given column families, sharing the block cache
+/// allotted to the database if any.
+pub(crate) fn db_cf_definitions(
+    cfs: &[&'static str],
+    resources: &DbResourcesConfig,
+) -> Vec<ColumnFamilyDescriptor> {
+    let block_cache = resources
+        .block_cache_size
+        .map(|size| Cache::new_lru_cache(size as usize));
+
     cfs.iter()
         .map(|cf| {
             let mut cfg = rocksdb::Options::default();
 
             cfg.set_compression_type(rocksdb::DBCompressionType::Lz4);
             cfg.create_if_missing(true);
+            if let Some(block_cache) = &block_cache {
+                let mut table_options = BlockBasedOptions::default();
+                table_options.set_block_cache(block_cache);
+                cfg.set_block_based_table_factory(&table_options);
+            }
 
             ColumnFamilyDescriptor::new(*cf, cfg)
         })
@@ -1,6 +1,9 @@
 use rocksdb::ColumnFamilyDescriptor;
 
-pub const CFS: [&str; 1] = [crate::columns::DEBUG_CERTIFICATES_CF];
+pub const CFS: [&str; 2] = [
+    crate::columns::DEBUG_CERTIFICATES_CF,
+    crate::columns::CERTIFICATION_FAILURE_PER_CERTIFICATE_CF,
+];
 
 /// Definitions for the column families in the debug storage.
 pub fn debug_db_cf_definitions() -> Vec<ColumnFamilyDescriptor> {
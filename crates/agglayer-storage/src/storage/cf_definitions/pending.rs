@@ -1,10 +1,14 @@
 use rocksdb::ColumnFamilyDescriptor;
 
-pub const CFS: [&str; 4] = [
+pub const CFS: [&str; 8] = [
     crate::columns::LATEST_PROVEN_CERTIFICATE_PER_NETWORK_CF,
     crate::columns::LATEST_PENDING_CERTIFICATE_PER_NETWORK_CF,
     crate::columns::PENDING_QUEUE_CF,
     crate::columns::PROOF_PER_CERTIFICATE_CF,
+    crate::columns::PROOF_CACHE_PER_CERTIFICATE_CF,
+    crate::columns::SUBMITTED_PROOF_PER_CERTIFICATE_CF,
+    crate::columns::CERTIFIED_ROOTS_PER_CERTIFICATE_CF,
+    crate::columns::VERIFIED_PROOF_PER_CERTIFICATE_CF,
 ];
 
 /// Definitions for the column families in the pending queue storage.
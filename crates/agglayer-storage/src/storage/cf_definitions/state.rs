@@ -1,14 +1,25 @@
 use rocksdb::ColumnFamilyDescriptor;
 
-pub const CFS: [&str; 8] = [
+pub const CFS: [&str; 19] = [
     crate::columns::CERTIFICATE_HEADER_CF,
     crate::columns::CERTIFICATE_PER_NETWORK_CF,
     crate::columns::LATEST_SETTLED_CERTIFICATE_PER_NETWORK_CF,
+    crate::columns::LATEST_PENDING_CERTIFICATE_PER_NETWORK_CF,
+    crate::columns::LATEST_PROVEN_CERTIFICATE_PER_NETWORK_CF,
     crate::columns::METADATA_CF,
     crate::columns::LOCAL_EXIT_TREE_PER_NETWORK_CF,
     crate::columns::BALANCE_TREE_PER_NETWORK_CF,
     crate::columns::NULLIFIER_TREE_PER_NETWORK_CF,
     crate::columns::NETWORK_INFO_CF,
+    crate::columns::SETTLEMENT_ATTEMPTS_PER_CERTIFICATE_CF,
+    crate::columns::SETTLEMENT_SENDER_PER_TX_CF,
+    crate::columns::REVERTED_SETTLEMENTS_PER_EPOCH_CF,
+    crate::columns::SETTLEMENT_COSTS_PER_NETWORK_CF,
+    crate::columns::SETTLED_ROOTS_PER_NETWORK_CF,
+    crate::columns::CALLBACK_PER_CERTIFICATE_CF,
+    crate::columns::AUDIT_LOG_PER_CERTIFICATE_CF,
+    crate::columns::EVENT_LOG_CF,
+    crate::columns::API_KEY_USAGE_CF,
 ];
 
 /// Definitions for the column families in the state storage.
@@ -0,0 +1,264 @@
+//! Manual and scheduled compaction of the column families.
+//!
+//! RocksDB compacts the column families on its own as they grow, which leaves
+//! the space of the pruned entries unclaimed for a while. The compactor
+//! compacts the whole key range of some column families on demand or
+//! periodically, reporting its progress through the storage metrics.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use agglayer_telemetry::storage as metrics;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::DB;
+
+/// A column family to compact.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactionTarget {
+    /// Name of the database.
+    pub db: String,
+    /// Name of the column family.
+    pub column: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CompactionError {
+    #[error("Unknown column family {0}")]
+    UnknownColumn(String),
+
+    #[error("A compaction is already in progress")]
+    AlreadyInProgress,
+}
+
+/// Compactor of the column families of the databases, running one compaction
+/// at a time.
+#[derive(Clone, Default)]
+pub struct Compactor {
+    dbs: Vec<(&'static str, Arc<DB>)>,
+    in_progress: Arc<AtomicBool>,
+}
+
+impl Compactor {
+    /// Create a compactor for the given databases, each labelled with its
+    /// name.
+    pub fn new(dbs: Vec<(&'static str, Arc<DB>)>) -> Self {
+        Self {
+            dbs,
+            in_progress: Arc::default(),
+        }
+    }
+
+    /// The column families with the given names, or all of them when none is
+    /// given.
+    pub fn targets(&self, columns: &[String]) -> Result<Vec<CompactionTarget>, CompactionError> {
+        let targets: Vec<_> = self
+            .dbs
+            .iter()
+            .flat_map(|(name, db)| {
+                db.backend()
+                    .column_families()
+                    .iter()
+                    .filter(|column| columns.is_empty() || columns.contains(column))
+                    .map(|column| CompactionTarget {
+                        db: name.to_string(),
+                        column: column.clone(),
+                    })
+            })
+            .collect();
+
+        if let Some(unknown) = columns
+            .iter()
+            .find(|column| !targets.iter().any(|target| &target.column == *column))
+        {
+            return Err(CompactionError::UnknownColumn(unknown.clone()));
+        }
+
+        Ok(targets)
+    }
+
+    /// Compact the column families with the given names, or all of them when
+    /// none is given, one after the other.
+    pub fn compact(&self, columns: &[String]) -> Result<Vec<CompactionTarget>, CompactionError> {
+        let targets = self.targets(columns)?;
+        let _guard = self.acquire()?;
+        self.compact_targets(&targets);
+
+        Ok(targets)
+    }
+
+    /// Compact the column families with the given names, or all of them when
+    /// none is given, in the background.
+    pub fn spawn(&self, columns: &[String]) -> Result<Vec<CompactionTarget>, CompactionError> {
+        let targets = self.targets(columns)?;
+        let guard = self.acquire()?;
+
+        let compactor = self.clone();
+        let compacted = targets.clone();
+        tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            compactor.compact_targets(&compacted);
+        });
+
+        Ok(targets)
+    }
+
+    /// Compact the column families with the given names periodically until
+    /// cancelled, the first compaction happening after the interval.
+    pub async fn run(
+        self,
+        columns: Vec<String>,
+        interval: Duration,
+        cancellation_token: CancellationToken,
+    ) {
+        let start = tokio::time::Instant::now() + interval;
+        let mut interval = tokio::time::interval_at(start, interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Storage compactor cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    let compactor = self.clone();
+                    let columns = columns.clone();
+                    match tokio::task::spawn_blocking(move || compactor.compact(&columns)).await {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(error)) => warn!(%error, "Scheduled storage compaction skipped"),
+                        Err(error) => warn!(?error, "Scheduled storage compaction failed"),
+                    }
+                }
+            }
+        }
+    }
+
+    fn acquire(&self) -> Result<InProgressGuard, CompactionError> {
+        self.in_progress
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .map_err(|_| CompactionError::AlreadyInProgress)?;
+
+        Ok(InProgressGuard(self.in_progress.clone()))
+    }
+
+    fn compact_targets(&self, targets: &[CompactionTarget]) {
+        for target in targets {
+            let Some((name, db)) = self.dbs.iter().find(|(name, _)| *name == target.db) else {
+                continue;
+            };
+
+            info!(db = name, column = %target.column, "Compacting the storage column");
+            metrics::record_compaction_started(name, &target.column);
+            let start = Instant::now();
+            let result = db.compact(&target.column);
+            let duration = start.elapsed();
+            metrics::record_compaction_completed(name, &target.column, duration, result.is_ok());
+
+            match result {
+                Ok(()) => info!(
+                    db = name,
+                    column = %target.column,
+                    ?duration,
+                    "Storage column compacted"
+                ),
+                Err(error) => warn!(
+                    ?error,
+                    db = name,
+                    column = %target.column,
+                    "Failed to compact the storage column"
+                ),
+            }
+        }
+    }
+}
+
+/// Marks the compaction as completed when dropped.
+struct InProgressGuard(Arc<AtomicBool>);
+
+impl Drop for InProgressGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_types::{CertificateId, CertificateIndex, EpochNumber, Height};
+
+    use super::*;
+    use crate::{
+        columns::{
+            latest_settled_certificate_per_network::{
+                LatestSettledCertificatePerNetworkColumn, SettledCertificate,
+            },
+            LATEST_SETTLED_CERTIFICATE_PER_NETWORK_CF,
+        },
+        storage::{pending_db_cf_definitions, state_db_cf_definitions},
+        tests::TempDBDir,
+    };
+
+    #[test]
+    fn compacts_the_requested_columns() {
+        let state_dir = TempDBDir::new();
+        let pending_dir = TempDBDir::new();
+        let state_db =
+            Arc::new(DB::open_cf(state_dir.path.as_path(), state_db_cf_definitions()).unwrap());
+        let pending_db =
+            Arc::new(DB::open_cf(pending_dir.path.as_path(), pending_db_cf_definitions()).unwrap());
+        let compactor = Compactor::new(vec![("state", state_db.clone()), ("pending", pending_db)]);
+
+        let certificate = SettledCertificate(
+            CertificateId::new([0; 32].into()),
+            Height::ZERO,
+            EpochNumber::ZERO,
+            CertificateIndex::ZERO,
+        );
+        state_db
+            .put::<LatestSettledCertificatePerNetworkColumn>(&1.into(), &certificate)
+            .unwrap();
+
+        let columns = vec![LATEST_SETTLED_CERTIFICATE_PER_NETWORK_CF.to_string()];
+        assert_eq!(
+            compactor.compact(&columns).unwrap(),
+            vec![CompactionTarget {
+                db: "state".to_string(),
+                column: LATEST_SETTLED_CERTIFICATE_PER_NETWORK_CF.to_string(),
+            }]
+        );
+        assert_eq!(
+            state_db
+                .get::<LatestSettledCertificatePerNetworkColumn>(&1.into())
+                .unwrap(),
+            Some(certificate)
+        );
+
+        assert_eq!(
+            compactor.targets(&[]).unwrap().len(),
+            state_db_cf_definitions().len() + pending_db_cf_definitions().len()
+        );
+        assert!(matches!(
+            compactor.compact(&["unknown_cf".to_string()]),
+            Err(CompactionError::UnknownColumn(column)) if column == "unknown_cf"
+        ));
+    }
+
+    #[test]
+    fn one_compaction_at_a_time() {
+        let compactor = Compactor::default();
+        let _guard = compactor.acquire().unwrap();
+
+        assert!(matches!(
+            compactor.compact(&[]),
+            Err(CompactionError::AlreadyInProgress)
+        ));
+    }
+}
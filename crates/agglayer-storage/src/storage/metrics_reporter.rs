@@ -0,0 +1,82 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use super::DB;
+
+/// Task periodically recording the RocksDB properties of the databases.
+pub struct MetricsReporter {
+    dbs: Vec<(&'static str, Arc<DB>)>,
+    interval: Duration,
+}
+
+impl MetricsReporter {
+    /// Create a reporter for the given databases, each labelled with its name.
+    pub fn new(dbs: Vec<(&'static str, Arc<DB>)>, interval: Duration) -> Self {
+        Self { dbs, interval }
+    }
+
+    /// Record the properties of every database.
+    pub fn report(&self) {
+        for (name, db) in &self.dbs {
+            db.record_metrics(name);
+        }
+    }
+
+    /// Record the properties periodically until cancelled.
+    pub async fn run(self, cancellation_token: CancellationToken) {
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Storage metrics reporter cancelled");
+                    break;
+                }
+                _ = interval.tick() => self.report(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_types::{CertificateId, CertificateIndex, EpochNumber, Height};
+
+    use super::*;
+    use crate::{
+        columns::latest_settled_certificate_per_network::{
+            LatestSettledCertificatePerNetworkColumn, SettledCertificate,
+        },
+        storage::state_db_cf_definitions,
+        tests::TempDBDir,
+    };
+
+    #[test]
+    fn instrumented_operations_and_report() {
+        let tmp = TempDBDir::new();
+        let db = Arc::new(DB::open_cf(tmp.path.as_path(), state_db_cf_definitions()).unwrap());
+        assert_eq!(
+            db.backend().column_families().len(),
+            state_db_cf_definitions().len()
+        );
+
+        let certificate = SettledCertificate(
+            CertificateId::new([0; 32].into()),
+            Height::ZERO,
+            EpochNumber::ZERO,
+            CertificateIndex::ZERO,
+        );
+        db.put::<LatestSettledCertificatePerNetworkColumn>(&1.into(), &certificate)
+            .unwrap();
+        assert_eq!(
+            db.get::<LatestSettledCertificatePerNetworkColumn>(&1.into())
+                .unwrap(),
+            Some(certificate)
+        );
+
+        MetricsReporter::new(vec![("state", db)], Duration::from_secs(1)).report();
+    }
+}
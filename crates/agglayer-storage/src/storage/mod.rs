@@ -1,21 +1,33 @@
-use std::path::Path;
+use std::{path::Path, time::Instant};
 
+use agglayer_config::storage::resources::DbResourcesConfig;
+use agglayer_telemetry::storage as metrics;
 use iterators::{ColumnIterator, KeysIterator};
-use rocksdb::{
-    ColumnFamily, ColumnFamilyDescriptor, DBPinnableSlice, Direction, Options, ReadOptions,
-    WriteBatch, WriteOptions,
-};
+use rocksdb::ColumnFamilyDescriptor;
+use scrubber::{CorruptedEntry, ScrubReport};
+use tracing::warn;
 
 use crate::columns::{Codec, ColumnSchema};
 
+pub mod backend;
 pub(crate) mod cf_definitions;
 pub(crate) mod iterators;
 
 pub mod backup;
+pub mod catch_up;
+pub mod compactor;
+pub mod metrics_reporter;
+pub mod scrubber;
+
+#[cfg(test)]
+mod tests;
 
+pub use backend::{Backend, Direction, MemoryBackend, RocksDbBackend, WriteBatch};
 pub use cf_definitions::{
-    debug::debug_db_cf_definitions, epochs::epochs_db_cf_definitions,
-    pending::pending_db_cf_definitions, state::state_db_cf_definitions,
+    debug::{debug_db_cf_definitions, CFS as DEBUG_DB_CFS},
+    epochs::{epochs_db_cf_definitions, CFS as EPOCHS_DB_CFS},
+    pending::{pending_db_cf_definitions, CFS as PENDING_DB_CFS},
+    state::{state_db_cf_definitions, CFS as STATE_DB_CFS},
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -43,117 +55,231 @@ pub enum BackupError {
 
     #[error("IO Error: {0}")]
     IO(#[from] std::io::Error),
+
+    #[error("The storage backend doesn't support backups")]
+    UnsupportedBackend,
 }
 
-/// A physical storage storage component with an active RocksDB.
+/// A physical storage component, holding its entries in a backend.
 pub struct DB {
-    rocksdb: rocksdb::DB,
-    default_write_options: Option<WriteOptions>,
+    backend: Box<dyn Backend>,
+    /// Database mirroring the writes, whose values are compared with the ones
+    /// read, to validate a storage migration before cutting over.
+    shadow: Option<Box<DB>>,
+}
+
+/// Run an operation on a column, recording its latency.
+fn instrumented<C: ColumnSchema, R>(operation: &'static str, f: impl FnOnce() -> R) -> R {
+    let started_at = Instant::now();
+    let result = f();
+    metrics::record_operation(C::COLUMN_FAMILY_NAME, operation, started_at.elapsed());
+
+    result
 }
 
 impl DB {
+    /// Create a database holding its entries in the given backend.
+    pub fn new(backend: impl Backend + 'static) -> Self {
+        DB {
+            backend: Box::new(backend),
+            shadow: None,
+        }
+    }
+
     /// Open a new RocksDB instance at the given path with some column families.
     pub fn open_cf(path: &Path, cfs: Vec<ColumnFamilyDescriptor>) -> Result<DB, DBError> {
-        let mut options = Options::default();
-        options.create_if_missing(true);
-        options.create_missing_column_families(true);
-
-        let mut writeopts = WriteOptions::default();
-        writeopts.set_sync(true);
-
-        Ok(DB {
-            rocksdb: rocksdb::DB::open_cf_descriptors(&options, path, cfs)?,
-            default_write_options: Some(writeopts),
-        })
+        Ok(DB::new(RocksDbBackend::open(path, cfs)?))
     }
 
     /// Open a RocksDB instance in read-only mode at the given path with some
     /// column families. This prevents concurrency issues when multiple
     /// processes need to read from the database.
     pub fn open_cf_readonly(path: &Path, cfs: Vec<ColumnFamilyDescriptor>) -> Result<DB, DBError> {
-        let mut options = Options::default();
-        options.create_if_missing(false); // Don't create if missing in readonly mode
-        options.create_missing_column_families(false); // Don't create missing column families
+        Ok(DB::new(RocksDbBackend::open_readonly(path, cfs)?))
+    }
+
+    /// Open a RocksDB instance as a secondary of the primary one at the given
+    /// path, which can catch up with the writes applied by the primary.
+    pub fn open_cf_secondary(
+        path: &Path,
+        secondary_path: &Path,
+        cfs: Vec<ColumnFamilyDescriptor>,
+    ) -> Result<DB, DBError> {
+        Ok(DB::new(RocksDbBackend::open_secondary(
+            path,
+            secondary_path,
+            cfs,
+        )?))
+    }
+
+    /// Open a new RocksDB instance at the given path with the given column
+    /// families, limited to the given resources.
+    pub fn open_cf_with_resources(
+        path: &Path,
+        cfs: &[&'static str],
+        resources: &DbResourcesConfig,
+    ) -> Result<DB, DBError> {
+        Ok(DB::new(RocksDbBackend::open_with_resources(
+            path,
+            cf_definitions::db_cf_definitions(cfs, resources),
+            resources,
+        )?))
+    }
+
+    /// Open a RocksDB instance in read-only mode at the given path with the
+    /// given column families, limited to the given resources.
+    pub fn open_cf_readonly_with_resources(
+        path: &Path,
+        cfs: &[&'static str],
+        resources: &DbResourcesConfig,
+    ) -> Result<DB, DBError> {
+        Ok(DB::new(RocksDbBackend::open_readonly_with_resources(
+            path,
+            cf_definitions::db_cf_definitions(cfs, resources),
+            resources,
+        )?))
+    }
 
-        Ok(DB {
-            rocksdb: rocksdb::DB::open_cf_descriptors_read_only(&options, path, cfs, false)?,
-            default_write_options: None,
-        })
+    /// Create an empty database held in memory with some column families.
+    pub fn open_in_memory(cfs: Vec<ColumnFamilyDescriptor>) -> DB {
+        DB::new(MemoryBackend::new(cfs.iter().map(|cf| cf.name())))
     }
 
-    fn write_options(&self) -> Result<&WriteOptions, DBError> {
-        self.default_write_options
-            .as_ref()
-            .ok_or(DBError::ReadOnlyMode)
+    /// The backend holding the entries of the database.
+    pub fn backend(&self) -> &dyn Backend {
+        self.backend.as_ref()
     }
 
-    fn cf<C: ColumnSchema>(&self) -> Result<&ColumnFamily, DBError> {
-        self.rocksdb
-            .cf_handle(C::COLUMN_FAMILY_NAME)
-            .ok_or(DBError::ColumnFamilyNotFound)
+    /// Mirror every write into the given database, opened with the same
+    /// column families, and compare the values read with the ones it holds.
+    ///
+    /// The shadow database is expected to start as a copy of this one, such
+    /// as a restored backup. Its failures and mismatches are only reported,
+    /// and never fail the operations on this database.
+    pub fn with_shadow(mut self, shadow: DB) -> Self {
+        self.shadow = Some(Box::new(shadow));
+        self
+    }
+
+    /// Run a write on the shadow database, if any, reporting its failure.
+    fn shadow_write(
+        &self,
+        operation: &'static str,
+        write: impl FnOnce(&dyn Backend) -> Result<(), DBError>,
+    ) {
+        let Some(shadow) = &self.shadow else {
+            return;
+        };
+
+        if let Err(error) = write(shadow.backend()) {
+            warn!(?error, operation, "Failed to write to the shadow storage");
+            metrics::record_shadow_write_error(operation);
+        }
+    }
+
+    /// Compare the raw value read for the given key with the one held by the
+    /// shadow database, if any.
+    fn shadow_compare<C: ColumnSchema>(&self, key: &[u8], value: Option<&[u8]>) {
+        let Some(shadow) = &self.shadow else {
+            return;
+        };
+
+        match shadow.backend.get(C::COLUMN_FAMILY_NAME, key) {
+            Ok(shadow_value) if shadow_value.as_deref() == value => {}
+            Ok(shadow_value) => {
+                warn!(
+                    column = C::COLUMN_FAMILY_NAME,
+                    key = hex::encode(key),
+                    found = shadow_value.is_some(),
+                    expected = value.is_some(),
+                    "Shadow storage mismatch"
+                );
+                metrics::record_shadow_mismatch(C::COLUMN_FAMILY_NAME);
+            }
+            Err(error) => {
+                warn!(
+                    ?error,
+                    column = C::COLUMN_FAMILY_NAME,
+                    "Failed to read from the shadow storage"
+                );
+                metrics::record_shadow_mismatch(C::COLUMN_FAMILY_NAME);
+            }
+        }
+    }
+
+    /// Apply the batch to the backend, then mirror it into the shadow
+    /// database, if any.
+    fn write(&self, operation: &'static str, batch: WriteBatch) -> Result<(), DBError> {
+        let shadow_batch = self.shadow.as_ref().map(|_| batch.clone());
+        self.backend.write(batch)?;
+        if let Some(shadow_batch) = shadow_batch {
+            self.shadow_write(operation, |shadow| shadow.write(shadow_batch));
+        }
+
+        Ok(())
     }
 
     /// Try to get the value for the given key.
     pub fn get<C: ColumnSchema>(&self, key: &C::Key) -> Result<Option<C::Value>, DBError> {
         let key = key.encode()?;
-        let cf = self.cf::<C>()?;
 
-        self.rocksdb
-            .get_cf(cf, &key)?
-            .map(|v| C::Value::decode(&v[..]).map_err(Into::into))
+        let value = instrumented::<C, _>("get", || self.backend.get(C::COLUMN_FAMILY_NAME, &key))?;
+        self.shadow_compare::<C>(&key, value.as_deref());
+
+        value
+            .map(|v| {
+                metrics::record_value_size(C::COLUMN_FAMILY_NAME, "read", v.len());
+                C::Value::decode(&v[..]).map_err(Into::into)
+            })
             // If the value is not found, return None.
             // If the value is found, decode it and wrap it in Some to propagate decode error.
             .map_or(Ok(None), |v| v.map(Some))
     }
 
+    /// Try to get the value for the given key as it is encoded in the
+    /// database, without decoding it.
+    pub fn get_encoded<C: ColumnSchema>(&self, key: &C::Key) -> Result<Option<Vec<u8>>, DBError> {
+        let key = key.encode()?;
+
+        let value = instrumented::<C, _>("get", || self.backend.get(C::COLUMN_FAMILY_NAME, &key))?;
+        self.shadow_compare::<C>(&key, value.as_deref());
+
+        Ok(value.map(|v| {
+            metrics::record_value_size(C::COLUMN_FAMILY_NAME, "read", v.len());
+            v[..].to_vec()
+        }))
+    }
+
+    /// Get the values for the given keys from a consistent view of the
+    /// database.
     pub fn atomic_multi_get<C: ColumnSchema>(
         &self,
         keys: impl IntoIterator<Item = C::Key>,
     ) -> Result<Vec<Option<C::Value>>, DBError> {
-        let snapshot = self.rocksdb.snapshot();
-        let cf = self
-            .rocksdb
-            .cf_handle(C::COLUMN_FAMILY_NAME)
-            .ok_or(DBError::ColumnFamilyNotFound)?;
-
-        let keys: Result<Vec<_>, _> = keys
-            .into_iter()
-            .map(|k| k.encode().map(|key| (cf, key)))
-            .collect();
-
-        let results = snapshot
-            .multi_get_cf(keys?)
-            .into_iter()
-            .map(|r| r.map_err(DBError::from))
-            .collect::<Result<Vec<Option<_>>, _>>()?;
-
-        results
-            .into_iter()
-            .map(|bytes| match bytes {
-                Some(bytes) => C::Value::decode(&bytes[..]).map_err(Into::into).map(Some),
-                None => Ok(None),
-            })
-            .collect()
+        self.multi_get::<C>(keys)
     }
 
     pub fn multi_get<C: ColumnSchema>(
         &self,
         keys: impl IntoIterator<Item = C::Key>,
     ) -> Result<Vec<Option<C::Value>>, DBError> {
-        let cf = self.cf::<C>()?;
         let keys: Result<Vec<_>, _> = keys.into_iter().map(|k| k.encode()).collect();
 
-        let results: Result<Vec<Option<DBPinnableSlice>>, _> = self
-            .rocksdb
-            .batched_multi_get_cf(cf, &keys?, false)
-            .into_iter()
-            .map(|r| r.map_err(DBError::from))
-            .collect();
+        let keys = keys?;
+        let results = instrumented::<C, _>("multi_get", || {
+            self.backend.multi_get(C::COLUMN_FAMILY_NAME, &keys)
+        })?;
+        for (key, value) in keys.iter().zip(&results) {
+            self.shadow_compare::<C>(key, value.as_deref());
+        }
 
-        results?
+        results
             .into_iter()
             .map(|bytes| match bytes {
-                Some(bytes) => C::Value::decode(&bytes[..]).map_err(Into::into).map(Some),
+                Some(bytes) => {
+                    metrics::record_value_size(C::COLUMN_FAMILY_NAME, "read", bytes.len());
+                    C::Value::decode(&bytes[..]).map_err(Into::into).map(Some)
+                }
                 None => Ok(None),
             })
             .collect()
@@ -163,19 +289,15 @@ impl DB {
     pub fn put<C: ColumnSchema>(&self, key: &C::Key, value: &C::Value) -> Result<(), DBError> {
         let key = key.encode()?;
         let value = value.encode()?;
-        let cf = self.cf::<C>()?;
-
-        let write_options = self.write_options()?;
-        self.rocksdb.put_cf_opt(cf, key, value, write_options)?;
 
-        Ok(())
+        metrics::record_value_size(C::COLUMN_FAMILY_NAME, "write", value.len());
+        let mut batch = WriteBatch::default();
+        batch.put(C::COLUMN_FAMILY_NAME, key, value);
+        instrumented::<C, _>("put", || self.write("put", batch))
     }
 
     pub fn write_batch(&self, batch: WriteBatch) -> Result<(), DBError> {
-        let write_options = self.write_options()?;
-        self.rocksdb.write_opt(batch, write_options)?;
-
-        Ok(())
+        self.write("write_batch", batch)
     }
 
     pub fn multi_insert_batch<'a, C: ColumnSchema + 'a>(
@@ -183,15 +305,14 @@ impl DB {
         key_val_pairs: impl IntoIterator<Item = (&'a C::Key, &'a C::Value)>,
         batch: &mut WriteBatch,
     ) -> Result<(), DBError> {
-        let cf = self.cf::<C>()?;
-
         key_val_pairs
             .into_iter()
             .try_for_each::<_, Result<_, DBError>>(|(k, v)| {
                 let k_buf = k.encode()?;
                 let v_buf = v.encode()?;
+                metrics::record_value_size(C::COLUMN_FAMILY_NAME, "write", v_buf.len());
 
-                batch.put_cf(&cf, k_buf, v_buf);
+                batch.put(C::COLUMN_FAMILY_NAME, k_buf, v_buf);
                 Ok(())
             })?;
 
@@ -204,43 +325,114 @@ impl DB {
     ) -> Result<(), DBError> {
         let mut batch = WriteBatch::default();
         self.multi_insert_batch::<C>(key_val_pairs, &mut batch)?;
-        self.write_batch(batch)?;
+        instrumented::<C, _>("multi_insert", || self.write_batch(batch))?;
 
         Ok(())
     }
 
     /// Try to get every key in the column family.
     pub fn keys<C: ColumnSchema>(&self) -> Result<KeysIterator<'_, C>, DBError> {
-        let cf = self.cf::<C>()?;
+        let iter = self
+            .backend
+            .iter(C::COLUMN_FAMILY_NAME, None, Direction::Forward)?;
 
-        let mut iterator = self.rocksdb.raw_iterator_cf(&cf);
-        iterator.seek_to_first();
-
-        Ok(KeysIterator::new(iterator, Direction::Forward))
+        Ok(KeysIterator::new(iter))
     }
 
+    /// Iterate over the entries of the column whose key is at or above the
+    /// lower bound, if any, in the given direction.
     pub(crate) fn iter_with_direction<C: ColumnSchema>(
         &self,
-        opts: ReadOptions,
+        lower_bound: Option<&C::Key>,
         direction: Direction,
     ) -> Result<ColumnIterator<'_, C>, DBError> {
-        let cf = self.cf::<C>()?;
+        let lower_bound = lower_bound.map(|key| key.encode()).transpose()?;
+        let iter = self
+            .backend
+            .iter(C::COLUMN_FAMILY_NAME, lower_bound, direction)?;
 
-        let mut iterator = self.rocksdb.raw_iterator_cf_opt(&cf, opts);
+        Ok(ColumnIterator::new(iter))
+    }
 
-        match direction {
-            Direction::Forward => iterator.seek_to_first(),
-            Direction::Reverse => iterator.seek_to_last(),
-        }
+    pub(crate) fn delete_batch<C: ColumnSchema>(
+        &self,
+        key: &C::Key,
+        batch: &mut WriteBatch,
+    ) -> Result<(), DBError> {
+        batch.delete(C::COLUMN_FAMILY_NAME, key.encode()?);
 
-        Ok(ColumnIterator::new(iterator, direction))
+        Ok(())
     }
 
     pub(crate) fn delete<C: ColumnSchema>(&self, key: &C::Key) -> Result<(), DBError> {
-        let cf = self.cf::<C>()?;
         let key = key.encode()?;
 
-        let write_options = self.write_options()?;
-        Ok(self.rocksdb.delete_cf_opt(&cf, key, write_options)?)
+        let mut batch = WriteBatch::default();
+        batch.delete(C::COLUMN_FAMILY_NAME, key);
+        instrumented::<C, _>("delete", || self.write("delete", batch))
+    }
+
+    /// Persist the writes applied so far, such as by flushing the memtables
+    /// of all the column families and the write-ahead log to disk.
+    pub fn flush(&self) -> Result<(), DBError> {
+        self.backend.flush()?;
+        self.shadow_write("flush", |shadow| shadow.flush());
+
+        Ok(())
+    }
+
+    /// Read every entry of the database back, verifying the block checksums
+    /// and decoding the keys and values, and add the scanned and corrupted
+    /// entries to the report, labelled with the given database name.
+    pub fn scrub(&self, db: &'static str, report: &mut ScrubReport) {
+        for name in self.backend.column_families() {
+            let mut corrupted = Vec::new();
+            match self.backend.iter(name, None, Direction::Forward) {
+                Ok(entries) => {
+                    for entry in entries {
+                        match entry {
+                            Ok((key, value)) => {
+                                report.scanned_entries += 1;
+                                if let Some(Err(error)) =
+                                    crate::columns::check_entry(name, &key, &value)
+                                {
+                                    corrupted.push((Some(hex::encode(key)), error.to_string()));
+                                }
+                            }
+                            // A checksum mismatch stops the iteration over the column.
+                            Err(error) => corrupted.push((None, error.to_string())),
+                        }
+                    }
+                }
+                Err(error) => corrupted.push((None, error.to_string())),
+            }
+
+            metrics::record_corrupted_entries(db, name, corrupted.len() as u64);
+            report
+                .corrupted_entries
+                .extend(corrupted.into_iter().map(|(key, error)| CorruptedEntry {
+                    db: db.to_string(),
+                    column: name.clone(),
+                    key,
+                    error,
+                }));
+        }
+    }
+
+    /// Compact the whole key range of the given column family.
+    pub fn compact(&self, column: &str) -> Result<(), DBError> {
+        self.backend.compact(column)
+    }
+
+    /// Catch up with the writes applied by the primary instance, if the
+    /// database was opened as a secondary one.
+    pub fn catch_up(&self) -> Result<(), DBError> {
+        self.backend.catch_up()
+    }
+
+    /// Record the metrics of the backend of the database, labelled with the
+    /// given database name.
+    pub fn record_metrics(&self, db: &'static str) {
+        self.backend.record_metrics(db);
     }
 }
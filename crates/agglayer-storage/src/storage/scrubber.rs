@@ -0,0 +1,195 @@
+//! Detection of the corrupted entries of the storage.
+//!
+//! RocksDB only verifies the checksums of the blocks it reads, so a corrupted
+//! entry goes unnoticed until a certification reads it. The scrubber reads
+//! every entry of the databases back, verifying the block checksums and
+//! decoding the key and the value with the codecs of their column, and keeps
+//! the report of the latest scrub for the admin API.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use super::DB;
+
+/// An entry of the storage which can't be read back.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CorruptedEntry {
+    /// Name of the database.
+    pub db: String,
+    /// Name of the column family.
+    pub column: String,
+    /// Hex-encoded key of the entry, unknown when the corruption prevented
+    /// reading the rest of the column.
+    pub key: Option<String>,
+    /// Error raised when reading or decoding the entry.
+    pub error: String,
+}
+
+/// Result of a scrub of the storage.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrubReport {
+    /// Time at which the scrub completed, if the storage was ever scrubbed.
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Number of entries read.
+    pub scanned_entries: u64,
+    pub corrupted_entries: Vec<CorruptedEntry>,
+}
+
+/// Report of the latest scrub, shared between the scrubber and its readers.
+#[derive(Clone, Debug, Default)]
+pub struct LatestScrubReport(Arc<RwLock<ScrubReport>>);
+
+impl LatestScrubReport {
+    /// The report of the latest scrub, empty if the storage was never
+    /// scrubbed.
+    pub fn get(&self) -> ScrubReport {
+        self.0
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn set(&self, report: ScrubReport) {
+        *self
+            .0
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = report;
+    }
+}
+
+/// Task periodically scrubbing the databases.
+pub struct Scrubber {
+    dbs: Vec<(&'static str, Arc<DB>)>,
+    interval: Duration,
+    latest_report: LatestScrubReport,
+}
+
+impl Scrubber {
+    /// Create a scrubber for the given databases, each labelled with its
+    /// name.
+    pub fn new(dbs: Vec<(&'static str, Arc<DB>)>, interval: Duration) -> Self {
+        Self {
+            dbs,
+            interval,
+            latest_report: LatestScrubReport::default(),
+        }
+    }
+
+    /// The report of the latest scrub, updated after each scrub.
+    pub fn latest_report(&self) -> LatestScrubReport {
+        self.latest_report.clone()
+    }
+
+    /// Scrub every database, recording the number of corrupted entries per
+    /// column.
+    pub fn scrub(&self) -> ScrubReport {
+        let mut report = ScrubReport::default();
+        for (name, db) in &self.dbs {
+            db.scrub(name, &mut report);
+        }
+        report.completed_at = Some(Utc::now());
+
+        for entry in &report.corrupted_entries {
+            error!(
+                db = %entry.db,
+                column = %entry.column,
+                key = ?entry.key,
+                error = %entry.error,
+                "Corrupted storage entry"
+            );
+        }
+        info!(
+            scanned_entries = report.scanned_entries,
+            corrupted_entries = report.corrupted_entries.len(),
+            "Storage scrub completed"
+        );
+
+        self.latest_report.set(report.clone());
+
+        report
+    }
+
+    /// Scrub the databases periodically until cancelled, starting right
+    /// away.
+    pub async fn run(self, cancellation_token: CancellationToken) {
+        let scrubber = Arc::new(self);
+        let mut interval = tokio::time::interval(scrubber.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => {
+                    info!("Storage scrubber cancelled");
+                    break;
+                }
+                _ = interval.tick() => {
+                    let scrubber = scrubber.clone();
+                    if let Err(error) = tokio::task::spawn_blocking(move || scrubber.scrub()).await {
+                        warn!(?error, "Storage scrub failed");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_types::{CertificateId, CertificateIndex, EpochNumber, Height};
+
+    use super::*;
+    use crate::{
+        columns::{
+            latest_settled_certificate_per_network::{
+                LatestSettledCertificatePerNetworkColumn, SettledCertificate,
+            },
+            CERTIFICATE_HEADER_CF,
+        },
+        storage::{state_db_cf_definitions, WriteBatch},
+        tests::TempDBDir,
+    };
+
+    #[test]
+    fn undecodable_entries_are_reported() {
+        let tmp = TempDBDir::new();
+        let db = Arc::new(DB::open_cf(tmp.path.as_path(), state_db_cf_definitions()).unwrap());
+
+        db.put::<LatestSettledCertificatePerNetworkColumn>(
+            &1.into(),
+            &SettledCertificate(
+                CertificateId::new([0; 32].into()),
+                Height::ZERO,
+                EpochNumber::ZERO,
+                CertificateIndex::ZERO,
+            ),
+        )
+        .unwrap();
+        let mut batch = WriteBatch::default();
+        batch.put(CERTIFICATE_HEADER_CF, vec![1; 32], vec![0xff; 3]);
+        db.backend().write(batch).unwrap();
+
+        let scrubber = Scrubber::new(vec![("state", db)], Duration::from_secs(1));
+        assert_eq!(scrubber.latest_report().get(), ScrubReport::default());
+
+        let report = scrubber.scrub();
+        assert!(report.completed_at.is_some());
+        assert_eq!(report.scanned_entries, 2);
+        let [entry] = report.corrupted_entries.as_slice() else {
+            panic!("Unexpected corrupted entries: {report:?}");
+        };
+        assert_eq!(entry.db, "state");
+        assert_eq!(entry.column, CERTIFICATE_HEADER_CF);
+        assert_eq!(entry.key, Some(hex::encode([1; 32])));
+
+        assert_eq!(scrubber.latest_report().get(), report);
+    }
+}
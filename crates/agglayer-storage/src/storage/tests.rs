@@ -0,0 +1,158 @@
+use agglayer_types::{CertificateId, CertificateIndex, EpochNumber, Height, NetworkId};
+
+use super::*;
+use crate::{
+    columns::latest_settled_certificate_per_network::{
+        LatestSettledCertificatePerNetworkColumn, SettledCertificate,
+    },
+    storage::state_db_cf_definitions,
+    tests::TempDBDir,
+};
+
+fn settled(height: u64) -> SettledCertificate {
+    SettledCertificate(
+        CertificateId::new([height as u8; 32].into()),
+        Height::new(height),
+        EpochNumber::ZERO,
+        CertificateIndex::ZERO,
+    )
+}
+
+#[test]
+fn writes_are_mirrored_into_the_shadow() {
+    let primary_dir = TempDBDir::new();
+    let shadow_dir = TempDBDir::new();
+    let db = DB::open_cf(primary_dir.path.as_path(), state_db_cf_definitions())
+        .unwrap()
+        .with_shadow(DB::open_cf(shadow_dir.path.as_path(), state_db_cf_definitions()).unwrap());
+    let shadow = db.shadow.as_deref().unwrap();
+
+    let (network_1, network_2) = (NetworkId::new(1), NetworkId::new(2));
+    db.put::<LatestSettledCertificatePerNetworkColumn>(&network_1, &settled(1))
+        .unwrap();
+    db.multi_insert::<LatestSettledCertificatePerNetworkColumn>([(&network_2, &settled(2))])
+        .unwrap();
+    assert_eq!(
+        shadow
+            .multi_get::<LatestSettledCertificatePerNetworkColumn>([network_1, network_2])
+            .unwrap(),
+        vec![Some(settled(1)), Some(settled(2))]
+    );
+
+    db.delete::<LatestSettledCertificatePerNetworkColumn>(&network_1)
+        .unwrap();
+    assert_eq!(
+        shadow
+            .get::<LatestSettledCertificatePerNetworkColumn>(&network_1)
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn shadow_mismatches_do_not_affect_the_reads() {
+    let primary_dir = TempDBDir::new();
+    let shadow_dir = TempDBDir::new();
+    let shadow = DB::open_cf(shadow_dir.path.as_path(), state_db_cf_definitions()).unwrap();
+    let network_id = NetworkId::new(1);
+    shadow
+        .put::<LatestSettledCertificatePerNetworkColumn>(&network_id, &settled(2))
+        .unwrap();
+
+    let db = DB::open_cf(primary_dir.path.as_path(), state_db_cf_definitions())
+        .unwrap()
+        .with_shadow(shadow);
+    // Written to the backend only, bypassing the shadow.
+    let mut batch = WriteBatch::default();
+    batch.put(
+        LatestSettledCertificatePerNetworkColumn::COLUMN_FAMILY_NAME,
+        network_id.encode().unwrap(),
+        settled(1).encode().unwrap(),
+    );
+    db.backend().write(batch).unwrap();
+
+    assert_eq!(
+        db.get::<LatestSettledCertificatePerNetworkColumn>(&network_id)
+            .unwrap(),
+        Some(settled(1))
+    );
+    assert_eq!(
+        db.atomic_multi_get::<LatestSettledCertificatePerNetworkColumn>([network_id])
+            .unwrap(),
+        vec![Some(settled(1))]
+    );
+}
+
+#[rstest::rstest]
+#[case::rocksdb(false)]
+#[case::in_memory(true)]
+fn backends_behave_alike(#[case] in_memory: bool) {
+    let tmp = TempDBDir::new();
+    let db = if in_memory {
+        DB::open_in_memory(state_db_cf_definitions())
+    } else {
+        DB::open_cf(tmp.path.as_path(), state_db_cf_definitions()).unwrap()
+    };
+
+    let networks: Vec<_> = (1..=3).map(NetworkId::new).collect();
+    for (height, network_id) in networks.iter().enumerate() {
+        db.put::<LatestSettledCertificatePerNetworkColumn>(network_id, &settled(height as u64))
+            .unwrap();
+    }
+    db.delete::<LatestSettledCertificatePerNetworkColumn>(&networks[1])
+        .unwrap();
+    db.flush().unwrap();
+
+    assert_eq!(
+        db.multi_get::<LatestSettledCertificatePerNetworkColumn>(networks.clone())
+            .unwrap(),
+        vec![Some(settled(0)), None, Some(settled(2))]
+    );
+    assert_eq!(
+        db.keys::<LatestSettledCertificatePerNetworkColumn>()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        vec![networks[0], networks[2]]
+    );
+    assert_eq!(
+        db.iter_with_direction::<LatestSettledCertificatePerNetworkColumn>(
+            Some(&networks[1]),
+            Direction::Reverse
+        )
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap(),
+        vec![(networks[2], settled(2))]
+    );
+
+    let mut batch = WriteBatch::default();
+    batch.put("unknown_cf", vec![0], vec![0]);
+    assert!(matches!(
+        db.write_batch(batch),
+        Err(DBError::ColumnFamilyNotFound)
+    ));
+}
+
+#[test]
+fn open_with_resources() {
+    let tmp = TempDBDir::new();
+    let resources = DbResourcesConfig {
+        block_cache_size: Some(8 * 1024 * 1024),
+        max_open_files: Some(16),
+    };
+    let db = DB::open_cf_with_resources(tmp.path.as_path(), &STATE_DB_CFS, &resources).unwrap();
+
+    let network = NetworkId::new(1);
+    db.put::<LatestSettledCertificatePerNetworkColumn>(&network, &settled(1))
+        .unwrap();
+    drop(db);
+
+    let db =
+        DB::open_cf_readonly_with_resources(tmp.path.as_path(), &STATE_DB_CFS, &resources).unwrap();
+    assert_eq!(
+        db.get::<LatestSettledCertificatePerNetworkColumn>(&network)
+            .unwrap(),
+        Some(settled(1))
+    );
+}
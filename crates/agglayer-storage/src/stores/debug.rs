@@ -3,7 +3,19 @@ use std::{path::Path, sync::Arc};
 use agglayer_types::{Certificate, CertificateId};
 
 use super::interfaces::{reader::DebugReader, writer::DebugWriter};
-use crate::{columns::debug_certificates::DebugCertificatesColumn, error::Error, storage::DB};
+use crate::{
+    columns::{
+        certification_failure_per_certificate::{
+            CertificationFailure, CertificationFailurePerCertificateColumn,
+        },
+        debug_certificates::DebugCertificatesColumn,
+    },
+    error::Error,
+    storage::{Direction, DB},
+};
+
+/// Default number of failed certifications kept in the debug storage.
+pub const DEFAULT_MAX_CERTIFICATION_FAILURES: usize = 100;
 
 pub enum DebugStore {
     Enabled(EnabledDebugStore),
@@ -14,11 +26,15 @@ pub enum DebugStore {
 #[derive(Clone)]
 pub struct EnabledDebugStore {
     db: Arc<DB>,
+    max_certification_failures: usize,
 }
 
 impl DebugStore {
     pub fn new(db: Arc<DB>) -> Self {
-        Self::Enabled(EnabledDebugStore { db })
+        Self::Enabled(EnabledDebugStore {
+            db,
+            max_certification_failures: DEFAULT_MAX_CERTIFICATION_FAILURES,
+        })
     }
 
     pub fn new_with_path(path: &Path) -> Result<Self, Error> {
@@ -29,6 +45,44 @@ impl DebugStore {
 
         Ok(Self::new(db))
     }
+
+    /// Keep at most the given number of failed certifications, none being
+    /// recorded when zero.
+    pub fn with_max_certification_failures(mut self, max: usize) -> Self {
+        if let DebugStore::Enabled(store) = &mut self {
+            store.max_certification_failures = max;
+        }
+
+        self
+    }
+}
+
+impl EnabledDebugStore {
+    /// Drop the oldest failed certifications until at most `max` of them are
+    /// left.
+    fn prune_certification_failures(&self, max: usize) -> Result<(), Error> {
+        let mut failures = self
+            .db
+            .iter_with_direction::<CertificationFailurePerCertificateColumn>(
+                None,
+                Direction::Forward,
+            )?
+            .map(|entry| {
+                entry.map(|(certificate_id, failure)| (failure.recorded_at, certificate_id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if failures.len() <= max {
+            return Ok(());
+        }
+
+        failures.sort();
+        for (_, certificate_id) in &failures[..failures.len() - max] {
+            self.db
+                .delete::<CertificationFailurePerCertificateColumn>(certificate_id)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl DebugReader for DebugStore {
@@ -43,6 +97,18 @@ impl DebugReader for DebugStore {
             DebugStore::Disabled => Ok(None),
         }
     }
+
+    fn get_certification_failure(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificationFailure>, Error> {
+        match self {
+            DebugStore::Enabled(store) => Ok(store
+                .db
+                .get::<CertificationFailurePerCertificateColumn>(certificate_id)?),
+            DebugStore::Disabled => Ok(None),
+        }
+    }
 }
 
 impl DebugWriter for DebugStore {
@@ -54,4 +120,75 @@ impl DebugWriter for DebugStore {
             DebugStore::Disabled => Ok(()),
         }
     }
+
+    fn add_certification_failure(
+        &self,
+        certificate: &Certificate,
+        failure: &CertificationFailure,
+    ) -> Result<(), Error> {
+        match self {
+            DebugStore::Enabled(store) if store.max_certification_failures > 0 => {
+                let certificate_id = certificate.hash();
+                store
+                    .db
+                    .put::<DebugCertificatesColumn>(&certificate_id, certificate)?;
+                store
+                    .db
+                    .put::<CertificationFailurePerCertificateColumn>(&certificate_id, failure)?;
+
+                store.prune_certification_failures(store.max_certification_failures)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agglayer_types::{Height, NetworkId};
+    use chrono::{TimeDelta, Utc};
+
+    use super::*;
+    use crate::tests::TempDBDir;
+
+    fn failure(recorded_at: chrono::DateTime<Utc>) -> CertificationFailure {
+        CertificationFailure {
+            recorded_at,
+            initial_roots: Default::default(),
+            error: "Native execution failed".to_string(),
+        }
+    }
+
+    #[test]
+    fn oldest_certification_failures_are_dropped() {
+        let tmp = TempDBDir::new();
+        let store = DebugStore::new_with_path(&tmp.path)
+            .unwrap()
+            .with_max_certification_failures(2);
+
+        let now = Utc::now();
+        let certificates: Vec<_> = (1..=3)
+            .map(|network| Certificate::new_for_test(NetworkId::new(network), Height::ZERO))
+            .collect();
+        for (age, certificate) in [3, 2, 1].into_iter().zip(&certificates) {
+            store
+                .add_certification_failure(certificate, &failure(now - TimeDelta::hours(age)))
+                .unwrap();
+        }
+
+        let oldest = certificates[0].hash();
+        assert_eq!(store.get_certification_failure(&oldest).unwrap(), None);
+        assert_eq!(
+            store.get_certificate(&oldest).unwrap().as_ref(),
+            Some(&certificates[0])
+        );
+        for (age, certificate) in [2, 1].into_iter().zip(&certificates[1..]) {
+            assert_eq!(
+                store
+                    .get_certification_failure(&certificate.hash())
+                    .unwrap(),
+                Some(failure(now - TimeDelta::hours(age)))
+            );
+        }
+    }
 }
@@ -1,10 +1,11 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, VecDeque},
     sync::Arc,
 };
 
 use agglayer_types::{Certificate, CertificateIndex, EpochNumber, Height, NetworkId};
-use parking_lot::RwLock;
+use parking_lot::Mutex;
+use tracing::debug;
 
 use super::{
     interfaces::reader::PerEpochReader, per_epoch::PerEpochStore, EpochStoreReader,
@@ -15,32 +16,98 @@ use crate::{error::Error, storage::backup::BackupClient};
 
 pub struct EpochsStore<PendingStore, StateStore> {
     config: Arc<agglayer_config::Config>,
-    #[allow(dead_code)]
-    open_epochs: RwLock<BTreeSet<EpochNumber>>,
+    /// Settled epochs opened read-only for the queries, the most recently
+    /// queried last.
+    open_epochs: Mutex<VecDeque<Arc<PerEpochStore<PendingStore, StateStore>>>>,
     pending_store: Arc<PendingStore>,
     state_store: Arc<StateStore>,
     backup_client: BackupClient,
 }
 
 impl<PendingStore, StateStore> EpochsStore<PendingStore, StateStore> {
+    /// Only the settled epochs are kept open for the queries, hence the
+    /// current epoch is never among them.
     pub fn new(
         config: Arc<agglayer_config::Config>,
-        epoch_number: EpochNumber,
+        _current_epoch: EpochNumber,
         pending_store: Arc<PendingStore>,
         state_store: Arc<StateStore>,
         backup_client: BackupClient,
     ) -> Result<Self, Error> {
-        let open_epochs = RwLock::new(BTreeSet::new());
-        open_epochs.write().insert(epoch_number);
-
         Ok(Self {
             config,
-            open_epochs,
+            open_epochs: Mutex::new(VecDeque::new()),
             pending_store,
             state_store,
             backup_client,
         })
     }
+
+    /// The epochs currently kept open for the queries, the most recently
+    /// queried last.
+    pub fn open_epochs(&self) -> Vec<EpochNumber> {
+        self.open_epochs
+            .lock()
+            .iter()
+            .map(|store| *store.epoch_number)
+            .collect()
+    }
+}
+
+impl<PendingStore, StateStore> EpochsStore<PendingStore, StateStore>
+where
+    PendingStore: PendingCertificateReader,
+    StateStore: StateReader,
+{
+    /// Open the epoch read-only for a query, reusing its handle if it is kept
+    /// open. A settled epoch is kept open afterwards, closing the least
+    /// recently queried one beyond the configured limit. An epoch still being
+    /// filled is opened for each query, a read-only handle not seeing the
+    /// later writes.
+    fn open_readonly(
+        &self,
+        epoch_number: EpochNumber,
+    ) -> Result<Arc<PerEpochStore<PendingStore, StateStore>>, Error> {
+        {
+            let mut open_epochs = self.open_epochs.lock();
+            if let Some(position) = open_epochs
+                .iter()
+                .position(|store| *store.epoch_number == epoch_number)
+            {
+                let store = open_epochs.remove(position).expect("position is in bounds");
+                open_epochs.push_back(store.clone());
+
+                return Ok(store);
+            }
+        }
+
+        // Use readonly access to prevent concurrency issues when multiple processes
+        // are accessing the database
+        let store = Arc::new(PerEpochStore::try_open_readonly(
+            self.config.clone(),
+            epoch_number,
+            self.pending_store.clone(),
+            self.state_store.clone(),
+        )?);
+
+        let max_open_epochs = self.config.storage.resources.max_open_epochs;
+        if store.is_epoch_packed() && max_open_epochs > 0 {
+            let mut open_epochs = self.open_epochs.lock();
+            if !open_epochs
+                .iter()
+                .any(|open| *open.epoch_number == epoch_number)
+            {
+                while open_epochs.len() >= max_open_epochs {
+                    if let Some(closed) = open_epochs.pop_front() {
+                        debug!(epoch_number = %closed.epoch_number, "Closing the epoch storage");
+                    }
+                }
+                open_epochs.push_back(store.clone());
+            }
+        }
+
+        Ok(store)
+    }
 }
 
 impl<PendingStore, StateStore> EpochStoreWriter for EpochsStore<PendingStore, StateStore>
@@ -53,14 +120,17 @@ where
         &self,
         epoch_number: EpochNumber,
     ) -> Result<PerEpochStore<PendingStore, StateStore>, Error> {
-        PerEpochStore::try_open(
+        let store = PerEpochStore::try_open(
             self.config.clone(),
             epoch_number,
             self.pending_store.clone(),
             self.state_store.clone(),
             None,
             self.backup_client.clone(),
-        )
+        )?;
+        store.resume_packing()?;
+
+        Ok(store)
     }
 
     fn open_with_start_checkpoint(
@@ -68,14 +138,17 @@ where
         epoch_number: EpochNumber,
         start_checkpoint: BTreeMap<NetworkId, Height>,
     ) -> Result<Self::PerEpochStore, Error> {
-        PerEpochStore::try_open(
+        let store = PerEpochStore::try_open(
             self.config.clone(),
             epoch_number,
             self.pending_store.clone(),
             self.state_store.clone(),
             Some(start_checkpoint),
             self.backup_client.clone(),
-        )
+        )?;
+        store.resume_packing()?;
+
+        Ok(store)
     }
 }
 
@@ -89,14 +162,7 @@ where
         epoch_number: EpochNumber,
         index: CertificateIndex,
     ) -> Result<Option<Certificate>, Error> {
-        // Use readonly access to prevent concurrency issues when multiple processes
-        // are accessing the database
-        let per_epoch_store = PerEpochStore::try_open_readonly(
-            self.config.clone(),
-            epoch_number,
-            self.pending_store.clone(),
-            self.state_store.clone(),
-        )?;
+        let per_epoch_store = self.open_readonly(epoch_number)?;
         per_epoch_store.get_certificate_at_index(index)
     }
 
@@ -105,14 +171,38 @@ where
         epoch_number: EpochNumber,
         index: CertificateIndex,
     ) -> Result<Option<agglayer_types::Proof>, Error> {
-        // Use readonly access to prevent concurrency issues when multiple processes
-        // are accessing the database
-        let per_epoch_store = PerEpochStore::try_open_readonly(
-            self.config.clone(),
-            epoch_number,
-            self.pending_store.clone(),
-            self.state_store.clone(),
-        )?;
+        let per_epoch_store = self.open_readonly(epoch_number)?;
         per_epoch_store.get_proof_at_index(index)
     }
+
+    fn get_encoded_proof(
+        &self,
+        epoch_number: EpochNumber,
+        index: CertificateIndex,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let per_epoch_store = self.open_readonly(epoch_number)?;
+        per_epoch_store.get_encoded_proof_at_index(index)
+    }
+
+    fn get_certificates(
+        &self,
+        epoch_number: EpochNumber,
+        from_index: CertificateIndex,
+        limit: usize,
+    ) -> Result<Vec<Certificate>, Error> {
+        let per_epoch_store = self.open_readonly(epoch_number)?;
+
+        let mut certificates = Vec::new();
+        for index in (from_index.as_u64()..).map(CertificateIndex::new) {
+            if certificates.len() >= limit {
+                break;
+            }
+            let Some(certificate) = per_epoch_store.get_certificate_at_index(index)? else {
+                break;
+            };
+            certificates.push(certificate);
+        }
+
+        Ok(certificates)
+    }
 }
@@ -1,16 +1,28 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use agglayer_types::{
-    Certificate, CertificateHeader, CertificateId, CertificateIndex, EpochNumber, Height,
-    LocalNetworkStateData, NetworkId, Proof,
+    Address, Certificate, CertificateHeader, CertificateId, CertificateIndex, Digest, EpochNumber,
+    EpochSettlementCosts, Height, LocalNetworkStateData, NetworkId, Proof, SettlementTxHash,
 };
+use pessimistic_proof::local_state::StateCommitment;
+use tokio::sync::watch;
 
 use crate::{
     columns::{
+        api_key_usage::ApiKeyUsage,
+        audit_log_per_certificate::AuditRecord,
+        callback_per_certificate::CertificateCallback,
+        certification_failure_per_certificate::CertificationFailure,
+        certified_roots_per_certificate::CertifiedRoots,
+        event_log::{EventId, LoggedEvent},
+        latest_pending_certificate_per_network::PendingCertificate,
         latest_proven_certificate_per_network::ProvenCertificate,
         latest_settled_certificate_per_network::SettledCertificate,
+        settlement_attempts_per_certificate::SettlementAttempt,
+        Codec as _,
     },
     error::Error,
+    storage::DBError,
 };
 
 pub mod network_info_reader;
@@ -18,6 +30,12 @@ pub mod network_info_reader;
 pub trait DebugReader: Send + Sync {
     fn get_certificate(&self, certificate_id: &CertificateId)
         -> Result<Option<Certificate>, Error>;
+
+    /// The last failed certification of the certificate, if recorded.
+    fn get_certification_failure(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificationFailure>, Error>;
 }
 
 pub trait EpochStoreReader: Send + Sync {
@@ -34,6 +52,27 @@ pub trait EpochStoreReader: Send + Sync {
         epoch_number: EpochNumber,
         index: CertificateIndex,
     ) -> Result<Option<Proof>, Error>;
+
+    /// Get a proof from a specific epoch by its index, as encoded in the
+    /// store, without decoding it.
+    fn get_encoded_proof(
+        &self,
+        epoch_number: EpochNumber,
+        index: CertificateIndex,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        self.get_proof(epoch_number, index)?
+            .map(|proof| proof.encode().map_err(|error| DBError::from(error).into()))
+            .transpose()
+    }
+
+    /// Get at most `limit` certificates of a specific epoch, in order,
+    /// starting at the given index.
+    fn get_certificates(
+        &self,
+        epoch_number: EpochNumber,
+        from_index: CertificateIndex,
+        limit: usize,
+    ) -> Result<Vec<Certificate>, Error>;
 }
 
 pub trait PendingCertificateReader: Send + Sync {
@@ -42,6 +81,13 @@ pub trait PendingCertificateReader: Send + Sync {
         network_id: &NetworkId,
     ) -> Result<Option<(CertificateId, Height)>, Error>;
 
+    /// Networks with a pending certificate, in ascending order.
+    fn get_pending_networks(&self) -> Result<Vec<NetworkId>, Error>;
+
+    /// Certificates of the pending queue, in ascending order of network and
+    /// height.
+    fn get_pending_certificates(&self) -> Result<Vec<(NetworkId, Height, CertificateId)>, Error>;
+
     fn get_certificate(
         &self,
         network_id: NetworkId,
@@ -50,6 +96,38 @@ pub trait PendingCertificateReader: Send + Sync {
 
     fn get_proof(&self, certificate_id: CertificateId) -> Result<Option<Proof>, Error>;
 
+    /// Get the generated proof of a certificate as encoded in the store,
+    /// without decoding it.
+    fn get_encoded_proof(&self, certificate_id: CertificateId) -> Result<Option<Vec<u8>>, Error> {
+        self.get_proof(certificate_id)?
+            .map(|proof| proof.encode().map_err(|error| DBError::from(error).into()))
+            .transpose()
+    }
+
+    /// Get the cached proof of a certificate, if it was generated from the
+    /// given initial roots.
+    fn get_cached_proof(
+        &self,
+        certificate_id: &CertificateId,
+        initial_roots: &StateCommitment,
+    ) -> Result<Option<Proof>, Error>;
+
+    /// Get the proof submitted for a certificate from outside of the agglayer.
+    fn get_submitted_proof(&self, certificate_id: &CertificateId) -> Result<Option<Proof>, Error>;
+
+    /// Get the roots of the network state before and after a certificate,
+    /// written along with its generated proof.
+    fn get_certified_roots(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertifiedRoots>, Error>;
+
+    /// Get the digest of the last proof of a certificate which was verified.
+    fn get_verified_proof(&self, certificate_id: &CertificateId) -> Result<Option<Digest>, Error>;
+
+    /// Certificates having a generated, cached or submitted proof.
+    fn get_proven_certificate_ids(&self) -> Result<BTreeSet<CertificateId>, Error>;
+
     fn multi_get_certificate(
         &self,
         keys: &[(NetworkId, Height)],
@@ -88,17 +166,114 @@ pub trait StateReader: Send + Sync {
         height: Height,
     ) -> Result<Option<CertificateHeader>, Error>;
 
+    /// Get the headers of the certificates, in the order of the given ids.
+    fn multi_get_certificate_header(
+        &self,
+        certificate_ids: &[CertificateId],
+    ) -> Result<Vec<Option<CertificateHeader>>, Error>;
+
+    /// Get the settlement transactions submitted for the certificate, in
+    /// submission order.
+    fn get_settlement_attempts(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Vec<SettlementAttempt>, Error>;
+
+    /// Get the account which sent the settlement transaction, if recorded.
+    fn get_settlement_sender(
+        &self,
+        settlement_tx_hash: &SettlementTxHash,
+    ) -> Result<Option<Address>, Error>;
+
+    /// Get the certificates whose settlement transaction reverted during the
+    /// epoch and which are not re-packed yet, in order.
+    fn get_reverted_settlements(
+        &self,
+        epoch_number: EpochNumber,
+    ) -> Result<Vec<CertificateId>, Error>;
+
+    /// Get the settlement costs of the network for each epoch of the inclusive
+    /// range in which at least one of its certificates was settled.
+    fn get_settlement_costs(
+        &self,
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    ) -> Result<Vec<EpochSettlementCosts>, Error>;
+
+    /// Get the callbacks registered for the certificates and not delivered
+    /// yet.
+    fn get_certificate_callbacks(&self)
+        -> Result<Vec<(CertificateId, CertificateCallback)>, Error>;
+
+    /// Get the callback registered for the certificate, if not delivered yet.
+    fn get_certificate_callback(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificateCallback>, Error>;
+
+    /// Get the audit log of the certificate, in chronological order.
+    fn get_audit_log(&self, certificate_id: &CertificateId) -> Result<Vec<AuditRecord>, Error>;
+
+    /// Get at most `limit` events of the event log, in order, starting right
+    /// after the given event or from the first one.
+    fn get_events(
+        &self,
+        after: Option<EventId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, LoggedEvent)>, Error>;
+
+    /// Get the identifier of the last event of the event log.
+    fn get_latest_event_id(&self) -> Result<Option<EventId>, Error>;
+
+    /// Watch the last event appended to the event log.
+    fn subscribe_events(&self) -> watch::Receiver<Option<EventId>>;
+
+    /// Get the usage of the API keys over the quota period, by name.
+    fn get_api_key_usage(&self, period: u64) -> Result<BTreeMap<String, ApiKeyUsage>, Error>;
+
     fn get_current_settled_height(&self) -> Result<Vec<(NetworkId, SettledCertificate)>, Error>;
     fn get_latest_settled_certificate_per_network(
         &self,
         network_id: &NetworkId,
     ) -> Result<Option<(NetworkId, SettledCertificate)>, Error>;
 
+    /// Get the latest pending certificate of the network, indexed along with
+    /// the certificate headers.
+    fn get_latest_pending_certificate_index(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<PendingCertificate>, Error>;
+
+    /// Get the latest proven certificate of the network, indexed along with
+    /// the certificate headers.
+    fn get_latest_proven_certificate_index(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<ProvenCertificate>, Error>;
+
     /// Get the local network state.
     fn read_local_network_state(
         &self,
         network_id: NetworkId,
     ) -> Result<Option<LocalNetworkStateData>, Error>;
+
+    /// Get the roots of the local network state once the certificate of the
+    /// network at the given height was settled. Only recorded for the
+    /// certificates settled since the roots are kept.
+    fn get_settled_roots(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<Option<StateCommitment>, Error>;
+
+    /// Get the leaves of the local exit tree of the network, in insertion
+    /// order.
+    fn read_local_exit_tree_leaves(&self, network_id: NetworkId) -> Result<Vec<Digest>, Error>;
+
+    /// Get the number of leaves of the local exit tree of the network, i.e.
+    /// the number of its settled bridge exits.
+    fn read_local_exit_tree_leaf_count(&self, network_id: NetworkId) -> Result<u32, Error>;
 }
 
 pub trait PerEpochReader: Send + Sync {
@@ -116,6 +291,16 @@ pub trait PerEpochReader: Send + Sync {
         index: CertificateIndex,
     ) -> Result<Option<Certificate>, Error>;
     fn get_proof_at_index(&self, index: CertificateIndex) -> Result<Option<Proof>, Error>;
+    /// Get the proof at the given index as encoded in the store, without
+    /// decoding it.
+    fn get_encoded_proof_at_index(
+        &self,
+        index: CertificateIndex,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        self.get_proof_at_index(index)?
+            .map(|proof| proof.encode().map_err(|error| DBError::from(error).into()))
+            .transpose()
+    }
     /// Get the height of a network's end checkpoint
     fn get_end_checkpoint_height_per_network(
         &self,
@@ -11,4 +11,8 @@ pub trait NetworkInfoReader: Send + Sync {
         &self,
         network_id: NetworkId,
     ) -> Result<Option<CertificateId>, Error>;
+
+    /// Networks with stored information or a settled certificate, in
+    /// ascending order.
+    fn get_known_networks(&self) -> Result<Vec<NetworkId>, Error>;
 }
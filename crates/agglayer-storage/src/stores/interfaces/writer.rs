@@ -1,14 +1,34 @@
 use std::collections::BTreeMap;
 
 use agglayer_types::{
-    primitives::Digest, Certificate, CertificateId, CertificateIndex, CertificateStatus,
-    EpochNumber, ExecutionMode, Height, LocalNetworkStateData, NetworkId, Proof, SettlementTxHash,
+    primitives::Digest, Address, Certificate, CertificateId, CertificateIndex, CertificateStatus,
+    EpochEvent, EpochNumber, ExecutionMode, Height, LocalNetworkStateData, NetworkId, Proof,
+    SettlementTxHash,
 };
+use pessimistic_proof::local_state::StateCommitment;
 
-use crate::{error::Error, stores::PerEpochReader};
+use crate::{
+    columns::{
+        api_key_usage::ApiKeyUsage, audit_log_per_certificate::AuditEvent,
+        callback_per_certificate::CertificateCallback,
+        certified_roots_per_certificate::CertifiedRoots,
+        certification_failure_per_certificate::CertificationFailure,
+        settlement_attempts_per_certificate::SettlementAttempt,
+    },
+    error::Error,
+    stores::PerEpochReader,
+};
 
 pub trait DebugWriter: Send + Sync {
     fn add_certificate(&self, certificate: &Certificate) -> Result<(), Error>;
+
+    /// Record the failed certification of the certificate, along with the
+    /// certificate itself, dropping the oldest failures beyond the limit.
+    fn add_certification_failure(
+        &self,
+        certificate: &Certificate,
+        failure: &CertificationFailure,
+    ) -> Result<(), Error>;
 }
 
 pub trait PerEpochWriter: Send + Sync {
@@ -49,6 +69,73 @@ pub trait StateWriter: Send + Sync {
         certificate_id: &CertificateId,
     ) -> Result<(), Error>;
 
+    /// Record the submission of a settlement transaction for the certificate.
+    fn record_settlement_attempt(
+        &self,
+        certificate_id: &CertificateId,
+        attempt: SettlementAttempt,
+    ) -> Result<(), Error>;
+
+    /// Record the account which sent the settlement transaction.
+    fn record_settlement_sender(
+        &self,
+        settlement_tx_hash: &SettlementTxHash,
+        sender: Address,
+    ) -> Result<(), Error>;
+
+    /// Record that the settlement transaction of the certificate reverted
+    /// during the epoch.
+    fn record_reverted_settlement(
+        &self,
+        epoch_number: EpochNumber,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error>;
+
+    /// Remove the reverted settlements recorded for the epoch, once
+    /// re-packed.
+    fn remove_reverted_settlements(&self, epoch_number: EpochNumber) -> Result<(), Error>;
+
+    /// Add the cost of the settlement of one certificate of the network to
+    /// the costs of the epoch.
+    fn record_settlement_cost(
+        &self,
+        network_id: NetworkId,
+        epoch_number: EpochNumber,
+        gas_used: u64,
+        fees: u128,
+    ) -> Result<(), Error>;
+
+    /// Register the callback to notify of the final status of the
+    /// certificate.
+    fn set_certificate_callback(
+        &self,
+        certificate_id: &CertificateId,
+        callback: &CertificateCallback,
+    ) -> Result<(), Error>;
+
+    /// Remove the callback of the certificate, once delivered.
+    fn remove_certificate_callback(&self, certificate_id: &CertificateId) -> Result<(), Error>;
+
+    /// Append an event to the audit log of the certificate. The status
+    /// changes and settlement submissions are recorded by the store itself.
+    fn record_audit_event(
+        &self,
+        certificate_id: &CertificateId,
+        event: AuditEvent,
+    ) -> Result<(), Error>;
+
+    /// Append the lifecycle event of an epoch to the event log.
+    fn record_epoch_event(&self, event: EpochEvent) -> Result<(), Error>;
+
+    /// Add to the usage of the API key over the quota period, returning the
+    /// resulting usage.
+    fn add_api_key_usage(
+        &self,
+        api_key: &str,
+        period: u64,
+        usage: &ApiKeyUsage,
+    ) -> Result<ApiKeyUsage, Error>;
+
     fn insert_certificate_header(
         &self,
         certificate: &Certificate,
@@ -77,6 +164,24 @@ pub trait StateWriter: Send + Sync {
         certificate_index: &CertificateIndex,
     ) -> Result<(), Error>;
 
+    /// Override the latest pending certificate of the network, which is
+    /// otherwise indexed along with the certificate headers.
+    fn set_latest_pending_certificate_index(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error>;
+
+    /// Override the latest proven certificate of the network, which is
+    /// otherwise indexed along with the certificate headers.
+    fn set_latest_proven_certificate_index(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error>;
+
     fn write_local_network_state(
         &self,
         network_id: &NetworkId,
@@ -107,6 +212,49 @@ pub trait PendingCertificateWriter: Send + Sync {
         proof: &Proof,
     ) -> Result<(), Error>;
 
+    /// Insert the proof generated for a certificate along with the roots of
+    /// the network state before and after it, in a single write, so that
+    /// neither is persisted without the other. The proof is also cached for
+    /// a retry from the same state when `cache` is set.
+    fn insert_certified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+        roots: &CertifiedRoots,
+        cache: bool,
+    ) -> Result<(), Error>;
+
+    /// Cache the proof of a certificate along with the initial roots it was
+    /// generated from, replacing any previously cached proof.
+    fn insert_cached_proof(
+        &self,
+        certificate_id: &CertificateId,
+        initial_roots: &StateCommitment,
+        proof: &Proof,
+    ) -> Result<(), Error>;
+
+    /// Remove the cached proof of a certificate, along with the marker of its
+    /// verified proof.
+    fn remove_cached_proof(&self, certificate_id: &CertificateId) -> Result<(), Error>;
+
+    /// Mark the proof of the given digest as verified for a certificate,
+    /// replacing any previously verified proof.
+    fn set_verified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof_digest: &Digest,
+    ) -> Result<(), Error>;
+
+    /// Store the proof of a certificate generated outside of the agglayer,
+    /// replacing any previously submitted proof.
+    fn insert_submitted_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+    ) -> Result<(), Error>;
+
+    fn remove_submitted_proof(&self, certificate_id: &CertificateId) -> Result<(), Error>;
+
     fn set_latest_proven_certificate_per_network(
         &self,
         network_id: &NetworkId,
@@ -1,11 +1,12 @@
-use std::{path::Path, sync::Arc};
+use std::{collections::BTreeSet, path::Path, sync::Arc};
 
-use agglayer_types::{Certificate, CertificateId, Height, NetworkId, Proof};
-use rocksdb::{Direction, ReadOptions};
+use agglayer_types::{Certificate, CertificateId, Digest, Height, NetworkId, Proof};
+use pessimistic_proof::local_state::StateCommitment;
 
 use super::{PendingCertificateReader, PendingCertificateWriter};
 use crate::{
     columns::{
+        certified_roots_per_certificate::{CertifiedRoots, CertifiedRootsPerCertificateColumn},
         latest_pending_certificate_per_network::{
             LatestPendingCertificatePerNetworkColumn, PendingCertificate,
         },
@@ -13,10 +14,13 @@ use crate::{
             LatestProvenCertificatePerNetworkColumn, ProvenCertificate,
         },
         pending_queue::{PendingQueueColumn, PendingQueueKey},
+        proof_cache_per_certificate::{CachedProof, ProofCachePerCertificateColumn},
         proof_per_certificate::ProofPerCertificateColumn,
+        submitted_proof_per_certificate::SubmittedProofPerCertificateColumn,
+        verified_proof_per_certificate::VerifiedProofPerCertificateColumn,
     },
     error::Error,
-    storage::DB,
+    storage::{Direction, WriteBatch, DB},
 };
 
 /// A logical store for pending.
@@ -102,10 +106,97 @@ impl PendingCertificateWriter for PendingStore {
     fn remove_generated_proof(
         &self,
         certificate_id: &agglayer_types::CertificateId,
+    ) -> Result<(), Error> {
+        let mut batch = WriteBatch::default();
+        self.db
+            .delete_batch::<ProofPerCertificateColumn>(certificate_id, &mut batch)?;
+        self.db
+            .delete_batch::<CertifiedRootsPerCertificateColumn>(certificate_id, &mut batch)?;
+
+        Ok(self.db.write_batch(batch)?)
+    }
+
+    fn insert_certified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+        roots: &CertifiedRoots,
+        cache: bool,
+    ) -> Result<(), Error> {
+        let mut batch = WriteBatch::default();
+        self.db.multi_insert_batch::<ProofPerCertificateColumn>(
+            [(certificate_id, proof)],
+            &mut batch,
+        )?;
+        self.db
+            .multi_insert_batch::<CertifiedRootsPerCertificateColumn>(
+                [(certificate_id, roots)],
+                &mut batch,
+            )?;
+        if cache {
+            let cached = CachedProof {
+                initial_roots: roots.initial_roots.clone(),
+                proof: proof.clone(),
+            };
+            self.db
+                .multi_insert_batch::<ProofCachePerCertificateColumn>(
+                    [(certificate_id, &cached)],
+                    &mut batch,
+                )?;
+        }
+
+        Ok(self.db.write_batch(batch)?)
+    }
+
+    fn insert_cached_proof(
+        &self,
+        certificate_id: &CertificateId,
+        initial_roots: &StateCommitment,
+        proof: &Proof,
+    ) -> Result<(), Error> {
+        Ok(self.db.put::<ProofCachePerCertificateColumn>(
+            certificate_id,
+            &CachedProof {
+                initial_roots: initial_roots.clone(),
+                proof: proof.clone(),
+            },
+        )?)
+    }
+
+    fn remove_cached_proof(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        let mut batch = WriteBatch::default();
+        self.db
+            .delete_batch::<ProofCachePerCertificateColumn>(certificate_id, &mut batch)?;
+        self.db
+            .delete_batch::<VerifiedProofPerCertificateColumn>(certificate_id, &mut batch)?;
+
+        Ok(self.db.write_batch(batch)?)
+    }
+
+    fn set_verified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof_digest: &Digest,
     ) -> Result<(), Error> {
         Ok(self
             .db
-            .delete::<ProofPerCertificateColumn>(certificate_id)?)
+            .put::<VerifiedProofPerCertificateColumn>(certificate_id, proof_digest)?)
+    }
+
+    fn insert_submitted_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+    ) -> Result<(), Error> {
+        Ok(self
+            .db
+            .put::<SubmittedProofPerCertificateColumn>(certificate_id, proof)?)
+    }
+
+    fn remove_submitted_proof(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        Ok(self
+            .db
+            .delete::<SubmittedProofPerCertificateColumn>(certificate_id)?)
     }
 
     fn set_latest_proven_certificate_per_network(
@@ -132,6 +223,23 @@ impl PendingCertificateReader for PendingStore {
             .map(|v| v.map(|PendingCertificate(id, height)| (id, height)))?)
     }
 
+    fn get_pending_networks(&self) -> Result<Vec<NetworkId>, Error> {
+        Ok(self
+            .db
+            .keys::<LatestPendingCertificatePerNetworkColumn>()?
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn get_pending_certificates(&self) -> Result<Vec<(NetworkId, Height, CertificateId)>, Error> {
+        self.db
+            .iter_with_direction::<PendingQueueColumn>(None, Direction::Forward)?
+            .map(|entry| {
+                let (PendingQueueKey(network_id, height), certificate) = entry?;
+                Ok((network_id, height, certificate.hash()))
+            })
+            .collect()
+    }
+
     fn get_certificate(
         &self,
         network_id: NetworkId,
@@ -146,11 +254,64 @@ impl PendingCertificateReader for PendingStore {
         Ok(self.db.get::<ProofPerCertificateColumn>(&certificate_id)?)
     }
 
+    fn get_encoded_proof(&self, certificate_id: CertificateId) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self
+            .db
+            .get_encoded::<ProofPerCertificateColumn>(&certificate_id)?)
+    }
+
+    fn get_cached_proof(
+        &self,
+        certificate_id: &CertificateId,
+        initial_roots: &StateCommitment,
+    ) -> Result<Option<Proof>, Error> {
+        Ok(self
+            .db
+            .get::<ProofCachePerCertificateColumn>(certificate_id)?
+            .filter(|cached| cached.initial_roots == *initial_roots)
+            .map(|cached| cached.proof))
+    }
+
+    fn get_submitted_proof(&self, certificate_id: &CertificateId) -> Result<Option<Proof>, Error> {
+        Ok(self
+            .db
+            .get::<SubmittedProofPerCertificateColumn>(certificate_id)?)
+    }
+
+    fn get_certified_roots(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertifiedRoots>, Error> {
+        Ok(self
+            .db
+            .get::<CertifiedRootsPerCertificateColumn>(certificate_id)?)
+    }
+
+    fn get_verified_proof(&self, certificate_id: &CertificateId) -> Result<Option<Digest>, Error> {
+        Ok(self
+            .db
+            .get::<VerifiedProofPerCertificateColumn>(certificate_id)?)
+    }
+
+    fn get_proven_certificate_ids(&self) -> Result<BTreeSet<CertificateId>, Error> {
+        let mut certificate_ids = BTreeSet::new();
+        for certificate_id in self
+            .db
+            .keys::<ProofPerCertificateColumn>()?
+            .chain(self.db.keys::<ProofCachePerCertificateColumn>()?)
+            .chain(self.db.keys::<SubmittedProofPerCertificateColumn>()?)
+        {
+            certificate_ids.insert(certificate_id?);
+        }
+
+        Ok(certificate_ids)
+    }
+
     fn get_current_proven_height(&self) -> Result<Vec<ProvenCertificate>, Error> {
         Ok(self
             .db
             .iter_with_direction::<LatestProvenCertificatePerNetworkColumn>(
-                ReadOptions::default(),
+                None,
                 Direction::Forward,
             )?
             .filter_map(|v| v.map(|(_, certificate)| certificate).ok())
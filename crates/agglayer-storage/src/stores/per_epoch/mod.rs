@@ -11,7 +11,6 @@ use agglayer_types::{
     Proof,
 };
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
-use rocksdb::ReadOptions;
 use tracing::{debug, error, warn};
 
 use super::{
@@ -25,8 +24,8 @@ use crate::{
         start_checkpoint::StartCheckpointColumn,
     },
     error::{CertificateCandidateError, Error},
-    storage::{backup::BackupClient, epochs_db_cf_definitions, DB},
-    types::{PerEpochMetadataKey, PerEpochMetadataValue},
+    storage::{backup::BackupClient, Direction, DB, EPOCHS_DB_CFS},
+    types::{PackingIntent, PerEpochMetadataKey, PerEpochMetadataValue},
 };
 
 #[cfg(test)]
@@ -59,7 +58,11 @@ impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore> {
     ) -> Result<Self, Error> {
 
 
-        let db = Arc::new(DB::open_cf(&config.storage.epoch_db_path(epoch_number), epochs_db_cf_definitions())?);
+        let db = Arc::new(DB::open_cf_with_resources(
+            &config.storage.epoch_db_path(epoch_number),
+            &EPOCHS_DB_CFS,
+            &config.storage.resources.epochs,
+        )?);
         
         Self::try_open_with_db(
             db,
@@ -81,7 +84,11 @@ impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore> {
         state_store: Arc<StateStore>,
     ) -> Result<Self, Error> {
         
-        let db = Arc::new(DB::open_cf_readonly(&config.storage.epoch_db_path(epoch_number), epochs_db_cf_definitions())?);
+        let db = Arc::new(DB::open_cf_readonly_with_resources(
+            &config.storage.epoch_db_path(epoch_number),
+            &EPOCHS_DB_CFS,
+            &config.storage.resources.epochs,
+        )?);
         
         Self::try_open_with_db(
             db,
@@ -108,21 +115,18 @@ impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore> {
         let packed = db
             .get::<PerEpochMetadataColumn>(&PerEpochMetadataKey::Packed)?
             .map(|value| match value {
-                PerEpochMetadataValue::SettlementTxHash(_digest) => Err(Error::Unexpected(
+                PerEpochMetadataValue::Packed(value) => Ok(value),
+                _ => Err(Error::Unexpected(
                     "Tried to retrieve the status of an epoch, retrieve another unexpected value"
                         .to_string(),
                 )),
-                PerEpochMetadataValue::Packed(value) => Ok(value),
             })
             .transpose()?
             .unwrap_or_default();
 
         let start_checkpoint = {
             let checkpoint = db
-                .iter_with_direction::<StartCheckpointColumn>(
-                    ReadOptions::default(),
-                    rocksdb::Direction::Forward,
-                )?
+                .iter_with_direction::<StartCheckpointColumn>(None, Direction::Forward)?
                 .filter_map(|v| v.ok())
                 .collect::<BTreeMap<NetworkId, Height>>();
 
@@ -161,10 +165,7 @@ impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore> {
         } else {
             // For read-write access, calculate the next index from existing certificates
             if let Some(Ok((index, _))) = db
-                .iter_with_direction::<CertificatePerIndexColumn>(
-                    ReadOptions::default(),
-                    rocksdb::Direction::Reverse,
-                )?
+                .iter_with_direction::<CertificatePerIndexColumn>(None, Direction::Reverse)?
                 .next()
             {
                 // We're starting from the next index after the last one found in the database.
@@ -176,10 +177,7 @@ impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore> {
 
         let end_checkpoint = {
             let checkpoint = db
-                .iter_with_direction::<EndCheckpointColumn>(
-                    ReadOptions::default(),
-                    rocksdb::Direction::Forward,
-                )?
+                .iter_with_direction::<EndCheckpointColumn>(None, Direction::Forward)?
                 .filter_map(|v| v.ok())
                 .collect::<BTreeMap<NetworkId, Height>>();
 
@@ -222,6 +220,149 @@ impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore> {
     fn lock_for_packing(&self) -> RwLockWriteGuard<'_, bool> {
         self.packing_lock.write()
     }
+
+    fn get_packing_intent(&self) -> Result<Option<PackingIntent>, Error> {
+        self.db
+            .get::<PerEpochMetadataColumn>(&PerEpochMetadataKey::PackingIntent)?
+            .map(|value| match value {
+                PerEpochMetadataValue::PackingIntent(intent) => Ok(intent),
+                _ => Err(Error::Unexpected(
+                    "Tried to retrieve the packing intent of an epoch, retrieve another \
+                     unexpected value"
+                        .to_string(),
+                )),
+            })
+            .transpose()
+    }
+
+    /// Ids of the certificates of the epoch, in the order of their index.
+    fn get_certificate_ids(&self) -> Result<Vec<CertificateId>, Error> {
+        self.db
+            .iter_with_direction::<CertificatePerIndexColumn>(None, Direction::Forward)?
+            .map(|entry| {
+                entry
+                    .map(|(_, certificate)| certificate.hash())
+                    .map_err(Error::from)
+            })
+            .collect()
+    }
+}
+
+impl<PendingStore, StateStore> PerEpochStore<PendingStore, StateStore>
+where
+    PendingStore: PendingCertificateReader + PendingCertificateWriter,
+    StateStore: MetadataWriter + StateWriter + StateReader,
+{
+    /// Resume the packing of the epoch if it was interrupted, as recorded by
+    /// its packing intent.
+    ///
+    /// The certificates of the epoch are checked against the intent, and the
+    /// ones which were not fully moved out of the pending store are, so that
+    /// they can't be settled again.
+    pub fn resume_packing(&self) -> Result<(), Error> {
+        let Some(intent) = self.get_packing_intent()? else {
+            return Ok(());
+        };
+
+        let mut lock = self.lock_for_packing();
+
+        warn!(
+            epoch_number = %self.epoch_number,
+            "Resuming the interrupted packing of the epoch"
+        );
+
+        let certificates = self.get_certificate_ids()?;
+        if certificates != intent.certificates {
+            error!(
+                epoch_number = %self.epoch_number,
+                expected = ?intent.certificates,
+                found = ?certificates,
+                "CRITICAL: The certificates of the epoch don't match its packing intent"
+            );
+
+            return Err(Error::Unexpected(format!(
+                "The certificates of the epoch {} don't match its packing intent",
+                self.epoch_number
+            )));
+        }
+
+        for (index, certificate_id) in intent.certificates.iter().enumerate() {
+            self.reconcile_certificate(certificate_id, CertificateIndex::new(index as u64))?;
+        }
+
+        self.complete_packing(&mut lock)
+    }
+
+    fn reconcile_certificate(
+        &self,
+        certificate_id: &CertificateId,
+        certificate_index: CertificateIndex,
+    ) -> Result<(), Error> {
+        let header = self
+            .state_store
+            .get_certificate_header(certificate_id)?
+            .ok_or(Error::NoCertificateHeader)?;
+
+        if self
+            .pending_store
+            .get_certificate(header.network_id, header.height)?
+            .is_some_and(|certificate| certificate.hash() == *certificate_id)
+        {
+            warn!(%certificate_id, "Removing packed certificate from pending store");
+
+            self.pending_store.remove_generated_proof(certificate_id)?;
+            self.pending_store.remove_cached_proof(certificate_id)?;
+            self.pending_store.remove_submitted_proof(certificate_id)?;
+            self.pending_store
+                .remove_pending_certificate(header.network_id, header.height)?;
+        }
+
+        if header.epoch_number.is_none() {
+            warn!(%certificate_id, "Assigning packed certificate to epoch");
+
+            self.state_store.assign_certificate_to_epoch(
+                certificate_id,
+                &self.epoch_number,
+                &certificate_index,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Mark the epoch as packed and settled, then drop its packing intent.
+    fn complete_packing(&self, lock: &mut RwLockWriteGuard<'_, bool>) -> Result<(), Error> {
+        self.db.put::<PerEpochMetadataColumn>(
+            &PerEpochMetadataKey::Packed,
+            &PerEpochMetadataValue::Packed(true),
+        )?;
+
+        if let Err(error) = self
+            .backup_client
+            .backup(crate::storage::backup::BackupRequest {
+                epoch_db: Some((self.db.clone(), *self.epoch_number)),
+            })
+        {
+            error!("Couldn't trigger the backup of the epoch DB: {}", error);
+        }
+
+        **lock = true;
+        match self
+            .state_store
+            .set_latest_settled_epoch(*self.epoch_number)
+        {
+            Err(Error::UnprocessedAction(error)) => {
+                warn!("Couldn't define the latest settled epoch: {}", error)
+            }
+            Err(error) => return Err(error),
+            Ok(_) => (),
+        }
+
+        self.db
+            .delete::<PerEpochMetadataColumn>(&PerEpochMetadataKey::PackingIntent)?;
+
+        Ok(())
+    }
 }
 
 impl<PendingStore, StateStore> PerEpochWriter for PerEpochStore<PendingStore, StateStore>
@@ -382,8 +523,10 @@ where
         self.db
             .put::<ProofPerIndexColumn>(&certificate_index, &proof)?;
 
-        // Removing the certificate and proof from the pending store
+        // Removing the certificate and proofs from the pending store
         self.pending_store.remove_generated_proof(&certificate_id)?;
+        self.pending_store.remove_cached_proof(&certificate_id)?;
+        self.pending_store.remove_submitted_proof(&certificate_id)?;
 
         self.pending_store
             .remove_pending_certificate(network_id, height)?;
@@ -427,33 +570,18 @@ where
             return Err(Error::AlreadyPacked(*self.epoch_number))?;
         }
 
+        // Record the intent before packing, for an interrupted packing to be
+        // resumed on restart.
+        let intent = PackingIntent {
+            epoch_number: *self.epoch_number,
+            certificates: self.get_certificate_ids()?,
+        };
         self.db.put::<PerEpochMetadataColumn>(
-            &PerEpochMetadataKey::Packed,
-            &PerEpochMetadataValue::Packed(true),
+            &PerEpochMetadataKey::PackingIntent,
+            &PerEpochMetadataValue::PackingIntent(intent),
         )?;
 
-        if let Err(error) = self
-            .backup_client
-            .backup(crate::storage::backup::BackupRequest {
-                epoch_db: Some((self.db.clone(), *self.epoch_number)),
-            })
-        {
-            error!("Couldn't trigger the backup of the epoch DB: {}", error);
-        }
-
-        *lock = true;
-        match self
-            .state_store
-            .set_latest_settled_epoch(*self.epoch_number)
-        {
-            Err(Error::UnprocessedAction(error)) => {
-                warn!("Couldn't define the latest settled epoch: {}", error)
-            }
-            Err(error) => return Err(error),
-            Ok(_) => (),
-        }
-
-        Ok(())
+        self.complete_packing(&mut lock)
     }
 }
 
@@ -480,6 +608,13 @@ where
         Ok(self.db.get::<ProofPerIndexColumn>(&index)?)
     }
 
+    fn get_encoded_proof_at_index(
+        &self,
+        index: CertificateIndex,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.db.get_encoded::<ProofPerIndexColumn>(&index)?)
+    }
+
     fn get_start_checkpoint(&self) -> &BTreeMap<NetworkId, Height> {
         &self.start_checkpoint
     }
@@ -9,7 +9,10 @@ use agglayer_types::{Height, NetworkId, Proof};
 use parking_lot::RwLock;
 use rstest::{fixture, rstest};
 
-use crate::stores::{PendingCertificateWriter as _, PerEpochReader as _, StateReader};
+use crate::stores::{
+    epochs::EpochsStore, EpochStoreReader as _, PendingCertificateWriter as _, PerEpochReader as _,
+    StateReader,
+};
 use crate::{
     error::Error,
     stores::{
@@ -382,3 +385,300 @@ fn can_retrieve_proof_at_index() {
     let non_existent_proof = store.get_proof_at_index(CertificateIndex::new(1)).unwrap();
     assert!(non_existent_proof.is_none(), "Should return None for non-existent index");
 }
+
+#[rstest]
+fn cached_and_submitted_proofs_are_dropped_once_settled(
+    store: PerEpochStore<PendingStore, StateStore>,
+) {
+    use pessimistic_proof::local_state::StateCommitment;
+
+    use crate::stores::PendingCertificateReader as _;
+
+    let network = 0.into();
+    let height = Height::ZERO;
+    let certificate = Certificate::new_for_test(network, height);
+    let certificate_id = certificate.hash();
+    let pending_store = store.pending_store.clone();
+    let state_store = store.state_store.clone();
+    let initial_roots = StateCommitment::default();
+
+    state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Proven)
+        .unwrap();
+    pending_store
+        .insert_pending_certificate(network, height, &certificate)
+        .unwrap();
+    pending_store
+        .insert_generated_proof(&certificate_id, &Proof::dummy())
+        .unwrap();
+    pending_store
+        .insert_cached_proof(&certificate_id, &initial_roots, &Proof::dummy())
+        .unwrap();
+    pending_store
+        .insert_submitted_proof(&certificate_id, &Proof::dummy())
+        .unwrap();
+
+    // The cached proof is only returned for the roots it was generated from.
+    assert!(pending_store
+        .get_cached_proof(&certificate_id, &initial_roots)
+        .unwrap()
+        .is_some());
+    let other_roots = StateCommitment {
+        ler_leaf_count: 1,
+        ..Default::default()
+    };
+    assert!(pending_store
+        .get_cached_proof(&certificate_id, &other_roots)
+        .unwrap()
+        .is_none());
+
+    store
+        .add_certificate(certificate_id, agglayer_types::ExecutionMode::Default)
+        .unwrap();
+
+    assert!(pending_store
+        .get_cached_proof(&certificate_id, &initial_roots)
+        .unwrap()
+        .is_none());
+    assert!(pending_store
+        .get_submitted_proof(&certificate_id)
+        .unwrap()
+        .is_none());
+}
+
+/// Add a certificate for the given network at height zero to the epoch.
+fn add_certificate_for_test(
+    store: &PerEpochStore<PendingStore, StateStore>,
+    network: NetworkId,
+) -> Certificate {
+    let certificate = Certificate::new_for_test(network, Height::ZERO);
+    let certificate_id = certificate.hash();
+
+    store
+        .state_store
+        .insert_certificate_header(&certificate, CertificateStatus::Proven)
+        .unwrap();
+    store
+        .pending_store
+        .insert_pending_certificate(network, Height::ZERO, &certificate)
+        .unwrap();
+    store
+        .pending_store
+        .insert_generated_proof(&certificate_id, &Proof::dummy())
+        .unwrap();
+    store
+        .add_certificate(certificate_id, agglayer_types::ExecutionMode::Default)
+        .unwrap();
+
+    certificate
+}
+
+#[rstest]
+fn epoch_certificates_are_listed_from_an_index() {
+    let tmp = TempDBDir::new();
+    let config = Arc::new(Config::new(&tmp.path));
+    let pending_store =
+        Arc::new(PendingStore::new_with_path(&config.storage.pending_db_path).unwrap());
+    let state_store = Arc::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop()).unwrap(),
+    );
+
+    let store = PerEpochStore::try_open(
+        config.clone(),
+        EpochNumber::ZERO,
+        pending_store.clone(),
+        state_store.clone(),
+        None,
+        BackupClient::noop(),
+    )
+    .unwrap();
+    let certificates = [1, 2, 3].map(|network| add_certificate_for_test(&store, network.into()));
+
+    let epochs_store = EpochsStore::new(
+        config,
+        EpochNumber::ZERO,
+        pending_store,
+        state_store,
+        BackupClient::noop(),
+    )
+    .unwrap();
+
+    let listed = epochs_store
+        .get_certificates(EpochNumber::ZERO, CertificateIndex::new(1), 10)
+        .unwrap();
+    assert_eq!(listed, certificates[1..]);
+
+    let listed = epochs_store
+        .get_certificates(EpochNumber::ZERO, CertificateIndex::ZERO, 1)
+        .unwrap();
+    assert_eq!(listed, certificates[..1]);
+}
+
+#[rstest]
+fn settled_epochs_are_kept_open_up_to_the_limit() {
+    let tmp = TempDBDir::new();
+    let mut config = Config::new(&tmp.path);
+    config.storage.resources.max_open_epochs = 2;
+    let config = Arc::new(config);
+    let pending_store =
+        Arc::new(PendingStore::new_with_path(&config.storage.pending_db_path).unwrap());
+    let state_store = Arc::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop()).unwrap(),
+    );
+
+    for epoch in 0..4 {
+        let store = PerEpochStore::try_open(
+            config.clone(),
+            EpochNumber::new(epoch),
+            pending_store.clone(),
+            state_store.clone(),
+            None,
+            BackupClient::noop(),
+        )
+        .unwrap();
+        add_certificate_for_test(&store, (epoch as u32 + 1).into());
+        if epoch < 3 {
+            store.start_packing().unwrap();
+        }
+    }
+
+    let epochs_store = EpochsStore::new(
+        config,
+        EpochNumber::new(3),
+        pending_store,
+        state_store,
+        BackupClient::noop(),
+    )
+    .unwrap();
+    let query = |epoch| {
+        epochs_store
+            .get_certificate(EpochNumber::new(epoch), CertificateIndex::ZERO)
+            .unwrap()
+            .unwrap()
+    };
+
+    query(0);
+    query(1);
+    query(0);
+    assert_eq!(
+        epochs_store.open_epochs(),
+        [EpochNumber::new(1), EpochNumber::ZERO]
+    );
+
+    query(2);
+    assert_eq!(
+        epochs_store.open_epochs(),
+        [EpochNumber::ZERO, EpochNumber::new(2)]
+    );
+
+    // The current epoch is still being filled.
+    query(3);
+    assert_eq!(
+        epochs_store.open_epochs(),
+        [EpochNumber::ZERO, EpochNumber::new(2)]
+    );
+}
+
+#[rstest]
+fn packing_drops_the_packing_intent(store: PerEpochStore<PendingStore, StateStore>) {
+    add_certificate_for_test(&store, 1.into());
+
+    store.start_packing().unwrap();
+
+    assert!(store.is_epoch_packed());
+    assert_eq!(store.get_packing_intent().unwrap(), None);
+}
+
+#[rstest]
+fn interrupted_packing_is_resumed_on_restart() {
+    use crate::{
+        columns::epochs::metadata::PerEpochMetadataColumn,
+        stores::{MetadataReader as _, PendingCertificateReader as _},
+        types::{PackingIntent, PerEpochMetadataKey, PerEpochMetadataValue},
+    };
+
+    let tmp = TempDBDir::new();
+    let config = Arc::new(Config::new(&tmp.path));
+    let pending_store =
+        Arc::new(PendingStore::new_with_path(&config.storage.pending_db_path).unwrap());
+    let state_store = Arc::new(
+        StateStore::new_with_path(&config.storage.state_db_path, BackupClient::noop()).unwrap(),
+    );
+    let store = PerEpochStore::try_open(
+        config.clone(),
+        EpochNumber::ZERO,
+        pending_store.clone(),
+        state_store.clone(),
+        None,
+        BackupClient::noop(),
+    )
+    .unwrap();
+
+    let certificate = add_certificate_for_test(&store, 1.into());
+    let certificate_id = certificate.hash();
+
+    // Crash after recording the intent, with the certificate left behind in
+    // the pending store.
+    store
+        .db
+        .put::<PerEpochMetadataColumn>(
+            &PerEpochMetadataKey::PackingIntent,
+            &PerEpochMetadataValue::PackingIntent(PackingIntent {
+                epoch_number: EpochNumber::ZERO,
+                certificates: vec![certificate_id],
+            }),
+        )
+        .unwrap();
+    pending_store
+        .insert_pending_certificate(certificate.network_id, certificate.height, &certificate)
+        .unwrap();
+    drop(store);
+
+    let store = PerEpochStore::try_open(
+        config,
+        EpochNumber::ZERO,
+        pending_store.clone(),
+        state_store.clone(),
+        None,
+        BackupClient::noop(),
+    )
+    .unwrap();
+    assert!(!store.is_epoch_packed());
+
+    store.resume_packing().unwrap();
+
+    assert!(store.is_epoch_packed());
+    assert_eq!(store.get_packing_intent().unwrap(), None);
+    assert!(pending_store
+        .get_certificate(certificate.network_id, certificate.height)
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        state_store.get_latest_settled_epoch().unwrap(),
+        Some(EpochNumber::ZERO)
+    );
+}
+
+#[rstest]
+fn resuming_packing_rejects_a_mismatching_intent(store: PerEpochStore<PendingStore, StateStore>) {
+    use crate::{
+        columns::epochs::metadata::PerEpochMetadataColumn,
+        types::{PackingIntent, PerEpochMetadataKey, PerEpochMetadataValue},
+    };
+
+    add_certificate_for_test(&store, 1.into());
+
+    store
+        .db
+        .put::<PerEpochMetadataColumn>(
+            &PerEpochMetadataKey::PackingIntent,
+            &PerEpochMetadataValue::PackingIntent(PackingIntent {
+                epoch_number: EpochNumber::ZERO,
+                certificates: vec![],
+            }),
+        )
+        .unwrap();
+
+    assert!(matches!(store.resume_packing(), Err(Error::Unexpected(_))));
+    assert!(!store.is_epoch_packed());
+}
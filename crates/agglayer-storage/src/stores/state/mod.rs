@@ -6,35 +6,55 @@ use std::{
 
 use agglayer_tries::{node::Node, smt::Smt};
 use agglayer_types::{
-    primitives::Digest, Certificate, CertificateHeader, CertificateId, CertificateIndex,
-    CertificateStatus, EpochNumber, Height, LocalNetworkStateData, NetworkId, SettlementTxHash,
+    primitives::Digest, Address, Certificate, CertificateHeader, CertificateId, CertificateIndex,
+    CertificateStatus, EpochEvent, EpochNumber, EpochSettlementCosts, Height,
+    LocalNetworkStateData, NetworkId, SettlementTxHash,
 };
+use parking_lot::Mutex;
 use pessimistic_proof::{
-    local_balance_tree::LOCAL_BALANCE_TREE_DEPTH, nullifier_tree::NULLIFIER_TREE_DEPTH,
+    local_balance_tree::LOCAL_BALANCE_TREE_DEPTH, local_exit_tree::LOCAL_EXIT_TREE_DEPTH,
+    local_state::StateCommitment, nullifier_tree::NULLIFIER_TREE_DEPTH,
     unified_bridge::LocalExitTree,
 };
-use rocksdb::{Direction, ReadOptions, WriteBatch};
+use tokio::sync::watch;
 use tracing::{info, warn};
 
 use self::LET::LocalExitTreePerNetworkColumn;
 use super::{MetadataReader, MetadataWriter, StateReader, StateWriter};
 use crate::{
     columns::{
+        api_key_usage::{self, ApiKeyUsage, ApiKeyUsageColumn},
+        audit_log_per_certificate::{AuditEvent, AuditLogPerCertificateColumn, AuditRecord},
         balance_tree_per_network::BalanceTreePerNetworkColumn,
+        callback_per_certificate::{CallbackPerCertificateColumn, CertificateCallback},
         certificate_header::CertificateHeaderColumn,
         certificate_per_network::{self, CertificatePerNetworkColumn},
+        event_log::{Event, EventId, EventLogColumn, LoggedEvent},
+        latest_pending_certificate_per_network::{
+            LatestPendingCertificatePerNetworkColumn, PendingCertificate,
+        },
+        latest_proven_certificate_per_network::{
+            LatestProvenCertificatePerNetworkColumn, ProvenCertificate,
+        },
         latest_settled_certificate_per_network::{
             LatestSettledCertificatePerNetworkColumn, SettledCertificate,
         },
         local_exit_tree_per_network as LET,
         metadata::MetadataColumn,
         nullifier_tree_per_network::NullifierTreePerNetworkColumn,
+        reverted_settlements_per_epoch::RevertedSettlementsPerEpochColumn,
+        settled_roots_per_network::{self, SettledRootsPerNetworkColumn},
+        settlement_attempts_per_certificate::{
+            SettlementAttempt, SettlementAttemptsPerCertificateColumn,
+        },
+        settlement_costs_per_network::{self, SettlementCostsPerNetworkColumn},
+        settlement_sender_per_tx::SettlementSenderPerTxColumn,
         ColumnSchema,
     },
     error::Error,
     storage::{
         backup::{BackupClient, BackupRequest},
-        DB,
+        Direction, WriteBatch, DB,
     },
     types::{MetadataKey, MetadataValue, SmtKey, SmtKeyType, SmtValue},
 };
@@ -46,13 +66,28 @@ mod tests;
 pub struct StateStore {
     db: Arc<DB>,
     backup_client: BackupClient,
+    /// Identifier of the next event of the event log, read from the log on
+    /// the first append. Held while appending so that the events are written
+    /// in order.
+    next_event_id: Mutex<Option<EventId>>,
+    /// Held while updating the usage of the API keys, so that the concurrent
+    /// requests are all accounted for.
+    api_key_usage_lock: Mutex<()>,
+    /// Last event appended to the event log by this store.
+    appended_events: watch::Sender<Option<EventId>>,
 }
 
 mod network_info;
 
 impl StateStore {
     pub fn new(db: Arc<DB>, backup_client: BackupClient) -> Self {
-        Self { db, backup_client }
+        Self {
+            db,
+            backup_client,
+            next_event_id: Mutex::new(None),
+            api_key_usage_lock: Mutex::new(()),
+            appended_events: watch::Sender::new(None),
+        }
     }
 
     pub fn new_with_path(path: &Path, backup_client: BackupClient) -> Result<Self, Error> {
@@ -61,7 +96,152 @@ impl StateStore {
             crate::storage::state_db_cf_definitions(),
         )?);
 
-        Ok(Self { db, backup_client })
+        Ok(Self::new(db, backup_client))
+    }
+
+    /// Append a record of the event to the audit log of the certificate, and
+    /// to the event log.
+    fn append_audit_record(
+        &self,
+        certificate_id: &CertificateId,
+        event: AuditEvent,
+    ) -> Result<(), Error> {
+        self.write_with_audit_record(WriteBatch::default(), certificate_id, event)
+    }
+
+    /// Write the batch along with the record of the event appended to the
+    /// audit log of the certificate, and to the event log.
+    fn write_with_audit_record(
+        &self,
+        mut batch: WriteBatch,
+        certificate_id: &CertificateId,
+        event: AuditEvent,
+    ) -> Result<(), Error> {
+        // TODO: make lockguard for certificate_id
+        let timestamp = chrono::Utc::now();
+        let mut records = self
+            .db
+            .get::<AuditLogPerCertificateColumn>(certificate_id)?
+            .unwrap_or_default();
+        records.push(AuditRecord {
+            timestamp,
+            event: event.clone(),
+        });
+
+        self.db.multi_insert_batch::<AuditLogPerCertificateColumn>(
+            [(certificate_id, &records)],
+            &mut batch,
+        )?;
+
+        self.write_with_logged_event(
+            batch,
+            LoggedEvent {
+                timestamp,
+                event: Event::Certificate {
+                    certificate_id: *certificate_id,
+                    event,
+                },
+            },
+        )
+    }
+
+    /// Write the batch along with the event appended to the event log, then
+    /// notify the subscribers of the event log.
+    fn write_with_logged_event(
+        &self,
+        mut batch: WriteBatch,
+        event: LoggedEvent,
+    ) -> Result<(), Error> {
+        let mut next_event_id = self.next_event_id.lock();
+        let event_id = match *next_event_id {
+            Some(event_id) => event_id,
+            None => self
+                .get_latest_event_id()?
+                .map_or(EventId::default(), |event_id| event_id.next()),
+        };
+
+        self.db
+            .multi_insert_batch::<EventLogColumn>([(&event_id, &event)], &mut batch)?;
+        self.db.write_batch(batch)?;
+
+        *next_event_id = Some(event_id.next());
+        drop(next_event_id);
+
+        self.appended_events.send_replace(Some(event_id));
+
+        Ok(())
+    }
+
+    /// Add to the batch the writes of the certificate header, of the latest
+    /// pending or proven certificate of its network when the header is at or
+    /// above it, and of the certificate of the network at its height once
+    /// settled.
+    fn certificate_header_batch(
+        &self,
+        header: &CertificateHeader,
+        batch: &mut WriteBatch,
+    ) -> Result<(), Error> {
+        let network_id = &header.network_id;
+
+        self.db.multi_insert_batch::<CertificateHeaderColumn>(
+            [(&header.certificate_id, header)],
+            batch,
+        )?;
+
+        match header.status {
+            CertificateStatus::Pending => {
+                let latest = self
+                    .db
+                    .get::<LatestPendingCertificatePerNetworkColumn>(network_id)?;
+                if !matches!(latest, Some(PendingCertificate(_, height)) if height > header.height)
+                {
+                    self.db
+                        .multi_insert_batch::<LatestPendingCertificatePerNetworkColumn>(
+                            [(
+                                network_id,
+                                &PendingCertificate(header.certificate_id, header.height),
+                            )],
+                            batch,
+                        )?;
+                }
+            }
+            CertificateStatus::Proven => {
+                let latest = self
+                    .db
+                    .get::<LatestProvenCertificatePerNetworkColumn>(network_id)?;
+                if !matches!(latest, Some(ProvenCertificate(_, _, height)) if height > header.height)
+                {
+                    self.db
+                        .multi_insert_batch::<LatestProvenCertificatePerNetworkColumn>(
+                            [(
+                                network_id,
+                                &ProvenCertificate(
+                                    header.certificate_id,
+                                    header.network_id,
+                                    header.height,
+                                ),
+                            )],
+                            batch,
+                        )?;
+                }
+            }
+            CertificateStatus::Settled => {
+                // TODO: Check certificate conflict during insert (if conflict it's too late)
+                self.db.multi_insert_batch::<CertificatePerNetworkColumn>(
+                    [(
+                        &certificate_per_network::Key {
+                            network_id: network_id.to_u32(),
+                            height: header.height,
+                        },
+                        &header.certificate_id,
+                    )],
+                    batch,
+                )?;
+            }
+            CertificateStatus::Candidate | CertificateStatus::InError { .. } => {}
+        }
+
+        Ok(())
     }
 }
 
@@ -97,6 +277,19 @@ impl StateWriter for StateStore {
             self.db
                 .put::<CertificateHeaderColumn>(certificate_id, &certificate_header)?;
 
+            self.append_audit_record(
+                certificate_id,
+                AuditEvent::SettlementSubmitted {
+                    settlement_tx_hash: tx_hash,
+                },
+            )?;
+            self.append_audit_record(
+                certificate_id,
+                AuditEvent::StatusChanged {
+                    status: CertificateStatus::Candidate,
+                },
+            )?;
+
             if let Err(error) = self.backup_client.backup(BackupRequest { epoch_db: None }) {
                 warn!(
                     hash = certificate_id.to_string(),
@@ -159,6 +352,142 @@ impl StateWriter for StateStore {
         Ok(())
     }
 
+    fn record_settlement_attempt(
+        &self,
+        certificate_id: &CertificateId,
+        attempt: SettlementAttempt,
+    ) -> Result<(), Error> {
+        // TODO: make lockguard for certificate_id
+        let mut attempts = self
+            .db
+            .get::<SettlementAttemptsPerCertificateColumn>(certificate_id)?
+            .unwrap_or_default();
+        attempts.push(attempt);
+
+        self.db
+            .put::<SettlementAttemptsPerCertificateColumn>(certificate_id, &attempts)?;
+
+        Ok(())
+    }
+
+    fn record_settlement_sender(
+        &self,
+        settlement_tx_hash: &SettlementTxHash,
+        sender: Address,
+    ) -> Result<(), Error> {
+        self.db
+            .put::<SettlementSenderPerTxColumn>(settlement_tx_hash, &sender)?;
+
+        Ok(())
+    }
+
+    fn record_reverted_settlement(
+        &self,
+        epoch_number: EpochNumber,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        let mut certificate_ids = self
+            .db
+            .get::<RevertedSettlementsPerEpochColumn>(&epoch_number)?
+            .unwrap_or_default();
+        if !certificate_ids.contains(certificate_id) {
+            certificate_ids.push(*certificate_id);
+        }
+
+        self.db
+            .put::<RevertedSettlementsPerEpochColumn>(&epoch_number, &certificate_ids)?;
+
+        Ok(())
+    }
+
+    fn remove_reverted_settlements(&self, epoch_number: EpochNumber) -> Result<(), Error> {
+        self.db
+            .delete::<RevertedSettlementsPerEpochColumn>(&epoch_number)?;
+
+        Ok(())
+    }
+
+    fn record_settlement_cost(
+        &self,
+        network_id: NetworkId,
+        epoch_number: EpochNumber,
+        gas_used: u64,
+        fees: u128,
+    ) -> Result<(), Error> {
+        let key = settlement_costs_per_network::Key {
+            network_id: network_id.to_u32(),
+            epoch_number,
+        };
+
+        // TODO: make lockguard for network_id
+        let mut costs = self
+            .db
+            .get::<SettlementCostsPerNetworkColumn>(&key)?
+            .unwrap_or_default();
+        costs.record_settlement(gas_used, fees);
+
+        self.db
+            .put::<SettlementCostsPerNetworkColumn>(&key, &costs)?;
+
+        Ok(())
+    }
+
+    fn set_certificate_callback(
+        &self,
+        certificate_id: &CertificateId,
+        callback: &CertificateCallback,
+    ) -> Result<(), Error> {
+        self.db
+            .put::<CallbackPerCertificateColumn>(certificate_id, callback)?;
+
+        Ok(())
+    }
+
+    fn remove_certificate_callback(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        self.db
+            .delete::<CallbackPerCertificateColumn>(certificate_id)?;
+
+        Ok(())
+    }
+
+    fn record_audit_event(
+        &self,
+        certificate_id: &CertificateId,
+        event: AuditEvent,
+    ) -> Result<(), Error> {
+        self.append_audit_record(certificate_id, event)
+    }
+
+    fn record_epoch_event(&self, event: EpochEvent) -> Result<(), Error> {
+        self.write_with_logged_event(
+            WriteBatch::default(),
+            LoggedEvent {
+                timestamp: chrono::Utc::now(),
+                event: Event::Epoch(event),
+            },
+        )
+    }
+
+    fn add_api_key_usage(
+        &self,
+        api_key: &str,
+        period: u64,
+        usage: &ApiKeyUsage,
+    ) -> Result<ApiKeyUsage, Error> {
+        let key = api_key_usage::Key {
+            period,
+            api_key: api_key.to_string(),
+        };
+
+        let _guard = self.api_key_usage_lock.lock();
+        let mut total = self.db.get::<ApiKeyUsageColumn>(&key)?.unwrap_or_default();
+        total.add(usage);
+
+        self.db.put::<ApiKeyUsageColumn>(&key, &total)?;
+
+        Ok(total)
+    }
+
     fn assign_certificate_to_epoch(
         &self,
         certificate_id: &CertificateId,
@@ -184,6 +513,13 @@ impl StateWriter for StateStore {
 
             self.db
                 .put::<CertificateHeaderColumn>(certificate_id, &certificate_header)?;
+
+            self.append_audit_record(
+                certificate_id,
+                AuditEvent::StatusChanged {
+                    status: CertificateStatus::Settled,
+                },
+            )?;
         }
 
         Ok(())
@@ -194,35 +530,24 @@ impl StateWriter for StateStore {
         certificate: &Certificate,
         status: CertificateStatus,
     ) -> Result<(), Error> {
-        // TODO: make it a batch write
-        self.db.put::<CertificateHeaderColumn>(
-            &certificate.hash(),
-            &CertificateHeader {
-                certificate_id: certificate.hash(),
-                network_id: certificate.network_id,
-                height: certificate.height,
-                epoch_number: None,
-                certificate_index: None,
-                prev_local_exit_root: certificate.prev_local_exit_root,
-                new_local_exit_root: certificate.new_local_exit_root,
-                status: status.clone(),
-                metadata: certificate.metadata,
-                settlement_tx_hash: None,
-            },
-        )?;
+        let certificate_id = certificate.hash();
+        let header = CertificateHeader {
+            certificate_id,
+            network_id: certificate.network_id,
+            height: certificate.height,
+            epoch_number: None,
+            certificate_index: None,
+            prev_local_exit_root: certificate.prev_local_exit_root,
+            new_local_exit_root: certificate.new_local_exit_root,
+            status: status.clone(),
+            metadata: certificate.metadata,
+            settlement_tx_hash: None,
+        };
 
-        if let CertificateStatus::Settled = status {
-            // TODO: Check certificate conflict during insert (if conflict it's too late)
-            self.db.put::<CertificatePerNetworkColumn>(
-                &certificate_per_network::Key {
-                    network_id: certificate.network_id.to_u32(),
-                    height: certificate.height,
-                },
-                &certificate.hash(),
-            )?;
-        }
+        let mut batch = WriteBatch::default();
+        self.certificate_header_batch(&header, &mut batch)?;
 
-        Ok(())
+        self.write_with_audit_record(batch, &certificate_id, AuditEvent::StatusChanged { status })
     }
 
     fn update_certificate_header_status(
@@ -235,18 +560,17 @@ impl StateWriter for StateStore {
 
         if let Some(mut certificate_header) = certificate_header {
             certificate_header.status = status.clone();
-            self.db
-                .put::<CertificateHeaderColumn>(certificate_id, &certificate_header)?;
 
-            if let CertificateStatus::Settled = status {
-                self.db.put::<CertificatePerNetworkColumn>(
-                    &certificate_per_network::Key {
-                        network_id: certificate_header.network_id.to_u32(),
-                        height: certificate_header.height,
-                    },
-                    &certificate_header.certificate_id,
-                )?;
-            }
+            let mut batch = WriteBatch::default();
+            self.certificate_header_batch(&certificate_header, &mut batch)?;
+
+            self.write_with_audit_record(
+                batch,
+                certificate_id,
+                AuditEvent::StatusChanged {
+                    status: status.clone(),
+                },
+            )?;
         }
 
         Ok(())
@@ -260,9 +584,50 @@ impl StateWriter for StateStore {
         epoch_number: &EpochNumber,
         certificate_index: &CertificateIndex,
     ) -> Result<(), Error> {
-        Ok(self.db.put::<LatestSettledCertificatePerNetworkColumn>(
+        let mut batch = WriteBatch::default();
+        let settled_certificate =
+            SettledCertificate(*certificate_id, *height, *epoch_number, *certificate_index);
+        self.db
+            .multi_insert_batch::<LatestSettledCertificatePerNetworkColumn>(
+                [(network_id, &settled_certificate)],
+                &mut batch,
+            )?;
+
+        // The local network state is written right before the certificate is
+        // recorded as settled, so its roots are the settled ones.
+        if let Some(roots) = self.read_state_roots(*network_id)? {
+            let key = settled_roots_per_network::Key {
+                network_id: network_id.to_u32(),
+                height: *height,
+            };
+            self.db
+                .multi_insert_batch::<SettledRootsPerNetworkColumn>([(&key, &roots)], &mut batch)?;
+        }
+
+        Ok(self.db.write_batch(batch)?)
+    }
+
+    fn set_latest_pending_certificate_index(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        Ok(self.db.put::<LatestPendingCertificatePerNetworkColumn>(
+            network_id,
+            &PendingCertificate(*certificate_id, *height),
+        )?)
+    }
+
+    fn set_latest_proven_certificate_index(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        Ok(self.db.put::<LatestProvenCertificatePerNetworkColumn>(
             network_id,
-            &SettledCertificate(*certificate_id, *height, *epoch_number, *certificate_index),
+            &ProvenCertificate(*certificate_id, *network_id, *height),
         )?)
     }
 
@@ -315,7 +680,7 @@ impl StateWriter for StateStore {
                     });
 
                 // Write frontier
-                (0..32).for_each(|layer| {
+                (0..LOCAL_EXIT_TREE_DEPTH as u32).for_each(|layer| {
                     writes.insert(
                         LET::Key {
                             network_id,
@@ -361,6 +726,47 @@ impl StateWriter for StateStore {
 }
 
 impl StateStore {
+    /// Read the roots of the stored local network state, without reading the
+    /// whole trees.
+    fn read_state_roots(&self, network_id: NetworkId) -> Result<Option<StateCommitment>, Error> {
+        let Some(exit_tree) = self.read_local_exit_tree(network_id)? else {
+            return Ok(None);
+        };
+        let balance_root = self.read_smt_root::<BalanceTreePerNetworkColumn>(network_id)?;
+        let nullifier_root = self.read_smt_root::<NullifierTreePerNetworkColumn>(network_id)?;
+
+        match (balance_root, nullifier_root) {
+            (Some(balance_root), Some(nullifier_root)) => Ok(Some(StateCommitment {
+                exit_root: exit_tree.get_root(),
+                ler_leaf_count: exit_tree.leaf_count(),
+                balance_root,
+                nullifier_root,
+            })),
+            (None, None) => Ok(None),
+            _ => Err(Error::InconsistentState { network_id }),
+        }
+    }
+
+    fn read_smt_root<C>(&self, network_id: NetworkId) -> Result<Option<Digest>, Error>
+    where
+        C: ColumnSchema<Key = SmtKey, Value = SmtValue>,
+    {
+        match self.db.get::<C>(&SmtKey {
+            network_id: network_id.into(),
+            key_type: SmtKeyType::Root,
+        })? {
+            Some(SmtValue::Node(left, right)) => Ok(Some(
+                Node {
+                    left: Digest(*left.as_bytes()),
+                    right: Digest(*right.as_bytes()),
+                }
+                .hash(),
+            )),
+            Some(_) => Err(Error::WrongValueType),
+            None => Ok(None),
+        }
+    }
+
     fn write_smt<C, const DEPTH: usize>(
         &self,
         network_id: u32,
@@ -405,7 +811,10 @@ impl StateStore {
         Ok(())
     }
 
-    fn read_local_exit_tree(&self, network_id: NetworkId) -> Result<Option<LocalExitTree>, Error> {
+    fn read_local_exit_tree(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<Option<LocalExitTree<LOCAL_EXIT_TREE_DEPTH>>, Error> {
         let leaf_count = if let Some(leaf_count_value) =
             self.db.get::<LocalExitTreePerNetworkColumn>(&LET::Key {
                 network_id: network_id.into(),
@@ -419,12 +828,13 @@ impl StateStore {
             return Ok(None);
         };
 
+        let frontier_keys = (0..LOCAL_EXIT_TREE_DEPTH as u32).map(|layer| LET::Key {
+            network_id: network_id.into(),
+            key_type: LET::KeyType::Frontier(layer),
+        });
         let retrieved_frontier: Vec<_> = self
             .db
-            .multi_get::<LocalExitTreePerNetworkColumn>((0..32).map(|layer| LET::Key {
-                network_id: network_id.into(),
-                key_type: LET::KeyType::Frontier(layer),
-            }))?
+            .multi_get::<LocalExitTreePerNetworkColumn>(frontier_keys)?
             .iter()
             .map(|v| match v {
                 Some(LET::Value::Frontier(hash)) => Ok(*hash),
@@ -432,7 +842,7 @@ impl StateStore {
             })
             .collect::<Result<_, _>>()?;
 
-        let mut frontier = [[0u8; 32].into(); 32];
+        let mut frontier = [[0u8; 32].into(); LOCAL_EXIT_TREE_DEPTH];
         for (i, l) in retrieved_frontier.iter().enumerate() {
             frontier[i] = Digest(*l);
         }
@@ -528,6 +938,15 @@ impl StateReader for StateStore {
         Ok(self.db.get::<CertificateHeaderColumn>(certificate_id)?)
     }
 
+    fn multi_get_certificate_header(
+        &self,
+        certificate_ids: &[CertificateId],
+    ) -> Result<Vec<Option<CertificateHeader>>, Error> {
+        Ok(self
+            .db
+            .multi_get::<CertificateHeaderColumn>(certificate_ids.iter().copied())?)
+    }
+
     fn get_certificate_header_by_cursor(
         &self,
         network_id: NetworkId,
@@ -553,11 +972,137 @@ impl StateReader for StateStore {
             })
     }
 
+    fn get_settlement_attempts(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Vec<SettlementAttempt>, Error> {
+        Ok(self
+            .db
+            .get::<SettlementAttemptsPerCertificateColumn>(certificate_id)?
+            .unwrap_or_default())
+    }
+
+    fn get_settlement_sender(
+        &self,
+        settlement_tx_hash: &SettlementTxHash,
+    ) -> Result<Option<Address>, Error> {
+        Ok(self
+            .db
+            .get::<SettlementSenderPerTxColumn>(settlement_tx_hash)?)
+    }
+
+    fn get_reverted_settlements(
+        &self,
+        epoch_number: EpochNumber,
+    ) -> Result<Vec<CertificateId>, Error> {
+        Ok(self
+            .db
+            .get::<RevertedSettlementsPerEpochColumn>(&epoch_number)?
+            .unwrap_or_default())
+    }
+
+    fn get_settlement_costs(
+        &self,
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    ) -> Result<Vec<EpochSettlementCosts>, Error> {
+        let start = settlement_costs_per_network::Key {
+            network_id: network_id.to_u32(),
+            epoch_number: from_epoch,
+        };
+
+        Ok(self
+            .db
+            .iter_with_direction::<SettlementCostsPerNetworkColumn>(
+                Some(&start),
+                Direction::Forward,
+            )?
+            .filter_map(|v| v.ok())
+            .take_while(|(key, _)| {
+                key.network_id == network_id.to_u32() && key.epoch_number <= to_epoch
+            })
+            .map(|(key, costs)| EpochSettlementCosts {
+                epoch_number: key.epoch_number,
+                costs,
+            })
+            .collect())
+    }
+
+    fn get_certificate_callbacks(
+        &self,
+    ) -> Result<Vec<(CertificateId, CertificateCallback)>, Error> {
+        Ok(self
+            .db
+            .iter_with_direction::<CallbackPerCertificateColumn>(
+                None,
+                Direction::Forward,
+            )?
+            .filter_map(|v| v.ok())
+            .collect())
+    }
+
+    fn get_certificate_callback(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificateCallback>, Error> {
+        Ok(self.db.get::<CallbackPerCertificateColumn>(certificate_id)?)
+    }
+
+    fn get_audit_log(&self, certificate_id: &CertificateId) -> Result<Vec<AuditRecord>, Error> {
+        Ok(self
+            .db
+            .get::<AuditLogPerCertificateColumn>(certificate_id)?
+            .unwrap_or_default())
+    }
+
+    fn get_events(
+        &self,
+        after: Option<EventId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, LoggedEvent)>, Error> {
+        let start = after.map(|after| after.next());
+
+        Ok(self
+            .db
+            .iter_with_direction::<EventLogColumn>(start.as_ref(), Direction::Forward)?
+            .take(limit)
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn get_latest_event_id(&self) -> Result<Option<EventId>, Error> {
+        Ok(self
+            .db
+            .iter_with_direction::<EventLogColumn>(None, Direction::Reverse)?
+            .next()
+            .transpose()?
+            .map(|(event_id, _)| event_id))
+    }
+
+    fn subscribe_events(&self) -> watch::Receiver<Option<EventId>> {
+        self.appended_events.subscribe()
+    }
+
+    fn get_api_key_usage(&self, period: u64) -> Result<BTreeMap<String, ApiKeyUsage>, Error> {
+        let start = api_key_usage::Key {
+            period,
+            api_key: String::new(),
+        };
+
+        Ok(self
+            .db
+            .iter_with_direction::<ApiKeyUsageColumn>(Some(&start), Direction::Forward)?
+            .filter_map(|v| v.ok())
+            .take_while(|(key, _)| key.period == period)
+            .map(|(key, usage)| (key.api_key, usage))
+            .collect())
+    }
+
     fn get_current_settled_height(&self) -> Result<Vec<(NetworkId, SettledCertificate)>, Error> {
         Ok(self
             .db
             .iter_with_direction::<LatestSettledCertificatePerNetworkColumn>(
-                ReadOptions::default(),
+                None,
                 Direction::Forward,
             )?
             .filter_map(|v| v.ok())
@@ -574,6 +1119,24 @@ impl StateReader for StateStore {
             .map(|v| v.map(|v| (*network_id, v)))?)
     }
 
+    fn get_latest_pending_certificate_index(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<PendingCertificate>, Error> {
+        Ok(self
+            .db
+            .get::<LatestPendingCertificatePerNetworkColumn>(network_id)?)
+    }
+
+    fn get_latest_proven_certificate_index(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<ProvenCertificate>, Error> {
+        Ok(self
+            .db
+            .get::<LatestProvenCertificatePerNetworkColumn>(network_id)?)
+    }
+
     fn read_local_network_state(
         &self,
         network_id: NetworkId,
@@ -596,6 +1159,50 @@ impl StateReader for StateStore {
             _ => Err(Error::InconsistentState { network_id }),
         }
     }
+
+    fn get_settled_roots(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<Option<StateCommitment>, Error> {
+        Ok(self
+            .db
+            .get::<SettledRootsPerNetworkColumn>(&settled_roots_per_network::Key {
+                network_id: network_id.to_u32(),
+                height,
+            })?)
+    }
+
+    fn read_local_exit_tree_leaves(&self, network_id: NetworkId) -> Result<Vec<Digest>, Error> {
+        let Some(exit_tree) = self.read_local_exit_tree(network_id)? else {
+            return Ok(Vec::new());
+        };
+
+        let leaf_keys = (0..exit_tree.leaf_count()).map(|index| LET::Key {
+            network_id: network_id.into(),
+            key_type: LET::KeyType::Leaf(index),
+        });
+
+        self.db
+            .multi_get::<LocalExitTreePerNetworkColumn>(leaf_keys)?
+            .into_iter()
+            .map(|value| match value {
+                Some(LET::Value::Leaf(hash)) => Ok(Digest(hash)),
+                _ => Err(Error::InconsistentState { network_id }),
+            })
+            .collect()
+    }
+
+    fn read_local_exit_tree_leaf_count(&self, network_id: NetworkId) -> Result<u32, Error> {
+        match self.db.get::<LocalExitTreePerNetworkColumn>(&LET::Key {
+            network_id: network_id.into(),
+            key_type: LET::KeyType::LeafCount,
+        })? {
+            Some(LET::Value::LeafCount(leaf_count)) => Ok(leaf_count),
+            Some(_) => Err(Error::InconsistentFrontier),
+            None => Ok(0),
+        }
+    }
 }
 
 impl MetadataWriter for StateStore {
@@ -3,10 +3,15 @@
 //! This module implements the `NetworkInfoReader` trait for `StateStore`,
 //! providing functionality to read and retrieve network-related information
 //! from the database.
+use std::collections::BTreeSet;
+
 use agglayer_types::{CertificateId, Height, NetworkId, NetworkInfo, NetworkType};
 
 use crate::{
-    columns::network_info::NetworkInfoColumn,
+    columns::{
+        latest_settled_certificate_per_network::LatestSettledCertificatePerNetworkColumn,
+        network_info::NetworkInfoColumn,
+    },
     error::Error,
     stores::{expected_type_or_fail, state::StateStore, try_digest, StateReader as _},
     types::network_info::{
@@ -181,6 +186,20 @@ impl crate::stores::NetworkInfoReader for StateStore {
                 )
             })
     }
+
+    fn get_known_networks(&self) -> Result<Vec<NetworkId>, Error> {
+        let mut networks = BTreeSet::new();
+
+        for key in self.db.keys::<NetworkInfoColumn>()? {
+            networks.insert(NetworkId::new(key?.network_id));
+        }
+
+        for network_id in self.db.keys::<LatestSettledCertificatePerNetworkColumn>()? {
+            networks.insert(network_id?);
+        }
+
+        Ok(networks.into_iter().collect())
+    }
 }
 
 #[cfg(test)]
@@ -227,4 +246,35 @@ mod tests {
             agglayer_types::NetworkType::MultisigOnly
         );
     }
+
+    #[rstest]
+    fn known_networks_are_listed_once(store: StateStore) {
+        assert!(store.get_known_networks().unwrap().is_empty());
+
+        for (network_id, kind) in [
+            (2, network_info_value::ValueDiscriminants::NetworkType),
+            (
+                2,
+                network_info_value::ValueDiscriminants::LatestPendingCertificateInfo,
+            ),
+            (1, network_info_value::ValueDiscriminants::NetworkType),
+        ] {
+            store
+                .db
+                .put::<NetworkInfoColumn>(
+                    &Key { network_id, kind },
+                    &NetworkInfoValue {
+                        value: Some(network_info_value::Value::NetworkType(
+                            NetworkType::Ecdsa as i32,
+                        )),
+                    },
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            store.get_known_networks().unwrap(),
+            vec![NetworkId::new(1), NetworkId::new(2)]
+        );
+    }
 }
@@ -24,7 +24,9 @@ fn can_retrieve_the_last_settled_epoch() {
     )
     .expect("Unable to put latest settled epoch into storage");
 
-    assert!(matches!(store.get_latest_settled_epoch().unwrap(), Some(e1) if e1 == EpochNumber::new(1)));
+    assert!(
+        matches!(store.get_latest_settled_epoch().unwrap(), Some(e1) if e1 == EpochNumber::new(1))
+    );
 }
 
 #[test]
@@ -7,6 +7,7 @@ use std::{
 
 use rand::Rng as _;
 
+pub mod faults;
 pub mod mocks;
 
 pub struct TempDBDir {
@@ -0,0 +1,1075 @@
+//! Store decorators injecting faults on the operations of the wrapped store,
+//! to exercise the recovery from storage errors without failpoints in the
+//! RocksDB path.
+//!
+//! The faults are shared between a store and the per-epoch stores it opens,
+//! and can be armed once the store is handed to the component under test.
+//!
+//! ```ignore
+//! let faults = Arc::new(Faults::default());
+//! let state_store = FaultyStore::new(state_store, faults.clone());
+//!
+//! // The second write of the state store fails.
+//! faults.inject(Operation::AnyWrite, Trigger::Nth(2), Fault::Error);
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use agglayer_types::{
+    primitives::Digest, Address, Certificate, CertificateHeader, CertificateId, CertificateIndex,
+    CertificateStatus, EpochEvent, EpochNumber, EpochSettlementCosts, ExecutionMode, Height,
+    LocalNetworkStateData, NetworkId, NetworkInfo, Proof, SettlementTxHash,
+};
+use pessimistic_proof::local_state::StateCommitment;
+use tokio::sync::watch;
+
+use crate::{
+    columns::{
+        api_key_usage::ApiKeyUsage,
+        audit_log_per_certificate::{AuditEvent, AuditRecord},
+        callback_per_certificate::CertificateCallback,
+        certification_failure_per_certificate::CertificationFailure,
+        certified_roots_per_certificate::CertifiedRoots,
+        event_log::{EventId, LoggedEvent},
+        latest_pending_certificate_per_network::PendingCertificate,
+        latest_proven_certificate_per_network::ProvenCertificate,
+        latest_settled_certificate_per_network::SettledCertificate,
+        settlement_attempts_per_certificate::SettlementAttempt,
+        CodecError,
+    },
+    error::Error,
+    storage::DBError,
+    stores::{
+        DebugReader, DebugWriter, EpochStoreReader, EpochStoreWriter, MetadataReader,
+        MetadataWriter, NetworkInfoReader, PendingCertificateReader, PendingCertificateWriter,
+        PerEpochReader, PerEpochWriter, StateReader, StateWriter,
+    },
+};
+
+/// Operations a fault applies to.
+#[derive(Clone, Copy, Debug)]
+pub enum Operation {
+    /// The operation of the given name, whichever store trait it belongs to.
+    Named(&'static str),
+    AnyRead,
+    AnyWrite,
+}
+
+/// Calls of the operations on which a fault is injected.
+#[derive(Clone, Copy, Debug)]
+pub enum Trigger {
+    /// The nth call, counting from 1, since the fault was injected.
+    Nth(usize),
+    Always,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Fault {
+    /// Fail the operation with a RocksDB-level error.
+    Error,
+    /// Fail the operation with a decoding error, as on a corrupted value.
+    Corruption,
+    /// Delay the operation, which then goes through.
+    ///
+    /// The store operations are synchronous, so the delay blocks the calling
+    /// thread with [`std::thread::sleep`]. On a tokio runtime, this stalls the
+    /// worker and every task scheduled on it: use a multi-threaded runtime
+    /// with more workers than concurrently delayed operations.
+    Latency(Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Read,
+    Write,
+}
+
+#[derive(Debug)]
+struct Rule {
+    operation: Operation,
+    trigger: Trigger,
+    fault: Fault,
+    /// Calls of the operations seen since the fault was injected.
+    calls: usize,
+}
+
+/// Faults injected on the operations of the [`FaultyStore`]s sharing them.
+#[derive(Debug, Default)]
+pub struct Faults {
+    rules: Mutex<Vec<Rule>>,
+    /// Calls of the operations of the stores, by name.
+    calls: Mutex<BTreeMap<&'static str, usize>>,
+}
+
+impl Faults {
+    /// Inject the fault on the given calls of the operations.
+    pub fn inject(&self, operation: Operation, trigger: Trigger, fault: Fault) {
+        self.rules
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(Rule {
+                operation,
+                trigger,
+                fault,
+                calls: 0,
+            });
+    }
+
+    /// Remove the injected faults.
+    pub fn clear(&self) {
+        self.rules
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+
+    /// Number of calls of the operation of the given name so far.
+    pub fn calls(&self, name: &str) -> usize {
+        self.calls
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(name)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn check(&self, kind: Kind, name: &'static str) -> Result<(), Error> {
+        *self
+            .calls
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(name)
+            .or_default() += 1;
+
+        let faults: Vec<Fault> = self
+            .rules
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter_mut()
+            .filter(|rule| match rule.operation {
+                Operation::Named(operation) => operation == name,
+                Operation::AnyRead => kind == Kind::Read,
+                Operation::AnyWrite => kind == Kind::Write,
+            })
+            .filter_map(|rule| {
+                rule.calls += 1;
+                match rule.trigger {
+                    Trigger::Nth(n) => (rule.calls == n).then_some(rule.fault),
+                    Trigger::Always => Some(rule.fault),
+                }
+            })
+            .collect();
+
+        for fault in faults {
+            match fault {
+                Fault::Error => {
+                    return Err(Error::Unexpected(format!(
+                        "Injected failure of the {name} operation"
+                    )))
+                }
+                Fault::Corruption => {
+                    return Err(DBError::CodecError(CodecError::BadCertificateVersion {
+                        version: u8::MAX,
+                    })
+                    .into())
+                }
+                Fault::Latency(delay) => std::thread::sleep(delay),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Store forwarding to the wrapped store, with the shared faults injected on
+/// its operations.
+pub struct FaultyStore<S> {
+    inner: S,
+    faults: Arc<Faults>,
+}
+
+impl<S> FaultyStore<S> {
+    pub fn new(inner: S, faults: Arc<Faults>) -> Self {
+        Self { inner, faults }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: EpochStoreWriter> EpochStoreWriter for FaultyStore<S> {
+    type PerEpochStore = FaultyStore<S::PerEpochStore>;
+
+    fn open(&self, epoch_number: EpochNumber) -> Result<Self::PerEpochStore, Error> {
+        self.faults.check(Kind::Write, "open")?;
+        let store = EpochStoreWriter::open(&self.inner, epoch_number)?;
+
+        Ok(FaultyStore::new(store, self.faults.clone()))
+    }
+
+    fn open_with_start_checkpoint(
+        &self,
+        epoch_number: EpochNumber,
+        start_checkpoint: BTreeMap<NetworkId, Height>,
+    ) -> Result<Self::PerEpochStore, Error> {
+        self.faults
+            .check(Kind::Write, "open_with_start_checkpoint")?;
+        let store = EpochStoreWriter::open_with_start_checkpoint(
+            &self.inner,
+            epoch_number,
+            start_checkpoint,
+        )?;
+
+        Ok(FaultyStore::new(store, self.faults.clone()))
+    }
+}
+
+impl<S: DebugReader> DebugReader for FaultyStore<S> {
+    fn get_certificate(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<Certificate>, Error> {
+        self.faults.check(Kind::Read, "get_certificate")?;
+        DebugReader::get_certificate(&self.inner, certificate_id)
+    }
+
+    fn get_certification_failure(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificationFailure>, Error> {
+        self.faults.check(Kind::Read, "get_certification_failure")?;
+        DebugReader::get_certification_failure(&self.inner, certificate_id)
+    }
+}
+
+impl<S: DebugWriter> DebugWriter for FaultyStore<S> {
+    fn add_certificate(&self, certificate: &Certificate) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "add_certificate")?;
+        DebugWriter::add_certificate(&self.inner, certificate)
+    }
+
+    fn add_certification_failure(
+        &self,
+        certificate: &Certificate,
+        failure: &CertificationFailure,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "add_certification_failure")?;
+        DebugWriter::add_certification_failure(&self.inner, certificate, failure)
+    }
+}
+
+impl<S: EpochStoreReader> EpochStoreReader for FaultyStore<S> {
+    fn get_certificate(
+        &self,
+        epoch_number: EpochNumber,
+        index: CertificateIndex,
+    ) -> Result<Option<Certificate>, Error> {
+        self.faults.check(Kind::Read, "get_certificate")?;
+        EpochStoreReader::get_certificate(&self.inner, epoch_number, index)
+    }
+
+    fn get_proof(
+        &self,
+        epoch_number: EpochNumber,
+        index: CertificateIndex,
+    ) -> Result<Option<Proof>, Error> {
+        self.faults.check(Kind::Read, "get_proof")?;
+        EpochStoreReader::get_proof(&self.inner, epoch_number, index)
+    }
+
+    fn get_certificates(
+        &self,
+        epoch_number: EpochNumber,
+        from_index: CertificateIndex,
+        limit: usize,
+    ) -> Result<Vec<Certificate>, Error> {
+        self.faults.check(Kind::Read, "get_certificates")?;
+        EpochStoreReader::get_certificates(&self.inner, epoch_number, from_index, limit)
+    }
+}
+
+impl<S: PendingCertificateReader> PendingCertificateReader for FaultyStore<S> {
+    fn get_latest_pending_certificate_for_network(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<(CertificateId, Height)>, Error> {
+        self.faults
+            .check(Kind::Read, "get_latest_pending_certificate_for_network")?;
+        PendingCertificateReader::get_latest_pending_certificate_for_network(
+            &self.inner,
+            network_id,
+        )
+    }
+
+    fn get_pending_networks(&self) -> Result<Vec<NetworkId>, Error> {
+        self.faults.check(Kind::Read, "get_pending_networks")?;
+        PendingCertificateReader::get_pending_networks(&self.inner)
+    }
+
+    fn get_pending_certificates(&self) -> Result<Vec<(NetworkId, Height, CertificateId)>, Error> {
+        self.faults.check(Kind::Read, "get_pending_certificates")?;
+        PendingCertificateReader::get_pending_certificates(&self.inner)
+    }
+
+    fn get_certificate(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<Option<Certificate>, Error> {
+        self.faults.check(Kind::Read, "get_certificate")?;
+        PendingCertificateReader::get_certificate(&self.inner, network_id, height)
+    }
+
+    fn get_proof(&self, certificate_id: CertificateId) -> Result<Option<Proof>, Error> {
+        self.faults.check(Kind::Read, "get_proof")?;
+        PendingCertificateReader::get_proof(&self.inner, certificate_id)
+    }
+
+    fn get_cached_proof(
+        &self,
+        certificate_id: &CertificateId,
+        initial_roots: &StateCommitment,
+    ) -> Result<Option<Proof>, Error> {
+        self.faults.check(Kind::Read, "get_cached_proof")?;
+        PendingCertificateReader::get_cached_proof(&self.inner, certificate_id, initial_roots)
+    }
+
+    fn get_submitted_proof(&self, certificate_id: &CertificateId) -> Result<Option<Proof>, Error> {
+        self.faults.check(Kind::Read, "get_submitted_proof")?;
+        PendingCertificateReader::get_submitted_proof(&self.inner, certificate_id)
+    }
+
+    fn get_verified_proof(&self, certificate_id: &CertificateId) -> Result<Option<Digest>, Error> {
+        self.faults.check(Kind::Read, "get_verified_proof")?;
+        PendingCertificateReader::get_verified_proof(&self.inner, certificate_id)
+    }
+
+    fn get_certified_roots(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertifiedRoots>, Error> {
+        self.faults.check(Kind::Read, "get_certified_roots")?;
+        PendingCertificateReader::get_certified_roots(&self.inner, certificate_id)
+    }
+
+    fn get_proven_certificate_ids(&self) -> Result<BTreeSet<CertificateId>, Error> {
+        self.faults
+            .check(Kind::Read, "get_proven_certificate_ids")?;
+        PendingCertificateReader::get_proven_certificate_ids(&self.inner)
+    }
+
+    fn multi_get_certificate(
+        &self,
+        keys: &[(NetworkId, Height)],
+    ) -> Result<Vec<Option<Certificate>>, Error> {
+        self.faults.check(Kind::Read, "multi_get_certificate")?;
+        PendingCertificateReader::multi_get_certificate(&self.inner, keys)
+    }
+
+    fn multi_get_proof(&self, keys: &[CertificateId]) -> Result<Vec<Option<Proof>>, Error> {
+        self.faults.check(Kind::Read, "multi_get_proof")?;
+        PendingCertificateReader::multi_get_proof(&self.inner, keys)
+    }
+
+    fn get_current_proven_height(&self) -> Result<Vec<ProvenCertificate>, Error> {
+        self.faults.check(Kind::Read, "get_current_proven_height")?;
+        PendingCertificateReader::get_current_proven_height(&self.inner)
+    }
+
+    fn get_current_proven_height_for_network(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<Height>, Error> {
+        self.faults
+            .check(Kind::Read, "get_current_proven_height_for_network")?;
+        PendingCertificateReader::get_current_proven_height_for_network(&self.inner, network_id)
+    }
+
+    fn get_latest_proven_certificate_per_network(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<(NetworkId, Height, CertificateId)>, Error> {
+        self.faults
+            .check(Kind::Read, "get_latest_proven_certificate_per_network")?;
+        PendingCertificateReader::get_latest_proven_certificate_per_network(&self.inner, network_id)
+    }
+}
+
+impl<S: PendingCertificateWriter> PendingCertificateWriter for FaultyStore<S> {
+    fn remove_pending_certificate(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "remove_pending_certificate")?;
+        PendingCertificateWriter::remove_pending_certificate(&self.inner, network_id, height)
+    }
+
+    fn remove_generated_proof(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "remove_generated_proof")?;
+        PendingCertificateWriter::remove_generated_proof(&self.inner, certificate_id)
+    }
+
+    fn insert_pending_certificate(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+        certificate: &Certificate,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "insert_pending_certificate")?;
+        PendingCertificateWriter::insert_pending_certificate(
+            &self.inner,
+            network_id,
+            height,
+            certificate,
+        )
+    }
+
+    fn insert_generated_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "insert_generated_proof")?;
+        PendingCertificateWriter::insert_generated_proof(&self.inner, certificate_id, proof)
+    }
+
+    fn insert_certified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+        roots: &CertifiedRoots,
+        cache: bool,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "insert_certified_proof")?;
+        PendingCertificateWriter::insert_certified_proof(
+            &self.inner,
+            certificate_id,
+            proof,
+            roots,
+            cache,
+        )
+    }
+
+    fn insert_cached_proof(
+        &self,
+        certificate_id: &CertificateId,
+        initial_roots: &StateCommitment,
+        proof: &Proof,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "insert_cached_proof")?;
+        PendingCertificateWriter::insert_cached_proof(
+            &self.inner,
+            certificate_id,
+            initial_roots,
+            proof,
+        )
+    }
+
+    fn remove_cached_proof(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "remove_cached_proof")?;
+        PendingCertificateWriter::remove_cached_proof(&self.inner, certificate_id)
+    }
+
+    fn set_verified_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof_digest: &Digest,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "set_verified_proof")?;
+        PendingCertificateWriter::set_verified_proof(&self.inner, certificate_id, proof_digest)
+    }
+
+    fn insert_submitted_proof(
+        &self,
+        certificate_id: &CertificateId,
+        proof: &Proof,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "insert_submitted_proof")?;
+        PendingCertificateWriter::insert_submitted_proof(&self.inner, certificate_id, proof)
+    }
+
+    fn remove_submitted_proof(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "remove_submitted_proof")?;
+        PendingCertificateWriter::remove_submitted_proof(&self.inner, certificate_id)
+    }
+
+    fn set_latest_proven_certificate_per_network(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "set_latest_proven_certificate_per_network")?;
+        PendingCertificateWriter::set_latest_proven_certificate_per_network(
+            &self.inner,
+            network_id,
+            height,
+            certificate_id,
+        )
+    }
+
+    fn set_latest_pending_certificate_per_network(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "set_latest_pending_certificate_per_network")?;
+        PendingCertificateWriter::set_latest_pending_certificate_per_network(
+            &self.inner,
+            network_id,
+            height,
+            certificate_id,
+        )
+    }
+}
+
+impl<S: MetadataReader> MetadataReader for FaultyStore<S> {
+    fn get_latest_settled_epoch(&self) -> Result<Option<EpochNumber>, Error> {
+        self.faults.check(Kind::Read, "get_latest_settled_epoch")?;
+        MetadataReader::get_latest_settled_epoch(&self.inner)
+    }
+}
+
+impl<S: MetadataWriter> MetadataWriter for FaultyStore<S> {
+    fn set_latest_settled_epoch(&self, value: EpochNumber) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "set_latest_settled_epoch")?;
+        MetadataWriter::set_latest_settled_epoch(&self.inner, value)
+    }
+}
+
+impl<S: StateReader> StateReader for FaultyStore<S> {
+    fn get_active_networks(&self) -> Result<Vec<NetworkId>, Error> {
+        self.faults.check(Kind::Read, "get_active_networks")?;
+        StateReader::get_active_networks(&self.inner)
+    }
+
+    fn get_certificate_header(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificateHeader>, Error> {
+        self.faults.check(Kind::Read, "get_certificate_header")?;
+        StateReader::get_certificate_header(&self.inner, certificate_id)
+    }
+
+    fn get_certificate_header_by_cursor(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<Option<CertificateHeader>, Error> {
+        self.faults
+            .check(Kind::Read, "get_certificate_header_by_cursor")?;
+        StateReader::get_certificate_header_by_cursor(&self.inner, network_id, height)
+    }
+
+    fn multi_get_certificate_header(
+        &self,
+        certificate_ids: &[CertificateId],
+    ) -> Result<Vec<Option<CertificateHeader>>, Error> {
+        self.faults
+            .check(Kind::Read, "multi_get_certificate_header")?;
+        StateReader::multi_get_certificate_header(&self.inner, certificate_ids)
+    }
+
+    fn get_settlement_attempts(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Vec<SettlementAttempt>, Error> {
+        self.faults.check(Kind::Read, "get_settlement_attempts")?;
+        StateReader::get_settlement_attempts(&self.inner, certificate_id)
+    }
+
+    fn get_settlement_sender(
+        &self,
+        settlement_tx_hash: &SettlementTxHash,
+    ) -> Result<Option<Address>, Error> {
+        self.faults.check(Kind::Read, "get_settlement_sender")?;
+        StateReader::get_settlement_sender(&self.inner, settlement_tx_hash)
+    }
+
+    fn get_reverted_settlements(
+        &self,
+        epoch_number: EpochNumber,
+    ) -> Result<Vec<CertificateId>, Error> {
+        self.faults.check(Kind::Read, "get_reverted_settlements")?;
+        StateReader::get_reverted_settlements(&self.inner, epoch_number)
+    }
+
+    fn get_settlement_costs(
+        &self,
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+    ) -> Result<Vec<EpochSettlementCosts>, Error> {
+        self.faults.check(Kind::Read, "get_settlement_costs")?;
+        StateReader::get_settlement_costs(&self.inner, network_id, from_epoch, to_epoch)
+    }
+
+    fn get_certificate_callbacks(
+        &self,
+    ) -> Result<Vec<(CertificateId, CertificateCallback)>, Error> {
+        self.faults.check(Kind::Read, "get_certificate_callbacks")?;
+        StateReader::get_certificate_callbacks(&self.inner)
+    }
+
+    fn get_certificate_callback(
+        &self,
+        certificate_id: &CertificateId,
+    ) -> Result<Option<CertificateCallback>, Error> {
+        self.faults.check(Kind::Read, "get_certificate_callback")?;
+        StateReader::get_certificate_callback(&self.inner, certificate_id)
+    }
+
+    fn get_audit_log(&self, certificate_id: &CertificateId) -> Result<Vec<AuditRecord>, Error> {
+        self.faults.check(Kind::Read, "get_audit_log")?;
+        StateReader::get_audit_log(&self.inner, certificate_id)
+    }
+
+    fn get_events(
+        &self,
+        after: Option<EventId>,
+        limit: usize,
+    ) -> Result<Vec<(EventId, LoggedEvent)>, Error> {
+        self.faults.check(Kind::Read, "get_events")?;
+        StateReader::get_events(&self.inner, after, limit)
+    }
+
+    fn get_latest_event_id(&self) -> Result<Option<EventId>, Error> {
+        self.faults.check(Kind::Read, "get_latest_event_id")?;
+        StateReader::get_latest_event_id(&self.inner)
+    }
+
+    fn subscribe_events(&self) -> watch::Receiver<Option<EventId>> {
+        StateReader::subscribe_events(&self.inner)
+    }
+
+    fn get_api_key_usage(&self, period: u64) -> Result<BTreeMap<String, ApiKeyUsage>, Error> {
+        self.faults.check(Kind::Read, "get_api_key_usage")?;
+        StateReader::get_api_key_usage(&self.inner, period)
+    }
+
+    fn get_current_settled_height(&self) -> Result<Vec<(NetworkId, SettledCertificate)>, Error> {
+        self.faults
+            .check(Kind::Read, "get_current_settled_height")?;
+        StateReader::get_current_settled_height(&self.inner)
+    }
+
+    fn get_latest_settled_certificate_per_network(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<(NetworkId, SettledCertificate)>, Error> {
+        self.faults
+            .check(Kind::Read, "get_latest_settled_certificate_per_network")?;
+        StateReader::get_latest_settled_certificate_per_network(&self.inner, network_id)
+    }
+
+    fn get_latest_pending_certificate_index(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<PendingCertificate>, Error> {
+        self.faults
+            .check(Kind::Read, "get_latest_pending_certificate_index")?;
+        StateReader::get_latest_pending_certificate_index(&self.inner, network_id)
+    }
+
+    fn get_latest_proven_certificate_index(
+        &self,
+        network_id: &NetworkId,
+    ) -> Result<Option<ProvenCertificate>, Error> {
+        self.faults
+            .check(Kind::Read, "get_latest_proven_certificate_index")?;
+        StateReader::get_latest_proven_certificate_index(&self.inner, network_id)
+    }
+
+    fn read_local_network_state(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<Option<LocalNetworkStateData>, Error> {
+        self.faults.check(Kind::Read, "read_local_network_state")?;
+        StateReader::read_local_network_state(&self.inner, network_id)
+    }
+
+    fn get_settled_roots(
+        &self,
+        network_id: NetworkId,
+        height: Height,
+    ) -> Result<Option<StateCommitment>, Error> {
+        self.faults.check(Kind::Read, "get_settled_roots")?;
+        StateReader::get_settled_roots(&self.inner, network_id, height)
+    }
+
+    fn read_local_exit_tree_leaves(&self, network_id: NetworkId) -> Result<Vec<Digest>, Error> {
+        self.faults
+            .check(Kind::Read, "read_local_exit_tree_leaves")?;
+        StateReader::read_local_exit_tree_leaves(&self.inner, network_id)
+    }
+
+    fn read_local_exit_tree_leaf_count(&self, network_id: NetworkId) -> Result<u32, Error> {
+        self.faults
+            .check(Kind::Read, "read_local_exit_tree_leaf_count")?;
+        StateReader::read_local_exit_tree_leaf_count(&self.inner, network_id)
+    }
+}
+
+impl<S: StateWriter> StateWriter for FaultyStore<S> {
+    fn update_settlement_tx_hash(
+        &self,
+        certificate_id: &CertificateId,
+        tx_hash: SettlementTxHash,
+        force: bool,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "update_settlement_tx_hash")?;
+        StateWriter::update_settlement_tx_hash(&self.inner, certificate_id, tx_hash, force)
+    }
+
+    fn remove_settlement_tx_hash(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "remove_settlement_tx_hash")?;
+        StateWriter::remove_settlement_tx_hash(&self.inner, certificate_id)
+    }
+
+    fn record_settlement_attempt(
+        &self,
+        certificate_id: &CertificateId,
+        attempt: SettlementAttempt,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "record_settlement_attempt")?;
+        StateWriter::record_settlement_attempt(&self.inner, certificate_id, attempt)
+    }
+
+    fn record_settlement_sender(
+        &self,
+        settlement_tx_hash: &SettlementTxHash,
+        sender: Address,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "record_settlement_sender")?;
+        StateWriter::record_settlement_sender(&self.inner, settlement_tx_hash, sender)
+    }
+
+    fn record_reverted_settlement(
+        &self,
+        epoch_number: EpochNumber,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "record_reverted_settlement")?;
+        StateWriter::record_reverted_settlement(&self.inner, epoch_number, certificate_id)
+    }
+
+    fn remove_reverted_settlements(&self, epoch_number: EpochNumber) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "remove_reverted_settlements")?;
+        StateWriter::remove_reverted_settlements(&self.inner, epoch_number)
+    }
+
+    fn record_settlement_cost(
+        &self,
+        network_id: NetworkId,
+        epoch_number: EpochNumber,
+        gas_used: u64,
+        fees: u128,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "record_settlement_cost")?;
+        StateWriter::record_settlement_cost(&self.inner, network_id, epoch_number, gas_used, fees)
+    }
+
+    fn set_certificate_callback(
+        &self,
+        certificate_id: &CertificateId,
+        callback: &CertificateCallback,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "set_certificate_callback")?;
+        StateWriter::set_certificate_callback(&self.inner, certificate_id, callback)
+    }
+
+    fn remove_certificate_callback(&self, certificate_id: &CertificateId) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "remove_certificate_callback")?;
+        StateWriter::remove_certificate_callback(&self.inner, certificate_id)
+    }
+
+    fn record_audit_event(
+        &self,
+        certificate_id: &CertificateId,
+        event: AuditEvent,
+    ) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "record_audit_event")?;
+        StateWriter::record_audit_event(&self.inner, certificate_id, event)
+    }
+
+    fn record_epoch_event(&self, event: EpochEvent) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "record_epoch_event")?;
+        StateWriter::record_epoch_event(&self.inner, event)
+    }
+
+    fn add_api_key_usage(
+        &self,
+        api_key: &str,
+        period: u64,
+        usage: &ApiKeyUsage,
+    ) -> Result<ApiKeyUsage, Error> {
+        self.faults.check(Kind::Write, "add_api_key_usage")?;
+        StateWriter::add_api_key_usage(&self.inner, api_key, period, usage)
+    }
+
+    fn insert_certificate_header(
+        &self,
+        certificate: &Certificate,
+        status: CertificateStatus,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "insert_certificate_header")?;
+        StateWriter::insert_certificate_header(&self.inner, certificate, status)
+    }
+
+    fn update_certificate_header_status(
+        &self,
+        certificate_id: &CertificateId,
+        status: &CertificateStatus,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "update_certificate_header_status")?;
+        StateWriter::update_certificate_header_status(&self.inner, certificate_id, status)
+    }
+
+    fn assign_certificate_to_epoch(
+        &self,
+        certificate_id: &CertificateId,
+        epoch_number: &EpochNumber,
+        certificate_index: &CertificateIndex,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "assign_certificate_to_epoch")?;
+        StateWriter::assign_certificate_to_epoch(
+            &self.inner,
+            certificate_id,
+            epoch_number,
+            certificate_index,
+        )
+    }
+
+    fn set_latest_settled_certificate_for_network(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+        epoch_number: &EpochNumber,
+        certificate_index: &CertificateIndex,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "set_latest_settled_certificate_for_network")?;
+        StateWriter::set_latest_settled_certificate_for_network(
+            &self.inner,
+            network_id,
+            height,
+            certificate_id,
+            epoch_number,
+            certificate_index,
+        )
+    }
+
+    fn set_latest_pending_certificate_index(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "set_latest_pending_certificate_index")?;
+        StateWriter::set_latest_pending_certificate_index(
+            &self.inner,
+            network_id,
+            height,
+            certificate_id,
+        )
+    }
+
+    fn set_latest_proven_certificate_index(
+        &self,
+        network_id: &NetworkId,
+        height: &Height,
+        certificate_id: &CertificateId,
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "set_latest_proven_certificate_index")?;
+        StateWriter::set_latest_proven_certificate_index(
+            &self.inner,
+            network_id,
+            height,
+            certificate_id,
+        )
+    }
+
+    fn write_local_network_state(
+        &self,
+        network_id: &NetworkId,
+        new_state: &LocalNetworkStateData,
+        new_leaves: &[Digest],
+    ) -> Result<(), Error> {
+        self.faults
+            .check(Kind::Write, "write_local_network_state")?;
+        StateWriter::write_local_network_state(&self.inner, network_id, new_state, new_leaves)
+    }
+}
+
+impl<S: PerEpochReader> PerEpochReader for FaultyStore<S> {
+    fn get_start_checkpoint(&self) -> &BTreeMap<NetworkId, Height> {
+        PerEpochReader::get_start_checkpoint(&self.inner)
+    }
+
+    fn get_end_checkpoint(&self) -> BTreeMap<NetworkId, Height> {
+        PerEpochReader::get_end_checkpoint(&self.inner)
+    }
+
+    fn get_epoch_number(&self) -> EpochNumber {
+        PerEpochReader::get_epoch_number(&self.inner)
+    }
+
+    fn get_certificate_at_index(
+        &self,
+        index: CertificateIndex,
+    ) -> Result<Option<Certificate>, Error> {
+        self.faults.check(Kind::Read, "get_certificate_at_index")?;
+        PerEpochReader::get_certificate_at_index(&self.inner, index)
+    }
+
+    fn get_proof_at_index(&self, index: CertificateIndex) -> Result<Option<Proof>, Error> {
+        self.faults.check(Kind::Read, "get_proof_at_index")?;
+        PerEpochReader::get_proof_at_index(&self.inner, index)
+    }
+
+    fn get_end_checkpoint_height_per_network(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<Option<Height>, Error> {
+        self.faults
+            .check(Kind::Read, "get_end_checkpoint_height_per_network")?;
+        PerEpochReader::get_end_checkpoint_height_per_network(&self.inner, network_id)
+    }
+
+    fn is_epoch_packed(&self) -> bool {
+        PerEpochReader::is_epoch_packed(&self.inner)
+    }
+}
+
+impl<S: PerEpochWriter> PerEpochWriter for FaultyStore<S> {
+    fn add_certificate(
+        &self,
+        certificate_id: CertificateId,
+        mode: ExecutionMode,
+    ) -> Result<(EpochNumber, CertificateIndex), Error> {
+        self.faults.check(Kind::Write, "add_certificate")?;
+        PerEpochWriter::add_certificate(&self.inner, certificate_id, mode)
+    }
+
+    fn start_packing(&self) -> Result<(), Error> {
+        self.faults.check(Kind::Write, "start_packing")?;
+        PerEpochWriter::start_packing(&self.inner)
+    }
+}
+
+impl<S: NetworkInfoReader> NetworkInfoReader for FaultyStore<S> {
+    fn get_network_info(&self, network_id: NetworkId) -> Result<NetworkInfo, Error> {
+        self.faults.check(Kind::Read, "get_network_info")?;
+        NetworkInfoReader::get_network_info(&self.inner, network_id)
+    }
+
+    fn get_latest_pending_height(&self, network_id: NetworkId) -> Result<Option<Height>, Error> {
+        self.faults.check(Kind::Read, "get_latest_pending_height")?;
+        NetworkInfoReader::get_latest_pending_height(&self.inner, network_id)
+    }
+
+    fn get_latest_settled_certificate_id(
+        &self,
+        network_id: NetworkId,
+    ) -> Result<Option<CertificateId>, Error> {
+        self.faults
+            .check(Kind::Read, "get_latest_settled_certificate_id")?;
+        NetworkInfoReader::get_latest_settled_certificate_id(&self.inner, network_id)
+    }
+
+    fn get_known_networks(&self) -> Result<Vec<NetworkId>, Error> {
+        self.faults.check(Kind::Read, "get_known_networks")?;
+        NetworkInfoReader::get_known_networks(&self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::mocks::MockPendingStore;
+
+    fn store() -> (FaultyStore<MockPendingStore>, Arc<Faults>) {
+        let mut inner = MockPendingStore::new();
+        inner.expect_remove_generated_proof().returning(|_| Ok(()));
+        inner.expect_get_proof().returning(|_| Ok(None));
+
+        let faults = Arc::new(Faults::default());
+        (FaultyStore::new(inner, faults.clone()), faults)
+    }
+
+    #[test]
+    fn nth_write_fails() {
+        let (store, faults) = store();
+        let certificate_id = CertificateId::new([1; 32].into());
+        faults.inject(Operation::AnyWrite, Trigger::Nth(2), Fault::Error);
+
+        assert!(store.remove_generated_proof(&certificate_id).is_ok());
+        assert!(store.get_proof(certificate_id).is_ok());
+        assert!(matches!(
+            store.remove_generated_proof(&certificate_id),
+            Err(Error::Unexpected(_))
+        ));
+        assert!(store.remove_generated_proof(&certificate_id).is_ok());
+
+        assert_eq!(faults.calls("remove_generated_proof"), 3);
+    }
+
+    #[test]
+    fn named_read_returns_corruption() {
+        let (store, faults) = store();
+        let certificate_id = CertificateId::new([1; 32].into());
+        faults.inject(
+            Operation::Named("get_proof"),
+            Trigger::Always,
+            Fault::Corruption,
+        );
+
+        assert!(store.remove_generated_proof(&certificate_id).is_ok());
+        assert!(matches!(
+            store.get_proof(certificate_id),
+            Err(Error::DBError(DBError::CodecError(_)))
+        ));
+
+        faults.clear();
+        assert!(store.get_proof(certificate_id).is_ok());
+    }
+
+    #[test]
+    fn latency_delays_the_operation() {
+        let (store, faults) = store();
+        let certificate_id = CertificateId::new([1; 32].into());
+        faults.inject(
+            Operation::AnyRead,
+            Trigger::Always,
+            Fault::Latency(Duration::from_millis(50)),
+        );
+
+        let start = std::time::Instant::now();
+        assert!(store.get_proof(certificate_id).is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
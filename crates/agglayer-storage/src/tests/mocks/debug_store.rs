@@ -1,16 +1,26 @@
 use mockall::mock;
 
-use crate::stores::{DebugReader, DebugWriter};
+use crate::{
+    columns::certification_failure_per_certificate::CertificationFailure,
+    stores::{DebugReader, DebugWriter},
+};
 
 mock! {
     pub DebugStore {}
 
     impl DebugWriter for DebugStore {
         fn add_certificate(&self, certificate: &agglayer_types::Certificate) -> Result<(), crate::error::Error>;
+        fn add_certification_failure(
+            &self,
+            certificate: &agglayer_types::Certificate,
+            failure: &CertificationFailure,
+        ) -> Result<(), crate::error::Error>;
     }
 
     impl DebugReader for DebugStore {
         fn get_certificate(&self, certificate_id: &agglayer_types::CertificateId)
                 -> Result<Option<agglayer_types::Certificate>, crate::error::Error>;
+        fn get_certification_failure(&self, certificate_id: &agglayer_types::CertificateId)
+                -> Result<Option<CertificationFailure>, crate::error::Error>;
     }
 }
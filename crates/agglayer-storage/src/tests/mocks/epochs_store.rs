@@ -32,5 +32,12 @@ mock! {
             epoch_number: EpochNumber,
             index: CertificateIndex,
         ) -> Result<Option<Proof>, Error>;
+
+        fn get_certificates(
+            &self,
+            epoch_number: EpochNumber,
+            from_index: CertificateIndex,
+            limit: usize,
+        ) -> Result<Vec<Certificate>, Error>;
     }
 }
@@ -1,8 +1,14 @@
-use agglayer_types::{Certificate, CertificateId, Height, NetworkId, Proof};
+use std::collections::BTreeSet;
+
+use agglayer_types::{Certificate, CertificateId, Digest, Height, NetworkId, Proof};
 use mockall::mock;
+use pessimistic_proof::local_state::StateCommitment;
 
 use crate::{
-    columns::latest_proven_certificate_per_network::ProvenCertificate,
+    columns::{
+        certified_roots_per_certificate::CertifiedRoots,
+        latest_proven_certificate_per_network::ProvenCertificate,
+    },
     error::Error,
     stores::{PendingCertificateReader, PendingCertificateWriter},
 };
@@ -25,8 +31,29 @@ mock! {
             network_id: &NetworkId,
         ) -> Result<Option<(CertificateId, Height)>, Error>;
 
+        fn get_pending_networks(&self) -> Result<Vec<NetworkId>, Error>;
+
+        fn get_pending_certificates(&self) -> Result<Vec<(NetworkId, Height, CertificateId)>, Error>;
+
         fn get_proof(&self, certificate_id: CertificateId) -> Result<Option<Proof>, Error>;
 
+        fn get_cached_proof(
+            &self,
+            certificate_id: &CertificateId,
+            initial_roots: &StateCommitment,
+        ) -> Result<Option<Proof>, Error>;
+
+        fn get_submitted_proof(&self, certificate_id: &CertificateId) -> Result<Option<Proof>, Error>;
+
+        fn get_certified_roots(
+            &self,
+            certificate_id: &CertificateId,
+        ) -> Result<Option<CertifiedRoots>, Error>;
+
+        fn get_verified_proof(&self, certificate_id: &CertificateId) -> Result<Option<Digest>, Error>;
+
+        fn get_proven_certificate_ids(&self) -> Result<BTreeSet<CertificateId>, Error>;
+
         fn multi_get_certificate(
             &self,
             keys: &[(NetworkId, Height)],
@@ -63,6 +90,37 @@ mock! {
             proof: &Proof,
         ) -> Result<(), Error>;
 
+        fn insert_certified_proof(
+            &self,
+            certificate_id: &CertificateId,
+            proof: &Proof,
+            roots: &CertifiedRoots,
+            cache: bool,
+        ) -> Result<(), Error>;
+
+        fn insert_cached_proof(
+            &self,
+            certificate_id: &CertificateId,
+            initial_roots: &StateCommitment,
+            proof: &Proof,
+        ) -> Result<(), Error>;
+
+        fn remove_cached_proof(&self, certificate_id: &CertificateId) -> Result<(), Error>;
+
+        fn set_verified_proof(
+            &self,
+            certificate_id: &CertificateId,
+            proof_digest: &Digest,
+        ) -> Result<(), Error>;
+
+        fn insert_submitted_proof(
+            &self,
+            certificate_id: &CertificateId,
+            proof: &Proof,
+        ) -> Result<(), Error>;
+
+        fn remove_submitted_proof(&self, certificate_id: &CertificateId) -> Result<(), Error>;
+
         fn set_latest_proven_certificate_per_network(
             &self,
             network_id: &NetworkId,
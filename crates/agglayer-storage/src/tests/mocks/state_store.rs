@@ -1,11 +1,23 @@
 use agglayer_types::{
-    primitives::Digest, Certificate, CertificateHeader, CertificateId, CertificateStatus,
-    EpochNumber, Height, LocalNetworkStateData, NetworkId, SettlementTxHash,
+    primitives::Digest, Address, Certificate, CertificateHeader, CertificateId, CertificateStatus,
+    EpochEvent, EpochNumber, EpochSettlementCosts, Height, LocalNetworkStateData, NetworkId,
+    SettlementTxHash,
 };
 use mockall::mock;
+use pessimistic_proof::local_state::StateCommitment;
+use tokio::sync::watch;
 
 use crate::{
-    columns::latest_settled_certificate_per_network::SettledCertificate,
+    columns::{
+        api_key_usage::ApiKeyUsage,
+        audit_log_per_certificate::{AuditEvent, AuditRecord},
+        callback_per_certificate::CertificateCallback,
+        event_log::{EventId, LoggedEvent},
+        latest_pending_certificate_per_network::PendingCertificate,
+        latest_proven_certificate_per_network::ProvenCertificate,
+        latest_settled_certificate_per_network::SettledCertificate,
+        settlement_attempts_per_certificate::SettlementAttempt,
+    },
     error::Error,
     stores::{MetadataReader, MetadataWriter, NetworkInfoReader, StateReader, StateWriter},
 };
@@ -20,6 +32,8 @@ mock! {
             &self,
             network_id: NetworkId,
         ) -> Result<Option<CertificateId>, Error>;
+
+        fn get_known_networks(&self) -> Result<Vec<NetworkId>, Error>;
     }
 
     impl MetadataReader for StateStore {
@@ -43,6 +57,57 @@ mock! {
             certificate_id: &CertificateId,
         ) -> Result<(), Error>;
 
+        fn record_settlement_attempt(
+            &self,
+            certificate_id: &CertificateId,
+            attempt: SettlementAttempt,
+        ) -> Result<(), Error>;
+
+        fn record_settlement_sender(
+            &self,
+            settlement_tx_hash: &SettlementTxHash,
+            sender: Address,
+        ) -> Result<(), Error>;
+
+        fn record_reverted_settlement(
+            &self,
+            epoch_number: EpochNumber,
+            certificate_id: &CertificateId,
+        ) -> Result<(), Error>;
+
+        fn remove_reverted_settlements(&self, epoch_number: EpochNumber) -> Result<(), Error>;
+
+        fn record_settlement_cost(
+            &self,
+            network_id: NetworkId,
+            epoch_number: EpochNumber,
+            gas_used: u64,
+            fees: u128,
+        ) -> Result<(), Error>;
+
+        fn set_certificate_callback(
+            &self,
+            certificate_id: &CertificateId,
+            callback: &CertificateCallback,
+        ) -> Result<(), Error>;
+
+        fn remove_certificate_callback(&self, certificate_id: &CertificateId) -> Result<(), Error>;
+
+        fn record_audit_event(
+            &self,
+            certificate_id: &CertificateId,
+            event: AuditEvent,
+        ) -> Result<(), Error>;
+
+        fn record_epoch_event(&self, event: EpochEvent) -> Result<(), Error>;
+
+        fn add_api_key_usage(
+            &self,
+            api_key: &str,
+            period: u64,
+            usage: &ApiKeyUsage,
+        ) -> Result<ApiKeyUsage, Error>;
+
         fn assign_certificate_to_epoch(
             &self,
             certificate_id: &CertificateId,
@@ -71,6 +136,20 @@ mock! {
             certificate_index: &agglayer_types::CertificateIndex
         ) -> Result<(), Error>;
 
+        fn set_latest_pending_certificate_index(
+            &self,
+            network_id: &NetworkId,
+            height: &Height,
+            certificate_id: &CertificateId,
+        ) -> Result<(), Error>;
+
+        fn set_latest_proven_certificate_index(
+            &self,
+            network_id: &NetworkId,
+            height: &Height,
+            certificate_id: &CertificateId,
+        ) -> Result<(), Error>;
+
         fn write_local_network_state(
             &self,
             network_id: &NetworkId,
@@ -87,6 +166,16 @@ mock! {
             network_id: &NetworkId,
         ) -> Result<Option<(NetworkId, SettledCertificate)>, Error>;
 
+        fn get_latest_pending_certificate_index(
+            &self,
+            network_id: &NetworkId,
+        ) -> Result<Option<PendingCertificate>, Error>;
+
+        fn get_latest_proven_certificate_index(
+            &self,
+            network_id: &NetworkId,
+        ) -> Result<Option<ProvenCertificate>, Error>;
+
         fn get_certificate_header(
             &self,
             certificate_id: &CertificateId,
@@ -97,11 +186,74 @@ mock! {
             network_id: NetworkId,
             height: Height,
         ) -> Result<Option<CertificateHeader>, Error>;
+
+        fn multi_get_certificate_header(
+            &self,
+            certificate_ids: &[CertificateId],
+        ) -> Result<Vec<Option<CertificateHeader>>, Error>;
+        fn get_settlement_attempts(
+            &self,
+            certificate_id: &CertificateId,
+        ) -> Result<Vec<SettlementAttempt>, Error>;
+
+        fn get_settlement_sender(
+            &self,
+            settlement_tx_hash: &SettlementTxHash,
+        ) -> Result<Option<Address>, Error>;
+
+        fn get_reverted_settlements(
+            &self,
+            epoch_number: EpochNumber,
+        ) -> Result<Vec<CertificateId>, Error>;
+
+        fn get_settlement_costs(
+            &self,
+            network_id: NetworkId,
+            from_epoch: EpochNumber,
+            to_epoch: EpochNumber,
+        ) -> Result<Vec<EpochSettlementCosts>, Error>;
+
+        fn get_certificate_callbacks(
+            &self,
+        ) -> Result<Vec<(CertificateId, CertificateCallback)>, Error>;
+
+        fn get_certificate_callback(
+            &self,
+            certificate_id: &CertificateId,
+        ) -> Result<Option<CertificateCallback>, Error>;
+
+        fn get_audit_log(&self, certificate_id: &CertificateId) -> Result<Vec<AuditRecord>, Error>;
+
+        fn get_events(
+            &self,
+            after: Option<EventId>,
+            limit: usize,
+        ) -> Result<Vec<(EventId, LoggedEvent)>, Error>;
+
+        fn get_latest_event_id(&self) -> Result<Option<EventId>, Error>;
+
+        fn subscribe_events(&self) -> watch::Receiver<Option<EventId>>;
+
+        fn get_api_key_usage(
+            &self,
+            period: u64,
+        ) -> Result<std::collections::BTreeMap<String, ApiKeyUsage>, Error>;
+
         fn get_current_settled_height(&self) -> Result<Vec<(NetworkId, SettledCertificate)>, Error>;
 
         fn read_local_network_state(
             &self,
             network_id: NetworkId,
         ) -> Result<Option<LocalNetworkStateData>, Error>;
+
+        fn get_settled_roots(
+            &self,
+            network_id: NetworkId,
+            height: Height,
+        ) -> Result<Option<StateCommitment>, Error>;
+
+        fn read_local_exit_tree_leaves(&self, network_id: NetworkId) -> Result<Vec<Digest>, Error>;
+
+        fn read_local_exit_tree_leaf_count(&self, network_id: NetworkId) -> Result<u32, Error>;
     }
 }
@@ -1,6 +1,6 @@
 //! Definitions of the certificate storage format with backwards compatibility.
 //!
-//! Currently, we have two versions of certificate storage format. The first
+//! Currently, we have three versions of certificate storage format. The first
 //! byte determines the storage format version.
 //!
 //! In version 0, where backwards compatibility is required, the first byte
@@ -13,6 +13,12 @@
 //! straightforward encoding of the certificate, restoring the full range of
 //! network IDs.
 //!
+//! Version 2 is the version byte followed by the zstd-compressed `v1` encoding,
+//! including its own version byte. It is only used for the certificates whose
+//! `v1` encoding exceeds [`COMPRESSION_THRESHOLD`], such as the ones with many
+//! bridge exits or with an aggchain proof, the smaller ones being stored in
+//! `v1`.
+//!
 //! In the unlikely scenario where it turns out we need more than 256 storage
 //! format versions, another byte can be allocated to specify a "sub-version" in
 //! one of the future versions.
@@ -337,15 +343,36 @@ impl From<AggchainDataV1<'_>> for AggchainData {
 /// Type specifying the current certificate encoding format.
 type CurrentCertificate<'a> = CertificateV1<'a>;
 
+/// Version byte of the compressed certificate encoding format (`v2`).
+const COMPRESSED_VERSION: u8 = 2;
+
+/// Size of the encoding above which a certificate is stored compressed.
+const COMPRESSION_THRESHOLD: usize = 1024;
+
 fn decode<T: for<'de> Deserialize<'de> + Into<Certificate>>(
     bytes: &[u8],
 ) -> Result<Certificate, CodecError> {
     Ok(bincode_codec().deserialize::<T>(bytes)?.into())
 }
 
+fn decode_compressed(bytes: &[u8]) -> Result<Certificate, CodecError> {
+    let bytes = zstd::stream::decode_all(bytes).map_err(CodecError::Decompression)?;
+
+    decode::<CertificateV1>(&bytes)
+}
+
 impl crate::columns::Codec for Certificate {
-    fn encode_into<W: std::io::Write>(&self, writer: W) -> Result<(), CodecError> {
-        Ok(bincode_codec().serialize_into(writer, &CurrentCertificate::from(self))?)
+    fn encode_into<W: std::io::Write>(&self, mut writer: W) -> Result<(), CodecError> {
+        let bytes = bincode_codec().serialize(&CurrentCertificate::from(self))?;
+        if bytes.len() <= COMPRESSION_THRESHOLD {
+            writer.write_all(&bytes)?;
+            return Ok(());
+        }
+
+        writer.write_all(&[COMPRESSED_VERSION])?;
+        zstd::stream::copy_encode(bytes.as_slice(), writer, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+
+        Ok(())
     }
 
     fn decode(bytes: &[u8]) -> Result<Self, CodecError> {
@@ -353,6 +380,7 @@ impl crate::columns::Codec for Certificate {
             None => Err(CodecError::CertificateEmpty),
             Some(0) => decode::<CertificateV0>(bytes),
             Some(1) => decode::<CertificateV1>(bytes),
+            Some(COMPRESSED_VERSION) => decode_compressed(&bytes[1..]),
             Some(version) => Err(CodecError::BadCertificateVersion { version }),
         }
     }
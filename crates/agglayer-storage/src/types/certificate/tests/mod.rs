@@ -384,22 +384,55 @@ fn regressions(#[case] cert_filename: &str) {
     let _certificate = Certificate::decode(&bytes).expect("decoding failed");
 }
 
+#[rstest::rstest]
+#[case("n15-cert_h0")]
+#[case("n15-cert_h3")]
+fn large_cert_is_compressed(#[case] cert_name: &str) {
+    let certificate = sample_data::load_certificate(&format!("{cert_name}.json"));
+
+    let bytes = certificate.encode().unwrap();
+    assert_eq!(bytes[0], COMPRESSED_VERSION);
+
+    let uncompressed = bincode_codec()
+        .serialize(&CurrentCertificate::from(&certificate))
+        .unwrap();
+    assert!(bytes.len() < uncompressed.len());
+
+    assert_eq!(Certificate::decode(&bytes).unwrap(), certificate);
+}
+
+#[test]
+fn small_cert_is_not_compressed() {
+    let certificate = Certificate::new_for_test(74.into(), Height::new(998));
+
+    let bytes = certificate.encode().unwrap();
+    assert!(bytes.len() <= COMPRESSION_THRESHOLD);
+    assert_eq!(bytes[0], 1);
+
+    assert_eq!(Certificate::decode(&bytes).unwrap(), certificate);
+}
+
 #[test]
 fn bad_format() {
-    const NEXT_VERSION: u8 = 2;
+    const NEXT_VERSION: u8 = 3;
 
     assert!(matches!(
         Certificate::decode(&[]).unwrap_err(),
         CodecError::CertificateEmpty
     ));
 
-    for v in 0..NEXT_VERSION {
+    for v in 0..COMPRESSED_VERSION {
         assert!(matches!(
             Certificate::decode(&[v]).unwrap_err(),
             CodecError::Serialization(_)
         ));
     }
 
+    assert!(matches!(
+        Certificate::decode(&[COMPRESSED_VERSION]).unwrap_err(),
+        CodecError::Decompression(_)
+    ));
+
     for v in NEXT_VERSION..=u8::MAX {
         match Certificate::decode(&[v]).unwrap_err() {
             CodecError::BadCertificateVersion { version } => assert_eq!(version, v),
@@ -1,6 +1,8 @@
-use agglayer_types::{CertificateStatus, CertificateStatusError, Digest, NetworkId};
+use agglayer_types::{
+    CertificateStatus, CertificateStatusError, Digest, GlobalIndexError, NetworkId,
+};
 use alloy_primitives::Bytes;
-use pessimistic_proof::{error::ProofVerificationError, ProofError};
+use pessimistic_proof::{error::ProofVerificationError, unified_bridge::GlobalIndex, ProofError};
 
 use crate::columns::bincode_codec;
 
@@ -47,6 +49,15 @@ fn ver_err(error: ProofVerificationError) -> CertificateStatus {
 #[case("err-pce", err(Cse::PreCertificationError("precert".into())))]
 #[case("err-ce", err(Cse::CertificationError("cert".into())))]
 #[case("err-l1", err(Cse::L1InfoRootNotFound(0xabcd)))]
+#[case("err-st", err(Cse::SettlementTimeout(5)))]
+#[case("err-pt", err(Cse::ProvingTimeout(600)))]
+#[case("err-exp", err(Cse::Expired(3600)))]
+#[case("err-pqe", err(Cse::ProverQuotaExhausted(120)))]
+#[case("err-cancel", err(Cse::Cancelled))]
+#[case("err-tc-gi", err(Cse::TypeConversionError(agglayer_types::Error::InvalidGlobalIndex {
+    global_index: GlobalIndex::new(NetworkId::new(3), 7),
+    source: GlobalIndexError::UnusedBitsSet,
+})))]
 fn encoding(#[case] name: &'static str, #[case] status: CertificateStatus) {
     // Check for changes in encoding of certificate status.
     // Reordering arms in the status enum causes the storage encoding to change, causing
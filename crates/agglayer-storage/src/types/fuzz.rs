@@ -0,0 +1,207 @@
+//! Fuzzers making sure that corrupted values read from the database are
+//! reported as codec errors instead of panicking.
+//!
+//! Each fuzzer runs as a regular test, and can be run as a fuzz target with
+//! `cargo bolero test -p agglayer-storage <fuzzer name>`, or along with the
+//! other fuzzers with `scripts/fuzz.sh`, which seeds their corpus with the
+//! sample data.
+
+use std::path::Path;
+
+use agglayer_types::{
+    Certificate, CertificateHeader, CertificateId, CertificateIndex, Digest, EpochNumber, Height,
+    NetworkId, Proof,
+};
+use pessimistic_proof_test_suite::sample_data;
+
+use crate::{
+    columns::{
+        api_key_usage, audit_log_per_certificate, callback_per_certificate,
+        certificate_per_network, event_log,
+        latest_pending_certificate_per_network::PendingCertificate,
+        latest_proven_certificate_per_network::ProvenCertificate,
+        latest_settled_certificate_per_network::SettledCertificate, local_exit_tree_per_network,
+        pending_queue::PendingQueueKey, proof_cache_per_certificate, settled_roots_per_network,
+        settlement_attempts_per_certificate, settlement_costs_per_network, Codec,
+    },
+    types::{
+        network_info, MetadataKey, MetadataValue, PerEpochMetadataKey, PerEpochMetadataValue,
+        SmtKey, SmtValue,
+    },
+};
+
+macro_rules! make_codec_fuzzers {
+    ($($test:ident => $type:ty),* $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                bolero::check!().for_each(|bytes: &[u8]| {
+                    let _ = <$type as Codec>::decode(bytes);
+                })
+            }
+        )*
+    };
+}
+
+make_codec_fuzzers!(
+    fuzz_decode_api_key_usage_key => api_key_usage::Key,
+    fuzz_decode_api_key_usage_value => api_key_usage::Value,
+    fuzz_decode_audit_log => audit_log_per_certificate::Value,
+    fuzz_decode_callback => callback_per_certificate::Value,
+    fuzz_decode_certificate => Certificate,
+    fuzz_decode_certificate_header => CertificateHeader,
+    fuzz_decode_certificate_id => CertificateId,
+    fuzz_decode_certificate_index => CertificateIndex,
+    fuzz_decode_certificate_per_network_key => certificate_per_network::Key,
+    fuzz_decode_digest => Digest,
+    fuzz_decode_epoch_number => EpochNumber,
+    fuzz_decode_event_log_key => event_log::Key,
+    fuzz_decode_event_log_value => event_log::Value,
+    fuzz_decode_height => Height,
+    fuzz_decode_local_exit_tree_key => local_exit_tree_per_network::Key,
+    fuzz_decode_local_exit_tree_value => local_exit_tree_per_network::Value,
+    fuzz_decode_metadata_key => MetadataKey,
+    fuzz_decode_metadata_value => MetadataValue,
+    fuzz_decode_network_id => NetworkId,
+    fuzz_decode_network_info_key => network_info::Key,
+    fuzz_decode_network_info_value => network_info::Value,
+    fuzz_decode_pending_certificate => PendingCertificate,
+    fuzz_decode_pending_queue_key => PendingQueueKey,
+    fuzz_decode_per_epoch_metadata_key => PerEpochMetadataKey,
+    fuzz_decode_per_epoch_metadata_value => PerEpochMetadataValue,
+    fuzz_decode_proof => Proof,
+    fuzz_decode_proof_cache => proof_cache_per_certificate::Value,
+    fuzz_decode_proven_certificate => ProvenCertificate,
+    fuzz_decode_settled_certificate => SettledCertificate,
+    fuzz_decode_settled_roots_key => settled_roots_per_network::Key,
+    fuzz_decode_settled_roots_value => settled_roots_per_network::Value,
+    fuzz_decode_settlement_attempts => settlement_attempts_per_certificate::Value,
+    fuzz_decode_settlement_costs_key => settlement_costs_per_network::Key,
+    fuzz_decode_settlement_costs_value => settlement_costs_per_network::Value,
+    fuzz_decode_smt_key => SmtKey,
+    fuzz_decode_smt_value => SmtValue,
+);
+
+/// Certificates of the sample data.
+fn sample_certificates() -> Vec<Certificate> {
+    [
+        "cert_h0.json",
+        "cert_h1.json",
+        "cert_h2.json",
+        "n15-cert_h0.json",
+    ]
+    .into_iter()
+    .map(sample_data::load_certificate)
+    .collect()
+}
+
+/// Header of the given certificate, as stored once it is pending.
+fn pending_header(certificate: &Certificate) -> CertificateHeader {
+    CertificateHeader {
+        network_id: certificate.network_id,
+        height: certificate.height,
+        epoch_number: None,
+        certificate_index: None,
+        certificate_id: certificate.hash(),
+        prev_local_exit_root: certificate.prev_local_exit_root,
+        new_local_exit_root: certificate.new_local_exit_root,
+        metadata: certificate.metadata,
+        status: agglayer_types::CertificateStatus::Pending,
+        settlement_tx_hash: None,
+    }
+}
+
+/// Encoded values derived from the sample data, used as seeds for the
+/// corruption tests.
+fn seeds() -> Vec<Vec<u8>> {
+    sample_certificates()
+        .iter()
+        .flat_map(|certificate| {
+            [
+                certificate.encode().expect("valid certificate"),
+                pending_header(certificate).encode().expect("valid header"),
+            ]
+        })
+        .chain(std::iter::once(
+            Proof::dummy().encode().expect("valid proof"),
+        ))
+        .collect()
+}
+
+/// Write the given seeds as the corpus of a fuzzer, under `dir/<fuzzer>`.
+fn write_corpus(dir: &Path, fuzzer: &str, seeds: impl IntoIterator<Item = Vec<u8>>) {
+    let dir = dir.join(fuzzer);
+    std::fs::create_dir_all(&dir).expect("corpus directory created");
+    for (index, seed) in seeds.into_iter().enumerate() {
+        std::fs::write(dir.join(format!("sample-{index}")), seed).expect("seed written");
+    }
+}
+
+/// Write the encoded sample data as the corpus of the fuzzers decoding it,
+/// under `$FUZZ_CORPUS_DIR`. Run by `scripts/fuzz.sh` before fuzzing.
+#[test]
+#[ignore = "writes the fuzzing corpus, run by scripts/fuzz.sh"]
+fn write_fuzz_corpus() {
+    let dir = std::env::var("FUZZ_CORPUS_DIR").expect("FUZZ_CORPUS_DIR is set");
+    let dir = Path::new(&dir);
+    let certificates = sample_certificates();
+
+    write_corpus(
+        dir,
+        "fuzz_decode_certificate",
+        certificates
+            .iter()
+            .map(|certificate| certificate.encode().expect("valid certificate")),
+    );
+    write_corpus(
+        dir,
+        "fuzz_decode_certificate_header",
+        certificates.iter().map(|certificate| {
+            pending_header(certificate)
+                .encode()
+                .expect("valid header")
+        }),
+    );
+    write_corpus(
+        dir,
+        "fuzz_decode_certificate_id",
+        certificates.iter().map(|certificate| {
+            certificate
+                .hash()
+                .encode()
+                .expect("valid certificate id")
+        }),
+    );
+    write_corpus(
+        dir,
+        "fuzz_decode_proof",
+        [Proof::dummy().encode().expect("valid proof")],
+    );
+}
+
+/// Maximum number of truncations and corruptions derived from each seed.
+const MAX_CORRUPTIONS: usize = 1024;
+
+/// Decode truncations and single byte corruptions of the seeds.
+fn decode_corrupted_seeds<T: Codec>() {
+    for seed in seeds() {
+        let step = seed.len() / MAX_CORRUPTIONS + 1;
+
+        for len in (0..seed.len()).step_by(step) {
+            let _ = T::decode(&seed[..len]);
+        }
+
+        for index in (0..seed.len()).step_by(step) {
+            let mut corrupted = seed.clone();
+            corrupted[index] ^= 0xff;
+            let _ = T::decode(&corrupted);
+        }
+    }
+}
+
+#[test]
+fn corrupted_seeds_do_not_panic() {
+    decode_corrupted_seeds::<Certificate>();
+    decode_corrupted_seeds::<CertificateHeader>();
+    decode_corrupted_seeds::<Proof>();
+}
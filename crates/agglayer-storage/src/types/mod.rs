@@ -8,6 +8,9 @@ mod certificate;
 mod generated;
 pub(crate) mod network_info;
 
+#[cfg(test)]
+mod fuzz;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum MetadataKey {
     LatestSettledEpoch,
@@ -24,12 +27,23 @@ pub enum MetadataValue {
 pub enum PerEpochMetadataKey {
     SettlementTxHash,
     Packed,
+    PackingIntent,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum PerEpochMetadataValue {
     SettlementTxHash(Digest),
     Packed(bool),
+    PackingIntent(PackingIntent),
+}
+
+/// Intent to pack an epoch, recorded before its packing starts and removed
+/// once it is done, so that an interrupted packing is resumed on restart.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackingIntent {
+    pub epoch_number: EpochNumber,
+    /// The certificates of the epoch, in the order of their index.
+    pub certificates: Vec<CertificateId>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
@@ -57,14 +71,16 @@ crate::columns::impl_codec_using_bincode_for!(
     CertificateIndex,
     CertificateHeader,
     Digest,
+    EpochNumber,
     Height,
     MetadataKey,
     MetadataValue,
     NetworkId,
     PerEpochMetadataKey,
     PerEpochMetadataValue,
-    Proof,
     SmtKey,
     SmtValue,
     network_info::Key
 );
+
+crate::columns::impl_codec_using_compressed_bincode_for!(Proof);
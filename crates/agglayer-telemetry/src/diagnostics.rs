@@ -0,0 +1,47 @@
+//! Diagnostics metrics for observability
+//!
+//! This module provides the metrics of the self-diagnostics task: the state of
+//! each alerting rule and the number of alerts raised.
+
+use lazy_static::lazy_static;
+use opentelemetry::{global, metrics::*, KeyValue};
+
+const AGGLAYER_DIAGNOSTICS_OTEL_SCOPE_NAME: &str = "agglayer_node_diagnostics";
+
+lazy_static! {
+    /// Gauge for the state of the alerts (1 = firing, 0 = resolved), per rule
+    pub static ref ALERT_FIRING: Gauge<u64> = global::meter(AGGLAYER_DIAGNOSTICS_OTEL_SCOPE_NAME)
+        .u64_gauge("diagnostics_alert_firing")
+        .with_description("Whether the alert of the rule is firing (1=firing, 0=resolved)")
+        .build();
+
+    /// Counter for the alerts raised, per rule
+    pub static ref ALERTS_RAISED: Counter<u64> = global::meter(AGGLAYER_DIAGNOSTICS_OTEL_SCOPE_NAME)
+        .u64_counter("diagnostics_alerts_raised_total")
+        .with_description("Total number of alerts raised by the diagnostics")
+        .build();
+}
+
+/// Helper function to record the state of the alert of a rule
+#[inline]
+pub fn record_alert_state(rule: &'static str, firing: bool) {
+    ALERT_FIRING.record(firing as u64, &[KeyValue::new("rule", rule)]);
+}
+
+/// Helper function to record an alert being raised
+#[inline]
+pub fn record_alert_raised(rule: &'static str) {
+    ALERTS_RAISED.add(1, &[KeyValue::new("rule", rule)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_functions() {
+        record_alert_state("no_settlement", false);
+        record_alert_raised("no_settlement");
+        record_alert_state("no_settlement", true);
+    }
+}
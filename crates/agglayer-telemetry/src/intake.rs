@@ -0,0 +1,85 @@
+//! Certificate intake metrics for observability
+//!
+//! This module provides metrics for monitoring the pool of workers validating
+//! the submitted certificates: the certificates waiting for a worker, the
+//! validation latencies, and the submissions rejected.
+
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use opentelemetry::{global, metrics::*, KeyValue};
+
+use crate::constant::AGGLAYER_RPC_OTEL_SCOPE_NAME;
+
+/// Boundaries of the validation latency histogram, in seconds.
+const LATENCY_BOUNDARIES: [f64; 10] = [0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+lazy_static! {
+    /// Gauge for the certificates waiting for a validation worker
+    pub static ref QUEUED: Gauge<u64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .u64_gauge("intake_queued_certificates")
+        .with_description("Number of submitted certificates waiting for a validation worker")
+        .build();
+
+    /// Histogram of the time spent waiting for a validation worker
+    pub static ref WAIT: Histogram<f64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .f64_histogram("intake_wait_duration_seconds")
+        .with_description("Time spent by the submitted certificates waiting for a validation worker, in seconds")
+        .with_unit("s")
+        .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+        .build();
+
+    /// Histogram of the validation latencies
+    pub static ref VALIDATION: Histogram<f64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .f64_histogram("intake_validation_duration_seconds")
+        .with_description("Time taken to validate the submitted certificates, in seconds")
+        .with_unit("s")
+        .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+        .build();
+
+    /// Counter for the submissions rejected at intake, per reason
+    pub static ref REJECTED: Counter<u64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .u64_counter("intake_rejected_total")
+        .with_description("Total number of submitted certificates rejected at intake")
+        .build();
+}
+
+/// Helper function to record the number of certificates waiting for a
+/// validation worker
+#[inline]
+pub fn record_queued(queued: usize) {
+    QUEUED.record(queued as u64, &[]);
+}
+
+/// Helper function to record a validation, along with the time spent waiting
+/// for a worker
+#[inline]
+pub fn record_validation(network_id: u32, wait: Duration, validation: Duration) {
+    let labels = [KeyValue::new("network_id", network_id.to_string())];
+    WAIT.record(wait.as_secs_f64(), &labels);
+    VALIDATION.record(validation.as_secs_f64(), &labels);
+}
+
+/// Helper function to record a submission rejected at intake
+#[inline]
+pub fn record_rejected(network_id: u32, reason: &'static str) {
+    REJECTED.add(
+        1,
+        &[
+            KeyValue::new("network_id", network_id.to_string()),
+            KeyValue::new("reason", reason),
+        ],
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_functions() {
+        record_queued(3);
+        record_validation(1, Duration::ZERO, Duration::from_millis(10));
+        record_rejected(1, "overloaded");
+    }
+}
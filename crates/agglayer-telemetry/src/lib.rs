@@ -23,6 +23,12 @@ mod constant;
 mod error;
 
 pub mod clock;
+pub mod diagnostics;
+pub mod intake;
+pub mod rpc;
+pub mod settlement;
+pub mod storage;
+pub mod verification;
 
 pub use error::Error;
 pub use opentelemetry::KeyValue;
@@ -0,0 +1,77 @@
+//! RPC metrics for observability
+//!
+//! This module provides per-method metrics for the requests served by the
+//! JSON-RPC API: request counts, error counts by error code and latencies.
+
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use opentelemetry::{global, metrics::*, KeyValue};
+
+use crate::constant::AGGLAYER_RPC_OTEL_SCOPE_NAME;
+
+/// Boundaries of the request latency histogram, in seconds.
+const LATENCY_BOUNDARIES: [f64; 12] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+lazy_static! {
+    /// Counter for the requests handled, per method
+    pub static ref REQUESTS: Counter<u64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .u64_counter("rpc_requests_total")
+        .with_description("Total number of RPC requests handled")
+        .build();
+
+    /// Counter for the requests answered with an error, per method and error
+    /// code
+    pub static ref ERRORS: Counter<u64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .u64_counter("rpc_errors_total")
+        .with_description("Total number of RPC requests answered with an error")
+        .build();
+
+    /// Histogram of the request latencies, per method
+    pub static ref LATENCY: Histogram<f64> = global::meter(AGGLAYER_RPC_OTEL_SCOPE_NAME)
+        .f64_histogram("rpc_request_duration_seconds")
+        .with_description("Time taken to handle the RPC requests, in seconds")
+        .with_unit("s")
+        .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+        .build();
+}
+
+/// Helper function to record a handled request, along with its error code if
+/// it failed
+pub fn record_request(
+    method: &str,
+    network_id: Option<u32>,
+    error_code: Option<i32>,
+    latency: Duration,
+) {
+    let mut labels = vec![KeyValue::new("method", method.to_string())];
+    if let Some(network_id) = network_id {
+        labels.push(KeyValue::new("network_id", network_id.to_string()));
+    }
+
+    REQUESTS.add(1, &labels);
+    LATENCY.record(latency.as_secs_f64(), &labels);
+
+    if let Some(error_code) = error_code {
+        labels.push(KeyValue::new("code", error_code.to_string()));
+        ERRORS.add(1, &labels);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_functions() {
+        record_request("interop_getEpochConfiguration", None, None, Duration::ZERO);
+        record_request(
+            "interop_sendCertificate",
+            Some(1),
+            Some(-32602),
+            Duration::from_millis(10),
+        );
+    }
+}
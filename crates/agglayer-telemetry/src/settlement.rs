@@ -0,0 +1,62 @@
+//! Settlement metrics for observability
+//!
+//! This module provides metrics for monitoring the account sending the
+//! settlement transactions to L1, the settlements deferred for lack of funds,
+//! and the verifications observed on L1 that were not initiated by the node.
+
+use lazy_static::lazy_static;
+use opentelemetry::{global, metrics::*, KeyValue};
+
+const AGGLAYER_SETTLEMENT_OTEL_SCOPE_NAME: &str = "agglayer_node_settlement";
+
+lazy_static! {
+    /// Balance of the settlement account, in gwei
+    pub static ref ACCOUNT_BALANCE: Gauge<u64> = global::meter(AGGLAYER_SETTLEMENT_OTEL_SCOPE_NAME)
+        .u64_gauge("settlement_account_balance_gwei")
+        .with_description("Balance of the L1 account sending the settlement transactions, in gwei")
+        .build();
+
+    /// Counter for settlements deferred because of insufficient funds
+    pub static ref DEFERRED_SETTLEMENTS: Counter<u64> = global::meter(AGGLAYER_SETTLEMENT_OTEL_SCOPE_NAME)
+        .u64_counter("settlements_deferred_total")
+        .with_description("Total number of settlements deferred because the settlement account cannot cover the estimated gas")
+        .build();
+
+    /// Counter for verifications observed on L1 that were not sent by the
+    /// settlement account
+    pub static ref UNEXPECTED_VERIFICATIONS: Counter<u64> = global::meter(AGGLAYER_SETTLEMENT_OTEL_SCOPE_NAME)
+        .u64_counter("unexpected_verifications_total")
+        .with_description("Total number of pessimistic verifications observed on L1 that were not sent by the settlement account")
+        .build();
+}
+
+/// Helper function to record the balance of the settlement account
+#[inline]
+pub fn record_account_balance(balance_gwei: u64) {
+    ACCOUNT_BALANCE.record(balance_gwei, &[]);
+}
+
+/// Helper function to record a settlement deferred for lack of funds
+#[inline]
+pub fn record_deferred_settlement() {
+    DEFERRED_SETTLEMENTS.add(1, &[]);
+}
+
+/// Helper function to record a verification observed on L1 that was not sent
+/// by the settlement account
+#[inline]
+pub fn record_unexpected_verification(network_id: u32) {
+    UNEXPECTED_VERIFICATIONS.add(1, &[KeyValue::new("network_id", network_id.to_string())]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_functions() {
+        record_account_balance(1_000_000_000);
+        record_deferred_settlement();
+        record_unexpected_verification(1);
+    }
+}
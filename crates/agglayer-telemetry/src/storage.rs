@@ -0,0 +1,216 @@
+//! Storage metrics for observability
+//!
+//! This module provides metrics for monitoring the RocksDB storage: per-column
+//! operation latencies and value sizes, the RocksDB internal properties such
+//! as the pending compactions, the write stalls and the SST files, the
+//! corrupted entries found by the scrubber, the manual and scheduled
+//! compactions, and the divergences of the shadow storage.
+
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use opentelemetry::{global, metrics::*, KeyValue};
+
+const AGGLAYER_STORAGE_OTEL_SCOPE_NAME: &str = "agglayer_node_storage";
+
+/// Boundaries of the operation latency histogram, in seconds.
+const LATENCY_BOUNDARIES: [f64; 12] = [
+    0.00001, 0.000025, 0.00005, 0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.1, 1.0,
+];
+
+/// Boundaries of the compaction duration histogram, in seconds.
+const COMPACTION_BOUNDARIES: [f64; 8] = [1.0, 5.0, 15.0, 60.0, 300.0, 900.0, 3600.0, 10800.0];
+
+/// Boundaries of the value size histogram, in bytes.
+const SIZE_BOUNDARIES: [f64; 10] = [
+    32.0, 128.0, 512.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0,
+];
+
+lazy_static! {
+    /// Histogram of the storage operation latencies, per column and operation
+    pub static ref OPERATION_LATENCY: Histogram<f64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .f64_histogram("storage_operation_duration_seconds")
+        .with_description("Time taken by the storage operations, in seconds")
+        .with_unit("s")
+        .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+        .build();
+
+    /// Histogram of the sizes of the values read and written, per column and
+    /// operation
+    pub static ref VALUE_SIZE: Histogram<u64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .u64_histogram("storage_value_size_bytes")
+        .with_description("Size of the values read from and written to the storage, in bytes")
+        .with_unit("By")
+        .with_boundaries(SIZE_BOUNDARIES.to_vec())
+        .build();
+
+    /// Gauge for the RocksDB integer properties, per database and column when
+    /// the property is column specific
+    pub static ref ROCKSDB_PROPERTY: Gauge<u64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .u64_gauge("rocksdb_property")
+        .with_description("Value of the RocksDB integer properties")
+        .build();
+
+    /// Gauge for the number of corrupted entries found by the latest scrub,
+    /// per database and column
+    pub static ref CORRUPTED_ENTRIES: Gauge<u64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .u64_gauge("storage_corrupted_entries")
+        .with_description("Number of corrupted entries found by the latest storage scrub")
+        .build();
+
+    /// Gauge for the column families being compacted, per database and
+    /// column
+    pub static ref COMPACTION_IN_PROGRESS: Gauge<u64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .u64_gauge("storage_compaction_in_progress")
+        .with_description("Whether the column family is being compacted")
+        .build();
+
+    /// Histogram of the compaction durations, per database, column and result
+    pub static ref COMPACTION_DURATION: Histogram<f64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .f64_histogram("storage_compaction_duration_seconds")
+        .with_description("Time taken by the compactions of the column families, in seconds")
+        .with_unit("s")
+        .with_boundaries(COMPACTION_BOUNDARIES.to_vec())
+        .build();
+
+    /// Counter of the values read which differ in the shadow storage, per
+    /// column
+    pub static ref SHADOW_MISMATCHES: Counter<u64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .u64_counter("storage_shadow_mismatches_total")
+        .with_description("Total number of values read which differ in the shadow storage")
+        .build();
+
+    /// Counter of the writes which failed on the shadow storage, per operation
+    pub static ref SHADOW_WRITE_ERRORS: Counter<u64> = global::meter(AGGLAYER_STORAGE_OTEL_SCOPE_NAME)
+        .u64_counter("storage_shadow_write_errors_total")
+        .with_description("Total number of writes which failed on the shadow storage")
+        .build();
+}
+
+/// Helper function to record the latency of an operation on a column
+#[inline]
+pub fn record_operation(column: &'static str, operation: &'static str, latency: Duration) {
+    OPERATION_LATENCY.record(
+        latency.as_secs_f64(),
+        &[
+            KeyValue::new("column", column),
+            KeyValue::new("operation", operation),
+        ],
+    );
+}
+
+/// Helper function to record the size of a value read from or written to a
+/// column
+#[inline]
+pub fn record_value_size(column: &'static str, operation: &'static str, size: usize) {
+    VALUE_SIZE.record(
+        size as u64,
+        &[
+            KeyValue::new("column", column),
+            KeyValue::new("operation", operation),
+        ],
+    );
+}
+
+/// Helper function to record the value of a RocksDB property
+#[inline]
+pub fn record_rocksdb_property(
+    db: &'static str,
+    column: Option<&str>,
+    property: &'static str,
+    value: u64,
+) {
+    let mut labels = vec![KeyValue::new("db", db), KeyValue::new("property", property)];
+    if let Some(column) = column {
+        labels.push(KeyValue::new("column", column.to_string()));
+    }
+
+    ROCKSDB_PROPERTY.record(value, &labels);
+}
+
+/// Helper function to record the number of corrupted entries of a column
+#[inline]
+pub fn record_corrupted_entries(db: &'static str, column: &str, count: u64) {
+    CORRUPTED_ENTRIES.record(
+        count,
+        &[
+            KeyValue::new("db", db),
+            KeyValue::new("column", column.to_string()),
+        ],
+    );
+}
+
+/// Helper function to record the start of the compaction of a column
+#[inline]
+pub fn record_compaction_started(db: &'static str, column: &str) {
+    COMPACTION_IN_PROGRESS.record(
+        1,
+        &[
+            KeyValue::new("db", db),
+            KeyValue::new("column", column.to_string()),
+        ],
+    );
+}
+
+/// Helper function to record the end of the compaction of a column
+#[inline]
+pub fn record_compaction_completed(
+    db: &'static str,
+    column: &str,
+    duration: Duration,
+    succeeded: bool,
+) {
+    let labels = [
+        KeyValue::new("db", db),
+        KeyValue::new("column", column.to_string()),
+    ];
+    COMPACTION_IN_PROGRESS.record(0, &labels);
+    COMPACTION_DURATION.record(
+        duration.as_secs_f64(),
+        &[
+            labels[0].clone(),
+            labels[1].clone(),
+            KeyValue::new("result", if succeeded { "success" } else { "failure" }),
+        ],
+    );
+}
+
+/// Helper function to record a value read which differs in the shadow storage
+#[inline]
+pub fn record_shadow_mismatch(column: &'static str) {
+    SHADOW_MISMATCHES.add(1, &[KeyValue::new("column", column)]);
+}
+
+/// Helper function to record a write which failed on the shadow storage
+#[inline]
+pub fn record_shadow_write_error(operation: &'static str) {
+    SHADOW_WRITE_ERRORS.add(1, &[KeyValue::new("operation", operation)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_functions() {
+        record_operation("certificate_header_cf", "get", Duration::from_micros(10));
+        record_value_size("certificate_header_cf", "read", 256);
+        record_rocksdb_property("state", None, "rocksdb.compaction-pending", 0);
+        record_rocksdb_property(
+            "state",
+            Some("certificate_header_cf"),
+            "rocksdb.estimate-num-keys",
+            42,
+        );
+        record_corrupted_entries("state", "certificate_header_cf", 0);
+        record_compaction_started("state", "certificate_header_cf");
+        record_compaction_completed(
+            "state",
+            "certificate_header_cf",
+            Duration::from_secs(3),
+            true,
+        );
+        record_shadow_mismatch("certificate_header_cf");
+        record_shadow_write_error("put");
+    }
+}
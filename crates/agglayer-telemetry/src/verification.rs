@@ -0,0 +1,86 @@
+//! Proof verification metrics for observability
+//!
+//! This module provides metrics for monitoring the pool of workers verifying
+//! the pessimistic proofs: the proofs waiting for a worker, the verification
+//! latencies, and the outcome of the verifications.
+
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use opentelemetry::{global, metrics::*, KeyValue};
+
+use crate::constant::AGGLAYER_KERNEL_OTEL_SCOPE_NAME;
+
+/// Boundaries of the verification latency histograms, in seconds.
+const LATENCY_BOUNDARIES: [f64; 10] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+lazy_static! {
+    /// Gauge for the proofs waiting for a verification worker
+    pub static ref QUEUED: Gauge<u64> = global::meter(AGGLAYER_KERNEL_OTEL_SCOPE_NAME)
+        .u64_gauge("verification_queued_proofs")
+        .with_description("Number of proofs waiting for a verification worker")
+        .build();
+
+    /// Histogram of the time spent waiting for a verification worker
+    pub static ref WAIT: Histogram<f64> = global::meter(AGGLAYER_KERNEL_OTEL_SCOPE_NAME)
+        .f64_histogram("verification_wait_duration_seconds")
+        .with_description("Time spent by the proofs waiting for a verification worker, in seconds")
+        .with_unit("s")
+        .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+        .build();
+
+    /// Histogram of the verification latencies
+    pub static ref VERIFICATION: Histogram<f64> = global::meter(AGGLAYER_KERNEL_OTEL_SCOPE_NAME)
+        .f64_histogram("verification_duration_seconds")
+        .with_description("Time taken to verify the proofs, in seconds")
+        .with_unit("s")
+        .with_boundaries(LATENCY_BOUNDARIES.to_vec())
+        .build();
+
+    /// Counter for the proofs, per outcome of their verification
+    pub static ref VERIFIED: Counter<u64> = global::meter(AGGLAYER_KERNEL_OTEL_SCOPE_NAME)
+        .u64_counter("verification_proofs_total")
+        .with_description("Total number of proofs going through verification, per outcome")
+        .build();
+}
+
+/// Helper function to record the number of proofs waiting for a verification
+/// worker
+#[inline]
+pub fn record_queued(queued: usize) {
+    QUEUED.record(queued as u64, &[]);
+}
+
+/// Helper function to record a verification, along with the time spent
+/// waiting for a worker
+#[inline]
+pub fn record_verification(wait: Duration, verification: Duration, valid: bool) {
+    WAIT.record(wait.as_secs_f64(), &[]);
+    VERIFICATION.record(verification.as_secs_f64(), &[]);
+    VERIFIED.add(
+        1,
+        &[KeyValue::new(
+            "outcome",
+            if valid { "valid" } else { "invalid" },
+        )],
+    );
+}
+
+/// Helper function to record a proof whose verification is skipped, as it
+/// was already verified
+#[inline]
+pub fn record_skipped() {
+    VERIFIED.add(1, &[KeyValue::new("outcome", "skipped")]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_helper_functions() {
+        record_queued(2);
+        record_verification(Duration::ZERO, Duration::from_millis(100), true);
+        record_skipped();
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use super::CertificateHeader;
+
+/// Certificate header along with the inclusion of its settlement transaction
+/// on L1.
+///
+/// The header fields are flattened, so that the details deserialize as a
+/// plain [`CertificateHeader`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CertificateHeaderDetails {
+    #[serde(flatten)]
+    pub header: CertificateHeader,
+    /// L1 block including the settlement transaction, `None` until it is
+    /// mined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_block_number: Option<u64>,
+    /// Number of L1 blocks since the settlement transaction was mined,
+    /// counting the block including it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+}
+
+impl From<CertificateHeader> for CertificateHeaderDetails {
+    fn from(header: CertificateHeader) -> Self {
+        Self {
+            header,
+            l1_block_number: None,
+            confirmations: None,
+        }
+    }
+}
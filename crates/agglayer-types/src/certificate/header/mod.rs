@@ -2,10 +2,14 @@ use crate::{
     CertificateId, CertificateIndex, EpochNumber, Height, LocalExitRoot, Metadata, NetworkId,
 };
 
+mod details;
 mod settlement_tx_hash;
+mod signed;
 mod status;
 
+pub use details::CertificateHeaderDetails;
 pub use settlement_tx_hash::SettlementTxHash;
+pub use signed::SignedCertificateHeader;
 pub use status::CertificateStatus;
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
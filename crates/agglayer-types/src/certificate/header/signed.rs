@@ -0,0 +1,126 @@
+use agglayer_primitives::{Address, Signature, SignatureError, B256};
+use pessimistic_proof::keccak::keccak256_combine;
+use serde::{Deserialize, Serialize};
+
+use super::{CertificateHeader, CertificateStatus};
+use crate::Digest;
+
+/// Certificate header signed by the agglayer, so that the services relaying
+/// it can check that it originates from the agglayer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedCertificateHeader {
+    pub header: CertificateHeader,
+    /// Signature of the [`CertificateHeader::signature_commitment`] by the
+    /// agglayer.
+    pub signature: Signature,
+}
+
+impl SignedCertificateHeader {
+    /// Recover the signer of the header, to be compared with the address of
+    /// the agglayer.
+    pub fn recover_signer(&self) -> Result<Address, SignatureError> {
+        let commitment = self.header.signature_commitment();
+
+        self.signature
+            .recover_address_from_prehash(&B256::new(commitment.0))
+    }
+}
+
+impl CertificateHeader {
+    /// Computes the commitment signed by the agglayer when serving the header.
+    ///
+    /// The optional fields are prefixed by a presence byte. The status is
+    /// committed to by its variant only, the details of the errors are not.
+    pub fn signature_commitment(&self) -> Digest {
+        let epoch_number = optional(self.epoch_number.map(|epoch| epoch.as_u64()));
+        let certificate_index = optional(self.certificate_index.map(|index| index.as_u64()));
+        let settlement_tx_hash = match self.settlement_tx_hash {
+            Some(tx_hash) => [[1u8].as_slice(), Digest::from(tx_hash).as_slice()].concat(),
+            None => vec![0u8],
+        };
+
+        keccak256_combine([
+            self.certificate_id.as_digest().as_slice(),
+            self.network_id.to_be_bytes().as_slice(),
+            self.height.as_u64().to_be_bytes().as_slice(),
+            epoch_number.as_slice(),
+            certificate_index.as_slice(),
+            self.prev_local_exit_root.as_ref(),
+            self.new_local_exit_root.as_ref(),
+            self.metadata.as_digest().as_slice(),
+            [self.status.commitment_tag()].as_slice(),
+            settlement_tx_hash.as_slice(),
+        ])
+    }
+}
+
+impl CertificateStatus {
+    fn commitment_tag(&self) -> u8 {
+        match self {
+            CertificateStatus::Pending => 0,
+            CertificateStatus::Proven => 1,
+            CertificateStatus::Candidate => 2,
+            CertificateStatus::InError { .. } => 3,
+            CertificateStatus::Settled => 4,
+        }
+    }
+}
+
+fn optional(value: Option<u64>) -> Vec<u8> {
+    match value {
+        Some(value) => [[1u8].as_slice(), value.to_be_bytes().as_slice()].concat(),
+        None => vec![0u8],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::{local::PrivateKeySigner, SignerSync as _};
+
+    use super::*;
+    use crate::{
+        Certificate, CertificateId, CertificateIndex, EpochNumber, Height, LocalExitRoot, Metadata,
+        NetworkId, SettlementTxHash,
+    };
+
+    fn header(status: CertificateStatus) -> CertificateHeader {
+        CertificateHeader {
+            network_id: NetworkId::new(1),
+            height: Height::new(2),
+            epoch_number: Some(EpochNumber::ONE),
+            certificate_index: Some(CertificateIndex::new(0)),
+            certificate_id: CertificateId::new(Digest([0x01; 32])),
+            prev_local_exit_root: LocalExitRoot::new(Digest([0x02; 32])),
+            new_local_exit_root: LocalExitRoot::new(Digest([0x03; 32])),
+            metadata: Metadata::DEFAULT,
+            status,
+            settlement_tx_hash: Some(SettlementTxHash::for_tests()),
+        }
+    }
+
+    fn sign(wallet: &PrivateKeySigner, header: CertificateHeader) -> SignedCertificateHeader {
+        let signature = wallet
+            .sign_hash_sync(&B256::new(header.signature_commitment().0))
+            .unwrap()
+            .into();
+
+        SignedCertificateHeader { header, signature }
+    }
+
+    #[test]
+    fn signer_is_recovered() {
+        let wallet = Certificate::wallet_for_test(NetworkId::ETH_L1);
+        let signed = sign(&wallet, header(CertificateStatus::Settled));
+
+        assert_eq!(signed.recover_signer().unwrap(), wallet.address().into());
+    }
+
+    #[test]
+    fn tampered_header_recovers_another_signer() {
+        let wallet = Certificate::wallet_for_test(NetworkId::ETH_L1);
+        let mut signed = sign(&wallet, header(CertificateStatus::Candidate));
+        signed.header.status = CertificateStatus::Settled;
+
+        assert_ne!(signed.recover_signer().unwrap(), wallet.address().into());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::Digest;
+use pessimistic_proof::keccak::{keccak256, keccak256_combine};
+
+use crate::{bincode, Digest, Proof};
 
 #[derive(
     Clone,
@@ -28,4 +30,23 @@ impl CertificateId {
     pub const fn as_digest(&self) -> &Digest {
         &self.0
     }
+
+    /// Commitment signed by the network to cancel the certificate while it
+    /// is still pending.
+    pub fn cancellation_commitment(&self) -> Digest {
+        keccak256_combine([b"CANCEL_CERTIFICATE".as_slice(), self.0.as_slice()])
+    }
+
+    /// Commitment signed by the network to submit a proof of the certificate
+    /// generated outside of the agglayer. The proof is bound through the
+    /// keccak hash of its bincode encoding.
+    pub fn proof_submission_commitment(&self, proof: &Proof) -> Result<Digest, bincode::Error> {
+        let proof_digest = keccak256(&bincode::default().serialize(proof)?);
+
+        Ok(keccak256_combine([
+            b"SUBMIT_PROOF".as_slice(),
+            self.0.as_slice(),
+            proof_digest.as_slice(),
+        ]))
+    }
 }
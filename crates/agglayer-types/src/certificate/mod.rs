@@ -21,7 +21,10 @@ mod metadata;
 #[cfg(feature = "testutils")]
 mod testutils;
 
-pub use header::{CertificateHeader, CertificateStatus, SettlementTxHash};
+pub use header::{
+    CertificateHeader, CertificateHeaderDetails, CertificateStatus, SettlementTxHash,
+    SignedCertificateHeader,
+};
 pub use height::Height;
 pub use id::CertificateId;
 pub use index::CertificateIndex;
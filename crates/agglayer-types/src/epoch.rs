@@ -46,3 +46,38 @@ pub struct EpochConfiguration {
     /// The duration of an epoch in blocks.
     pub epoch_duration: u64,
 }
+
+/// Status of the current epoch, for the submitters to time their
+/// certificates.
+///
+/// The blocks are the L1 blocks for a block clock, and the seconds elapsed
+/// since the start of the clock for a time clock.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct EpochStatus {
+    pub epoch_number: EpochNumber,
+    /// The first block of the epoch.
+    pub start_block: u64,
+    /// The block at which the epoch closes, the first one of the next epoch.
+    pub end_block: u64,
+    /// The number of blocks left before the epoch closes.
+    pub remaining_blocks: u64,
+    /// The estimated time at which the epoch closes, in seconds since the UNIX
+    /// epoch. Unknown for a manual clock.
+    pub expected_close_time: Option<u64>,
+    /// The number of networks which settled a certificate in the epoch.
+    pub settled_networks: u64,
+    /// The number of known networks which can still settle a certificate in
+    /// the epoch, a network settling at most one certificate per epoch.
+    pub remaining_capacity: u64,
+}
+
+/// Lifecycle event of an epoch.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum EpochEvent {
+    /// The epoch is opened, the settled certificates are now assigned to it.
+    Opened { epoch_number: EpochNumber },
+    /// The epoch is closed and its packing started.
+    PackingStarted { epoch_number: EpochNumber },
+    /// The epoch is packed, all its certificates are settled.
+    Settled { epoch_number: EpochNumber },
+}
@@ -0,0 +1,101 @@
+use agglayer_tries::roots::LocalExitRoot;
+use pessimistic_proof::keccak::keccak256_combine;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    CertificateHeader, CertificateId, Digest, EpochNumber, Height, NetworkId, SettlementTxHash,
+};
+
+/// Certificates settled in an epoch, committed to once the epoch is settled
+/// as a compact checkpoint of the agglayer activity.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochSummary {
+    pub epoch_number: EpochNumber,
+    /// The certificates of the epoch, in the order of their index in it.
+    pub certificates: Vec<EpochSummaryEntry>,
+}
+
+/// Certificate settled in an epoch, as committed to in its [`EpochSummary`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochSummaryEntry {
+    pub certificate_id: CertificateId,
+    pub network_id: NetworkId,
+    pub height: Height,
+    pub new_local_exit_root: LocalExitRoot,
+    pub settlement_tx_hash: Option<SettlementTxHash>,
+}
+
+impl From<&CertificateHeader> for EpochSummaryEntry {
+    fn from(header: &CertificateHeader) -> Self {
+        Self {
+            certificate_id: header.certificate_id,
+            network_id: header.network_id,
+            height: header.height,
+            new_local_exit_root: header.new_local_exit_root,
+            settlement_tx_hash: header.settlement_tx_hash,
+        }
+    }
+}
+
+impl EpochSummaryEntry {
+    pub fn hash(&self) -> Digest {
+        let settlement_tx_hash = self.settlement_tx_hash.map_or(Digest::ZERO, Digest::from);
+
+        keccak256_combine([
+            self.certificate_id.as_digest().as_slice(),
+            self.network_id.to_be_bytes().as_slice(),
+            self.height.as_u64().to_be_bytes().as_slice(),
+            self.new_local_exit_root.as_ref(),
+            settlement_tx_hash.as_slice(),
+        ])
+    }
+}
+
+impl EpochSummary {
+    /// Commitment to the epoch number and to its certificates, in order.
+    pub fn commitment(&self) -> Digest {
+        let commit_certificates =
+            keccak256_combine(self.certificates.iter().map(|entry| entry.hash()));
+
+        keccak256_combine([
+            self.epoch_number.as_u64().to_be_bytes().as_slice(),
+            commit_certificates.as_slice(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(network_id: u32, height: u64) -> EpochSummaryEntry {
+        EpochSummaryEntry {
+            certificate_id: CertificateId::new(Digest([network_id as u8; 32])),
+            network_id: NetworkId::new(network_id),
+            height: Height::new(height),
+            new_local_exit_root: LocalExitRoot::new(Digest([0x09; 32])),
+            settlement_tx_hash: Some(SettlementTxHash::for_tests()),
+        }
+    }
+
+    #[test]
+    fn commitment_covers_the_epoch_and_the_certificate_order() {
+        let summary = EpochSummary {
+            epoch_number: EpochNumber::ONE,
+            certificates: vec![entry(1, 0), entry(2, 3)],
+        };
+
+        let other_epoch = EpochSummary {
+            epoch_number: EpochNumber::new(2),
+            ..summary.clone()
+        };
+        let reordered = EpochSummary {
+            certificates: vec![entry(2, 3), entry(1, 0)],
+            ..summary.clone()
+        };
+
+        assert_eq!(summary.commitment(), summary.clone().commitment());
+        assert_ne!(summary.commitment(), other_epoch.commitment());
+        assert_ne!(summary.commitment(), reordered.commitment());
+    }
+}
@@ -5,7 +5,7 @@ use pessimistic_proof::{core::MultisigError, error::ProofVerificationError, Proo
 use serde::{Deserialize, Serialize};
 use unified_bridge::{GlobalIndex, LocalExitTreeError, NetworkId, TokenInfo};
 
-use crate::{aggchain_data::AggchainDataError, Digest, GenerationType};
+use crate::{aggchain_data::AggchainDataError, Digest, GenerationType, GlobalIndexError};
 
 #[derive(Debug, thiserror::Error, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename = "agglayer_types::Error")]
@@ -94,6 +94,13 @@ pub enum Error {
 
     #[error("Invalid multisig, signature or aggchain proof related data. {0:?}")]
     InvalidChainData(AggchainDataError),
+
+    /// The global index of an imported bridge exit is malformed.
+    #[error("Invalid global index {global_index:?}: {source}")]
+    InvalidGlobalIndex {
+        global_index: GlobalIndex,
+        source: GlobalIndexError,
+    },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error, PartialEq, Eq)]
@@ -135,6 +142,32 @@ pub enum CertificateStatusError {
 
     #[error("Last pessimistic root not found for network: {0}")]
     LastPessimisticRootNotFound(NetworkId),
+
+    /// The settlement transaction was not mined after the maximum number of
+    /// submission attempts.
+    #[error("Settlement timeout after {0} attempts")]
+    SettlementTimeout(u32),
+
+    /// The proof was not generated within the proving timeout, in seconds.
+    /// The certificate can be resubmitted to retry its proving.
+    #[error("Proving timeout after {0}s")]
+    ProvingTimeout(u64),
+
+    /// The certificate stayed pending without being certified for longer
+    /// than the pending TTL, in seconds. The certificate can be resubmitted.
+    #[error("Expired after being pending for {0}s")]
+    Expired(u64),
+
+    /// The quota of the SP1 network was exhausted for longer than the queue
+    /// wait, with the number of seconds until it frees up. The certificate
+    /// can be resubmitted once it does.
+    #[error("SP1 network quota exhausted, retry in {0}s")]
+    ProverQuotaExhausted(u64),
+
+    /// The certificate was withdrawn by its network while still pending. The
+    /// height can be submitted again.
+    #[error("Cancelled by the network")]
+    Cancelled,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,26 @@
+use agglayer_tries::roots::LocalExitRoot;
+use pessimistic_proof::local_exit_tree::LOCAL_EXIT_TREE_DEPTH;
+use serde::{Deserialize, Serialize};
+use unified_bridge::LETMerkleProof;
+
+use crate::{Digest, Height, NetworkId};
+
+/// Merkle proof of a leaf of the local exit tree of a network against its
+/// latest settled local exit root.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SettledExitProof {
+    /// The network the local exit tree belongs to.
+    pub network_id: NetworkId,
+    /// The height of the latest settled certificate of the network.
+    pub settled_height: Height,
+    /// The local exit root the proof is against.
+    pub local_exit_root: LocalExitRoot,
+    /// The number of leaves of the settled local exit tree.
+    pub leaf_count: u32,
+    /// The index of the proven leaf.
+    pub leaf_index: u32,
+    /// The hash of the proven bridge exit.
+    pub leaf: Digest,
+    /// The siblings of the leaf, from the bottom of the tree up.
+    pub proof: LETMerkleProof<LOCAL_EXIT_TREE_DEPTH>,
+}
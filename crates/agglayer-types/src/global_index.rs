@@ -0,0 +1,137 @@
+//! Typed decoding of the global index of the imported bridge exits.
+//!
+//! The global index is a 256-bit word identifying a bridge exit across the
+//! networks, laid out as follows:
+//!
+//! | Bits      | Content                                         |
+//! |-----------|-------------------------------------------------|
+//! | `0..32`   | Leaf index in the local exit tree of the origin |
+//! | `32..64`  | Rollup index, i.e. the network id minus one     |
+//! | `64`      | Mainnet flag, set when the origin is L1         |
+//! | `65..256` | Unused, must be zero                            |
+
+use serde::{Deserialize, Serialize};
+use unified_bridge::GlobalIndex;
+
+use crate::{NetworkId, U256};
+
+const ROLLUP_INDEX_OFFSET: usize = 32;
+const MAINNET_FLAG_OFFSET: usize = 64;
+
+/// Errors on the decoding of a global index.
+#[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error, PartialEq, Eq)]
+pub enum GlobalIndexError {
+    /// The bits above the mainnet flag are not all zero.
+    #[error("Unused bits of the global index are set")]
+    UnusedBitsSet,
+    /// The mainnet flag is set along with a rollup index.
+    #[error("Global index with the mainnet flag set has a rollup index of {0}")]
+    RollupIndexOnMainnet(u32),
+    /// The rollup index does not map to any network id.
+    #[error("Rollup index {0} does not map to any network id")]
+    RollupIndexOverflow(u32),
+}
+
+/// The fields of a global index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodedGlobalIndex {
+    pub mainnet_flag: bool,
+    pub rollup_index: u32,
+    pub leaf_index: u32,
+}
+
+impl DecodedGlobalIndex {
+    /// Decode the given global index, checking that its fields are
+    /// consistent.
+    pub fn decode(value: U256) -> Result<Self, GlobalIndexError> {
+        if value >> (MAINNET_FLAG_OFFSET + 1) != U256::ZERO {
+            return Err(GlobalIndexError::UnusedBitsSet);
+        }
+
+        let decoded = Self {
+            mainnet_flag: value.bit(MAINNET_FLAG_OFFSET),
+            rollup_index: (value >> ROLLUP_INDEX_OFFSET).wrapping_to::<u32>(),
+            leaf_index: value.wrapping_to::<u32>(),
+        };
+
+        if decoded.mainnet_flag && decoded.rollup_index != 0 {
+            return Err(GlobalIndexError::RollupIndexOnMainnet(decoded.rollup_index));
+        }
+
+        if !decoded.mainnet_flag && decoded.rollup_index == u32::MAX {
+            return Err(GlobalIndexError::RollupIndexOverflow(decoded.rollup_index));
+        }
+
+        Ok(decoded)
+    }
+
+    /// Encode the fields back into a global index.
+    pub fn encode(&self) -> U256 {
+        (U256::from(self.mainnet_flag) << MAINNET_FLAG_OFFSET)
+            | (U256::from(self.rollup_index) << ROLLUP_INDEX_OFFSET)
+            | U256::from(self.leaf_index)
+    }
+
+    /// Network id of the origin of the bridge exit.
+    ///
+    /// The rollup index is assumed to be valid, as checked by
+    /// [`Self::decode`].
+    pub fn network_id(&self) -> NetworkId {
+        if self.mainnet_flag {
+            NetworkId::new(0)
+        } else {
+            NetworkId::new(self.rollup_index + 1)
+        }
+    }
+}
+
+impl From<DecodedGlobalIndex> for GlobalIndex {
+    fn from(value: DecodedGlobalIndex) -> Self {
+        GlobalIndex::new(value.network_id(), value.leaf_index)
+    }
+}
+
+/// Validate the given global index, returning its decoded fields.
+pub fn validate_global_index(
+    global_index: GlobalIndex,
+) -> Result<DecodedGlobalIndex, GlobalIndexError> {
+    DecodedGlobalIndex::decode(global_index.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::mainnet(NetworkId::new(0), 12)]
+    #[case::rollup(NetworkId::new(1), 0)]
+    #[case::last_rollup(NetworkId::new(u32::MAX), u32::MAX)]
+    fn roundtrip(#[case] network_id: NetworkId, #[case] leaf_index: u32) {
+        let global_index = GlobalIndex::new(network_id, leaf_index);
+
+        let decoded = validate_global_index(global_index).unwrap();
+        assert_eq!(decoded.network_id(), network_id);
+        assert_eq!(decoded.leaf_index, leaf_index);
+        assert_eq!(decoded.mainnet_flag, network_id == NetworkId::new(0));
+
+        assert_eq!(decoded.encode(), U256::from(global_index));
+        assert_eq!(GlobalIndex::from(decoded), global_index);
+    }
+
+    #[rstest]
+    #[case::unused_bits(U256::from(1) << 65, GlobalIndexError::UnusedBitsSet)]
+    #[case::highest_bit(U256::from(1) << 255, GlobalIndexError::UnusedBitsSet)]
+    #[case::rollup_on_mainnet(
+        (U256::from(1) << 64) | (U256::from(3) << 32),
+        GlobalIndexError::RollupIndexOnMainnet(3)
+    )]
+    #[case::rollup_overflow(
+        U256::from(u32::MAX) << 32,
+        GlobalIndexError::RollupIndexOverflow(u32::MAX)
+    )]
+    fn invalid(#[case] value: U256, #[case] expected: GlobalIndexError) {
+        assert_eq!(DecodedGlobalIndex::decode(value), Err(expected));
+    }
+}
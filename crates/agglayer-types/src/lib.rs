@@ -1,25 +1,48 @@
-pub use agglayer_interop_types::{aggchain_proof, bincode, NetworkId};
+pub use agglayer_interop_types::{aggchain_proof, bincode, NetworkId, TokenInfo};
 pub use agglayer_primitives::{self as primitives, Address, Digest, Signature, B256, U256, U512};
 use agglayer_tries::roots::LocalExitRoot;
 pub use pessimistic_proof::proof::Proof;
+pub use unified_bridge::GlobalIndex;
 
 pub mod aggchain_data;
 
 mod certificate;
 mod epoch;
+mod epoch_summary;
 mod error;
+mod exit_proof;
+mod global_index;
 mod local_network_state;
+mod native_execution;
 mod network_info;
+mod network_state_export;
 mod proof_modes;
+mod proving_cost;
+mod settlement_costs;
+mod submission_receipt;
+mod version_info;
 
 #[cfg(feature = "testutils")]
 pub use certificate::compute_signature_info;
 pub use certificate::{
-    Certificate, CertificateHeader, CertificateId, CertificateIndex, CertificateStatus, Height,
-    Metadata, SettlementTxHash,
+    Certificate, CertificateHeader, CertificateHeaderDetails, CertificateId, CertificateIndex,
+    CertificateStatus, Height, Metadata, SettlementTxHash, SignedCertificateHeader,
 };
-pub use epoch::{EpochConfiguration, EpochNumber};
+pub use epoch::{EpochConfiguration, EpochEvent, EpochNumber, EpochStatus};
+pub use epoch_summary::{EpochSummary, EpochSummaryEntry};
 pub use error::{CertificateStatusError, Error, SignerError};
+pub use exit_proof::SettledExitProof;
+pub use global_index::{validate_global_index, DecodedGlobalIndex, GlobalIndexError};
 pub use local_network_state::{L1WitnessCtx, LocalNetworkStateData, PessimisticRootInput};
-pub use network_info::{NetworkInfo, NetworkStatus, NetworkType, SettledClaim};
+pub use native_execution::{BalanceChange, NativeExecutionReport};
+pub use network_info::{
+    NetworkInfo, NetworkRoots, NetworkStatus, NetworkSummary, NetworkType, SettledClaim,
+};
+pub use network_state_export::{
+    NetworkStateExport, NetworkStateExportError, NETWORK_STATE_EXPORT_VERSION,
+};
 pub use proof_modes::{ExecutionMode, GenerationType};
+pub use proving_cost::ProvingCostEstimate;
+pub use settlement_costs::{EpochSettlementCosts, SettlementCosts, SettlementCostsReport};
+pub use submission_receipt::{CertificateSubmissionReceipt, EpochWindow, QueuePosition};
+pub use version_info::{BuildInfo, VersionInfo};
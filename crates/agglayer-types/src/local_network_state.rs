@@ -4,8 +4,13 @@ use agglayer_interop_types::{ImportedBridgeExit, TokenInfo};
 use agglayer_primitives::{ruint::UintTryFrom, FromBool, Hashable};
 use agglayer_tries::{roots::LocalExitRoot, smt::Smt};
 use pessimistic_proof::{
-    core::commitment::{PessimisticRootCommitmentValues, PessimisticRootCommitmentVersion},
+    core::{
+        balance_token_info,
+        commitment::{PessimisticRootCommitmentValues, PessimisticRootCommitmentVersion},
+        moves_balance,
+    },
     local_balance_tree::{LocalBalancePath, LocalBalanceTree, LOCAL_BALANCE_TREE_DEPTH},
+    local_exit_tree::LOCAL_EXIT_TREE_DEPTH,
     local_state::StateCommitment,
     multi_batch_header::MultiBatchHeader,
     nullifier_tree::{NullifierKey, NullifierPath, NullifierTree, NULLIFIER_TREE_DEPTH},
@@ -15,7 +20,7 @@ use unified_bridge::LocalExitTree;
 
 use crate::{
     aggchain_data::{CertificateAggchainDataCtx, CertificateAggchainDataWithCtx},
-    Certificate, Digest, Error, U256, U512,
+    validate_global_index, Certificate, Digest, Error, U256, U512,
 };
 
 /// Local state data of one network.
@@ -23,7 +28,7 @@ use crate::{
 #[derive(Clone, Debug, Default)]
 pub struct LocalNetworkStateData {
     /// The local exit tree without leaves.
-    pub exit_tree: LocalExitTree,
+    pub exit_tree: LocalExitTree<LOCAL_EXIT_TREE_DEPTH>,
     /// The full local balance tree.
     pub balance_tree: Smt<LOCAL_BALANCE_TREE_DEPTH>,
     /// The full nullifier tree.
@@ -62,6 +67,9 @@ pub struct L1WitnessCtx {
     pub l1_info_root: Digest,
     pub prev_pessimistic_root: PessimisticRootInput,
     pub aggchain_data_ctx: CertificateAggchainDataCtx,
+    /// Bridged ERC20 used as native gas token by the network, `None` for L1
+    /// ETH.
+    pub gas_token: Option<TokenInfo>,
 }
 
 impl LocalNetworkStateData {
@@ -84,6 +92,7 @@ impl LocalNetworkStateData {
             prev_pessimistic_root,
             l1_info_root,
             aggchain_data_ctx,
+            gas_token,
         } = ctx_from_l1;
 
         let gers_are_consistent = certificate
@@ -95,6 +104,14 @@ impl LocalNetworkStateData {
             return Err(Error::InconsistentGlobalExitRoot);
         }
 
+        for imported_bridge_exit in &certificate.imported_bridge_exits {
+            let global_index = imported_bridge_exit.global_index;
+            validate_global_index(global_index).map_err(|source| Error::InvalidGlobalIndex {
+                global_index,
+                source,
+            })?;
+        }
+
         // Retrieve the pp root
         let prev_pessimistic_root = match prev_pessimistic_root {
             PessimisticRootInput::Fetched(settled_from_l1) => settled_from_l1,
@@ -121,23 +138,29 @@ impl LocalNetworkStateData {
         }
 
         let balances_proofs: BTreeMap<TokenInfo, (U256, LocalBalancePath)> = {
-            // Consider all the imported bridge exits except for the native token
+            // Consider all the imported bridge exits except for the native token and the
+            // messages without value
             let imported_bridge_exits = certificate.imported_bridge_exits.iter().filter(|b| {
-                b.bridge_exit.amount_token_info().origin_network != certificate.network_id
+                balance_token_info(&b.bridge_exit, gas_token).origin_network
+                    != certificate.network_id
+                    && moves_balance(&b.bridge_exit)
             });
 
-            // Consider all the bridge exits except for the native token
-            let bridge_exits = certificate
-                .bridge_exits
-                .iter()
-                .filter(|b| b.amount_token_info().origin_network != certificate.network_id);
+            // Consider all the bridge exits except for the native token and the messages
+            // without value
+            let bridge_exits = certificate.bridge_exits.iter().filter(|b| {
+                balance_token_info(b, gas_token).origin_network != certificate.network_id
+                    && moves_balance(b)
+            });
 
             // Set of dedup tokens mutated in the transition
             let mutated_tokens: BTreeSet<TokenInfo> = {
                 let imported_tokens = imported_bridge_exits
                     .clone()
-                    .map(|exit| exit.bridge_exit.amount_token_info());
-                let exported_tokens = bridge_exits.clone().map(|exit| exit.amount_token_info());
+                    .map(|exit| balance_token_info(&exit.bridge_exit, gas_token));
+                let exported_tokens = bridge_exits
+                    .clone()
+                    .map(|exit| balance_token_info(exit, gas_token));
                 imported_tokens.chain(exported_tokens).collect()
             };
 
@@ -156,7 +179,7 @@ impl LocalNetworkStateData {
                 .collect();
 
             for imported_bridge_exit in imported_bridge_exits {
-                let token = imported_bridge_exit.bridge_exit.amount_token_info();
+                let token = balance_token_info(&imported_bridge_exit.bridge_exit, gas_token);
                 new_balances.insert(
                     token,
                     new_balances[&token]
@@ -166,7 +189,7 @@ impl LocalNetworkStateData {
             }
 
             for bridge_exit in bridge_exits {
-                let token = bridge_exit.amount_token_info();
+                let token = balance_token_info(bridge_exit, gas_token);
                 new_balances.insert(
                     token,
                     new_balances[&token]
@@ -255,6 +278,7 @@ impl LocalNetworkStateData {
             prev_pessimistic_root,
             aggchain_data,
             certificate_id: certificate.hash().into(),
+            gas_token,
         })
     }
 
@@ -0,0 +1,27 @@
+use pessimistic_proof::local_state::StateCommitment;
+use serde::{Deserialize, Serialize};
+
+use crate::{CertificateId, Digest, TokenInfo, U256};
+
+/// Outcome of the native execution of a certificate on top of a state of its
+/// network, for the chains to debug their certificates.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NativeExecutionReport {
+    pub certificate_id: CertificateId,
+    /// Roots of the network state the certificate was applied on.
+    pub initial_roots: StateCommitment,
+    /// Roots of the network state once the certificate is applied.
+    pub new_roots: StateCommitment,
+    /// Pessimistic root of the network once the certificate is applied.
+    pub new_pessimistic_root: Digest,
+    /// Balances of the tokens moved by the certificate.
+    pub balance_changes: Vec<BalanceChange>,
+}
+
+/// Balance of a token of the network, before and after the certificate.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub token: TokenInfo,
+    pub initial_balance: U256,
+    pub new_balance: U256,
+}
@@ -104,3 +104,38 @@ impl NetworkInfo {
         }
     }
 }
+
+/// Summary of a network known by the agglayer, listing the networks without
+/// fetching the full information of each of them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkSummary {
+    /// The unique identifier for this network.
+    pub network_id: NetworkId,
+    /// The aggchain type of a network, as stored by the agglayer.
+    pub network_type: NetworkType,
+    /// The height of the latest settled certificate.
+    pub settled_height: Option<Height>,
+    /// The height of the latest pending certificate.
+    pub latest_pending_height: Option<Height>,
+    /// Time of the latest recorded event of the certificates of the network,
+    /// in seconds since the UNIX epoch.
+    pub latest_activity: Option<u64>,
+}
+
+/// Roots of the local network state of a network once its certificate at a
+/// given height is settled.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkRoots {
+    /// The unique identifier for this network.
+    pub network_id: NetworkId,
+    /// The height of the settled certificate.
+    pub height: Height,
+    /// The root of the local exit tree.
+    pub exit_root: Digest,
+    /// The leaf count of the local exit tree.
+    pub ler_leaf_count: u32,
+    /// The root of the local balance tree.
+    pub balance_root: Digest,
+    /// The root of the nullifier tree.
+    pub nullifier_root: Digest,
+}
@@ -0,0 +1,187 @@
+use agglayer_tries::{node::Node, smt::Smt};
+use pessimistic_proof::local_state::StateCommitment;
+use serde::{Deserialize, Serialize};
+use unified_bridge::{LocalExitTreeError, NetworkId};
+
+use crate::{Digest, LocalNetworkStateData};
+
+/// Version of the format of the network state exports.
+pub const NETWORK_STATE_EXPORT_VERSION: u16 = 1;
+
+/// Full local state of one network, exported to migrate it between agglayer
+/// instances.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NetworkStateExport {
+    /// Version of the format, checked on import.
+    pub version: u16,
+    pub network_id: NetworkId,
+    /// Roots of the exported state, checked against the rebuilt trees.
+    pub roots: StateCommitment,
+    /// All the leaves of the local exit tree.
+    pub exit_leaves: Vec<Digest>,
+    /// Nodes of the local balance tree, as pairs of children.
+    pub balance_nodes: Vec<(Digest, Digest)>,
+    /// Nodes of the nullifier tree, as pairs of children.
+    pub nullifier_nodes: Vec<(Digest, Digest)>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkStateExportError {
+    #[error("Unsupported network state export version {0}")]
+    UnsupportedVersion(u16),
+
+    #[error("Exported local exit tree is {exported} leaves long, the roots expect {expected}")]
+    LeafCountMismatch { exported: u32, expected: u32 },
+
+    #[error("Invalid exported local exit tree")]
+    InvalidExitTree(#[from] LocalExitTreeError),
+
+    #[error("Exported state roots mismatch: expected {expected:?}, rebuilt {actual:?}")]
+    RootsMismatch {
+        expected: StateCommitment,
+        actual: StateCommitment,
+    },
+}
+
+impl NetworkStateExport {
+    /// Export the given state, along with the leaves of its local exit tree.
+    pub fn new(
+        network_id: NetworkId,
+        state: &LocalNetworkStateData,
+        exit_leaves: Vec<Digest>,
+    ) -> Self {
+        Self {
+            version: NETWORK_STATE_EXPORT_VERSION,
+            network_id,
+            roots: state.get_roots(),
+            exit_leaves,
+            balance_nodes: smt_nodes(&state.balance_tree),
+            nullifier_nodes: smt_nodes(&state.nullifier_tree),
+        }
+    }
+
+    /// Rebuild the exported state, once checked against its roots.
+    pub fn to_state(&self) -> Result<LocalNetworkStateData, NetworkStateExportError> {
+        if self.version != NETWORK_STATE_EXPORT_VERSION {
+            return Err(NetworkStateExportError::UnsupportedVersion(self.version));
+        }
+
+        let exported = self.exit_leaves.len() as u32;
+        if exported != self.roots.ler_leaf_count {
+            return Err(NetworkStateExportError::LeafCountMismatch {
+                exported,
+                expected: self.roots.ler_leaf_count,
+            });
+        }
+
+        let mut state = LocalNetworkStateData::default();
+        for leaf in &self.exit_leaves {
+            state.exit_tree.add_leaf(*leaf)?;
+        }
+        state.balance_tree =
+            Smt::new_with_nodes(self.roots.balance_root, &to_nodes(&self.balance_nodes));
+        state.nullifier_tree =
+            Smt::new_with_nodes(self.roots.nullifier_root, &to_nodes(&self.nullifier_nodes));
+
+        let actual = state.get_roots();
+        if actual != self.roots || !self.contains_root_nodes(&state) {
+            return Err(NetworkStateExportError::RootsMismatch {
+                expected: self.roots.clone(),
+                actual,
+            });
+        }
+
+        Ok(state)
+    }
+
+    /// Whether the exported nodes hash up to the roots of the non-empty trees.
+    fn contains_root_nodes(&self, state: &LocalNetworkStateData) -> bool {
+        let default = LocalNetworkStateData::default();
+        let contains = |root: Digest, default_root: Digest, nodes: &[(Digest, Digest)]| {
+            root == default_root
+                || nodes
+                    .iter()
+                    .any(|&(left, right)| Node { left, right }.hash() == root)
+        };
+
+        contains(
+            state.balance_tree.root,
+            default.balance_tree.root,
+            &self.balance_nodes,
+        ) && contains(
+            state.nullifier_tree.root,
+            default.nullifier_tree.root,
+            &self.nullifier_nodes,
+        )
+    }
+}
+
+fn smt_nodes<const DEPTH: usize>(smt: &Smt<DEPTH>) -> Vec<(Digest, Digest)> {
+    let mut nodes: Vec<_> = smt
+        .tree
+        .values()
+        .map(|node| (node.left, node.right))
+        .collect();
+    nodes.sort_unstable_by_key(|(left, right)| (left.0, right.0));
+    nodes
+}
+
+fn to_nodes(nodes: &[(Digest, Digest)]) -> Vec<Node> {
+    nodes
+        .iter()
+        .map(|&(left, right)| Node { left, right })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_state() -> (LocalNetworkStateData, Vec<Digest>) {
+        let mut state = LocalNetworkStateData::default();
+        let leaves: Vec<Digest> = (1u8..=3).map(|i| Digest([i; 32])).collect();
+        for leaf in &leaves {
+            state.exit_tree.add_leaf(*leaf).unwrap();
+        }
+
+        (state, leaves)
+    }
+
+    #[test]
+    fn roundtrip() {
+        let (state, leaves) = sample_state();
+        let export = NetworkStateExport::new(1.into(), &state, leaves);
+
+        let bytes = crate::bincode::default().serialize(&export).unwrap();
+        let decoded: NetworkStateExport = crate::bincode::default().deserialize(&bytes).unwrap();
+        assert_eq!(decoded, export);
+
+        let rebuilt = decoded.to_state().unwrap();
+        assert_eq!(rebuilt.get_roots(), state.get_roots());
+        assert_eq!(rebuilt.exit_tree.frontier(), state.exit_tree.frontier());
+    }
+
+    #[test]
+    fn rejects_tampered_leaves() {
+        let (state, leaves) = sample_state();
+        let mut export = NetworkStateExport::new(1.into(), &state, leaves);
+        export.exit_leaves[0] = Digest([42; 32]);
+
+        assert!(matches!(
+            export.to_state(),
+            Err(NetworkStateExportError::RootsMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let (state, leaves) = sample_state();
+        let mut export = NetworkStateExport::new(1.into(), &state, leaves);
+        export.version = NETWORK_STATE_EXPORT_VERSION + 1;
+
+        assert!(matches!(
+            export.to_state(),
+            Err(NetworkStateExportError::UnsupportedVersion(_))
+        ));
+    }
+}
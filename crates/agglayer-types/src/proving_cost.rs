@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Estimation of the cost of proving a certificate on the SP1 network.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProvingCostEstimate {
+    /// The number of cycles spent by the pessimistic proof program on the
+    /// certificate.
+    pub cycles: u64,
+    /// The estimated cost of the proof, in the smallest unit of the token
+    /// paying for the SP1 network, if its pricing is known.
+    pub cost: Option<u128>,
+    /// The estimated proving time, in seconds, if the pricing of the SP1
+    /// network is known.
+    pub proving_time_secs: Option<u64>,
+}
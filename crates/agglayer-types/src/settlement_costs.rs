@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{EpochNumber, NetworkId};
+
+/// L1 costs of the settlement of certificates.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SettlementCosts {
+    /// The number of settled certificates.
+    pub settled_certificates: u64,
+    /// The gas used by the settlement transactions.
+    pub gas_used: u64,
+    /// The fees paid for the settlement transactions, in wei.
+    pub fees: u128,
+}
+
+impl SettlementCosts {
+    /// Account for the settlement transaction of one certificate.
+    pub fn record_settlement(&mut self, gas_used: u64, fees: u128) {
+        *self += SettlementCosts {
+            settled_certificates: 1,
+            gas_used,
+            fees,
+        };
+    }
+}
+
+impl std::ops::AddAssign for SettlementCosts {
+    fn add_assign(&mut self, other: Self) {
+        self.settled_certificates = self
+            .settled_certificates
+            .saturating_add(other.settled_certificates);
+        self.gas_used = self.gas_used.saturating_add(other.gas_used);
+        self.fees = self.fees.saturating_add(other.fees);
+    }
+}
+
+/// Settlement costs of a network during one epoch.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochSettlementCosts {
+    pub epoch_number: EpochNumber,
+    pub costs: SettlementCosts,
+}
+
+/// Settlement costs of a network over a range of epochs.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SettlementCostsReport {
+    /// The network the certificates were settled for.
+    pub network_id: NetworkId,
+    /// The first epoch of the range, inclusive.
+    pub from_epoch: EpochNumber,
+    /// The last epoch of the range, inclusive.
+    pub to_epoch: EpochNumber,
+    /// The costs of each epoch of the range in which at least one
+    /// certificate of the network was settled, in increasing epoch order.
+    pub epochs: Vec<EpochSettlementCosts>,
+    /// The costs over the whole range.
+    pub total: SettlementCosts,
+}
+
+impl SettlementCostsReport {
+    pub fn new(
+        network_id: NetworkId,
+        from_epoch: EpochNumber,
+        to_epoch: EpochNumber,
+        epochs: Vec<EpochSettlementCosts>,
+    ) -> Self {
+        let mut total = SettlementCosts::default();
+        for epoch in &epochs {
+            total += epoch.costs;
+        }
+
+        Self {
+            network_id,
+            from_epoch,
+            to_epoch,
+            epochs,
+            total,
+        }
+    }
+}
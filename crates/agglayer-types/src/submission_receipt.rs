@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CertificateId, EpochNumber};
+
+/// Receipt of the submission of a certificate, giving its position in the
+/// queue of its network for the submitter to time its retries and timeouts.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CertificateSubmissionReceipt {
+    pub certificate_id: CertificateId,
+    #[serde(flatten)]
+    pub position: QueuePosition,
+}
+
+/// Position of a certificate in the queue of its network.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct QueuePosition {
+    /// Number of certificates of the network to be settled before this one.
+    pub queue_position: u64,
+    /// The current epoch, if the agglayer is clocked.
+    pub current_epoch: Option<EpochNumber>,
+    /// Epochs in which the certificate is expected to be certified and
+    /// settled, given that at most one certificate of a network is settled
+    /// per epoch.
+    pub estimated_certification_window: Option<EpochWindow>,
+}
+
+/// Inclusive range of epochs.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EpochWindow {
+    pub first: EpochNumber,
+    pub last: EpochNumber,
+}
@@ -0,0 +1,40 @@
+use pessimistic_proof::core::PESSIMISTIC_PROOF_PROGRAM_VERSION;
+use serde::{Deserialize, Serialize};
+
+use crate::Digest;
+
+/// Build of the running agglayer binary.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// The version of the agglayer crates.
+    pub version: String,
+    /// The git commit the binary was built from.
+    pub git_commit: String,
+    /// The cargo features enabled at build time.
+    pub features: Vec<String>,
+}
+
+/// Version of the running agglayer, for the deployments to verify what is
+/// actually running.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VersionInfo {
+    #[serde(flatten)]
+    pub build: BuildInfo,
+    /// The version of the embedded pessimistic proof program.
+    pub pp_program_version: u32,
+    /// The hash of the vkey of the embedded pessimistic proof program, as
+    /// registered in the `AggLayerGateway` contract.
+    pub pp_vkey_hash: Digest,
+}
+
+impl VersionInfo {
+    /// Version of the given build, embedding the pessimistic proof program
+    /// with the given vkey hash.
+    pub fn new(build: BuildInfo, pp_vkey_hash: Digest) -> Self {
+        Self {
+            build,
+            pp_program_version: PESSIMISTIC_PROOF_PROGRAM_VERSION,
+            pp_vkey_hash,
+        }
+    }
+}
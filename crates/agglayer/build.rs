@@ -1,5 +1,5 @@
 use eyre::eyre;
-use vergen_git2::{Emitter, Git2Builder};
+use vergen_git2::{CargoBuilder, Emitter, Git2Builder};
 
 fn main() -> eyre::Result<()> {
     color_eyre::install()?;
@@ -8,9 +8,12 @@ fn main() -> eyre::Result<()> {
             &Git2Builder::default()
                 .describe(true, true, None)
                 .commit_timestamp(true)
+                .sha(false)
                 .build()?,
         )
         .map_err(|e| eyre!(e))?
+        .add_instructions(&CargoBuilder::default().features(true).build()?)
+        .map_err(|e| eyre!(e))?
         .emit()
         .map_err(|e| eyre!(e))?;
     Ok(())
@@ -1,6 +1,7 @@
 //! Agglayer command line interface.
 use std::path::{Path, PathBuf};
 
+use agglayer_types::CertificateId;
 use clap::{Parser, Subcommand, ValueHint};
 
 use crate::version;
@@ -54,8 +55,72 @@ pub(crate) enum Commands {
     Vkey,
     VkeySelector,
 
+    /// Print the version of the binary.
+    Version {
+        /// Print the crate version, git commit, embedded pessimistic proof
+        /// program version and vkey hash, and enabled features as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
     #[clap(subcommand)]
     Backup(Backup),
+
+    /// Replay the proving of a certificate from the stdin captured when its
+    /// proving failed.
+    ReplayProof {
+        /// The path to the captured stdin.
+        #[arg(value_hint = ValueHint::FilePath)]
+        stdin: PathBuf,
+        /// Also generate and verify the proof with the local CPU prover.
+        #[arg(long)]
+        prove: bool,
+    },
+
+    /// Print the audit log of a certificate as JSON.
+    Audit {
+        #[arg(long, short, value_hint = ValueHint::FilePath, default_value = "agglayer.toml", env = "CONFIG_PATH")]
+        config_path: PathBuf,
+        /// The id of the certificate, as a 0x-prefixed hex string.
+        #[arg(value_parser = parse_certificate_id)]
+        certificate_id: CertificateId,
+    },
+
+    /// Replay the certification of a stored certificate, without proving,
+    /// and print its report as JSON.
+    ReplayCertification {
+        #[arg(long, short, value_hint = ValueHint::FilePath, default_value = "agglayer.toml", env = "CONFIG_PATH")]
+        config_path: PathBuf,
+        /// The id of the certificate, as a 0x-prefixed hex string.
+        #[arg(value_parser = parse_certificate_id)]
+        certificate_id: CertificateId,
+        /// Path to a snapshot of the state storage to replay against, the
+        /// state storage of the node by default.
+        #[arg(long, value_hint = ValueHint::DirPath)]
+        state_snapshot: Option<PathBuf>,
+    },
+
+    /// Export the full local state of a network to a versioned file, to
+    /// migrate it to another agglayer instance.
+    ExportState {
+        #[arg(long, short, value_hint = ValueHint::FilePath, default_value = "agglayer.toml", env = "CONFIG_PATH")]
+        config_path: PathBuf,
+        /// The id of the network to export.
+        network_id: u32,
+        /// Path of the file to write the export to.
+        #[arg(long, short, value_hint = ValueHint::FilePath)]
+        output: PathBuf,
+    },
+
+    /// Import the local state of a network from a file written by
+    /// `export-state`, into a state storage without state for that network.
+    ImportState {
+        #[arg(long, short, value_hint = ValueHint::FilePath, default_value = "agglayer.toml", env = "CONFIG_PATH")]
+        config_path: PathBuf,
+        /// Path of the file to read the export from.
+        #[arg(value_hint = ValueHint::FilePath)]
+        input: PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -137,6 +202,11 @@ fn parse_db_kind_version(s: &str) -> Result<(DbKind, u32), String> {
     Ok((db_kind, version))
 }
 
+fn parse_certificate_id(s: &str) -> Result<CertificateId, String> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_string()))
+        .map_err(|e| format!("Invalid certificate id '{s}': {e}"))
+}
+
 #[cfg(test)]
 mod tests {
     use agglayer_config::Config;
@@ -184,4 +254,16 @@ mod tests {
         assert_eq!(destination, path_normal.join("epochs/10"));
         assert_eq!(backup, path_backup.join("epochs/10"));
     }
+
+    #[test]
+    fn parsing_certificate_id() {
+        let id = format!("0x{}", "01".repeat(32));
+
+        assert_eq!(
+            parse_certificate_id(&id).unwrap(),
+            CertificateId::new([1; 32].into())
+        );
+        assert!(parse_certificate_id("0x01").is_err());
+        assert!(parse_certificate_id("not-an-id").is_err());
+    }
 }
@@ -1,10 +1,15 @@
-use std::process::exit;
+use std::{process::exit, sync::Arc};
 
 use agglayer_config::storage::backup::BackupConfig;
+use agglayer_prover_config::ProgramElfConfig;
+use agglayer_storage::{
+    storage::{backup::BackupClient, state_db_cf_definitions, DB},
+    stores::{state::StateStore, StateReader as _, StateWriter as _},
+};
+use agglayer_types::{BuildInfo, Digest, NetworkStateExport, VersionInfo};
 use clap::Parser;
 use cli::Cli;
 use eyre::Context as _;
-use pessimistic_proof::ELF;
 
 mod cli;
 
@@ -14,8 +19,12 @@ fn main() -> eyre::Result<()> {
     let cli = Cli::parse();
 
     match cli.cmd {
-        cli::Commands::Run { cfg } => agglayer_node::main(cfg, &version(), None)?,
-        cli::Commands::Prover { cfg } => agglayer_prover::main(cfg, &version(), ELF)?,
+        cli::Commands::Run { cfg } => agglayer_node::main(cfg, &version(), build_info(), None)?,
+        cli::Commands::Prover { cfg } => {
+            let config = agglayer_prover_config::ProverConfig::try_load(&cfg)?;
+            let program = program_elf(config.elf.as_ref(), config.expected_elf_hash)?;
+            agglayer_prover::main(cfg, &version(), program)?
+        }
         cli::Commands::ProverConfig => println!(
             "{}",
             toml::to_string_pretty(&agglayer_prover_config::ProverConfig::default())
@@ -39,11 +48,12 @@ fn main() -> eyre::Result<()> {
             }
         }
         cli::Commands::Vkey => {
+            let program = program_elf(None, None)?;
             tokio::runtime::Builder::new_multi_thread()
                 .enable_all()
                 .build()?
                 .block_on(async move {
-                    let vkey_hex = agglayer_prover::compute_program_vkey(ELF)
+                    let vkey_hex = agglayer_prover::compute_program_vkey(program)
                         .await
                         .context("Failed to compute program vkey");
                     match vkey_hex {
@@ -59,6 +69,23 @@ fn main() -> eyre::Result<()> {
             println!("0x{vkey_selector_hex}");
         }
 
+        cli::Commands::Version { json: false } => println!("{}", version()),
+
+        cli::Commands::Version { json: true } => {
+            let vkey_hash = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()?
+                .block_on(agglayer_prover::compute_program_vkey(program_elf(
+                    None, None,
+                )?))
+                .context("Failed to compute program vkey")?;
+            let vkey_hash = serde_json::from_value(serde_json::Value::String(vkey_hash))
+                .context("Failed to parse program vkey")?;
+
+            let version_info = VersionInfo::new(build_info(), vkey_hash);
+            println!("{}", serde_json::to_string_pretty(&version_info)?);
+        }
+
         cli::Commands::Backup(cli::Backup::List { config_path: cfg }) => {
             let cfg = agglayer_config::Config::try_load(&cfg)?;
 
@@ -91,11 +118,127 @@ fn main() -> eyre::Result<()> {
                 exit(1);
             }
         }
+
+        cli::Commands::ReplayProof { stdin, prove } => {
+            let program = program_elf(None, None)?;
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()?
+                .block_on(async move {
+                    match agglayer_prover::replay::replay(program, &stdin, prove).await {
+                        Ok(report) => {
+                            println!("cycles: {}", report.cycles);
+                            println!("public values: 0x{}", hex::encode(&report.public_values));
+                            if report.proven {
+                                println!("proof generated and verified");
+                            }
+                        }
+                        Err(error) => {
+                            eprintln!("{error:?}");
+                            exit(1);
+                        }
+                    }
+                });
+        }
+
+        cli::Commands::Audit {
+            config_path: cfg,
+            certificate_id,
+        } => {
+            let cfg = agglayer_config::Config::try_load(&cfg)?;
+
+            // Read-only, to be usable alongside a running node.
+            let db = DB::open_cf_readonly(&cfg.storage.state_db_path, state_db_cf_definitions())
+                .context("Failed to open the state database")?;
+            let state_store = StateStore::new(Arc::new(db), BackupClient::noop());
+
+            let records = state_store.get_audit_log(&certificate_id)?;
+            println!("{}", serde_json::to_string_pretty(&records)?);
+        }
+
+        cli::Commands::ReplayCertification {
+            config_path: cfg,
+            certificate_id,
+            state_snapshot,
+        } => {
+            let cfg = Arc::new(agglayer_config::Config::try_load(&cfg)?);
+
+            let replay = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()?
+                .block_on(agglayer_node::replay::replay_certification(
+                    cfg,
+                    certificate_id,
+                    state_snapshot.as_deref(),
+                ))?;
+            println!("{}", serde_json::to_string_pretty(&replay)?);
+        }
+
+        cli::Commands::ExportState {
+            config_path: cfg,
+            network_id,
+            output,
+        } => {
+            let cfg = agglayer_config::Config::try_load(&cfg)?;
+            let network_id = network_id.into();
+
+            let db = DB::open_cf_readonly(&cfg.storage.state_db_path, state_db_cf_definitions())
+                .context("Failed to open the state database")?;
+            let state_store = StateStore::new(Arc::new(db), BackupClient::noop());
+
+            let state = state_store
+                .read_local_network_state(network_id)?
+                .ok_or_else(|| eyre::eyre!("No local state for network {network_id}"))?;
+            let leaves = state_store.read_local_exit_tree_leaves(network_id)?;
+
+            let export = NetworkStateExport::new(network_id, &state, leaves);
+            std::fs::write(&output, serde_json::to_vec_pretty(&export)?)
+                .with_context(|| format!("Failed to write {}", output.display()))?;
+        }
+
+        cli::Commands::ImportState {
+            config_path: cfg,
+            input,
+        } => {
+            let cfg = agglayer_config::Config::try_load(&cfg)?;
+
+            let bytes = std::fs::read(&input)
+                .with_context(|| format!("Failed to read {}", input.display()))?;
+            let export: NetworkStateExport = serde_json::from_slice(&bytes)?;
+            let state = export.to_state()?;
+            let network_id = export.network_id;
+
+            let db = DB::open_cf(&cfg.storage.state_db_path, state_db_cf_definitions())
+                .context("Failed to open the state database")?;
+            let state_store = StateStore::new(Arc::new(db), BackupClient::noop());
+
+            if state_store.read_local_network_state(network_id)?.is_some() {
+                eyre::bail!("The state storage already has a local state for network {network_id}");
+            }
+            state_store.write_local_network_state(&network_id, &state, &export.exit_leaves)?;
+        }
     }
 
     Ok(())
 }
 
+/// ELF of the pessimistic proof program, loaded from the configured path if
+/// any, or embedded in the binary, and checked against the expected hash if
+/// any.
+fn program_elf(
+    config: Option<&ProgramElfConfig>,
+    expected_hash: Option<Digest>,
+) -> eyre::Result<&'static [u8]> {
+    let external = config.map(|elf| (elf.path.as_path(), elf.hash));
+
+    let program = pessimistic_proof::elf::resolve(external)
+        .context("Failed to load the pessimistic proof program")?;
+    pessimistic_proof::elf::check(program, expected_hash)
+        .context("Integrity check of the pessimistic proof program failed")?;
+
+    Ok(program)
+}
+
 /// Common version information about the executed agglayer binary.
 pub fn version() -> String {
     let pkg_name = env!("CARGO_PKG_NAME");
@@ -103,3 +246,16 @@ pub fn version() -> String {
     let timestamp = env!("VERGEN_GIT_COMMIT_TIMESTAMP");
     format!("{pkg_name} ({git_describe}) [git commit timestamp: {timestamp}]")
 }
+
+/// Build of the executed agglayer binary.
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: env!("VERGEN_GIT_SHA").to_string(),
+        features: env!("VERGEN_CARGO_FEATURES")
+            .split(',')
+            .filter(|feature| !feature.is_empty())
+            .map(str::to_string)
+            .collect(),
+    }
+}
@@ -23,10 +23,42 @@ fn main() {
 
     let major_version = version.major.to_string();
 
-    let dest_path = Path::new(&env::var_os("OUT_DIR").expect("OUT_DIR not set")).join("version.rs");
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR not set");
+    let dest_path = Path::new(&out_dir).join("version.rs");
     fs::write(
         &dest_path,
         format!("pub const PESSIMISTIC_PROOF_PROGRAM_VERSION: u32 = {major_version};\n"),
     )
     .expect("Failed to write pessimistic-proof-core version.rs");
+
+    // The local exit tree depth is declared in the program manifest, under
+    // `[package.metadata.pessimistic-proof.tree-depths]`, and defaults to the
+    // one of the unified bridge. The other trees have the depth of their keys.
+    let tree_depths = parsed_toml
+        .get("package")
+        .and_then(|pkg| pkg.get("metadata"))
+        .and_then(|metadata| metadata.get("pessimistic-proof"))
+        .and_then(|pp| pp.get("tree-depths"))
+        .and_then(Value::as_table);
+    if let Some(name) = tree_depths
+        .into_iter()
+        .flat_map(|depths| depths.keys())
+        .find(|name| *name != "local-exit")
+    {
+        panic!("The {name} tree depth can't be configured");
+    }
+    let local_exit_tree_depth = match tree_depths.and_then(|depths| depths.get("local-exit")) {
+        None => 32,
+        Some(depth) => depth
+            .as_integer()
+            .filter(|depth| *depth > 0)
+            .unwrap_or_else(|| panic!("Invalid local-exit tree depth: {depth}")),
+    };
+
+    let dest_path = Path::new(&out_dir).join("tree_depths.rs");
+    fs::write(
+        &dest_path,
+        format!("pub const LOCAL_EXIT_TREE_DEPTH: usize = {local_exit_tree_depth};\n"),
+    )
+    .expect("Failed to write pessimistic-proof-core tree_depths.rs");
 }
@@ -1,7 +1,10 @@
 pub use agglayer_primitives::keccak;
 
 pub mod proof;
-pub use proof::{generate_pessimistic_proof, PessimisticProofOutput, ProofError};
+pub use proof::{
+    generate_pessimistic_proof, PessimisticProofOutput, PessimisticProofOutputVersion, ProofError,
+    PublicValuesError,
+};
 
 pub mod local_balance_tree;
 
@@ -9,6 +12,7 @@ pub mod aggchain_data;
 pub mod local_state;
 pub mod multi_batch_header;
 pub mod nullifier_tree;
+pub mod tree_depths;
 
 pub use local_state::NetworkState;
 
@@ -4,23 +4,41 @@ use agglayer_primitives::{ruint::UintTryFrom, Hashable, U256, U512};
 use agglayer_tries::roots::{LocalBalanceRoot, LocalNullifierRoot};
 use commitment::StateCommitment;
 use serde::{Deserialize, Serialize};
-use unified_bridge::{Error, LocalExitTree, NetworkId, L1_ETH};
+use unified_bridge::{BridgeExit, Error, LocalExitTree, NetworkId, TokenInfo, L1_ETH};
 
 use crate::{
     local_balance_tree::LocalBalanceTree,
     multi_batch_header::MultiBatchHeader,
     nullifier_tree::{NullifierKey, NullifierTree},
+    tree_depths::LOCAL_EXIT_TREE_DEPTH,
     ProofError,
 };
 
 pub mod commitment;
 
+/// Whether the bridge exit moves the balance of its amount token. Messages
+/// without value don't, and require no balance proof.
+pub fn moves_balance(bridge_exit: &BridgeExit) -> bool {
+    !(bridge_exit.is_message() && bridge_exit.amount.is_zero())
+}
+
+/// Token of the balance moved by the amount of the bridge exit.
+///
+/// The value of a message is in the native gas token of the network, which is
+/// L1 ETH unless the network uses a bridged ERC20 as its gas token.
+pub fn balance_token_info(bridge_exit: &BridgeExit, gas_token: Option<TokenInfo>) -> TokenInfo {
+    match gas_token {
+        Some(gas_token) if bridge_exit.is_message() => gas_token,
+        _ => bridge_exit.amount_token_info(),
+    }
+}
+
 /// State representation of one network without the leaves, taken as input by
 /// the prover.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NetworkState {
     /// Commitment to the [`BridgeExit`](struct@crate::bridge_exit::BridgeExit).
-    pub exit_tree: LocalExitTree,
+    pub exit_tree: LocalExitTree<LOCAL_EXIT_TREE_DEPTH>,
     /// Commitment to the balance for each token.
     pub balance_tree: LocalBalanceTree,
     /// Commitment to the Nullifier tree for the local network, tracks claimed
@@ -95,14 +113,21 @@ impl NetworkState {
             self.nullifier_tree
                 .verify_and_update(nullifier_key, nullifier_path)?;
 
-            // The amount corresponds to L1 ETH if the leaf is a message
-            let token_info = imported_bridge_exit.bridge_exit.amount_token_info();
+            // The amount corresponds to the gas token if the leaf is a message
+            let token_info = balance_token_info(
+                &imported_bridge_exit.bridge_exit,
+                multi_batch_header.gas_token,
+            );
 
             if multi_batch_header.origin_network == token_info.origin_network {
                 // When the token is native to the chain, we don't care about the local balance
                 continue;
             }
 
+            if !moves_balance(&imported_bridge_exit.bridge_exit) {
+                continue;
+            }
+
             // Update the token balance.
             let amount = imported_bridge_exit.bridge_exit.amount;
             let entry = new_balances.entry(token_info);
@@ -140,14 +165,18 @@ impl NetworkState {
                 return Err(ProofError::InvalidL1TokenInfo(bridge_exit.token_info));
             }
 
-            // The amount corresponds to L1 ETH if the leaf is a message
-            let token_info = bridge_exit.amount_token_info();
+            // The amount corresponds to the gas token if the leaf is a message
+            let token_info = balance_token_info(bridge_exit, multi_batch_header.gas_token);
 
             if multi_batch_header.origin_network == token_info.origin_network {
                 // When the token is native to the chain, we don't care about the local balance
                 continue;
             }
 
+            if !moves_balance(bridge_exit) {
+                continue;
+            }
+
             // Update the token balance.
             let amount = bridge_exit.amount;
             let entry = new_balances.entry(token_info);
@@ -1,7 +1,7 @@
 #![allow(clippy::too_many_arguments)]
 use std::collections::BTreeMap;
 
-use agglayer_primitives::{Digest, U256};
+use agglayer_primitives::{keccak::keccak256_combine, Digest, U256};
 use serde::{Deserialize, Serialize};
 use unified_bridge::{
     BridgeExit, ImportedBridgeExit, ImportedBridgeExitCommitmentValues, NetworkId, TokenInfo,
@@ -34,6 +34,9 @@ pub struct MultiBatchHeader {
     pub aggchain_data: AggchainData,
     /// Certificate id used as nonce to compute the commitment.
     pub certificate_id: Digest,
+    /// Bridged ERC20 used as native gas token by the origin network, in which
+    /// the value of the messages is accounted. `None` for L1 ETH.
+    pub gas_token: Option<TokenInfo>,
 }
 
 impl MultiBatchHeader {
@@ -47,4 +50,15 @@ impl MultiBatchHeader {
                 .collect(),
         }
     }
+
+    /// Returns the commitment on the gas token, zero for L1 ETH.
+    pub fn commit_gas_token(&self) -> Digest {
+        match self.gas_token {
+            None => Digest::ZERO,
+            Some(gas_token) => keccak256_combine([
+                gas_token.origin_network.to_u32().to_be_bytes().as_slice(),
+                gas_token.origin_token_address.as_slice(),
+            ]),
+        }
+    }
 }
@@ -18,6 +18,7 @@ use crate::{
         NetworkState,
     },
     multi_batch_header::MultiBatchHeader,
+    tree_depths::{LOCAL_EXIT_TREE_DEPTH, UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH},
 };
 
 /// Refers to the commitment on the imported bridge exits involved in the
@@ -168,12 +169,293 @@ pub struct PessimisticProofOutput {
     pub new_local_exit_root: LocalExitRoot,
     /// The new pessimistic root.
     pub new_pessimistic_root: Digest,
+    /// The commitment on the imported bridge exits claimed by the
+    /// certificate, which binds the proof to the exact set of claims.
+    ///
+    /// Zero for the outputs decoded from [`PessimisticProofOutputVersion::V1`]
+    /// public values, which don't commit to the claims.
+    pub commit_imported_bridge_exits: Digest,
+    /// The depth of the local exit tree the program is built with.
+    ///
+    /// [`UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH`] for the outputs decoded from
+    /// public values prior to [`PessimisticProofOutputVersion::V3`].
+    pub local_exit_tree_depth: u32,
+    /// The commitment on the gas token in which the value of the messages is
+    /// accounted, which binds the proof to the token whose balance they move.
+    ///
+    /// Zero for L1 ETH, and for the outputs decoded from public values prior
+    /// to [`PessimisticProofOutputVersion::V4`].
+    pub commit_gas_token: Digest,
+}
+
+/// Fields of the [`PessimisticProofOutput`] committed by the
+/// [`PessimisticProofOutputVersion::V1`] public values.
+#[derive(Serialize, Deserialize)]
+struct PessimisticProofOutputV1 {
+    prev_local_exit_root: LocalExitRoot,
+    prev_pessimistic_root: Digest,
+    l1_info_root: Digest,
+    origin_network: NetworkId,
+    aggchain_hash: Digest,
+    new_local_exit_root: LocalExitRoot,
+    new_pessimistic_root: Digest,
+}
+
+impl From<PessimisticProofOutputV1> for PessimisticProofOutput {
+    fn from(output: PessimisticProofOutputV1) -> Self {
+        Self {
+            prev_local_exit_root: output.prev_local_exit_root,
+            prev_pessimistic_root: output.prev_pessimistic_root,
+            l1_info_root: output.l1_info_root,
+            origin_network: output.origin_network,
+            aggchain_hash: output.aggchain_hash,
+            new_local_exit_root: output.new_local_exit_root,
+            new_pessimistic_root: output.new_pessimistic_root,
+            commit_imported_bridge_exits: Digest::ZERO,
+            local_exit_tree_depth: UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH,
+            commit_gas_token: Digest::ZERO,
+        }
+    }
+}
+
+impl From<&PessimisticProofOutput> for PessimisticProofOutputV1 {
+    fn from(output: &PessimisticProofOutput) -> Self {
+        Self {
+            prev_local_exit_root: output.prev_local_exit_root,
+            prev_pessimistic_root: output.prev_pessimistic_root,
+            l1_info_root: output.l1_info_root,
+            origin_network: output.origin_network,
+            aggchain_hash: output.aggchain_hash,
+            new_local_exit_root: output.new_local_exit_root,
+            new_pessimistic_root: output.new_pessimistic_root,
+        }
+    }
+}
+
+/// Fields of the [`PessimisticProofOutput`] committed by the
+/// [`PessimisticProofOutputVersion::V2`] public values.
+#[derive(Serialize, Deserialize)]
+struct PessimisticProofOutputV2 {
+    prev_local_exit_root: LocalExitRoot,
+    prev_pessimistic_root: Digest,
+    l1_info_root: Digest,
+    origin_network: NetworkId,
+    aggchain_hash: Digest,
+    new_local_exit_root: LocalExitRoot,
+    new_pessimistic_root: Digest,
+    commit_imported_bridge_exits: Digest,
+}
+
+impl From<PessimisticProofOutputV2> for PessimisticProofOutput {
+    fn from(output: PessimisticProofOutputV2) -> Self {
+        Self {
+            prev_local_exit_root: output.prev_local_exit_root,
+            prev_pessimistic_root: output.prev_pessimistic_root,
+            l1_info_root: output.l1_info_root,
+            origin_network: output.origin_network,
+            aggchain_hash: output.aggchain_hash,
+            new_local_exit_root: output.new_local_exit_root,
+            new_pessimistic_root: output.new_pessimistic_root,
+            commit_imported_bridge_exits: output.commit_imported_bridge_exits,
+            local_exit_tree_depth: UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH,
+            commit_gas_token: Digest::ZERO,
+        }
+    }
+}
+
+impl From<&PessimisticProofOutput> for PessimisticProofOutputV2 {
+    fn from(output: &PessimisticProofOutput) -> Self {
+        Self {
+            prev_local_exit_root: output.prev_local_exit_root,
+            prev_pessimistic_root: output.prev_pessimistic_root,
+            l1_info_root: output.l1_info_root,
+            origin_network: output.origin_network,
+            aggchain_hash: output.aggchain_hash,
+            new_local_exit_root: output.new_local_exit_root,
+            new_pessimistic_root: output.new_pessimistic_root,
+            commit_imported_bridge_exits: output.commit_imported_bridge_exits,
+        }
+    }
+}
+
+/// Fields of the [`PessimisticProofOutput`] committed by the
+/// [`PessimisticProofOutputVersion::V3`] public values.
+#[derive(Serialize, Deserialize)]
+struct PessimisticProofOutputV3 {
+    prev_local_exit_root: LocalExitRoot,
+    prev_pessimistic_root: Digest,
+    l1_info_root: Digest,
+    origin_network: NetworkId,
+    aggchain_hash: Digest,
+    new_local_exit_root: LocalExitRoot,
+    new_pessimistic_root: Digest,
+    commit_imported_bridge_exits: Digest,
+    local_exit_tree_depth: u32,
+}
+
+impl From<PessimisticProofOutputV3> for PessimisticProofOutput {
+    fn from(output: PessimisticProofOutputV3) -> Self {
+        Self {
+            prev_local_exit_root: output.prev_local_exit_root,
+            prev_pessimistic_root: output.prev_pessimistic_root,
+            l1_info_root: output.l1_info_root,
+            origin_network: output.origin_network,
+            aggchain_hash: output.aggchain_hash,
+            new_local_exit_root: output.new_local_exit_root,
+            new_pessimistic_root: output.new_pessimistic_root,
+            commit_imported_bridge_exits: output.commit_imported_bridge_exits,
+            local_exit_tree_depth: output.local_exit_tree_depth,
+            commit_gas_token: Digest::ZERO,
+        }
+    }
+}
+
+impl From<&PessimisticProofOutput> for PessimisticProofOutputV3 {
+    fn from(output: &PessimisticProofOutput) -> Self {
+        Self {
+            prev_local_exit_root: output.prev_local_exit_root,
+            prev_pessimistic_root: output.prev_pessimistic_root,
+            l1_info_root: output.l1_info_root,
+            origin_network: output.origin_network,
+            aggchain_hash: output.aggchain_hash,
+            new_local_exit_root: output.new_local_exit_root,
+            new_pessimistic_root: output.new_pessimistic_root,
+            commit_imported_bridge_exits: output.commit_imported_bridge_exits,
+            local_exit_tree_depth: output.local_exit_tree_depth,
+        }
+    }
+}
+
+/// Version of the encoding of the [`PessimisticProofOutput`] committed as the
+/// public values of the pessimistic proof.
+///
+/// The rollup manager rebuilds the public values from the settlement calldata
+/// and its own state, so the encoding of a given version never changes, and
+/// the program can only commit a version the deployed rollup manager
+/// rebuilds.
+///
+/// The programs commit [`PessimisticProofOutputVersion::V1`], the only
+/// version the rollup manager rebuilds. [`PessimisticProofOutputVersion::V2`]
+/// to [`PessimisticProofOutputVersion::V4`] are reserved for a rollup manager
+/// taking the commitments on the imported bridge exits and on the gas token
+/// and the local exit tree depth in its calldata, and are only decoded for
+/// the proofs of development builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PessimisticProofOutputVersion {
+    /// Packed big-endian encoding of the fields of the output, in their
+    /// declaration order, up to the new pessimistic root.
+    V1,
+    /// [`PessimisticProofOutputVersion::V1`] followed by the commitment on
+    /// the imported bridge exits.
+    V2,
+    /// [`PessimisticProofOutputVersion::V2`] followed by the depth of the
+    /// local exit tree.
+    V3,
+    /// [`PessimisticProofOutputVersion::V3`] followed by the commitment on
+    /// the gas token.
+    V4,
+}
+
+impl PessimisticProofOutputVersion {
+    /// Length in bytes of the public values encoded with this version.
+    pub const fn encoded_len(self) -> usize {
+        match self {
+            // 6 digests and the origin network.
+            PessimisticProofOutputVersion::V1 => 6 * 32 + 4,
+            // Followed by the imported bridge exits commitment.
+            PessimisticProofOutputVersion::V2 => 7 * 32 + 4,
+            // Followed by the local exit tree depth.
+            PessimisticProofOutputVersion::V3 => 7 * 32 + 4 + 4,
+            // Followed by the gas token commitment.
+            PessimisticProofOutputVersion::V4 => 8 * 32 + 4 + 4,
+        }
+    }
+
+    /// Version of the public values of the given length, if any.
+    pub fn from_encoded_len(len: usize) -> Option<Self> {
+        [Self::V1, Self::V2, Self::V3, Self::V4]
+            .into_iter()
+            .find(|version| version.encoded_len() == len)
+    }
+}
+
+/// Errors on the encoding of the [`PessimisticProofOutput`] as public values.
+#[derive(Debug, Error)]
+pub enum PublicValuesError {
+    #[error("Invalid length of the public values, matching no known version: {0}")]
+    InvalidLength(usize),
+
+    #[error("Unable to encode or decode the public values")]
+    Codec(#[source] bincode::Error),
 }
 
 impl PessimisticProofOutput {
+    /// Version of the encoding of the public values committed by the program.
+    pub const VERSION: PessimisticProofOutputVersion = PessimisticProofOutputVersion::V1;
+
     pub fn bincode_codec() -> bincode::Codec<impl bincode::Options> {
         bincode::contracts()
     }
+
+    /// Encode the output as the public values committed by the program.
+    pub fn to_public_values(&self) -> Result<Vec<u8>, PublicValuesError> {
+        self.to_public_values_of(Self::VERSION)
+    }
+
+    /// Encode the output as the public values of the given version, leaving
+    /// out the fields the version doesn't commit to.
+    pub fn to_public_values_of(
+        &self,
+        version: PessimisticProofOutputVersion,
+    ) -> Result<Vec<u8>, PublicValuesError> {
+        let codec = Self::bincode_codec();
+        match version {
+            PessimisticProofOutputVersion::V1 => {
+                codec.serialize(&PessimisticProofOutputV1::from(self))
+            }
+            PessimisticProofOutputVersion::V2 => {
+                codec.serialize(&PessimisticProofOutputV2::from(self))
+            }
+            PessimisticProofOutputVersion::V3 => {
+                codec.serialize(&PessimisticProofOutputV3::from(self))
+            }
+            PessimisticProofOutputVersion::V4 => codec.serialize(self),
+        }
+        .map_err(PublicValuesError::Codec)
+    }
+
+    /// The output as decoded from the public values committed by the program,
+    /// which is what the proofs are compared against.
+    pub fn committed(&self) -> Self {
+        match Self::VERSION {
+            PessimisticProofOutputVersion::V1 => PessimisticProofOutputV1::from(self).into(),
+            PessimisticProofOutputVersion::V2 => PessimisticProofOutputV2::from(self).into(),
+            PessimisticProofOutputVersion::V3 => PessimisticProofOutputV3::from(self).into(),
+            PessimisticProofOutputVersion::V4 => self.clone(),
+        }
+    }
+
+    /// Decode the output from the public values of a pessimistic proof, of
+    /// any known version.
+    pub fn from_public_values(public_values: &[u8]) -> Result<Self, PublicValuesError> {
+        let version = PessimisticProofOutputVersion::from_encoded_len(public_values.len())
+            .ok_or(PublicValuesError::InvalidLength(public_values.len()))?;
+
+        let codec = Self::bincode_codec();
+        match version {
+            PessimisticProofOutputVersion::V1 => codec
+                .deserialize::<PessimisticProofOutputV1>(public_values)
+                .map(Self::from),
+            PessimisticProofOutputVersion::V2 => codec
+                .deserialize::<PessimisticProofOutputV2>(public_values)
+                .map(Self::from),
+            PessimisticProofOutputVersion::V3 => codec
+                .deserialize::<PessimisticProofOutputV3>(public_values)
+                .map(Self::from),
+            PessimisticProofOutputVersion::V4 => codec.deserialize(public_values),
+        }
+        .map_err(PublicValuesError::Codec)
+    }
 }
 
 pub const EMPTY_LER: LocalExitRoot = LocalExitRoot::new(Digest(hex!(
@@ -250,6 +532,12 @@ pub fn generate_pessimistic_proof(
         &final_state_commitment,
     )?;
 
+    let commit_imported_bridge_exits = Digest::from(
+        constrained_values
+            .commit_imported_bridge_exits
+            .commitment(IMPORTED_BRIDGE_EXIT_COMMITMENT_VERSION),
+    );
+
     // Verify multisig, aggchain proof, or both.
     let target_pp_root_version = batch_header.aggchain_data.verify(constrained_values)?;
 
@@ -276,6 +564,9 @@ pub fn generate_pessimistic_proof(
             aggchain_hash: batch_header.aggchain_data.aggchain_hash(),
             new_local_exit_root: zero_if_empty_local_exit_root(final_state_commitment.exit_root),
             new_pessimistic_root,
+            commit_imported_bridge_exits,
+            local_exit_tree_depth: LOCAL_EXIT_TREE_DEPTH as u32,
+            commit_gas_token: batch_header.commit_gas_token(),
         },
         final_state_commitment,
     ))
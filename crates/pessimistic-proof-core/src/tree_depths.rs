@@ -0,0 +1,19 @@
+//! Depth of the local exit tree maintained by the pessimistic proof.
+//!
+//! The depth is set at build time from the
+//! `[package.metadata.pessimistic-proof.tree-depths]` section of the program
+//! manifest, and defaults to the one of the unified bridge. The depths of the
+//! local balance and nullifier trees are set by the width of their keys and
+//! can't be configured.
+
+include!(concat!(env!("OUT_DIR"), "/tree_depths.rs"));
+
+/// Depth of the local exit tree of the unified bridge, implied by the public
+/// values which don't commit to the depth.
+pub const UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH: u32 = 32;
+
+// The leaf index of a local exit tree is a `u32`.
+const _: () = assert!(
+    LOCAL_EXIT_TREE_DEPTH <= 32,
+    "The local exit tree depth must be at most 32"
+);
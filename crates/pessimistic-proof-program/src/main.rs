@@ -2,7 +2,6 @@
 
 use pessimistic_proof_core::{
     generate_pessimistic_proof, multi_batch_header::MultiBatchHeader, NetworkState,
-    PessimisticProofOutput,
 };
 
 sp1_zkvm::entrypoint!(main);
@@ -12,9 +11,7 @@ pub fn main() {
 
     let (outputs, _targets) = generate_pessimistic_proof(initial_state, &batch_header).unwrap();
 
-    let pp_inputs = PessimisticProofOutput::bincode_codec()
-        .serialize(&outputs)
-        .unwrap();
+    let pp_inputs = outputs.to_public_values().unwrap();
 
     sp1_zkvm::io::commit_slice(&pp_inputs);
 }
@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use pessimistic_proof_test_suite::{
+    cycles::{CycleReport, Workload},
+    runner::Runner,
+};
+use tracing::{error, info};
+
+/// The arguments for the cycle count benchmark.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct CyclesArgs {
+    /// The numbers of bridge exits to benchmark.
+    #[clap(long, value_delimiter = ',', default_value = "10,100,1000")]
+    n_exits: Vec<usize>,
+
+    /// The numbers of imported bridge exits to benchmark.
+    #[clap(long, value_delimiter = ',', default_value = "0,10,100")]
+    n_imported_exits: Vec<usize>,
+
+    /// The path of the JSON report to write.
+    #[clap(long, default_value = "cycles.json")]
+    output: PathBuf,
+
+    /// The optional report to compare against.
+    #[clap(long)]
+    baseline: Option<PathBuf>,
+
+    /// The tolerated increase of instruction count over the baseline, in
+    /// percent.
+    #[clap(long, default_value = "5")]
+    max_regression: f64,
+}
+
+pub fn main() -> eyre::Result<()> {
+    sp1_sdk::utils::setup_logger();
+
+    let args = CyclesArgs::parse();
+    let runner = Runner::new();
+
+    let mut entries = Vec::new();
+    for &n_exits in &args.n_exits {
+        for &n_imported_exits in &args.n_imported_exits {
+            let workload = Workload {
+                n_exits,
+                n_imported_exits,
+            };
+
+            let count = runner.count_cycles(workload)?;
+            info!(
+                "{workload}: {} instructions, {} syscalls",
+                count.total_instruction_count, count.total_syscall_count
+            );
+            entries.push(count);
+        }
+    }
+
+    let report = CycleReport::new(entries);
+    report.save(&args.output)?;
+    info!("Writing the cycle report to {:?}", args.output);
+
+    if let Some(baseline) = args.baseline {
+        let regressions = report.regressions(&CycleReport::load(&baseline)?, args.max_regression);
+
+        for regression in &regressions {
+            error!("Cycle count regression on {regression}");
+        }
+
+        eyre::ensure!(
+            regressions.is_empty(),
+            "{} workload(s) regressed by more than {}% against {baseline:?}",
+            regressions.len(),
+            args.max_regression
+        );
+    }
+
+    Ok(())
+}
@@ -38,6 +38,10 @@ struct PPGenArgs {
     /// The optional path to the custom sample data.
     #[clap(long)]
     sample_path: Option<PathBuf>,
+
+    /// Only execute the program, without generating the proof.
+    #[clap(long)]
+    execute_only: bool,
 }
 
 fn get_events(n: usize, path: Option<PathBuf>) -> Vec<(TokenInfo, U256)> {
@@ -104,10 +108,36 @@ pub fn main() {
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
                     signer: state.get_signer(),
                 },
+                gas_token: None,
             },
         )
         .unwrap();
 
+    if args.execute_only {
+        info!(
+            "Executing the program for {} bridge exit(s) and {} imported bridge exit(s)",
+            bridge_exits.len(),
+            imported_bridge_exits.len()
+        );
+
+        let start = Instant::now();
+        let execution = Runner::new()
+            .execute_only(&old_state.into(), &multi_batch_header)
+            .expect("execution failed");
+        info!(
+            "Successfully executed the program in {:?} with {} cycles",
+            start.elapsed(),
+            execution.cycles
+        );
+        info!(
+            "Public values: 0x{} {:?}",
+            hex::encode(execution.public_values.as_slice()),
+            VerifierInputs::from(execution.output)
+        );
+
+        return;
+    }
+
     info!(
         "Generating the proof for {} bridge exit(s) and {} imported bridge exit(s)",
         bridge_exits.len(),
@@ -174,6 +204,13 @@ pub struct VerifierInputs {
     /// The new pessimistic root which commits to the balance and nullifier
     /// tree.
     pub new_pessimistic_root: String,
+    /// The commitment on the imported bridge exits claimed by the
+    /// certificate.
+    pub commit_imported_bridge_exits: String,
+    /// The depth of the local exit tree the program is built with.
+    pub local_exit_tree_depth: u32,
+    /// The commitment on the gas token, zero for L1 ETH.
+    pub commit_gas_token: String,
 }
 
 impl From<PessimisticProofOutput> for VerifierInputs {
@@ -186,6 +223,12 @@ impl From<PessimisticProofOutput> for VerifierInputs {
             aggchain_hash: format!("0x{}", hex::encode(v.aggchain_hash)),
             new_local_exit_root: format!("0x{}", hex::encode(v.new_local_exit_root)),
             new_pessimistic_root: format!("0x{}", hex::encode(v.new_pessimistic_root)),
+            commit_imported_bridge_exits: format!(
+                "0x{}",
+                hex::encode(v.commit_imported_bridge_exits)
+            ),
+            local_exit_tree_depth: v.local_exit_tree_depth,
+            commit_gas_token: format!("0x{}", hex::encode(v.commit_gas_token)),
         }
     }
 }
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use pessimistic_proof_test_suite::sample_data::corpus::{Corpus, CorpusConfig};
+use tracing::info;
+
+/// The arguments for the sample data generator.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct SampleDataArgs {
+    /// The number of certificates of the corpus.
+    #[clap(long, default_value = "100")]
+    certificates: usize,
+
+    /// The number of distinct tokens, ETH and USDC being the most popular.
+    #[clap(long, default_value = "10")]
+    tokens: usize,
+
+    /// The mean number of events, bridge exits and imported bridge exits
+    /// together, per certificate.
+    #[clap(long, default_value = "4")]
+    mean_events: f64,
+
+    /// The maximum number of events per certificate.
+    #[clap(long, default_value = "256")]
+    max_events: usize,
+
+    /// The probability of an event being an imported bridge exit.
+    #[clap(long, default_value = "0.3")]
+    imported_ratio: f64,
+
+    /// The exponent of the Zipf law of the popularity of the tokens.
+    #[clap(long, default_value = "1.2")]
+    token_skew: f64,
+
+    /// The seed of the generation.
+    #[clap(long, default_value = "0")]
+    seed: u64,
+
+    /// The optional path of the JSON corpus to write. If not set, the corpus
+    /// is printed.
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+pub fn main() -> eyre::Result<()> {
+    sp1_sdk::utils::setup_logger();
+
+    let args = SampleDataArgs::parse();
+    let corpus = Corpus::generate(CorpusConfig {
+        certificates: args.certificates,
+        tokens: args.tokens,
+        mean_events: args.mean_events,
+        max_events: args.max_events,
+        imported_ratio: args.imported_ratio,
+        token_skew: args.token_skew,
+        seed: args.seed,
+    })?;
+
+    let corpus = serde_json::to_string_pretty(&corpus)?;
+    match &args.output {
+        Some(output) => {
+            std::fs::write(output, corpus)?;
+            info!("Writing the corpus to {:?}", output);
+        }
+        None => println!("{corpus}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use pessimistic_proof_test_suite::test_vector::write_certificate_test_vectors;
+use tracing::info;
+
+/// The arguments for the test vector generator.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct TestVectorArgs {
+    /// The directory in which the versioned test vector directory is created.
+    #[clap(long, default_value = "./data/test_vector/certificates")]
+    output_dir: PathBuf,
+}
+
+pub fn main() -> eyre::Result<()> {
+    sp1_sdk::utils::setup_logger();
+
+    let args = TestVectorArgs::parse();
+    let dir = write_certificate_test_vectors(&args.output_dir)?;
+    info!("Writing the certificate test vectors to {:?}", dir);
+
+    Ok(())
+}
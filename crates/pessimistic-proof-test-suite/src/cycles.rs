@@ -0,0 +1,218 @@
+//! Cycle count measurements of the pessimistic proof program over
+//! parameterized workloads.
+
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use agglayer_types::{
+    aggchain_data::CertificateAggchainDataCtx, L1WitnessCtx, PessimisticRootInput,
+};
+use pessimistic_proof::core::commitment::PessimisticRootCommitmentVersion;
+use serde::{Deserialize, Serialize};
+
+use crate::{runner::Runner, sample_data as data};
+
+/// Version of the report format.
+pub const REPORT_VERSION: u32 = 1;
+
+/// Shape of the certificate given to the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Workload {
+    /// The number of bridge exits.
+    pub n_exits: usize,
+    /// The number of imported bridge exits.
+    pub n_imported_exits: usize,
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "be{:04}_ibe{:04}", self.n_exits, self.n_imported_exits)
+    }
+}
+
+/// Cycles spent by the program on one workload.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CycleCount {
+    pub workload: Workload,
+    pub total_instruction_count: u64,
+    pub total_syscall_count: u64,
+    /// Cycles spent in each tracked section of the program.
+    pub cycle_tracker: BTreeMap<String, u64>,
+}
+
+/// Cycle counts over a set of workloads.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CycleReport {
+    pub version: u32,
+    pub entries: Vec<CycleCount>,
+}
+
+/// Workload whose instruction count increased beyond the tolerance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub workload: Workload,
+    pub baseline: u64,
+    pub current: u64,
+}
+
+impl Regression {
+    pub fn increase_percent(&self) -> f64 {
+        (self.current as f64 - self.baseline as f64) * 100.0 / self.baseline as f64
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} instructions (+{:.2}%)",
+            self.workload,
+            self.baseline,
+            self.current,
+            self.increase_percent()
+        )
+    }
+}
+
+impl CycleReport {
+    pub fn new(entries: Vec<CycleCount>) -> Self {
+        Self {
+            version: REPORT_VERSION,
+            entries,
+        }
+    }
+
+    pub fn load(path: &Path) -> eyre::Result<Self> {
+        let report: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        eyre::ensure!(
+            report.version == REPORT_VERSION,
+            "unsupported cycle report version {}",
+            report.version
+        );
+
+        Ok(report)
+    }
+
+    pub fn save(&self, path: &Path) -> eyre::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Compare against a baseline, returning the workloads whose instruction
+    /// count increased by more than `tolerance_percent`.
+    ///
+    /// Workloads missing from the baseline are ignored.
+    pub fn regressions(&self, baseline: &CycleReport, tolerance_percent: f64) -> Vec<Regression> {
+        let baseline: BTreeMap<_, _> = baseline
+            .entries
+            .iter()
+            .map(|entry| (entry.workload, entry.total_instruction_count))
+            .collect();
+
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let regression = Regression {
+                    workload: entry.workload,
+                    baseline: *baseline.get(&entry.workload)?,
+                    current: entry.total_instruction_count,
+                };
+
+                (regression.current > regression.baseline
+                    && regression.increase_percent() > tolerance_percent)
+                    .then_some(regression)
+            })
+            .collect()
+    }
+}
+
+impl Runner {
+    /// Execute the program on a certificate of the given shape and count the
+    /// cycles.
+    ///
+    /// The bridge exits and imported bridge exits are cyclically taken from
+    /// the sample withdrawals, on top of the sample state 01.
+    pub fn count_cycles(&self, workload: Workload) -> eyre::Result<CycleCount> {
+        let mut forest = data::sample_state_01();
+        let initial_state = forest.state_b.clone();
+
+        let events = |n| {
+            data::sample_bridge_exits_01()
+                .cycle()
+                .take(n)
+                .map(|exit| (exit.token_info, exit.amount))
+                .collect::<Vec<_>>()
+        };
+        let certificate = forest.apply_events(
+            &events(workload.n_imported_exits),
+            &events(workload.n_exits),
+        );
+
+        let multi_batch_header = initial_state.make_multi_batch_header(
+            &certificate,
+            L1WitnessCtx {
+                l1_info_root: certificate.l1_info_root()?.unwrap_or_default(),
+                prev_pessimistic_root: PessimisticRootInput::Computed(
+                    PessimisticRootCommitmentVersion::V2,
+                ),
+                aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
+                    signer: forest.get_signer(),
+                },
+                gas_token: None,
+            },
+        )?;
+
+        let (_output, report) = self.execute(&initial_state.into(), &multi_batch_header)?;
+
+        Ok(CycleCount {
+            workload,
+            total_instruction_count: report.total_instruction_count(),
+            total_syscall_count: report.total_syscall_count(),
+            cycle_tracker: report.cycle_tracker.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(counts: &[(usize, u64)]) -> CycleReport {
+        CycleReport::new(
+            counts
+                .iter()
+                .map(|(n_exits, count)| CycleCount {
+                    workload: Workload {
+                        n_exits: *n_exits,
+                        n_imported_exits: 0,
+                    },
+                    total_instruction_count: *count,
+                    total_syscall_count: 0,
+                    cycle_tracker: BTreeMap::new(),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn detects_regressions_above_tolerance() {
+        let baseline = report(&[(10, 1000), (100, 10_000), (1000, 100_000)]);
+        let current = report(&[(10, 1049), (100, 10_600), (1000, 90_000), (5, 1)]);
+
+        let regressions = current.regressions(&baseline, 5.0);
+
+        assert_eq!(
+            regressions,
+            vec![Regression {
+                workload: Workload {
+                    n_exits: 100,
+                    n_imported_exits: 0
+                },
+                baseline: 10_000,
+                current: 10_600,
+            }]
+        );
+    }
+}
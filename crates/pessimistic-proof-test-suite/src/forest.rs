@@ -10,7 +10,7 @@ use ecdsa_proof_lib::AggchainECDSA;
 use pessimistic_proof::{
     core::commitment::{SignatureCommitmentValues, SignatureCommitmentVersion},
     keccak::keccak256_combine,
-    local_exit_tree::{data::LocalExitTreeData, LocalExitTree},
+    local_exit_tree::{data::LocalExitTreeData, LocalExitTree, LOCAL_EXIT_TREE_DEPTH},
     local_state::LocalNetworkState,
     proof::zero_if_empty_local_exit_root,
     unified_bridge::{
@@ -116,12 +116,20 @@ impl Forest {
         &mut self,
         events: impl IntoIterator<Item = (TokenInfo, U256)>,
     ) -> Vec<ImportedBridgeExit> {
-        let mut res = Vec::new();
-
-        let exits: Vec<BridgeExit> = events
+        let exits = events
             .into_iter()
-            .map(|(token, amount)| exit_to_b(token, amount))
-            .collect();
+            .map(|(token, amount)| exit_to_b(token, amount));
+
+        self.import_bridge_exits(exits)
+    }
+
+    /// Import the given bridge exits from network A to network B.
+    pub fn import_bridge_exits(
+        &mut self,
+        exits: impl IntoIterator<Item = BridgeExit>,
+    ) -> Vec<ImportedBridgeExit> {
+        let mut res = Vec::new();
+        let exits: Vec<BridgeExit> = exits.into_iter().collect();
 
         // Append all the leafs in LET A (mainnet)
         for exit in &exits {
@@ -192,10 +200,21 @@ impl Forest {
         imported_bridge_events: impl IntoIterator<Item = (TokenInfo, U256)>,
         bridge_exits: impl IntoIterator<Item = BridgeExit>,
         version: SignatureCommitmentVersion,
+    ) -> Certificate {
+        let imported_bridge_exits = self.imported_bridge_exits(imported_bridge_events);
+        self.apply_imported_bridge_exits(imported_bridge_exits, bridge_exits, version)
+    }
+
+    /// Apply already imported bridge exits along with bridge exits and return
+    /// the corresponding [`Certificate`].
+    pub fn apply_imported_bridge_exits(
+        &mut self,
+        imported_bridge_exits: Vec<ImportedBridgeExit>,
+        bridge_exits: impl IntoIterator<Item = BridgeExit>,
+        version: SignatureCommitmentVersion,
     ) -> Certificate {
         let prev_local_exit_root = self.state_b.exit_tree.get_root().into();
 
-        let imported_bridge_exits = self.imported_bridge_exits(imported_bridge_events);
         let bridge_exits = bridge_exits
             .into_iter()
             .inspect(|exit| {
@@ -295,6 +314,32 @@ impl Forest {
         )
     }
 
+    /// Apply a sequence of messages, given by their value in L1 ETH, imported
+    /// from network A and sent to network A, and return the corresponding
+    /// [`Certificate`].
+    pub fn apply_message_events(
+        &mut self,
+        imported_messages: &[U256],
+        messages: &[U256],
+    ) -> Certificate {
+        let network_id = self.network_id;
+        let imported_bridge_exits = self.import_bridge_exits(
+            imported_messages
+                .iter()
+                .map(|amount| message(NETWORK_A.to_u32(), NETWORK_B.to_u32(), *amount)),
+        );
+        let bridge_exits = messages
+            .iter()
+            .map(|amount| message(network_id, NETWORK_A.to_u32(), *amount))
+            .collect::<Vec<_>>();
+
+        self.apply_imported_bridge_exits(
+            imported_bridge_exits,
+            bridge_exits,
+            SignatureCommitmentVersion::V2,
+        )
+    }
+
     pub fn get_signer(&self) -> Address {
         self.wallet.address().into()
     }
@@ -313,10 +358,11 @@ impl Forest {
                 self.state_b.exit_tree.leaf_count().to_le_bytes().as_slice(),
             ])
         );
+        assert_eq!(output.local_exit_tree_depth, LOCAL_EXIT_TREE_DEPTH as u32);
     }
 }
 
-fn exit(token_info: TokenInfo, dest_network: NetworkId, amount: U256) -> BridgeExit {
+pub(crate) fn exit(token_info: TokenInfo, dest_network: NetworkId, amount: U256) -> BridgeExit {
     BridgeExit {
         leaf_type: LeafType::Transfer,
         token_info,
@@ -327,6 +373,25 @@ fn exit(token_info: TokenInfo, dest_network: NetworkId, amount: U256) -> BridgeE
     }
 }
 
+/// Message from the given network carrying the given value in L1 ETH.
+pub(crate) fn message(
+    origin_network: NetworkId,
+    dest_network: NetworkId,
+    amount: U256,
+) -> BridgeExit {
+    BridgeExit {
+        leaf_type: LeafType::Message,
+        token_info: TokenInfo {
+            origin_network: origin_network.into(),
+            origin_token_address: random::<[u8; 20]>().into(),
+        },
+        dest_network: dest_network.into(),
+        dest_address: random::<[u8; 20]>().into(),
+        amount,
+        metadata: Some(keccak256(&random::<[u8; 32]>())),
+    }
+}
+
 fn exit_to_a(token_info: TokenInfo, amount: U256) -> BridgeExit {
     exit(token_info, NETWORK_A.to_u32(), amount)
 }
@@ -0,0 +1,303 @@
+//! Arbitrary certificate generators, along with a harness comparing the native
+//! execution of the pessimistic proof with its execution in the zkVM.
+
+use std::collections::BTreeMap;
+
+use agglayer_types::{
+    aggchain_data::CertificateAggchainDataCtx, Address, Certificate, Digest, L1WitnessCtx,
+    LocalNetworkStateData, PessimisticRootInput, U256,
+};
+use arbitrary::{Arbitrary, Unstructured};
+use pessimistic_proof::{
+    core::{commitment::PessimisticRootCommitmentVersion, generate_pessimistic_proof},
+    local_state::LocalNetworkState,
+    multi_batch_header::MultiBatchHeader,
+    unified_bridge::TokenInfo,
+    NetworkState, PessimisticProofOutput, ProofError,
+};
+
+use crate::{
+    forest::Forest,
+    runner::Runner,
+    sample_data::{ETH, USDC},
+};
+
+/// Upper bound on the number of events of each kind in a generated case, to
+/// keep the zkVM execution time reasonable.
+const MAX_EVENTS: usize = 8;
+
+/// Token involved in a generated event.
+#[derive(Arbitrary, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Token {
+    Usdc,
+    Eth,
+}
+
+impl From<Token> for TokenInfo {
+    fn from(token: Token) -> Self {
+        match token {
+            Token::Usdc => USDC,
+            Token::Eth => ETH,
+        }
+    }
+}
+
+/// A token amount along with its token.
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub struct Event {
+    pub token: Token,
+    pub amount: u32,
+}
+
+impl Event {
+    fn as_tuple(&self) -> (TokenInfo, U256) {
+        (self.token.into(), U256::from(self.amount))
+    }
+}
+
+fn events(u: &mut Unstructured<'_>) -> arbitrary::Result<Vec<Event>> {
+    let len = u.int_in_range(0..=MAX_EVENTS)?;
+    (0..len).map(|_| Event::arbitrary(u)).collect()
+}
+
+/// Certificate content which is accepted by the pessimistic proof.
+///
+/// The bridge exits are capped so that no token balance can become negative.
+#[derive(Clone, Debug)]
+pub struct ValidCase {
+    pub initial_balances: Vec<Event>,
+    pub imported_bridge_exits: Vec<Event>,
+    pub bridge_exits: Vec<Event>,
+}
+
+impl<'a> Arbitrary<'a> for ValidCase {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Initial balances are defined once per token.
+        let initial_balances: BTreeMap<Token, u32> = events(u)?
+            .into_iter()
+            .map(|event| (event.token, event.amount))
+            .collect();
+        let imported_bridge_exits = events(u)?;
+
+        let mut available: BTreeMap<Token, u64> = initial_balances
+            .iter()
+            .map(|(token, amount)| (*token, u64::from(*amount)))
+            .collect();
+        for event in &imported_bridge_exits {
+            *available.entry(event.token).or_default() += u64::from(event.amount);
+        }
+
+        let bridge_exits = events(u)?
+            .into_iter()
+            .map(|event| {
+                let available = available.entry(event.token).or_default();
+                let amount = u64::from(event.amount).min(*available);
+                *available -= amount;
+
+                Event {
+                    token: event.token,
+                    amount: amount as u32,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            initial_balances: initial_balances
+                .into_iter()
+                .map(|(token, amount)| Event { token, amount })
+                .collect(),
+            imported_bridge_exits,
+            bridge_exits,
+        })
+    }
+}
+
+/// Tampering applied on top of a [`ValidCase`] to make it invalid.
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub enum Mutation {
+    /// Add a bridge exit spending more than the available balance.
+    Overspend(Token),
+    /// Replace the previous local exit root.
+    PrevLocalExitRoot([u8; 32]),
+    /// Replace the new local exit root.
+    NewLocalExitRoot([u8; 32]),
+    /// Increase the amount of the first bridge exit, if any.
+    BridgeExitAmount(u32),
+    /// Import the first imported bridge exit twice, if any.
+    DuplicateImportedBridgeExit,
+    /// Sign the certificate with an unexpected key.
+    WrongSigner(u32),
+}
+
+/// Certificate content which is expected to be rejected by the pessimistic
+/// proof, unless the mutation turns out to be a no-op.
+#[derive(Arbitrary, Clone, Debug)]
+pub struct AdversarialCase {
+    pub base: ValidCase,
+    pub mutation: Mutation,
+}
+
+/// Certificate generated from a case, along with its initial state.
+#[derive(Clone)]
+pub struct GeneratedCertificate {
+    pub initial_state: LocalNetworkStateData,
+    pub certificate: Certificate,
+    pub signer: Address,
+}
+
+impl GeneratedCertificate {
+    /// Build the witness of the pessimistic proof, fails if the certificate
+    /// is rejected before reaching the proof.
+    pub fn multi_batch_header(&self) -> Result<MultiBatchHeader, agglayer_types::Error> {
+        self.initial_state.make_multi_batch_header(
+            &self.certificate,
+            L1WitnessCtx {
+                l1_info_root: self.certificate.l1_info_root()?.unwrap_or_default(),
+                prev_pessimistic_root: PessimisticRootInput::Computed(
+                    PessimisticRootCommitmentVersion::V2,
+                ),
+                aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
+                    signer: self.signer,
+                },
+                gas_token: None,
+            },
+        )
+    }
+
+    pub fn network_state(&self) -> NetworkState {
+        LocalNetworkState::from(self.initial_state.clone()).into()
+    }
+}
+
+impl ValidCase {
+    fn forest(&self) -> Forest {
+        Forest::new(self.initial_balances.iter().map(Event::as_tuple))
+    }
+
+    pub fn generate(&self) -> GeneratedCertificate {
+        self.generate_with_extra_exits(&[])
+    }
+
+    fn generate_with_extra_exits(&self, extra: &[(TokenInfo, U256)]) -> GeneratedCertificate {
+        let mut forest = self.forest();
+        let initial_state = forest.state_b.clone();
+
+        let imported: Vec<_> = self
+            .imported_bridge_exits
+            .iter()
+            .map(Event::as_tuple)
+            .collect();
+        let exits: Vec<_> = self
+            .bridge_exits
+            .iter()
+            .map(Event::as_tuple)
+            .chain(extra.iter().cloned())
+            .collect();
+
+        let certificate = forest.apply_events(&imported, &exits);
+
+        GeneratedCertificate {
+            initial_state,
+            certificate,
+            signer: forest.get_signer(),
+        }
+    }
+}
+
+impl AdversarialCase {
+    pub fn generate(&self) -> GeneratedCertificate {
+        match self.mutation {
+            Mutation::Overspend(token) => {
+                let available: u64 = self
+                    .base
+                    .initial_balances
+                    .iter()
+                    .chain(self.base.imported_bridge_exits.iter())
+                    .filter(|event| event.token == token)
+                    .map(|event| u64::from(event.amount))
+                    .sum();
+
+                self.base
+                    .generate_with_extra_exits(&[(token.into(), U256::from(available + 1))])
+            }
+            Mutation::WrongSigner(seed) => {
+                let mut generated = self.base.generate();
+                let mut forest = self.base.forest().with_signer_seed(seed);
+                let imported: Vec<_> = self
+                    .base
+                    .imported_bridge_exits
+                    .iter()
+                    .map(Event::as_tuple)
+                    .collect();
+                let exits: Vec<_> = self.base.bridge_exits.iter().map(Event::as_tuple).collect();
+                generated.certificate = forest.apply_events(&imported, &exits);
+
+                generated
+            }
+            mutation => {
+                let mut generated = self.base.generate();
+                let certificate = &mut generated.certificate;
+
+                match mutation {
+                    Mutation::PrevLocalExitRoot(root) => {
+                        certificate.prev_local_exit_root = Digest(root).into();
+                    }
+                    Mutation::NewLocalExitRoot(root) => {
+                        certificate.new_local_exit_root = Digest(root).into();
+                    }
+                    Mutation::BridgeExitAmount(increase) => {
+                        if let Some(exit) = certificate.bridge_exits.first_mut() {
+                            exit.amount += U256::from(increase.max(1));
+                        }
+                    }
+                    Mutation::DuplicateImportedBridgeExit => {
+                        if let Some(imported) = certificate.imported_bridge_exits.first().cloned() {
+                            certificate.imported_bridge_exits.push(imported);
+                        }
+                    }
+                    Mutation::Overspend(_) | Mutation::WrongSigner(_) => unreachable!(),
+                }
+
+                generated
+            }
+        }
+    }
+}
+
+/// Outcome of the native execution and of the zkVM execution of one
+/// certificate.
+pub struct Verdicts {
+    pub native: Result<PessimisticProofOutput, ProofError>,
+    pub zkvm: eyre::Result<PessimisticProofOutput>,
+}
+
+impl Verdicts {
+    pub fn agree(&self) -> bool {
+        match (&self.native, &self.zkvm) {
+            (Ok(native), Ok(zkvm)) => native == zkvm,
+            (Err(_), Err(_)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn accepted(&self) -> bool {
+        self.native.is_ok()
+    }
+}
+
+/// Run the certificate both natively and in the zkVM.
+///
+/// Returns `None` if the certificate is rejected before the witness can be
+/// built, in which case the zkVM never gets to see it.
+pub fn execute_both(runner: &Runner, generated: &GeneratedCertificate) -> Option<Verdicts> {
+    let multi_batch_header = generated.multi_batch_header().ok()?;
+
+    // The zkVM output is decoded from the committed public values.
+    let native = generate_pessimistic_proof(generated.network_state(), &multi_batch_header)
+        .map(|(output, _commitment)| output.committed());
+    let zkvm = runner
+        .execute(&generated.network_state(), &multi_batch_header)
+        .map(|(output, _report)| output);
+
+    Some(Verdicts { native, zkvm })
+}
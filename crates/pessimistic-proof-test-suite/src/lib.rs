@@ -1,7 +1,10 @@
 //! A collection of shared testing utilities.
 
+pub mod cycles;
 pub mod event_data;
 pub mod forest;
+pub mod generators;
+pub mod multi_network;
 pub mod runner;
 pub mod sample_data;
 pub mod test_vector;
@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use agglayer_types::{
+    aggchain_proof::AggchainData, compute_signature_info, primitives::Hashable, Certificate,
+    Digest, Height, NetworkId, U256,
+};
+use pessimistic_proof::{
+    core::commitment::SignatureCommitmentVersion,
+    keccak::keccak256_combine,
+    local_exit_tree::data::LocalExitTreeData,
+    unified_bridge::{
+        BridgeExit, Claim, ClaimFromRollup, GlobalIndex, ImportedBridgeExit, L1InfoTreeLeaf,
+        L1InfoTreeLeafInner, MerkleProof, TokenInfo,
+    },
+};
+
+use crate::forest::{exit, Forest};
+
+/// Bridge exit emitted by a certificate which is waiting to be imported on its
+/// destination network.
+#[derive(Clone, Debug)]
+struct InFlightExit {
+    origin_network: NetworkId,
+    leaf_index: u32,
+    bridge_exit: BridgeExit,
+}
+
+/// One network of a [`MultiNetworkForest`].
+#[derive(Clone)]
+struct Node {
+    forest: Forest,
+    /// Full local exit tree of the network, used to build the inclusion proofs
+    /// of the claims on the destination networks.
+    exit_tree_data: LocalExitTreeData,
+    next_height: Height,
+    /// Bridge exits to include in the next certificate of this network.
+    queued_exits: Vec<BridgeExit>,
+}
+
+/// Set of [`Forest`]s modeling several rollups bridging assets to each other.
+///
+/// Certificates are generated network by network, with consecutive heights.
+/// The bridge exits of a generated certificate are considered settled right
+/// away and are imported, with rollup claims proven against a shared L1 info
+/// tree, by the next certificate of their destination network.
+#[derive(Clone, Default)]
+pub struct MultiNetworkForest {
+    networks: BTreeMap<NetworkId, Node>,
+    l1_info_tree: LocalExitTreeData,
+    l1_info_leaf_count: u32,
+    in_flight: BTreeMap<NetworkId, Vec<InFlightExit>>,
+    signature_version: Option<SignatureCommitmentVersion>,
+}
+
+impl MultiNetworkForest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rollup with the given initial balances.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the network is the mainnet or if it has already been added.
+    pub fn with_network(
+        mut self,
+        network_id: NetworkId,
+        initial_balances: impl IntoIterator<Item = (TokenInfo, U256)>,
+    ) -> Self {
+        assert_ne!(
+            network_id.to_u32(),
+            0,
+            "mainnet cannot be part of a scenario"
+        );
+        assert!(
+            !self.networks.contains_key(&network_id),
+            "network {network_id} already added"
+        );
+
+        let forest = Forest::new(initial_balances).with_network_id(network_id.to_u32());
+        self.networks.insert(
+            network_id,
+            Node {
+                forest,
+                exit_tree_data: LocalExitTreeData::new(),
+                next_height: Height::ZERO,
+                queued_exits: Vec::new(),
+            },
+        );
+
+        self
+    }
+
+    /// Override the signature commitment version of the generated
+    /// certificates, defaults to [`SignatureCommitmentVersion::V2`].
+    pub fn with_signature_version(mut self, version: SignatureCommitmentVersion) -> Self {
+        self.signature_version = Some(version);
+        self
+    }
+
+    pub fn network_ids(&self) -> impl Iterator<Item = NetworkId> + '_ {
+        self.networks.keys().copied()
+    }
+
+    pub fn forest(&self, network_id: NetworkId) -> Option<&Forest> {
+        self.networks.get(&network_id).map(|node| &node.forest)
+    }
+
+    /// Height of the next certificate generated for the given network.
+    pub fn next_height(&self, network_id: NetworkId) -> Option<Height> {
+        self.networks.get(&network_id).map(|node| node.next_height)
+    }
+
+    /// Queue a bridge exit from `from` to `to`, to be included in the next
+    /// certificate of `from`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the networks is not part of the scenario.
+    pub fn bridge(
+        &mut self,
+        from: NetworkId,
+        to: NetworkId,
+        token: TokenInfo,
+        amount: U256,
+    ) -> &mut Self {
+        assert!(self.networks.contains_key(&to), "unknown network {to}");
+        self.node_mut(from)
+            .queued_exits
+            .push(exit(token, to.to_u32(), amount));
+
+        self
+    }
+
+    /// Generate the next certificate of the given network.
+    ///
+    /// The certificate contains the queued bridge exits of the network, and
+    /// imports every bridge exit sent to it by previously generated
+    /// certificates.
+    pub fn next_certificate(&mut self, network_id: NetworkId) -> Certificate {
+        let imported_bridge_exits = self.claim_in_flight_exits(network_id);
+        let version = self
+            .signature_version
+            .unwrap_or(SignatureCommitmentVersion::V2);
+
+        let node = self.node_mut(network_id);
+        let prev_local_exit_root = node.forest.state_b.exit_tree.get_root().into();
+
+        let mut outgoing = Vec::new();
+        let bridge_exits: Vec<BridgeExit> = std::mem::take(&mut node.queued_exits);
+        for bridge_exit in &bridge_exits {
+            let leaf_index = node.forest.state_b.exit_tree.leaf_count();
+            node.forest
+                .state_b
+                .exit_tree
+                .add_leaf(bridge_exit.hash())
+                .unwrap();
+            node.exit_tree_data.add_leaf(bridge_exit.hash()).unwrap();
+            outgoing.push(InFlightExit {
+                origin_network: network_id,
+                leaf_index,
+                bridge_exit: bridge_exit.clone(),
+            });
+        }
+
+        let new_local_exit_root = node.forest.state_b.exit_tree.get_root().into();
+        let height = node.next_height;
+        node.next_height = height.next();
+
+        let (_combined_hash, signature, _signer) = compute_signature_info(
+            new_local_exit_root,
+            &imported_bridge_exits,
+            &node.forest.wallet,
+            height,
+            version,
+        );
+
+        for in_flight in outgoing {
+            self.in_flight
+                .entry(in_flight.bridge_exit.dest_network)
+                .or_default()
+                .push(in_flight);
+        }
+
+        Certificate {
+            network_id,
+            height,
+            prev_local_exit_root,
+            new_local_exit_root,
+            bridge_exits,
+            imported_bridge_exits,
+            aggchain_data: AggchainData::ECDSA { signature },
+            metadata: Default::default(),
+            custom_chain_data: vec![],
+            l1_info_tree_leaf_count: None,
+        }
+    }
+
+    /// Generate one certificate per network, interleaved in network id order.
+    pub fn next_round(&mut self) -> Vec<Certificate> {
+        let network_ids: Vec<_> = self.network_ids().collect();
+
+        network_ids
+            .into_iter()
+            .map(|network_id| self.next_certificate(network_id))
+            .collect()
+    }
+
+    fn node_mut(&mut self, network_id: NetworkId) -> &mut Node {
+        self.networks
+            .get_mut(&network_id)
+            .unwrap_or_else(|| panic!("unknown network {network_id}"))
+    }
+
+    /// Rollup exit tree built from the current local exit root of every
+    /// network of the scenario.
+    fn rollup_exit_tree(&self) -> LocalExitTreeData {
+        let mut rollup_exit_tree = LocalExitTreeData::new();
+        let last_rollup = self
+            .networks
+            .keys()
+            .map(|network_id| network_id.to_u32())
+            .max()
+            .unwrap_or_default();
+
+        for rollup_index in 0..last_rollup {
+            let ler = self
+                .networks
+                .get(&NetworkId::new(rollup_index + 1))
+                .map(|node| node.exit_tree_data.get_root())
+                .unwrap_or_default();
+            rollup_exit_tree.add_leaf(ler).unwrap();
+        }
+
+        rollup_exit_tree
+    }
+
+    /// Build the imported bridge exits for every in-flight exit destined to
+    /// the given network, all proven against a new L1 info tree leaf.
+    fn claim_in_flight_exits(&mut self, network_id: NetworkId) -> Vec<ImportedBridgeExit> {
+        let in_flight = self.in_flight.remove(&network_id).unwrap_or_default();
+        if in_flight.is_empty() {
+            return Vec::new();
+        }
+
+        let rollup_exit_tree = self.rollup_exit_tree();
+        let (rer, mer) = (rollup_exit_tree.get_root(), Digest::default());
+
+        let l1_leaf = L1InfoTreeLeaf {
+            l1_info_tree_index: self.l1_info_leaf_count,
+            rer,
+            mer,
+            inner: L1InfoTreeLeafInner {
+                block_hash: Digest::default(),
+                timestamp: 0,
+                global_exit_root: keccak256_combine([mer, rer]),
+            },
+        };
+        self.l1_info_tree.add_leaf(l1_leaf.hash()).unwrap();
+        self.l1_info_leaf_count += 1;
+
+        let proof_ger_l1root = MerkleProof {
+            proof: self
+                .l1_info_tree
+                .get_proof(l1_leaf.l1_info_tree_index)
+                .unwrap(),
+            root: self.l1_info_tree.get_root(),
+        };
+
+        in_flight
+            .into_iter()
+            .map(|in_flight| {
+                let origin = &self.networks[&in_flight.origin_network];
+                let rollup_index = in_flight.origin_network.to_u32() - 1;
+
+                ImportedBridgeExit {
+                    bridge_exit: in_flight.bridge_exit,
+                    global_index: GlobalIndex::new(in_flight.origin_network, in_flight.leaf_index),
+                    claim_data: Claim::Rollup(Box::new(ClaimFromRollup {
+                        proof_leaf_ler: MerkleProof {
+                            proof: origin
+                                .exit_tree_data
+                                .get_proof(in_flight.leaf_index)
+                                .unwrap(),
+                            root: origin.exit_tree_data.get_root(),
+                        },
+                        proof_ler_rer: MerkleProof {
+                            proof: rollup_exit_tree.get_proof(rollup_index).unwrap(),
+                            root: rer,
+                        },
+                        proof_ger_l1root: proof_ger_l1root.clone(),
+                        l1_leaf: l1_leaf.clone(),
+                    })),
+                }
+            })
+            .collect()
+    }
+}
@@ -8,6 +8,17 @@ use crate::PESSIMISTIC_PROOF_ELF;
 
 pub struct ProofOutput {}
 
+/// Result of the execution of the ELF without proving.
+pub struct ExecutionOutput {
+    /// The decoded public values.
+    pub output: PessimisticProofOutput,
+    /// The raw public values committed by the program.
+    pub public_values: SP1PublicValues,
+    /// The total number of cycles spent by the program.
+    pub cycles: u64,
+    pub report: ExecutionReport,
+}
+
 /// A convenient interface to run the pessimistic proof ELF bytecode.
 pub struct Runner {
     client: sp1_sdk::EnvProver,
@@ -40,9 +51,7 @@ impl Runner {
 
     /// Extract outputs from the committed public values.
     pub fn extract_output(public_vals: SP1PublicValues) -> PessimisticProofOutput {
-        PessimisticProofOutput::bincode_codec()
-            .deserialize(public_vals.as_slice())
-            .expect("deser")
+        PessimisticProofOutput::from_public_values(public_vals.as_slice()).expect("deser")
     }
 
     /// Execute the ELF with given inputs.
@@ -51,16 +60,31 @@ impl Runner {
         state: &NetworkState,
         batch_header: &MultiBatchHeader,
     ) -> eyre::Result<(PessimisticProofOutput, ExecutionReport)> {
+        let ExecutionOutput { output, report, .. } = self.execute_only(state, batch_header)?;
+
+        Ok((output, report))
+    }
+
+    /// Execute the ELF with given inputs in the SP1 executor, without
+    /// generating any proof.
+    pub fn execute_only(
+        &self,
+        state: &NetworkState,
+        batch_header: &MultiBatchHeader,
+    ) -> eyre::Result<ExecutionOutput> {
         let stdin = Self::prepare_stdin(state, batch_header);
-        let (public_vals, report) = self
+        let (public_values, report) = self
             .client
             .execute(PESSIMISTIC_PROOF_ELF, &stdin)
             .run()
             .map_err(|e| eyre!(e))?;
 
-        let output = Self::extract_output(public_vals);
-
-        Ok((output, report))
+        Ok(ExecutionOutput {
+            output: Self::extract_output(public_values.clone()),
+            public_values,
+            cycles: report.total_instruction_count(),
+            report,
+        })
     }
 
     pub fn get_vkey(&self) -> SP1VerifyingKey {
@@ -1,5 +1,7 @@
 //! Sample data, either synthetic or taken from real traces.
 
+pub mod corpus;
+
 use agglayer_types::{
     primitives::{address, U256},
     Certificate, NetworkId,
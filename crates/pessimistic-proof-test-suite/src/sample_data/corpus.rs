@@ -0,0 +1,182 @@
+//! Corpora of certificates drawn from mainnet-like distributions.
+//!
+//! The number of events of a certificate is geometric, most certificates
+//! carrying a handful of exits with a long tail of larger ones. The tokens
+//! follow a Zipf law over their popularity, ETH and USDC being the most bridged
+//! ones, and the amounts are log-uniform over the orders of magnitude seen on
+//! the bridge.
+
+use agglayer_types::{Address, Certificate, LocalNetworkStateData, NetworkId, U256};
+use pessimistic_proof::{
+    core::commitment::SignatureCommitmentVersion,
+    keccak::keccak256,
+    unified_bridge::{BridgeExit, LeafType, TokenInfo},
+};
+use rand::{
+    distr::{weighted::WeightedIndex, Distribution as _},
+    rngs::StdRng,
+    Rng, SeedableRng as _,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{ETH, NETWORK_A, NETWORK_B, USDC};
+use crate::{forest::Forest, test_vector::CertificateTestInput};
+
+/// Largest order of magnitude of the amounts, in base units of the token.
+const MAX_AMOUNT_EXPONENT: u32 = 22;
+
+/// Parameters of the generation of a corpus.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CorpusConfig {
+    pub certificates: usize,
+    /// Number of distinct tokens, ETH and USDC being the first two.
+    pub tokens: usize,
+    /// Mean number of events, bridge exits and imported bridge exits
+    /// together, of a certificate.
+    pub mean_events: f64,
+    /// Upper bound on the number of events of a certificate.
+    pub max_events: usize,
+    /// Probability of an event being an imported bridge exit.
+    pub imported_ratio: f64,
+    /// Exponent of the Zipf law of the popularity of the tokens.
+    pub token_skew: f64,
+    pub seed: u64,
+}
+
+impl Default for CorpusConfig {
+    fn default() -> Self {
+        Self {
+            certificates: 100,
+            tokens: 10,
+            mean_events: 4.0,
+            max_events: 256,
+            imported_ratio: 0.3,
+            token_skew: 1.2,
+            seed: 0,
+        }
+    }
+}
+
+/// Corpus of certificates, each of them applying on top of the initial state
+/// of the corpus.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Corpus {
+    pub config: CorpusConfig,
+    /// Tokens with a balance in the initial state.
+    pub tokens: Vec<TokenInfo>,
+    pub signer: Address,
+    pub certificates: Vec<Certificate>,
+}
+
+impl Corpus {
+    /// Generate the corpus of the given configuration.
+    ///
+    /// The generation is deterministic for a given seed.
+    pub fn generate(config: CorpusConfig) -> eyre::Result<Self> {
+        eyre::ensure!(config.tokens > 0, "The corpus needs at least one token");
+        eyre::ensure!(
+            config.mean_events >= 0.0,
+            "The mean number of events must be non-negative"
+        );
+        eyre::ensure!(
+            (0.0..=1.0).contains(&config.imported_ratio),
+            "The imported ratio must be between 0 and 1"
+        );
+
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let tokens = [ETH, USDC]
+            .into_iter()
+            .chain(std::iter::repeat_with(|| TokenInfo {
+                origin_network: NETWORK_A,
+                origin_token_address: rng.random::<[u8; 20]>().into(),
+            }))
+            .take(config.tokens)
+            .collect::<Vec<_>>();
+        let popularity = WeightedIndex::new(
+            (1..=tokens.len()).map(|rank| (rank as f64).powf(-config.token_skew)),
+        )?;
+
+        let forest = initial_forest(&tokens);
+        let certificates = (0..config.certificates)
+            .map(|_| {
+                let mut forest = forest.clone();
+                let (mut imported_exits, mut exits) = (Vec::new(), Vec::new());
+                for _ in 0..event_count(&mut rng, config.mean_events, config.max_events) {
+                    let token_info = tokens[popularity.sample(&mut rng)];
+                    if rng.random_bool(config.imported_ratio) {
+                        imported_exits.push(bridge_exit(&mut rng, token_info, NETWORK_B));
+                    } else {
+                        exits.push(bridge_exit(&mut rng, token_info, NETWORK_A));
+                    }
+                }
+
+                let imported_bridge_exits = forest.import_bridge_exits(imported_exits);
+                forest.apply_imported_bridge_exits(
+                    imported_bridge_exits,
+                    exits,
+                    SignatureCommitmentVersion::V2,
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            config,
+            signer: forest.get_signer(),
+            tokens,
+            certificates,
+        })
+    }
+
+    /// State on top of which every certificate of the corpus applies.
+    pub fn initial_state(&self) -> LocalNetworkStateData {
+        initial_forest(&self.tokens).state_b
+    }
+
+    /// Certificates of the corpus as test inputs.
+    pub fn test_inputs(&self) -> impl Iterator<Item = CertificateTestInput> + '_ {
+        let initial_state = self.initial_state();
+        self.certificates
+            .iter()
+            .enumerate()
+            .map(move |(index, certificate)| CertificateTestInput {
+                name: format!("corpus_{index:04}"),
+                initial_state: initial_state.clone(),
+                certificate: certificate.clone(),
+                signer: self.signer,
+            })
+    }
+}
+
+fn initial_forest(tokens: &[TokenInfo]) -> Forest {
+    // Not the max to leave room for the imported bridge exits.
+    let balance = U256::MAX.checked_div(U256::from(2u64)).unwrap();
+    Forest::new(tokens.iter().map(|token| (*token, balance)))
+}
+
+/// Draw a number of events from a geometric distribution of the given mean.
+fn event_count(rng: &mut impl Rng, mean: f64, max: usize) -> usize {
+    let continuation = mean / (1.0 + mean);
+    if continuation == 0.0 {
+        return 0;
+    }
+
+    let uniform: f64 = rng.random();
+    let count = ((1.0 - uniform).ln() / continuation.ln()).floor();
+    (count as usize).min(max)
+}
+
+fn bridge_exit(rng: &mut impl Rng, token_info: TokenInfo, dest_network: NetworkId) -> BridgeExit {
+    let exponent = rng.random_range(0..=MAX_AMOUNT_EXPONENT);
+    let mantissa = rng.random_range(1..10u128);
+
+    BridgeExit {
+        leaf_type: LeafType::Transfer,
+        token_info,
+        dest_network,
+        dest_address: rng.random::<[u8; 20]>().into(),
+        amount: U256::from(mantissa * 10u128.pow(exponent)),
+        metadata: Some(keccak256(&[])),
+    }
+}
@@ -1,6 +1,22 @@
-use agglayer_types::Address;
-use alloy::primitives::B256;
-use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use agglayer_types::{
+    aggchain_data::CertificateAggchainDataCtx, Address, Certificate, CertificateId, Digest,
+    L1WitnessCtx, LocalNetworkStateData, PessimisticRootInput,
+};
+use alloy::primitives::{Bytes, B256};
+use pessimistic_proof::{
+    core::{
+        commitment::{PessimisticRootCommitmentVersion, SignatureCommitmentVersion},
+        generate_pessimistic_proof,
+    },
+    multi_batch_header::MultiBatchHeader,
+    unified_bridge::BridgeExit,
+    PessimisticProofOutput,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{forest::Forest, sample_data as data};
 
 #[derive(Debug, Deserialize)]
 pub struct TestFile {
@@ -49,3 +65,189 @@ impl ExpectedOutput {
         }
     }
 }
+
+/// Version of the certificate test vector format, used as the name of the
+/// directory containing the generated vectors.
+pub const CERTIFICATE_TEST_VECTOR_VERSION: u32 = 1;
+
+/// Roots committing to the state of a network.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateRoots {
+    pub local_exit_root: Digest,
+    pub local_exit_tree_leaf_count: u32,
+    pub balance_root: Digest,
+    pub nullifier_root: Digest,
+}
+
+impl From<&LocalNetworkStateData> for StateRoots {
+    fn from(state: &LocalNetworkStateData) -> Self {
+        Self {
+            local_exit_root: state.exit_tree.get_root().into(),
+            local_exit_tree_leaf_count: state.exit_tree.leaf_count(),
+            balance_root: state.balance_tree.root.into(),
+            nullifier_root: state.nullifier_tree.root.into(),
+        }
+    }
+}
+
+/// Canonical certificate fixture, shared with the other implementations of
+/// the pessimistic proof and with the verifier contracts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CertificateTestVector {
+    pub name: String,
+    pub certificate: Certificate,
+    pub signer: Address,
+    pub prev_state: StateRoots,
+    pub expected_new_state: StateRoots,
+    pub expected_certificate_hash: CertificateId,
+    pub expected_output: PessimisticProofOutput,
+    /// The public values committed by the pessimistic proof program.
+    pub expected_public_values: Bytes,
+}
+
+impl CertificateTestVector {
+    /// Build the test vector of a certificate applied on top of the given
+    /// initial state.
+    pub fn new(
+        name: &str,
+        initial_state: LocalNetworkStateData,
+        certificate: Certificate,
+        signer: Address,
+    ) -> eyre::Result<Self> {
+        let (new_state, multi_batch_header) =
+            apply_certificate(&initial_state, &certificate, signer)?;
+
+        let (expected_output, _commitment) =
+            generate_pessimistic_proof(initial_state.clone().into(), &multi_batch_header)?;
+        let expected_public_values = expected_output.to_public_values()?.into();
+
+        Ok(Self {
+            name: name.to_string(),
+            expected_certificate_hash: certificate.hash(),
+            certificate,
+            signer,
+            prev_state: (&initial_state).into(),
+            expected_new_state: (&new_state).into(),
+            expected_output,
+            expected_public_values,
+        })
+    }
+}
+
+/// Apply the certificate on top of the initial state, returning the new state
+/// and the batch header read by the pessimistic proof program.
+pub fn apply_certificate(
+    initial_state: &LocalNetworkStateData,
+    certificate: &Certificate,
+    signer: Address,
+) -> eyre::Result<(LocalNetworkStateData, MultiBatchHeader)> {
+    let mut new_state = initial_state.clone();
+    let multi_batch_header = new_state.apply_certificate(
+        certificate,
+        L1WitnessCtx {
+            l1_info_root: certificate.l1_info_root()?.unwrap_or_default(),
+            prev_pessimistic_root: PessimisticRootInput::Computed(
+                PessimisticRootCommitmentVersion::V2,
+            ),
+            aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+            gas_token: None,
+        },
+    )?;
+
+    Ok((new_state, multi_batch_header))
+}
+
+fn sample_bridge_exits(n: usize, dest_network: u32) -> Vec<BridgeExit> {
+    data::sample_bridge_exits_01()
+        .cycle()
+        .take(n)
+        .map(|mut exit| {
+            exit.dest_network = dest_network.into();
+            exit
+        })
+        .collect()
+}
+
+fn certificate_test_input(
+    name: &str,
+    mut forest: Forest,
+    n_imported_exits: usize,
+    n_exits: usize,
+) -> CertificateTestInput {
+    let initial_state = forest.state_b.clone();
+
+    let imported_bridge_exits =
+        forest.import_bridge_exits(sample_bridge_exits(n_imported_exits, forest.network_id));
+    let certificate = forest.apply_imported_bridge_exits(
+        imported_bridge_exits,
+        sample_bridge_exits(n_exits, data::NETWORK_A.to_u32()),
+        SignatureCommitmentVersion::V2,
+    );
+
+    CertificateTestInput {
+        name: name.to_string(),
+        initial_state,
+        certificate,
+        signer: forest.get_signer(),
+    }
+}
+
+/// Inputs of a canonical certificate test vector.
+#[derive(Clone, Debug)]
+pub struct CertificateTestInput {
+    pub name: String,
+    pub initial_state: LocalNetworkStateData,
+    pub certificate: Certificate,
+    pub signer: Address,
+}
+
+/// Generate the inputs of the canonical certificate test vectors.
+///
+/// The generation is deterministic: every event comes from the sample data.
+pub fn certificate_test_inputs() -> Vec<CertificateTestInput> {
+    [
+        ("empty", data::sample_state_00(), 0, 0),
+        ("imported_only", data::sample_state_00(), 5, 0),
+        ("s01_be001", data::sample_state_01(), 0, 1),
+        ("s01_be010", data::sample_state_01(), 0, 10),
+        ("s01_ibe010_be010", data::sample_state_01(), 10, 10),
+    ]
+    .into_iter()
+    .map(|(name, forest, n_imported_exits, n_exits)| {
+        certificate_test_input(name, forest, n_imported_exits, n_exits)
+    })
+    .collect()
+}
+
+/// Generate the canonical certificate test vectors.
+pub fn certificate_test_vectors() -> eyre::Result<Vec<CertificateTestVector>> {
+    certificate_test_inputs()
+        .into_iter()
+        .map(|input| {
+            CertificateTestVector::new(
+                &input.name,
+                input.initial_state,
+                input.certificate,
+                input.signer,
+            )
+        })
+        .collect()
+}
+
+/// Write the canonical certificate test vectors as JSON files, in a
+/// subdirectory of `dir` named after the format version.
+///
+/// Returns the directory containing the test vectors.
+pub fn write_certificate_test_vectors(dir: &Path) -> eyre::Result<PathBuf> {
+    let dir = dir.join(format!("v{CERTIFICATE_TEST_VECTOR_VERSION}"));
+    std::fs::create_dir_all(&dir)?;
+
+    for vector in certificate_test_vectors()? {
+        std::fs::write(
+            dir.join(format!("{}.json", vector.name)),
+            serde_json::to_string_pretty(&vector)?,
+        )?;
+    }
+
+    Ok(dir)
+}
@@ -0,0 +1,33 @@
+use pessimistic_proof_test_suite::test_vector::{certificate_test_vectors, CertificateTestVector};
+
+#[test]
+fn certificate_test_vectors_are_deterministic() {
+    let serialize = |vectors: Vec<CertificateTestVector>| {
+        vectors
+            .iter()
+            .map(|vector| serde_json::to_string(vector).unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    let first = serialize(certificate_test_vectors().unwrap());
+    let second = serialize(certificate_test_vectors().unwrap());
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn certificate_test_vectors_round_trip() {
+    for vector in certificate_test_vectors().unwrap() {
+        let json = serde_json::to_string(&vector).unwrap();
+        let parsed: CertificateTestVector = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.certificate.hash(), vector.expected_certificate_hash);
+        assert_eq!(parsed.expected_output, vector.expected_output);
+        assert_eq!(
+            parsed.expected_output.new_local_exit_root,
+            pessimistic_proof::proof::zero_if_empty_local_exit_root(
+                vector.expected_new_state.local_exit_root.into()
+            )
+        );
+    }
+}
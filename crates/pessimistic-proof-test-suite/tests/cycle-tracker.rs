@@ -46,6 +46,7 @@ fn cycles_on_sample_inputs(
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
                     signer: state.get_signer(),
                 },
+                gas_token: None,
             },
         )
         .unwrap();
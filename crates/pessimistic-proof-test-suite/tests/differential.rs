@@ -0,0 +1,41 @@
+use pessimistic_proof_test_suite::{
+    generators::{execute_both, AdversarialCase, ValidCase},
+    runner::Runner,
+};
+
+/// Number of generated cases per harness, each case going through the zkVM
+/// executor.
+const ITERATIONS: usize = 16;
+
+#[test]
+fn valid_certificates_are_accepted_by_both() {
+    let runner = Runner::new();
+
+    bolero::check!()
+        .with_arbitrary::<ValidCase>()
+        .with_iterations(ITERATIONS)
+        .for_each(|case: &ValidCase| {
+            let generated = case.generate();
+            let verdicts = execute_both(&runner, &generated)
+                .unwrap_or_else(|| panic!("witness generation failed for {case:?}"));
+
+            assert!(verdicts.accepted(), "rejected valid case {case:?}");
+            assert!(verdicts.agree(), "native and zkVM disagree on {case:?}");
+        })
+}
+
+#[test]
+fn adversarial_certificates_are_judged_equally_by_both() {
+    let runner = Runner::new();
+
+    bolero::check!()
+        .with_arbitrary::<AdversarialCase>()
+        .with_iterations(ITERATIONS)
+        .for_each(|case: &AdversarialCase| {
+            // Certificates rejected during witness generation never reach the
+            // zkVM.
+            if let Some(verdicts) = execute_both(&runner, &case.generate()) {
+                assert!(verdicts.agree(), "native and zkVM disagree on {case:?}");
+            }
+        })
+}
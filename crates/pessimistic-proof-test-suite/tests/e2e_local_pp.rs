@@ -9,10 +9,11 @@ use pessimistic_proof::{
             SignatureCommitmentVersion,
         },
         generate_pessimistic_proof, AggchainData, AggchainProof, MultiSignature,
+        IMPORTED_BRIDGE_EXIT_COMMITMENT_VERSION,
     },
     local_state::LocalNetworkState,
     unified_bridge::TokenInfo,
-    NetworkState, ProofError,
+    NetworkState, PessimisticProofOutput, PessimisticProofOutputVersion, ProofError,
 };
 use pessimistic_proof_test_suite::{
     forest::Forest,
@@ -94,6 +95,7 @@ impl VersionConsistencyChecker {
                     l1_info_root,
                     prev_pessimistic_root: PessimisticRootInput::Fetched(expected_prev_pp_root),
                     aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                    gas_token: None,
                 },
             )
             .unwrap();
@@ -107,6 +109,7 @@ impl VersionConsistencyChecker {
                         l1_info_root,
                         prev_pessimistic_root: PessimisticRootInput::Fetched(expected_prev_pp_root),
                         aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa { signer },
+                        gas_token: None,
                     },
                 )
                 .unwrap();
@@ -191,12 +194,164 @@ fn e2e_local_pp_simple_helper(
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
                     signer: forest.get_signer(),
                 },
+                gas_token: None,
             },
         )
         .unwrap();
     generate_pessimistic_proof(initial_state.into(), &multi_batch_header).unwrap();
 }
 
+#[test]
+fn output_commits_to_the_imported_bridge_exits() {
+    let commit_imported_bridge_exits = |imported_events: &[(TokenInfo, U256)]| {
+        let mut forest = Forest::new(vec![(USDC, u(100)), (ETH, u(200))]);
+        let initial_state = forest.state_b.clone();
+        let certificate = forest.apply_events(imported_events, &[(USDC, u(20))]);
+        let multi_batch_header = initial_state
+            .make_multi_batch_header(
+                &certificate,
+                L1WitnessCtx {
+                    l1_info_root: certificate.l1_info_root().unwrap().unwrap_or_default(),
+                    prev_pessimistic_root: PessimisticRootInput::Computed(
+                        PessimisticRootCommitmentVersion::V2,
+                    ),
+                    aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
+                        signer: forest.get_signer(),
+                    },
+                    gas_token: None,
+                },
+            )
+            .unwrap();
+
+        let (pv, _) =
+            generate_pessimistic_proof(initial_state.into(), &multi_batch_header).unwrap();
+        assert_eq!(
+            pv.commit_imported_bridge_exits,
+            Digest::from(
+                multi_batch_header
+                    .commit_imported_bridge_exits()
+                    .commitment(IMPORTED_BRIDGE_EXIT_COMMITMENT_VERSION)
+            )
+        );
+
+        pv.commit_imported_bridge_exits
+    };
+
+    assert_ne!(
+        commit_imported_bridge_exits(&[(USDC, u(50)), (ETH, u(100))]),
+        commit_imported_bridge_exits(&[(USDC, u(50))])
+    );
+}
+
+#[test]
+fn public_values_commit_to_the_gas_token() {
+    let mut forest = Forest::new(vec![(ETH, u(100)), (USDC, u(100))]);
+    let initial_state = forest.state_b.clone();
+    let certificate = forest.apply_message_events(&[], &[u(20)]);
+
+    let output = |gas_token: Option<TokenInfo>| {
+        let multi_batch_header = initial_state
+            .make_multi_batch_header(
+                &certificate,
+                L1WitnessCtx {
+                    l1_info_root: certificate.l1_info_root().unwrap().unwrap_or_default(),
+                    prev_pessimistic_root: PessimisticRootInput::Computed(
+                        PessimisticRootCommitmentVersion::V2,
+                    ),
+                    aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
+                        signer: forest.get_signer(),
+                    },
+                    gas_token,
+                },
+            )
+            .unwrap();
+
+        let (pv, _) =
+            generate_pessimistic_proof(initial_state.clone().into(), &multi_batch_header).unwrap();
+        pv
+    };
+
+    let eth = output(None);
+    let usdc = output(Some(USDC));
+    assert_eq!(eth.commit_gas_token, Digest::ZERO);
+    assert_ne!(usdc.commit_gas_token, Digest::ZERO);
+
+    // The committed public values carry the balance moved by the messages in
+    // the new pessimistic root, and V4 commits to the gas token itself.
+    for version in [
+        PessimisticProofOutput::VERSION,
+        PessimisticProofOutputVersion::V4,
+    ] {
+        assert_ne!(
+            eth.to_public_values_of(version).unwrap(),
+            usdc.to_public_values_of(version).unwrap(),
+            "{version:?}"
+        );
+    }
+}
+
+#[rstest]
+// Messages without value need no balance for L1 ETH
+#[case(vec![], None, vec![u(0), u(0)], vec![u(0)])]
+// Messages with value move the balance of L1 ETH
+#[case(vec![(ETH, u(100))], None, vec![u(50), u(0)], vec![u(120)])]
+// Messages with value move the balance of the gas token of the network
+#[case(vec![(USDC, u(100))], Some(USDC), vec![u(50), u(0)], vec![u(120)])]
+fn message_bridge_exits(
+    #[case] initial_balances: Vec<(TokenInfo, U256)>,
+    #[case] gas_token: Option<TokenInfo>,
+    #[case] imported_messages: Vec<U256>,
+    #[case] messages: Vec<U256>,
+) {
+    let mut forest = Forest::new(initial_balances);
+    let initial_state = forest.state_b.clone();
+    let certificate = forest.apply_message_events(&imported_messages, &messages);
+
+    let mut new_state = initial_state.clone();
+    let multi_batch_header = new_state
+        .apply_certificate(
+            &certificate,
+            L1WitnessCtx {
+                l1_info_root: certificate.l1_info_root().unwrap().unwrap_or_default(),
+                prev_pessimistic_root: PessimisticRootInput::Computed(
+                    PessimisticRootCommitmentVersion::V2,
+                ),
+                aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
+                    signer: forest.get_signer(),
+                },
+                gas_token,
+            },
+        )
+        .unwrap();
+
+    let (pv, _) = generate_pessimistic_proof(initial_state.into(), &multi_batch_header).unwrap();
+    forest.state_b = new_state;
+    forest.assert_output_matches(&pv);
+
+    // The balance of the gas token is only proven when a message carries value
+    let gas_token = gas_token.unwrap_or(ETH);
+    let total = |amounts: &[U256]| amounts.iter().fold(U256::ZERO, |sum, amount| sum + *amount);
+    let moved = !total(&imported_messages).is_zero() || !total(&messages).is_zero();
+    let proven_tokens: Vec<_> = multi_batch_header.balances_proofs.keys().collect();
+    assert_eq!(
+        proven_tokens,
+        moved.then_some(&gas_token).into_iter().collect::<Vec<_>>()
+    );
+
+    if let Some((initial_balance, _)) = multi_batch_header.balances_proofs.get(&gas_token) {
+        let balance = forest
+            .state_b
+            .balance_tree
+            .get(gas_token)
+            .unwrap_or_default();
+        let balance = U256::from_be_bytes(*balance);
+        assert_eq!(
+            balance,
+            *initial_balance + total(&imported_messages) - total(&messages)
+        );
+    }
+}
+
 #[test]
 fn e2e_local_pp_simple() {
     e2e_local_pp_simple_helper(
@@ -276,6 +431,7 @@ fn e2e_local_pp_random() {
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
                     signer: forest.get_signer(),
                 },
+                gas_token: None,
             },
         )
         .unwrap();
@@ -313,6 +469,7 @@ fn inconsistent_ger() {
             aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
                 signer: forest.get_signer(),
             },
+            gas_token: None,
         },
     );
 
@@ -346,6 +503,7 @@ fn test_sp1_simple() {
                 aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
                     signer: forest.get_signer(),
                 },
+                gas_token: None,
             },
         )
         .unwrap();
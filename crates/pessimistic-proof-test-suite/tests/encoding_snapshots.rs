@@ -0,0 +1,38 @@
+//! Pin the exact bytes of the encodings verified on-chain or read by the
+//! pessimistic proof program, for the canonical certificate test vectors.
+//!
+//! A change in any of these snapshots breaks the verification of the proofs
+//! generated before it, and must be deliberate.
+
+use pessimistic_proof_test_suite::{
+    runner::Runner,
+    test_vector::{apply_certificate, certificate_test_inputs, CertificateTestVector},
+};
+
+#[test]
+fn encoding_snapshots() {
+    for input in certificate_test_inputs() {
+        let (_, multi_batch_header) =
+            apply_certificate(&input.initial_state, &input.certificate, input.signer).unwrap();
+        let stdin = Runner::prepare_stdin(&input.initial_state.clone().into(), &multi_batch_header);
+
+        let vector = CertificateTestVector::new(
+            &input.name,
+            input.initial_state,
+            input.certificate,
+            input.signer,
+        )
+        .unwrap();
+
+        let encodings = format!(
+            "certificate_hash: {}\npublic_values: {}\nnetwork_state: 0x{}\nmulti_batch_header: \
+             0x{}\n",
+            vector.expected_certificate_hash,
+            vector.expected_public_values,
+            hex::encode(&stdin.buffer[0]),
+            hex::encode(&stdin.buffer[1]),
+        );
+
+        insta::assert_snapshot!(input.name, encodings);
+    }
+}
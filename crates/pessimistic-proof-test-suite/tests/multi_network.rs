@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use agglayer_types::{
+    aggchain_data::CertificateAggchainDataCtx, primitives::U256, L1WitnessCtx,
+    LocalNetworkStateData, NetworkId, PessimisticRootInput,
+};
+use pessimistic_proof::core::{
+    commitment::PessimisticRootCommitmentVersion, generate_pessimistic_proof,
+};
+use pessimistic_proof_test_suite::{
+    multi_network::MultiNetworkForest,
+    sample_data::{ETH, USDC},
+};
+
+fn u(x: u64) -> U256 {
+    x.try_into().unwrap()
+}
+
+#[test]
+fn interleaved_certificates_are_valid() {
+    let (n1, n2, n3) = (NetworkId::new(1), NetworkId::new(2), NetworkId::new(3));
+    let mut scenario = MultiNetworkForest::new()
+        .with_network(n1, [(USDC, u(100)), (ETH, u(100))])
+        .with_network(n2, [(USDC, u(100))])
+        .with_network(n3, []);
+
+    let mut states: BTreeMap<NetworkId, LocalNetworkStateData> = scenario
+        .network_ids()
+        .map(|network_id| {
+            let forest = scenario.forest(network_id).unwrap();
+            (network_id, forest.state_b.clone())
+        })
+        .collect();
+
+    scenario
+        .bridge(n1, n2, USDC, u(10))
+        .bridge(n1, n3, ETH, u(20))
+        .bridge(n2, n3, USDC, u(30));
+    let first_round = scenario.next_round();
+
+    // Network 3 re-exports what it received during the first round.
+    scenario
+        .bridge(n3, n1, ETH, u(5))
+        .bridge(n2, n1, USDC, u(50));
+    let second_round = scenario.next_round();
+
+    assert_eq!(first_round[1].imported_bridge_exits.len(), 1);
+    assert_eq!(first_round[2].imported_bridge_exits.len(), 2);
+    assert_eq!(second_round[0].imported_bridge_exits.len(), 2);
+
+    for certificate in first_round.iter().chain(second_round.iter()) {
+        let state = states.get_mut(&certificate.network_id).unwrap();
+        let forest = scenario.forest(certificate.network_id).unwrap();
+
+        let initial_state = state.clone();
+        let multi_batch_header = state
+            .apply_certificate(
+                certificate,
+                L1WitnessCtx {
+                    l1_info_root: certificate.l1_info_root().unwrap().unwrap_or_default(),
+                    prev_pessimistic_root: PessimisticRootInput::Computed(
+                        PessimisticRootCommitmentVersion::V2,
+                    ),
+                    aggchain_data_ctx: CertificateAggchainDataCtx::LegacyEcdsa {
+                        signer: forest.get_signer(),
+                    },
+                    gas_token: None,
+                },
+            )
+            .unwrap();
+
+        generate_pessimistic_proof(initial_state.into(), &multi_batch_header).unwrap();
+    }
+
+    for network_id in scenario.network_ids() {
+        assert_eq!(
+            scenario.next_height(network_id),
+            Some(agglayer_types::Height::new(2))
+        );
+        assert_eq!(
+            states[&network_id].exit_tree.get_root(),
+            scenario
+                .forest(network_id)
+                .unwrap()
+                .state_b
+                .exit_tree
+                .get_root()
+        );
+    }
+}
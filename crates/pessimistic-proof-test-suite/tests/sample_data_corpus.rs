@@ -0,0 +1,59 @@
+use pessimistic_proof_test_suite::{
+    sample_data::corpus::{Corpus, CorpusConfig},
+    test_vector::apply_certificate,
+};
+
+fn config() -> CorpusConfig {
+    CorpusConfig {
+        certificates: 20,
+        tokens: 5,
+        mean_events: 3.0,
+        max_events: 16,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn corpus_is_deterministic() {
+    let first = serde_json::to_string(&Corpus::generate(config()).unwrap()).unwrap();
+    let second = serde_json::to_string(&Corpus::generate(config()).unwrap()).unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn corpus_certificates_apply_on_initial_state() {
+    let corpus = Corpus::generate(config()).unwrap();
+    assert_eq!(corpus.certificates.len(), 20);
+
+    for input in corpus.test_inputs() {
+        assert!(
+            input.certificate.bridge_exits.len() + input.certificate.imported_bridge_exits.len()
+                <= 16
+        );
+        apply_certificate(&input.initial_state, &input.certificate, input.signer).unwrap();
+    }
+}
+
+#[test]
+fn corpus_without_imported_exits() {
+    let corpus = Corpus::generate(CorpusConfig {
+        imported_ratio: 0.0,
+        ..config()
+    })
+    .unwrap();
+
+    assert!(corpus
+        .certificates
+        .iter()
+        .all(|certificate| certificate.imported_bridge_exits.is_empty()));
+}
+
+#[test]
+fn corpus_rejects_invalid_ratio() {
+    assert!(Corpus::generate(CorpusConfig {
+        imported_ratio: 1.5,
+        ..config()
+    })
+    .is_err());
+}
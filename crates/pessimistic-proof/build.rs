@@ -1,5 +1,10 @@
 pub fn main() -> eyre::Result<()> {
     color_eyre::install()?;
-    agglayer_elf_build::build_program("crates/pessimistic-proof-program")?;
+
+    // The program is only built to be embedded.
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_ELF").is_some() {
+        agglayer_elf_build::build_program("crates/pessimistic-proof-program")?;
+    }
+
     Ok(())
 }
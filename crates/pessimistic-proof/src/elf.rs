@@ -0,0 +1,130 @@
+//! Loading of the ELF of the pessimistic proof program.
+//!
+//! The ELF is embedded in the binary with the `embedded-elf` feature. Without
+//! it, e.g. for the read-only replicas which never prove, the ELF is loaded at
+//! runtime from a path, and checked against its expected keccak hash.
+
+use std::path::{Path, PathBuf};
+
+use agglayer_primitives::Digest;
+
+use crate::keccak::keccak256;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ElfError {
+    #[error("Failed to read the ELF at {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("The hash of the ELF at {path} is {actual}, expected {expected}")]
+    HashMismatch {
+        path: PathBuf,
+        expected: Digest,
+        actual: Digest,
+    },
+
+    #[error("The hash of the pessimistic proof program is {actual}, expected {expected}")]
+    UnexpectedHash { expected: Digest, actual: Digest },
+
+    #[error("The ELF is not embedded in this build and no path to load it from is configured")]
+    NotEmbedded,
+}
+
+/// Load the ELF at the given path, checking that its keccak hash is the
+/// expected one.
+///
+/// The ELF is loaded once per process, so it is leaked to be used like the
+/// embedded one.
+pub fn load(path: &Path, expected_hash: Digest) -> Result<&'static [u8], ElfError> {
+    let elf = std::fs::read(path).map_err(|source| ElfError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let actual = keccak256(&elf);
+    if actual != expected_hash {
+        return Err(ElfError::HashMismatch {
+            path: path.to_path_buf(),
+            expected: expected_hash,
+            actual,
+        });
+    }
+
+    Ok(Vec::leak(elf))
+}
+
+/// ELF loaded from the given path and expected hash if any, or the embedded
+/// one otherwise.
+pub fn resolve(external: Option<(&Path, Digest)>) -> Result<&'static [u8], ElfError> {
+    match external {
+        Some((path, expected_hash)) => load(path, expected_hash),
+        #[cfg(feature = "embedded-elf")]
+        None => Ok(crate::ELF),
+        #[cfg(not(feature = "embedded-elf"))]
+        None => Err(ElfError::NotEmbedded),
+    }
+}
+
+/// Integrity check of the resolved ELF, returning its keccak hash once
+/// checked against the expected one if any.
+///
+/// Unlike [`load`], this also covers the embedded ELF, so that a binary
+/// packaged with the wrong program fails to start.
+pub fn check(elf: &[u8], expected_hash: Option<Digest>) -> Result<Digest, ElfError> {
+    let actual = keccak256(elf);
+    match expected_hash {
+        Some(expected) if expected != actual => Err(ElfError::UnexpectedHash { expected, actual }),
+        _ => Ok(actual),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_elf(name: &str, content: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}.elf", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn elf_with_the_expected_hash_is_loaded() {
+        let path = write_elf("expected-hash", b"program");
+
+        let elf = load(&path, keccak256(b"program")).unwrap();
+
+        assert_eq!(elf, b"program");
+    }
+
+    #[test]
+    fn elf_with_another_hash_is_rejected() {
+        let path = write_elf("another-hash", b"tampered program");
+
+        let error = load(&path, keccak256(b"program")).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ElfError::HashMismatch { actual, .. } if actual == keccak256(b"tampered program")
+        ));
+    }
+
+    #[test]
+    fn elf_is_checked_against_the_expected_hash_if_any() {
+        assert_eq!(check(b"program", None).unwrap(), keccak256(b"program"));
+        assert_eq!(
+            check(b"program", Some(keccak256(b"program"))).unwrap(),
+            keccak256(b"program")
+        );
+
+        let error = check(b"tampered program", Some(keccak256(b"program"))).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ElfError::UnexpectedHash { actual, .. } if actual == keccak256(b"tampered program")
+        ));
+    }
+}
@@ -1,5 +1,5 @@
 pub mod proof;
-pub use proof::{PessimisticProofOutput, Proof};
+pub use proof::{PessimisticProofOutput, PessimisticProofOutputVersion, Proof, PublicValuesError};
 
 pub mod local_balance_tree;
 pub mod local_exit_tree;
@@ -24,12 +24,15 @@ pub mod core {
             AggchainData, AggchainHashValues, AggchainProof, MultiSignature, MultisigError, Vkey,
         },
         generate_pessimistic_proof,
-        local_state::commitment,
+        local_state::{balance_token_info, commitment, moves_balance},
+        proof::IMPORTED_BRIDGE_EXIT_COMMITMENT_VERSION,
         PESSIMISTIC_PROOF_PROGRAM_SELECTOR, PESSIMISTIC_PROOF_PROGRAM_VERSION,
     };
 }
 
+pub mod elf;
 pub mod error;
 
 /// ELF of the pessimistic proof program
+#[cfg(feature = "embedded-elf")]
 pub const ELF: &[u8] = agglayer_elf_build::elf_bytes!();
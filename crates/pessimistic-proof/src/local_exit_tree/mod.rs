@@ -4,6 +4,7 @@ use unified_bridge::LocalExitTreeError;
 use crate::local_exit_tree::data::LocalExitTreeData;
 pub mod data;
 
+pub use pessimistic_proof_core::tree_depths::LOCAL_EXIT_TREE_DEPTH;
 pub use unified_bridge::LocalExitTree;
 
 #[cfg(test)]
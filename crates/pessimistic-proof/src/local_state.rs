@@ -3,14 +3,17 @@ use agglayer_tries::roots::{LocalBalanceRoot, LocalExitRoot, LocalNullifierRoot}
 use serde::{Deserialize, Serialize};
 use unified_bridge::LocalExitTree;
 
-use crate::{local_balance_tree::LocalBalanceTree, nullifier_tree::NullifierTree};
+use crate::{
+    local_balance_tree::LocalBalanceTree, local_exit_tree::LOCAL_EXIT_TREE_DEPTH,
+    nullifier_tree::NullifierTree,
+};
 
 /// State representation of one network without the leaves, taken as input by
 /// the prover.
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct LocalNetworkState {
     /// Commitment to the [`BridgeExit`](struct@crate::bridge_exit::BridgeExit).
-    pub exit_tree: LocalExitTree,
+    pub exit_tree: LocalExitTree<LOCAL_EXIT_TREE_DEPTH>,
     /// Commitment to the balance for each token.
     pub balance_tree: LocalBalanceTree,
     /// Commitment to the Nullifier tree for the local network, tracks claimed
@@ -1,8 +1,10 @@
 #[cfg(any(test, feature = "testutils"))]
 pub use pessimistic_proof_core::proof::zero_if_empty_local_exit_root;
-pub use pessimistic_proof_core::PessimisticProofOutput;
 #[cfg(any(test, feature = "testutils"))]
 use pessimistic_proof_core::{multi_batch_header::MultiBatchHeader, NetworkState};
+pub use pessimistic_proof_core::{
+    PessimisticProofOutput, PessimisticProofOutputVersion, PublicValuesError,
+};
 use serde::{Deserialize, Serialize};
 #[cfg(any(test, feature = "testutils"))]
 use sp1_sdk::{Prover, ProverClient, SP1Stdin};
@@ -20,7 +22,8 @@ impl DisplayToHex for PessimisticProofOutput {
         format!(
             "prev_local_exit_root: {}, prev_pessimistic_root: {}, l1_info_root: {}, \
              origin_network: {}, aggchain_hash: {}, new_local_exit_root: {}, \
-             new_pessimistic_root: {}",
+             new_pessimistic_root: {}, commit_imported_bridge_exits: {}, local_exit_tree_depth: \
+             {}, commit_gas_token: {}",
             self.prev_local_exit_root,
             self.prev_pessimistic_root,
             self.l1_info_root,
@@ -28,6 +31,9 @@ impl DisplayToHex for PessimisticProofOutput {
             self.aggchain_hash,
             self.new_local_exit_root,
             self.new_pessimistic_root,
+            self.commit_imported_bridge_exits,
+            self.local_exit_tree_depth,
+            self.commit_gas_token,
         )
     }
 }
@@ -66,14 +72,173 @@ impl Proof {
 
 #[cfg(test)]
 mod tests {
+    use agglayer_primitives::Digest;
     use agglayer_tries::roots::LocalExitRoot;
     use pessimistic_proof_core::{
         keccak::keccak256_combine,
         proof::{EMPTY_LER, EMPTY_PP_ROOT_V2},
+        tree_depths::UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH,
     };
+    use unified_bridge::NetworkId;
 
+    use super::{PessimisticProofOutput, PessimisticProofOutputVersion, PublicValuesError};
     use crate::local_state::LocalNetworkState;
 
+    fn output() -> PessimisticProofOutput {
+        PessimisticProofOutput {
+            prev_local_exit_root: LocalExitRoot::new(Digest([0x01; 32])),
+            prev_pessimistic_root: Digest([0x02; 32]),
+            l1_info_root: Digest([0x03; 32]),
+            origin_network: NetworkId::new(0x04050607),
+            aggchain_hash: Digest([0x08; 32]),
+            new_local_exit_root: LocalExitRoot::new(Digest([0x09; 32])),
+            new_pessimistic_root: Digest([0x0a; 32]),
+            commit_imported_bridge_exits: Digest([0x0b; 32]),
+            local_exit_tree_depth: 0x0c,
+            commit_gas_token: Digest([0x0d; 32]),
+        }
+    }
+
+    fn v1_public_values() -> Vec<u8> {
+        [
+            [0x01; 32].as_slice(),
+            &[0x02; 32],
+            &[0x03; 32],
+            &[0x04, 0x05, 0x06, 0x07],
+            &[0x08; 32],
+            &[0x09; 32],
+            &[0x0a; 32],
+        ]
+        .concat()
+    }
+
+    fn v2_public_values() -> Vec<u8> {
+        [v1_public_values().as_slice(), &[0x0b; 32]].concat()
+    }
+
+    fn v3_public_values() -> Vec<u8> {
+        [v2_public_values().as_slice(), &[0x00, 0x00, 0x00, 0x0c]].concat()
+    }
+
+    fn v4_public_values() -> Vec<u8> {
+        [v3_public_values().as_slice(), &[0x0d; 32]].concat()
+    }
+
+    #[test]
+    fn public_values_encoding() {
+        // The rollup manager packs the same values in the same order, any
+        // change in this encoding breaks the settlement.
+        let public_values = output().to_public_values().unwrap();
+
+        assert_eq!(
+            PessimisticProofOutput::VERSION,
+            PessimisticProofOutputVersion::V1
+        );
+        assert_eq!(public_values, v1_public_values());
+        assert_eq!(
+            PessimisticProofOutput::from_public_values(&public_values).unwrap(),
+            output().committed()
+        );
+    }
+
+    #[test]
+    fn public_values_encoding_of_each_version() {
+        for (version, expected) in [
+            (PessimisticProofOutputVersion::V1, v1_public_values()),
+            (PessimisticProofOutputVersion::V2, v2_public_values()),
+            (PessimisticProofOutputVersion::V3, v3_public_values()),
+            (PessimisticProofOutputVersion::V4, v4_public_values()),
+        ] {
+            let public_values = output().to_public_values_of(version).unwrap();
+
+            assert_eq!(public_values.len(), version.encoded_len(), "{version:?}");
+            assert_eq!(public_values, expected, "{version:?}");
+        }
+    }
+
+    #[test]
+    fn v3_public_values_are_decoded() {
+        let public_values = v3_public_values();
+
+        assert_eq!(
+            PessimisticProofOutputVersion::from_encoded_len(public_values.len()),
+            Some(PessimisticProofOutputVersion::V3)
+        );
+        assert_eq!(
+            PessimisticProofOutput::from_public_values(&public_values).unwrap(),
+            PessimisticProofOutput {
+                commit_gas_token: Digest::ZERO,
+                ..output()
+            }
+        );
+    }
+
+    #[test]
+    fn v4_public_values_are_decoded() {
+        let public_values = v4_public_values();
+
+        assert_eq!(
+            PessimisticProofOutputVersion::from_encoded_len(public_values.len()),
+            Some(PessimisticProofOutputVersion::V4)
+        );
+        assert_eq!(
+            PessimisticProofOutput::from_public_values(&public_values).unwrap(),
+            output()
+        );
+    }
+
+    #[test]
+    fn v1_public_values_are_decoded() {
+        let public_values = v1_public_values();
+
+        assert_eq!(
+            PessimisticProofOutputVersion::from_encoded_len(public_values.len()),
+            Some(PessimisticProofOutputVersion::V1)
+        );
+        assert_eq!(
+            PessimisticProofOutput::from_public_values(&public_values).unwrap(),
+            PessimisticProofOutput {
+                commit_imported_bridge_exits: Digest::ZERO,
+                local_exit_tree_depth: UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH,
+                commit_gas_token: Digest::ZERO,
+                ..output()
+            }
+        );
+    }
+
+    #[test]
+    fn v2_public_values_are_decoded() {
+        let public_values = v2_public_values();
+
+        assert_eq!(
+            PessimisticProofOutputVersion::from_encoded_len(public_values.len()),
+            Some(PessimisticProofOutputVersion::V2)
+        );
+        assert_eq!(
+            PessimisticProofOutput::from_public_values(&public_values).unwrap(),
+            PessimisticProofOutput {
+                local_exit_tree_depth: UNIFIED_BRIDGE_LOCAL_EXIT_TREE_DEPTH,
+                commit_gas_token: Digest::ZERO,
+                ..output()
+            }
+        );
+    }
+
+    #[test]
+    fn public_values_of_another_length_are_rejected() {
+        let mut public_values = v4_public_values();
+        public_values.push(0);
+
+        assert!(matches!(
+            PessimisticProofOutput::from_public_values(&public_values),
+            Err(PublicValuesError::InvalidLength(265))
+        ));
+        assert!(matches!(
+            PessimisticProofOutput::from_public_values(&public_values[..100]),
+            Err(PublicValuesError::InvalidLength(100))
+        ));
+    }
+
     #[test]
     fn empty_tree_roots() {
         let empty_state = LocalNetworkState::default();
@@ -0,0 +1,115 @@
+#!/usr/bin/env bash
+# Dump the state of the L1 contracts image as a genesis for anvil, to run the
+# integration tests without docker:
+#
+#   scripts/dump-l1-genesis.sh /tmp/l1-genesis.json
+#   AGGLAYER_TEST_L1_GENESIS=/tmp/l1-genesis.json cargo nextest run -P integrations-anvil
+#
+# The geth dump of the image only holds the hashes of the storage slots, so
+# the transactions of the image are replayed on anvil instead, which dumps
+# the resulting state with the plain slots at the same addresses.
+#
+# Requires docker, anvil, curl and jq.
+set -euo pipefail
+
+output="${1:-l1-genesis.json}"
+image="${L1_IMAGE:-hermeznetwork/geth-zkevm-contracts}"
+geth_port="${GETH_PORT:-18545}"
+anvil_port="${ANVIL_PORT:-18546}"
+geth="http://127.0.0.1:$geth_port"
+anvil="http://127.0.0.1:$anvil_port"
+
+workdir="$(mktemp -d)"
+container=""
+anvil_pid=""
+
+cleanup() {
+    if [ -n "$anvil_pid" ]; then
+        kill "$anvil_pid" 2>/dev/null || true
+    fi
+    if [ -n "$container" ]; then
+        docker rm -f "$container" >/dev/null
+    fi
+    rm -rf "$workdir"
+}
+trap cleanup EXIT
+
+rpc() {
+    local url="$1" method="$2" params="$3"
+    curl -sSf -X POST -H 'Content-Type: application/json' \
+        --data "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"$method\",\"params\":$params}" \
+        "$url" | jq -ce 'if .error then error(.error.message) else .result end'
+}
+
+wait_for() {
+    for _ in $(seq 60); do
+        if rpc "$1" eth_blockNumber '[]' >/dev/null 2>&1; then
+            return
+        fi
+        sleep 1
+    done
+    echo "Timed out waiting for $1" >&2
+    exit 1
+}
+
+container="$(docker run -d -p "$geth_port:8545" "$image")"
+wait_for "$geth"
+
+chain_id=$(($(rpc "$geth" eth_chainId '[]' | jq -r .)))
+latest=$(($(rpc "$geth" eth_blockNumber '[]' | jq -r .)))
+
+# Mine each replayed transaction on submission, without fees so that the
+# gas prices of the image are accepted, and without the prefunded accounts.
+anvil --port "$anvil_port" --chain-id "$chain_id" --accounts 0 --base-fee 0 \
+    --disable-block-gas-limit --dump-state "$workdir/state.json" >"$workdir/anvil.log" &
+anvil_pid=$!
+wait_for "$anvil"
+
+touched="$workdir/touched"
+for number in $(seq 1 "$latest"); do
+    for hash in $(rpc "$geth" eth_getBlockByNumber "[\"$(printf '0x%x' "$number")\",false]" |
+        jq -r '.transactions[]'); do
+        tx="$(rpc "$geth" eth_getTransactionByHash "[\"$hash\"]")"
+        receipt="$(rpc "$geth" eth_getTransactionReceipt "[\"$hash\"]")"
+        raw="$(rpc "$geth" eth_getRawTransactionByHash "[\"$hash\"]")"
+        from="$(jq -r .from <<<"$tx")"
+
+        # The balances are set back to the ones of the image once replayed.
+        rpc "$anvil" anvil_setBalance "[\"$from\",\"0xffffffffffffffffffffffffffff\"]" >/dev/null
+        rpc "$anvil" eth_sendRawTransaction "[$raw]" >/dev/null
+
+        jq -r '.from, .to // empty' <<<"$tx" >>"$touched"
+        jq -r '.contractAddress // empty' <<<"$receipt" >>"$touched"
+    done
+done
+
+for address in $(sort -u "$touched"); do
+    balance="$(rpc "$geth" eth_getBalance "[\"$address\",\"latest\"]")"
+    rpc "$anvil" anvil_setBalance "[\"$address\",$balance]" >/dev/null
+done
+
+# Anvil dumps its state on exit.
+kill -INT "$anvil_pid"
+wait "$anvil_pid" || true
+anvil_pid=""
+
+jq --argjson chain_id "$chain_id" '
+    def tohex:
+        if . == 0 then "0x0"
+        else "0x" + ([recurse(if . >= 16 then (. / 16 | floor) else empty end) | . % 16
+            | "0123456789abcdef"[.:. + 1]] | reverse | join(""))
+        end;
+    def word: "0x" + (("0" * 64) + .[2:] | .[-64:]);
+    {
+        config: { chainId: $chain_id },
+        difficulty: "0x0",
+        gasLimit: "0x1c9c380",
+        alloc: (.accounts | map_values({
+            balance,
+            nonce: (.nonce | tohex),
+            code,
+            storage: (.storage | with_entries({ key: (.key | word), value: (.value | word) }))
+        }))
+    }' "$workdir/state.json" >"$output"
+
+echo "L1 genesis written to $output"
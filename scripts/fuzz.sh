@@ -7,10 +7,68 @@ fuzzers=(
     "agglayer-grpc-types/compat::v1::tests::fuzz_parser_epoch_configuration"
     "agglayer-grpc-types/compat::v1::tests::fuzz_round_trip_certificate_id"
     "agglayer-grpc-types/compat::v1::tests::fuzz_round_trip_epoch_configuration"
+    "agglayer-jsonrpc-api/tests::fuzz::fuzz_parse_certificate"
+    "agglayer-jsonrpc-api/tests::fuzz::fuzz_parse_certificate_id"
+    "agglayer-jsonrpc-api/tests::fuzz::fuzz_parse_network_id"
+    "agglayer-jsonrpc-api/tests::fuzz::fuzz_parse_proof"
+    "agglayer-storage/types::fuzz::fuzz_decode_api_key_usage_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_api_key_usage_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_audit_log"
+    "agglayer-storage/types::fuzz::fuzz_decode_callback"
+    "agglayer-storage/types::fuzz::fuzz_decode_certificate"
+    "agglayer-storage/types::fuzz::fuzz_decode_certificate_header"
+    "agglayer-storage/types::fuzz::fuzz_decode_certificate_id"
+    "agglayer-storage/types::fuzz::fuzz_decode_certificate_index"
+    "agglayer-storage/types::fuzz::fuzz_decode_certificate_per_network_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_digest"
+    "agglayer-storage/types::fuzz::fuzz_decode_epoch_number"
+    "agglayer-storage/types::fuzz::fuzz_decode_event_log_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_event_log_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_height"
+    "agglayer-storage/types::fuzz::fuzz_decode_local_exit_tree_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_local_exit_tree_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_metadata_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_metadata_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_network_id"
+    "agglayer-storage/types::fuzz::fuzz_decode_network_info_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_network_info_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_pending_certificate"
+    "agglayer-storage/types::fuzz::fuzz_decode_pending_queue_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_per_epoch_metadata_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_per_epoch_metadata_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_proof"
+    "agglayer-storage/types::fuzz::fuzz_decode_proof_cache"
+    "agglayer-storage/types::fuzz::fuzz_decode_proven_certificate"
+    "agglayer-storage/types::fuzz::fuzz_decode_settled_certificate"
+    "agglayer-storage/types::fuzz::fuzz_decode_settled_roots_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_settled_roots_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_settlement_attempts"
+    "agglayer-storage/types::fuzz::fuzz_decode_settlement_costs_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_settlement_costs_value"
+    "agglayer-storage/types::fuzz::fuzz_decode_smt_key"
+    "agglayer-storage/types::fuzz::fuzz_decode_smt_value"
 )
 
+# Seed the corpus of the fuzzers with the sample data, written under
+# target/fuzz-corpus/<crate>/<fuzzer>.
+corpus="$(pwd)/target/fuzz-corpus"
+corpus_writers=(
+    "agglayer-jsonrpc-api/tests::fuzz::write_fuzz_corpus"
+    "agglayer-storage/types::fuzz::write_fuzz_corpus"
+)
+for writer in "${corpus_writers[@]}"; do
+    crate="${writer%%/*}"
+    FUZZ_CORPUS_DIR="$corpus/$crate" cargo test -p "$crate" --all-features --lib -- \
+        --ignored --exact "${writer#*/}"
+done
+
 printf '%s\0' "${fuzzers[@]}" | parallel --null --bar --joblog fuzz.log bash -c '
     crate="$(echo {} | cut -d/ -f1)"
     target="$(echo {} | cut -d/ -f2)"
-    cargo bolero test --rustc-bootstrap -p "$crate" --all-features "$target" -T '"$time"'
+    corpus_dir="'"$corpus"'/$crate/${target##*::}"
+    corpus_args=()
+    if [ -d "$corpus_dir" ]; then
+        corpus_args=(--corpus-dir "$corpus_dir")
+    fi
+    cargo bolero test --rustc-bootstrap -p "$crate" --all-features "$target" -T '"$time"' "${corpus_args[@]}"
 '
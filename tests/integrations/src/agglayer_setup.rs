@@ -1,14 +1,15 @@
 use std::{path::Path, time::Duration};
 
-use agglayer_config::{log::LogLevel, Config};
+use agglayer_config::{log::LogLevel, Config, StartupChecks};
 use agglayer_prover::fake::FakeProver;
+use agglayer_types::BuildInfo;
 use alloy::signers::local::{coins_bip39::English, MnemonicBuilder, PrivateKeySigner};
 use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pessimistic_proof::ELF;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 
-use crate::l1_setup::{self, next_available_addr, L1Docker};
+use crate::l1_setup::{next_available_addr, L1};
 
 const PHRASE: &str = "test test test test test test test test test test test junk";
 
@@ -45,16 +46,16 @@ macro_rules! wait_for_settlement_or_error {
     }};
 }
 
-pub async fn start_l1() -> L1Docker {
+pub async fn start_l1() -> L1 {
     let name = std::thread::current().name().unwrap().replace("::", "_");
-    let l1 = l1_setup::L1Docker::new(name).await;
+    let l1 = L1::start(name).await;
     tokio::time::sleep(Duration::from_secs(1)).await;
     l1
 }
 
 pub async fn start_agglayer(
     config_path: &Path,
-    l1: &L1Docker,
+    l1: &L1,
     config: Option<agglayer_config::Config>,
     token: Option<CancellationToken>,
 ) -> (oneshot::Receiver<()>, WsClient, CancellationToken) {
@@ -111,8 +112,8 @@ pub async fn start_agglayer(
 
     config.telemetry.addr = next_available_addr();
     config.log.level = LogLevel::Debug;
-    config.l1.node_url = l1.rpc.parse().unwrap();
-    config.l1.ws_node_url = l1.ws.parse().unwrap();
+    config.l1.node_url = l1.rpc().parse().unwrap();
+    config.l1.ws_node_url = l1.ws().parse().unwrap();
     config.l1.rollup_manager_contract = "0x0B306BF915C4d645ff596e518fAf3F9669b97016"
         .parse()
         .unwrap();
@@ -120,6 +121,9 @@ pub async fn start_agglayer(
         "0x610178dA211FEF7D417bC0e6FeD39F05609AD788"
             .parse()
             .unwrap();
+    // The pinned L1 image registers a pessimistic proof vkey that may lag
+    // behind the embedded program.
+    config.l1.startup_checks = StartupChecks::Warn;
 
     let config_file = config_path.join("config.toml");
     let toml = toml::to_string_pretty(&config).unwrap();
@@ -127,8 +131,12 @@ pub async fn start_agglayer(
 
     let graceful_shutdown_token = cancellation.clone();
     let handle = std::thread::spawn(move || {
-        if let Err(error) = agglayer_node::main(config_file, "test", Some(graceful_shutdown_token))
-        {
+        if let Err(error) = agglayer_node::main(
+            config_file,
+            "test",
+            BuildInfo::default(),
+            Some(graceful_shutdown_token),
+        ) {
             eprintln!("Error: {error}");
         }
         _ = shutdown.send(());
@@ -164,7 +172,7 @@ pub async fn setup_network(
     tmp_dir: &Path,
     config: Option<Config>,
     token: Option<CancellationToken>,
-) -> (oneshot::Receiver<()>, L1Docker, WsClient) {
+) -> (oneshot::Receiver<()>, L1, WsClient) {
     let l1 = start_l1().await;
     let (receiver, client, _token) = start_agglayer(tmp_dir, &l1, config, token).await;
 
@@ -0,0 +1,109 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use agglayer_types::{Certificate, NetworkId};
+use clap::Parser;
+use integrations::{
+    agglayer_setup::get_signer,
+    load::{self, LoadConfig},
+};
+use jsonrpsee::http_client::HttpClientBuilder;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+/// Submit certificate streams against a running agglayer, expected to run
+/// with mock proving, and report the latencies up to their settlement.
+///
+/// The networks must be without settled certificates, each stream starting
+/// at height 0.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct LoadArgs {
+    /// The JSON-RPC endpoint of the agglayer.
+    #[clap(long, default_value = "http://127.0.0.1:9090")]
+    rpc_url: String,
+
+    /// The certificates submitted per second, across the networks.
+    #[clap(long, default_value = "1")]
+    rate: f64,
+
+    /// The duration of the submission, in seconds.
+    #[clap(long, default_value = "60")]
+    duration: u64,
+
+    /// The number of networks submitting certificates.
+    #[clap(long, default_value = "1")]
+    networks: u32,
+
+    /// The id of the first network, the others following it.
+    #[clap(long, default_value = "1")]
+    first_network_id: u32,
+
+    /// The minimum number of bridge exits per certificate.
+    #[clap(long, default_value = "0")]
+    min_exits: usize,
+
+    /// The maximum number of bridge exits per certificate.
+    #[clap(long, default_value = "10")]
+    max_exits: usize,
+
+    /// The time after which an unsettled certificate is given up on, in
+    /// seconds.
+    #[clap(long, default_value = "600")]
+    settlement_timeout: u64,
+
+    /// The seed of the generation of the certificates.
+    #[clap(long, default_value = "0")]
+    seed: u64,
+
+    /// The index of the signer derived from the test mnemonic, used by all
+    /// the networks. Each network uses its test wallet otherwise.
+    #[clap(long)]
+    signer_index: Option<u32>,
+
+    /// The optional path of the JSON report to write.
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+#[tokio::main]
+pub async fn main() -> eyre::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let args = LoadArgs::parse();
+    eyre::ensure!(args.rate > 0.0, "The rate must be positive");
+    eyre::ensure!(
+        args.min_exits <= args.max_exits,
+        "The minimum number of bridge exits exceeds the maximum"
+    );
+
+    let client = Arc::new(HttpClientBuilder::default().build(&args.rpc_url)?);
+    let config = LoadConfig {
+        rate: args.rate,
+        duration: Duration::from_secs(args.duration),
+        network_ids: (args.first_network_id..)
+            .take(args.networks as usize)
+            .map(NetworkId::new)
+            .collect(),
+        bridge_exits: args.min_exits..=args.max_exits,
+        settlement_timeout: Duration::from_secs(args.settlement_timeout),
+        poll_interval: Duration::from_millis(500),
+        seed: args.seed,
+    };
+
+    let report = load::run(client, config, |network_id| match args.signer_index {
+        Some(index) => get_signer(index),
+        None => Certificate::wallet_for_test(network_id),
+    })
+    .await;
+
+    let report = serde_json::to_string_pretty(&report)?;
+    info!("Load run done");
+    match &args.output {
+        Some(output) => std::fs::write(output, report)?,
+        None => println!("{report}"),
+    }
+
+    Ok(())
+}
@@ -1,7 +1,61 @@
-use std::time::Duration;
+use std::{path::Path, time::Duration};
 
+use alloy::node_bindings::{Anvil, AnvilInstance};
 use tokio::process::Command;
 
+/// Environment variable holding the path of the genesis of an L1 run with
+/// anvil, in place of the L1 docker container.
+///
+/// The genesis holds the contracts of the `hermeznetwork/geth-zkevm-contracts`
+/// image at the same addresses, which allows to run the settlement tests
+/// where docker is not available, without waiting for the container to
+/// start. It is dumped from the image by `scripts/dump-l1-genesis.sh`.
+pub const L1_GENESIS_ENV: &str = "AGGLAYER_TEST_L1_GENESIS";
+
+/// L1 node the agglayer settles on, torn down once dropped.
+pub enum L1 {
+    Docker(L1Docker),
+    Anvil(AnvilInstance),
+}
+
+impl L1 {
+    /// Start an L1 run with anvil from the genesis at [`L1_GENESIS_ENV`] if
+    /// set, the L1 docker container otherwise.
+    pub async fn start(name: String) -> Self {
+        match std::env::var_os(L1_GENESIS_ENV) {
+            Some(genesis) => Self::anvil(Path::new(&genesis)),
+            None => Self::Docker(L1Docker::new(name).await),
+        }
+    }
+
+    /// Start an L1 run with anvil from the given genesis, mining a block
+    /// every second.
+    pub fn anvil(genesis: &Path) -> Self {
+        let anvil = Anvil::new()
+            .port(next_available_addr().port())
+            .block_time(1)
+            .arg("--init")
+            .arg(genesis.as_os_str())
+            .spawn();
+
+        Self::Anvil(anvil)
+    }
+
+    pub fn rpc(&self) -> String {
+        match self {
+            Self::Docker(docker) => docker.rpc.clone(),
+            Self::Anvil(anvil) => anvil.endpoint(),
+        }
+    }
+
+    pub fn ws(&self) -> String {
+        match self {
+            Self::Docker(docker) => docker.ws.clone(),
+            Self::Anvil(anvil) => anvil.ws_endpoint(),
+        }
+    }
+}
+
 pub struct L1Docker {
     id: String,
     pub ws: String,
@@ -1,2 +1,3 @@
 pub mod agglayer_setup;
 pub mod l1_setup;
+pub mod load;
@@ -0,0 +1,357 @@
+//! Load generation against a running agglayer.
+//!
+//! Each network submits a stream of certificates carrying bridge exits of its
+//! native token, which leaves its balance tree untouched, so that the streams
+//! are valid against a network without any settled certificate. The target is
+//! expected to run with mock proving, the latencies measured being the ones of
+//! the agglayer itself.
+
+use std::{ops::RangeInclusive, sync::Arc, time::Duration};
+
+use agglayer_types::{
+    aggchain_proof::AggchainData, compute_signature_info, primitives::Hashable, Address,
+    Certificate, CertificateHeader, CertificateId, CertificateStatus, CertificateSubmissionReceipt,
+    Height, NetworkId, U256,
+};
+use alloy::signers::local::PrivateKeySigner;
+use jsonrpsee::{core::client::ClientT as _, http_client::HttpClient, rpc_params};
+use pessimistic_proof::{
+    core::commitment::SignatureCommitmentVersion,
+    keccak::keccak256,
+    local_exit_tree::LocalExitTree,
+    unified_bridge::{BridgeExit, LeafType, TokenInfo},
+};
+use rand::{rngs::StdRng, Rng as _, SeedableRng as _};
+use serde::Serialize;
+use serde_with::{serde_as, DurationMilliSeconds};
+use tokio::{
+    sync::mpsc,
+    task::JoinSet,
+    time::{Instant, MissedTickBehavior},
+};
+use tracing::{debug, info, warn};
+
+/// Parameters of a load run.
+#[derive(Clone, Debug)]
+pub struct LoadConfig {
+    /// Certificates submitted per second, across the networks.
+    pub rate: f64,
+    /// Duration over which the certificates are submitted.
+    pub duration: Duration,
+    pub network_ids: Vec<NetworkId>,
+    /// Bounds of the number of bridge exits of each certificate, drawn
+    /// uniformly.
+    pub bridge_exits: RangeInclusive<usize>,
+    /// Time after which a certificate which is neither settled nor in error
+    /// is given up on.
+    pub settlement_timeout: Duration,
+    /// Interval at which the status of the submitted certificates is polled.
+    pub poll_interval: Duration,
+    /// Seed of the generation of the certificates, for reproducible streams.
+    pub seed: u64,
+}
+
+/// Stream of certificates of a network.
+struct NetworkStream {
+    network_id: NetworkId,
+    wallet: PrivateKeySigner,
+    height: Height,
+    exit_tree: LocalExitTree,
+    rng: StdRng,
+}
+
+impl NetworkStream {
+    fn new(network_id: NetworkId, wallet: PrivateKeySigner, seed: u64) -> Self {
+        Self {
+            network_id,
+            wallet,
+            height: Height::ZERO,
+            exit_tree: LocalExitTree::default(),
+            rng: StdRng::seed_from_u64(seed ^ u64::from(network_id.to_u32())),
+        }
+    }
+
+    /// Build the next certificate of the network, with the given number of
+    /// bridge exits. The stream is only valid as long as the certificates it
+    /// built are settled.
+    fn next_certificate(&mut self, bridge_exits: usize) -> Certificate {
+        let prev_local_exit_root = self.exit_tree.get_root().into();
+
+        let token_info = TokenInfo {
+            origin_network: self.network_id,
+            origin_token_address: Address::new([0x11; 20]),
+        };
+        let bridge_exits: Vec<BridgeExit> = (0..bridge_exits)
+            .map(|_| BridgeExit {
+                leaf_type: LeafType::Transfer,
+                token_info,
+                dest_network: NetworkId::new(0),
+                dest_address: self.rng.random::<[u8; 20]>().into(),
+                amount: U256::from(self.rng.random_range(1..=1_000_000u64)),
+                metadata: Some(keccak256(&[])),
+            })
+            .collect();
+        for exit in &bridge_exits {
+            self.exit_tree.add_leaf(exit.hash()).unwrap();
+        }
+
+        let new_local_exit_root = self.exit_tree.get_root().into();
+        let (_, signature, _) = compute_signature_info(
+            new_local_exit_root,
+            &[],
+            &self.wallet,
+            self.height,
+            SignatureCommitmentVersion::V2,
+        );
+
+        let certificate = Certificate {
+            network_id: self.network_id,
+            height: self.height,
+            prev_local_exit_root,
+            new_local_exit_root,
+            bridge_exits,
+            imported_bridge_exits: vec![],
+            aggchain_data: AggchainData::ECDSA { signature },
+            metadata: Default::default(),
+            custom_chain_data: vec![],
+            l1_info_tree_leaf_count: None,
+        };
+        self.height = self.height.next();
+
+        certificate
+    }
+}
+
+/// Outcome of the submission of a certificate.
+#[derive(Debug)]
+enum Outcome {
+    Rejected,
+    Settled {
+        submission: Duration,
+        settlement: Duration,
+    },
+    InError {
+        submission: Duration,
+    },
+    TimedOut {
+        submission: Duration,
+    },
+}
+
+/// Latency percentiles over a set of samples.
+#[serde_as]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Percentiles {
+    pub samples: usize,
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    pub p50: Duration,
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    pub p90: Duration,
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    pub p99: Duration,
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    pub max: Duration,
+}
+
+impl Percentiles {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let percentile = |q: f64| {
+            samples
+                .get(((samples.len().saturating_sub(1)) as f64 * q).round() as usize)
+                .copied()
+                .unwrap_or_default()
+        };
+
+        Self {
+            samples: samples.len(),
+            p50: percentile(0.5),
+            p90: percentile(0.9),
+            p99: percentile(0.99),
+            max: samples.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Report of a load run.
+#[serde_as]
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct LoadReport {
+    pub submitted: usize,
+    /// Certificates refused on submission.
+    pub rejected: usize,
+    pub settled: usize,
+    pub in_error: usize,
+    /// Certificates neither settled nor in error within the settlement
+    /// timeout.
+    pub timed_out: usize,
+    /// Submissions skipped as every network had a certificate in flight, the
+    /// sign of a rate above the throughput of the agglayer.
+    pub skipped: usize,
+    #[serde_as(as = "DurationMilliSeconds<u64>")]
+    pub elapsed: Duration,
+    /// Settled certificates per second over the run.
+    pub throughput: f64,
+    /// Latency of the submission RPC call.
+    pub submission_latency: Percentiles,
+    /// Latency from the submission to the settlement.
+    pub settlement_latency: Percentiles,
+}
+
+/// Submit certificate streams of the configured networks at the configured
+/// rate, and wait for their settlement.
+///
+/// A network has at most one certificate in flight, the next height being
+/// submitted once the previous one is settled. A network whose certificate is
+/// not settled leaves the run, its stream being no longer valid.
+pub async fn run(
+    client: Arc<HttpClient>,
+    config: LoadConfig,
+    wallet: impl Fn(NetworkId) -> PrivateKeySigner,
+) -> LoadReport {
+    let (idle_sender, mut idle_receiver) = mpsc::unbounded_channel();
+    for network_id in &config.network_ids {
+        _ = idle_sender.send(NetworkStream::new(
+            *network_id,
+            wallet(*network_id),
+            config.seed,
+        ));
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut report = LoadReport::default();
+    let mut in_flight = JoinSet::new();
+
+    let start = Instant::now();
+    let deadline = start + config.duration;
+    let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / config.rate));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    info!(
+        rate = config.rate,
+        duration = ?config.duration,
+        networks = config.network_ids.len(),
+        "Starting the load run"
+    );
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep_until(deadline) => break,
+            _ = interval.tick() => {}
+        }
+
+        let Ok(mut stream) = idle_receiver.try_recv() else {
+            report.skipped += 1;
+            continue;
+        };
+
+        let certificate = stream.next_certificate(rng.random_range(config.bridge_exits.clone()));
+        report.submitted += 1;
+
+        let client = client.clone();
+        let idle_sender = idle_sender.clone();
+        let config = config.clone();
+        in_flight.spawn(async move {
+            let network_id = stream.network_id;
+            let outcome = submit(&client, certificate, &config).await;
+            match outcome {
+                Outcome::Settled { .. } => _ = idle_sender.send(stream),
+                _ => warn!(%network_id, ?outcome, "Network leaving the load run"),
+            }
+
+            outcome
+        });
+    }
+
+    info!(
+        in_flight = in_flight.len(),
+        "Done submitting, waiting for the certificates in flight"
+    );
+
+    let mut submission_latency = Vec::new();
+    let mut settlement_latency = Vec::new();
+    while let Some(outcome) = in_flight.join_next().await {
+        match outcome.expect("load task panicked") {
+            Outcome::Rejected => report.rejected += 1,
+            Outcome::Settled {
+                submission,
+                settlement,
+            } => {
+                report.settled += 1;
+                submission_latency.push(submission);
+                settlement_latency.push(settlement);
+            }
+            Outcome::InError { submission } => {
+                report.in_error += 1;
+                submission_latency.push(submission);
+            }
+            Outcome::TimedOut { submission } => {
+                report.timed_out += 1;
+                submission_latency.push(submission);
+            }
+        }
+    }
+
+    report.elapsed = start.elapsed();
+    report.throughput = report.settled as f64 / report.elapsed.as_secs_f64();
+    report.submission_latency = Percentiles::new(submission_latency);
+    report.settlement_latency = Percentiles::new(settlement_latency);
+
+    report
+}
+
+/// Submit the certificate and wait for it to be settled or in error.
+async fn submit(client: &HttpClient, certificate: Certificate, config: &LoadConfig) -> Outcome {
+    let network_id = certificate.network_id;
+    let height = certificate.height;
+
+    let start = Instant::now();
+    let certificate_id: CertificateId = match client
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
+        .await
+    {
+        Ok(receipt) => receipt.certificate_id,
+        Err(error) => {
+            warn!(%network_id, %height, "Certificate rejected: {error}");
+            return Outcome::Rejected;
+        }
+    };
+    let submission = start.elapsed();
+
+    let deadline = start + config.settlement_timeout;
+    while Instant::now() < deadline {
+        tokio::time::sleep(config.poll_interval).await;
+
+        let header: CertificateHeader = match client
+            .request("interop_getCertificateHeader", rpc_params![certificate_id])
+            .await
+        {
+            Ok(header) => header,
+            Err(error) => {
+                debug!(%certificate_id, "Failed to get the certificate header: {error}");
+                continue;
+            }
+        };
+
+        match header.status {
+            CertificateStatus::Settled => {
+                return Outcome::Settled {
+                    submission,
+                    settlement: start.elapsed(),
+                }
+            }
+            CertificateStatus::InError { error } => {
+                warn!(%certificate_id, %network_id, %height, "Certificate in error: {error}");
+                return Outcome::InError { submission };
+            }
+            CertificateStatus::Pending
+            | CertificateStatus::Proven
+            | CertificateStatus::Candidate => {}
+        }
+    }
+
+    Outcome::TimedOut { submission }
+}
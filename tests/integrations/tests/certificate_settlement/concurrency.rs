@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use agglayer_storage::tests::TempDBDir;
-use agglayer_types::{CertificateHeader, CertificateId, CertificateStatus};
+use agglayer_types::{
+    CertificateHeader, CertificateId, CertificateStatus, CertificateSubmissionReceipt,
+};
 use fail::FailScenario;
 use integrations::{agglayer_setup::setup_network, wait_for_settlement_or_error};
 use jsonrpsee::{core::client::ClientT as _, rpc_params};
@@ -28,20 +30,22 @@ async fn schedule_two_certs(#[case] mut state: Forest) {
     certificate_two.height = Height::new(1);
 
     let certificate_one_id: CertificateId = client
-        .request(
+        .request::<CertificateSubmissionReceipt, _>(
             "interop_sendCertificate",
             rpc_params![certificate_one.clone()],
         )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let certificate_two_id: CertificateId = client
-        .request(
+        .request::<CertificateSubmissionReceipt, _>(
             "interop_sendCertificate",
             rpc_params![certificate_two.clone()],
         )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_two_id).await;
 
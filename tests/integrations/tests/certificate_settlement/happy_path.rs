@@ -1,7 +1,7 @@
 use std::{str::FromStr, time::Duration};
 
 use agglayer_storage::tests::TempDBDir;
-use agglayer_types::{CertificateId, CertificateStatus};
+use agglayer_types::{CertificateId, CertificateStatus, CertificateSubmissionReceipt};
 use alloy::{
     network::Ethereum,
     primitives::{Address, U256},
@@ -31,9 +31,13 @@ async fn successfully_push_certificate(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -75,16 +79,20 @@ async fn send_multiple_certificates(#[case] mut state: Forest) {
         certificate.aggchain_data = AggchainData::ECDSA { signature };
 
         let certificate_id: CertificateId = client
-            .request("interop_sendCertificate", rpc_params![certificate.clone()])
+            .request::<CertificateSubmissionReceipt, _>(
+                "interop_sendCertificate",
+                rpc_params![certificate.clone()],
+            )
             .await
-            .unwrap();
+            .unwrap()
+            .certificate_id;
 
         let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
         assert!(matches!(result.status, CertificateStatus::Settled));
     }
 
-    let provider = RootProvider::<Ethereum>::new_http(reqwest::Url::parse(&l1.rpc).unwrap());
+    let provider = RootProvider::<Ethereum>::new_http(reqwest::Url::parse(&l1.rpc()).unwrap());
     let last_block = provider.get_block_number().await.unwrap();
     assert!(last_block != 0);
     println!("last_block: {last_block}");
@@ -104,3 +112,82 @@ async fn send_multiple_certificates(#[case] mut state: Forest) {
     }
     assert_eq!(events.len(), 5);
 }
+
+#[rstest]
+#[tokio::test]
+#[timeout(Duration::from_secs(200))]
+#[case::type_0_ecdsa(crate::common::type_0_ecdsa_forest())]
+async fn rollup_manager_rebuilds_the_public_values(#[case] mut state: Forest) {
+    use agglayer_contracts::contracts::PolygonRollupManager::VerifyPessimisticStateTransition;
+    use agglayer_types::{aggchain_proof::AggchainData, compute_signature_info};
+    use alloy::providers::Provider as _;
+    use pessimistic_proof::{
+        core::commitment::SignatureCommitmentVersion, proof::zero_if_empty_local_exit_root,
+    };
+
+    let tmp_dir = TempDBDir::new();
+
+    // L1 is a RAII guard
+    let (_handle, l1, client) = setup_network(&tmp_dir.path, None, None).await;
+
+    let mut certificates = Vec::new();
+    for i in 0..2 {
+        let mut certificate = state.apply_events(&[], &[]);
+        certificate.height = i.into();
+        let (_, signature, _) = compute_signature_info(
+            certificate.new_local_exit_root,
+            &certificate.imported_bridge_exits,
+            &state.wallet,
+            certificate.height,
+            SignatureCommitmentVersion::V3,
+        );
+        certificate.aggchain_data = AggchainData::ECDSA { signature };
+
+        let certificate_id: CertificateId = client
+            .request::<CertificateSubmissionReceipt, _>(
+                "interop_sendCertificate",
+                rpc_params![certificate.clone()],
+            )
+            .await
+            .unwrap()
+            .certificate_id;
+
+        let result = wait_for_settlement_or_error!(client, certificate_id).await;
+        assert_eq!(result.status, CertificateStatus::Settled);
+
+        certificates.push(certificate);
+    }
+
+    // The rollup manager verified the proofs against the public values it
+    // rebuilt from the settlement calldata and its own state, as emitted.
+    let provider = RootProvider::<Ethereum>::new_http(reqwest::Url::parse(&l1.rpc()).unwrap());
+    let filter = alloy::rpc::types::Filter::default()
+        .event_signature(VerifyPessimisticStateTransition::SIGNATURE_HASH)
+        .select(FilterBlockOption::Range {
+            from_block: Some(alloy::eips::BlockNumberOrTag::Earliest),
+            to_block: None,
+        })
+        .topic1(U256::from(state.network_id))
+        .address(Address::from_str("0x0b306bf915c4d645ff596e518faf3f9669b97016").unwrap());
+    let events = provider
+        .get_logs(&filter)
+        .await
+        .unwrap()
+        .iter()
+        .map(|log| VerifyPessimisticStateTransition::decode_log(&log.clone().into()).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), certificates.len());
+
+    for (event, certificate) in events.iter().zip(&certificates) {
+        assert_eq!(
+            zero_if_empty_local_exit_root(certificate.prev_local_exit_root),
+            event.prevLocalExitRoot.0.into()
+        );
+        assert_eq!(
+            zero_if_empty_local_exit_root(certificate.new_local_exit_root),
+            event.newLocalExitRoot.0.into()
+        );
+    }
+    // Each settlement starts from the pessimistic root of the previous one.
+    assert_eq!(events[1].prevPessimisticRoot, events[0].newPessimisticRoot);
+}
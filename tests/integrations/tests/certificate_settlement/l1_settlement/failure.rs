@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use agglayer_storage::tests::TempDBDir;
-use agglayer_types::{CertificateId, CertificateStatus};
+use agglayer_types::{
+    CertificateId, CertificateStatus, CertificateStatusError, CertificateSubmissionReceipt,
+};
 use fail::FailScenario;
 use integrations::{agglayer_setup::setup_network, wait_for_settlement_or_error};
 use jsonrpsee::{core::client::ClientT as _, rpc_params};
@@ -32,9 +34,13 @@ async fn transaction_with_receipt_status_0(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -67,9 +73,13 @@ async fn transaction_with_receipt_status_0_retry(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -83,9 +93,13 @@ async fn transaction_with_receipt_status_0_retry(#[case] state: Forest) {
     .expect("Failed to configure failpoint");
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -117,9 +131,13 @@ async fn transaction_without_receipt_status(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -134,8 +152,7 @@ async fn transaction_without_receipt_status(#[case] state: Forest) {
 #[case::type_0_ecdsa(crate::common::type_0_ecdsa_forest())]
 async fn transaction_with_receipt_timeout_many_times(#[case] state: Forest) {
     // Retry the settlement transaction limited number of times,
-    // then the certificate should be in InError status with a SettlementError about
-    // too many transactions
+    // then the certificate should be in InError status with a SettlementTimeout
     let tmp_dir = TempDBDir::new();
     let scenario = FailScenario::setup();
 
@@ -157,23 +174,23 @@ async fn transaction_with_receipt_timeout_many_times(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
-    // Check that we got an InError status with a SettlementError about too many
-    // transactions
+    // Check that we got an InError status with a SettlementTimeout after the
+    // configured number of attempts
     match result.status {
         CertificateStatus::InError { error } => {
-            let error_message = error.to_string();
             assert!(
-                error_message.contains(
-                    "Too many different settlement transactions submitted for the same certificate"
-                ),
-                "Expected error message about too many settlement transactions, but got: \
-                 {error_message}"
+                matches!(*error, CertificateStatusError::SettlementTimeout(_)),
+                "Expected a settlement timeout, but got: {error}"
             );
         }
         status => panic!("Expected InError status, but got: {status:?}"),
@@ -210,9 +227,13 @@ async fn transaction_with_receipt_timeout_2_times(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
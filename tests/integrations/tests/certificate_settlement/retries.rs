@@ -1,7 +1,9 @@
 use std::time::Duration;
 
 use agglayer_storage::tests::TempDBDir;
-use agglayer_types::{CertificateHeader, CertificateId, CertificateStatus};
+use agglayer_types::{
+    CertificateHeader, CertificateId, CertificateStatus, CertificateSubmissionReceipt,
+};
 use fail::FailScenario;
 use integrations::{agglayer_setup::setup_network, wait_for_settlement_or_error};
 use jsonrpsee::{core::client::ClientT as _, rpc_params};
@@ -33,9 +35,13 @@ async fn retry_on_error(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -48,9 +54,13 @@ async fn retry_on_error(#[case] state: Forest) {
     .expect("Failed to configure failpoint");
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -78,16 +88,23 @@ async fn regression_pushing_certificate_while_settling(#[case] state: Forest) {
     let network_id = certificate.network_id;
 
     let first_certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     loop {
         let certificate = certificate
             .clone()
             .with_new_local_exit_root(random::<[u8; 32]>().into());
         assert!(client
-            .request::<CertificateId, _>("interop_sendCertificate", rpc_params![certificate])
+            .request::<CertificateSubmissionReceipt, _>(
+                "interop_sendCertificate",
+                rpc_params![certificate]
+            )
             .await
             .is_err());
 
@@ -124,10 +141,14 @@ async fn regression_pushing_certificate_after_settling(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let first_certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
         .inspect_err(|err| eprintln!("Error sending first certificate: {err:?}"))
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     // Send the first certificate. This should be settled.
     wait_for_settlement_or_error!(client, first_certificate_id).await;
@@ -147,7 +168,10 @@ async fn regression_pushing_certificate_after_settling(#[case] state: Forest) {
 
     // Send the second certificate, identical to the first and check the error.
     let second_submission_err = client
-        .request::<CertificateId, _>("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
         .inspect(|result| eprintln!("Managed to settle same certificate twice! Result: {result:?}"))
         .unwrap_err();
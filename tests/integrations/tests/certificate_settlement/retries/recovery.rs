@@ -3,6 +3,7 @@ use std::time::Duration;
 use agglayer_storage::tests::TempDBDir;
 use agglayer_types::{
     aggchain_proof::AggchainData, compute_signature_info, CertificateId, CertificateStatus,
+    CertificateSubmissionReceipt,
 };
 use fail::FailScenario;
 use integrations::{
@@ -41,9 +42,13 @@ async fn sent_transaction_recover(#[case] failpoints: &[&str], #[case] state: Fo
         .clone()
         .apply_events(&imported_bridge_events, &withdrawals);
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
     _ = agglayer_shutdowned.await;
 
     println!("Node killed, recovering...");
@@ -80,9 +85,13 @@ async fn sent_transaction_recover_after_settlement(#[case] mut state: Forest) {
     let imported_bridge_events = vec![];
     let certificate = state.apply_events(&imported_bridge_events, &withdrawals);
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
     assert!(matches!(result.status, CertificateStatus::Settled));
@@ -116,9 +125,13 @@ async fn sent_transaction_recover_after_settlement(#[case] mut state: Forest) {
     };
 
     let certificate2_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate2.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate2.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     _ = agglayer_shutdowned.await;
 
@@ -192,9 +205,13 @@ async fn recover_after_invalid_transaction_in_header(#[case] state: Forest) {
         .clone()
         .apply_events(&imported_bridge_events, &withdrawals);
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate.clone()])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate.clone()],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     _ = agglayer_shutdowned.await;
 
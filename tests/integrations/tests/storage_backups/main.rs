@@ -2,7 +2,9 @@ use std::time::Duration;
 
 use agglayer_config::storage::backup::BackupConfig;
 use agglayer_storage::{storage::backup::BackupEngine, tests::TempDBDir};
-use agglayer_types::{CertificateHeader, CertificateId, CertificateStatus};
+use agglayer_types::{
+    CertificateHeader, CertificateId, CertificateStatus, CertificateSubmissionReceipt,
+};
 use fail::FailScenario;
 use futures::FutureExt;
 use integrations::{
@@ -42,9 +44,13 @@ async fn recover_with_backup(#[case] state: Forest) {
     let certificate = state.clone().apply_events(&[], &withdrawals);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -116,18 +122,26 @@ async fn purge_after_n_backup(#[case] state: Forest) {
     certificate2.height = Height::new(1);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
     assert_eq!(result.status, CertificateStatus::Settled);
 
     let certificate_id2: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate2])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate2],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id2).await;
 
@@ -207,18 +221,26 @@ async fn report_contains_all_backups(#[case] state: Forest) {
     certificate2.height = Height::new(1);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
     assert_eq!(result.status, CertificateStatus::Settled);
 
     let certificate_id2: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate2])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate2],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id2).await;
 
@@ -301,9 +323,13 @@ async fn restore_at_particular_level(#[case] state: Forest) {
     certificate2.height = Height::new(1);
 
     let certificate_id: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id).await;
 
@@ -312,9 +338,13 @@ async fn restore_at_particular_level(#[case] state: Forest) {
     tokio::time::sleep(std::time::Duration::from_secs(5)).await;
 
     let certificate_id2: CertificateId = client
-        .request("interop_sendCertificate", rpc_params![certificate2])
+        .request::<CertificateSubmissionReceipt, _>(
+            "interop_sendCertificate",
+            rpc_params![certificate2],
+        )
         .await
-        .unwrap();
+        .unwrap()
+        .certificate_id;
 
     let result = wait_for_settlement_or_error!(client, certificate_id2).await;
 